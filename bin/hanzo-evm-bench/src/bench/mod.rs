@@ -17,6 +17,7 @@ mod new_payload_fcu;
 mod new_payload_only;
 mod output;
 mod persistence_waiter;
+mod replay_engine;
 mod replay_payloads;
 mod send_invalid_payload;
 mod send_payload;
@@ -78,6 +79,18 @@ pub enum Subcommands {
     /// http://localhost:8551 --jwt-secret ~/.local/share/reth/mainnet/jwt.hex`
     ReplayPayloads(replay_payloads::Command),
 
+    /// Replay engine API messages stored by a node with `--debug.engine-api-store`.
+    ///
+    /// This command sends the stored `newPayload` and `forkchoiceUpdated` messages to a node
+    /// that runs without a consensus client, optionally comparing the returned statuses with the
+    /// statuses the original node returned.
+    ///
+    /// Example:
+    ///
+    /// `evm-bench replay-engine --engine-api-store ./engine-messages --engine-rpc-url
+    /// http://localhost:8551 --jwt-secret ~/.local/share/reth/mainnet/jwt.hex --compare-statuses`
+    ReplayEngine(replay_engine::Command),
+
     /// Generate and send an invalid `engine_newPayload` request for testing.
     ///
     /// Takes a valid block and modifies fields to make it invalid, allowing you to test
@@ -104,6 +117,7 @@ impl BenchmarkCommand {
             Subcommands::SendPayload(command) => command.execute(ctx).await,
             Subcommands::GenerateBigBlock(command) => command.execute(ctx).await,
             Subcommands::ReplayPayloads(command) => command.execute(ctx).await,
+            Subcommands::ReplayEngine(command) => command.execute(ctx).await,
             Subcommands::SendInvalidPayload(command) => (*command).execute(ctx).await,
         }
     }
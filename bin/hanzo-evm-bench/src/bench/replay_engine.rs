@@ -0,0 +1,281 @@
+//! Command for replaying engine API messages that a node stored with `--debug.engine-api-store`.
+//!
+//! The messages are sent to the engine API of a node in the order they were received, which
+//! reproduces the consensus layer traffic of the original run without a consensus client. The
+//! node should be started from the same database state as the original node.
+//!
+//! Supports:
+//! - **`--to-block`**: Stops before the first `newPayload` above the given block.
+//! - **`--compare-statuses`**: Compares the returned statuses with the statuses the original node
+//!   returned, which are stored next to the messages.
+//! - **`--output`**: Writes the latency of every replayed message to `replay_engine.csv`.
+
+use crate::{
+    authenticated_transport::AuthenticatedTransportConnect, valid_payload::payload_to_new_payload,
+};
+use alloy_primitives::B256;
+use alloy_provider::{ext::EngineApi, network::AnyNetwork, Provider, RootProvider};
+use alloy_rpc_client::ClientBuilder;
+use alloy_rpc_types_engine::{JwtSecret, PayloadStatus};
+use clap::Parser;
+use csv::Writer;
+use eyre::Context;
+use hanzo_evm_cli_runner::CliContext;
+use hanzo_evm_engine_primitives::ForkchoiceStatus;
+use hanzo_evm_engine_util::engine_store::{
+    EngineMessageStore, StoredEngineApiMessage, StoredEngineApiStatus,
+};
+use hanzo_evm_ethereum_engine_primitives::EthEngineTypes;
+use hanzo_evm_node_api::EngineApiMessageVersion;
+use serde::Serialize;
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+use tracing::{info, warn};
+use url::Url;
+
+/// The name of the csv file with the latency of every replayed message.
+const REPLAY_OUTPUT_FILE: &str = "replay_engine.csv";
+
+/// `evm-bench replay-engine` command
+///
+/// Replays the engine API messages stored with `--debug.engine-api-store` against the engine API
+/// of a node.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The engine RPC URL (with JWT authentication).
+    #[arg(long, value_name = "ENGINE_RPC_URL", default_value = "http://localhost:8551")]
+    engine_rpc_url: String,
+
+    /// Path to the JWT secret file for engine API authentication.
+    #[arg(long, value_name = "JWT_SECRET")]
+    jwt_secret: PathBuf,
+
+    /// Directory with the engine API messages stored with `--debug.engine-api-store`.
+    #[arg(long, value_name = "PATH")]
+    engine_api_store: PathBuf,
+
+    /// Stop before the first `newPayload` of a block above this block number.
+    #[arg(long, value_name = "BLOCK")]
+    to_block: Option<u64>,
+
+    /// Compare the returned statuses with the statuses that were stored with the messages.
+    #[arg(long, default_value = "false")]
+    compare_statuses: bool,
+
+    /// Stop at the first status that differs from the stored status.
+    #[arg(long, default_value = "false", requires = "compare_statuses")]
+    fail_on_mismatch: bool,
+
+    /// Optional output directory for the latency of every replayed message (CSV file).
+    #[arg(long, value_name = "OUTPUT")]
+    output: Option<PathBuf>,
+}
+
+/// A replayed engine API message.
+#[derive(Debug)]
+struct ReplayedMessage {
+    /// The engine API method that was called.
+    method: &'static str,
+    /// The block number of the payload, `None` for forkchoice updates.
+    block_number: Option<u64>,
+    /// The payload hash or the head block hash of the forkchoice update.
+    block_hash: B256,
+    /// The returned status.
+    status: StoredEngineApiStatus,
+    /// Whether the returned status matches the stored status, `None` if not compared.
+    matches: Option<bool>,
+    /// The latency of the call.
+    latency: Duration,
+}
+
+/// Serializes the latency as microseconds for the csv writer.
+impl Serialize for ReplayedMessage {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let status = match &self.status {
+            StoredEngineApiStatus::NewPayload { status } => status.status.as_str().to_string(),
+            StoredEngineApiStatus::ForkchoiceUpdated { status } => format!("{status:?}"),
+        };
+        let mut state = serializer.serialize_struct("ReplayedMessage", 6)?;
+        state.serialize_field("method", self.method)?;
+        state.serialize_field("block_number", &self.block_number)?;
+        state.serialize_field("block_hash", &self.block_hash)?;
+        state.serialize_field("status", &status)?;
+        state.serialize_field("matches", &self.matches)?;
+        state.serialize_field("latency", &self.latency.as_micros())?;
+        state.end()
+    }
+}
+
+impl Command {
+    /// Execute the `replay-engine` command.
+    pub async fn execute(self, _ctx: CliContext) -> eyre::Result<()> {
+        let store = EngineMessageStore::new(self.engine_api_store.clone());
+        let messages = store.engine_messages_iter()?.collect::<Vec<_>>();
+        info!(
+            target: "evm-bench",
+            count = messages.len(),
+            path = %self.engine_api_store.display(),
+            "Replaying engine API messages"
+        );
+
+        let jwt =
+            std::fs::read_to_string(&self.jwt_secret).wrap_err("Failed to read JWT secret file")?;
+        let jwt = JwtSecret::from_hex(jwt.trim())?;
+        let auth_url = Url::parse(&self.engine_rpc_url)?;
+
+        info!(target: "evm-bench", "Connecting to Engine RPC at {}", auth_url);
+        let auth_transport = AuthenticatedTransportConnect::new(auth_url, jwt);
+        let auth_client = ClientBuilder::default().connect_with(auth_transport).await?;
+        let auth_provider = RootProvider::<AnyNetwork>::new(auth_client);
+
+        // forkchoice updates without payload attributes use the version of the last payload
+        let mut version = EngineApiMessageVersion::V3;
+        let mut replayed = Vec::with_capacity(messages.len());
+        let mut mismatches = 0;
+        let total_duration = Instant::now();
+
+        for path in messages {
+            let message: StoredEngineApiMessage<EthEngineTypes> = serde_json::from_slice(
+                &std::fs::read(&path).wrap_err_with(|| format!("Failed to read {path:?}"))?,
+            )
+            .wrap_err_with(|| format!("Failed to parse {path:?}"))?;
+
+            let start = Instant::now();
+            let mut message = match message {
+                StoredEngineApiMessage::NewPayload { payload } => {
+                    let block_number = payload.payload.block_number();
+                    if self.to_block.is_some_and(|to_block| block_number > to_block) {
+                        info!(target: "evm-bench", block_number, "Reached the target block");
+                        break
+                    }
+
+                    let block_hash = payload.payload.block_hash();
+                    let (payload_version, params, _) = payload_to_new_payload(
+                        payload.payload,
+                        payload.sidecar,
+                        false,
+                        None,
+                        None,
+                    )?;
+                    version = payload_version;
+                    let status: PayloadStatus =
+                        auth_provider.client().request(version.method_name(), &params).await?;
+                    ReplayedMessage {
+                        method: version.method_name(),
+                        block_number: Some(block_number),
+                        block_hash,
+                        status: StoredEngineApiStatus::NewPayload { status },
+                        matches: None,
+                        latency: start.elapsed(),
+                    }
+                }
+                StoredEngineApiMessage::ForkchoiceUpdated { state, payload_attrs } => {
+                    let fcu_version = match &payload_attrs {
+                        Some(attrs) if attrs.parent_beacon_block_root.is_some() => {
+                            EngineApiMessageVersion::V3
+                        }
+                        Some(attrs) if attrs.withdrawals.is_some() => EngineApiMessageVersion::V2,
+                        Some(_) => EngineApiMessageVersion::V1,
+                        None => version,
+                    };
+                    let (method, response) = match fcu_version {
+                        EngineApiMessageVersion::V1 => (
+                            "engine_forkchoiceUpdatedV1",
+                            auth_provider.fork_choice_updated_v1(state, payload_attrs).await?,
+                        ),
+                        EngineApiMessageVersion::V2 => (
+                            "engine_forkchoiceUpdatedV2",
+                            auth_provider.fork_choice_updated_v2(state, payload_attrs).await?,
+                        ),
+                        // there is no forkchoiceUpdatedV4
+                        _ => (
+                            "engine_forkchoiceUpdatedV3",
+                            auth_provider.fork_choice_updated_v3(state, payload_attrs).await?,
+                        ),
+                    };
+                    let status =
+                        ForkchoiceStatus::from_payload_status(&response.payload_status.status);
+                    ReplayedMessage {
+                        method,
+                        block_number: None,
+                        block_hash: state.head_block_hash,
+                        status: StoredEngineApiStatus::ForkchoiceUpdated { status },
+                        matches: None,
+                        latency: start.elapsed(),
+                    }
+                }
+            };
+
+            if self.compare_statuses {
+                match store.stored_status(&path)? {
+                    Some(stored) => {
+                        let matches = stored == message.status;
+                        if !matches {
+                            mismatches += 1;
+                            warn!(
+                                target: "evm-bench",
+                                method = message.method,
+                                block_hash = %message.block_hash,
+                                ?stored,
+                                replayed = ?message.status,
+                                "Status differs from the stored status"
+                            );
+                        }
+                        message.matches = Some(matches);
+                    }
+                    None => {
+                        warn!(target: "evm-bench", path = %path.display(), "No stored status")
+                    }
+                }
+            }
+
+            info!(
+                target: "evm-bench",
+                method = message.method,
+                block_number = ?message.block_number,
+                block_hash = %message.block_hash,
+                status = ?message.status,
+                latency = ?message.latency,
+                "Replayed message"
+            );
+
+            let mismatch = message.matches == Some(false);
+            replayed.push(message);
+            if mismatch && self.fail_on_mismatch {
+                break
+            }
+        }
+
+        if let Some(output) = &self.output {
+            std::fs::create_dir_all(output)?;
+            let output_path = output.join(REPLAY_OUTPUT_FILE);
+            info!(target: "evm-bench", "Writing replay output to file: {:?}", output_path);
+            let mut writer = Writer::from_path(&output_path)?;
+            for message in &replayed {
+                writer.serialize(message)?;
+            }
+            writer.flush()?;
+        }
+
+        info!(
+            target: "evm-bench",
+            replayed = replayed.len(),
+            mismatches,
+            total_duration = ?total_duration.elapsed(),
+            "Replay complete"
+        );
+
+        if mismatches > 0 && self.fail_on_mismatch {
+            return Err(eyre::eyre!("Replayed status differs from the stored status"))
+        }
+
+        Ok(())
+    }
+}
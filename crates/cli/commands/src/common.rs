@@ -8,7 +8,7 @@ use hanzo_evm_chainspec::EthChainSpec;
 use hanzo_evm_cli::chainspec::ChainSpecParser;
 use hanzo_evm_config::{config::EtlConfig, Config};
 use hanzo_evm_consensus::noop::NoopConsensus;
-use hanzo_evm_db::{init_db, open_db_read_only, DatabaseEnv};
+use hanzo_evm_db::{init_db, lockfile::StorageLock, open_db_read_only, DatabaseEnv};
 use hanzo_evm_db_common::init::init_genesis_with_settings;
 use hanzo_evm_downloaders::{bodies::noop::NoopBodiesDownloader, headers::noop::NoopHeaderDownloader};
 use hanzo_evm_eth_wire::NetPrimitivesFor;
@@ -31,7 +31,10 @@ use hanzo_evm_provider::{
 };
 use hanzo_evm_stages::{sets::DefaultStages, Pipeline, PipelineTarget};
 use hanzo_evm_static_file::StaticFileProducer;
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use tokio::sync::watch;
 use tracing::{debug, info, warn};
 
@@ -78,12 +81,12 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
     /// The base storage mode is determined by `--storage.v2`:
     /// - When `--storage.v2` is set: uses [`StorageSettings::v2()`] defaults
     /// - Otherwise: uses [`StorageSettings::base()`] defaults
+    ///
+    /// State tables are additionally routed to `RocksDB` when `--storage.state-in-rocksdb` is set.
     pub fn storage_settings(&self) -> StorageSettings {
-        if self.storage.v2 {
-            StorageSettings::v2()
-        } else {
-            StorageSettings::base()
-        }
+        let settings =
+            if self.storage.v2 { StorageSettings::v2() } else { StorageSettings::base() };
+        settings.with_state_in_rocksdb(self.storage.state_in_rocksdb)
     }
 
     /// Initializes environment according to [`AccessRights`] and returns an instance of
@@ -135,7 +138,7 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
                     .with_genesis_block_number(genesis_block_number)
                     .build()?,
             ),
-            AccessRights::RO | AccessRights::RoInconsistent => {
+            AccessRights::RO | AccessRights::RoInconsistent | AccessRights::Secondary => {
                 (open_db_read_only(&db_path, self.db.database_args())?, {
                     let provider = StaticFileProviderBuilder::read_only(sf_path)
                         .with_metrics()
@@ -146,8 +149,20 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
                 })
             }
         };
-        let rocksdb_provider = if !access.is_read_write() && !RocksDBProvider::exists(&rocksdb_path)
-        {
+        let rocksdb_provider = if access.is_secondary() {
+            if !RocksDBProvider::exists(&rocksdb_path) {
+                eyre::bail!("RocksDB not found at {rocksdb_path:?}, is the primary node running?")
+            }
+            // Each secondary instance keeps its own info logs, so they must not share a directory.
+            let secondaries_path = data_dir.data_dir().join("rocksdb-secondary");
+            remove_stale_secondary_dirs(&secondaries_path)?;
+            let secondary_path = secondaries_path.join(std::process::id().to_string());
+            RocksDBProvider::builder(data_dir.rocksdb())
+                .with_default_tables()
+                .with_database_log_level(self.db.log_level)
+                .with_secondary(secondary_path)
+                .build()?
+        } else if !access.is_read_write() && !RocksDBProvider::exists(&rocksdb_path) {
             // RocksDB database doesn't exist yet (e.g. datadir restored from a snapshot
             // or created before RocksDB storage). Create an empty one so read-only
             // commands can proceed.
@@ -204,6 +219,7 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
 
         // Check for consistency between database and static files.
         if !access.is_read_only_inconsistent() &&
+            !access.is_secondary() &&
             let Some(unwind_target) =
                 factory.static_file_provider().check_consistency(&factory.provider()?)?
         {
@@ -248,6 +264,26 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
     }
 }
 
+/// Removes the directories of secondary `RocksDB` instances whose process exited without removing
+/// them, e.g. after a crash.
+///
+/// A secondary instance locks its directory while it is open, so a directory that can be locked is
+/// no longer in use.
+fn remove_stale_secondary_dirs(path: &Path) -> eyre::Result<()> {
+    if !path.exists() {
+        return Ok(())
+    }
+
+    for entry in hanzo_evm_fs_util::read_dir(path)? {
+        let dir = entry?.path();
+        if dir.is_dir() && StorageLock::try_acquire(&dir).is_ok() {
+            debug!(target: "evm::cli", ?dir, "Removing stale secondary RocksDB directory");
+            hanzo_evm_fs_util::remove_dir_all(&dir)?;
+        }
+    }
+    Ok(())
+}
+
 /// Environment built from [`EnvironmentArgs`].
 #[derive(Debug)]
 pub struct Environment<N: NodeTypes> {
@@ -268,6 +304,11 @@ pub enum AccessRights {
     RO,
     /// Read-only access with possibly inconsistent data
     RoInconsistent,
+    /// Read-only access to storage that is concurrently written by a running primary node.
+    ///
+    /// `RocksDB` is opened as a secondary instance, which can catch up with the writes of the
+    /// primary. The consistency check is skipped, since the primary is responsible for healing.
+    Secondary,
 }
 
 impl AccessRights {
@@ -281,6 +322,11 @@ impl AccessRights {
     pub const fn is_read_only_inconsistent(&self) -> bool {
         matches!(self, Self::RoInconsistent)
     }
+
+    /// Returns `true` if it follows the storage of a running primary node.
+    pub const fn is_secondary(&self) -> bool {
+        matches!(self, Self::Secondary)
+    }
 }
 
 /// Helper alias to satisfy `FullNodeTypes` bound on [`Node`] trait generic.
@@ -305,11 +351,12 @@ where
     type NetworkPrimitives = <<<N::ComponentsBuilder as NodeComponentsBuilder<FullTypesAdapter<Self>>>::Components as NodeComponents<FullTypesAdapter<Self>>>::Network as NetworkEventListenerProvider>::Primitives;
 }
 
-type EvmFor<N> = <<<N as Node<FullTypesAdapter<N>>>::ComponentsBuilder as NodeComponentsBuilder<
-    FullTypesAdapter<N>,
->>::Components as NodeComponents<FullTypesAdapter<N>>>::Evm;
+pub(crate) type EvmFor<N> =
+    <<<N as Node<FullTypesAdapter<N>>>::ComponentsBuilder as NodeComponentsBuilder<
+        FullTypesAdapter<N>,
+    >>::Components as NodeComponents<FullTypesAdapter<N>>>::Evm;
 
-type ConsensusFor<N> =
+pub(crate) type ConsensusFor<N> =
     <<<N as Node<FullTypesAdapter<N>>>::ComponentsBuilder as NodeComponentsBuilder<
         FullTypesAdapter<N>,
     >>::Components as NodeComponents<FullTypesAdapter<N>>>::Consensus;
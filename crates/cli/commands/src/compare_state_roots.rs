@@ -0,0 +1,500 @@
+//! Re-execute blocks and compare the state root strategies of the engine.
+
+use crate::common::{
+    AccessRights, CliComponentsBuilder, CliNodeComponents, CliNodeTypes, Environment,
+    EnvironmentArgs,
+};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::B256;
+use clap::{Parser, ValueEnum};
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use human_bytes::human_bytes;
+use hanzo_evm_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
+use hanzo_evm_cli::chainspec::ChainSpecParser;
+use hanzo_evm_engine_tree::tree::{
+    precompile_cache::PrecompileCacheMap, trie_updates::diff_trie_updates, ExecutionEnv,
+    PayloadProcessor, StateProviderBuilder, TreeConfig,
+};
+use hanzo_evm_execution::{execute::Executor, ConfigureEvm};
+use hanzo_evm_primitives_traits::{BlockBody, Recovered, TxTy};
+use hanzo_evm_provider::{
+    providers::{BlockchainProvider, OverlayStateProviderFactory, ProviderNodeTypes},
+    BlockNumReader, BlockReader, DatabaseProviderFactory, DatabaseProviderROFactory,
+    HashedPostStateProvider, HeaderProvider, ProviderFactory, StateProviderFactory,
+    TransactionVariant,
+};
+use hanzo_evm_revm::database::StateProviderDatabase;
+use hanzo_evm_trie::{updates::TrieUpdates, StateRoot};
+use hanzo_evm_trie_db::ChangesetCache;
+use hanzo_evm_trie_parallel::root::ParallelStateRoot;
+use std::{
+    collections::BTreeMap,
+    convert::Infallible,
+    fmt,
+    ops::RangeInclusive,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tracing::*;
+
+/// `evm compare-state-roots` command
+///
+/// Re-executes blocks and computes the state root of every block with each of the selected state
+/// root strategies, reporting their timing, heap growth and any divergence between them.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The height to start at.
+    #[arg(long, default_value = "1")]
+    from: u64,
+
+    /// The height to end at. Defaults to the latest block.
+    #[arg(long)]
+    to: Option<u64>,
+
+    /// The state root strategies to compare.
+    #[arg(
+        long = "strategy",
+        value_enum,
+        value_delimiter = ',',
+        default_values_t = [
+            StateRootMode::SparseTrie,
+            StateRootMode::Parallel,
+            StateRootMode::Serial,
+        ]
+    )]
+    strategies: Vec<StateRootMode>,
+
+    /// Stops at the first block for which a strategy computes a wrong state root or diverging
+    /// trie updates.
+    #[arg(long)]
+    fail_fast: bool,
+}
+
+/// A state root strategy of the engine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum StateRootMode {
+    /// The sparse trie task, which is fed with state updates while the block is executed.
+    SparseTrie,
+    /// The parallel state root computation, see `ParallelStateRoot`.
+    Parallel,
+    /// The serial state root computation, see `StateRoot`.
+    Serial,
+}
+
+impl fmt::Display for StateRootMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SparseTrie => f.write_str("sparse-trie"),
+            Self::Parallel => f.write_str("parallel"),
+            Self::Serial => f.write_str("serial"),
+        }
+    }
+}
+
+/// The result of a single state root computation.
+#[derive(Debug)]
+struct StateRootOutcome {
+    state_root: B256,
+    trie_updates: TrieUpdates,
+    elapsed: Duration,
+    /// Growth of the allocated heap while computing the state root, if known.
+    allocated: Option<usize>,
+}
+
+/// Aggregated results of a state root strategy over the block range.
+#[derive(Debug, Default)]
+struct StrategyStats {
+    blocks: u64,
+    total: Duration,
+    max: Duration,
+    max_allocated: Option<usize>,
+    wrong_roots: u64,
+    diverging_blocks: u64,
+}
+
+impl StrategyStats {
+    fn record(&mut self, outcome: &StateRootOutcome) {
+        self.blocks += 1;
+        self.total += outcome.elapsed;
+        self.max = self.max.max(outcome.elapsed);
+        if let Some(allocated) = outcome.allocated {
+            self.max_allocated = Some(self.max_allocated.unwrap_or_default().max(allocated));
+        }
+    }
+}
+
+/// Trie updates of a strategy that diverge from the reference strategy.
+#[derive(Debug)]
+struct Divergence {
+    block: u64,
+    strategy: StateRootMode,
+    reference: StateRootMode,
+    paths: Vec<(Option<B256>, hanzo_evm_trie::Nibbles)>,
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>> Command<C> {
+    /// Execute `compare-state-roots` command
+    pub async fn execute<N>(
+        self,
+        components: impl CliComponentsBuilder<N>,
+        runtime: reth_tasks::Runtime,
+    ) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+    {
+        let Environment { provider_factory, .. } =
+            self.env.init::<N>(AccessRights::RO, runtime.clone())?;
+
+        let components = components(provider_factory.chain_spec());
+        let hanzo_evm_config = components.hanzo_evm_config().clone();
+
+        let best_block = DatabaseProviderFactory::database_provider_ro(&provider_factory)?
+            .best_block_number()?;
+        let to = self.to.unwrap_or(best_block);
+        if to > best_block {
+            eyre::bail!("Requested --to {to} is beyond the best block {best_block}")
+        }
+        let from = self.from.max(1);
+
+        let mut strategies = self.strategies;
+        strategies.sort_unstable();
+        strategies.dedup();
+        // trie updates of all strategies are compared against the serial computation if selected
+        let Some(&reference) =
+            strategies.iter().find(|mode| **mode == StateRootMode::Serial).or(strategies.first())
+        else {
+            eyre::bail!("No state root strategy selected")
+        };
+        let fail_fast = self.fail_fast;
+
+        tokio::task::spawn_blocking(move || {
+            let (stats, divergences) = compare_blocks(
+                provider_factory,
+                hanzo_evm_config,
+                runtime,
+                from..=to,
+                &strategies,
+                reference,
+                fail_fast,
+            )?;
+
+            print_report(&stats, &divergences);
+
+            if stats.values().any(|stats| stats.wrong_roots > 0) || !divergences.is_empty() {
+                eyre::bail!("State root strategies diverged")
+            }
+
+            eyre::Ok(())
+        })
+        .await?
+    }
+}
+
+/// Re-executes the blocks in `range` and computes their state roots with each of the
+/// `strategies`, comparing the trie updates of every strategy against the `reference` one.
+fn compare_blocks<N, E>(
+    provider_factory: ProviderFactory<N>,
+    hanzo_evm_config: E,
+    runtime: reth_tasks::Runtime,
+    range: RangeInclusive<u64>,
+    strategies: &[StateRootMode],
+    reference: StateRootMode,
+    fail_fast: bool,
+) -> eyre::Result<(BTreeMap<StateRootMode, StrategyStats>, Vec<Divergence>)>
+where
+    N: ProviderNodeTypes,
+    E: ConfigureEvm<Primitives = N::Primitives> + 'static,
+{
+    let blockchain_provider = BlockchainProvider::new(provider_factory.clone())?;
+    let changeset_cache = ChangesetCache::new();
+    let tree_config = TreeConfig::default();
+    let mut payload_processor = PayloadProcessor::new(
+        runtime.clone(),
+        hanzo_evm_config.clone(),
+        &tree_config,
+        PrecompileCacheMap::default(),
+    );
+
+    let mut stats = BTreeMap::<StateRootMode, StrategyStats>::new();
+    let mut divergences = Vec::new();
+
+    for number in range {
+        let block = provider_factory
+            .recovered_block(number.into(), TransactionVariant::NoHash)?
+            .ok_or_else(|| eyre::eyre!("Block {number} not found"))?;
+        let parent = provider_factory
+            .sealed_header(number - 1)?
+            .ok_or_else(|| eyre::eyre!("Header {} not found", number - 1))?;
+
+        // all strategies compute the root on top of the trie of the parent block
+        let overlay_factory =
+            OverlayStateProviderFactory::new(provider_factory.clone(), changeset_cache.clone())
+                .with_block_hash(Some(parent.hash()));
+
+        let mut outcomes = BTreeMap::new();
+
+        let db = StateProviderDatabase::new(provider_factory.history_by_block_hash(parent.hash())?);
+        let execution_start = Instant::now();
+        let output = if strategies.contains(&StateRootMode::SparseTrie) {
+            let env = ExecutionEnv {
+                evm_env: hanzo_evm_config.evm_env(block.header())?,
+                hash: block.hash(),
+                parent_hash: parent.hash(),
+                parent_state_root: parent.state_root(),
+                // transactions are executed below and not prewarmed
+                transaction_count: 0,
+                gas_used: block.gas_used(),
+                withdrawals: block.body().withdrawals().map(|w| w.to_vec()),
+            };
+            let mut handle = payload_processor.spawn(
+                env,
+                (
+                    Vec::<Result<Recovered<TxTy<N::Primitives>>, Infallible>>::new(),
+                    std::convert::identity,
+                ),
+                StateProviderBuilder::new(blockchain_provider.clone(), parent.hash(), None),
+                overlay_factory.clone(),
+                &tree_config,
+                None,
+            );
+            let output = hanzo_evm_config
+                .executor(db)
+                .execute_with_state_hook(&block, handle.state_hook())?;
+
+            // like in the engine, only the time spent after execution is accounted
+            let allocated_before = allocated_bytes();
+            let root_start = Instant::now();
+            let outcome = handle.state_root()?;
+            outcomes.insert(
+                StateRootMode::SparseTrie,
+                StateRootOutcome {
+                    state_root: outcome.state_root,
+                    trie_updates: outcome.trie_updates,
+                    elapsed: root_start.elapsed(),
+                    allocated: allocated_growth(allocated_before),
+                },
+            );
+            output
+        } else {
+            hanzo_evm_config.executor(db).execute(&block)?
+        };
+        let execution_elapsed = execution_start.elapsed();
+
+        let hashed_state =
+            provider_factory.history_by_block_hash(parent.hash())?.hashed_post_state(&output.state);
+        let prefix_sets = hashed_state.construct_prefix_sets().freeze();
+        let state_overlay_factory = overlay_factory
+            .clone()
+            .with_extended_hashed_state_overlay(hashed_state.clone_into_sorted());
+
+        if strategies.contains(&StateRootMode::Parallel) {
+            let allocated_before = allocated_bytes();
+            let start = Instant::now();
+            let (state_root, trie_updates) = ParallelStateRoot::new(
+                state_overlay_factory.clone(),
+                prefix_sets.clone(),
+                runtime.clone(),
+            )
+            .incremental_root_with_updates()?;
+            outcomes.insert(
+                StateRootMode::Parallel,
+                StateRootOutcome {
+                    state_root,
+                    trie_updates,
+                    elapsed: start.elapsed(),
+                    allocated: allocated_growth(allocated_before),
+                },
+            );
+        }
+
+        if strategies.contains(&StateRootMode::Serial) {
+            let allocated_before = allocated_bytes();
+            let start = Instant::now();
+            let provider = state_overlay_factory.database_provider_ro()?;
+            let (state_root, trie_updates) = StateRoot::new(&provider, &provider)
+                .with_prefix_sets(prefix_sets)
+                .root_with_updates()?;
+            outcomes.insert(
+                StateRootMode::Serial,
+                StateRootOutcome {
+                    state_root,
+                    trie_updates,
+                    elapsed: start.elapsed(),
+                    allocated: allocated_growth(allocated_before),
+                },
+            );
+        }
+
+        let mut failed = false;
+        let trie_cursor_factory = overlay_factory.database_provider_ro()?;
+        for (mode, outcome) in &outcomes {
+            let mode_stats = stats.entry(*mode).or_default();
+            mode_stats.record(outcome);
+
+            if outcome.state_root != block.state_root() {
+                error!(
+                    block = number,
+                    strategy = %mode,
+                    got = ?outcome.state_root,
+                    expected = ?block.state_root(),
+                    "Wrong state root"
+                );
+                mode_stats.wrong_roots += 1;
+                failed = true;
+            }
+
+            if *mode == reference {
+                continue
+            }
+            let diff = diff_trie_updates(
+                &trie_cursor_factory,
+                outcome.trie_updates.clone(),
+                outcomes[&reference].trie_updates.clone(),
+            )?;
+            if diff.has_differences() {
+                let paths = diff.diverging_paths();
+                error!(
+                    block = number,
+                    strategy = %mode,
+                    %reference,
+                    diverging_paths = paths.len(),
+                    "Trie updates diverge"
+                );
+                mode_stats.diverging_blocks += 1;
+                divergences.push(Divergence { block: number, strategy: *mode, reference, paths });
+                failed = true;
+            }
+        }
+
+        info!(
+            block = number,
+            gas_used = block.gas_used(),
+            ?execution_elapsed,
+            timings = ?outcomes
+                .iter()
+                .map(|(mode, outcome)| (mode.to_string(), outcome.elapsed))
+                .collect::<Vec<_>>(),
+            "Compared state roots"
+        );
+
+        if failed && fail_fast {
+            break
+        }
+    }
+
+    Ok((stats, divergences))
+}
+
+/// Prints the summary table of all strategies and the diverging trie paths.
+fn print_report(stats: &BTreeMap<StateRootMode, StrategyStats>, divergences: &[Divergence]) {
+    let mut table = ComfyTable::new();
+    table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+    table.set_header([
+        "Strategy",
+        "Blocks",
+        "Total",
+        "Mean",
+        "Max",
+        "Max Heap Growth",
+        "Wrong Roots",
+        "Diverging Blocks",
+    ]);
+
+    for (mode, stats) in stats {
+        let mean = stats.total.checked_div(stats.blocks as u32).unwrap_or_default();
+        let mut row = Row::new();
+        row.add_cell(Cell::new(mode))
+            .add_cell(Cell::new(stats.blocks))
+            .add_cell(Cell::new(format!("{:?}", stats.total)))
+            .add_cell(Cell::new(format!("{mean:?}")))
+            .add_cell(Cell::new(format!("{:?}", stats.max)))
+            .add_cell(Cell::new(
+                stats
+                    .max_allocated
+                    .map(|allocated| human_bytes(allocated as f64))
+                    .unwrap_or_else(|| "n/a".to_string()),
+            ))
+            .add_cell(Cell::new(stats.wrong_roots))
+            .add_cell(Cell::new(stats.diverging_blocks));
+        table.add_row(row);
+    }
+
+    println!("{table}");
+
+    for Divergence { block, strategy, reference, paths } in divergences {
+        println!("\nBlock {block}: {strategy} trie updates diverge from {reference}");
+        for (hashed_address, path) in paths {
+            match hashed_address {
+                Some(hashed_address) => println!("  storage {hashed_address} {path:?}"),
+                None => println!("  account {path:?}"),
+            }
+        }
+    }
+}
+
+/// Returns the number of bytes currently allocated by the process.
+///
+/// Only available with the jemalloc allocator.
+#[cfg(all(feature = "jemalloc", unix))]
+fn allocated_bytes() -> Option<usize> {
+    use tikv_jemalloc_ctl::{epoch, stats};
+
+    epoch::advance().ok()?;
+    stats::allocated::read().ok()
+}
+
+#[cfg(not(all(feature = "jemalloc", unix)))]
+const fn allocated_bytes() -> Option<usize> {
+    None
+}
+
+/// Returns by how many bytes the allocated heap grew since `before` was read.
+fn allocated_growth(before: Option<usize>) -> Option<usize> {
+    Some(allocated_bytes()?.saturating_sub(before?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::fixture_chain;
+    use hanzo_evm_chainspec::ChainSpecProvider;
+    use hanzo_evm_eth_execution::EthEvmConfig;
+    use hanzo_evm_provider::StorageSettings;
+
+    #[test]
+    fn strategies_agree_on_fixture_chain() {
+        let provider_factory = fixture_chain(4, StorageSettings::base()).unwrap();
+        let hanzo_evm_config = EthEvmConfig::new(provider_factory.chain_spec());
+        let strategies =
+            [StateRootMode::SparseTrie, StateRootMode::Parallel, StateRootMode::Serial];
+
+        let (stats, divergences) = compare_blocks(
+            provider_factory,
+            hanzo_evm_config,
+            reth_tasks::Runtime::test(),
+            1..=4,
+            &strategies,
+            StateRootMode::Serial,
+            false,
+        )
+        .unwrap();
+
+        assert!(divergences.is_empty(), "{divergences:?}");
+        for mode in strategies {
+            let stats = &stats[&mode];
+            assert_eq!(stats.blocks, 4, "{mode}");
+            assert_eq!(stats.wrong_roots, 0, "{mode}");
+            assert_eq!(stats.diverging_blocks, 0, "{mode}");
+        }
+    }
+}
@@ -111,7 +111,7 @@ impl Command {
 }
 
 /// Creates a new hasher with the standard seed used for checksum computation.
-fn checksum_hasher() -> impl Hasher {
+pub(crate) fn checksum_hasher() -> impl Hasher {
     FixedState::with_seed(u64::from_be_bytes(*b"HANZOEVM")).build_hasher()
 }
 
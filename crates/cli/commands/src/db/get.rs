@@ -1,4 +1,4 @@
-use alloy_primitives::{hex, BlockHash};
+use alloy_primitives::{hex, BlockHash, B256};
 use clap::Parser;
 use hanzo_evm_db::{
     static_file::{
@@ -118,6 +118,26 @@ impl Command {
                     return Ok(());
                 }
 
+                if let StaticFileSegment::StateSnapshots = segment {
+                    let block = table_key::<tables::Headers>(&key)?;
+                    let hashed_address: B256 = serde_json::from_str(
+                        subkey.as_deref().unwrap_or_default(),
+                    )
+                    .map_err(|_| eyre::eyre!("state snapshots require a hashed address subkey"))?;
+
+                    let account = tool
+                        .provider_factory
+                        .static_file_provider()
+                        .state_snapshot_account(block, hashed_address)?;
+
+                    if let Some(account) = account {
+                        println!("{}", serde_json::to_string_pretty(&account)?);
+                    } else {
+                        error!(target: "evm::cli", "No content for the given table key.");
+                    }
+                    return Ok(());
+                }
+
                 let (key, subkey, mask): (u64, _, _) = match segment {
                     StaticFileSegment::Headers => (
                         table_key::<tables::Headers>(&key)?,
@@ -151,6 +171,9 @@ impl Command {
                     StaticFileSegment::StorageChangeSets => {
                         unreachable!("storage changesets handled above");
                     }
+                    StaticFileSegment::StateSnapshots => {
+                        unreachable!("state snapshots handled above");
+                    }
                 };
 
                 // handle account changesets differently if a subkey is provided.
@@ -232,6 +255,9 @@ impl Command {
                                 StaticFileSegment::StorageChangeSets => {
                                     unreachable!("storage changeset static files are special cased before this match")
                                 }
+                                StaticFileSegment::StateSnapshots => {
+                                    unreachable!("state snapshot static files are special cased before this match")
+                                }
                             }
                         }
                     }
@@ -0,0 +1,656 @@
+//! `evm db migrate-storage` command for converting a v1 database to the v2 storage layout.
+//!
+//! Receipts, transaction senders and changesets are appended to static files, while the
+//! history indices and the transaction hash lookup table are copied into `RocksDB`. Every step
+//! commits its progress as it goes, so an interrupted migration continues where it stopped when
+//! the command is run again. The stored storage settings are only switched to v2 once all tables
+//! have been moved and verified.
+
+use super::checksum::checksum_hasher;
+use alloy_primitives::{Address, BlockNumber, Bytes, TxNumber, B256, U256};
+use clap::Parser;
+use hanzo_evm_db_api::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    models::{BlockNumberAddress, StorageBeforeTx, StorageShardedKey},
+    table::{Compress, Encode, Table},
+    tables,
+    transaction::{DbTx, DbTxMut},
+    RawKey, RawTable,
+};
+use hanzo_evm_db_common::DbTool;
+use hanzo_evm_node_api::ReceiptTy;
+use hanzo_evm_primitives_traits::{NodePrimitives, StorageSlotKey};
+use hanzo_evm_provider::{
+    providers::{
+        ProviderNodeTypes, RocksDBProvider, StaticFileProvider, StaticFileProviderRWRefMut,
+    },
+    BlockBodyIndicesProvider, ChangeSetReader, DBProvider, DatabaseProviderFactory,
+    DatabaseProviderRO, MetadataProvider, MetadataWriter, ProviderError, ReceiptProvider,
+    RocksDBProviderFactory, RocksDBStateTable, StageCheckpointReader, StateCursorProvider,
+    StaticFileProviderFactory, StaticFileSegment, StaticFileWriter, StorageChangeSetReader,
+    StorageSettings, StorageSettingsCache, TransactionsProvider,
+};
+use hanzo_evm_stages::StageId;
+use hanzo_evm_storage_api::metadata::keys;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, hash::Hasher, ops::Range};
+use tracing::{info, warn};
+
+/// Stages whose output is moved by the migration and must have caught up with the tip.
+const MIGRATED_STAGES: [StageId; 7] = [
+    StageId::SenderRecovery,
+    StageId::Execution,
+    StageId::AccountHashing,
+    StageId::StorageHashing,
+    StageId::TransactionLookup,
+    StageId::IndexAccountHistory,
+    StageId::IndexStorageHistory,
+];
+
+/// `evm db migrate-storage` subcommand
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// Number of blocks, or `RocksDB` entries, to move before committing progress.
+    #[arg(long, default_value_t = 10_000, value_parser = clap::value_parser!(u64).range(1..))]
+    batch_size: u64,
+
+    /// Skip comparing entry counts and checksums of the migrated tables before switching the
+    /// storage settings.
+    #[arg(long)]
+    skip_verify: bool,
+}
+
+/// Resume point of the `RocksDB` part of the migration, stored under
+/// [`keys::STORAGE_MIGRATION`].
+///
+/// Static file segments don't need an entry here, their highest block already tells where to
+/// continue from.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct MigrationProgress {
+    /// Last MDBX key copied into `RocksDB`, by table name.
+    rocksdb: BTreeMap<String, Bytes>,
+}
+
+impl Command {
+    /// Execute `db migrate-storage` command
+    pub fn execute<N: ProviderNodeTypes>(self, tool: &DbTool<N>) -> eyre::Result<()> {
+        warn!("This command should be run without the node running!");
+
+        let provider = tool.provider_factory.provider()?;
+        if provider.storage_settings()?.is_some_and(|settings| settings.is_v2()) {
+            println!("Database already uses the v2 storage layout.");
+            return Ok(())
+        }
+
+        let tip = synced_tip(&provider)?;
+        let mut progress: MigrationProgress = provider
+            .get_metadata(keys::STORAGE_MIGRATION)?
+            .map(|bytes| serde_json::from_slice(&bytes))
+            .transpose()?
+            .unwrap_or_default();
+        drop(provider);
+
+        info!(tip, "Migrating database to the v2 storage layout");
+
+        self.migrate_segment(
+            tool,
+            StaticFileSegment::TransactionSenders,
+            tip,
+            |provider, writer, block| {
+                writer.increment_block(block)?;
+                let mut cursor = provider.tx_ref().cursor_read::<tables::TransactionSenders>()?;
+                for entry in cursor.walk_range(block_tx_range(provider, block)?)? {
+                    let (tx_num, sender) = entry?;
+                    writer.append_transaction_sender(tx_num, &sender)?;
+                }
+                Ok(())
+            },
+        )?;
+
+        self.migrate_segment(tool, StaticFileSegment::Receipts, tip, |provider, writer, block| {
+            writer.increment_block(block)?;
+            let mut cursor = provider.tx_ref().cursor_read::<tables::Receipts<ReceiptTy<N>>>()?;
+            for entry in cursor.walk_range(block_tx_range(provider, block)?)? {
+                let (tx_num, receipt) = entry?;
+                writer.append_receipt(tx_num, &receipt)?;
+            }
+            Ok(())
+        })?;
+
+        self.migrate_segment(
+            tool,
+            StaticFileSegment::AccountChangeSets,
+            tip,
+            |provider, writer, block| {
+                let mut cursor =
+                    provider.tx_ref().cursor_dup_read::<tables::AccountChangeSets>()?;
+                let changeset = cursor
+                    .walk_dup(Some(block), None)?
+                    .map(|entry| entry.map(|(_, change)| change))
+                    .collect::<Result<Vec<_>, _>>()?;
+                writer.append_account_changeset(changeset, block)?;
+                Ok(())
+            },
+        )?;
+
+        self.migrate_segment(
+            tool,
+            StaticFileSegment::StorageChangeSets,
+            tip,
+            |provider, writer, block| {
+                let changeset = mdbx_storage_changeset(provider, block)?
+                    .into_iter()
+                    .map(|(address, key, value)| StorageBeforeTx { address, key, value })
+                    .collect();
+                writer.append_storage_changeset(changeset, block)?;
+                Ok(())
+            },
+        )?;
+
+        self.migrate_rocksdb_table::<N, tables::TransactionHashNumbers>(
+            tool,
+            &mut progress,
+            |key| key,
+        )?;
+        self.migrate_rocksdb_table::<N, tables::AccountsHistory>(tool, &mut progress, |key| key)?;
+        self.migrate_rocksdb_table::<N, tables::StoragesHistory>(
+            tool,
+            &mut progress,
+            hash_storage_history_key,
+        )?;
+
+        if self.skip_verify {
+            warn!("Skipping verification of the migrated tables");
+        } else {
+            verify_static_files(tool, tip)?;
+            verify_rocksdb_table::<N, tables::TransactionHashNumbers>(tool, |key| key)?;
+            verify_rocksdb_table::<N, tables::AccountsHistory>(tool, |key| key)?;
+            verify_rocksdb_table::<N, tables::StoragesHistory>(tool, hash_storage_history_key)?;
+        }
+
+        tool.provider_factory.rocksdb_provider().flush(&[
+            tables::TransactionHashNumbers::NAME,
+            tables::AccountsHistory::NAME,
+            tables::StoragesHistory::NAME,
+        ])?;
+
+        // Drop the MDBX copies and flip the settings in a single transaction, so the database is
+        // either still v1 with the migrated data unused or fully v2. Even without verification,
+        // a table is only cleared once its data was found at the new location.
+        let static_file_provider = tool.provider_factory.static_file_provider();
+        let rocksdb = tool.provider_factory.rocksdb_provider();
+        let provider_rw = tool.provider_factory.database_provider_rw()?;
+        let tx = provider_rw.tx_ref();
+        ensure_transactions_in_static_files::<tables::TransactionSenders>(
+            tx,
+            &static_file_provider,
+            StaticFileSegment::TransactionSenders,
+            tip,
+        )?;
+        tx.clear::<tables::TransactionSenders>()?;
+        ensure_transactions_in_static_files::<tables::Receipts<ReceiptTy<N>>>(
+            tx,
+            &static_file_provider,
+            StaticFileSegment::Receipts,
+            tip,
+        )?;
+        tx.clear::<tables::Receipts<ReceiptTy<N>>>()?;
+        ensure_segment_at_tip(&static_file_provider, StaticFileSegment::AccountChangeSets, tip)?;
+        tx.clear::<tables::AccountChangeSets>()?;
+        ensure_segment_at_tip(&static_file_provider, StaticFileSegment::StorageChangeSets, tip)?;
+        tx.clear::<tables::StorageChangeSets>()?;
+        ensure_in_rocksdb::<tables::TransactionHashNumbers>(tx, &rocksdb, |key| key)?;
+        tx.clear::<tables::TransactionHashNumbers>()?;
+        ensure_in_rocksdb::<tables::AccountsHistory>(tx, &rocksdb, |key| key)?;
+        tx.clear::<tables::AccountsHistory>()?;
+        ensure_in_rocksdb::<tables::StoragesHistory>(tx, &rocksdb, hash_storage_history_key)?;
+        tx.clear::<tables::StoragesHistory>()?;
+        // v2 reads current state from the hashed tables only, wherever the state is stored
+        ensure_hashed::<_, tables::PlainAccountState, tables::HashedAccounts>(&provider_rw)?;
+        provider_rw.clear_state_table::<tables::PlainAccountState>()?;
+        ensure_hashed::<_, tables::PlainStorageState, tables::HashedStorages>(&provider_rw)?;
+        provider_rw.clear_state_table::<tables::PlainStorageState>()?;
+        tx.delete::<tables::Metadata>(keys::STORAGE_MIGRATION.to_string(), None)?;
+        let settings = StorageSettings::v2()
+            .with_state_in_rocksdb(provider_rw.cached_storage_settings().state_in_rocksdb);
+        provider_rw.write_storage_settings(settings)?;
+        provider_rw.commit()?;
+        tool.provider_factory.set_storage_settings_cache(settings);
+
+        println!("Database migrated to the v2 storage layout.");
+
+        Ok(())
+    }
+
+    /// Fills `segment` block by block up to `tip`, continuing after the highest block already in
+    /// static files and committing every `batch_size` blocks.
+    fn migrate_segment<N, F>(
+        &self,
+        tool: &DbTool<N>,
+        segment: StaticFileSegment,
+        tip: BlockNumber,
+        mut append_block: F,
+    ) -> eyre::Result<()>
+    where
+        N: ProviderNodeTypes,
+        F: FnMut(
+            &DatabaseProviderRO<N::DB, N>,
+            &mut StaticFileProviderRWRefMut<'_, N::Primitives>,
+            BlockNumber,
+        ) -> eyre::Result<()>,
+    {
+        let static_file_provider = tool.provider_factory.static_file_provider();
+        let start = static_file_provider
+            .get_highest_static_file_block(segment)
+            .map_or(0, |block| block + 1);
+        if start > tip {
+            info!(%segment, "Static file segment already migrated");
+            return Ok(())
+        }
+
+        for batch_start in (start..=tip).step_by(self.batch_size as usize) {
+            let batch_end = (batch_start + self.batch_size - 1).min(tip);
+            let provider = tool.provider_factory.provider()?;
+            let mut writer = static_file_provider.latest_writer(segment)?;
+            for block in batch_start..=batch_end {
+                append_block(&provider, &mut writer, block)?;
+            }
+            writer.commit()?;
+            info!(%segment, block = batch_end, tip, "Migrated static file segment");
+        }
+
+        Ok(())
+    }
+
+    /// Copies `T` from MDBX into `RocksDB` in key order, recording the last copied key after
+    /// every batch.
+    fn migrate_rocksdb_table<N: ProviderNodeTypes, T: Table>(
+        &self,
+        tool: &DbTool<N>,
+        progress: &mut MigrationProgress,
+        rekey: impl Fn(T::Key) -> T::Key,
+    ) -> eyre::Result<()> {
+        let rocksdb = tool.provider_factory.rocksdb_provider();
+        let mut migrated = 0u64;
+
+        loop {
+            let resume_key = progress.rocksdb.get(T::NAME).map(|key| key.to_vec());
+            let provider = tool.provider_factory.provider()?;
+            let mut cursor = provider.tx_ref().cursor_read::<RawTable<T>>()?;
+
+            let mut batch = rocksdb.batch();
+            let mut last_key = None;
+            for entry in cursor.walk(resume_key.clone().map(RawKey::from_vec))? {
+                let (key, value) = entry?;
+                // The resume key itself was already copied by the previous batch
+                if resume_key.as_ref() == Some(key.raw_key()) {
+                    continue
+                }
+
+                batch.put::<T>(rekey(key.key()?), &value.value()?)?;
+                last_key = Some(key.into_key());
+                if batch.len() as u64 >= self.batch_size {
+                    break
+                }
+            }
+
+            let Some(last_key) = last_key else { break };
+            migrated += batch.len() as u64;
+            batch.commit()?;
+
+            progress.rocksdb.insert(T::NAME.to_string(), last_key.into());
+            let provider_rw = tool.provider_factory.database_provider_rw()?;
+            provider_rw.write_metadata(keys::STORAGE_MIGRATION, serde_json::to_vec(progress)?)?;
+            provider_rw.commit()?;
+
+            info!(table = T::NAME, migrated, "Migrated RocksDB table");
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the synced tip, making sure every stage whose output is migrated has reached it.
+fn synced_tip(provider: &impl StageCheckpointReader) -> eyre::Result<BlockNumber> {
+    let tip = provider.get_stage_checkpoint(StageId::Finish)?.unwrap_or_default().block_number;
+
+    for stage in MIGRATED_STAGES {
+        let checkpoint = provider.get_stage_checkpoint(stage)?.unwrap_or_default().block_number;
+        eyre::ensure!(
+            checkpoint == tip,
+            "{stage} stage checkpoint ({checkpoint}) != Finish stage checkpoint ({tip}), you must first complete the pipeline sync by running `evm node`",
+        );
+    }
+
+    Ok(tip)
+}
+
+/// Returns the transaction numbers of `block`.
+fn block_tx_range(
+    provider: &impl BlockBodyIndicesProvider,
+    block: BlockNumber,
+) -> eyre::Result<Range<TxNumber>> {
+    Ok(provider
+        .block_body_indices(block)?
+        .ok_or(ProviderError::BlockBodyIndicesNotFound(block))?
+        .tx_num_range())
+}
+
+/// Reads the storage changeset of `block` from MDBX with the slot keys hashed the way v2 stores
+/// them, sorted by address and hashed slot.
+fn mdbx_storage_changeset(
+    provider: &impl DBProvider,
+    block: BlockNumber,
+) -> eyre::Result<Vec<(Address, B256, U256)>> {
+    let mut cursor = provider.tx_ref().cursor_read::<tables::StorageChangeSets>()?;
+    let mut changeset = cursor
+        .walk_range(BlockNumberAddress::range(block..=block))?
+        .map(|entry| {
+            entry.map(|(BlockNumberAddress((_, address)), entry)| {
+                (address, StorageSlotKey::plain(entry.key).to_changeset_key(true), entry.value)
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    changeset.sort_unstable_by_key(|(address, key, _)| (*address, *key));
+    Ok(changeset)
+}
+
+/// Storage history in v2 is keyed by hashed slot.
+fn hash_storage_history_key(mut key: StorageShardedKey) -> StorageShardedKey {
+    key.sharded_key.key = StorageSlotKey::plain(key.sharded_key.key).to_hashed();
+    key
+}
+
+/// Fails unless the static files of `segment` reach `tip`.
+fn ensure_segment_at_tip<N: NodePrimitives>(
+    static_file_provider: &StaticFileProvider<N>,
+    segment: StaticFileSegment,
+    tip: BlockNumber,
+) -> eyre::Result<()> {
+    let highest = static_file_provider.get_highest_static_file_block(segment);
+    eyre::ensure!(
+        highest == Some(tip),
+        "{segment}: static files end at block {highest:?} instead of the tip {tip}"
+    );
+    Ok(())
+}
+
+/// Fails unless the static files of `segment` reach `tip` and hold the last transaction of `T`
+/// in MDBX, so that `T` can be cleared.
+fn ensure_transactions_in_static_files<T: Table<Key = TxNumber>>(
+    tx: &impl DbTx,
+    static_file_provider: &StaticFileProvider<impl NodePrimitives>,
+    segment: StaticFileSegment,
+    tip: BlockNumber,
+) -> eyre::Result<()> {
+    ensure_segment_at_tip(static_file_provider, segment, tip)?;
+
+    if let Some((last, _)) = tx.cursor_read::<T>()?.last()? {
+        let highest = static_file_provider.get_highest_static_file_tx(segment);
+        eyre::ensure!(
+            highest.is_some_and(|highest| highest >= last),
+            "{}: transaction {last} is missing from static files ending at {highest:?}",
+            T::NAME,
+        );
+    }
+    Ok(())
+}
+
+/// Fails unless the first and last entries of `T` in MDBX are present in `RocksDB`, so that `T`
+/// can be cleared.
+fn ensure_in_rocksdb<T: Table>(
+    tx: &impl DbTx,
+    rocksdb: &RocksDBProvider,
+    rekey: impl Fn(T::Key) -> T::Key,
+) -> eyre::Result<()> {
+    let mut cursor = tx.cursor_read::<T>()?;
+    for (key, value) in [cursor.first()?, cursor.last()?].into_iter().flatten() {
+        let migrated = rocksdb.get::<T>(rekey(key.clone()))?;
+        eyre::ensure!(
+            migrated.is_some_and(|migrated| {
+                migrated.compress().as_ref() == value.compress().as_ref()
+            }),
+            "{}: entry {key:?} is missing from RocksDB",
+            T::NAME,
+        );
+    }
+    Ok(())
+}
+
+/// Fails unless the hashed table `H` holds as many entries as the plain table `P`, so that `P`
+/// can be cleared.
+fn ensure_hashed<S: StateCursorProvider, P: RocksDBStateTable, H: RocksDBStateTable>(
+    provider: &S,
+) -> eyre::Result<()> {
+    let (plain, hashed) = (provider.state_entries::<P>()?, provider.state_entries::<H>()?);
+    eyre::ensure!(plain == hashed, "{}: {plain} entries but {hashed} in {}", P::NAME, H::NAME);
+    Ok(())
+}
+
+/// Entry count and checksum of one side of a migrated table.
+struct Tally<H> {
+    hasher: H,
+    entries: u64,
+}
+
+impl<H: Hasher> Tally<H> {
+    const fn new(hasher: H) -> Self {
+        Self { hasher, entries: 0 }
+    }
+
+    fn write(&mut self, key: &[u8], value: &[u8]) {
+        self.hasher.write(key);
+        self.hasher.write(value);
+        self.entries += 1;
+    }
+
+    /// Fails if the migrated copy of `table` differs from its MDBX source.
+    fn ensure_matches(self, table: &str, migrated: Self) -> eyre::Result<()> {
+        let (source_checksum, migrated_checksum) = (self.hasher.finish(), migrated.hasher.finish());
+        eyre::ensure!(
+            self.entries == migrated.entries,
+            "{table}: {} entries in MDBX but {} after migration",
+            self.entries,
+            migrated.entries,
+        );
+        eyre::ensure!(
+            source_checksum == migrated_checksum,
+            "{table}: checksum {migrated_checksum:#x} after migration does not match MDBX checksum {source_checksum:#x}",
+        );
+
+        info!(table, entries = self.entries, "Verified migrated table: checksum {source_checksum:#x}");
+        Ok(())
+    }
+}
+
+/// Compares every migrated static file segment against its MDBX table.
+fn verify_static_files<N: ProviderNodeTypes>(
+    tool: &DbTool<N>,
+    tip: BlockNumber,
+) -> eyre::Result<()> {
+    let provider = tool.provider_factory.provider()?.disable_long_read_transaction_safety();
+    let static_file_provider = tool.provider_factory.static_file_provider();
+
+    for segment in [
+        StaticFileSegment::TransactionSenders,
+        StaticFileSegment::Receipts,
+        StaticFileSegment::AccountChangeSets,
+        StaticFileSegment::StorageChangeSets,
+    ] {
+        ensure_segment_at_tip(&static_file_provider, segment, tip)?;
+    }
+
+    let mut senders = (Tally::new(checksum_hasher()), Tally::new(checksum_hasher()));
+    let mut receipts = (Tally::new(checksum_hasher()), Tally::new(checksum_hasher()));
+    let mut account_changesets = (Tally::new(checksum_hasher()), Tally::new(checksum_hasher()));
+    let mut storage_changesets = (Tally::new(checksum_hasher()), Tally::new(checksum_hasher()));
+
+    let tx = provider.tx_ref();
+    let mut senders_cursor = tx.cursor_read::<tables::TransactionSenders>()?;
+    let mut receipts_cursor = tx.cursor_read::<tables::Receipts<ReceiptTy<N>>>()?;
+    let mut account_changesets_cursor = tx.cursor_dup_read::<tables::AccountChangeSets>()?;
+
+    for block in 0..=tip {
+        let tx_range = block_tx_range(&provider, block)?;
+
+        // Pruned transactions are missing on both sides, so only the stored range is compared.
+        let source = senders_cursor.walk_range(tx_range.clone())?.collect::<Result<Vec<_>, _>>()?;
+        if let (Some(&(first, _)), Some(&(last, _))) = (source.first(), source.last()) {
+            for (tx_num, sender) in source {
+                senders.0.write(&tx_num.to_be_bytes(), sender.as_slice());
+            }
+            for (tx_num, sender) in
+                (first..).zip(static_file_provider.senders_by_tx_range(first..=last)?)
+            {
+                senders.1.write(&tx_num.to_be_bytes(), sender.as_slice());
+            }
+        }
+
+        let source = receipts_cursor.walk_range(tx_range)?.collect::<Result<Vec<_>, _>>()?;
+        if let (Some(&(first, _)), Some(&(last, _))) = (source.first(), source.last()) {
+            for (tx_num, receipt) in source {
+                receipts.0.write(&tx_num.to_be_bytes(), receipt.compress().as_ref());
+            }
+            for (tx_num, receipt) in
+                (first..).zip(static_file_provider.receipts_by_tx_range(first..=last)?)
+            {
+                receipts.1.write(&tx_num.to_be_bytes(), receipt.compress().as_ref());
+            }
+        }
+
+        for entry in account_changesets_cursor.walk_dup(Some(block), None)? {
+            let (_, change) = entry?;
+            account_changesets.0.write(&block.to_be_bytes(), change.compress().as_ref());
+        }
+        for change in static_file_provider.account_block_changeset(block)? {
+            account_changesets.1.write(&block.to_be_bytes(), change.compress().as_ref());
+        }
+
+        for (address, slot, value) in mdbx_storage_changeset(&provider, block)? {
+            storage_changesets.0.write(
+                BlockNumberAddress((block, address)).encode().as_ref(),
+                &[slot.as_slice(), &value.to_be_bytes::<32>()].concat(),
+            );
+        }
+        let mut migrated = static_file_provider
+            .storage_changeset(block)?
+            .into_iter()
+            .map(|(BlockNumberAddress((_, address)), entry)| {
+                (address, entry.key.as_b256(), entry.value)
+            })
+            .collect::<Vec<_>>();
+        migrated.sort_unstable_by_key(|(address, key, _)| (*address, *key));
+        for (address, slot, value) in migrated {
+            storage_changesets.1.write(
+                BlockNumberAddress((block, address)).encode().as_ref(),
+                &[slot.as_slice(), &value.to_be_bytes::<32>()].concat(),
+            );
+        }
+    }
+
+    senders.0.ensure_matches(tables::TransactionSenders::NAME, senders.1)?;
+    receipts.0.ensure_matches(tables::Receipts::<ReceiptTy<N>>::NAME, receipts.1)?;
+    account_changesets.0.ensure_matches(tables::AccountChangeSets::NAME, account_changesets.1)?;
+    storage_changesets.0.ensure_matches(tables::StorageChangeSets::NAME, storage_changesets.1)?;
+
+    Ok(())
+}
+
+/// Compares the `RocksDB` copy of `T` against its MDBX table.
+fn verify_rocksdb_table<N: ProviderNodeTypes, T: Table>(
+    tool: &DbTool<N>,
+    rekey: impl Fn(T::Key) -> T::Key,
+) -> eyre::Result<()> {
+    let provider = tool.provider_factory.provider()?.disable_long_read_transaction_safety();
+    let rocksdb = tool.provider_factory.rocksdb_provider();
+
+    let mut source = Tally::new(checksum_hasher());
+    let mut migrated = Tally::new(checksum_hasher());
+    for entry in provider.tx_ref().cursor_read::<T>()?.walk(None)? {
+        let (key, value) = entry?;
+        let key = rekey(key);
+        let encoded_key = key.clone().encode();
+
+        source.write(encoded_key.as_ref(), value.compress().as_ref());
+        if let Some(value) = rocksdb.get::<T>(key)? {
+            migrated.write(encoded_key.as_ref(), value.compress().as_ref());
+        }
+    }
+
+    // Lookups only cover keys present in MDBX, so also make sure nothing else was written.
+    let mut rocksdb_entries = 0u64;
+    for entry in rocksdb.raw_iter::<T>()? {
+        entry?;
+        rocksdb_entries += 1;
+    }
+    eyre::ensure!(
+        rocksdb_entries == source.entries,
+        "{}: {} entries in MDBX but {rocksdb_entries} in RocksDB",
+        T::NAME,
+        source.entries,
+    );
+
+    source.ensure_matches(T::NAME, migrated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{fixture_chain, hashed_state_root, read_chain};
+    use alloy_consensus::BlockHeader;
+    use hanzo_evm_provider::HeaderProvider;
+
+    #[test]
+    fn migrates_v1_database() {
+        let tip = 6;
+        let provider_factory = fixture_chain(tip, StorageSettings::v1()).unwrap();
+        let before = read_chain(&provider_factory, tip).unwrap();
+
+        let tool = DbTool::new(provider_factory.clone()).unwrap();
+        Command { batch_size: 4, skip_verify: false }.execute(&tool).unwrap();
+
+        let provider = provider_factory.provider().unwrap();
+        assert_eq!(provider.storage_settings().unwrap(), Some(StorageSettings::v2()));
+        assert_eq!(provider.tx_ref().entries::<tables::AccountChangeSets>().unwrap(), 0);
+        assert_eq!(provider.tx_ref().entries::<tables::StoragesHistory>().unwrap(), 0);
+        assert_eq!(provider.tx_ref().entries::<tables::PlainStorageState>().unwrap(), 0);
+
+        assert_eq!(read_chain(&provider_factory, tip).unwrap(), before);
+        assert_eq!(before[tip as usize].slots, [Some(U256::from(tip)); 2]);
+
+        assert_eq!(
+            hashed_state_root(&provider).unwrap(),
+            provider.header_by_number(tip).unwrap().unwrap().state_root()
+        );
+    }
+
+    #[test]
+    fn skips_verification_but_not_presence_checks() {
+        let tip = 2;
+        let provider_factory = fixture_chain(tip, StorageSettings::v1()).unwrap();
+
+        // a history entry that is not in RocksDB must keep the MDBX tables from being cleared
+        let tool = DbTool::new(provider_factory.clone()).unwrap();
+        let provider_rw = provider_factory.database_provider_rw().unwrap();
+        provider_rw
+            .tx_ref()
+            .put::<tables::Metadata>(
+                keys::STORAGE_MIGRATION.to_string(),
+                serde_json::to_vec(&MigrationProgress {
+                    rocksdb: [(tables::AccountsHistory::NAME.to_string(), Bytes::from([0xff; 28]))]
+                        .into(),
+                })
+                .unwrap(),
+            )
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        let err = Command { batch_size: 4, skip_verify: true }.execute(&tool).unwrap_err();
+        assert!(err.to_string().contains("missing from RocksDB"), "{err}");
+
+        let provider = provider_factory.provider().unwrap();
+        assert_eq!(provider.storage_settings().unwrap(), Some(StorageSettings::v1()));
+        assert!(provider.tx_ref().entries::<tables::AccountsHistory>().unwrap() > 0);
+        assert!(provider.tx_ref().entries::<tables::PlainAccountState>().unwrap() > 0);
+    }
+}
@@ -16,14 +16,20 @@ mod copy;
 mod diff;
 mod get;
 mod list;
+#[cfg(all(unix, feature = "rocksdb"))]
+mod migrate_storage;
 mod prune_checkpoints;
+mod query;
 mod repair_trie;
 mod settings;
+mod snapshot;
 mod state;
+mod static_file_dictionary;
 mod static_file_header;
 mod stats;
 /// DB List TUI
 mod tui;
+mod verify;
 
 /// `evm db` command
 #[derive(Debug, Parser)]
@@ -46,10 +52,15 @@ pub enum Subcommands {
     Checksum(checksum::Command),
     /// Copies the MDBX database to a new location (bundled mdbx_copy)
     Copy(copy::Command),
+    /// Creates a snapshot of the database, static files and `RocksDB` that `download` can
+    /// restore from
+    Snapshot(snapshot::Command),
     /// Create a diff between two database tables or two entire databases.
     Diff(diff::Command),
     /// Gets the content of a table for the given key
     Get(get::Command),
+    /// Queries the rows of a table matching a filter expression
+    Query(query::Command),
     /// Deletes all database entries
     Drop {
         /// Bypasses the interactive confirmation and drops the database directly
@@ -60,14 +71,21 @@ pub enum Subcommands {
     Clear(clear::Command),
     /// Verifies trie consistency and outputs any inconsistencies
     RepairTrie(repair_trie::Command),
+    /// Cross-checks blocks, state, history indices and trie tables across all storage backends
+    Verify(verify::Command),
     /// Reads and displays the static file segment header
     StaticFileHeader(static_file_header::Command),
+    /// Trains zstd dictionaries for a static file segment and recompresses its static files
+    StaticFileDictionary(static_file_dictionary::Command),
     /// Lists current and local database versions
     Version,
     /// Returns the full database path
     Path,
     /// Manage storage settings
     Settings(settings::Command),
+    /// Migrates a v1 database to the v2 storage layout in place
+    #[cfg(all(unix, feature = "rocksdb"))]
+    MigrateStorage(migrate_storage::Command),
     /// View or set prune checkpoints
     PruneCheckpoints(prune_checkpoints::Command),
     /// Gets storage size information for an account
@@ -136,6 +154,11 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     command.execute(tool.provider_factory.db_ref())?;
                 });
             }
+            Subcommands::Snapshot(command) => {
+                db_exec!(self.env, tool, N, AccessRights::RW, {
+                    command.execute(&tool)?;
+                });
+            }
             Subcommands::Diff(command) => {
                 db_exec!(self.env, tool, N, AccessRights::RO, {
                     command.execute(&tool)?;
@@ -146,6 +169,11 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     command.execute(&tool)?;
                 });
             }
+            Subcommands::Query(command) => {
+                db_exec!(self.env, tool, N, AccessRights::RO, {
+                    command.execute(&tool)?;
+                });
+            }
             Subcommands::Drop { force } => {
                 if !force {
                     // Ask for confirmation
@@ -180,11 +208,21 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     command.execute(&tool, ctx.task_executor, &data_dir)?;
                 });
             }
+            Subcommands::Verify(command) => {
+                db_exec!(self.env, tool, N, AccessRights::RO, {
+                    command.execute(&tool)?;
+                });
+            }
             Subcommands::StaticFileHeader(command) => {
                 db_exec!(self.env, tool, N, AccessRights::RoInconsistent, {
                     command.execute(&tool)?;
                 });
             }
+            Subcommands::StaticFileDictionary(command) => {
+                db_exec!(self.env, tool, N, AccessRights::RW, {
+                    command.execute(&tool)?;
+                });
+            }
             Subcommands::Version => {
                 let local_db_version = match get_db_version(&db_path) {
                     Ok(version) => Some(version),
@@ -208,6 +246,12 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     command.execute(&tool)?;
                 });
             }
+            #[cfg(all(unix, feature = "rocksdb"))]
+            Subcommands::MigrateStorage(command) => {
+                db_exec!(self.env, tool, N, AccessRights::RW, {
+                    command.execute(&tool)?;
+                });
+            }
             Subcommands::PruneCheckpoints(command) => {
                 db_exec!(self.env, tool, N, command.access_rights(), {
                     command.execute(&tool)?;
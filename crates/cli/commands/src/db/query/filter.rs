@@ -0,0 +1,377 @@
+//! Filter expressions of the `evm db query` command.
+//!
+//! A filter is matched against the JSON form of a row, `{"key": .., "value": ..}`:
+//!
+//! ```text
+//! filter     := and ("or" and)*
+//! and        := not ("and" not)*
+//! not        := "not" not | "(" filter ")" | comparison
+//! comparison := path ("==" | "!=" | "<" | "<=" | ">" | ">=" | "contains") literal
+//! path       := field ("." field)*
+//! literal    := number | 0x-prefixed hex | "string" | true | false | null | word
+//! ```
+//!
+//! Elements of arrays, like the `(block, address)` key of `StorageChangeSets`, are selected by
+//! their index, e.g. `key.1`. Numbers and hex strings of up to 32 bytes compare numerically, other
+//! strings compare case-insensitively.
+
+use alloy_primitives::U256;
+use serde_json::Value;
+use std::{cmp::Ordering, str::FromStr};
+
+/// A parsed filter expression.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Filter {
+    /// Matches if both filters match.
+    And(Box<Self>, Box<Self>),
+    /// Matches if either filter matches.
+    Or(Box<Self>, Box<Self>),
+    /// Matches if the filter doesn't match.
+    Not(Box<Self>),
+    /// Matches if the field at `path` compares to `literal` with `op`.
+    Compare {
+        /// Fields leading to the compared value.
+        path: Vec<String>,
+        /// Comparison operator.
+        op: Operator,
+        /// Value to compare with.
+        literal: Value,
+    },
+}
+
+/// Comparison operator of a [`Filter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Operator {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    /// Substring of a string, or element of an array.
+    Contains,
+}
+
+impl Filter {
+    /// Returns `true` if the JSON row matches the filter.
+    ///
+    /// Comparisons of missing fields never match.
+    pub(crate) fn matches(&self, row: &Value) -> bool {
+        match self {
+            Self::And(left, right) => left.matches(row) && right.matches(row),
+            Self::Or(left, right) => left.matches(row) || right.matches(row),
+            Self::Not(filter) => !filter.matches(row),
+            Self::Compare { path, op, literal } => {
+                resolve(row, path).is_some_and(|value| compare(value, *op, literal))
+            }
+        }
+    }
+}
+
+impl FromStr for Filter {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser { tokens: tokenize(s)?, position: 0 };
+        let filter = parser.or()?;
+        if let Some(token) = parser.peek() {
+            eyre::bail!("Unexpected {token:?} after the end of the filter")
+        }
+        Ok(filter)
+    }
+}
+
+/// Returns the value at `path` in `value`.
+fn resolve<'a>(mut value: &'a Value, path: &[String]) -> Option<&'a Value> {
+    for field in path {
+        value = match value {
+            Value::Object(object) => object.get(field)?,
+            Value::Array(array) => array.get(field.parse::<usize>().ok()?)?,
+            _ => return None,
+        };
+    }
+    Some(value)
+}
+
+fn compare(value: &Value, op: Operator, literal: &Value) -> bool {
+    if op == Operator::Contains {
+        return match (value, literal) {
+            (Value::String(value), Value::String(literal)) => {
+                value.to_lowercase().contains(&literal.to_lowercase())
+            }
+            (Value::Array(values), literal) => {
+                values.iter().any(|value| compare(value, Operator::Eq, literal))
+            }
+            _ => false,
+        }
+    }
+
+    let ordering = match (as_number(value), as_number(literal)) {
+        (Some(value), Some(literal)) => value.cmp(&literal),
+        _ => match (value, literal) {
+            (Value::String(value), Value::String(literal)) => {
+                value.to_lowercase().cmp(&literal.to_lowercase())
+            }
+            // Other values only support equality
+            _ => {
+                return match op {
+                    Operator::Eq => value == literal,
+                    Operator::Ne => value != literal,
+                    _ => false,
+                }
+            }
+        },
+    };
+
+    match op {
+        Operator::Eq => ordering == Ordering::Equal,
+        Operator::Ne => ordering != Ordering::Equal,
+        Operator::Lt => ordering == Ordering::Less,
+        Operator::Le => ordering != Ordering::Greater,
+        Operator::Gt => ordering == Ordering::Greater,
+        Operator::Ge => ordering != Ordering::Less,
+        Operator::Contains => unreachable!("handled above"),
+    }
+}
+
+/// Interprets JSON numbers and 0x-prefixed hex strings of up to 32 bytes as numbers.
+fn as_number(value: &Value) -> Option<U256> {
+    match value {
+        Value::Number(number) => number.as_u64().map(U256::from),
+        Value::String(string) => {
+            string.strip_prefix("0x").and_then(|hex| U256::from_str_radix(hex, 16).ok())
+        }
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    OpenParen,
+    CloseParen,
+    Operator(Operator),
+    /// A quoted string.
+    String(String),
+    /// A path, keyword or unquoted literal.
+    Word(String),
+}
+
+fn tokenize(input: &str) -> eyre::Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some(&(position, char)) = chars.peek() {
+        match char {
+            char if char.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::OpenParen);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::CloseParen);
+            }
+            '"' => {
+                chars.next();
+                let mut string = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) => break,
+                        Some((_, '\\')) => string.extend(chars.next().map(|(_, char)| char)),
+                        Some((_, char)) => string.push(char),
+                        None => eyre::bail!("Unterminated string starting at {position}"),
+                    }
+                }
+                tokens.push(Token::String(string));
+            }
+            '=' | '!' | '<' | '>' => {
+                chars.next();
+                let or_equal = chars.next_if(|(_, char)| *char == '=').is_some();
+                let op = match (char, or_equal) {
+                    ('=', true) => Operator::Eq,
+                    ('!', true) => Operator::Ne,
+                    ('<', false) => Operator::Lt,
+                    ('<', true) => Operator::Le,
+                    ('>', false) => Operator::Gt,
+                    ('>', true) => Operator::Ge,
+                    _ => eyre::bail!("Invalid operator at {position}"),
+                };
+                tokens.push(Token::Operator(op));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some((_, char)) = chars
+                    .next_if(|(_, char)| char.is_alphanumeric() || matches!(char, '_' | '.' | '-'))
+                {
+                    word.push(char);
+                }
+                if word.is_empty() {
+                    eyre::bail!("Unexpected character {char:?} at {position}")
+                }
+                tokens.push(Token::Word(word));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Parses an unquoted literal. Words that are neither numbers nor keywords are strings.
+fn parse_literal(word: &str) -> Value {
+    match word {
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        "null" => Value::Null,
+        _ => word.parse::<u64>().map_or_else(|_| Value::String(word.to_string()), Value::from),
+    }
+}
+
+/// Recursive descent parser over the tokens of a filter.
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    /// Consumes the next token if it's the given keyword.
+    fn keyword(&mut self, keyword: &str) -> bool {
+        let found =
+            matches!(self.peek(), Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword));
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn or(&mut self) -> eyre::Result<Filter> {
+        let mut filter = self.and()?;
+        while self.keyword("or") {
+            filter = Filter::Or(Box::new(filter), Box::new(self.and()?));
+        }
+        Ok(filter)
+    }
+
+    fn and(&mut self) -> eyre::Result<Filter> {
+        let mut filter = self.not()?;
+        while self.keyword("and") {
+            filter = Filter::And(Box::new(filter), Box::new(self.not()?));
+        }
+        Ok(filter)
+    }
+
+    fn not(&mut self) -> eyre::Result<Filter> {
+        if self.keyword("not") {
+            return Ok(Filter::Not(Box::new(self.not()?)))
+        }
+
+        if self.peek() == Some(&Token::OpenParen) {
+            self.position += 1;
+            let filter = self.or()?;
+            eyre::ensure!(self.next() == Some(Token::CloseParen), "Expected `)`");
+            return Ok(filter)
+        }
+
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> eyre::Result<Filter> {
+        let path = match self.next() {
+            Some(Token::Word(path)) => path.split('.').map(str::to_string).collect(),
+            token => eyre::bail!("Expected a field path, found {token:?}"),
+        };
+        let op = match self.next() {
+            Some(Token::Operator(op)) => op,
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case("contains") => Operator::Contains,
+            token => eyre::bail!("Expected a comparison operator, found {token:?}"),
+        };
+        let literal = match self.next() {
+            Some(Token::String(string)) => Value::String(string),
+            Some(Token::Word(word)) => parse_literal(&word),
+            token => eyre::bail!("Expected a value, found {token:?}"),
+        };
+
+        Ok(Filter::Compare { path, op, literal })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn matches(filter: &str, row: &Value) -> bool {
+        filter.parse::<Filter>().unwrap().matches(row)
+    }
+
+    #[test]
+    fn parse_precedence() {
+        let filter: Filter = "not key == 1 or key == 2 and value == 3".parse().unwrap();
+        let compare = |path: &str, literal: u64| {
+            Box::new(Filter::Compare {
+                path: vec![path.to_string()],
+                op: Operator::Eq,
+                literal: json!(literal),
+            })
+        };
+        assert_eq!(
+            filter,
+            Filter::Or(
+                Box::new(Filter::Not(compare("key", 1))),
+                Box::new(Filter::And(compare("key", 2), compare("value", 3)))
+            )
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        for filter in ["", "key", "key ==", "key = 1", "(key == 1", "key == 1 key", "key == \"a"] {
+            assert!(filter.parse::<Filter>().is_err(), "{filter}");
+        }
+    }
+
+    #[test]
+    fn match_storage_changeset() {
+        let row = json!({
+            "key": [15, "0x1F9840a85d5aF5bf1D1762F925BDADdC4201F984"],
+            "value": {
+                "key": "0x0000000000000000000000000000000000000000000000000000000000000003",
+                "value": "0x64"
+            }
+        });
+
+        assert!(matches("key.1 == 0x1f9840a85d5af5bf1d1762f925bdaddc4201f984", &row));
+        assert!(matches("key.0 >= 10 and key.0 < 20", &row));
+        assert!(matches("value.key == 0x3 and value.value > 99", &row));
+        assert!(matches("not (value.value > 100 or key.0 == 16)", &row));
+        assert!(matches("key contains 15", &row));
+        assert!(matches("key.1 contains \"9840A8\"", &row));
+        assert!(!matches("key.2 == 15", &row));
+        assert!(!matches("value.missing != 1", &row));
+    }
+
+    #[test]
+    fn match_non_numeric_values() {
+        let row = json!({
+            "key": "MerkleExecution",
+            "value": { "block_number": 5, "stage_checkpoint": null }
+        });
+
+        assert!(matches("key == merkleexecution", &row));
+        assert!(matches("key > Execution", &row));
+        assert!(matches("value.stage_checkpoint == null", &row));
+        assert!(!matches("value.stage_checkpoint < 1", &row));
+        assert!(matches("value.block_number != true", &row));
+    }
+}
@@ -0,0 +1,583 @@
+use crate::db::get::{maybe_json_value_parser, table_key};
+use clap::{Parser, ValueEnum};
+use filter::Filter;
+use hanzo_evm_db::static_file::{
+    BlockHashMask, ColumnSelectorOne, HeaderMask, ReceiptMask, TotalDifficultyMask,
+    TransactionMask, TransactionSenderMask,
+};
+use hanzo_evm_db_api::{
+    cursor::DbCursorRO,
+    database::Database,
+    models::BlockNumberAddress,
+    table::{Decompress, Table},
+    tables,
+    transaction::DbTx,
+    TableViewer, Tables,
+};
+use hanzo_evm_db_common::DbTool;
+use hanzo_evm_node_builder::NodeTypesWithDB;
+use hanzo_evm_primitives_traits::StorageEntry;
+use hanzo_evm_provider::{
+    providers::ProviderNodeTypes, RocksDBStateTable, StateCursorProvider,
+    StaticFileProviderFactory, StorageSettingsCache,
+};
+use hanzo_evm_static_file_types::StaticFileSegment;
+use itertools::Itertools;
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+    cell::RefCell,
+    io::{self, Write},
+    ops::Range,
+};
+
+mod filter;
+
+/// The arguments for the `evm db query` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The table name
+    table: Tables,
+    /// Filter the rows must match, e.g. `key.1 == 0x1f98.. and value.value > 0`
+    ///
+    /// Compares fields of the JSON form of a row, `{"key": .., "value": ..}`, with `==`, `!=`,
+    /// `<`, `<=`, `>`, `>=` or `contains`, and combines comparisons with `and`, `or`, `not` and
+    /// parentheses. Array elements are selected by index, e.g. `key.0`. Numbers and hex strings
+    /// compare numerically.
+    filter: Option<String>,
+    /// First key to query (inclusive)
+    ///
+    /// Tables stored in static files and the changeset tables take a block or transaction
+    /// number.
+    #[arg(long, value_parser = maybe_json_value_parser)]
+    from: Option<String>,
+    /// Last key to query (exclusive)
+    #[arg(long, value_parser = maybe_json_value_parser)]
+    to: Option<String>,
+    /// Maximum number of matching rows to output
+    #[arg(long, short)]
+    limit: Option<usize>,
+    /// Output format of the matching rows
+    #[arg(long, value_enum, default_value_t = OutputFormat::Json)]
+    format: OutputFormat,
+}
+
+/// Output format of `evm db query`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// One JSON object per row
+    Json,
+    /// Comma-separated values, with the fields of the first matching row as columns
+    Csv,
+}
+
+impl Command {
+    /// Execute `db query` command
+    pub fn execute<N: ProviderNodeTypes>(self, tool: &DbTool<N>) -> eyre::Result<()> {
+        self.query(tool, io::stdout().lock())?.flush()?;
+        Ok(())
+    }
+
+    /// Writes the matching rows to `writer` and returns it.
+    fn query<N: ProviderNodeTypes, W: Write>(self, tool: &DbTool<N>, writer: W) -> eyre::Result<W> {
+        let filter = self.filter.as_deref().map(str::parse::<Filter>).transpose()?;
+        let output = RowWriter {
+            writer,
+            format: self.format,
+            filter,
+            remaining: self.limit.unwrap_or(usize::MAX),
+            columns: None,
+        };
+
+        let viewer = QueryViewer {
+            tool,
+            from: self.from.as_deref(),
+            to: self.to.as_deref(),
+            output: RefCell::new(output),
+        };
+        self.table.view(&viewer)?;
+
+        Ok(viewer.output.into_inner().writer)
+    }
+}
+
+struct QueryViewer<'a, N: NodeTypesWithDB, W> {
+    tool: &'a DbTool<N>,
+    from: Option<&'a str>,
+    to: Option<&'a str>,
+    output: RefCell<RowWriter<W>>,
+}
+
+impl<N: ProviderNodeTypes, W: Write> TableViewer<()> for QueryViewer<'_, N, W> {
+    type Error = eyre::Report;
+
+    fn view<T: Table>(&self) -> Result<(), Self::Error> {
+        if let Some((segment, mask)) = static_file_columns::<T>() {
+            return self.query_static_file_table::<T>(segment, mask)
+        }
+
+        if T::NAME == tables::AccountChangeSets::NAME {
+            return self.query_account_changesets()
+        }
+        if T::NAME == tables::StorageChangeSets::NAME {
+            return self.query_storage_changesets()
+        }
+
+        // State tables may be stored in `RocksDB`, see `StorageSettings::state_in_rocksdb`
+        match T::NAME.parse::<Tables>() {
+            Ok(Tables::PlainAccountState) => {
+                return self.query_state_table::<tables::PlainAccountState>()
+            }
+            Ok(Tables::PlainStorageState) => {
+                return self.query_state_table::<tables::PlainStorageState>()
+            }
+            Ok(Tables::HashedAccounts) => {
+                return self.query_state_table::<tables::HashedAccounts>()
+            }
+            Ok(Tables::HashedStorages) => {
+                return self.query_state_table::<tables::HashedStorages>()
+            }
+            Ok(Tables::AccountsTrie) => return self.query_state_table::<tables::AccountsTrie>(),
+            Ok(Tables::StoragesTrie) => return self.query_state_table::<tables::StoragesTrie>(),
+            _ => {}
+        }
+
+        #[cfg(all(unix, feature = "rocksdb"))]
+        if self.in_rocksdb(T::NAME) {
+            return self.query_rocksdb::<T>()
+        }
+
+        let from = self.from.map(table_key::<T>).transpose()?;
+        let to = self.to.map(table_key::<T>).transpose()?;
+        self.query_database::<T>(from, to)
+    }
+}
+
+impl<N: ProviderNodeTypes, W: Write> QueryViewer<'_, N, W> {
+    /// Writes the row if it matches the filter. Returns `false` once the limit is reached.
+    fn push(&self, key: impl Serialize, value: impl Serialize) -> eyre::Result<bool> {
+        self.output.borrow_mut().push(key, value)
+    }
+
+    /// Returns the block or transaction number range of the query.
+    fn number_range(&self) -> eyre::Result<Range<u64>> {
+        let from = self.from.map(serde_json::from_str).transpose()?.unwrap_or(0);
+        let to = self.to.map(serde_json::from_str).transpose()?.unwrap_or(u64::MAX);
+        Ok(from..to)
+    }
+
+    /// Queries rows in `[from, to)` from the database.
+    fn query_database<T: Table>(
+        &self,
+        from: Option<T::Key>,
+        to: Option<T::Key>,
+    ) -> eyre::Result<()> {
+        self.tool.provider_factory.db_ref().view(|tx| {
+            tx.disable_long_read_transaction_safety();
+
+            let mut cursor = tx.cursor_read::<T>()?;
+            for row in cursor.walk(from)? {
+                let (key, value) = row?;
+                if to.as_ref().is_some_and(|to| &key >= to) || !self.push(&key, &value)? {
+                    break
+                }
+            }
+
+            Ok::<_, eyre::Report>(())
+        })?
+    }
+
+    /// Queries rows in `[from, to)` from a state table, wherever the storage settings place it.
+    fn query_state_table<T: RocksDBStateTable>(&self) -> eyre::Result<()> {
+        let from = self.from.map(table_key::<T>).transpose()?;
+        let to = self.to.map(table_key::<T>).transpose()?;
+
+        let provider =
+            self.tool.provider_factory.provider()?.disable_long_read_transaction_safety();
+        let mut cursor = provider.state_cursor_read::<T>()?;
+        for row in cursor.walk(from)? {
+            let (key, value) = row?;
+            if to.as_ref().is_some_and(|to| &key >= to) || !self.push(&key, &value)? {
+                break
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Queries a table whose rows are moved to static files, reading the rows up to the highest
+    /// static file block or transaction from static files, and the rest from the database.
+    fn query_static_file_table<T: Table>(
+        &self,
+        segment: StaticFileSegment,
+        mask: usize,
+    ) -> eyre::Result<()> {
+        let static_file_provider = self.tool.provider_factory.static_file_provider();
+        let range = self.number_range()?;
+
+        let highest = if segment.is_tx_based() {
+            static_file_provider.get_highest_static_file_tx(segment)
+        } else {
+            static_file_provider.get_highest_static_file_block(segment)
+        };
+        let static_file_end = highest
+            .map_or(range.start, |highest| highest.saturating_add(1).clamp(range.start, range.end));
+
+        let rows = static_file_provider.fetch_range_iter(
+            segment,
+            range.start..static_file_end,
+            |cursor, number| {
+                Ok(cursor
+                    .get(number.into(), mask)?
+                    .map(|columns| T::Value::decompress(columns[0]))
+                    .transpose()?)
+            },
+        )?;
+        for (number, row) in (range.start..).zip(rows) {
+            if let Some(value) = row? &&
+                !self.push(number, &value)?
+            {
+                return Ok(())
+            }
+        }
+
+        if static_file_end < range.end {
+            let from = table_key::<T>(&static_file_end.to_string())?;
+            let to = (range.end != u64::MAX)
+                .then(|| table_key::<T>(&range.end.to_string()))
+                .transpose()?;
+            self.query_database::<T>(Some(from), to)?;
+        }
+
+        Ok(())
+    }
+
+    fn query_account_changesets(&self) -> eyre::Result<()> {
+        let range = self.number_range()?;
+        if !self.tool.provider_factory.cached_storage_settings().storage_v2 {
+            return self.query_database::<tables::AccountChangeSets>(
+                Some(range.start),
+                (range.end != u64::MAX).then_some(range.end),
+            )
+        }
+
+        let static_file_provider = self.tool.provider_factory.static_file_provider();
+        let Some(highest) = static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::AccountChangeSets)
+        else {
+            return Ok(())
+        };
+
+        let range = range.start..range.end.min(highest.saturating_add(1));
+        for row in static_file_provider.walk_account_changeset_range(range) {
+            let (block, account) = row?;
+            if !self.push(block, account)? {
+                break
+            }
+        }
+
+        Ok(())
+    }
+
+    fn query_storage_changesets(&self) -> eyre::Result<()> {
+        let range = self.number_range()?;
+        if !self.tool.provider_factory.cached_storage_settings().storage_v2 {
+            return self.query_database::<tables::StorageChangeSets>(
+                Some(BlockNumberAddress((range.start, Default::default()))),
+                (range.end != u64::MAX)
+                    .then(|| BlockNumberAddress((range.end, Default::default()))),
+            )
+        }
+
+        let static_file_provider = self.tool.provider_factory.static_file_provider();
+        let Some(highest) = static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::StorageChangeSets)
+        else {
+            return Ok(())
+        };
+
+        let range = range.start..range.end.min(highest.saturating_add(1));
+        for row in static_file_provider.walk_storage_changeset_range(range) {
+            let (key, entry) = row?;
+            if !self.push(key, StorageEntry::from(entry))? {
+                break
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if the table is stored in `RocksDB` according to the storage settings.
+    #[cfg(all(unix, feature = "rocksdb"))]
+    fn in_rocksdb(&self, table: &str) -> bool {
+        let settings = self.tool.provider_factory.cached_storage_settings();
+        (table == tables::TransactionHashNumbers::NAME &&
+            settings.transaction_hash_numbers_in_rocksdb()) ||
+            (table == tables::AccountsHistory::NAME && settings.account_history_in_rocksdb()) ||
+            (table == tables::StoragesHistory::NAME && settings.storages_history_in_rocksdb())
+    }
+
+    #[cfg(all(unix, feature = "rocksdb"))]
+    fn query_rocksdb<T: Table>(&self) -> eyre::Result<()> {
+        use hanzo_evm_provider::RocksDBProviderFactory;
+
+        let rocksdb = self.tool.provider_factory.rocksdb_provider();
+        let to = self.to.map(table_key::<T>).transpose()?;
+        let rows = match self.from.map(table_key::<T>).transpose()? {
+            Some(from) => rocksdb.iter_from::<T>(from)?,
+            None => rocksdb.iter::<T>()?,
+        };
+
+        for row in rows {
+            let (key, value) = row?;
+            if to.as_ref().is_some_and(|to| &key >= to) || !self.push(&key, &value)? {
+                break
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the static file segment and column mask holding the values of a table, if its rows
+/// are moved to static files by block or transaction number.
+fn static_file_columns<T: Table>() -> Option<(StaticFileSegment, usize)> {
+    Some(match T::NAME.parse::<Tables>().ok()? {
+        Tables::Headers => (StaticFileSegment::Headers, HeaderMask::<T::Value>::MASK),
+        Tables::HeaderTerminalDifficulties => {
+            (StaticFileSegment::Headers, TotalDifficultyMask::MASK)
+        }
+        Tables::CanonicalHeaders => (StaticFileSegment::Headers, BlockHashMask::MASK),
+        Tables::Transactions => {
+            (StaticFileSegment::Transactions, TransactionMask::<T::Value>::MASK)
+        }
+        Tables::TransactionSenders => {
+            (StaticFileSegment::TransactionSenders, TransactionSenderMask::MASK)
+        }
+        Tables::Receipts => (StaticFileSegment::Receipts, ReceiptMask::<T::Value>::MASK),
+        _ => return None,
+    })
+}
+
+/// Filters rows and writes the matching ones in the chosen format.
+struct RowWriter<W> {
+    writer: W,
+    format: OutputFormat,
+    filter: Option<Filter>,
+    /// Number of rows that may still be written.
+    remaining: usize,
+    /// Columns of the CSV output, set by the first row.
+    columns: Option<Vec<String>>,
+}
+
+impl<W: Write> RowWriter<W> {
+    /// Writes the row if it matches the filter. Returns `false` once the limit is reached.
+    fn push(&mut self, key: impl Serialize, value: impl Serialize) -> eyre::Result<bool> {
+        if self.remaining == 0 {
+            return Ok(false)
+        }
+
+        let row = serde_json::json!({ "key": key, "value": value });
+        if self.filter.as_ref().is_some_and(|filter| !filter.matches(&row)) {
+            return Ok(true)
+        }
+
+        match self.format {
+            OutputFormat::Json => writeln!(self.writer, "{row}")?,
+            OutputFormat::Csv => {
+                let mut fields = Vec::new();
+                flatten(&row, String::new(), &mut fields);
+
+                let columns = match &self.columns {
+                    Some(columns) => columns,
+                    None => {
+                        let columns = fields.iter().map(|(column, _)| column.clone()).collect_vec();
+                        writeln!(
+                            self.writer,
+                            "{}",
+                            columns.iter().map(|c| csv_field(c)).join(",")
+                        )?;
+                        self.columns.insert(columns)
+                    }
+                };
+
+                let line = columns
+                    .iter()
+                    .map(|column| {
+                        fields
+                            .iter()
+                            .find(|(field, _)| field == column)
+                            .map(|(_, value)| csv_field(value))
+                            .unwrap_or_default()
+                    })
+                    .join(",");
+                writeln!(self.writer, "{line}")?;
+            }
+        }
+
+        self.remaining -= 1;
+        Ok(self.remaining > 0)
+    }
+}
+
+/// Flattens a JSON value into `(path, value)` pairs of its scalar fields, with `.` separated
+/// paths.
+fn flatten(value: &Value, path: String, fields: &mut Vec<(String, String)>) {
+    let join = |field: &dyn std::fmt::Display| {
+        if path.is_empty() {
+            field.to_string()
+        } else {
+            format!("{path}.{field}")
+        }
+    };
+
+    match value {
+        Value::Object(object) if !object.is_empty() => {
+            for (field, value) in object {
+                flatten(value, join(field), fields);
+            }
+        }
+        Value::Array(array) if !array.is_empty() => {
+            for (index, value) in array.iter().enumerate() {
+                flatten(value, join(&index), fields);
+            }
+        }
+        Value::String(string) => fields.push((path, string.clone())),
+        Value::Null => fields.push((path, String::new())),
+        value => fields.push((path, value.to_string())),
+    }
+}
+
+/// Quotes a CSV field if needed.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{fixture_chain, STORAGE_CONTRACT};
+    use alloy_primitives::{keccak256, Address, B256};
+    use hanzo_evm_db_api::transaction::DbTxMut;
+    use hanzo_evm_provider::{ChangeSetReader, StorageChangeSetReader, StorageSettings};
+    use serde_json::json;
+
+    /// Queries all rows of `table` from `from` and returns their keys.
+    fn query_keys<N: ProviderNodeTypes>(
+        tool: &DbTool<N>,
+        table: Tables,
+        from: Option<&str>,
+    ) -> Vec<Value> {
+        let command = Command {
+            table,
+            filter: None,
+            from: from.map(String::from),
+            to: None,
+            limit: None,
+            format: OutputFormat::Json,
+        };
+        let output = command.query(tool, Vec::new()).unwrap();
+        String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<Value>(line).unwrap()["key"].clone())
+            .collect()
+    }
+
+    #[test]
+    fn query_rows_across_storages() {
+        let tip = 3;
+        let mut layouts = vec![StorageSettings::v1(), StorageSettings::v2()];
+        #[cfg(all(unix, feature = "rocksdb"))]
+        layouts.extend(layouts.clone().into_iter().map(|layout| layout.with_state_in_rocksdb(true)));
+
+        for settings in layouts {
+            let provider_factory = fixture_chain(tip, settings).unwrap();
+
+            // A row above the highest static file block is read from the database
+            let provider_rw = provider_factory.database_provider_rw().unwrap();
+            provider_rw
+                .tx_ref()
+                .put::<tables::CanonicalHeaders>(tip + 1, B256::repeat_byte(1))
+                .unwrap();
+            provider_rw.commit().unwrap();
+
+            let tool = DbTool::new(provider_factory.clone()).unwrap();
+            assert_eq!(
+                query_keys(&tool, Tables::CanonicalHeaders, None),
+                (0..=tip + 1).map(|block| json!(block)).collect_vec(),
+                "{settings:?}"
+            );
+            assert_eq!(
+                query_keys(&tool, Tables::CanonicalHeaders, Some("2")),
+                [json!(2), json!(3), json!(4)],
+                "{settings:?}"
+            );
+
+            // Changesets are read from the database or static files, depending on the settings
+            let provider = provider_factory.provider().unwrap();
+            let account_changes = (0..=tip)
+                .flat_map(|block| {
+                    let changes = provider.account_block_changeset(block).unwrap().len();
+                    vec![json!(block); changes]
+                })
+                .collect_vec();
+            assert!(!account_changes.is_empty());
+            assert_eq!(
+                query_keys(&tool, Tables::AccountChangeSets, None),
+                account_changes,
+                "{settings:?}"
+            );
+            let storage_changes =
+                (0..=tip).map(|block| provider.storage_changeset(block).unwrap().len()).sum();
+            assert!(storage_changes > 0);
+            assert_eq!(
+                query_keys(&tool, Tables::StorageChangeSets, None).len(),
+                storage_changes,
+                "{settings:?}"
+            );
+
+            let (table, key): (_, fn(Address) -> Value) = if settings.use_hashed_state() {
+                (Tables::HashedAccounts, |address| json!(keccak256(address)))
+            } else {
+                (Tables::PlainAccountState, |address| json!(address))
+            };
+            let accounts = query_keys(&tool, table, None);
+            for address in [STORAGE_CONTRACT, Address::with_last_byte(tip as u8)] {
+                assert!(accounts.contains(&key(address)), "{settings:?}: {address} missing");
+            }
+            assert!(!query_keys(&tool, Tables::AccountsHistory, None).is_empty(), "{settings:?}");
+        }
+    }
+
+    #[test]
+    fn write_csv_rows() {
+        let mut output = RowWriter {
+            writer: Vec::new(),
+            format: OutputFormat::Csv,
+            filter: Some("value.nonce > 0".parse().unwrap()),
+            remaining: 2,
+            columns: None,
+        };
+
+        let rows = [
+            (json!([1, "0x01"]), json!({ "nonce": 1, "note": "a,\"b\"" })),
+            (json!([2, "0x02"]), json!({ "nonce": 0, "note": null })),
+            (json!([3, "0x03"]), json!({ "nonce": 2, "note": null })),
+            (json!([4, "0x04"]), json!({ "nonce": 3, "note": null })),
+        ];
+        let written =
+            rows.into_iter().map(|(key, value)| output.push(key, value).unwrap()).collect_vec();
+
+        // The limit is reached with the third row
+        assert_eq!(written, [true, true, false, false]);
+        assert_eq!(
+            String::from_utf8(output.writer).unwrap(),
+            "key.0,key.1,value.nonce,value.note\n1,0x01,1,\"a,\"\"b\"\"\"\n3,0x03,2,\n"
+        );
+    }
+}
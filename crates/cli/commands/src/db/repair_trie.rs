@@ -4,9 +4,8 @@ use hanzo_evm_chainspec::EthChainSpec;
 use hanzo_evm_cli_util::parse_socket_address;
 use hanzo_evm_db_api::{
     cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO},
-    database::Database,
     tables,
-    transaction::{DbTx, DbTxMut},
+    transaction::DbTx,
 };
 use hanzo_evm_db_common::DbTool;
 use hanzo_evm_node_core::{
@@ -19,7 +18,10 @@ use hanzo_evm_node_metrics::{
     server::{MetricServer, MetricServerConfig},
     version::VersionInfo,
 };
-use hanzo_evm_provider::{providers::ProviderNodeTypes, ChainSpecProvider, StageCheckpointReader};
+use hanzo_evm_provider::{
+    providers::ProviderNodeTypes, ChainSpecProvider, StageCheckpointReader, StateCursorFactory,
+    StateCursorProvider,
+};
 use hanzo_evm_stages::StageId;
 use hanzo_evm_tasks::TaskExecutor;
 use hanzo_evm_trie::{
@@ -27,7 +29,6 @@ use hanzo_evm_trie::{
     Nibbles,
 };
 use hanzo_evm_trie_common::{StorageTrieEntry, StoredNibbles, StoredNibblesSubKey};
-use hanzo_evm_trie_db::{DatabaseHashedCursorFactory, DatabaseTrieCursorFactory};
 use std::{
     net::SocketAddr,
     time::{Duration, Instant},
@@ -111,15 +112,13 @@ fn verify_only<N: ProviderNodeTypes>(tool: &DbTool<N>) -> eyre::Result<()> {
         .unwrap_or_default();
     info!("Database block tip: {}", finish_checkpoint.block_number);
 
-    // Get a database transaction directly from the database
-    let db = tool.provider_factory.db_ref();
-    let mut tx = db.tx()?;
-    tx.disable_long_read_transaction_safety();
+    // Get a read-only database provider
+    let mut provider = tool.provider_factory.provider()?;
+    provider.tx_mut().disable_long_read_transaction_safety();
 
-    // Create the verifier
-    let hashed_cursor_factory = DatabaseHashedCursorFactory::new(&tx);
-    let trie_cursor_factory = DatabaseTrieCursorFactory::new(&tx);
-    let verifier = Verifier::new(&trie_cursor_factory, hashed_cursor_factory)?;
+    // Create the verifier. The state tables are read from wherever the provider stores them.
+    let cursor_factory = StateCursorFactory::new(&provider);
+    let verifier = Verifier::new(&cursor_factory, cursor_factory)?;
 
     let metrics = RepairTrieMetrics::new();
 
@@ -209,20 +208,16 @@ fn verify_and_repair<N: ProviderNodeTypes>(tool: &DbTool<N>) -> eyre::Result<()>
     // Check that a pipeline sync isn't in progress.
     verify_checkpoints(provider_rw.as_ref())?;
 
-    // Create cursors for making modifications with
-    let tx = provider_rw.tx_mut();
-    tx.disable_long_read_transaction_safety();
-    let mut account_trie_cursor = tx.cursor_write::<tables::AccountsTrie>()?;
-    let mut storage_trie_cursor = tx.cursor_dup_write::<tables::StoragesTrie>()?;
+    provider_rw.tx_mut().disable_long_read_transaction_safety();
+    let provider = provider_rw.as_ref();
 
-    // Create the cursor factories. These cannot accept the `&mut` tx above because they require it
-    // to be AsRef.
-    let tx = provider_rw.tx_ref();
-    let hashed_cursor_factory = DatabaseHashedCursorFactory::new(tx);
-    let trie_cursor_factory = DatabaseTrieCursorFactory::new(tx);
+    // Create cursors for making modifications with
+    let mut account_trie_cursor = provider.state_cursor_write::<tables::AccountsTrie>()?;
+    let mut storage_trie_cursor = provider.state_cursor_dup_write::<tables::StoragesTrie>()?;
 
-    // Create the verifier
-    let verifier = Verifier::new(&trie_cursor_factory, hashed_cursor_factory)?;
+    // Create the verifier. The state tables are read from wherever the provider stores them.
+    let cursor_factory = StateCursorFactory::new(provider);
+    let verifier = Verifier::new(&cursor_factory, cursor_factory)?;
 
     let metrics = RepairTrieMetrics::new();
 
@@ -316,22 +311,7 @@ fn verify_and_repair<N: ProviderNodeTypes>(tool: &DbTool<N>) -> eyre::Result<()>
 
 /// Output progress information based on the last seen account path.
 fn output_progress(last_account: Nibbles, start_time: Instant, inconsistent_nodes: u64) {
-    // Calculate percentage based on position in the trie path space
-    // For progress estimation, we'll use the first few nibbles as an approximation
-
-    // Convert the first 16 nibbles (8 bytes) to a u64 for progress calculation
-    let mut current_value: u64 = 0;
-    let nibbles_to_use = last_account.len().min(16);
-
-    for i in 0..nibbles_to_use {
-        current_value = (current_value << 4) | (last_account.get(i).unwrap_or(0) as u64);
-    }
-    // Shift left to fill remaining bits if we have fewer than 16 nibbles
-    if nibbles_to_use < 16 {
-        current_value <<= (16 - nibbles_to_use) * 4;
-    }
-
-    let progress_percent = current_value as f64 / u64::MAX as f64 * 100.0;
+    let progress_percent = trie_path_progress(&last_account) * 100.0;
     let progress_percent_str = format!("{progress_percent:.2}");
 
     // Calculate ETA based on current speed
@@ -351,6 +331,25 @@ fn output_progress(last_account: Nibbles, start_time: Instant, inconsistent_node
     );
 }
 
+/// Returns the position of a trie path in the path space, from `0.0` to `1.0`.
+pub(crate) fn trie_path_progress(path: &Nibbles) -> f64 {
+    // For progress estimation, we'll use the first few nibbles as an approximation
+
+    // Convert the first 16 nibbles (8 bytes) to a u64 for progress calculation
+    let mut current_value: u64 = 0;
+    let nibbles_to_use = path.len().min(16);
+
+    for i in 0..nibbles_to_use {
+        current_value = (current_value << 4) | (path.get(i).unwrap_or(0) as u64);
+    }
+    // Shift left to fill remaining bits if we have fewer than 16 nibbles
+    if nibbles_to_use < 16 {
+        current_value <<= (16 - nibbles_to_use) * 4;
+    }
+
+    current_value as f64 / u64::MAX as f64
+}
+
 /// Metrics for tracking trie repair inconsistencies
 #[derive(Debug)]
 struct RepairTrieMetrics {
@@ -87,10 +87,11 @@ impl Command {
             println!("No storage settings found, creating new settings.");
         }
 
-        let mut settings @ StorageSettings { storage_v2: _ } =
+        let mut settings @ StorageSettings { storage_v2: _, state_in_rocksdb: _ } =
             settings.unwrap_or_else(StorageSettings::v1);
 
-        // Update the setting based on the key
+        // Update the setting based on the key. `state_in_rocksdb` is not settable, since it can
+        // only be chosen at genesis.
         match cmd {
             SetCommand::V2 { value } => {
                 if settings.storage_v2 == value {
@@ -0,0 +1,37 @@
+use clap::Parser;
+use hanzo_evm_db_common::DbTool;
+use hanzo_evm_provider::providers::ProviderNodeTypes;
+use hanzo_evm_storage_api::StorageSnapshotProvider;
+use human_bytes::human_bytes;
+use std::path::PathBuf;
+use tracing::info;
+
+/// The arguments for the `evm db snapshot` command
+///
+/// Opens the database read-write, so the node has to be stopped. A running node can be
+/// snapshotted through `admin_createSnapshot` instead.
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Directory to write the snapshot to. Must not exist yet.
+    dest: PathBuf,
+}
+
+impl Command {
+    /// Execute `db snapshot` command
+    pub fn execute<N: ProviderNodeTypes>(self, tool: &DbTool<N>) -> eyre::Result<()> {
+        let manifest = tool.provider_factory.create_storage_snapshot(&self.dest)?;
+        let size = manifest.files.iter().map(|file| file.size).sum::<u64>();
+
+        info!(
+            target: "evm::cli",
+            block_number = manifest.block_number,
+            block_hash = %manifest.block_hash,
+            files = manifest.files.len(),
+            size = %human_bytes(size as f64),
+            path = %self.dest.display(),
+            "Created storage snapshot"
+        );
+
+        Ok(())
+    }
+}
@@ -9,7 +9,7 @@ use hanzo_evm_db_api::{
 };
 use hanzo_evm_db_common::DbTool;
 use hanzo_evm_node_builder::NodeTypesWithDB;
-use hanzo_evm_provider::providers::ProviderNodeTypes;
+use hanzo_evm_provider::{providers::ProviderNodeTypes, StateCursorProvider};
 use hanzo_evm_storage_api::{BlockNumReader, StateProvider, StorageSettingsCache};
 use hanzo_evm_tasks::spawn_scoped_os_thread;
 use std::{
@@ -65,66 +65,61 @@ impl Command {
     ) -> eyre::Result<()> {
         let use_hashed_state = tool.provider_factory.cached_storage_settings().use_hashed_state();
 
-        let entries = tool.provider_factory.db_ref().view(|tx| {
-            let (account, walker_entries) = if use_hashed_state {
-                let hashed_address = keccak256(address);
-                let account = tx.get::<tables::HashedAccounts>(hashed_address)?;
-                let mut cursor = tx.cursor_dup_read::<tables::HashedStorages>()?;
-                let walker = cursor.walk_dup(Some(hashed_address), None)?;
-                let mut entries = Vec::new();
-                let mut last_log = Instant::now();
-                for (idx, entry) in walker.enumerate() {
-                    let (_, storage_entry) = entry?;
-                    if storage_entry.value != U256::ZERO {
-                        entries.push((storage_entry.key, storage_entry.value));
-                    }
-                    if entries.len() >= limit {
-                        break;
-                    }
-                    if last_log.elapsed() >= LOG_INTERVAL {
-                        info!(
-                            target: "reth::cli",
-                            address = %address,
-                            slots_scanned = idx,
-                            "Scanning storage slots"
-                        );
-                        last_log = Instant::now();
-                    }
+        let provider = tool.provider_factory.provider()?;
+        let (account, storage_entries) = if use_hashed_state {
+            let hashed_address = keccak256(address);
+            let account = provider.get_state::<tables::HashedAccounts>(hashed_address)?;
+            let mut cursor = provider.state_cursor_dup_read::<tables::HashedStorages>()?;
+            let walker = cursor.walk_dup(Some(hashed_address), None)?;
+            let mut entries = Vec::new();
+            let mut last_log = Instant::now();
+            for (idx, entry) in walker.enumerate() {
+                let (_, storage_entry) = entry?;
+                if storage_entry.value != U256::ZERO {
+                    entries.push((storage_entry.key, storage_entry.value));
                 }
-                (account, entries)
-            } else {
-                // Get account info
-                let account = tx.get::<tables::PlainAccountState>(address)?;
-                // Get storage entries
-                let mut cursor = tx.cursor_dup_read::<tables::PlainStorageState>()?;
-                let walker = cursor.walk_dup(Some(address), None)?;
-                let mut entries = Vec::new();
-                let mut last_log = Instant::now();
-                for (idx, entry) in walker.enumerate() {
-                    let (_, storage_entry) = entry?;
-                    if storage_entry.value != U256::ZERO {
-                        entries.push((storage_entry.key, storage_entry.value));
-                    }
-                    if entries.len() >= limit {
-                        break;
-                    }
-                    if last_log.elapsed() >= LOG_INTERVAL {
-                        info!(
-                            target: "reth::cli",
-                            address = %address,
-                            slots_scanned = idx,
-                            "Scanning storage slots"
-                        );
-                        last_log = Instant::now();
-                    }
+                if entries.len() >= limit {
+                    break;
                 }
-                (account, entries)
-            };
-
-            Ok::<_, eyre::Report>((account, walker_entries))
-        })??;
-
-        let (account, storage_entries) = entries;
+                if last_log.elapsed() >= LOG_INTERVAL {
+                    info!(
+                        target: "reth::cli",
+                        address = %address,
+                        slots_scanned = idx,
+                        "Scanning storage slots"
+                    );
+                    last_log = Instant::now();
+                }
+            }
+            (account, entries)
+        } else {
+            // Get account info
+            let account = provider.get_state::<tables::PlainAccountState>(address)?;
+            // Get storage entries
+            let mut cursor = provider.state_cursor_dup_read::<tables::PlainStorageState>()?;
+            let walker = cursor.walk_dup(Some(address), None)?;
+            let mut entries = Vec::new();
+            let mut last_log = Instant::now();
+            for (idx, entry) in walker.enumerate() {
+                let (_, storage_entry) = entry?;
+                if storage_entry.value != U256::ZERO {
+                    entries.push((storage_entry.key, storage_entry.value));
+                }
+                if entries.len() >= limit {
+                    break;
+                }
+                if last_log.elapsed() >= LOG_INTERVAL {
+                    info!(
+                        target: "reth::cli",
+                        address = %address,
+                        slots_scanned = idx,
+                        "Scanning storage slots"
+                    );
+                    last_log = Instant::now();
+                }
+            }
+            (account, entries)
+        };
 
         self.print_results(address, None, account, &storage_entries);
 
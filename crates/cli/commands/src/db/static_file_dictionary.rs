@@ -0,0 +1,94 @@
+use clap::{Parser, Subcommand};
+use hanzo_evm_db_common::DbTool;
+use hanzo_evm_provider::{
+    providers::{ProviderNodeTypes, SegmentDictionaries},
+    StaticFileProviderFactory,
+};
+use hanzo_evm_static_file_types::StaticFileSegment;
+use human_bytes::human_bytes;
+use tracing::info;
+
+/// The arguments for the `evm db static-file-dictionary` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+#[derive(Subcommand, Debug)]
+enum Subcommands {
+    /// Trains a new version of the zstd dictionaries of a segment from its static files
+    Train {
+        /// Static file segment
+        #[arg(value_enum)]
+        segment: StaticFileSegment,
+        /// First block of the static files to sample
+        #[arg(long, default_value_t = 0)]
+        from: u64,
+        /// Last block of the static files to sample
+        #[arg(long, default_value_t = u64::MAX)]
+        to: u64,
+        /// Maximum size of a dictionary in bytes
+        #[arg(long, default_value_t = 112_640)]
+        max_dict_size: usize,
+        /// Maximum size of the samples per column in bytes
+        #[arg(long, default_value_t = 100 * 1024 * 1024)]
+        max_samples_size: usize,
+    },
+    /// Recompresses the finished static files of a segment with its trained dictionaries
+    ///
+    /// Static files already compressed with the chosen version are skipped, so an interrupted
+    /// run can be resumed. Nodes recompress with the latest version in the background.
+    Recompress {
+        /// Static file segment
+        #[arg(value_enum)]
+        segment: StaticFileSegment,
+        /// Version of the dictionaries to use, defaults to the latest one
+        #[arg(long)]
+        dictionary_id: Option<u32>,
+    },
+}
+
+impl Command {
+    /// Execute `db static-file-dictionary` command
+    pub fn execute<N: ProviderNodeTypes>(self, tool: &DbTool<N>) -> eyre::Result<()> {
+        let static_file_provider = tool.provider_factory.static_file_provider();
+        let directory = static_file_provider.directory();
+
+        match self.command {
+            Subcommands::Train { segment, from, to, max_dict_size, max_samples_size } => {
+                let columns = static_file_provider.train_zstd_dictionaries(
+                    segment,
+                    from..=to,
+                    max_dict_size,
+                    max_samples_size,
+                )?;
+                let id = SegmentDictionaries::latest_id(directory, segment)?.map_or(1, |id| id + 1);
+
+                let size = columns.iter().map(Vec::len).sum::<usize>();
+                SegmentDictionaries { segment, id, columns }.save(directory)?;
+
+                info!(
+                    target: "evm::cli",
+                    ?segment,
+                    id,
+                    size = %human_bytes(size as f64),
+                    "Trained dictionaries"
+                );
+            }
+            Subcommands::Recompress { segment, dictionary_id } => {
+                let id = match dictionary_id {
+                    Some(id) => id,
+                    None => SegmentDictionaries::latest_id(directory, segment)?
+                        .ok_or_else(|| eyre::eyre!("No trained dictionaries for {segment}"))?,
+                };
+                let dictionaries = SegmentDictionaries::load(directory, segment, id)?;
+                let recompressed = static_file_provider.recompress_segment(&dictionaries)?;
+
+                info!(target: "evm::cli", ?segment, id, recompressed, "Recompressed static files");
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,883 @@
+//! Checks of the `evm db verify` command.
+
+use super::{CheckRun, VerifyContext};
+use crate::db::repair_trie::trie_path_progress;
+use alloy_consensus::{BlockHeader, Sealable, TxReceipt};
+use alloy_primitives::{keccak256, BlockNumber, Bloom};
+use hanzo_evm_chainspec::EthereumHardforks;
+use hanzo_evm_db_api::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    models::StoredBlockBodyIndices,
+    table::Table,
+    tables,
+    transaction::DbTx,
+    BlockNumberList,
+};
+use hanzo_evm_primitives_traits::{
+    proofs::{calculate_receipt_root, calculate_transaction_root},
+    SignerRecoverable,
+};
+use hanzo_evm_provider::{
+    providers::{HistoryInfo, ProviderNodeTypes},
+    ChainSpecProvider, DBProvider, DatabaseProviderRO, EitherReader, RocksDBProviderFactory,
+    StateCursorFactory, StateCursorProvider, StaticFileProviderFactory, StorageSettingsCache,
+};
+use hanzo_evm_prune_types::PruneSegment;
+use hanzo_evm_stages::StageId;
+use hanzo_evm_static_file_types::StaticFileSegment;
+use hanzo_evm_storage_api::{
+    BlockBodyIndicesProvider, BlockHashReader, ChangeSetReader, HeaderProvider,
+    PruneCheckpointReader, ReceiptProvider, StageCheckpointReader, StorageChangeSetReader,
+    TransactionsProvider,
+};
+use hanzo_evm_trie::{
+    verify::{Output, Verifier},
+    Nibbles,
+};
+use hanzo_evm_trie_common::StoredNibbles;
+use rayon::prelude::*;
+use std::ops::RangeInclusive;
+
+/// Number of blocks read at once.
+const BATCH_SIZE: u64 = 10_000;
+
+/// Units of work of checks whose progress is a fraction.
+const PROGRESS_SCALE: u64 = 10_000;
+
+/// Checks that headers hash to the canonical hashes and link to their parents.
+pub(super) fn headers<N: ProviderNodeTypes>(
+    context: &VerifyContext<'_, N>,
+    run: &CheckRun,
+) -> eyre::Result<()> {
+    let provider = context.provider()?;
+    let range = context.range.clone();
+    run.set_total(block_count(&range));
+
+    let mut parent_hash = match range.start().checked_sub(1) {
+        Some(parent) => provider.block_hash(parent)?,
+        None => None,
+    };
+    for batch in batches(range) {
+        let headers = provider.headers_range(batch.clone())?;
+        let hashes = provider.canonical_hashes_range(*batch.start(), batch.end() + 1)?;
+
+        for (index, number) in batch.clone().enumerate() {
+            let (Some(header), Some(&hash)) = (headers.get(index), hashes.get(index)) else {
+                run.issue(Some(number), "Header or canonical hash is missing");
+                parent_hash = None;
+                continue
+            };
+
+            if header.number() != number {
+                run.issue(Some(number), format!("Header has number {}", header.number()));
+            }
+            let header_hash = header.hash_slow();
+            if header_hash != hash {
+                run.issue(
+                    Some(number),
+                    format!("Header hashes to {header_hash}, the canonical hash is {hash}"),
+                );
+            }
+            if let Some(parent_hash) = parent_hash &&
+                header.parent_hash() != parent_hash
+            {
+                run.issue(
+                    Some(number),
+                    format!(
+                        "Parent hash {} differs from the canonical hash of the parent \
+                         {parent_hash}",
+                        header.parent_hash()
+                    ),
+                );
+            }
+            parent_hash = Some(hash);
+        }
+
+        run.advance(block_count(&batch));
+    }
+
+    Ok(())
+}
+
+/// Checks that body indices are contiguous, and that the stored transactions match their counts
+/// and the transactions roots of the headers.
+pub(super) fn bodies<N: ProviderNodeTypes>(
+    context: &VerifyContext<'_, N>,
+    run: &CheckRun,
+) -> eyre::Result<()> {
+    let provider = context.provider()?;
+    let Some(range) = unpruned_range(&provider, run, &context.range, &[PruneSegment::Bodies])?
+    else {
+        return Ok(())
+    };
+    run.set_total(block_count(&range));
+
+    let mut next_tx_num = match range.start().checked_sub(1) {
+        Some(parent) => provider.block_body_indices(parent)?.map(|indices| indices.next_tx_num()),
+        None => Some(0),
+    };
+    for batch in batches(range.clone()) {
+        let headers = provider.headers_range(batch.clone())?;
+        let indices = provider.block_body_indices_range(batch.clone())?;
+        let (Some(first), Some(last)) = (indices.first(), indices.last()) else {
+            run.issue(Some(*batch.start()), "Body indices are missing");
+            next_tx_num = None;
+            run.advance(block_count(&batch));
+            continue
+        };
+
+        let first_tx_num = first.first_tx_num();
+        let transactions = provider.transactions_by_tx_range(first_tx_num..last.next_tx_num())?;
+
+        for (index, number) in batch.clone().enumerate() {
+            let Some(block_indices) = indices.get(index) else {
+                run.issue(Some(number), "Body indices are missing");
+                next_tx_num = None;
+                continue
+            };
+
+            if let Some(expected) = next_tx_num &&
+                block_indices.first_tx_num() != expected
+            {
+                run.issue(
+                    Some(number),
+                    format!(
+                        "First transaction is {}, the previous block ends before {expected}",
+                        block_indices.first_tx_num()
+                    ),
+                );
+            }
+            next_tx_num = Some(block_indices.next_tx_num());
+
+            let Some(block_transactions) = block_slice(&transactions, first_tx_num, block_indices)
+            else {
+                run.issue(
+                    Some(number),
+                    format!(
+                        "Transactions {:?} are missing, {} of the blocks' transactions are stored",
+                        block_indices.tx_num_range(),
+                        transactions.len()
+                    ),
+                );
+                continue
+            };
+            if let Some(header) = headers.get(index) {
+                let transactions_root = calculate_transaction_root(block_transactions);
+                if transactions_root != header.transactions_root() {
+                    run.issue(
+                        Some(number),
+                        format!(
+                            "Transactions root is {transactions_root}, the header has {}",
+                            header.transactions_root()
+                        ),
+                    );
+                }
+            }
+        }
+
+        run.advance(block_count(&batch));
+    }
+
+    // Transactions past the last body are left over from an interrupted unwind
+    let bodies_tip =
+        provider.get_stage_checkpoint(StageId::Bodies)?.unwrap_or_default().block_number;
+    if *range.end() == context.tip &&
+        let Some(indices) = provider.block_body_indices(bodies_tip)?
+    {
+        let highest_tx = provider
+            .static_file_provider()
+            .get_highest_static_file_tx(StaticFileSegment::Transactions);
+        if highest_tx.map_or(0, |tx| tx + 1) != indices.next_tx_num() {
+            run.issue(
+                Some(bodies_tip),
+                format!(
+                    "Static files hold transactions up to {highest_tx:?}, bodies end before {}",
+                    indices.next_tx_num()
+                ),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that stored senders match the signers recovered from the transaction signatures.
+pub(super) fn senders<N: ProviderNodeTypes>(
+    context: &VerifyContext<'_, N>,
+    run: &CheckRun,
+) -> eyre::Result<()> {
+    let provider = context.provider()?;
+    let segments = [PruneSegment::SenderRecovery, PruneSegment::Bodies];
+    let Some(range) = unpruned_range(&provider, run, &context.range, &segments)? else {
+        return Ok(())
+    };
+    run.set_total(block_count(&range));
+
+    for batch in batches(range) {
+        let indices = provider.block_body_indices_range(batch.clone())?;
+        let (Some(first), Some(last)) = (indices.first(), indices.last()) else {
+            run.issue(Some(*batch.start()), "Body indices are missing");
+            run.advance(block_count(&batch));
+            continue
+        };
+
+        let tx_range = first.first_tx_num()..last.next_tx_num();
+        let transactions = provider.transactions_by_tx_range(tx_range.clone())?;
+        let senders = provider.senders_by_tx_range(tx_range.clone())?;
+        if transactions.len() != senders.len() ||
+            transactions.len() as u64 != tx_range.end - tx_range.start
+        {
+            run.issue(
+                Some(*batch.start()),
+                format!(
+                    "Blocks {batch:?} have {} transactions and {} senders, body indices expect {}",
+                    transactions.len(),
+                    senders.len(),
+                    tx_range.end - tx_range.start
+                ),
+            );
+            run.advance(block_count(&batch));
+            continue
+        }
+
+        // Signature recovery dominates the check, so it's spread over all cores
+        let mismatches: Vec<_> = transactions
+            .par_iter()
+            .zip(senders.par_iter())
+            .enumerate()
+            .filter_map(|(index, (transaction, sender))| {
+                match transaction.recover_signer_unchecked() {
+                    Ok(signer) if signer == *sender => None,
+                    recovered => Some((index, *sender, recovered.ok())),
+                }
+            })
+            .collect();
+
+        for (index, sender, signer) in mismatches {
+            let tx_num = tx_range.start + index as u64;
+            let block = batch.start() +
+                indices.partition_point(|indices| indices.next_tx_num() <= tx_num) as u64;
+            let message = match signer {
+                Some(signer) => {
+                    format!("Sender of transaction {tx_num} is {sender}, its signer is {signer}")
+                }
+                None => format!("Signer of transaction {tx_num} can't be recovered"),
+            };
+            run.issue(Some(block), message);
+        }
+
+        run.advance(block_count(&batch));
+    }
+
+    Ok(())
+}
+
+/// Checks that stored receipts match the receipts roots, logs blooms and gas used of the headers.
+pub(super) fn receipts<N: ProviderNodeTypes>(
+    context: &VerifyContext<'_, N>,
+    run: &CheckRun,
+) -> eyre::Result<()> {
+    let provider = context.provider()?;
+    let segments = [PruneSegment::Receipts, PruneSegment::ContractLogs, PruneSegment::Bodies];
+    let Some(range) = unpruned_range(&provider, run, &context.range, &segments)? else {
+        return Ok(())
+    };
+    run.set_total(block_count(&range));
+
+    let chain_spec = context.tool.provider_factory.chain_spec();
+    for batch in batches(range) {
+        let headers = provider.headers_range(batch.clone())?;
+        let indices = provider.block_body_indices_range(batch.clone())?;
+        let (Some(first), Some(last)) = (indices.first(), indices.last()) else {
+            run.issue(Some(*batch.start()), "Body indices are missing");
+            run.advance(block_count(&batch));
+            continue
+        };
+
+        let first_tx_num = first.first_tx_num();
+        let receipts = provider.receipts_by_tx_range(first_tx_num..last.next_tx_num())?;
+
+        for (index, number) in batch.clone().enumerate() {
+            let (Some(header), Some(block_indices)) = (headers.get(index), indices.get(index))
+            else {
+                run.issue(Some(number), "Header or body indices are missing");
+                continue
+            };
+            let Some(block_receipts) = block_slice(&receipts, first_tx_num, block_indices) else {
+                run.issue(
+                    Some(number),
+                    format!("Receipts {:?} are missing", block_indices.tx_num_range()),
+                );
+                continue
+            };
+
+            let gas_used = block_receipts.last().map_or(0, |receipt| receipt.cumulative_gas_used());
+            if gas_used != header.gas_used() {
+                run.issue(
+                    Some(number),
+                    format!("Receipts use {gas_used} gas, the header has {}", header.gas_used()),
+                );
+            }
+
+            // Before Byzantium, receipts contained intermediate state roots that aren't stored
+            if !chain_spec.is_byzantium_active_at_block(number) {
+                continue
+            }
+
+            let receipts_with_bloom =
+                block_receipts.iter().map(TxReceipt::with_bloom_ref).collect::<Vec<_>>();
+            let receipts_root = calculate_receipt_root(&receipts_with_bloom);
+            if receipts_root != header.receipts_root() {
+                run.issue(
+                    Some(number),
+                    format!(
+                        "Receipts root is {receipts_root}, the header has {}",
+                        header.receipts_root()
+                    ),
+                );
+            }
+            let logs_bloom =
+                receipts_with_bloom.iter().fold(Bloom::ZERO, |bloom, r| bloom | r.bloom_ref());
+            if logs_bloom != header.logs_bloom() {
+                run.issue(Some(number), "Logs bloom of the receipts differs from the header");
+            }
+        }
+
+        run.advance(block_count(&batch));
+    }
+
+    Ok(())
+}
+
+/// Checks that changesets don't extend past the executed blocks, that they agree with the state
+/// of the tip, and that the plain state matches the hashed state.
+///
+/// Nodes using the hashed state as canonical state don't keep a plain state, their changesets are
+/// checked against the hashed state.
+pub(super) fn changesets<N: ProviderNodeTypes>(
+    context: &VerifyContext<'_, N>,
+    run: &CheckRun,
+) -> eyre::Result<()> {
+    let provider = context.provider()?;
+    let settings = provider.cached_storage_settings();
+    let tx = provider.tx_ref();
+
+    // Changesets past the executed blocks are left over from an interrupted unwind
+    let executed = provider.get_stage_checkpoint(StageId::Execution)?.unwrap_or_default();
+    let (highest_account_change, highest_storage_change) = if settings.storage_v2 {
+        let static_file_provider = provider.static_file_provider();
+        (
+            static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::AccountChangeSets),
+            static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::StorageChangeSets),
+        )
+    } else {
+        (
+            tx.cursor_read::<tables::AccountChangeSets>()?.last()?.map(|(block, _)| block),
+            tx.cursor_read::<tables::StorageChangeSets>()?
+                .last()?
+                .map(|(key, _)| key.block_number()),
+        )
+    };
+    for (kind, highest) in
+        [("Account", highest_account_change), ("Storage", highest_storage_change)]
+    {
+        if let Some(highest) = highest.filter(|highest| *highest > executed.block_number) {
+            run.issue(
+                Some(highest),
+                format!(
+                    "{kind} changesets extend to block {highest}, blocks are executed up to {}",
+                    executed.block_number
+                ),
+            );
+        }
+    }
+
+    let plain_entries = if settings.use_hashed_state() {
+        0
+    } else {
+        provider.state_entries::<tables::PlainAccountState>()? +
+            provider.state_entries::<tables::PlainStorageState>()?
+    };
+    run.set_total(2 * block_count(&(0..=executed.block_number)) + plain_entries as u64);
+
+    changesets_match_state(&provider, run, executed.block_number)?;
+
+    if settings.use_hashed_state() {
+        run.note("Plain state isn't kept with the hashed state as canonical state, skipped");
+        return Ok(())
+    }
+    plain_state_matches_hashed_state(&provider, run)
+}
+
+/// Checks that the first change of every account and slot starts from an empty value, and that
+/// the state holds exactly the accounts and slots that are set after their last change.
+///
+/// The first and last changes are found with the history indices, which the `history` check
+/// verifies against the changesets. Progress advances by two for every executed block.
+fn changesets_match_state<N: ProviderNodeTypes>(
+    provider: &DatabaseProviderRO<N::DB, N>,
+    run: &CheckRun,
+    executed: BlockNumber,
+) -> eyre::Result<()> {
+    // Without the changesets since genesis, the first and last changes are unknown
+    for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+        if provider
+            .get_prune_checkpoint(segment)?
+            .is_some_and(|checkpoint| checkpoint.block_number.is_some())
+        {
+            run.note(format!(
+                "{segment} is pruned, comparison of the changesets with the state skipped"
+            ));
+            return Ok(())
+        }
+    }
+    for stage in [StageId::IndexAccountHistory, StageId::IndexStorageHistory] {
+        let indexed = provider.get_stage_checkpoint(stage)?.unwrap_or_default().block_number;
+        if indexed < executed {
+            run.note(format!(
+                "{stage} is at block {indexed}, blocks are executed up to {executed}, comparison \
+                 of the changesets with the state skipped"
+            ));
+            return Ok(())
+        }
+    }
+
+    let use_hashed_state = provider.cached_storage_settings().use_hashed_state();
+    let range = 0..=executed;
+
+    let mut plain_accounts = provider.state_cursor_read::<tables::PlainAccountState>()?;
+    let mut hashed_accounts = provider.state_cursor_read::<tables::HashedAccounts>()?;
+    let mut set_accounts = 0;
+    provider.with_rocksdb_tx(|rocksdb_tx| {
+        let mut history = EitherReader::new_accounts_history(provider, rocksdb_tx)?;
+        for batch in batches(range.clone()) {
+            for (block, change) in provider.account_changesets_range(batch.clone())? {
+                let address = change.address;
+                let is_first = block == 0 ||
+                    history.account_history_info(address, block - 1, None)? ==
+                        HistoryInfo::NotYetWritten;
+                if is_first && let Some(account) = change.info {
+                    run.issue(
+                        Some(block),
+                        format!(
+                            "First change of account {address} is from {account:?}, expected \
+                             no account"
+                        ),
+                    );
+                }
+
+                let is_last = !matches!(
+                    history.account_history_info(address, block + 1, None)?,
+                    HistoryInfo::InChangeset(_)
+                );
+                if is_last {
+                    let is_set = if use_hashed_state {
+                        hashed_accounts.seek_exact(keccak256(address))?.is_some()
+                    } else {
+                        plain_accounts.seek_exact(address)?.is_some()
+                    };
+                    set_accounts += usize::from(is_set);
+                }
+            }
+            run.advance(block_count(&batch));
+        }
+        Ok(())
+    })?;
+
+    let state_accounts = if use_hashed_state {
+        provider.state_entries::<tables::HashedAccounts>()?
+    } else {
+        provider.state_entries::<tables::PlainAccountState>()?
+    };
+    if state_accounts != set_accounts {
+        run.issue(
+            None,
+            format!(
+                "State has {state_accounts} accounts, changesets leave {set_accounts} accounts set"
+            ),
+        );
+    }
+
+    let mut plain_storages = provider.state_cursor_dup_read::<tables::PlainStorageState>()?;
+    let mut hashed_storages = provider.state_cursor_dup_read::<tables::HashedStorages>()?;
+    let mut set_slots = 0;
+    provider.with_rocksdb_tx(|rocksdb_tx| {
+        let mut history = EitherReader::new_storages_history(provider, rocksdb_tx)?;
+        for batch in batches(range.clone()) {
+            for (key, entry) in provider.storage_changesets_range(batch.clone())? {
+                let (block, address) = (key.block_number(), key.address());
+                let slot = entry.key.as_b256();
+                let is_first = block == 0 ||
+                    history.storage_history_info(address, slot, block - 1, None)? ==
+                        HistoryInfo::NotYetWritten;
+                if is_first && !entry.value.is_zero() {
+                    run.issue(
+                        Some(block),
+                        format!(
+                            "First change of slot {slot} of {address} is from {}, expected zero",
+                            entry.value
+                        ),
+                    );
+                }
+
+                let is_last = !matches!(
+                    history.storage_history_info(address, slot, block + 1, None)?,
+                    HistoryInfo::InChangeset(_)
+                );
+                if is_last {
+                    let is_set = if use_hashed_state {
+                        let hashed_slot = entry.key.to_hashed();
+                        hashed_storages
+                            .seek_by_key_subkey(keccak256(address), hashed_slot)?
+                            .is_some_and(|hashed| hashed.key == hashed_slot)
+                    } else {
+                        plain_storages
+                            .seek_by_key_subkey(address, slot)?
+                            .is_some_and(|plain| plain.key == slot)
+                    };
+                    set_slots += usize::from(is_set);
+                }
+            }
+            run.advance(block_count(&batch));
+        }
+        Ok(())
+    })?;
+
+    let state_slots = if use_hashed_state {
+        provider.state_entries::<tables::HashedStorages>()?
+    } else {
+        provider.state_entries::<tables::PlainStorageState>()?
+    };
+    if state_slots != set_slots {
+        run.issue(
+            None,
+            format!("State has {state_slots} slots, changesets leave {set_slots} slots set"),
+        );
+    }
+
+    Ok(())
+}
+
+/// Checks that the plain state matches the hashed state.
+///
+/// Progress advances by one for every plain state entry.
+fn plain_state_matches_hashed_state<N: ProviderNodeTypes>(
+    provider: &DatabaseProviderRO<N::DB, N>,
+    run: &CheckRun,
+) -> eyre::Result<()> {
+    let plain_accounts = provider.state_entries::<tables::PlainAccountState>()?;
+    let plain_storages = provider.state_entries::<tables::PlainStorageState>()?;
+
+    let mut hashed_accounts = provider.state_cursor_read::<tables::HashedAccounts>()?;
+    for entry in provider.state_cursor_read::<tables::PlainAccountState>()?.walk(None)? {
+        let (address, account) = entry?;
+        match hashed_accounts.seek_exact(keccak256(address))? {
+            Some((_, hashed)) if hashed == account => {}
+            Some((_, hashed)) => run.issue(
+                None,
+                format!("Account {address} is {account:?}, its hashed account is {hashed:?}"),
+            ),
+            None => run.issue(None, format!("Account {address} is missing from the hashed state")),
+        }
+        run.advance(1);
+    }
+
+    let mut hashed_storages = provider.state_cursor_dup_read::<tables::HashedStorages>()?;
+    for entry in provider.state_cursor_dup_read::<tables::PlainStorageState>()?.walk(None)? {
+        let (address, slot) = entry?;
+        let hashed_slot = keccak256(slot.key);
+        match hashed_storages
+            .seek_by_key_subkey(keccak256(address), hashed_slot)?
+            .filter(|hashed| hashed.key == hashed_slot)
+        {
+            Some(hashed) if hashed.value == slot.value => {}
+            Some(hashed) => run.issue(
+                None,
+                format!(
+                    "Slot {} of {address} is {}, its hashed slot is {}",
+                    slot.key, slot.value, hashed.value
+                ),
+            ),
+            None => run.issue(
+                None,
+                format!("Slot {} of {address} is missing from the hashed state", slot.key),
+            ),
+        }
+        run.advance(1);
+    }
+
+    // Together with the lookups above, equal counts mean there are no extra hashed entries
+    let hashed_accounts = provider.state_entries::<tables::HashedAccounts>()?;
+    if hashed_accounts != plain_accounts {
+        run.issue(
+            None,
+            format!(
+                "Hashed state has {hashed_accounts} accounts, plain state has {plain_accounts}"
+            ),
+        );
+    }
+    let hashed_storages = provider.state_entries::<tables::HashedStorages>()?;
+    if hashed_storages != plain_storages {
+        run.issue(
+            None,
+            format!("Hashed state has {hashed_storages} slots, plain state has {plain_storages}"),
+        );
+    }
+
+    Ok(())
+}
+
+/// Checks that history indices hold exactly the blocks of the changesets.
+pub(super) fn history<N: ProviderNodeTypes>(
+    context: &VerifyContext<'_, N>,
+    run: &CheckRun,
+) -> eyre::Result<()> {
+    let provider = context.provider()?;
+    let account_range =
+        unpruned_range(&provider, run, &context.range, &[PruneSegment::AccountHistory])?;
+    let storage_range =
+        unpruned_range(&provider, run, &context.range, &[PruneSegment::StorageHistory])?;
+    run.set_total(
+        account_range.as_ref().map_or(0, block_count) +
+            storage_range.as_ref().map_or(0, block_count),
+    );
+
+    if let Some(range) = account_range {
+        let mut changes = 0;
+        provider.with_rocksdb_tx(|rocksdb_tx| {
+            let mut reader = EitherReader::new_accounts_history(&provider, rocksdb_tx)?;
+            for batch in batches(range.clone()) {
+                for (block, change) in provider.account_changesets_range(batch.clone())? {
+                    let info = reader.account_history_info(change.address, block, None)?;
+                    if info != HistoryInfo::InChangeset(block) {
+                        run.issue(
+                            Some(block),
+                            format!(
+                                "Change of account {} is missing from the history index, \
+                                 lookup returned {info:?}",
+                                change.address
+                            ),
+                        );
+                    }
+                    changes += 1;
+                }
+                run.advance(block_count(&batch));
+            }
+            Ok(())
+        })?;
+
+        let indexed = count_history_entries::<tables::AccountsHistory, N>(
+            &provider,
+            &range,
+            provider.cached_storage_settings().account_history_in_rocksdb(),
+        )?;
+        if indexed != changes {
+            run.issue(
+                None,
+                format!(
+                    "Account history index has {indexed} entries in blocks {range:?}, \
+                     changesets have {changes}"
+                ),
+            );
+        }
+    }
+
+    if let Some(range) = storage_range {
+        let mut changes = 0;
+        provider.with_rocksdb_tx(|rocksdb_tx| {
+            let mut reader = EitherReader::new_storages_history(&provider, rocksdb_tx)?;
+            for batch in batches(range.clone()) {
+                for (key, entry) in provider.storage_changesets_range(batch.clone())? {
+                    let (block, address) = (key.block_number(), key.address());
+                    let slot = entry.key.as_b256();
+                    let info = reader.storage_history_info(address, slot, block, None)?;
+                    if info != HistoryInfo::InChangeset(block) {
+                        run.issue(
+                            Some(block),
+                            format!(
+                                "Change of slot {slot} of {address} is missing from the history \
+                                 index, lookup returned {info:?}"
+                            ),
+                        );
+                    }
+                    changes += 1;
+                }
+                run.advance(block_count(&batch));
+            }
+            Ok(())
+        })?;
+
+        let indexed = count_history_entries::<tables::StoragesHistory, N>(
+            &provider,
+            &range,
+            provider.cached_storage_settings().storages_history_in_rocksdb(),
+        )?;
+        if indexed != changes {
+            run.issue(
+                None,
+                format!(
+                    "Storage history index has {indexed} entries in blocks {range:?}, \
+                     changesets have {changes}"
+                ),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that the trie tables match the hashed state, and that their root matches the state
+/// root of the tip.
+pub(super) fn state_root<N: ProviderNodeTypes>(
+    context: &VerifyContext<'_, N>,
+    run: &CheckRun,
+) -> eyre::Result<()> {
+    let provider = context.provider()?;
+    let merkle = provider.get_stage_checkpoint(StageId::MerkleExecute)?.unwrap_or_default();
+    if merkle.block_number != context.tip {
+        run.issue(
+            Some(merkle.block_number),
+            format!("Trie tables are at block {}, the tip is {}", merkle.block_number, context.tip),
+        );
+        return Ok(())
+    }
+    let Some(header) = provider.header_by_number(context.tip)? else {
+        run.issue(Some(context.tip), "Header of the tip is missing");
+        return Ok(())
+    };
+
+    run.set_total(PROGRESS_SCALE);
+    let cursor_factory = StateCursorFactory::new(&provider);
+    let verifier = Verifier::new(&cursor_factory, cursor_factory)?;
+    for output in verifier {
+        match output? {
+            Output::Progress(path) => {
+                run.set_done((trie_path_progress(&path) * PROGRESS_SCALE as f64) as u64)
+            }
+            inconsistency => run.issue(
+                None,
+                format!("Trie table differs from the hashed state: {inconsistency:?}"),
+            ),
+        }
+    }
+    run.set_done(PROGRESS_SCALE);
+
+    // The root node stores the state root, unless the trie is too small to have branch nodes
+    let root = match provider
+        .get_state::<tables::AccountsTrie>(StoredNibbles(Nibbles::default()))?
+        .and_then(|node| node.root_hash)
+    {
+        Some(root) => root,
+        None => cursor_factory.state_root().root()?,
+    };
+    if root != header.state_root() {
+        run.issue(
+            Some(context.tip),
+            format!("State root of the trie is {root}, the tip has {}", header.state_root()),
+        );
+    }
+
+    Ok(())
+}
+
+/// Returns the part of `range` whose data isn't pruned by any of the segments, noting the pruned
+/// part on the run. Returns `None` if all of it is pruned.
+fn unpruned_range<N: ProviderNodeTypes>(
+    provider: &DatabaseProviderRO<N::DB, N>,
+    run: &CheckRun,
+    range: &RangeInclusive<BlockNumber>,
+    segments: &[PruneSegment],
+) -> eyre::Result<Option<RangeInclusive<BlockNumber>>> {
+    let mut start = *range.start();
+    for segment in segments {
+        if let Some(pruned) =
+            provider.get_prune_checkpoint(*segment)?.and_then(|checkpoint| checkpoint.block_number)
+        {
+            start = start.max(pruned + 1);
+        }
+    }
+
+    if start > *range.end() {
+        run.note(format!("Blocks {range:?} are pruned, skipped"));
+        return Ok(None)
+    }
+    if start > *range.start() {
+        run.note(format!("Blocks {}..={} are pruned, skipped", range.start(), start - 1));
+    }
+    Ok(Some(start..=*range.end()))
+}
+
+/// Counts the entries of a history index in the block range, reading it from `RocksDB` if
+/// `in_rocksdb` is set.
+fn count_history_entries<T, N>(
+    provider: &DatabaseProviderRO<N::DB, N>,
+    range: &RangeInclusive<BlockNumber>,
+    in_rocksdb: bool,
+) -> eyre::Result<u64>
+where
+    T: Table<Value = BlockNumberList>,
+    N: ProviderNodeTypes,
+{
+    let count =
+        |list: &BlockNumberList| list.iter().filter(|block| range.contains(block)).count() as u64;
+
+    #[cfg(all(unix, feature = "rocksdb"))]
+    if in_rocksdb {
+        let mut total = 0;
+        for entry in provider.rocksdb_provider().iter::<T>()? {
+            total += count(&entry?.1);
+        }
+        return Ok(total)
+    }
+
+    #[cfg(not(all(unix, feature = "rocksdb")))]
+    eyre::ensure!(!in_rocksdb, "History index is stored in RocksDB, which isn't supported");
+
+    let mut total = 0;
+    for entry in provider.tx_ref().cursor_read::<T>()?.walk(None)? {
+        total += count(&entry?.1);
+    }
+    Ok(total)
+}
+
+/// Returns the entries of a block from entries starting at transaction `first_tx_num`.
+fn block_slice<'a, T>(
+    entries: &'a [T],
+    first_tx_num: u64,
+    indices: &StoredBlockBodyIndices,
+) -> Option<&'a [T]> {
+    let start = usize::try_from(indices.first_tx_num().checked_sub(first_tx_num)?).ok()?;
+    entries.get(start..start + indices.tx_count() as usize)
+}
+
+/// Splits a block range into batches of [`BATCH_SIZE`] blocks.
+fn batches(
+    range: RangeInclusive<BlockNumber>,
+) -> impl Iterator<Item = RangeInclusive<BlockNumber>> {
+    let end = *range.end();
+    range.step_by(BATCH_SIZE as usize).map(move |start| start..=end.min(start + BATCH_SIZE - 1))
+}
+
+fn block_count(range: &RangeInclusive<BlockNumber>) -> u64 {
+    range.end() + 1 - range.start()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_batches() {
+        assert_eq!(batches(5..=5).collect::<Vec<_>>(), [5..=5]);
+        assert_eq!(
+            batches(1..=2 * BATCH_SIZE + 1).collect::<Vec<_>>(),
+            [
+                1..=BATCH_SIZE,
+                BATCH_SIZE + 1..=2 * BATCH_SIZE,
+                2 * BATCH_SIZE + 1..=2 * BATCH_SIZE + 1
+            ]
+        );
+    }
+}
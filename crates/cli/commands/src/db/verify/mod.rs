@@ -0,0 +1,663 @@
+use alloy_primitives::BlockNumber;
+use clap::{Parser, ValueEnum};
+use hanzo_evm_db_common::DbTool;
+use hanzo_evm_node_builder::NodeTypesWithDB;
+use hanzo_evm_provider::{
+    providers::ProviderNodeTypes, DBProvider, DatabaseProviderRO, StageCheckpointReader,
+};
+use hanzo_evm_stages::StageId;
+use hanzo_evm_tasks::spawn_scoped_os_thread;
+use itertools::Itertools;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::{
+    fs::File,
+    io::BufWriter,
+    ops::RangeInclusive,
+    path::PathBuf,
+    sync::atomic::{AtomicU64, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+use tracing::{info, warn};
+
+mod checks;
+
+/// Interval between progress reports of the running checks.
+const PROGRESS_PERIOD: Duration = Duration::from_secs(5);
+
+/// Interval at which the running checks are polled for completion.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The arguments for the `evm db verify` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Checks to run, all of them if not set
+    #[arg(long, value_enum, value_delimiter = ',')]
+    checks: Vec<Check>,
+
+    /// First block to verify (inclusive)
+    #[arg(long, default_value_t = 0)]
+    from: BlockNumber,
+
+    /// Last block to verify (inclusive), the tip if not set
+    ///
+    /// The `changesets` and `state-root` checks always verify the state at the tip.
+    #[arg(long)]
+    to: Option<BlockNumber>,
+
+    /// Maximum number of issues recorded per check, further issues are only counted
+    #[arg(long, default_value_t = 100)]
+    max_issues: usize,
+
+    /// Writes a JSON report of the verification to this file
+    #[arg(long, value_name = "FILE")]
+    report: Option<PathBuf>,
+}
+
+/// An integrity check of `evm db verify`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum Check {
+    /// Headers hash to the canonical hashes and link to their parents
+    Headers,
+    /// Body indices are contiguous, and the stored transactions match their counts and the
+    /// transactions roots of the headers
+    Bodies,
+    /// Stored senders match the signers recovered from the transaction signatures
+    Senders,
+    /// Stored receipts match the receipts roots, logs blooms and gas used of the headers
+    Receipts,
+    /// Changesets don't extend past the executed blocks, agree with the state of the tip, and the
+    /// plain state matches the hashed state
+    Changesets,
+    /// History indices hold exactly the blocks of the changesets
+    History,
+    /// Trie tables match the hashed state, and their root matches the state root of the tip
+    StateRoot,
+}
+
+impl Check {
+    const fn name(&self) -> &'static str {
+        match self {
+            Self::Headers => "headers",
+            Self::Bodies => "bodies",
+            Self::Senders => "senders",
+            Self::Receipts => "receipts",
+            Self::Changesets => "changesets",
+            Self::History => "history",
+            Self::StateRoot => "state-root",
+        }
+    }
+
+    fn run<N: ProviderNodeTypes>(
+        self,
+        context: &VerifyContext<'_, N>,
+        run: &CheckRun,
+    ) -> eyre::Result<()> {
+        match self {
+            Self::Headers => checks::headers(context, run),
+            Self::Bodies => checks::bodies(context, run),
+            Self::Senders => checks::senders(context, run),
+            Self::Receipts => checks::receipts(context, run),
+            Self::Changesets => checks::changesets(context, run),
+            Self::History => checks::history(context, run),
+            Self::StateRoot => checks::state_root(context, run),
+        }
+    }
+}
+
+impl Command {
+    /// Execute `db verify` command
+    pub fn execute<N: ProviderNodeTypes>(self, tool: &DbTool<N>) -> eyre::Result<()> {
+        let tip = tool
+            .provider_factory
+            .provider()?
+            .get_stage_checkpoint(StageId::Finish)?
+            .unwrap_or_default()
+            .block_number;
+        let to = self.to.unwrap_or(tip);
+        eyre::ensure!(self.from <= to, "--from ({}) is greater than --to ({to})", self.from);
+        eyre::ensure!(to <= tip, "--to ({to}) is greater than the tip ({tip})");
+
+        let checks = if self.checks.is_empty() {
+            Check::value_variants().to_vec()
+        } else {
+            self.checks.into_iter().unique().collect()
+        };
+        info!(
+            target: "evm::cli",
+            tip,
+            from = self.from,
+            to,
+            checks = %checks.iter().map(Check::name).join(","),
+            "Verifying database"
+        );
+
+        let context = VerifyContext { tool, range: self.from..=to, tip };
+        let runs =
+            checks.into_iter().map(|check| CheckRun::new(check, self.max_issues)).collect_vec();
+
+        let reports = thread::scope(|scope| {
+            let handles = runs
+                .iter()
+                .map(|run| {
+                    let context = &context;
+                    spawn_scoped_os_thread(scope, "db-verify", move || {
+                        let started_at = Instant::now();
+                        let result = run.check.run(context, run);
+                        (result, started_at.elapsed())
+                    })
+                })
+                .collect_vec();
+
+            let mut last_progress_time = Instant::now();
+            while !handles.iter().all(|handle| handle.is_finished()) {
+                thread::sleep(POLL_INTERVAL);
+                if last_progress_time.elapsed() > PROGRESS_PERIOD {
+                    for (run, handle) in runs.iter().zip(&handles) {
+                        if !handle.is_finished() {
+                            run.output_progress();
+                        }
+                    }
+                    last_progress_time = Instant::now();
+                }
+            }
+
+            runs.iter()
+                .zip(handles)
+                .map(|(run, handle)| {
+                    let (result, elapsed) = handle
+                        .join()
+                        .unwrap_or_else(|_| (Err(eyre::eyre!("Check panicked")), Duration::ZERO));
+                    run.report(result, elapsed)
+                })
+                .collect_vec()
+        });
+
+        let report = Report {
+            tip,
+            from: self.from,
+            to,
+            passed: reports.iter().all(|report| report.status == CheckStatus::Passed),
+            checks: reports,
+        };
+
+        for check in &report.checks {
+            let elapsed = humantime::format_duration(Duration::from_secs(check.elapsed_secs));
+            match check.status {
+                CheckStatus::Passed => {
+                    info!(target: "evm::cli", check = check.check.name(), %elapsed, "Check passed")
+                }
+                CheckStatus::Failed => warn!(
+                    target: "evm::cli",
+                    check = check.check.name(),
+                    issues = check.issue_count,
+                    %elapsed,
+                    "Check failed"
+                ),
+                CheckStatus::Errored => warn!(
+                    target: "evm::cli",
+                    check = check.check.name(),
+                    error = check.error.as_deref().unwrap_or_default(),
+                    "Check could not be completed"
+                ),
+            }
+        }
+
+        if let Some(path) = &self.report {
+            serde_json::to_writer_pretty(BufWriter::new(File::create(path)?), &report)?;
+            info!(target: "evm::cli", ?path, "Wrote verification report");
+        }
+
+        eyre::ensure!(report.passed, "Database verification failed");
+        info!(target: "evm::cli", "Database verification passed");
+
+        Ok(())
+    }
+}
+
+/// Shared inputs of the checks.
+struct VerifyContext<'a, N: NodeTypesWithDB> {
+    tool: &'a DbTool<N>,
+    /// Blocks to verify.
+    range: RangeInclusive<BlockNumber>,
+    /// Highest block that finished all stages.
+    tip: BlockNumber,
+}
+
+impl<N: ProviderNodeTypes> VerifyContext<'_, N> {
+    /// Returns a read-only provider for long-running reads.
+    fn provider(&self) -> eyre::Result<DatabaseProviderRO<N::DB, N>> {
+        Ok(self.tool.provider_factory.provider()?.disable_long_read_transaction_safety())
+    }
+}
+
+/// Progress and findings of a check, shared with the progress reporter while it runs.
+#[derive(Debug)]
+struct CheckRun {
+    check: Check,
+    max_issues: usize,
+    /// Units of work done so far, out of `total`.
+    done: AtomicU64,
+    total: AtomicU64,
+    issue_count: AtomicU64,
+    issues: Mutex<Vec<Issue>>,
+    notes: Mutex<Vec<String>>,
+}
+
+impl CheckRun {
+    fn new(check: Check, max_issues: usize) -> Self {
+        Self {
+            check,
+            max_issues,
+            done: AtomicU64::new(0),
+            total: AtomicU64::new(0),
+            issue_count: AtomicU64::new(0),
+            issues: Mutex::new(Vec::new()),
+            notes: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Sets the units of work of the check.
+    fn set_total(&self, total: u64) {
+        self.total.store(total, Ordering::Relaxed);
+    }
+
+    /// Records units of work as done.
+    fn advance(&self, done: u64) {
+        self.done.fetch_add(done, Ordering::Relaxed);
+    }
+
+    /// Sets the units of work done so far.
+    fn set_done(&self, done: u64) {
+        self.done.store(done, Ordering::Relaxed);
+    }
+
+    /// Records an inconsistency, optionally at a block.
+    fn issue(&self, block: Option<BlockNumber>, message: impl Into<String>) {
+        let message = message.into();
+        let count = self.issue_count.fetch_add(1, Ordering::Relaxed);
+        if count < self.max_issues as u64 {
+            warn!(target: "evm::cli", check = self.check.name(), ?block, "{message}");
+            self.issues.lock().push(Issue { block, message });
+        }
+    }
+
+    /// Records something that limited the check without being an inconsistency, like pruned
+    /// data.
+    fn note(&self, note: impl Into<String>) {
+        let note = note.into();
+        info!(target: "evm::cli", check = self.check.name(), "{note}");
+        self.notes.lock().push(note);
+    }
+
+    fn output_progress(&self) {
+        let done = self.done.load(Ordering::Relaxed);
+        let total = self.total.load(Ordering::Relaxed);
+        let progress_percent = if total == 0 { 0.0 } else { done as f64 / total as f64 * 100.0 };
+
+        info!(
+            target: "evm::cli",
+            check = self.check.name(),
+            progress_percent = format!("{progress_percent:.2}"),
+            issues = self.issue_count.load(Ordering::Relaxed),
+            "Verifying database"
+        );
+    }
+
+    fn report(&self, result: eyre::Result<()>, elapsed: Duration) -> CheckReport {
+        let issue_count = self.issue_count.load(Ordering::Relaxed);
+        let status = match &result {
+            Err(_) => CheckStatus::Errored,
+            Ok(()) if issue_count > 0 => CheckStatus::Failed,
+            Ok(()) => CheckStatus::Passed,
+        };
+
+        CheckReport {
+            check: self.check,
+            status,
+            checked: self.done.load(Ordering::Relaxed),
+            issue_count,
+            issues: std::mem::take(&mut *self.issues.lock()),
+            notes: std::mem::take(&mut *self.notes.lock()),
+            error: result.err().map(|error| format!("{error:#}")),
+            elapsed_secs: elapsed.as_secs(),
+        }
+    }
+}
+
+/// Machine-readable report of `evm db verify`.
+#[derive(Debug, Serialize)]
+struct Report {
+    tip: BlockNumber,
+    from: BlockNumber,
+    to: BlockNumber,
+    /// Whether all checks passed.
+    passed: bool,
+    checks: Vec<CheckReport>,
+}
+
+/// Outcome of a single check.
+#[derive(Debug, Serialize)]
+struct CheckReport {
+    check: Check,
+    status: CheckStatus,
+    /// Units of work checked, e.g. blocks or state entries.
+    checked: u64,
+    /// Number of inconsistencies found, including the ones beyond `--max-issues`.
+    issue_count: u64,
+    issues: Vec<Issue>,
+    notes: Vec<String>,
+    /// Error that stopped the check.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    elapsed_secs: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum CheckStatus {
+    /// No inconsistencies were found.
+    Passed,
+    /// Inconsistencies were found.
+    Failed,
+    /// The check stopped with an error.
+    Errored,
+}
+
+/// An inconsistency found by a check.
+#[derive(Debug, Serialize)]
+struct Issue {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    block: Option<BlockNumber>,
+    message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{fixture_chain, hashed_state_root};
+    use alloy_primitives::{keccak256, Address, Bytes, U256};
+    use hanzo_evm_db_api::{
+        cursor::{DbCursorRW, DbDupCursorRO},
+        models::{AccountBeforeTx, ShardedKey, StoredBlockBodyIndices},
+        tables,
+        transaction::{DbTx, DbTxMut},
+    };
+    use hanzo_evm_primitives_traits::Account;
+    use hanzo_evm_provider::{
+        test_utils::MockNodeTypesWithDB, BlockBodyIndicesProvider, BlockExecutionWriter,
+        BlockHashReader, HeaderProvider, ProviderFactory, ReceiptProvider, StageCheckpointWriter,
+        StateCursorFactory, StateCursorProvider, StaticFileProviderFactory, StaticFileWriter,
+        StorageSettings,
+    };
+    use hanzo_evm_stages::StageCheckpoint;
+    use hanzo_evm_static_file_types::StaticFileSegment;
+    use serde_json::json;
+
+    const TIP: BlockNumber = 3;
+
+    /// Block whose data the tests corrupt.
+    const FAULTY_BLOCK: BlockNumber = 2;
+
+    /// Creates a fixture chain of [`TIP`] blocks on which all stages finished.
+    fn verified_chain(settings: StorageSettings) -> ProviderFactory<MockNodeTypesWithDB> {
+        let provider_factory = fixture_chain(TIP, settings).unwrap();
+        let provider_rw = provider_factory.provider_rw().unwrap();
+        for stage in StageId::ALL {
+            provider_rw.save_stage_checkpoint(stage, StageCheckpoint::new(TIP)).unwrap();
+        }
+        provider_rw.commit().unwrap();
+        provider_factory
+    }
+
+    /// Runs a check on all blocks.
+    fn run_check(
+        provider_factory: &ProviderFactory<MockNodeTypesWithDB>,
+        check: Check,
+    ) -> CheckReport {
+        let tool = DbTool::new(provider_factory.clone()).unwrap();
+        let context = VerifyContext { tool: &tool, range: 0..=TIP, tip: TIP };
+        let run = CheckRun::new(check, 100);
+        let result = check.run(&context, &run);
+        run.report(result, Duration::ZERO)
+    }
+
+    /// Asserts that the check finds issues, one of them at `block` if set.
+    fn assert_detected(
+        provider_factory: &ProviderFactory<MockNodeTypesWithDB>,
+        check: Check,
+        block: Option<BlockNumber>,
+    ) {
+        let report = run_check(provider_factory, check);
+        assert_eq!(report.status, CheckStatus::Failed, "{}: {report:?}", check.name());
+        if block.is_some() {
+            assert!(
+                report.issues.iter().any(|issue| issue.block == block),
+                "{}: no issue at block {block:?} in {report:?}",
+                check.name()
+            );
+        }
+    }
+
+    #[test]
+    fn checks_pass_on_consistent_database() {
+        let mut layouts = vec![StorageSettings::v1(), StorageSettings::v2()];
+        #[cfg(all(unix, feature = "rocksdb"))]
+        layouts.extend(layouts.clone().into_iter().map(|layout| layout.with_state_in_rocksdb(true)));
+
+        for settings in layouts {
+            let provider_factory = verified_chain(settings);
+            for &check in Check::value_variants() {
+                let report = run_check(&provider_factory, check);
+                assert_eq!(
+                    report.status,
+                    CheckStatus::Passed,
+                    "{settings:?} {}: {report:?}",
+                    check.name()
+                );
+            }
+        }
+    }
+
+    #[cfg(all(unix, feature = "rocksdb"))]
+    #[test]
+    fn state_root_matches_after_unwind_with_state_in_rocksdb() {
+        for settings in [StorageSettings::v1(), StorageSettings::v2()] {
+            let settings = settings.with_state_in_rocksdb(true);
+            let provider_factory = verified_chain(settings);
+            let target = TIP - 1;
+            let state_root = provider_factory.header_by_number(target).unwrap().unwrap().state_root;
+
+            let provider_rw = provider_factory.unwind_provider_rw().unwrap();
+            provider_rw.remove_block_and_execution_above(target).unwrap();
+            provider_rw.commit().unwrap();
+
+            // The state is only kept in RocksDB
+            let provider = provider_factory.provider().unwrap();
+            assert_eq!(provider.tx_ref().entries::<tables::HashedAccounts>().unwrap(), 0);
+            assert!(provider.state_entries::<tables::HashedAccounts>().unwrap() > 0);
+
+            assert_eq!(hashed_state_root(&provider).unwrap(), state_root, "{settings:?}");
+            assert_eq!(
+                StateCursorFactory::new(&provider).state_root().root().unwrap(),
+                state_root,
+                "{settings:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn headers_detects_rewritten_header() {
+        let provider_factory = verified_chain(StorageSettings::v1());
+        let provider = provider_factory.provider().unwrap();
+        let mut header = provider.header_by_number(TIP).unwrap().unwrap();
+        let hash = provider.block_hash(TIP).unwrap().unwrap();
+        drop(provider);
+
+        header.extra_data = Bytes::from_static(b"fault");
+        let static_file_provider = provider_factory.static_file_provider();
+        let mut writer = static_file_provider.latest_writer(StaticFileSegment::Headers).unwrap();
+        writer.prune_headers(1).unwrap();
+        writer.commit().unwrap();
+        writer.append_header(&header, &hash).unwrap();
+        writer.commit().unwrap();
+        drop(writer);
+
+        assert_detected(&provider_factory, Check::Headers, Some(TIP));
+    }
+
+    #[test]
+    fn bodies_detects_shifted_body_indices() {
+        let provider_factory = verified_chain(StorageSettings::v1());
+        let provider_rw = provider_factory.database_provider_rw().unwrap();
+        let tx = provider_rw.tx_ref();
+        let indices = tx.get::<tables::BlockBodyIndices>(FAULTY_BLOCK).unwrap().unwrap();
+        let shifted = StoredBlockBodyIndices {
+            first_tx_num: indices.first_tx_num + 1,
+            tx_count: indices.tx_count,
+        };
+        tx.put::<tables::BlockBodyIndices>(FAULTY_BLOCK, shifted).unwrap();
+        provider_rw.commit().unwrap();
+
+        assert_detected(&provider_factory, Check::Bodies, Some(FAULTY_BLOCK));
+    }
+
+    #[test]
+    fn senders_detects_wrong_sender() {
+        let provider_factory = verified_chain(StorageSettings::v1());
+        let tx_num = first_tx_num(&provider_factory, FAULTY_BLOCK);
+        let provider_rw = provider_factory.database_provider_rw().unwrap();
+        provider_rw.tx_ref().put::<tables::TransactionSenders>(tx_num, Address::ZERO).unwrap();
+        provider_rw.commit().unwrap();
+
+        assert_detected(&provider_factory, Check::Senders, Some(FAULTY_BLOCK));
+    }
+
+    #[test]
+    fn receipts_detects_wrong_receipt() {
+        let provider_factory = verified_chain(StorageSettings::v1());
+        let tx_num = first_tx_num(&provider_factory, FAULTY_BLOCK);
+        let mut receipt = provider_factory.provider().unwrap().receipt(tx_num).unwrap().unwrap();
+        receipt.success = !receipt.success;
+        let provider_rw = provider_factory.database_provider_rw().unwrap();
+        provider_rw.tx_ref().put::<tables::Receipts>(tx_num, receipt).unwrap();
+        provider_rw.commit().unwrap();
+
+        assert_detected(&provider_factory, Check::Receipts, Some(FAULTY_BLOCK));
+    }
+
+    #[test]
+    fn changesets_detect_wrong_previous_value() {
+        // The recipient of the faulty block is created by it, so it had no account before
+        let recipient = Address::with_last_byte(FAULTY_BLOCK as u8);
+        let provider_factory = verified_chain(StorageSettings::v1());
+        let provider_rw = provider_factory.database_provider_rw().unwrap();
+        let mut cursor =
+            provider_rw.tx_ref().cursor_dup_write::<tables::AccountChangeSets>().unwrap();
+        cursor.seek_by_key_subkey(FAULTY_BLOCK, recipient).unwrap().unwrap();
+        cursor.delete_current().unwrap();
+        let info = Some(Account { balance: U256::from(1), ..Default::default() });
+        cursor.upsert(FAULTY_BLOCK, &AccountBeforeTx { address: recipient, info }).unwrap();
+        drop(cursor);
+        provider_rw.commit().unwrap();
+
+        assert_detected(&provider_factory, Check::Changesets, Some(FAULTY_BLOCK));
+    }
+
+    #[test]
+    fn changesets_detect_missing_state() {
+        let recipient = Address::with_last_byte(FAULTY_BLOCK as u8);
+
+        for settings in [StorageSettings::v1(), StorageSettings::v2()] {
+            let provider_factory = verified_chain(settings);
+            let provider_rw = provider_factory.database_provider_rw().unwrap();
+            // Nodes without plain state are checked against the hashed state
+            let deleted = if settings.use_hashed_state() {
+                provider_rw.tx_ref().delete::<tables::HashedAccounts>(keccak256(recipient), None)
+            } else {
+                provider_rw.tx_ref().delete::<tables::PlainAccountState>(recipient, None)
+            };
+            assert!(deleted.unwrap());
+            provider_rw.commit().unwrap();
+
+            assert_detected(&provider_factory, Check::Changesets, None);
+        }
+    }
+
+    #[test]
+    fn history_detects_missing_index() {
+        let recipient = Address::with_last_byte(FAULTY_BLOCK as u8);
+        let provider_factory = verified_chain(StorageSettings::v1());
+        let provider_rw = provider_factory.database_provider_rw().unwrap();
+        provider_rw
+            .tx_ref()
+            .delete::<tables::AccountsHistory>(ShardedKey::new(recipient, u64::MAX), None)
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        assert_detected(&provider_factory, Check::History, Some(FAULTY_BLOCK));
+    }
+
+    #[test]
+    fn state_root_detects_wrong_hashed_account() {
+        let recipient = Address::with_last_byte(FAULTY_BLOCK as u8);
+        let provider_factory = verified_chain(StorageSettings::v1());
+        let provider_rw = provider_factory.database_provider_rw().unwrap();
+        let hashed_address = keccak256(recipient);
+        let tx = provider_rw.tx_ref();
+        let mut account = tx.get::<tables::HashedAccounts>(hashed_address).unwrap().unwrap();
+        account.nonce += 1;
+        tx.put::<tables::HashedAccounts>(hashed_address, account).unwrap();
+        provider_rw.commit().unwrap();
+
+        assert_detected(&provider_factory, Check::StateRoot, None);
+    }
+
+    /// Returns the number of the first transaction of `block`.
+    fn first_tx_num(
+        provider_factory: &ProviderFactory<MockNodeTypesWithDB>,
+        block: BlockNumber,
+    ) -> u64 {
+        provider_factory
+            .provider()
+            .unwrap()
+            .block_body_indices(block)
+            .unwrap()
+            .unwrap()
+            .first_tx_num
+    }
+
+    #[test]
+    fn check_report() {
+        let run = CheckRun::new(Check::StateRoot, 1);
+        run.set_total(4);
+        run.advance(3);
+        run.issue(Some(7), "first");
+        run.issue(None, "second");
+        run.note("skipped");
+
+        let report = run.report(Ok(()), Duration::from_secs(2));
+        assert_eq!(report.status, CheckStatus::Failed);
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            json!({
+                "check": "state-root",
+                "status": "failed",
+                "checked": 3,
+                "issue_count": 2,
+                "issues": [{ "block": 7, "message": "first" }],
+                "notes": ["skipped"],
+                "elapsed_secs": 2,
+            })
+        );
+
+        let report = CheckRun::new(Check::Headers, 1).report(Ok(()), Duration::ZERO);
+        assert_eq!(report.status, CheckStatus::Passed);
+        let report = CheckRun::new(Check::Headers, 1)
+            .report(Err(eyre::eyre!("missing table")), Duration::ZERO);
+        assert_eq!(report.status, CheckStatus::Errored);
+        assert_eq!(report.error.as_deref(), Some("missing table"));
+    }
+}
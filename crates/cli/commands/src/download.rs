@@ -1,22 +1,31 @@
-use crate::common::EnvironmentArgs;
+use crate::{
+    common::EnvironmentArgs,
+    snapshot::{ArchiveManifest, HashingWriter, SnapshotArchive},
+};
+use alloy_primitives::Address;
 use clap::Parser;
 use eyre::Result;
 use lz4::Decoder;
 use reqwest::{blocking::Client as BlockingClient, header::RANGE, Client, StatusCode};
 use hanzo_evm_chainspec::{EthChainSpec, EthereumHardforks};
 use hanzo_evm_cli::chainspec::ChainSpecParser;
+use hanzo_evm_db_api::models::StorageSnapshotManifest;
 use hanzo_evm_fs_util as fs;
 use std::{
     borrow::Cow,
     fs::OpenOptions,
     io::{self, BufWriter, Read, Write},
-    path::{Path, PathBuf},
-    sync::{Arc, OnceLock},
+    path::{Component, Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, OnceLock,
+    },
+    thread,
     time::{Duration, Instant},
 };
 use tar::Archive;
 use tokio::task;
-use tracing::info;
+use tracing::{info, warn};
 use url::Url;
 use zstd::stream::read::Decoder as ZstdDecoder;
 
@@ -24,6 +33,8 @@ const BYTE_UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
 const MERKLE_BASE_URL: &str = "https://downloads.merkle.io";
 const EXTENSION_TAR_LZ4: &str = ".tar.lz4";
 const EXTENSION_TAR_ZSTD: &str = ".tar.zst";
+/// Directory inside the datadir that archives of an [`ArchiveManifest`] are downloaded to.
+const ARCHIVE_DOWNLOAD_DIR: &str = ".snapshot-download";
 
 /// Global static download defaults
 static DOWNLOAD_DEFAULTS: OnceLock<DownloadDefaults> = OnceLock::new();
@@ -99,7 +110,9 @@ impl DownloadDefaults {
             self.default_chain_aware_base_url.as_deref().unwrap_or(&self.default_base_url),
         );
         help.push_str(
-            ".\n\nLocal file:// URLs are also supported for extracting snapshots from disk.",
+            ".\n\nLocal file:// URLs are also supported for extracting snapshots from disk, or for \
+             restoring a snapshot directory created by `evm db snapshot`.\n\nURLs of an \
+             `archives.json` manifest created by `evm snapshot` download its archives in parallel.",
         );
         help
     }
@@ -149,6 +162,20 @@ pub struct DownloadCommand<C: ChainSpecParser> {
     /// Custom URL to download the snapshot from
     #[arg(long, short, long_help = DownloadDefaults::get_global().long_help())]
     url: Option<String>,
+
+    /// Number of archives to download in parallel when the URL points to an archive manifest.
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u64).range(1..))]
+    concurrency: u64,
+
+    /// Address that must have signed the archive manifest.
+    ///
+    /// Required when the URL points to an archive manifest, unless `--insecure` is given.
+    #[arg(long, value_name = "ADDRESS")]
+    snapshot_signer: Option<Address>,
+
+    /// Download the archives of an archive manifest without checking its signature.
+    #[arg(long, conflicts_with = "snapshot_signer")]
+    insecure: bool,
 }
 
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> DownloadCommand<C> {
@@ -172,7 +199,14 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> DownloadCo
             "Starting snapshot download and extraction"
         );
 
-        stream_and_extract(&url, data_dir.data_dir()).await?;
+        if is_archive_manifest_url(&url) {
+            let target_dir = data_dir.data_dir().to_path_buf();
+            let (concurrency, signer) = (self.concurrency as usize, self.manifest_signer()?);
+            task::spawn_blocking(move || download_archives(&url, &target_dir, concurrency, signer))
+                .await??;
+        } else {
+            stream_and_extract(&url, data_dir.data_dir()).await?;
+        }
         info!(target: "evm::cli", "Snapshot downloaded and extracted successfully");
 
         Ok(())
@@ -184,6 +218,18 @@ impl<C: ChainSpecParser> DownloadCommand<C> {
     pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
         Some(&self.env.chain)
     }
+
+    /// Returns the address that must have signed an archive manifest, or `None` if `--insecure`
+    /// skips the signature check.
+    fn manifest_signer(&self) -> Result<Option<Address>> {
+        match self.snapshot_signer {
+            Some(signer) => Ok(Some(signer)),
+            None if self.insecure => Ok(None),
+            None => eyre::bail!(
+                "Pass the manifest signer with --snapshot-signer or skip the check with --insecure"
+            ),
+        }
+    }
 }
 
 // Monitor process status and display progress every 100ms
@@ -193,13 +239,15 @@ struct DownloadProgress {
     total_size: u64,
     last_displayed: Instant,
     started_at: Instant,
+    /// Whether to skip displaying progress, e.g. when several downloads run in parallel.
+    quiet: bool,
 }
 
 impl DownloadProgress {
     /// Creates new progress tracker with given total size
     fn new(total_size: u64) -> Self {
         let now = Instant::now();
-        Self { downloaded: 0, total_size, last_displayed: now, started_at: now }
+        Self { downloaded: 0, total_size, last_displayed: now, started_at: now, quiet: false }
     }
 
     /// Converts bytes to human readable format (B, KB, MB, GB)
@@ -232,7 +280,7 @@ impl DownloadProgress {
         self.downloaded += chunk_size;
 
         // Only update display at most 10 times per second for efficiency
-        if self.last_displayed.elapsed() >= Duration::from_millis(100) {
+        if !self.quiet && self.last_displayed.elapsed() >= Duration::from_millis(100) {
             let formatted_downloaded = Self::format_size(self.downloaded);
             let formatted_total = Self::format_size(self.total_size);
             let progress = (self.downloaded as f64 / self.total_size as f64) * 100.0;
@@ -271,8 +319,10 @@ struct ProgressReader<R> {
 }
 
 impl<R: Read> ProgressReader<R> {
-    fn new(reader: R, total_size: u64) -> Self {
-        Self { reader, progress: DownloadProgress::new(total_size) }
+    fn new(reader: R, total_size: u64, quiet: bool) -> Self {
+        let mut progress = DownloadProgress::new(total_size);
+        progress.quiet = quiet;
+        Self { reader, progress }
     }
 }
 
@@ -320,8 +370,9 @@ fn extract_archive<R: Read>(
     total_size: u64,
     format: CompressionFormat,
     target_dir: &Path,
+    quiet: bool,
 ) -> Result<()> {
-    let progress_reader = ProgressReader::new(reader, total_size);
+    let progress_reader = ProgressReader::new(reader, total_size, quiet);
 
     match format {
         CompressionFormat::Lz4 => {
@@ -334,7 +385,9 @@ fn extract_archive<R: Read>(
         }
     }
 
-    info!(target: "evm::cli", "Extraction complete.");
+    if !quiet {
+        info!(target: "evm::cli", "Extraction complete.");
+    }
     Ok(())
 }
 
@@ -342,7 +395,79 @@ fn extract_archive<R: Read>(
 fn extract_from_file(path: &Path, format: CompressionFormat, target_dir: &Path) -> Result<()> {
     let file = std::fs::File::open(path)?;
     let total_size = file.metadata()?.len();
-    extract_archive(file, total_size, format, target_dir)
+    extract_archive(file, total_size, format, target_dir, false)
+}
+
+/// Restores a snapshot directory created by `evm db snapshot` or `admin_createSnapshot`.
+///
+/// Copies every file listed in the snapshot manifest into `target_dir`, checking its size and
+/// hash against the manifest. If any file doesn't match, the restored files are removed again.
+fn restore_from_snapshot_dir(snapshot_dir: &Path, target_dir: &Path) -> Result<()> {
+    let manifest: StorageSnapshotManifest =
+        fs::read_json_file(&snapshot_dir.join(StorageSnapshotManifest::FILE_NAME))?;
+    eyre::ensure!(
+        manifest.version == StorageSnapshotManifest::VERSION,
+        "Unsupported snapshot manifest version {}, expected {}",
+        manifest.version,
+        StorageSnapshotManifest::VERSION
+    );
+    eyre::ensure!(
+        !target_dir.join("db").exists(),
+        "Database already exists in {}, refusing to overwrite it",
+        target_dir.display()
+    );
+
+    let total_size = manifest.files.iter().map(|file| file.size).sum();
+    let mut progress = DownloadProgress::new(total_size);
+    info!(target: "evm::cli",
+        block_number = manifest.block_number,
+        block_hash = %manifest.block_hash,
+        files = manifest.files.len(),
+        "Restoring snapshot"
+    );
+
+    let mut restored = Vec::with_capacity(manifest.files.len());
+    let result = manifest.files.iter().try_for_each(|file| {
+        let src = snapshot_dir.join(&file.path);
+        let size = fs::metadata(&src)?.len();
+        eyre::ensure!(
+            size == file.size,
+            "Snapshot file {} has size {size}, expected {}",
+            file.path,
+            file.size
+        );
+
+        let dest = target_dir.join(&file.path);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        restored.push(dest.clone());
+        let mut writer = HashingWriter::new(BufWriter::new(fs::create_file(&dest)?));
+        io::copy(&mut fs::open(&src)?, &mut writer)?;
+        writer.flush()?;
+        let (_, hash) = writer.finalize();
+        eyre::ensure!(
+            hash == file.hash,
+            "Snapshot file {} has hash {hash}, expected {}",
+            file.path,
+            file.hash
+        );
+
+        progress.update(size)
+    });
+    if let Err(err) = result {
+        for path in restored {
+            let _ = std::fs::remove_file(&path);
+            // only succeeds once a directory is empty
+            for dir in path.ancestors().skip(1).take_while(|dir| *dir != target_dir) {
+                let _ = std::fs::remove_dir(dir);
+            }
+        }
+        return Err(err)
+    }
+
+    info!(target: "evm::cli", "Restore complete.");
+    Ok(())
 }
 
 const MAX_DOWNLOAD_RETRIES: u32 = 10;
@@ -370,7 +495,7 @@ impl<W: Write> Write for ProgressWriter<W> {
 /// Downloads a file with resume support using HTTP Range requests.
 /// Automatically retries on failure, resuming from where it left off.
 /// Returns the path to the downloaded file and its total size.
-fn resumable_download(url: &str, target_dir: &Path) -> Result<(PathBuf, u64)> {
+fn resumable_download(url: &str, target_dir: &Path, quiet: bool) -> Result<(PathBuf, u64)> {
     let file_name = Url::parse(url)
         .ok()
         .and_then(|u| u.path_segments()?.next_back().map(|s| s.to_string()))
@@ -461,13 +586,16 @@ fn resumable_download(url: &str, target_dir: &Path) -> Result<(PathBuf, u64)> {
         let start_offset = if is_partial { existing_size } else { 0 };
         let mut progress = DownloadProgress::new(current_total);
         progress.downloaded = start_offset;
+        progress.quiet = quiet;
 
         let mut writer = ProgressWriter { inner: BufWriter::new(file), progress };
         let mut reader = response;
 
         let copy_result = io::copy(&mut reader, &mut writer);
         let flush_result = writer.inner.flush();
-        println!();
+        if !quiet {
+            println!();
+        }
 
         if let Err(e) = copy_result.and(flush_result) {
             last_error = Some(e.into());
@@ -489,11 +617,11 @@ fn resumable_download(url: &str, target_dir: &Path) -> Result<(PathBuf, u64)> {
 
 /// Fetches the snapshot from a remote URL with resume support, then extracts it.
 fn download_and_extract(url: &str, format: CompressionFormat, target_dir: &Path) -> Result<()> {
-    let (downloaded_path, total_size) = resumable_download(url, target_dir)?;
+    let (downloaded_path, total_size) = resumable_download(url, target_dir, false)?;
 
     info!(target: "evm::cli", "Extracting snapshot...");
     let file = fs::open(&downloaded_path)?;
-    extract_archive(file, total_size, format, target_dir)?;
+    extract_archive(file, total_size, format, target_dir, false)?;
 
     fs::remove_file(&downloaded_path)?;
     info!(target: "evm::cli", "Removed downloaded archive");
@@ -505,20 +633,202 @@ fn download_and_extract(url: &str, format: CompressionFormat, target_dir: &Path)
 ///
 /// Supports both `file://` URLs for local files and HTTP(S) URLs for remote downloads.
 fn blocking_download_and_extract(url: &str, target_dir: &Path) -> Result<()> {
-    let format = CompressionFormat::from_url(url)?;
-
     if let Ok(parsed_url) = Url::parse(url) &&
         parsed_url.scheme() == "file"
     {
         let file_path = parsed_url
             .to_file_path()
             .map_err(|_| eyre::eyre!("Invalid file:// URL path: {}", url))?;
+        if file_path.join(StorageSnapshotManifest::FILE_NAME).is_file() {
+            return restore_from_snapshot_dir(&file_path, target_dir)
+        }
+
+        let format = CompressionFormat::from_url(url)?;
         extract_from_file(&file_path, format, target_dir)
     } else {
+        let format = CompressionFormat::from_url(url)?;
         download_and_extract(url, format, target_dir)
     }
 }
 
+/// Returns `true` if the URL points to an [`ArchiveManifest`].
+fn is_archive_manifest_url(url: &str) -> bool {
+    Url::parse(url)
+        .map(|u| u.path().to_string())
+        .unwrap_or_else(|_| url.to_string())
+        .ends_with(".json")
+}
+
+/// Fetches the [`ArchiveManifest`] at the given URL.
+fn fetch_archive_manifest(url: &Url) -> Result<ArchiveManifest> {
+    if url.scheme() == "file" {
+        let path =
+            url.to_file_path().map_err(|_| eyre::eyre!("Invalid file:// URL path: {}", url))?;
+        return Ok(fs::read_json_file(&path)?)
+    }
+
+    let client = BlockingClient::builder().timeout(Duration::from_secs(30)).build()?;
+    let bytes = client.get(url.clone()).send()?.error_for_status()?.bytes()?;
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+/// Downloads the archives listed in the [`ArchiveManifest`] at `manifest_url` and extracts them
+/// into `target_dir`.
+///
+/// Archives are resolved relative to the manifest URL and fetched by `concurrency` workers, each
+/// resuming interrupted downloads. Every archive is checked against the size and hash of the
+/// manifest before it is extracted. Extracted archives are recorded, so an interrupted run only
+/// fetches the remaining ones. If `signer` is set, the manifest must be signed by it, otherwise
+/// the signature is not checked.
+pub(crate) fn download_archives(
+    manifest_url: &str,
+    target_dir: &Path,
+    concurrency: usize,
+    signer: Option<Address>,
+) -> Result<()> {
+    let manifest_url = Url::parse(manifest_url)?;
+    let manifest = fetch_archive_manifest(&manifest_url)?;
+    eyre::ensure!(
+        manifest.version == ArchiveManifest::VERSION,
+        "Unsupported archive manifest version {}, expected {}",
+        manifest.version,
+        ArchiveManifest::VERSION
+    );
+    if let Some(expected) = signer {
+        let recovered = manifest.recover_signer()?;
+        eyre::ensure!(
+            recovered == expected,
+            "Archive manifest is signed by {recovered}, expected {expected}"
+        );
+    } else {
+        warn!(target: "evm::cli", "Skipping the signature check of the archive manifest");
+    }
+
+    let download_dir = target_dir.join(ARCHIVE_DOWNLOAD_DIR);
+    fs::create_dir_all(&download_dir)?;
+    info!(target: "evm::cli",
+        block_number = manifest.block_number,
+        block_hash = %manifest.block_hash,
+        archives = manifest.archives.len(),
+        concurrency,
+        "Downloading snapshot archives"
+    );
+
+    let next = AtomicUsize::new(0);
+    let done = AtomicUsize::new(0);
+    thread::scope(|scope| {
+        let handles = (0..concurrency.min(manifest.archives.len()).max(1))
+            .map(|_| {
+                scope.spawn(|| {
+                    while let Some(archive) =
+                        manifest.archives.get(next.fetch_add(1, Ordering::Relaxed))
+                    {
+                        fetch_archive(&manifest_url, archive, &download_dir, target_dir)?;
+                        info!(target: "evm::cli",
+                            archive = %archive.name,
+                            done = done.fetch_add(1, Ordering::Relaxed) + 1,
+                            total = manifest.archives.len(),
+                            "Archive extracted"
+                        );
+                    }
+                    Ok::<_, eyre::Report>(())
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles.into_iter().try_for_each(|handle| {
+            handle.join().map_err(|_| eyre::eyre!("Download worker panicked"))?
+        })
+    })?;
+
+    fs::remove_dir_all(&download_dir)?;
+    Ok(())
+}
+
+/// Downloads, verifies and extracts a single archive of an [`ArchiveManifest`].
+fn fetch_archive(
+    manifest_url: &Url,
+    archive: &SnapshotArchive,
+    download_dir: &Path,
+    target_dir: &Path,
+) -> Result<()> {
+    // the name is joined to the manifest URL and the download directory, so it must not be able
+    // to point anywhere else
+    let mut components = Path::new(&archive.name).components();
+    eyre::ensure!(
+        matches!(
+            (components.next(), components.next()),
+            (Some(Component::Normal(name)), None) if name == archive.name.as_str()
+        ),
+        "Invalid archive name {:?}, expected a plain file name",
+        archive.name
+    );
+
+    // the marker records the hash of the extracted archive, an archive that changed in the
+    // manifest since then is fetched again
+    let done_marker = download_dir.join(format!("{}.done", archive.name));
+    if done_marker.exists() {
+        if fs::read_to_string(&done_marker)?.trim() == archive.hash.to_string() {
+            return Ok(())
+        }
+        warn!(target: "evm::cli",
+            archive = %archive.name,
+            "Archive changed since it was extracted, fetching it again"
+        );
+        fs::remove_file(&done_marker)?;
+    }
+
+    let url = manifest_url.join(&archive.name)?;
+    let (path, is_local) = if url.scheme() == "file" {
+        let path =
+            url.to_file_path().map_err(|_| eyre::eyre!("Invalid file:// URL path: {}", url))?;
+        (path, true)
+    } else {
+        let path = download_dir.join(&archive.name);
+        if path.exists() && verify_archive(&path, archive).is_ok() {
+            (path, false)
+        } else {
+            (resumable_download(url.as_str(), download_dir, true)?.0, false)
+        }
+    };
+
+    if let Err(err) = verify_archive(&path, archive) {
+        if !is_local {
+            fs::remove_file(&path)?;
+        }
+        return Err(err)
+    }
+
+    extract_archive(fs::open(&path)?, archive.size, CompressionFormat::Zstd, target_dir, true)?;
+    if !is_local {
+        fs::remove_file(&path)?;
+    }
+    fs::write(&done_marker, archive.hash.to_string())?;
+
+    Ok(())
+}
+
+/// Checks the size and hash of a downloaded archive against its manifest entry.
+fn verify_archive(path: &Path, archive: &SnapshotArchive) -> Result<()> {
+    let mut writer = HashingWriter::new(io::sink());
+    io::copy(&mut fs::open(path)?, &mut writer)?;
+    let (size, hash) = writer.finalize();
+
+    eyre::ensure!(
+        size == archive.size,
+        "Size mismatch for archive {}: got {size}, expected {}",
+        archive.name,
+        archive.size
+    );
+    eyre::ensure!(
+        hash == archive.hash,
+        "Hash mismatch for archive {}: got {hash}, expected {}",
+        archive.name,
+        archive.hash
+    );
+    Ok(())
+}
+
 async fn stream_and_extract(url: &str, target_dir: &Path) -> Result<()> {
     let target_dir = target_dir.to_path_buf();
     let url = url.to_string();
@@ -551,6 +861,69 @@ async fn get_latest_snapshot_url(chain_id: u64) -> Result<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::{fixture_chain, hashed_state_root, read_chain};
+    use alloy_consensus::BlockHeader;
+    use hanzo_evm_chainspec::ChainSpecProvider;
+    use hanzo_evm_ethereum_cli::chainspec::EthereumChainSpecParser;
+    use hanzo_evm_provider::{
+        providers::{ProviderFactoryBuilder, ReadOnlyConfig},
+        test_utils::{MockNodeTypes, MockNodeTypesWithDB},
+        BlockNumReader, HeaderProvider, ProviderFactory, StorageSettings,
+    };
+    use hanzo_evm_storage_api::StorageSnapshotProvider;
+
+    /// Creates a snapshot of a fixture chain with `tip` blocks in `dir`.
+    fn create_snapshot(dir: &Path, tip: u64) -> ProviderFactory<MockNodeTypesWithDB> {
+        let provider_factory = fixture_chain(tip, StorageSettings::base()).unwrap();
+        provider_factory.create_storage_snapshot(dir).unwrap();
+        provider_factory
+    }
+
+    #[test]
+    fn restores_snapshot_dir() {
+        let tip = 3;
+        let (snapshot, target) = (tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap());
+        let snapshot_dir = snapshot.path().join("snapshot");
+        let provider_factory = create_snapshot(&snapshot_dir, tip);
+
+        restore_from_snapshot_dir(&snapshot_dir, target.path()).unwrap();
+
+        let restored = ProviderFactoryBuilder::<MockNodeTypes>::default()
+            .open_read_only(
+                provider_factory.chain_spec(),
+                ReadOnlyConfig::from_datadir(target.path()).no_watch(),
+                reth_tasks::Runtime::test(),
+            )
+            .unwrap();
+        let provider = restored.provider().unwrap();
+        assert_eq!(provider.best_block_number().unwrap(), tip);
+        assert_eq!(
+            hashed_state_root(&provider).unwrap(),
+            provider.header_by_number(tip).unwrap().unwrap().state_root()
+        );
+        assert_eq!(
+            read_chain(&restored, tip).unwrap(),
+            read_chain(&provider_factory, tip).unwrap()
+        );
+    }
+
+    #[test]
+    fn restore_rejects_corrupted_file() {
+        let (snapshot, target) = (tempfile::tempdir().unwrap(), tempfile::tempdir().unwrap());
+        let snapshot_dir = snapshot.path().join("snapshot");
+        create_snapshot(&snapshot_dir, 1);
+
+        // flip a byte without changing the size
+        let path = snapshot_dir.join("db/mdbx.dat");
+        let mut bytes = std::fs::read(&path).unwrap();
+        bytes[0] ^= 0xff;
+        std::fs::write(&path, bytes).unwrap();
+
+        let err = restore_from_snapshot_dir(&snapshot_dir, target.path()).unwrap_err();
+        assert!(err.to_string().contains("Snapshot file db/mdbx.dat has hash"), "{err}");
+        // nothing is left behind that would block restoring again
+        assert!(!target.path().join("db").exists());
+    }
 
     #[test]
     fn test_download_defaults_builder() {
@@ -627,4 +1000,23 @@ mod tests {
         ));
         assert!(CompressionFormat::from_url("https://example.com/snapshot.tar.gz").is_err());
     }
+
+    #[test]
+    fn archive_manifest_signer_is_required() {
+        let parse = |args: &[&str]| {
+            DownloadCommand::<EthereumChainSpecParser>::try_parse_from(
+                ["evm", "--url", "https://example.com/archives.json"].iter().chain(args),
+            )
+        };
+        let signer = Address::repeat_byte(1);
+
+        let err = parse(&[]).unwrap().manifest_signer().unwrap_err();
+        assert!(err.to_string().contains("--snapshot-signer"), "{err}");
+        assert_eq!(parse(&["--insecure"]).unwrap().manifest_signer().unwrap(), None);
+        assert_eq!(
+            parse(&["--snapshot-signer", &signer.to_string()]).unwrap().manifest_signer().unwrap(),
+            Some(signer)
+        );
+        assert!(parse(&["--insecure", "--snapshot-signer", &signer.to_string()]).is_err());
+    }
 }
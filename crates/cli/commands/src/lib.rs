@@ -9,6 +9,7 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
 pub mod common;
+pub mod compare_state_roots;
 pub mod config_cmd;
 pub mod db;
 pub mod download;
@@ -24,7 +25,11 @@ pub mod node;
 pub mod p2p;
 pub mod prune;
 pub mod re_execute;
+pub mod reader;
+pub mod snapshot;
 pub mod stage;
+#[cfg(test)]
+pub(crate) mod test_utils;
 #[cfg(feature = "arbitrary")]
 pub mod test_vectors;
 
@@ -0,0 +1,707 @@
+//! Command that serves RPC requests from the storage of a running primary node.
+//!
+//! A reader opens the database, static files and `RocksDB` of a datadir owned by a running
+//! primary node read-only, and serves the `eth`, `debug` and `trace` namespaces from them. It does
+//! not sync, execute or persist blocks by itself. Instead, it subscribes to the persisted block
+//! notifications of the primary and advances its canonical head whenever the primary persists new
+//! blocks. This allows scaling reads on one machine by running several readers next to a single
+//! primary.
+//!
+//! If the subscription fails, e.g. because the primary restarted, the reader reconnects and
+//! resynchronizes its canonical head with the storage. Blocks unwound by the primary are reported
+//! to subscriptions as a reorg once the primary persists new blocks on top of the unwind target.
+//!
+//! Limitations:
+//! - Only blocks persisted by the primary are visible, so the reader lags behind the in-memory
+//!   canonical head of the primary.
+//! - The transaction pool and the network are not available, so transactions can't be submitted
+//!   through a reader and pending block queries return the latest persisted block.
+//! - Only the last [`MAX_REORG_DEPTH`] blocks committed since the reader started are known to it,
+//!   so older blocks unwound by the primary are missing from the reverted chain of a reorg.
+//! - If the head advances by more than [`MAX_NOTIFIED_BLOCKS`] blocks at once, subscriptions are
+//!   not notified of the new blocks.
+
+use crate::common::{
+    AccessRights, CliComponentsBuilder, CliNodeComponents, CliNodeTypes, ConsensusFor, Environment,
+    EnvironmentArgs, EvmFor,
+};
+use alloy_consensus::BlockHeader;
+use alloy_eips::BlockNumHash;
+use alloy_network::Ethereum;
+use alloy_primitives::BlockNumber;
+use backon::{ExponentialBuilder, Retryable};
+use clap::Parser;
+use futures::StreamExt;
+use hanzo_evm_chainspec::EthChainSpec;
+use hanzo_evm_cli::chainspec::ChainSpecParser;
+use hanzo_evm_cli_runner::CliContext;
+use hanzo_evm_consensus::FullConsensus;
+use hanzo_evm_db::DatabaseEnv;
+use hanzo_evm_ipc::client::IpcClientBuilder;
+use hanzo_evm_network_api::noop::NoopNetwork;
+use hanzo_evm_node_builder::{NodeTypes, NodeTypesWithDBAdapter};
+use hanzo_evm_node_core::args::RpcServerArgs;
+use hanzo_evm_primitives_traits::{NodePrimitives, SealedHeader};
+use hanzo_evm_provider::{
+    providers::{BlockchainProvider, ProviderNodeTypes},
+    BlockHashReader, BlockNumReader, BlockReader, CanonStateNotification, Chain, ChainSpecProvider,
+    ChainStateBlockReader, DatabaseProviderFactory, DatabaseProviderRO, HeaderProvider,
+    ProviderError, ProviderResult, RocksDBProviderFactory, StaticFileProviderFactory,
+};
+use hanzo_evm_rpc::EthApi;
+use hanzo_evm_rpc_api::RethApiClient;
+use hanzo_evm_rpc_builder::{
+    config::EvmRpcServerConfig, constants::DEFAULT_IPC_ENDPOINT, EvmRpcModule, RpcModuleBuilder,
+    RpcModuleSelection, TransportRpcModuleConfig, TransportRpcModules,
+};
+use hanzo_evm_rpc_eth_api::{
+    helpers::pending_block::PendingEnvBuilder, node::RpcNodeCoreAdapter, FullEthApiServer,
+    RpcConvert, RpcConverter, RpcNodeCore,
+};
+use hanzo_evm_rpc_eth_types::{receipt::EthReceiptConverter, EthConfig};
+use hanzo_evm_transaction_pool::noop::NoopTransactionPool;
+use std::{
+    collections::{BTreeMap, VecDeque},
+    iter,
+    sync::Arc,
+    time::Duration,
+};
+use tracing::{debug, info, warn};
+
+/// The RPC namespaces a reader can serve.
+const READER_MODULES: [EvmRpcModule; 3] =
+    [EvmRpcModule::Eth, EvmRpcModule::Debug, EvmRpcModule::Trace];
+
+/// Maximum delay between attempts to subscribe to the primary node.
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(10);
+
+/// Minimum number of committed blocks kept to report them as reverted when the primary unwinds
+/// them.
+const MAX_REORG_DEPTH: usize = 64;
+
+/// Maximum number of blocks loaded for a single canonical state notification.
+const NOTIFICATION_BATCH_SIZE: u64 = 64;
+
+/// Maximum number of blocks the head advances by with notifications. Subscriptions aren't
+/// notified of larger advances, e.g. after the reader was disconnected for a long time.
+const MAX_NOTIFIED_BLOCKS: u64 = 1024;
+
+/// `evm reader` command
+///
+/// Serves the `eth`, `debug` and `trace` RPC namespaces from the datadir of a running primary node.
+#[derive(Debug, Parser)]
+pub struct ReaderCommand<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// All rpc related arguments.
+    ///
+    /// Only the `eth`, `debug` and `trace` namespaces can be selected, which is also the default.
+    #[command(flatten)]
+    rpc: RpcServerArgs,
+
+    /// IPC endpoint of the primary node owning the datadir.
+    ///
+    /// The reader subscribes to the persisted block notifications of the primary on this endpoint.
+    #[arg(long = "reader.primary-ipc", value_name = "PATH", default_value = DEFAULT_IPC_ENDPOINT)]
+    primary_ipc: String,
+}
+
+impl<C: ChainSpecParser> ReaderCommand<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
+
+impl<C: ChainSpecParser> ReaderCommand<C> {
+    /// Execute `reader` command
+    ///
+    /// The served RPC modules are built with `rpc_modules`, e.g.
+    /// [`ReaderNodeTypes::reader_rpc_modules`].
+    pub async fn execute<N>(
+        mut self,
+        ctx: CliContext,
+        components: impl CliComponentsBuilder<N>,
+        rpc_modules: ReaderRpcModules<N>,
+    ) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+    {
+        self.rpc.http_api = Some(reader_modules(self.rpc.http_api.take(), "http.api")?);
+        self.rpc.ws_api = Some(reader_modules(self.rpc.ws_api.take(), "ws.api")?);
+        if self.rpc.is_ipc_enabled() && self.rpc.ipc_path() == self.primary_ipc {
+            eyre::bail!(
+                "IPC endpoint {} is used by the primary node, set a different --ipcpath or \
+                 --ipcdisable",
+                self.primary_ipc
+            )
+        }
+
+        let mut module_config = self.rpc.transport_rpc_module_config();
+        if self.rpc.is_ipc_enabled() {
+            module_config = module_config.with_ipc(READER_MODULES);
+        }
+        if module_config.is_empty() {
+            eyre::bail!("No RPC transport enabled, set --http, --ws or an --ipcpath")
+        }
+
+        let Environment { provider_factory, .. } =
+            self.env.init::<N>(AccessRights::Secondary, ctx.task_executor.clone())?;
+        let components = components(provider_factory.chain_spec());
+        let provider = BlockchainProvider::new(provider_factory)?;
+
+        let modules = rpc_modules(
+            provider.clone(),
+            &components,
+            module_config,
+            self.rpc.eth_config(),
+            ctx.task_executor.clone(),
+        );
+        let _handle = self.rpc.rpc_server_config().start(&modules).await?;
+        info!(
+            target: "evm::cli",
+            chain = %provider.chain_spec().chain(),
+            head = ?provider.canonical_in_memory_state().get_canonical_head().num_hash(),
+            primary = %self.primary_ipc,
+            "Reader started"
+        );
+
+        let primary_ipc = &self.primary_ipc;
+        let subscribe = move || async move {
+            let client = IpcClientBuilder::default().build(primary_ipc).await?;
+            let persisted_blocks = RethApiClient::reth_subscribe_persisted_block(&client).await?;
+            Ok::<_, eyre::Report>((client, persisted_blocks))
+        };
+
+        let mut follower = ChainFollower::default();
+        loop {
+            let backoff = ExponentialBuilder::default()
+                .with_max_delay(MAX_RECONNECT_DELAY)
+                .without_max_times();
+            let (_client, mut persisted_blocks) = subscribe
+                .retry(backoff)
+                .notify(|err, delay| {
+                    warn!(target: "evm::cli", %err, ?delay, %primary_ipc, "Failed to subscribe to the primary node, retrying")
+                })
+                .await?;
+
+            // The primary may have persisted or unwound blocks while the reader was not subscribed.
+            follower.resync(&provider)?;
+
+            while let Some(persisted) = persisted_blocks.next().await {
+                match persisted {
+                    Ok(persisted) => {
+                        debug!(target: "evm::cli", ?persisted, "Primary persisted block");
+                        follower.on_persisted_block(&provider, persisted)?;
+                    }
+                    Err(err) => {
+                        warn!(target: "evm::cli", %err, "Failed to decode persisted block notification");
+                        break
+                    }
+                }
+            }
+            warn!(target: "evm::cli", %primary_ipc, "Lost the subscription to the primary node, reconnecting");
+        }
+    }
+}
+
+/// Returns the RPC modules to serve on a transport, defaulting to all namespaces a reader can
+/// serve.
+fn reader_modules(
+    selection: Option<RpcModuleSelection>,
+    arg_name: &str,
+) -> eyre::Result<RpcModuleSelection> {
+    let Some(selection) = selection else { return Ok(READER_MODULES.into()) };
+    if let Some(module) = selection.iter_selection().find(|module| !READER_MODULES.contains(module))
+    {
+        eyre::bail!("--{arg_name}: the {module} namespace can't be served by a reader")
+    }
+    Ok(selection)
+}
+
+/// Makes the data the primary node persisted so far visible to the provider.
+fn catch_up<P: ProviderNodeTypes>(provider: &BlockchainProvider<P>) -> ProviderResult<()> {
+    provider.static_file_provider().initialize_index()?;
+    provider.rocksdb_provider().try_catch_up_with_primary()
+}
+
+/// Follows the chain persisted by the primary node, advancing the canonical head of the reader
+/// and notifying its canonical state subscriptions.
+#[derive(Debug)]
+struct ChainFollower<N: NodePrimitives> {
+    /// The most recently committed chains, oldest first, kept to report their blocks as reverted
+    /// if the primary unwinds them.
+    committed: VecDeque<Arc<Chain<N>>>,
+    /// Blocks unwound by the primary, reported once it persists new blocks.
+    reverted: Option<Chain<N>>,
+    /// Maximum number of blocks loaded for a single notification.
+    batch_size: u64,
+    /// Maximum number of blocks the head advances by with notifications.
+    max_notified_blocks: u64,
+}
+
+impl<N: NodePrimitives> Default for ChainFollower<N> {
+    fn default() -> Self {
+        Self {
+            committed: VecDeque::new(),
+            reverted: None,
+            batch_size: NOTIFICATION_BATCH_SIZE,
+            max_notified_blocks: MAX_NOTIFIED_BLOCKS,
+        }
+    }
+}
+
+impl<N: NodePrimitives> ChainFollower<N> {
+    /// Catches up with the storage of the primary node and advances the canonical head to the
+    /// block the primary persisted, unless it was already replaced.
+    fn on_persisted_block<P>(
+        &mut self,
+        provider: &BlockchainProvider<P>,
+        persisted: BlockNumHash,
+    ) -> ProviderResult<()>
+    where
+        P: ProviderNodeTypes<Primitives = N>,
+    {
+        catch_up(provider)?;
+
+        let db_provider = provider.database_provider_ro()?;
+        let Some(header) = db_provider.sealed_header(persisted.number)? else {
+            warn!(target: "evm::cli", ?persisted, "Persisted block not found in storage");
+            return Ok(())
+        };
+        if header.hash() != persisted.hash {
+            // The primary already unwound or replaced the block, a later notification will follow.
+            debug!(target: "evm::cli", ?persisted, "Persisted block was replaced");
+            return Ok(())
+        }
+
+        self.advance(provider, &db_provider, header)
+    }
+
+    /// Catches up with the storage of the primary node and advances the canonical head to the
+    /// best block in storage.
+    fn resync<P>(&mut self, provider: &BlockchainProvider<P>) -> ProviderResult<()>
+    where
+        P: ProviderNodeTypes<Primitives = N>,
+    {
+        catch_up(provider)?;
+
+        let db_provider = provider.database_provider_ro()?;
+        let best_block = db_provider.best_block_number()?;
+        let header = db_provider
+            .sealed_header(best_block)?
+            .ok_or(ProviderError::HeaderNotFound(best_block.into()))?;
+
+        self.advance(provider, &db_provider, header)
+    }
+
+    /// Advances the canonical head to `header`, notifying subscriptions of the blocks committed
+    /// and reverted since the previous head.
+    ///
+    /// The committed blocks are loaded and notified in batches. If there are more than
+    /// `max_notified_blocks` of them, only the head is moved and the known blocks are forgotten.
+    fn advance<P>(
+        &mut self,
+        provider: &BlockchainProvider<P>,
+        db_provider: &DatabaseProviderRO<P::DB, P>,
+        header: SealedHeader<N::BlockHeader>,
+    ) -> ProviderResult<()>
+    where
+        P: ProviderNodeTypes<Primitives = N>,
+    {
+        let state = provider.canonical_in_memory_state();
+        let head = state.get_canonical_head();
+        let tip = header.num_hash();
+
+        if tip.hash != head.hash() {
+            let fork = self.fork_block(db_provider, head.num_hash(), tip.number)?;
+            if fork == head.number() && tip.number < fork {
+                // The head is still canonical, so the notification is outdated.
+                debug!(target: "evm::cli", ?tip, head = ?head.num_hash(), "Persisted block is below the head");
+                return Ok(())
+            }
+
+            self.revert_above(fork);
+            state.set_canonical_head(header);
+            state.set_persisted(tip);
+
+            if tip.number.saturating_sub(fork) > self.max_notified_blocks {
+                warn!(
+                    target: "evm::cli",
+                    old_head = ?head.num_hash(),
+                    new_head = ?tip,
+                    "Head advanced too far, skipping notifications of the new blocks"
+                );
+                self.committed.clear();
+                self.reverted = None;
+            } else if tip.number > fork {
+                for start in (fork + 1..=tip.number).step_by(self.batch_size as usize) {
+                    let range = start..=tip.number.min(start + self.batch_size - 1);
+                    let blocks = db_provider.recovered_block_range(range.clone())?;
+                    let Some(execution_outcome) = provider.get_state(range)? else { break };
+                    let chain = Chain::new(blocks, execution_outcome, BTreeMap::new());
+                    state.notify_canon_state(self.commit(chain));
+                }
+            } else {
+                info!(
+                    target: "evm::cli",
+                    old_head = ?head.num_hash(),
+                    new_head = ?tip,
+                    "Primary node unwound the chain"
+                );
+            }
+        }
+
+        if let Some(finalized) = db_provider.last_finalized_block_number()? &&
+            let Some(header) = db_provider.sealed_header(finalized)?
+        {
+            state.set_finalized(header);
+        }
+        if let Some(safe) = db_provider.last_safe_block_number()? &&
+            let Some(header) = db_provider.sealed_header(safe)?
+        {
+            state.set_safe(header);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the number of the highest block known to the follower that is still canonical in
+    /// storage, falling back to the parent of the oldest known block if none of them are.
+    fn fork_block(
+        &self,
+        provider: &impl BlockHashReader,
+        head: BlockNumHash,
+        tip: BlockNumber,
+    ) -> ProviderResult<BlockNumber> {
+        let known = iter::once(head).chain(
+            self.committed
+                .iter()
+                .rev()
+                .flat_map(|chain| chain.blocks().values().rev().map(|block| block.num_hash())),
+        );
+        for block in known {
+            if provider.block_hash(block.number)? == Some(block.hash) {
+                return Ok(block.number)
+            }
+        }
+
+        let oldest = self.committed.front().map_or(head.number, |chain| chain.fork_block().number);
+        Ok(oldest.min(tip))
+    }
+
+    /// Moves the committed blocks above `fork` to the reverted blocks.
+    fn revert_above(&mut self, fork: BlockNumber) {
+        // Reverted chains, newest first.
+        let mut reverted = Vec::new();
+        while let Some(chain) = self.committed.pop_back() {
+            if chain.tip().number() <= fork {
+                self.committed.push_back(chain);
+                break
+            }
+
+            let chain = Arc::unwrap_or_clone(chain);
+            if chain.first().number() > fork {
+                reverted.push(chain);
+                continue
+            }
+
+            // The chain forks off inside, so only its blocks above the fork block are reverted.
+            let (blocks, execution_outcome, mut trie_data) = chain.into_inner();
+            let (blocks, reverted_blocks): (Vec<_>, Vec<_>) =
+                blocks.into_blocks().partition(|block| block.number() <= fork);
+            let (execution_outcome, reverted_execution_outcome) =
+                execution_outcome.split_at(fork + 1);
+            let reverted_trie_data = trie_data.split_off(&(fork + 1));
+            self.committed.push_back(Arc::new(Chain::new(
+                blocks,
+                execution_outcome.expect("chain starts at or below the fork block"),
+                trie_data,
+            )));
+            reverted.push(Chain::new(
+                reverted_blocks,
+                reverted_execution_outcome,
+                reverted_trie_data,
+            ));
+            break
+        }
+
+        // Blocks reverted earlier are above the ones reverted now.
+        let mut chains = reverted.into_iter().rev().chain(self.reverted.take());
+        let Some(mut old) = chains.next() else { return };
+        for chain in chains {
+            if let Err(chain) = old.append_chain(chain) {
+                warn!(target: "evm::cli", range = ?chain.range(), "Reverted blocks are not connected");
+            }
+        }
+        self.reverted = Some(old);
+    }
+
+    /// Records the blocks newly committed by the primary, returning the notification reporting
+    /// them together with the blocks reverted since the last notification.
+    fn commit(&mut self, chain: Chain<N>) -> CanonStateNotification<N> {
+        let new = Arc::new(chain);
+        self.committed.push_back(new.clone());
+        while self.committed.iter().skip(1).map(|chain| chain.len()).sum::<usize>() >=
+            MAX_REORG_DEPTH
+        {
+            self.committed.pop_front();
+        }
+
+        match self.reverted.take() {
+            Some(old) => CanonStateNotification::Reorg { old: Arc::new(old), new },
+            None => CanonStateNotification::Commit { new },
+        }
+    }
+}
+
+/// The provider a reader serves RPC requests from.
+pub type ReaderProvider<N> = BlockchainProvider<NodeTypesWithDBAdapter<N, DatabaseEnv>>;
+
+/// The `eth` API served by a reader.
+pub type ReaderEthApi<N> = EthApi<
+    RpcNodeCoreAdapter<ReaderProvider<N>, NoopTransactionPool, NoopNetwork, EvmFor<N>>,
+    RpcConverter<Ethereum, EvmFor<N>, EthReceiptConverter<<N as NodeTypes>::ChainSpec>>,
+>;
+
+/// Builds the RPC modules a [`ReaderCommand`] serves over the given provider.
+pub type ReaderRpcModules<N> = fn(
+    ReaderProvider<N>,
+    &dyn CliNodeComponents<N>,
+    TransportRpcModuleConfig,
+    EthConfig,
+    reth_tasks::Runtime,
+) -> TransportRpcModules;
+
+/// Helper trait for the [`CliNodeTypes`] that can be served by a [`ReaderCommand`].
+pub trait ReaderNodeTypes: CliNodeTypes {
+    /// Builds the RPC modules of a reader over the given provider.
+    fn reader_rpc_modules(
+        provider: ReaderProvider<Self>,
+        components: &dyn CliNodeComponents<Self>,
+        module_config: TransportRpcModuleConfig,
+        eth_config: EthConfig,
+        runtime: reth_tasks::Runtime,
+    ) -> TransportRpcModules;
+}
+
+impl<N> ReaderNodeTypes for N
+where
+    N: CliNodeTypes,
+    ConsensusFor<N>: FullConsensus<N::Primitives> + Clone + 'static,
+    RpcNodeCoreAdapter<ReaderProvider<N>, NoopTransactionPool, NoopNetwork, EvmFor<N>>:
+        RpcNodeCore<Provider: ChainSpecProvider<ChainSpec = N::ChainSpec>, Evm = EvmFor<N>>,
+    RpcConverter<Ethereum, EvmFor<N>, EthReceiptConverter<N::ChainSpec>>: RpcConvert,
+    (): PendingEnvBuilder<EvmFor<N>>,
+    ReaderEthApi<N>: FullEthApiServer<Provider = ReaderProvider<N>, Pool = NoopTransactionPool>,
+{
+    fn reader_rpc_modules(
+        provider: ReaderProvider<Self>,
+        components: &dyn CliNodeComponents<Self>,
+        module_config: TransportRpcModuleConfig,
+        eth_config: EthConfig,
+        runtime: reth_tasks::Runtime,
+    ) -> TransportRpcModules {
+        let rpc_builder = RpcModuleBuilder::default()
+            .with_provider(provider)
+            .with_noop_pool()
+            .with_noop_network()
+            .with_executor(runtime.clone())
+            .with_hanzo_evm_config(components.hanzo_evm_config().clone())
+            .with_consensus(components.consensus().clone());
+
+        let eth_api = rpc_builder
+            .eth_api_builder()
+            .task_spawner(runtime)
+            .eth_state_cache_config(eth_config.cache)
+            .gas_cap(eth_config.rpc_gas_cap.into())
+            .max_simulate_blocks(eth_config.rpc_max_simulate_blocks)
+            .eth_proof_window(eth_config.eth_proof_window)
+            .fee_history_cache_config(eth_config.fee_history_cache)
+            .proof_permits(eth_config.proof_permits)
+            .gas_oracle_config(eth_config.gas_oracle)
+            .max_batch_size(eth_config.max_batch_size)
+            .max_blocking_io_requests(eth_config.max_blocking_io_requests)
+            .evm_memory_limit(eth_config.rpc_evm_memory_limit)
+            .build();
+
+        rpc_builder.build(module_config, eth_api, Default::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{extend_fixture_chain, fixture_chain};
+    use alloy_primitives::Bytes;
+    use hanzo_evm_ethereum_cli::chainspec::EthereumChainSpecParser;
+    use hanzo_evm_provider::{
+        test_utils::MockNodeTypesWithDB, BlockExecutionWriter, CanonStateSubscriptions, DBProvider,
+        ProviderFactory, StorageSettings,
+    };
+
+    /// Unwinds the chain of the primary to `block`.
+    fn unwind(provider_factory: &ProviderFactory<MockNodeTypesWithDB>, block: BlockNumber) {
+        let provider_rw = provider_factory.unwind_provider_rw().unwrap();
+        provider_rw.remove_block_and_execution_above(block).unwrap();
+        provider_rw.commit().unwrap();
+    }
+
+    /// Returns the numbers and hashes of the blocks of `chain`.
+    fn blocks(chain: &Chain) -> Vec<BlockNumHash> {
+        chain.blocks().values().map(|block| block.num_hash()).collect()
+    }
+
+    /// Returns the number and hash of the best block of the primary.
+    fn best_block(provider_factory: &ProviderFactory<MockNodeTypesWithDB>) -> BlockNumHash {
+        let number = provider_factory.best_block_number().unwrap();
+        BlockNumHash::new(number, provider_factory.block_hash(number).unwrap().unwrap())
+    }
+
+    #[test]
+    fn parse_reader_command() {
+        let args: ReaderCommand<EthereumChainSpecParser> =
+            ReaderCommand::parse_from(["evm", "--http", "--ipcpath", "/tmp/reader.ipc"]);
+        assert_eq!(args.primary_ipc, DEFAULT_IPC_ENDPOINT);
+        assert!(args.rpc.http);
+
+        let args: ReaderCommand<EthereumChainSpecParser> =
+            ReaderCommand::parse_from(["evm", "--reader.primary-ipc", "/tmp/primary.ipc"]);
+        assert_eq!(args.primary_ipc, "/tmp/primary.ipc");
+    }
+
+    #[test]
+    fn follower_notifies_commits_and_reorgs() {
+        let provider_factory = fixture_chain(1, StorageSettings::v1()).unwrap();
+        let provider = BlockchainProvider::new(provider_factory.clone()).unwrap();
+        let mut notifications = provider.subscribe_to_canonical_state();
+        let mut follower = ChainFollower::default();
+        let head = || provider.canonical_in_memory_state().get_canonical_head().num_hash();
+
+        // Nothing changed since the provider was created
+        follower.resync(&provider).unwrap();
+        assert_eq!(head(), best_block(&provider_factory));
+        assert!(notifications.try_recv().is_err());
+
+        // Blocks persisted by the primary are committed
+        extend_fixture_chain(&provider_factory, 2, Bytes::new()).unwrap();
+        follower.on_persisted_block(&provider, best_block(&provider_factory)).unwrap();
+        assert_eq!(head(), best_block(&provider_factory));
+        let CanonStateNotification::Commit { new } = notifications.try_recv().unwrap() else {
+            panic!("expected a commit")
+        };
+        assert_eq!(new.range(), 2..=3);
+        let reverted = blocks(&new);
+
+        // An outdated notification doesn't move the head back
+        follower.on_persisted_block(&provider, new.first().num_hash()).unwrap();
+        assert_eq!(head(), best_block(&provider_factory));
+        assert!(notifications.try_recv().is_err());
+
+        // Unwinding is reported together with the blocks persisted on top of the unwind target
+        unwind(&provider_factory, 1);
+        follower.resync(&provider).unwrap();
+        assert_eq!(head().number, 1);
+        assert!(notifications.try_recv().is_err());
+
+        extend_fixture_chain(&provider_factory, 1, Bytes::from_static(b"fork")).unwrap();
+        follower.resync(&provider).unwrap();
+        assert_eq!(head(), best_block(&provider_factory));
+        let CanonStateNotification::Reorg { old, new } = notifications.try_recv().unwrap() else {
+            panic!("expected a reorg")
+        };
+        assert_eq!(blocks(&old), reverted);
+        assert_eq!(new.range(), 2..=2);
+        assert_eq!(new.tip().num_hash(), best_block(&provider_factory));
+        assert_eq!(old.fork_block(), new.fork_block());
+
+        // Replacing blocks at once reverts only the replaced part of the committed chains
+        let replaced = new.tip().num_hash();
+        unwind(&provider_factory, 1);
+        extend_fixture_chain(&provider_factory, 2, Bytes::from_static(b"other fork")).unwrap();
+        follower.on_persisted_block(&provider, best_block(&provider_factory)).unwrap();
+        let CanonStateNotification::Reorg { old, new } = notifications.try_recv().unwrap() else {
+            panic!("expected a reorg")
+        };
+        assert_eq!(blocks(&old), [replaced]);
+        assert_eq!(new.range(), 2..=3);
+        assert_eq!(
+            follower.committed.iter().map(|chain| chain.range()).collect::<Vec<_>>(),
+            [2..=3]
+        );
+    }
+
+    #[test]
+    fn follower_notifies_in_batches() {
+        let provider_factory = fixture_chain(1, StorageSettings::v1()).unwrap();
+        let provider = BlockchainProvider::new(provider_factory.clone()).unwrap();
+        let mut notifications = provider.subscribe_to_canonical_state();
+        let mut follower =
+            ChainFollower { batch_size: 2, max_notified_blocks: 4, ..Default::default() };
+
+        extend_fixture_chain(&provider_factory, 3, Bytes::new()).unwrap();
+        follower.resync(&provider).unwrap();
+        let ranges = iter::from_fn(|| notifications.try_recv().ok())
+            .map(|notification| notification.committed().range())
+            .collect::<Vec<_>>();
+        assert_eq!(ranges, [2..=3, 4..=4]);
+
+        // Advancing by more blocks only moves the head
+        extend_fixture_chain(&provider_factory, 5, Bytes::new()).unwrap();
+        follower.resync(&provider).unwrap();
+        assert_eq!(
+            provider.canonical_in_memory_state().get_canonical_head().num_hash(),
+            best_block(&provider_factory)
+        );
+        assert!(notifications.try_recv().is_err());
+        assert!(follower.committed.is_empty());
+    }
+
+    #[test]
+    fn follower_reverts_part_of_a_committed_chain() {
+        let provider_factory = fixture_chain(1, StorageSettings::v1()).unwrap();
+        let provider = BlockchainProvider::new(provider_factory.clone()).unwrap();
+        let mut notifications = provider.subscribe_to_canonical_state();
+        let mut follower = ChainFollower::default();
+
+        extend_fixture_chain(&provider_factory, 3, Bytes::new()).unwrap();
+        follower.resync(&provider).unwrap();
+        let CanonStateNotification::Commit { new: committed } = notifications.try_recv().unwrap()
+        else {
+            panic!("expected a commit")
+        };
+        assert_eq!(committed.range(), 2..=4);
+
+        unwind(&provider_factory, 2);
+        extend_fixture_chain(&provider_factory, 1, Bytes::from_static(b"fork")).unwrap();
+        follower.resync(&provider).unwrap();
+        let CanonStateNotification::Reorg { old, new } = notifications.try_recv().unwrap() else {
+            panic!("expected a reorg")
+        };
+        assert_eq!(old.range(), 3..=4);
+        assert_eq!(new.range(), 3..=3);
+        assert_eq!(
+            old.execution_outcome(),
+            &committed.execution_outcome().clone().split_at(3).1,
+            "reverted blocks should keep their execution outcome"
+        );
+
+        // The blocks below the fork block stay committed
+        let kept = &follower.committed[0];
+        assert_eq!(kept.range(), 2..=2);
+        assert_eq!(kept.tip().num_hash(), committed.first().num_hash());
+    }
+
+    #[test]
+    fn reader_modules_default_and_validation() {
+        assert_eq!(reader_modules(None, "http.api").unwrap(), READER_MODULES.into());
+
+        let selection = RpcModuleSelection::from([EvmRpcModule::Eth, EvmRpcModule::Trace]);
+        assert_eq!(reader_modules(Some(selection.clone()), "http.api").unwrap(), selection);
+
+        let selection = RpcModuleSelection::from([EvmRpcModule::Eth, EvmRpcModule::Admin]);
+        assert!(reader_modules(Some(selection), "http.api").is_err());
+        assert!(reader_modules(Some(RpcModuleSelection::All), "http.api").is_err());
+    }
+}
@@ -0,0 +1,558 @@
+//! Command that packages a storage snapshot into archives that `evm download` can fetch.
+
+use alloy_primitives::{keccak256, Address, BlockHash, BlockNumber, Bytes, Keccak256, B256};
+use clap::Parser;
+use eyre::Result;
+use hanzo_evm_cli_util::parse_secret_key_from_hex;
+use hanzo_evm_db_api::models::{StorageSettings, StorageSnapshotManifest};
+use hanzo_evm_fs_util as fs;
+use hanzo_evm_static_file_types::{SegmentRangeInclusive, StaticFileSegment};
+use secp256k1::{
+    ecdsa::{RecoverableSignature, RecoveryId},
+    Message, SecretKey, SECP256K1,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+use tar::Builder;
+use tracing::info;
+use zstd::stream::write::Encoder as ZstdEncoder;
+
+/// Extension of the archives listed in an [`ArchiveManifest`].
+const ARCHIVE_EXTENSION: &str = ".tar.zst";
+
+/// `evm snapshot` command
+///
+/// Packages a directory created by `evm db snapshot` or `admin_createSnapshot` into zstd
+/// compressed tar archives. Every static file is archived on its own, split by its block range,
+/// next to one archive for the database and one for `RocksDB`. The archives are listed with
+/// their keccak256 hashes in an `archives.json` manifest that can be signed. Serving the output
+/// directory over HTTP lets `evm download --url <base>/archives.json` fetch the archives in
+/// parallel.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// Snapshot directory to package.
+    source: PathBuf,
+
+    /// Directory to write the archives and the manifest to.
+    #[arg(long, short)]
+    output: PathBuf,
+
+    /// The zstd compression level of the archives.
+    #[arg(long, default_value_t = 3)]
+    compression_level: i32,
+
+    /// Number of archives to compress in parallel.
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u64).range(1..))]
+    jobs: u64,
+
+    /// Path to a hex encoded secp256k1 secret key to sign the manifest with.
+    #[arg(long, value_name = "PATH")]
+    signing_key: Option<PathBuf>,
+}
+
+impl Command {
+    /// Execute `snapshot` command
+    pub async fn execute(self) -> Result<()> {
+        let signing_key = match &self.signing_key {
+            Some(path) => Some(parse_secret_key_from_hex(fs::read_to_string(path)?.trim())?),
+            None => None,
+        };
+
+        let manifest = tokio::task::spawn_blocking(move || {
+            create_archives(
+                &self.source,
+                &self.output,
+                self.compression_level,
+                self.jobs as usize,
+                signing_key.as_ref(),
+            )
+        })
+        .await??;
+
+        info!(target: "evm::cli",
+            block_number = manifest.block_number,
+            archives = manifest.archives.len(),
+            signer = ?manifest.recover_signer().ok(),
+            "Snapshot archives created"
+        );
+
+        Ok(())
+    }
+}
+
+/// Lists the archives of a snapshot served for `evm download`.
+///
+/// Written as [`ArchiveManifest::FILE_NAME`] next to the archives, which are resolved relative to
+/// the manifest URL.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveManifest {
+    /// Format version of the manifest.
+    pub version: u64,
+    /// Block the snapshot was pinned at.
+    pub block_number: BlockNumber,
+    /// Hash of [`Self::block_number`].
+    pub block_hash: BlockHash,
+    /// Storage settings of the snapshot.
+    pub storage_settings: StorageSettings,
+    /// Archives of the snapshot, sorted by name.
+    pub archives: Vec<SnapshotArchive>,
+    /// Recoverable secp256k1 signature over [`Self::signature_hash`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<Bytes>,
+}
+
+impl ArchiveManifest {
+    /// Current manifest format version.
+    pub const VERSION: u64 = 1;
+
+    /// Name of the manifest file.
+    pub const FILE_NAME: &'static str = "archives.json";
+
+    /// Returns the hash that is signed, the keccak256 hash of the JSON encoded manifest without
+    /// its signature.
+    pub fn signature_hash(&self) -> Result<B256> {
+        let unsigned = Self { signature: None, ..self.clone() };
+        Ok(keccak256(serde_json::to_vec(&unsigned)?))
+    }
+
+    /// Signs the manifest with the given secret key.
+    pub fn sign(&mut self, secret_key: &SecretKey) -> Result<()> {
+        let message = Message::from_digest(self.signature_hash()?.0);
+        let (recovery_id, signature) =
+            SECP256K1.sign_ecdsa_recoverable(&message, secret_key).serialize_compact();
+
+        let mut bytes = signature.to_vec();
+        bytes.push(i32::from(recovery_id) as u8);
+        self.signature = Some(bytes.into());
+        Ok(())
+    }
+
+    /// Recovers the address that signed the manifest.
+    pub fn recover_signer(&self) -> Result<Address> {
+        let signature = self
+            .signature
+            .as_ref()
+            .ok_or_else(|| eyre::eyre!("Snapshot manifest is not signed"))?;
+        eyre::ensure!(signature.len() == 65, "Invalid snapshot manifest signature length");
+
+        let recovery_id = RecoveryId::try_from(signature[64] as i32)?;
+        let signature = RecoverableSignature::from_compact(&signature[..64], recovery_id)?;
+        let message = Message::from_digest(self.signature_hash()?.0);
+        let public_key = SECP256K1.recover_ecdsa(&message, &signature)?;
+
+        Ok(Address::from_raw_public_key(&public_key.serialize_uncompressed()[1..]))
+    }
+}
+
+/// An archive of an [`ArchiveManifest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotArchive {
+    /// File name of the archive.
+    pub name: String,
+    /// Size of the archive in bytes.
+    pub size: u64,
+    /// Keccak256 hash of the archive.
+    pub hash: B256,
+    /// Static file segment contained in the archive, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub segment: Option<StaticFileSegment>,
+    /// Block range of the static file contained in the archive, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_range: Option<SegmentRangeInclusive>,
+}
+
+/// Packages the snapshot directory `source` into archives in `output` and writes their
+/// [`ArchiveManifest`].
+pub fn create_archives(
+    source: &Path,
+    output: &Path,
+    compression_level: i32,
+    jobs: usize,
+    signing_key: Option<&SecretKey>,
+) -> Result<ArchiveManifest> {
+    let manifest_path = source.join(StorageSnapshotManifest::FILE_NAME);
+    eyre::ensure!(
+        manifest_path.is_file(),
+        "{} is not a snapshot directory, create one with `evm db snapshot`",
+        source.display()
+    );
+    let snapshot: StorageSnapshotManifest = fs::read_json_file(&manifest_path)?;
+    fs::create_dir_all(output)?;
+
+    // Static files are archived per file and block range, everything else per top-level
+    // directory.
+    let mut groups = BTreeMap::<String, (Vec<String>, Option<_>)>::new();
+    for file in snapshot.files {
+        let (group, segment) = match file.path.split_once('/') {
+            Some(("static_files", name)) => {
+                let jar = name.split_once('.').map_or(name, |(jar, _)| jar);
+                (jar.to_string(), StaticFileSegment::parse_filename(jar))
+            }
+            Some((dir, _)) => (dir.to_string(), None),
+            None => (file.path.clone(), None),
+        };
+        let entry = groups.entry(group).or_insert_with(|| (Vec::new(), segment));
+        entry.0.push(file.path);
+    }
+    let groups = groups.into_iter().collect::<Vec<_>>();
+
+    info!(target: "evm::cli", archives = groups.len(), "Creating snapshot archives");
+    let next = AtomicUsize::new(0);
+    let results = thread::scope(|scope| {
+        let handles = (0..jobs.min(groups.len()).max(1))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut archives = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some((group, (files, segment))) = groups.get(index) else {
+                            return Ok::<_, eyre::Report>(archives)
+                        };
+
+                        let name = format!("{group}{ARCHIVE_EXTENSION}");
+                        let (size, hash) =
+                            write_archive(source, files, &output.join(&name), compression_level)?;
+                        info!(target: "evm::cli", %name, size, "Created archive");
+
+                        archives.push(SnapshotArchive {
+                            name,
+                            size,
+                            hash,
+                            segment: segment.as_ref().map(|(segment, _)| *segment),
+                            block_range: segment.as_ref().map(|(_, range)| *range),
+                        });
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().map_err(|_| eyre::eyre!("Archive worker panicked"))?)
+            .collect::<Result<Vec<_>>>()
+    })?;
+
+    let mut archives = results.into_iter().flatten().collect::<Vec<_>>();
+    archives.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut manifest = ArchiveManifest {
+        version: ArchiveManifest::VERSION,
+        block_number: snapshot.block_number,
+        block_hash: snapshot.block_hash,
+        storage_settings: snapshot.storage_settings,
+        archives,
+        signature: None,
+    };
+    if let Some(secret_key) = signing_key {
+        manifest.sign(secret_key)?;
+    }
+    fs::write_json_file(&output.join(ArchiveManifest::FILE_NAME), &manifest)?;
+
+    Ok(manifest)
+}
+
+/// Writes `files` of `source` into a zstd compressed tar archive at `dest`.
+///
+/// Returns the size and keccak256 hash of the archive.
+fn write_archive(
+    source: &Path,
+    files: &[String],
+    dest: &Path,
+    compression_level: i32,
+) -> Result<(u64, B256)> {
+    let part_path = PathBuf::from(format!("{}.part", dest.display()));
+    let writer = HashingWriter::new(io::BufWriter::new(fs::create_file(&part_path)?));
+
+    let mut builder = Builder::new(ZstdEncoder::new(writer, compression_level)?);
+    for file in files {
+        builder.append_path_with_name(source.join(file), file)?;
+    }
+    let mut writer = builder.into_inner()?.finish()?;
+    writer.flush()?;
+
+    let (size, hash) = writer.finalize();
+    fs::rename(&part_path, dest)?;
+    Ok((size, hash))
+}
+
+/// Writer that hashes and counts everything written through it.
+pub(crate) struct HashingWriter<W> {
+    inner: W,
+    hasher: Keccak256,
+    size: u64,
+}
+
+impl<W> HashingWriter<W> {
+    /// Creates a new writer wrapping `inner`.
+    pub(crate) fn new(inner: W) -> Self {
+        Self { inner, hasher: Keccak256::new(), size: 0 }
+    }
+
+    /// Returns the number of bytes written and their keccak256 hash.
+    pub(crate) fn finalize(self) -> (u64, B256) {
+        (self.size, self.hasher.finalize())
+    }
+}
+
+impl<W: Write> Write for HashingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.hasher.update(&buf[..n]);
+        self.size += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::download::download_archives;
+    use std::{
+        io::{BufRead, BufReader},
+        net::TcpListener,
+    };
+
+    /// Serves the files of `dir` over HTTP, honoring `Range` requests, like the CDN would.
+    fn serve(dir: PathBuf) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        thread::spawn(move || {
+            for mut stream in listener.incoming().map_while(Result::ok) {
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                reader.read_line(&mut request).unwrap();
+                let path = request.split_whitespace().nth(1).unwrap().trim_start_matches('/');
+
+                let mut range_start = None;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break
+                    }
+                    if let Some(range) = line.to_lowercase().strip_prefix("range: bytes=") {
+                        range_start = range.trim().trim_end_matches('-').parse::<usize>().ok();
+                    }
+                }
+
+                let Ok(body) = std::fs::read(dir.join(path)) else {
+                    stream
+                        .write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n")
+                        .unwrap();
+                    continue
+                };
+                let head = match range_start {
+                    Some(start) => format!(
+                        "HTTP/1.1 206 Partial Content\r\nContent-Length: {}\r\nContent-Range: bytes {start}-{}/{}\r\n",
+                        body.len() - start,
+                        body.len() - 1,
+                        body.len()
+                    ),
+                    None => format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n", body.len()),
+                };
+                let body = &body[range_start.unwrap_or_default()..];
+                stream.write_all(format!("{head}Connection: close\r\n\r\n").as_bytes()).unwrap();
+                stream.write_all(body).unwrap();
+            }
+        });
+
+        format!("http://{addr}/{}", ArchiveManifest::FILE_NAME)
+    }
+
+    /// Creates a snapshot directory with a few static files, a database and `RocksDB`.
+    fn create_snapshot_dir(dir: &Path) {
+        let files = [
+            ("db/mdbx.dat", 64 * 1024),
+            ("db/database.version", 1),
+            ("rocksdb/CURRENT", 16),
+            ("static_files/static_file_headers_0_499999", 32 * 1024),
+            ("static_files/static_file_headers_0_499999.conf", 128),
+            ("static_files/static_file_headers_0_499999.off", 256),
+            ("static_files/static_file_headers_500000_999999", 8 * 1024),
+            ("static_files/static_file_headers_500000_999999.conf", 128),
+        ];
+
+        let mut manifest = StorageSnapshotManifest {
+            version: StorageSnapshotManifest::VERSION,
+            block_number: 500_010,
+            block_hash: B256::repeat_byte(0x11),
+            storage_settings: StorageSettings::default(),
+            files: Vec::new(),
+        };
+        for (index, (path, size)) in files.into_iter().enumerate() {
+            let path_buf = dir.join(path);
+            std::fs::create_dir_all(path_buf.parent().unwrap()).unwrap();
+            let contents = (0..size).map(|i| (i * 31 + index) as u8).collect::<Vec<_>>();
+            std::fs::write(&path_buf, &contents).unwrap();
+            manifest.files.push(hanzo_evm_db_api::models::StorageSnapshotFile {
+                path: path.to_string(),
+                size: size as u64,
+                hash: keccak256(&contents),
+            });
+        }
+        fs::write_json_file(&dir.join(StorageSnapshotManifest::FILE_NAME), &manifest).unwrap();
+    }
+
+    fn signing_key() -> (SecretKey, Address) {
+        let secret_key = SecretKey::from_slice(&[0x42; 32]).unwrap();
+        let public_key = secret_key.public_key(SECP256K1);
+        (secret_key, Address::from_raw_public_key(&public_key.serialize_uncompressed()[1..]))
+    }
+
+    #[test]
+    fn create_and_download_archives() {
+        let (source, output, target) = (
+            tempfile::tempdir().unwrap(),
+            tempfile::tempdir().unwrap(),
+            tempfile::tempdir().unwrap(),
+        );
+        create_snapshot_dir(source.path());
+        let (secret_key, signer) = signing_key();
+
+        let manifest =
+            create_archives(source.path(), output.path(), 3, 2, Some(&secret_key)).unwrap();
+        assert_eq!(
+            manifest.archives.iter().map(|archive| archive.name.as_str()).collect::<Vec<_>>(),
+            [
+                "db.tar.zst",
+                "rocksdb.tar.zst",
+                "static_file_headers_0_499999.tar.zst",
+                "static_file_headers_500000_999999.tar.zst",
+            ]
+        );
+        assert_eq!(manifest.archives[3].segment, Some(StaticFileSegment::Headers));
+        assert_eq!(
+            manifest.archives[3].block_range,
+            Some(SegmentRangeInclusive::new(500_000, 999_999))
+        );
+        assert_eq!(manifest.recover_signer().unwrap(), signer);
+
+        // a partially downloaded archive is resumed with a range request
+        let first = &manifest.archives[0];
+        let download_dir = target.path().join(".snapshot-download");
+        std::fs::create_dir_all(&download_dir).unwrap();
+        let partial = std::fs::read(output.path().join(&first.name)).unwrap();
+        std::fs::write(
+            download_dir.join(format!("{}.part", first.name)),
+            &partial[..partial.len() / 2],
+        )
+        .unwrap();
+
+        let url = serve(output.path().to_path_buf());
+        download_archives(&url, target.path(), 3, Some(signer)).unwrap();
+
+        let snapshot: StorageSnapshotManifest =
+            fs::read_json_file(&source.path().join(StorageSnapshotManifest::FILE_NAME)).unwrap();
+        for file in snapshot.files {
+            assert_eq!(
+                std::fs::read(target.path().join(&file.path)).unwrap(),
+                std::fs::read(source.path().join(&file.path)).unwrap(),
+                "{}",
+                file.path
+            );
+        }
+        assert!(!download_dir.exists());
+    }
+
+    #[test]
+    fn download_refetches_archive_with_stale_marker() {
+        let (source, output, target) = (
+            tempfile::tempdir().unwrap(),
+            tempfile::tempdir().unwrap(),
+            tempfile::tempdir().unwrap(),
+        );
+        create_snapshot_dir(source.path());
+        let manifest = create_archives(source.path(), output.path(), 3, 1, None).unwrap();
+
+        // the database archive was extracted from an archive with a different hash
+        let download_dir = target.path().join(".snapshot-download");
+        std::fs::create_dir_all(target.path().join("db")).unwrap();
+        std::fs::create_dir_all(&download_dir).unwrap();
+        std::fs::write(target.path().join("db/mdbx.dat"), b"stale").unwrap();
+        std::fs::write(
+            download_dir.join(format!("{}.done", manifest.archives[0].name)),
+            B256::repeat_byte(0x22).to_string(),
+        )
+        .unwrap();
+
+        let url = serve(output.path().to_path_buf());
+        download_archives(&url, target.path(), 1, None).unwrap();
+        assert_eq!(
+            std::fs::read(target.path().join("db/mdbx.dat")).unwrap(),
+            std::fs::read(source.path().join("db/mdbx.dat")).unwrap()
+        );
+    }
+
+    #[test]
+    fn download_rejects_corrupted_archive() {
+        let (source, output, target) = (
+            tempfile::tempdir().unwrap(),
+            tempfile::tempdir().unwrap(),
+            tempfile::tempdir().unwrap(),
+        );
+        create_snapshot_dir(source.path());
+        let manifest = create_archives(source.path(), output.path(), 3, 1, None).unwrap();
+
+        // flip a byte without changing the size
+        let path = output.path().join(&manifest.archives[1].name);
+        let mut bytes = std::fs::read(&path).unwrap();
+        bytes[0] ^= 0xff;
+        std::fs::write(&path, bytes).unwrap();
+
+        let url = serve(output.path().to_path_buf());
+        let err = download_archives(&url, target.path(), 1, None).unwrap_err();
+        assert!(err.to_string().contains("Hash mismatch for archive rocksdb.tar.zst"), "{err}");
+    }
+
+    #[test]
+    fn download_rejects_unexpected_signer() {
+        let (source, output, target) = (
+            tempfile::tempdir().unwrap(),
+            tempfile::tempdir().unwrap(),
+            tempfile::tempdir().unwrap(),
+        );
+        create_snapshot_dir(source.path());
+        let (secret_key, _) = signing_key();
+        create_archives(source.path(), output.path(), 3, 1, Some(&secret_key)).unwrap();
+
+        let url = serve(output.path().to_path_buf());
+        let err =
+            download_archives(&url, target.path(), 1, Some(Address::repeat_byte(1))).unwrap_err();
+        assert!(err.to_string().contains("Archive manifest is signed by"), "{err}");
+        assert!(!target.path().join("db").exists());
+    }
+
+    #[test]
+    fn download_rejects_unsafe_archive_name() {
+        let (source, output, target) = (
+            tempfile::tempdir().unwrap(),
+            tempfile::tempdir().unwrap(),
+            tempfile::tempdir().unwrap(),
+        );
+        create_snapshot_dir(source.path());
+        let mut manifest = create_archives(source.path(), output.path(), 3, 1, None).unwrap();
+
+        for name in ["../db.tar.zst", "/tmp/db.tar.zst", "static_files/db.tar.zst", ".."] {
+            manifest.archives[0].name = name.to_string();
+            fs::write_json_file(&output.path().join(ArchiveManifest::FILE_NAME), &manifest)
+                .unwrap();
+
+            let url = serve(output.path().to_path_buf());
+            let err = download_archives(&url, target.path(), 1, None).unwrap_err();
+            assert!(err.to_string().contains("Invalid archive name"), "{name}: {err}");
+        }
+    }
+}
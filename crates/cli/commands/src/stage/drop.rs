@@ -18,8 +18,8 @@ use reth_db_common::{
 use hanzo_evm_node_api::{HeaderTy, ReceiptTy, TxTy};
 use hanzo_evm_node_core::args::StageEnum;
 use hanzo_evm_provider::{
-    DBProvider, RocksDBProviderFactory, StaticFileProviderFactory, StaticFileWriter,
-    StorageSettingsCache,
+    DBProvider, RocksDBProviderFactory, StateCursorProvider, StaticFileProviderFactory,
+    StaticFileWriter, StorageSettingsCache,
 };
 use hanzo_evm_prune::PruneSegment;
 use hanzo_evm_stages::StageId;
@@ -52,6 +52,7 @@ impl<C: ChainSpecParser> Command<C> {
                 StaticFileSegment::Receipts,
                 StaticFileSegment::AccountChangeSets,
                 StaticFileSegment::StorageChangeSets,
+                StaticFileSegment::StateSnapshots,
             ],
             StageEnum::Senders => vec![StaticFileSegment::TransactionSenders],
             _ => vec![],
@@ -99,6 +100,9 @@ impl<C: ChainSpecParser> Command<C> {
                     StaticFileSegment::StorageChangeSets => {
                         writer.prune_storage_changesets(highest_block)?;
                     }
+                    StaticFileSegment::StateSnapshots => {
+                        writer.prune_state_snapshots(highest_block)?;
+                    }
                 }
             }
         }
@@ -134,13 +138,13 @@ impl<C: ChainSpecParser> Command<C> {
             }
             StageEnum::Execution => {
                 if provider_rw.cached_storage_settings().use_hashed_state() {
-                    tx.clear::<tables::HashedAccounts>()?;
-                    tx.clear::<tables::HashedStorages>()?;
+                    provider_rw.clear_state_table::<tables::HashedAccounts>()?;
+                    provider_rw.clear_state_table::<tables::HashedStorages>()?;
                     reset_stage_checkpoint(tx, StageId::AccountHashing)?;
                     reset_stage_checkpoint(tx, StageId::StorageHashing)?;
                 } else {
-                    tx.clear::<tables::PlainAccountState>()?;
-                    tx.clear::<tables::PlainStorageState>()?;
+                    provider_rw.clear_state_table::<tables::PlainAccountState>()?;
+                    provider_rw.clear_state_table::<tables::PlainStorageState>()?;
                 }
                 tx.clear::<tables::AccountChangeSets>()?;
                 tx.clear::<tables::StorageChangeSets>()?;
@@ -155,25 +159,25 @@ impl<C: ChainSpecParser> Command<C> {
                 insert_genesis_state(&provider_rw, alloc.iter())?;
             }
             StageEnum::AccountHashing => {
-                tx.clear::<tables::HashedAccounts>()?;
+                provider_rw.clear_state_table::<tables::HashedAccounts>()?;
                 reset_stage_checkpoint(tx, StageId::AccountHashing)?;
             }
             StageEnum::StorageHashing => {
-                tx.clear::<tables::HashedStorages>()?;
+                provider_rw.clear_state_table::<tables::HashedStorages>()?;
                 reset_stage_checkpoint(tx, StageId::StorageHashing)?;
             }
             StageEnum::Hashing => {
                 // Clear hashed accounts
-                tx.clear::<tables::HashedAccounts>()?;
+                provider_rw.clear_state_table::<tables::HashedAccounts>()?;
                 reset_stage_checkpoint(tx, StageId::AccountHashing)?;
 
                 // Clear hashed storages
-                tx.clear::<tables::HashedStorages>()?;
+                provider_rw.clear_state_table::<tables::HashedStorages>()?;
                 reset_stage_checkpoint(tx, StageId::StorageHashing)?;
             }
             StageEnum::Merkle => {
-                tx.clear::<tables::AccountsTrie>()?;
-                tx.clear::<tables::StoragesTrie>()?;
+                provider_rw.clear_state_table::<tables::AccountsTrie>()?;
+                provider_rw.clear_state_table::<tables::StoragesTrie>()?;
 
                 reset_stage_checkpoint(tx, StageId::MerkleExecute)?;
                 reset_stage_checkpoint(tx, StageId::MerkleUnwind)?;
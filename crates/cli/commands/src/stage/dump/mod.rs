@@ -14,6 +14,7 @@ use hanzo_evm_node_core::{
     args::DatadirArgs,
     dirs::{DataDirPath, PlatformPath},
 };
+use hanzo_evm_provider::StorageSettingsCache;
 use std::{path::PathBuf, sync::Arc};
 use tracing::info;
 
@@ -102,6 +103,11 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
     {
         let Environment { provider_factory, .. } =
             self.env.init::<N>(AccessRights::RO, runtime.clone())?;
+        // The dumps copy state tables between MDBX databases
+        eyre::ensure!(
+            !provider_factory.cached_storage_settings().state_in_rocksdb,
+            "Dumping stages is not supported with state stored in RocksDB"
+        );
         let tool = DbTool::new(provider_factory)?;
         let components = components(tool.chain());
         let hanzo_evm_config = components.hanzo_evm_config().clone();
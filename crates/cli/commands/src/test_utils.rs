@@ -0,0 +1,238 @@
+//! Test helpers for running commands against a database with a real chain.
+
+use alloy_consensus::{constants::ETH_TO_WEI, transaction::TxHashRef, TxEip1559};
+use alloy_eips::eip1559::INITIAL_BASE_FEE;
+use alloy_genesis::{Genesis, GenesisAccount};
+use alloy_primitives::{bytes, Address, BlockNumber, Bytes, TxKind, TxNumber, B256, U256};
+use hanzo_evm_chain_state::{ComputedTrieData, ExecutedBlock};
+use hanzo_evm_chainspec::{ChainSpecBuilder, MAINNET};
+use hanzo_evm_db_common::init::init_genesis_with_settings;
+use hanzo_evm_eth_execution::EthEvmConfig;
+use hanzo_evm_ethereum_primitives::{EthPrimitives, Receipt, Transaction};
+use hanzo_evm_execution::{
+    execute::{BlockBuilder, BlockBuilderOutcome},
+    ConfigureEvm, NextBlockEnvAttributes,
+};
+use hanzo_evm_primitives_traits::{crypto::secp256k1::public_key_to_address, Account, Recovered};
+use hanzo_evm_provider::{
+    providers::ProviderNodeTypes,
+    test_utils::{create_test_provider_factory_with_chain_spec, MockNodeTypesWithDB},
+    BlockBodyIndicesProvider, BlockExecutionOutput, BlockHashReader, BlockNumReader,
+    ChainSpecProvider, DBProvider, HeaderProvider, ProviderError, ProviderFactory, ReceiptProvider,
+    SaveBlocksMode, StateCursorFactory, StateCursorProvider, StateProviderFactory, StorageSettings,
+    StorageSettingsCache, TransactionsProvider,
+};
+use hanzo_evm_revm::{database::StateProviderDatabase, db::State};
+use hanzo_evm_storage_api::StateProvider;
+use hanzo_evm_testing_utils::generators::sign_tx_with_key_pair;
+use hanzo_evm_trie::{trie_cursor::noop::NoopTrieCursorFactory, StateRoot};
+use secp256k1::{Keypair, SECP256K1};
+use std::sync::Arc;
+
+/// Address of the contract deployed in the genesis of the fixture chain.
+pub(crate) const STORAGE_CONTRACT: Address = Address::new([0x42; 20]);
+
+/// Stores the first calldata word both in slot `0` and in the slot of the current block number.
+///
+/// ```text
+/// PUSH1 0 CALLDATALOAD NUMBER SSTORE PUSH1 0 CALLDATALOAD PUSH1 0 SSTORE STOP
+/// ```
+const STORAGE_CONTRACT_CODE: Bytes = bytes!("600035435560003560005500");
+
+/// Secret key of the account sending the transactions of the fixture chain.
+const SIGNER_KEY: [u8; 32] = [0x11; 32];
+
+/// Creates a provider factory with the given storage `settings`, holding a chain of `blocks`
+/// executed blocks on top of genesis.
+///
+/// Every block contains a transfer to a new account and a call to [`STORAGE_CONTRACT`] that
+/// creates one storage slot and overwrites another, so that all state, changeset and history
+/// tables are populated.
+pub(crate) fn fixture_chain(
+    blocks: u64,
+    settings: StorageSettings,
+) -> eyre::Result<ProviderFactory<MockNodeTypesWithDB>> {
+    let signer = public_key_to_address(signer_key_pair().public_key());
+
+    let chain_spec = Arc::new(
+        ChainSpecBuilder::default()
+            .chain(MAINNET.chain)
+            .genesis(Genesis {
+                gas_limit: 30_000_000,
+                alloc: [
+                    (
+                        signer,
+                        GenesisAccount {
+                            balance: U256::from(ETH_TO_WEI) * U256::from(1000),
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        STORAGE_CONTRACT,
+                        GenesisAccount { code: Some(STORAGE_CONTRACT_CODE), ..Default::default() },
+                    ),
+                ]
+                .into(),
+                ..MAINNET.genesis.clone()
+            })
+            .shanghai_activated()
+            .build(),
+    );
+
+    let provider_factory = create_test_provider_factory_with_chain_spec(chain_spec);
+    provider_factory.set_storage_settings_cache(settings);
+    init_genesis_with_settings(&provider_factory, settings)?;
+    extend_fixture_chain(&provider_factory, blocks, Bytes::new())?;
+
+    Ok(provider_factory)
+}
+
+/// Executes and persists `blocks` more blocks of the fixture chain on top of the best block of
+/// `provider_factory`.
+///
+/// Blocks built with different `extra_data` on top of the same parent have different hashes, which
+/// allows building forks of the fixture chain.
+pub(crate) fn extend_fixture_chain(
+    provider_factory: &ProviderFactory<MockNodeTypesWithDB>,
+    blocks: u64,
+    extra_data: Bytes,
+) -> eyre::Result<()> {
+    let key_pair = signer_key_pair();
+    let signer = public_key_to_address(key_pair.public_key());
+    let chain_spec = provider_factory.chain_spec();
+    let hanzo_evm_config = EthEvmConfig::new(chain_spec.clone());
+
+    let best_block = provider_factory.best_block_number()?;
+    let mut parent = provider_factory.sealed_header(best_block)?.expect("best block should exist");
+    for number in best_block + 1..=best_block + blocks {
+        let transaction = |nonce, to, value, input| {
+            let tx = sign_tx_with_key_pair(
+                key_pair,
+                Transaction::Eip1559(TxEip1559 {
+                    chain_id: chain_spec.chain.id(),
+                    nonce,
+                    gas_limit: 100_000,
+                    max_fee_per_gas: INITIAL_BASE_FEE as u128,
+                    to: TxKind::Call(to),
+                    value,
+                    input,
+                    ..Default::default()
+                }),
+            );
+            Recovered::new_unchecked(tx, signer)
+        };
+        let transactions = [
+            transaction(
+                (number - 1) * 2,
+                Address::with_last_byte(number as u8),
+                U256::from(1),
+                Bytes::new(),
+            ),
+            transaction(
+                (number - 1) * 2 + 1,
+                STORAGE_CONTRACT,
+                U256::ZERO,
+                U256::from(number).to_be_bytes_vec().into(),
+            ),
+        ];
+
+        let state = provider_factory.latest()?;
+        let mut db = State::builder()
+            .with_bundle_update()
+            .with_database(StateProviderDatabase::new(&state))
+            .build();
+        let mut builder = hanzo_evm_config.builder_for_next_block(
+            &mut db,
+            &parent,
+            NextBlockEnvAttributes {
+                timestamp: parent.timestamp + 12,
+                suggested_fee_recipient: Address::ZERO,
+                prev_randao: B256::ZERO,
+                gas_limit: parent.gas_limit,
+                parent_beacon_block_root: None,
+                withdrawals: Some(Default::default()),
+                extra_data: extra_data.clone(),
+            },
+        )?;
+        builder.apply_pre_execution_changes()?;
+        for tx in transactions {
+            builder.execute_transaction(tx)?;
+        }
+        let BlockBuilderOutcome { execution_result, hashed_state, trie_updates, block } =
+            builder.finish(&state)?;
+        parent = block.clone_sealed_header();
+
+        let provider_rw = provider_factory.provider_rw()?;
+        provider_rw.save_blocks(
+            vec![ExecutedBlock::new(
+                Arc::new(block),
+                Arc::new(BlockExecutionOutput {
+                    result: execution_result,
+                    state: db.take_bundle(),
+                }),
+                ComputedTrieData::without_trie_input(
+                    Arc::new(hashed_state.into_sorted()),
+                    Arc::new(trie_updates.into_sorted()),
+                ),
+            )],
+            SaveBlocksMode::Full,
+        )?;
+        provider_rw.commit()?;
+    }
+
+    Ok(())
+}
+
+/// Returns the key pair of the account sending the transactions of the fixture chain.
+fn signer_key_pair() -> Keypair {
+    Keypair::from_seckey_slice(SECP256K1, &SIGNER_KEY).expect("valid secret key")
+}
+
+/// Everything read for a block of the fixture chain, which must not change when the data of the
+/// chain is moved to another storage layout or location.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct BlockReads {
+    pub(crate) hash: Option<B256>,
+    pub(crate) senders: Vec<Address>,
+    pub(crate) tx_numbers: Vec<Option<TxNumber>>,
+    pub(crate) receipts: Option<Vec<Receipt>>,
+    pub(crate) recipient: Option<Account>,
+    pub(crate) slots: [Option<U256>; 2],
+}
+
+/// Reads the blocks of the fixture chain up to `tip`, including the state after each of them.
+pub(crate) fn read_chain<N: ProviderNodeTypes<Primitives = EthPrimitives>>(
+    provider_factory: &ProviderFactory<N>,
+    tip: BlockNumber,
+) -> eyre::Result<Vec<BlockReads>> {
+    let provider = provider_factory.provider()?;
+    (0..=tip)
+        .map(|block| {
+            let tx_range = provider
+                .block_body_indices(block)?
+                .ok_or(ProviderError::BlockBodyIndicesNotFound(block))?
+                .tx_num_range();
+            let state = provider_factory.history_by_block_number(block)?;
+            Ok(BlockReads {
+                hash: provider.block_hash(block)?,
+                senders: provider.senders_by_tx_range(tx_range.clone())?,
+                tx_numbers: provider
+                    .transactions_by_tx_range(tx_range)?
+                    .iter()
+                    .map(|tx| provider.transaction_id(*tx.tx_hash()))
+                    .collect::<Result<_, _>>()?,
+                receipts: provider.receipts_by_block(block.into())?,
+                recipient: state.basic_account(&Address::with_last_byte(block as u8))?,
+                slots: [
+                    state.storage(STORAGE_CONTRACT, B256::ZERO)?,
+                    state.storage(STORAGE_CONTRACT, U256::from(block).into())?,
+                ],
+            })
+        })
+        .collect()
+}
+
+/// Computes the state root from the hashed state tables alone, ignoring the stored trie nodes.
+pub(crate) fn hashed_state_root(provider: &impl StateCursorProvider) -> eyre::Result<B256> {
+    Ok(StateRoot::new(NoopTrieCursorFactory, StateCursorFactory::new(provider)).root()?)
+}
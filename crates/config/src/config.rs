@@ -415,12 +415,30 @@ impl EtlConfig {
 }
 
 /// Static files configuration.
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(default))]
 pub struct StaticFilesConfig {
     /// Number of blocks per file for each segment.
     pub blocks_per_file: BlocksPerFileConfig,
+    /// Minimum number of blocks between two state snapshots.
+    ///
+    /// State snapshots are only written on nodes using the v2 storage layout, [`None`] disables
+    /// them.
+    pub state_snapshot_interval: Option<u64>,
+    /// Storage tiers that finished static files are moved to once they are old enough.
+    pub tiers: Vec<StaticFileTierConfig>,
+}
+
+/// Configuration of a storage tier for old static files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StaticFileTierConfig {
+    /// Directory of the tier, usually on a slower and larger volume.
+    pub path: PathBuf,
+    /// Number of blocks between the end of a static file and the highest block of its segment
+    /// before the file is moved to the tier.
+    pub min_age: u64,
 }
 
 /// Configuration for the number of blocks per file for each segment.
@@ -440,12 +458,15 @@ pub struct BlocksPerFileConfig {
     pub account_change_sets: Option<u64>,
     /// Number of blocks per file for the storage changesets segment.
     pub storage_change_sets: Option<u64>,
+    /// Number of blocks per file for the state snapshots segment.
+    pub state_snapshots: Option<u64>,
 }
 
 impl StaticFilesConfig {
     /// Validates the static files configuration.
     ///
-    /// Returns an error if any blocks per file value is zero.
+    /// Returns an error if any blocks per file value is zero, or if a storage tier has no minimum
+    /// age or shares its directory with another one.
     pub fn validate(&self) -> eyre::Result<()> {
         let BlocksPerFileConfig {
             headers,
@@ -454,6 +475,7 @@ impl StaticFilesConfig {
             transaction_senders,
             account_change_sets,
             storage_change_sets,
+            state_snapshots,
         } = self.blocks_per_file;
         eyre::ensure!(headers != Some(0), "Headers segment blocks per file must be greater than 0");
         eyre::ensure!(
@@ -476,6 +498,22 @@ impl StaticFilesConfig {
             storage_change_sets != Some(0),
             "Storage changesets segment blocks per file must be greater than 0"
         );
+        eyre::ensure!(
+            state_snapshots != Some(0),
+            "State snapshots segment blocks per file must be greater than 0"
+        );
+        eyre::ensure!(
+            self.state_snapshot_interval != Some(0),
+            "State snapshot interval must be greater than 0"
+        );
+        for (index, tier) in self.tiers.iter().enumerate() {
+            eyre::ensure!(tier.min_age > 0, "Static file tier minimum age must be greater than 0");
+            eyre::ensure!(
+                self.tiers[..index].iter().all(|other| other.path != tier.path),
+                "Static file tier directory {} is configured more than once",
+                tier.path.display()
+            );
+        }
         Ok(())
     }
 
@@ -488,6 +526,7 @@ impl StaticFilesConfig {
             transaction_senders,
             account_change_sets,
             storage_change_sets,
+            state_snapshots,
         } = self.blocks_per_file;
 
         let mut map = StaticFileMap::default();
@@ -501,6 +540,7 @@ impl StaticFilesConfig {
                 StaticFileSegment::TransactionSenders => transaction_senders,
                 StaticFileSegment::AccountChangeSets => account_change_sets,
                 StaticFileSegment::StorageChangeSets => storage_change_sets,
+                StaticFileSegment::StateSnapshots => state_snapshots,
             };
 
             if let Some(blocks_per_file) = blocks_per_file {
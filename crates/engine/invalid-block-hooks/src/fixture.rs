@@ -0,0 +1,362 @@
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{Address, Bloom, Bytes, B256, B64, U256};
+use hanzo_evm_engine_primitives::InvalidBlockHook;
+use hanzo_evm_execution::{execute::Executor, ConfigureEvm};
+use hanzo_evm_primitives_traits::{NodePrimitives, RecoveredBlock, SealedHeader};
+use hanzo_evm_provider::{AccountReader, BlockExecutionOutput, StateProvider, StateProviderFactory};
+use hanzo_evm_revm::database::StateProviderDatabase;
+use hanzo_evm_tracing::tracing::{debug, warn};
+use hanzo_evm_trie::updates::TrieUpdates;
+use revm::primitives::hardfork::SpecId;
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::File,
+    io::Write,
+    path::PathBuf,
+};
+
+/// A blockchain test in the format of the `testing/ef-tests` runner.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BlockchainTestFixture {
+    /// The parent of the invalid block, used as the genesis block of the test.
+    genesis_block_header: FixtureHeader,
+    /// The invalid block.
+    blocks: Vec<FixtureBlock>,
+    /// The parent state of all accounts and storage slots that the block touches.
+    pre: BTreeMap<Address, FixtureAccount>,
+    /// Hash of the invalid block.
+    lastblockhash: B256,
+    /// Network spec.
+    network: &'static str,
+    /// Engine spec.
+    seal_engine: &'static str,
+}
+
+/// A block header in the format of the `testing/ef-tests` runner.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FixtureHeader {
+    bloom: Bloom,
+    coinbase: Address,
+    difficulty: U256,
+    extra_data: Bytes,
+    gas_limit: U256,
+    gas_used: U256,
+    hash: B256,
+    mix_hash: B256,
+    nonce: B64,
+    number: U256,
+    parent_hash: B256,
+    receipt_trie: B256,
+    state_root: B256,
+    timestamp: U256,
+    transactions_trie: B256,
+    uncle_hash: B256,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    base_fee_per_gas: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    withdrawals_root: Option<B256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    blob_gas_used: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    excess_blob_gas: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_beacon_block_root: Option<B256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    requests_hash: Option<B256>,
+}
+
+impl FixtureHeader {
+    fn new<H: BlockHeader>(header: &H, hash: B256) -> Self {
+        Self {
+            bloom: header.logs_bloom(),
+            coinbase: header.beneficiary(),
+            difficulty: header.difficulty(),
+            extra_data: header.extra_data().clone(),
+            gas_limit: U256::from(header.gas_limit()),
+            gas_used: U256::from(header.gas_used()),
+            hash,
+            mix_hash: header.mix_hash().unwrap_or_default(),
+            nonce: header.nonce().unwrap_or_default(),
+            number: U256::from(header.number()),
+            parent_hash: header.parent_hash(),
+            receipt_trie: header.receipts_root(),
+            state_root: header.state_root(),
+            timestamp: U256::from(header.timestamp()),
+            transactions_trie: header.transactions_root(),
+            uncle_hash: header.ommers_hash(),
+            base_fee_per_gas: header.base_fee_per_gas().map(U256::from),
+            withdrawals_root: header.withdrawals_root(),
+            blob_gas_used: header.blob_gas_used().map(U256::from),
+            excess_blob_gas: header.excess_blob_gas().map(U256::from),
+            parent_beacon_block_root: header.parent_beacon_block_root(),
+            requests_hash: header.requests_hash(),
+        }
+    }
+}
+
+/// A block in the format of the `testing/ef-tests` runner.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FixtureBlock {
+    block_header: FixtureHeader,
+    rlp: Bytes,
+}
+
+/// An account in the format of the `testing/ef-tests` runner.
+#[derive(Debug, Serialize)]
+struct FixtureAccount {
+    balance: U256,
+    code: Bytes,
+    nonce: U256,
+    storage: BTreeMap<U256, U256>,
+}
+
+/// Returns the name of the `testing/ef-tests` network that the block was executed with.
+fn network_for_spec(spec: SpecId) -> eyre::Result<&'static str> {
+    Ok(match spec {
+        SpecId::FRONTIER | SpecId::FRONTIER_THAWING => "Frontier",
+        SpecId::HOMESTEAD | SpecId::DAO_FORK => "Homestead",
+        SpecId::TANGERINE => "EIP150",
+        SpecId::SPURIOUS_DRAGON => "EIP158",
+        SpecId::BYZANTIUM => "Byzantium",
+        SpecId::CONSTANTINOPLE => "Constantinople",
+        SpecId::PETERSBURG => "ConstantinopleFix",
+        SpecId::ISTANBUL | SpecId::MUIR_GLACIER => "Istanbul",
+        SpecId::BERLIN => "Berlin",
+        SpecId::LONDON | SpecId::ARROW_GLACIER | SpecId::GRAY_GLACIER => "London",
+        SpecId::MERGE => "Merge",
+        SpecId::SHANGHAI => "Shanghai",
+        SpecId::CANCUN => "Cancun",
+        SpecId::PRAGUE => "Prague",
+        SpecId::OSAKA => "Osaka",
+        spec => eyre::bail!("spec {spec:?} has no `testing/ef-tests` network"),
+    })
+}
+
+/// Hook for writing invalid blocks as self-contained blockchain test fixtures.
+///
+/// The fixture uses the parent of the invalid block as the genesis block and contains the parent
+/// state of all accounts and storage slots that the block touches, so it can be run with the
+/// `testing/ef-tests` runner without a database.
+///
+/// The pre-state is only a subset of the parent state, so the state root of the parent header
+/// doesn't match it. Hashes of older blocks that are read with `BLOCKHASH` are not included.
+#[derive(Debug)]
+pub struct InvalidBlockFixtureHook<P, E> {
+    /// The provider to read the historical state and do the EVM execution.
+    provider: P,
+    /// The EVM configuration to use for the execution.
+    hanzo_evm_config: E,
+    /// The directory to write the fixtures to.
+    output_directory: PathBuf,
+}
+
+impl<P, E> InvalidBlockFixtureHook<P, E> {
+    /// Creates a new fixture hook.
+    pub const fn new(provider: P, hanzo_evm_config: E, output_directory: PathBuf) -> Self {
+        Self { provider, hanzo_evm_config, output_directory }
+    }
+}
+
+impl<P, E, N> InvalidBlockFixtureHook<P, E>
+where
+    P: StateProviderFactory + Send + Sync + 'static,
+    E: ConfigureEvm<Primitives = N> + 'static,
+    N: NodePrimitives,
+{
+    /// Re-executes the block and returns the storage slots of all accounts it touched.
+    ///
+    /// If the execution fails, the accounts that were loaded before the failure are returned.
+    fn touched_accounts(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+    ) -> eyre::Result<BTreeMap<Address, BTreeSet<U256>>> {
+        let mut executor = self.hanzo_evm_config.batch_executor(StateProviderDatabase::new(
+            self.provider.state_by_block_hash(parent_header.hash())?,
+        ));
+        if let Err(err) = executor.execute_one(block) {
+            debug!(
+                target: "engine::invalid_block_hooks::fixture",
+                %err,
+                "Failed to re-execute block, the pre-state is incomplete"
+            );
+        }
+        let db = executor.into_state();
+
+        let mut touched = BTreeMap::<Address, BTreeSet<U256>>::new();
+        for (address, account) in &db.cache.accounts {
+            let slots = touched.entry(*address).or_default();
+            if let Some(account) = &account.account {
+                slots.extend(account.storage.keys().copied());
+            }
+        }
+        for (address, account) in &db.bundle_state.state {
+            touched.entry(*address).or_default().extend(account.storage.keys().copied());
+        }
+
+        Ok(touched)
+    }
+
+    /// Reads the touched accounts and storage slots from the parent state.
+    fn pre_state(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        touched: BTreeMap<Address, BTreeSet<U256>>,
+    ) -> eyre::Result<BTreeMap<Address, FixtureAccount>> {
+        let state_provider = self.provider.state_by_block_hash(parent_header.hash())?;
+
+        let mut pre = BTreeMap::new();
+        for (address, slots) in touched {
+            let Some(account) = state_provider.basic_account(&address)? else { continue };
+            let code = state_provider
+                .account_code(&address)?
+                .map(|code| code.original_bytes())
+                .unwrap_or_default();
+
+            let mut storage = BTreeMap::new();
+            for slot in slots {
+                let value = state_provider.storage(address, B256::from(slot))?.unwrap_or_default();
+                if !value.is_zero() {
+                    storage.insert(slot, value);
+                }
+            }
+
+            pre.insert(
+                address,
+                FixtureAccount {
+                    balance: account.balance,
+                    code,
+                    nonce: U256::from(account.nonce),
+                    storage,
+                },
+            );
+        }
+
+        Ok(pre)
+    }
+
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+    ) -> eyre::Result<()> {
+        let touched = self.touched_accounts(parent_header, block)?;
+        let pre = self.pre_state(parent_header, touched)?;
+
+        let spec = self.hanzo_evm_config.evm_env(block.header())?.cfg_env.spec.into();
+        let block_prefix = format!("{}_{}", block.number(), block.hash());
+        let fixture = BlockchainTestFixture {
+            genesis_block_header: FixtureHeader::new(parent_header.header(), parent_header.hash()),
+            blocks: vec![FixtureBlock {
+                block_header: FixtureHeader::new(block.header(), block.hash()),
+                rlp: alloy_rlp::encode(block.sealed_block()).into(),
+            }],
+            pre,
+            lastblockhash: block.hash(),
+            network: network_for_spec(spec)?,
+            seal_engine: "NoProof",
+        };
+
+        let path = self.output_directory.join(format!("{block_prefix}.fixture.json"));
+        let tests = BTreeMap::from([(block_prefix, fixture)]);
+        File::create(&path)?.write_all(serde_json::to_string_pretty(&tests)?.as_bytes())?;
+
+        warn!(
+            target: "engine::invalid_block_hooks::fixture",
+            path = %path.display(),
+            "Saved invalid block fixture"
+        );
+
+        Ok(())
+    }
+}
+
+impl<P, E, N: NodePrimitives> InvalidBlockHook<N> for InvalidBlockFixtureHook<P, E>
+where
+    P: StateProviderFactory + Send + Sync + 'static,
+    E: ConfigureEvm<Primitives = N> + 'static,
+{
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+        _output: &BlockExecutionOutput<N::Receipt>,
+        _trie_updates: Option<(&TrieUpdates, B256)>,
+    ) {
+        if let Err(err) = self.on_invalid_block(parent_header, block) {
+            warn!(target: "engine::invalid_block_hooks::fixture", %err, "Failed to invoke hook");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rlp::Decodable;
+    use hanzo_evm_chainspec::ChainSpec;
+    use hanzo_evm_eth_execution::EthEvmConfig;
+    use hanzo_evm_ethereum_primitives::{Block, EthPrimitives};
+    use hanzo_evm_primitives_traits::SealedBlock;
+    use hanzo_evm_provider::test_utils::MockEthProvider;
+    use hanzo_evm_testing_utils::generators::{self, random_block, BlockParams};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_fixture() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let output_directory = temp_dir.path().to_path_buf();
+        let hook = InvalidBlockFixtureHook::new(
+            MockEthProvider::<EthPrimitives, ChainSpec>::default(),
+            EthEvmConfig::mainnet(),
+            output_directory.clone(),
+        );
+
+        let mut rng = generators::rng();
+        let parent_header = generators::random_header(&mut rng, 1, None);
+        let recovered_block = random_block(
+            &mut rng,
+            2,
+            BlockParams {
+                parent: Some(parent_header.hash()),
+                tx_count: Some(0),
+                ..Default::default()
+            },
+        )
+        .try_recover()
+        .unwrap();
+
+        hook.on_invalid_block(&parent_header, &recovered_block).unwrap();
+
+        let block_prefix = format!("{}_{}", recovered_block.number(), recovered_block.hash());
+        let path = output_directory.join(format!("{block_prefix}.fixture.json"));
+        let tests: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        let fixture = &tests[&block_prefix];
+
+        assert_eq!(
+            fixture["genesisBlockHeader"]["hash"],
+            serde_json::to_value(parent_header.hash()).unwrap()
+        );
+        assert_eq!(
+            fixture["lastblockhash"],
+            serde_json::to_value(recovered_block.hash()).unwrap()
+        );
+        assert_eq!(fixture["sealEngine"], "NoProof");
+        assert_eq!(fixture["network"], "Frontier");
+
+        let rlp: Bytes = serde_json::from_value(fixture["blocks"][0]["rlp"].clone()).unwrap();
+        let decoded = SealedBlock::<Block>::decode(&mut rlp.as_ref()).unwrap();
+        assert_eq!(decoded.hash(), recovered_block.hash());
+    }
+
+    #[test]
+    fn test_network_for_spec() {
+        assert_eq!(network_for_spec(SpecId::PRAGUE).unwrap(), "Prague");
+        assert_eq!(network_for_spec(SpecId::OSAKA).unwrap(), "Osaka");
+        assert_eq!(network_for_spec(SpecId::GRAY_GLACIER).unwrap(), "London");
+    }
+}
@@ -1,5 +1,11 @@
 //! Invalid block hook implementations.
 
+mod fixture;
+mod trace;
+mod webhook;
 mod witness;
 
+pub use fixture::InvalidBlockFixtureHook;
+pub use trace::InvalidBlockTraceHook;
+pub use webhook::{InvalidBlockReport, InvalidBlockWebhookHook};
 pub use witness::InvalidBlockWitnessHook;
@@ -0,0 +1,290 @@
+use alloy_consensus::{transaction::TxHashRef, BlockHeader};
+use alloy_primitives::B256;
+use alloy_rpc_types_trace::geth::{
+    CallConfig, GethDebugBuiltInTracerType, GethDebugTracerType, GethDebugTracingOptions,
+    PreStateConfig, TraceResult,
+};
+use hanzo_evm_engine_primitives::InvalidBlockHook;
+use hanzo_evm_execution::{block::BlockExecutor, ConfigureEvm, Evm};
+use hanzo_evm_primitives_traits::{BlockBody, NodePrimitives, RecoveredBlock, SealedHeader};
+use hanzo_evm_provider::{BlockExecutionOutput, StateProviderFactory};
+use hanzo_evm_revm::{database::StateProviderDatabase, db::State};
+use hanzo_evm_tracing::tracing::warn;
+use hanzo_evm_trie::updates::TrieUpdates;
+use revm::DatabaseCommit;
+use revm_inspectors::tracing::{DebugInspector, TransactionContext};
+use serde::Serialize;
+use std::{fs::File, io::Write, path::PathBuf};
+
+/// Hook for writing geth-style traces of invalid blocks.
+///
+/// The block is re-executed on top of the state of its parent and the output of the `callTracer`
+/// and the `prestateTracer` is written for every transaction, in the same format as
+/// `debug_traceBlock` returns it.
+#[derive(Debug)]
+pub struct InvalidBlockTraceHook<P, E> {
+    /// The provider to read the historical state and do the EVM execution.
+    provider: P,
+    /// The EVM configuration to use for the execution.
+    hanzo_evm_config: E,
+    /// The directory to write the traces to.
+    output_directory: PathBuf,
+}
+
+impl<P, E> InvalidBlockTraceHook<P, E> {
+    /// Creates a new trace hook.
+    pub const fn new(provider: P, hanzo_evm_config: E, output_directory: PathBuf) -> Self {
+        Self { provider, hanzo_evm_config, output_directory }
+    }
+}
+
+impl<P, E, N> InvalidBlockTraceHook<P, E>
+where
+    P: StateProviderFactory + Send + Sync + 'static,
+    E: ConfigureEvm<Primitives = N> + 'static,
+    N: NodePrimitives,
+{
+    /// Re-executes the block on top of the parent state and traces every transaction with the
+    /// given tracer.
+    ///
+    /// If a transaction fails to execute, an error is recorded for it and the remaining
+    /// transactions are not traced, since their state would be undefined.
+    fn trace_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+        opts: GethDebugTracingOptions,
+    ) -> eyre::Result<Vec<TraceResult>> {
+        let state = self.provider.state_by_block_hash(parent_header.hash())?;
+        let mut db = State::builder().with_database(StateProviderDatabase::new(state)).build();
+
+        // system calls are not part of any transaction trace
+        self.hanzo_evm_config
+            .executor_for_block(&mut db, block.sealed_block())?
+            .apply_pre_execution_changes()?;
+
+        let evm_env = self.hanzo_evm_config.evm_env(block.header())?;
+        let block_env = evm_env.block_env.clone();
+        let inspector = DebugInspector::new(opts)?;
+        let mut evm = self.hanzo_evm_config.evm_with_env_and_inspector(&mut db, evm_env, inspector);
+
+        let mut results = Vec::with_capacity(block.body().transactions().len());
+        for (index, tx) in block.transactions_recovered().enumerate() {
+            let tx_hash = *tx.tx_hash();
+            let tx_env = self.hanzo_evm_config.tx_env(tx);
+            let res = match evm.transact(tx_env.clone()) {
+                Ok(res) => res,
+                Err(err) => {
+                    results.push(TraceResult::Error {
+                        error: err.to_string(),
+                        tx_hash: Some(tx_hash),
+                    });
+                    break
+                }
+            };
+
+            let (db, inspector, _) = evm.components_mut();
+            let result = inspector.get_result(
+                Some(TransactionContext {
+                    block_hash: Some(block.hash()),
+                    tx_hash: Some(tx_hash),
+                    tx_index: Some(index),
+                }),
+                &tx_env,
+                &block_env,
+                &res,
+                db,
+            )?;
+            results.push(TraceResult::Success { result, tx_hash: Some(tx_hash) });
+
+            // need to apply the state changes of this transaction before executing the next
+            // transaction
+            inspector.fuse()?;
+            db.commit(res.state);
+        }
+
+        Ok(results)
+    }
+
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+    ) -> eyre::Result<()> {
+        let block_prefix = format!("{}_{}", block.number(), block.hash());
+
+        let call_traces = self.trace_block(parent_header, block, call_tracer_options())?;
+        let call_path = self.save_file(format!("{block_prefix}.trace.call.json"), &call_traces)?;
+
+        let prestate_traces = self.trace_block(parent_header, block, prestate_tracer_options())?;
+        let prestate_path =
+            self.save_file(format!("{block_prefix}.trace.prestate.json"), &prestate_traces)?;
+
+        warn!(
+            target: "engine::invalid_block_hooks::trace",
+            call_path = %call_path.display(),
+            prestate_path = %prestate_path.display(),
+            "Traced invalid block"
+        );
+
+        Ok(())
+    }
+
+    /// Serializes and saves a value to a JSON file in the output directory
+    fn save_file<T: Serialize>(&self, filename: String, value: &T) -> eyre::Result<PathBuf> {
+        let path = self.output_directory.join(filename);
+        File::create(&path)?.write_all(serde_json::to_string(value)?.as_bytes())?;
+
+        Ok(path)
+    }
+}
+
+impl<P, E, N: NodePrimitives> InvalidBlockHook<N> for InvalidBlockTraceHook<P, E>
+where
+    P: StateProviderFactory + Send + Sync + 'static,
+    E: ConfigureEvm<Primitives = N> + 'static,
+{
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+        _output: &BlockExecutionOutput<N::Receipt>,
+        _trie_updates: Option<(&TrieUpdates, B256)>,
+    ) {
+        if let Err(err) = self.on_invalid_block(parent_header, block) {
+            warn!(target: "engine::invalid_block_hooks::trace", %err, "Failed to invoke hook");
+        }
+    }
+}
+
+/// Returns the options of the `callTracer` with logs.
+fn call_tracer_options() -> GethDebugTracingOptions {
+    GethDebugTracingOptions::default()
+        .with_tracer(GethDebugTracerType::BuiltInTracer(GethDebugBuiltInTracerType::CallTracer))
+        .with_call_config(CallConfig::default().with_log())
+}
+
+/// Returns the options of the `prestateTracer` in prestate mode.
+fn prestate_tracer_options() -> GethDebugTracingOptions {
+    GethDebugTracingOptions::default()
+        .with_tracer(GethDebugTracerType::BuiltInTracer(GethDebugBuiltInTracerType::PreStateTracer))
+        .with_prestate_config(PreStateConfig::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Header, TxLegacy};
+    use alloy_primitives::{Address, TxKind, U256};
+    use hanzo_evm_chainspec::ChainSpec;
+    use hanzo_evm_eth_execution::EthEvmConfig;
+    use hanzo_evm_ethereum_primitives::{
+        Block, BlockBody as EthBlockBody, EthPrimitives, Transaction, TransactionSigned,
+    };
+    use hanzo_evm_primitives_traits::Block as _;
+    use hanzo_evm_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use hanzo_evm_testing_utils::generators::{
+        self, generate_key, random_block, sign_tx_with_key_pair, BlockParams,
+    };
+    use secp256k1::Keypair;
+    use tempfile::TempDir;
+
+    /// Returns a signed value transfer from the given key.
+    fn transfer(key: Keypair, nonce: u64) -> TransactionSigned {
+        sign_tx_with_key_pair(
+            key,
+            Transaction::Legacy(TxLegacy {
+                chain_id: None,
+                nonce,
+                gas_price: 1,
+                gas_limit: 21_000,
+                to: TxKind::Call(Address::repeat_byte(0x42)),
+                value: U256::from(1),
+                input: Default::default(),
+            }),
+        )
+    }
+
+    #[test]
+    fn test_trace_empty_block() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let output_directory = temp_dir.path().to_path_buf();
+        let hook = InvalidBlockTraceHook::new(
+            MockEthProvider::<EthPrimitives, ChainSpec>::default(),
+            EthEvmConfig::mainnet(),
+            output_directory.clone(),
+        );
+
+        let mut rng = generators::rng();
+        let parent_header = generators::random_header(&mut rng, 1, None);
+        let recovered_block = random_block(
+            &mut rng,
+            2,
+            BlockParams {
+                parent: Some(parent_header.hash()),
+                tx_count: Some(0),
+                ..Default::default()
+            },
+        )
+        .try_recover()
+        .unwrap();
+
+        hook.on_invalid_block(&parent_header, &recovered_block).unwrap();
+
+        let block_prefix = format!("{}_{}", recovered_block.number(), recovered_block.hash());
+        for suffix in ["trace.call.json", "trace.prestate.json"] {
+            let path = output_directory.join(format!("{block_prefix}.{suffix}"));
+            let traces: Vec<serde_json::Value> =
+                serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+            assert!(traces.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_trace_stops_at_failed_transaction() {
+        let provider = MockEthProvider::<EthPrimitives, ChainSpec>::default();
+        let hook = InvalidBlockTraceHook::new(
+            provider.clone(),
+            EthEvmConfig::mainnet(),
+            TempDir::new().unwrap().path().to_path_buf(),
+        );
+
+        let mut rng = generators::rng();
+        let parent_header = generators::random_header(&mut rng, 1, None);
+        let funded = generate_key(&mut rng);
+        let unfunded = generate_key(&mut rng);
+        // the second transaction can't pay for its gas, so the third one is never traced
+        let transactions = vec![transfer(funded, 0), transfer(unfunded, 0), transfer(funded, 1)];
+        let block = Block {
+            header: Header {
+                parent_hash: parent_header.hash(),
+                number: 2,
+                gas_limit: 1_000_000,
+                ..Default::default()
+            },
+            body: EthBlockBody { transactions, ..Default::default() },
+        }
+        .seal_slow()
+        .try_recover()
+        .unwrap();
+        provider.add_account(
+            block.senders()[0],
+            ExtendedAccount::new(0, U256::from(1_000_000_000_000u64)),
+        );
+
+        let tx_hashes =
+            block.body().transactions().iter().map(|tx| *tx.tx_hash()).collect::<Vec<_>>();
+        for opts in [call_tracer_options(), prestate_tracer_options()] {
+            let traces = hook.trace_block(&parent_header, &block, opts).unwrap();
+            assert_eq!(traces.len(), 2);
+            assert!(matches!(
+                &traces[0],
+                TraceResult::Success { tx_hash, .. } if *tx_hash == Some(tx_hashes[0])
+            ));
+            assert!(matches!(
+                &traces[1],
+                TraceResult::Error { tx_hash, .. } if *tx_hash == Some(tx_hashes[1])
+            ));
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use alloy_consensus::BlockHeader;
+use alloy_primitives::B256;
+use hanzo_evm_engine_primitives::InvalidBlockHook;
+use hanzo_evm_primitives_traits::{BlockBody, NodePrimitives, RecoveredBlock, SealedHeader};
+use hanzo_evm_provider::BlockExecutionOutput;
+use hanzo_evm_tracing::tracing::{debug, warn};
+use hanzo_evm_trie::updates::TrieUpdates;
+use serde::{Deserialize, Serialize};
+use tokio::runtime::Handle;
+
+/// The report that is posted to the webhook for every invalid block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvalidBlockReport {
+    /// Number of the invalid block.
+    pub number: u64,
+    /// Hash of the invalid block.
+    pub hash: B256,
+    /// Hash of the parent block.
+    pub parent_hash: B256,
+    /// Timestamp of the invalid block.
+    pub timestamp: u64,
+    /// Number of transactions in the invalid block.
+    pub transaction_count: usize,
+    /// Gas used according to the block header.
+    pub header_gas_used: u64,
+    /// Gas used by the local execution.
+    pub executed_gas_used: u64,
+    /// State root of the block header.
+    pub header_state_root: B256,
+    /// State root computed after the local execution, if it got that far.
+    pub computed_state_root: Option<B256>,
+    /// Number of receipts of the local execution.
+    pub receipt_count: usize,
+    /// Number of accounts changed by the local execution.
+    pub changed_account_count: usize,
+}
+
+impl InvalidBlockReport {
+    /// Creates the report of an invalid block from its local execution output.
+    pub fn new<N: NodePrimitives>(
+        block: &RecoveredBlock<N::Block>,
+        output: &BlockExecutionOutput<N::Receipt>,
+        trie_updates: Option<(&TrieUpdates, B256)>,
+    ) -> Self {
+        Self {
+            number: block.number(),
+            hash: block.hash(),
+            parent_hash: block.parent_hash(),
+            timestamp: block.timestamp(),
+            transaction_count: block.body().transactions().len(),
+            header_gas_used: block.gas_used(),
+            executed_gas_used: output.result.gas_used,
+            header_state_root: block.state_root(),
+            computed_state_root: trie_updates.map(|(_, state_root)| state_root),
+            receipt_count: output.result.receipts.len(),
+            changed_account_count: output.state.state.len(),
+        }
+    }
+}
+
+/// Hook for posting a report of invalid blocks to a webhook.
+///
+/// The [`InvalidBlockReport`] is posted as JSON from a task on the given runtime, so an
+/// unreachable webhook doesn't block the engine.
+#[derive(Debug, Clone)]
+pub struct InvalidBlockWebhookHook {
+    /// The URL the reports are posted to.
+    url: String,
+    /// The client that posts the reports, shared by all of them.
+    client: reqwest::Client,
+    /// The runtime the reports are posted on.
+    runtime: Handle,
+}
+
+impl InvalidBlockWebhookHook {
+    /// Creates a new webhook hook that posts the reports on the given runtime.
+    pub fn new(url: String, runtime: Handle) -> Self {
+        Self { url, client: reqwest::Client::new(), runtime }
+    }
+
+    /// Posts the report to the webhook.
+    async fn post(
+        client: &reqwest::Client,
+        url: &str,
+        report: &InvalidBlockReport,
+    ) -> eyre::Result<()> {
+        client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(serde_json::to_vec(report)?)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+impl<N: NodePrimitives> InvalidBlockHook<N> for InvalidBlockWebhookHook {
+    fn on_invalid_block(
+        &self,
+        _parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+        output: &BlockExecutionOutput<N::Receipt>,
+        trie_updates: Option<(&TrieUpdates, B256)>,
+    ) {
+        let report = InvalidBlockReport::new::<N>(block, output, trie_updates);
+        let client = self.client.clone();
+        let url = self.url.clone();
+        self.runtime.spawn(async move {
+            match Self::post(&client, &url, &report).await {
+                Ok(()) => debug!(
+                    target: "engine::invalid_block_hooks::webhook",
+                    number = report.number,
+                    hash = %report.hash,
+                    "Posted invalid block report"
+                ),
+                Err(err) => warn!(
+                    target: "engine::invalid_block_hooks::webhook",
+                    %err,
+                    "Failed to post invalid block report"
+                ),
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::eip7685::Requests;
+    use hanzo_evm_ethereum_primitives::EthPrimitives;
+    use hanzo_evm_provider::BlockExecutionResult;
+    use hanzo_evm_testing_utils::generators::{self, random_block, BlockParams};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    #[test]
+    fn test_invalid_block_report() {
+        let mut rng = generators::rng();
+        let block =
+            random_block(&mut rng, 2, BlockParams { tx_count: Some(2), ..Default::default() })
+                .try_recover()
+                .unwrap();
+        let output = BlockExecutionOutput {
+            state: Default::default(),
+            result: BlockExecutionResult {
+                receipts: vec![],
+                requests: Requests::default(),
+                gas_used: 21_000,
+                blob_gas_used: 0,
+            },
+        };
+        let computed_state_root = B256::random();
+
+        let report = InvalidBlockReport::new::<EthPrimitives>(
+            &block,
+            &output,
+            Some((&TrieUpdates::default(), computed_state_root)),
+        );
+        assert_eq!(report.number, 2);
+        assert_eq!(report.hash, block.hash());
+        assert_eq!(report.transaction_count, 2);
+        assert_eq!(report.executed_gas_used, 21_000);
+        assert_eq!(report.computed_state_root, Some(computed_state_root));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["executedGasUsed"], 21_000);
+        assert_eq!(serde_json::from_value::<InvalidBlockReport>(json).unwrap(), report);
+    }
+
+    #[tokio::test]
+    async fn test_post_invalid_block_report() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let hook = InvalidBlockWebhookHook::new(
+            format!("http://{}", listener.local_addr().unwrap()),
+            Handle::current(),
+        );
+
+        let mut rng = generators::rng();
+        let parent_header = generators::random_header(&mut rng, 1, None);
+        let block = random_block(
+            &mut rng,
+            2,
+            BlockParams { parent: Some(parent_header.hash()), ..Default::default() },
+        )
+        .try_recover()
+        .unwrap();
+        let output = BlockExecutionOutput::<hanzo_evm_ethereum_primitives::Receipt>::default();
+        InvalidBlockHook::<EthPrimitives>::on_invalid_block(
+            &hook,
+            &parent_header,
+            &block,
+            &output,
+            None,
+        );
+
+        // read the request up to the end of the body and answer it
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let body = loop {
+            let mut buf = [0; 1024];
+            let read = stream.read(&mut buf).await.unwrap();
+            assert_ne!(read, 0, "connection closed before the body was received");
+            request.extend_from_slice(&buf[..read]);
+
+            let text = String::from_utf8_lossy(&request);
+            let Some(headers_end) = text.find("\r\n\r\n") else { continue };
+            let content_length = text[..headers_end]
+                .lines()
+                .find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    if !name.eq_ignore_ascii_case("content-length") {
+                        return None
+                    }
+                    value.trim().parse().ok()
+                })
+                .unwrap_or(0usize);
+            if request.len() >= headers_end + 4 + content_length {
+                break request[headers_end + 4..].to_vec()
+            }
+        };
+        stream.write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n").await.unwrap();
+
+        let report: InvalidBlockReport = serde_json::from_slice(&body).unwrap();
+        assert_eq!(report, InvalidBlockReport::new::<EthPrimitives>(&block, &output, None));
+    }
+}
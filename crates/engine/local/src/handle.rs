@@ -0,0 +1,75 @@
+//! A handle to control a running [`LocalMiner`](super::LocalMiner).
+
+use alloy_eips::BlockNumHash;
+use alloy_primitives::Bytes;
+use eyre::OptionExt;
+use std::sync::{Arc, OnceLock};
+use tokio::sync::{mpsc, oneshot};
+
+/// A command for the [`LocalMiner`](super::LocalMiner).
+#[derive(Debug)]
+pub(crate) enum LocalMinerCommand {
+    /// Replaces the last `depth` blocks with an alternative chain of the same length.
+    Reorg {
+        /// The number of blocks to replace.
+        depth: usize,
+        /// The EIP-2718 encoded transactions the first block of the alternative chain must
+        /// include.
+        transactions: Vec<Bytes>,
+        /// The sender for the outcome.
+        tx: oneshot::Sender<eyre::Result<LocalReorg>>,
+    },
+}
+
+/// The outcome of a reorg of the local dev chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalReorg {
+    /// The blocks that were removed from the canonical chain, in ascending order.
+    pub reorged: Vec<BlockNumHash>,
+    /// The blocks of the alternative chain that replaced them, in ascending order.
+    pub blocks: Vec<BlockNumHash>,
+}
+
+/// A handle to send commands to a running [`LocalMiner`](super::LocalMiner).
+///
+/// The handle can be created and shared before the miner is spawned, commands fail until a miner
+/// is connected with [`LocalMiner::with_handle`](super::LocalMiner::with_handle).
+#[derive(Debug, Clone, Default)]
+pub struct LocalMinerHandle {
+    to_miner: Arc<OnceLock<mpsc::UnboundedSender<LocalMinerCommand>>>,
+}
+
+impl LocalMinerHandle {
+    /// Creates a new handle that is not connected to a miner yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` if a miner is connected to the handle.
+    pub fn is_connected(&self) -> bool {
+        self.to_miner.get().is_some_and(|to_miner| !to_miner.is_closed())
+    }
+
+    /// Connects a miner to the handle and returns the receiver of its commands.
+    ///
+    /// Returns `None` if another miner is already connected.
+    pub(crate) fn connect(&self) -> Option<mpsc::UnboundedReceiver<LocalMinerCommand>> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.to_miner.set(tx).ok()?;
+        Some(rx)
+    }
+
+    /// Replaces the last `depth` blocks of the chain with an alternative chain of the same length
+    /// and makes it canonical.
+    ///
+    /// The transactions are submitted to the pool and included in the first block of the
+    /// alternative chain.
+    pub async fn reorg(&self, depth: usize, transactions: Vec<Bytes>) -> eyre::Result<LocalReorg> {
+        let to_miner = self.to_miner.get().ok_or_eyre("Local miner is not running")?;
+        let (tx, rx) = oneshot::channel();
+        to_miner
+            .send(LocalMinerCommand::Reorg { depth, transactions, tx })
+            .map_err(|_| eyre::eyre!("Local miner is not running"))?;
+        rx.await.map_err(|_| eyre::eyre!("Local miner stopped before the reorg completed"))?
+    }
+}
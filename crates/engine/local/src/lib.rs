@@ -8,8 +8,12 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+pub mod handle;
 pub mod miner;
 pub mod payload;
+pub mod scenario;
 
+pub use handle::{LocalMinerHandle, LocalReorg};
 pub use miner::{LocalMiner, MiningMode};
 pub use payload::LocalPayloadAttributesBuilder;
+pub use scenario::{Scenario, ScenarioStep};
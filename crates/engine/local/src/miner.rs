@@ -1,17 +1,22 @@
 //! Contains the implementation of the mining mode for the local engine.
 
-use alloy_primitives::{TxHash, B256};
+use crate::{
+    handle::{LocalMinerCommand, LocalMinerHandle, LocalReorg},
+    scenario::{Scenario, ScenarioStep},
+};
+use alloy_eips::eip2718::Decodable2718;
+use alloy_primitives::{Bytes, TxHash};
 use alloy_rpc_types_engine::ForkchoiceState;
-use eyre::OptionExt;
+use eyre::{OptionExt, WrapErr};
 use futures_util::{stream::Fuse, Stream, StreamExt};
 use reth_engine_primitives::ConsensusEngineHandle;
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_payload_primitives::{
     BuiltPayload, EngineApiMessageVersion, PayloadAttributesBuilder, PayloadKind, PayloadTypes,
 };
-use hanzo_evm_primitives_traits::{HeaderTy, SealedHeaderFor};
+use hanzo_evm_primitives_traits::{BlockBody, HeaderTy, SealedHeaderFor, SignedTransaction};
 use hanzo_evm_storage_api::BlockReader;
-use hanzo_evm_transaction_pool::TransactionPool;
+use hanzo_evm_transaction_pool::{PoolTransaction, TransactionOrigin, TransactionPool};
 use std::{
     collections::VecDeque,
     fmt,
@@ -20,9 +25,9 @@ use std::{
     task::{Context, Poll},
     time::Duration,
 };
-use tokio::time::Interval;
+use tokio::{sync::mpsc, time::Interval};
 use tokio_stream::wrappers::ReceiverStream;
-use tracing::error;
+use tracing::{debug, error, info, warn};
 
 /// A mining mode for the local dev engine.
 pub enum MiningMode<Pool: TransactionPool + Unpin> {
@@ -48,6 +53,17 @@ pub enum MiningMode<Pool: TransactionPool + Unpin> {
     /// This is a general-purpose trigger that can be fired on demand, for example via a channel
     /// or any other [`Stream`] implementation.
     Trigger(Pin<Box<dyn Stream<Item = ()> + Send + Sync>>),
+    /// In this mode the blocks, their timestamps and transactions, and reorgs are scripted by a
+    /// [`Scenario`].
+    ///
+    /// The scenario is executed once by [`LocalMiner::run_scenario`], afterwards no more blocks
+    /// are built.
+    Scripted {
+        /// The transaction pool the scripted transactions are submitted to.
+        pool: Pool,
+        /// The scenario to execute.
+        scenario: Scenario,
+    },
 }
 
 impl<Pool: TransactionPool + Unpin> fmt::Debug for MiningMode<Pool> {
@@ -60,6 +76,9 @@ impl<Pool: TransactionPool + Unpin> fmt::Debug for MiningMode<Pool> {
                 .finish(),
             Self::Interval(interval) => f.debug_tuple("Interval").field(interval).finish(),
             Self::Trigger(_) => f.debug_tuple("Trigger").finish(),
+            Self::Scripted { scenario, .. } => {
+                f.debug_struct("Scripted").field("steps", &scenario.steps.len()).finish()
+            }
         }
     }
 }
@@ -84,6 +103,11 @@ impl<Pool: TransactionPool + Unpin> MiningMode<Pool> {
     pub fn trigger(trigger: impl Stream<Item = ()> + Send + Sync + 'static) -> Self {
         Self::Trigger(Box::pin(trigger))
     }
+
+    /// Constructor for a [`MiningMode::Scripted`]
+    pub const fn scripted(pool: Pool, scenario: Scenario) -> Self {
+        Self::Scripted { pool, scenario }
+    }
 }
 
 impl<Pool: TransactionPool + Unpin> Future for MiningMode<Pool> {
@@ -124,10 +148,16 @@ impl<Pool: TransactionPool + Unpin> Future for MiningMode<Pool> {
                 }
                 Poll::Pending
             }
+            // the scenario is driven by the miner itself
+            Self::Scripted { .. } => Poll::Pending,
         }
     }
 }
 
+/// The sealed header of a block mined by the [`LocalMiner`].
+type MinedHeader<T> =
+    SealedHeaderFor<<<T as PayloadTypes>::BuiltPayload as BuiltPayload>::Primitives>;
+
 /// Local miner advancing the chain
 #[derive(Debug)]
 pub struct LocalMiner<T: PayloadTypes, B, Pool: TransactionPool + Unpin> {
@@ -139,10 +169,10 @@ pub struct LocalMiner<T: PayloadTypes, B, Pool: TransactionPool + Unpin> {
     mode: MiningMode<Pool>,
     /// The payload builder for the engine
     payload_builder: PayloadBuilderHandle<T>,
-    /// Latest block in the chain so far.
-    last_header: SealedHeaderFor<<T::BuiltPayload as BuiltPayload>::Primitives>,
-    /// Stores latest mined blocks.
-    last_block_hashes: VecDeque<B256>,
+    /// Stores the headers of the latest mined blocks, the last one is the head of the chain.
+    last_headers: VecDeque<SealedHeaderFor<<T::BuiltPayload as BuiltPayload>::Primitives>>,
+    /// The pool and the commands of a connected [`LocalMinerHandle`].
+    commands: Option<(Pool, mpsc::UnboundedReceiver<LocalMinerCommand>)>,
 }
 
 impl<T, B, Pool> LocalMiner<T, B, Pool>
@@ -170,44 +200,194 @@ where
             to_engine,
             mode,
             payload_builder,
-            last_block_hashes: VecDeque::from([last_header.hash()]),
-            last_header,
+            last_headers: VecDeque::from([last_header]),
+            commands: None,
         }
     }
 
+    /// Connects the miner to the given [`LocalMinerHandle`], the transactions of the commands are
+    /// submitted to the given pool.
+    ///
+    /// Does nothing if another miner is already connected to the handle.
+    pub fn with_handle(mut self, handle: &LocalMinerHandle, pool: Pool) -> Self {
+        match handle.connect() {
+            Some(commands) => self.commands = Some((pool, commands)),
+            None => warn!(target: "engine::local", "Local miner handle is already connected"),
+        }
+        self
+    }
+
+    /// Executes the scenario of the [`MiningMode::Scripted`] mode, does nothing in other modes.
+    ///
+    /// Must be called before [`LocalMiner::run`], a failed step is returned as an error.
+    pub async fn run_scenario(&mut self) -> eyre::Result<()> {
+        if let MiningMode::Scripted { pool, scenario } = &mut self.mode {
+            let (pool, scenario) = (pool.clone(), std::mem::take(scenario));
+            self.execute_scenario(&pool, scenario).await.wrap_err("Failed to run the scenario")?;
+        }
+        Ok(())
+    }
+
     /// Runs the [`LocalMiner`] in a loop, polling the miner and building payloads.
+    ///
+    /// Failed blocks, forkchoice updates and commands are logged and the miner keeps running.
     pub async fn run(mut self) {
         let mut fcu_interval = tokio::time::interval(Duration::from_secs(1));
         loop {
             tokio::select! {
                 // Wait for the interval or the pool to receive a transaction
                 _ = &mut self.mode => {
-                    if let Err(e) = self.advance().await {
-                        error!(target: "engine::local", "Error advancing the chain: {:?}", e);
+                    if let Err(err) = self.advance().await {
+                        error!(target: "engine::local", %err, "Failed to advance the chain");
+                    }
+                }
+                // Wait for a command of the handle
+                command = next_command(&mut self.commands) => {
+                    if let Err(err) = self.on_command(command).await {
+                        error!(target: "engine::local", %err, "Failed to execute command");
                     }
                 }
                 // send FCU once in a while
                 _ = fcu_interval.tick() => {
-                    if let Err(e) = self.update_forkchoice_state().await {
-                        error!(target: "engine::local", "Error updating fork choice: {:?}", e);
+                    if let Err(err) = self.update_forkchoice_state().await {
+                        error!(target: "engine::local", %err, "Failed to update fork choice");
                     }
                 }
             }
         }
     }
 
+    /// Executes the steps of the scenario in order.
+    async fn execute_scenario(&mut self, pool: &Pool, scenario: Scenario) -> eyre::Result<()> {
+        info!(target: "engine::local", steps = scenario.steps.len(), "Running scripted scenario");
+
+        for (index, step) in scenario.steps.into_iter().enumerate() {
+            debug!(target: "engine::local", index, ?step, "Running scenario step");
+            let res = match step {
+                ScenarioStep::Block { timestamp, transactions } => {
+                    self.advance_with_transactions(pool, timestamp, transactions).await
+                }
+                ScenarioStep::EmptyBlock { timestamp } => self.advance_empty(timestamp).await,
+                ScenarioStep::Reorg { depth } => self.rewind(depth).await?.map(drop),
+            };
+            res.wrap_err_with(|| format!("Scenario step {index} failed"))?;
+
+            info!(
+                target: "engine::local",
+                index,
+                head = ?self.last_header().num_hash(),
+                "Executed scenario step"
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Executes a command of the [`LocalMinerHandle`].
+    ///
+    /// The outcome of the command is sent to the handle, errors that leave the miner without a
+    /// valid head are returned.
+    async fn on_command(&mut self, command: LocalMinerCommand) -> eyre::Result<()> {
+        match command {
+            LocalMinerCommand::Reorg { depth, transactions, tx } => {
+                let res = self.reorg(depth, transactions).await.wrap_err("Failed to reorg")?;
+                if let Err(Err(err)) = tx.send(res) {
+                    warn!(target: "engine::local", %err, "Reorg failed after the caller left");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Replaces the last `depth` blocks with an alternative chain of the same length that
+    /// includes the given transactions in its first block, and makes it canonical.
+    ///
+    /// The previous head is restored if the alternative chain can't be built, the inner error is
+    /// returned in that case. The outer error is returned if the engine rejects the forkchoice
+    /// update of the new or the restored head.
+    async fn reorg(
+        &mut self,
+        depth: usize,
+        transactions: Vec<Bytes>,
+    ) -> eyre::Result<eyre::Result<LocalReorg>> {
+        let Some((pool, _)) = self.commands.as_ref() else {
+            return Ok(Err(eyre::eyre!("Local miner is not connected")))
+        };
+        let pool = pool.clone();
+        let reorged = match self.rewind(depth).await? {
+            Ok(reorged) => reorged,
+            Err(err) => return Ok(Err(err)),
+        };
+
+        let mut transactions = Some(transactions);
+        let mut blocks = Vec::with_capacity(depth);
+        for _ in 0..depth {
+            let res = match transactions.take() {
+                Some(transactions) => {
+                    self.advance_with_transactions(&pool, None, transactions).await
+                }
+                None => self.advance().await,
+            };
+            if let Err(err) = res {
+                // drop the partial alternative chain and go back to the previous head
+                self.last_headers.truncate(self.last_headers.len() - blocks.len());
+                self.last_headers.extend(reorged);
+                self.update_forkchoice_state().await.wrap_err("Failed to restore the head")?;
+                return Ok(Err(err))
+            }
+            blocks.push(self.last_header().num_hash());
+        }
+
+        self.update_forkchoice_state().await?;
+        info!(
+            target: "engine::local",
+            depth,
+            head = ?self.last_header().num_hash(),
+            "Reorged chain"
+        );
+
+        Ok(Ok(LocalReorg {
+            reorged: reorged.iter().map(|header| header.num_hash()).collect(),
+            blocks,
+        }))
+    }
+
+    /// Moves the head back by `depth` blocks and returns the removed headers.
+    ///
+    /// The following blocks are built on the new head, which requires the engine to process
+    /// payload attributes on canonical heads.
+    ///
+    /// The inner error is returned if the depth is out of range, the outer error if the engine
+    /// rejects the forkchoice update of the new head.
+    async fn rewind(&mut self, depth: usize) -> eyre::Result<eyre::Result<Vec<MinedHeader<T>>>> {
+        if depth == 0 || depth >= self.last_headers.len() {
+            return Ok(Err(eyre::eyre!(
+                "Reorg depth {depth} must be between 1 and the {} known blocks",
+                self.last_headers.len() - 1
+            )))
+        }
+        let reorged = self.last_headers.split_off(self.last_headers.len() - depth);
+        self.update_forkchoice_state().await?;
+        Ok(Ok(reorged.into()))
+    }
+
+    /// Returns the head of the chain.
+    fn last_header(&self) -> &SealedHeaderFor<<T::BuiltPayload as BuiltPayload>::Primitives> {
+        self.last_headers.back().expect("at least 1 block exists")
+    }
+
     /// Returns current forkchoice state.
     fn forkchoice_state(&self) -> ForkchoiceState {
+        let hash_at = |depth: usize| {
+            self.last_headers
+                .get(self.last_headers.len().saturating_sub(depth))
+                .expect("at least 1 block exists")
+                .hash()
+        };
         ForkchoiceState {
-            head_block_hash: *self.last_block_hashes.back().expect("at least 1 block exists"),
-            safe_block_hash: *self
-                .last_block_hashes
-                .get(self.last_block_hashes.len().saturating_sub(32))
-                .expect("at least 1 block exists"),
-            finalized_block_hash: *self
-                .last_block_hashes
-                .get(self.last_block_hashes.len().saturating_sub(64))
-                .expect("at least 1 block exists"),
+            head_block_hash: self.last_header().hash(),
+            safe_block_hash: hash_at(32),
+            finalized_block_hash: hash_at(64),
         }
     }
 
@@ -229,11 +409,54 @@ where
     /// Generates payload attributes for a new block, passes them to FCU and inserts built payload
     /// through newPayload.
     async fn advance(&mut self) -> eyre::Result<()> {
+        let payload = self.build_payload(None).await?;
+        self.insert_payload(payload).await
+    }
+
+    /// Submits the transactions to the pool and builds a block that must include them.
+    async fn advance_with_transactions(
+        &mut self,
+        pool: &Pool,
+        timestamp: Option<u64>,
+        transactions: Vec<Bytes>,
+    ) -> eyre::Result<()> {
+        let hashes = submit_transactions(pool, transactions)
+            .await
+            .wrap_err("Failed to submit transactions")?;
+        let payload = self.build_payload(timestamp).await?;
+        let body = payload.block().body();
+        if let Some(missing) = hashes.iter().find(|hash| !body.contains_transaction(hash)) {
+            eyre::bail!("Block doesn't include transaction {missing}")
+        }
+        self.insert_payload(payload).await
+    }
+
+    /// Builds a block that must not include any transaction.
+    async fn advance_empty(&mut self, timestamp: Option<u64>) -> eyre::Result<()> {
+        let payload = self.build_payload(timestamp).await?;
+        if !payload.block().body().transactions().is_empty() {
+            eyre::bail!("Block includes pending transactions of the pool")
+        }
+        self.insert_payload(payload).await
+    }
+
+    /// Passes the payload attributes for a new block on top of the head to FCU and returns the
+    /// built payload.
+    ///
+    /// The payload attributes builder picks the timestamp if no timestamp is given.
+    async fn build_payload(&self, timestamp: Option<u64>) -> eyre::Result<T::BuiltPayload> {
+        let attributes = match timestamp {
+            Some(timestamp) => self
+                .payload_attributes_builder
+                .build_with_timestamp(self.last_header(), timestamp)
+                .ok_or_eyre("Payload attributes builder doesn't support fixed timestamps")?,
+            None => self.payload_attributes_builder.build(self.last_header()),
+        };
         let res = self
             .to_engine
             .fork_choice_updated(
                 self.forkchoice_state(),
-                Some(self.payload_attributes_builder.build(&self.last_header)),
+                Some(attributes),
                 EngineApiMessageVersion::default(),
             )
             .await?;
@@ -242,6 +465,8 @@ where
             eyre::bail!("Invalid payload status")
         }
 
+        // a block on top of an earlier canonical block requires
+        // `--engine.always-process-payload-attributes-on-canonical-head`
         let payload_id = res.payload_id.ok_or_eyre("No payload id")?;
 
         let Some(Ok(payload)) =
@@ -250,6 +475,11 @@ where
             eyre::bail!("No payload")
         };
 
+        Ok(payload)
+    }
+
+    /// Inserts the built payload through newPayload and makes it the head of the chain.
+    async fn insert_payload(&mut self, payload: T::BuiltPayload) -> eyre::Result<()> {
         let header = payload.block().sealed_header().clone();
         let payload = T::block_to_payload(payload.block().clone());
         let res = self.to_engine.new_payload(payload).await?;
@@ -258,13 +488,50 @@ where
             eyre::bail!("Invalid payload")
         }
 
-        self.last_block_hashes.push_back(header.hash());
-        self.last_header = header;
+        self.last_headers.push_back(header);
         // ensure we keep at most 64 blocks
-        if self.last_block_hashes.len() > 64 {
-            self.last_block_hashes.pop_front();
+        if self.last_headers.len() > 64 {
+            self.last_headers.pop_front();
         }
 
         Ok(())
     }
 }
+
+/// Returns the next command of a connected [`LocalMinerHandle`], pending forever if there is none.
+async fn next_command<Pool>(
+    commands: &mut Option<(Pool, mpsc::UnboundedReceiver<LocalMinerCommand>)>,
+) -> LocalMinerCommand {
+    match commands {
+        Some((_, rx)) => match rx.recv().await {
+            Some(command) => command,
+            None => std::future::pending().await,
+        },
+        None => std::future::pending().await,
+    }
+}
+
+/// Decodes the EIP-2718 encoded transactions and submits them to the pool, returns their hashes.
+///
+/// Transactions that are already in the pool, e.g. because their block was reorged, are skipped.
+async fn submit_transactions<Pool: TransactionPool>(
+    pool: &Pool,
+    transactions: Vec<Bytes>,
+) -> eyre::Result<Vec<TxHash>> {
+    let mut hashes = Vec::with_capacity(transactions.len());
+    for raw in transactions {
+        let tx =
+            <<Pool::Transaction as PoolTransaction>::Consensus as Decodable2718>::decode_2718_exact(
+                &raw,
+            )
+            .map_err(|err| eyre::eyre!("failed to decode transaction: {err}"))?;
+        let tx = SignedTransaction::try_into_recovered(tx)
+            .map_err(|_| eyre::eyre!("failed to recover transaction signer"))?;
+        let hash = *tx.tx_hash();
+        if !pool.contains(&hash) {
+            pool.add_consensus_transaction(tx, TransactionOrigin::Local).await?;
+        }
+        hashes.push(hash);
+    }
+    Ok(hashes)
+}
@@ -0,0 +1,125 @@
+//! Scripted scenarios for the [`LocalMiner`](super::LocalMiner).
+//!
+//! A scenario is a JSON file with a list of steps that are executed in order, for example:
+//!
+//! ```json
+//! {
+//!   "steps": [
+//!     { "type": "block", "timestamp": 1700000012, "transactions": ["0x02f8..."] },
+//!     { "type": "emptyBlock", "timestamp": 1700000024 },
+//!     { "type": "reorg", "depth": 1 },
+//!     { "type": "block", "timestamp": 1700000025 }
+//!   ]
+//! }
+//! ```
+
+use alloy_primitives::Bytes;
+use eyre::WrapErr;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// A scripted scenario of the local dev chain.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Scenario {
+    /// The steps of the scenario, executed in order.
+    pub steps: Vec<ScenarioStep>,
+}
+
+impl Scenario {
+    /// Creates a new scenario with the given steps.
+    pub const fn new(steps: Vec<ScenarioStep>) -> Self {
+        Self { steps }
+    }
+
+    /// Reads the scenario from the JSON file at the given path.
+    pub fn from_path(path: impl AsRef<Path>) -> eyre::Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read(path)
+            .wrap_err_with(|| format!("failed to read scenario file {}", path.display()))?;
+        let scenario: Self = serde_json::from_slice(&contents)
+            .wrap_err_with(|| format!("failed to parse scenario file {}", path.display()))?;
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    /// Checks the steps that can be checked without a chain.
+    fn validate(&self) -> eyre::Result<()> {
+        for (index, step) in self.steps.iter().enumerate() {
+            if let ScenarioStep::Reorg { depth: 0 } = step {
+                eyre::bail!("step {index}: reorg depth must be at least 1")
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A step of a [`Scenario`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ScenarioStep {
+    /// Builds a block on top of the current head that includes the given transactions.
+    ///
+    /// The transactions are submitted to the pool before the block is built. Because the payload
+    /// builder picks transactions from the pool, any other pending transaction of the pool is
+    /// included as well.
+    Block {
+        /// The exact timestamp of the block, the payload attributes builder decides if not set.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timestamp: Option<u64>,
+        /// The EIP-2718 encoded transactions that the block must include.
+        #[serde(default)]
+        transactions: Vec<Bytes>,
+    },
+    /// Builds a block without transactions on top of the current head.
+    ///
+    /// Fails if the pool has pending transactions.
+    EmptyBlock {
+        /// The exact timestamp of the block, the payload attributes builder decides if not set.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timestamp: Option<u64>,
+    },
+    /// Moves the head back by `depth` blocks, the following blocks are built on the ancestor
+    /// and replace the reorged blocks once they are canonical.
+    ///
+    /// Requires the engine to process payload attributes on canonical heads, see
+    /// `--engine.always-process-payload-attributes-on-canonical-head`.
+    Reorg {
+        /// The number of blocks to remove from the head of the chain.
+        depth: usize,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_scenario() {
+        let scenario: Scenario = serde_json::from_str(
+            r#"{
+                "steps": [
+                    { "type": "block", "timestamp": 12, "transactions": ["0x01"] },
+                    { "type": "block" },
+                    { "type": "emptyBlock", "timestamp": 24 },
+                    { "type": "reorg", "depth": 2 }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            scenario,
+            Scenario::new(vec![
+                ScenarioStep::Block {
+                    timestamp: Some(12),
+                    transactions: vec![Bytes::from_static(&[1])]
+                },
+                ScenarioStep::Block { timestamp: None, transactions: vec![] },
+                ScenarioStep::EmptyBlock { timestamp: Some(24) },
+                ScenarioStep::Reorg { depth: 2 },
+            ])
+        );
+        assert!(scenario.validate().is_ok());
+        assert!(Scenario::new(vec![ScenarioStep::Reorg { depth: 0 }]).validate().is_err());
+    }
+}
@@ -58,6 +58,13 @@ pub const DEFAULT_SPARSE_TRIE_MAX_STORAGE_TRIES: usize = 100;
 /// Default timeout for the state root task before spawning a sequential fallback.
 pub const DEFAULT_STATE_ROOT_TASK_TIMEOUT: Duration = Duration::from_secs(1);
 
+/// Default number of optimistically accepted blocks that are kept unexecuted.
+pub const DEFAULT_OPTIMISTIC_EXECUTION_LAG: u64 = 0;
+
+/// Default time without engine messages after which all optimistically accepted blocks are
+/// executed.
+pub const DEFAULT_OPTIMISTIC_IDLE_TIMEOUT: Duration = Duration::from_secs(1);
+
 const DEFAULT_BLOCK_BUFFER_LIMIT: u32 = EPOCH_SLOTS as u32 * 2;
 const DEFAULT_MAX_INVALID_HEADER_CACHE_LENGTH: u32 = 256;
 const DEFAULT_MAX_EXECUTE_BLOCK_BATCH_SIZE: usize = 4;
@@ -170,6 +177,35 @@ pub struct TreeConfig {
     /// computation is spawned in parallel and whichever finishes first is used.
     /// If `None`, the timeout fallback is disabled.
     state_root_task_timeout: Option<Duration>,
+    /// Whether to accept payloads with a known parent before executing them.
+    ///
+    /// If enabled, `newPayload` answers `ACCEPTED` right after the block was pre-validated and
+    /// `forkchoiceUpdated` answers `SYNCING` while its head is not executed yet. The accepted
+    /// blocks are executed in the background and discarded if they turn out to be invalid. This
+    /// must only be used behind a trusted consensus client.
+    optimistic_sync: bool,
+    /// Number of optimistically accepted blocks that are kept unexecuted, the oldest accepted
+    /// blocks are executed once this is exceeded.
+    optimistic_execution_lag: u64,
+    /// Time without engine messages after which all optimistically accepted blocks are executed,
+    /// regardless of [`Self::optimistic_execution_lag`].
+    optimistic_idle_timeout: Duration,
+    /// Whether to execute the transactions of blocks that come with a block access list in
+    /// parallel.
+    ///
+    /// Every transaction is executed against the state described by the block access list and
+    /// its changes are validated against it. Any mismatch falls back to sequential execution.
+    parallel_execution: bool,
+    /// Whether to speculatively execute a predicted next block on top of the canonical head.
+    ///
+    /// The predicted block is built from the best pending transactions while the engine waits for
+    /// the next payload, which warms the execution cache and the sparse trie of the head block.
+    speculative_execution: bool,
+    /// Whether to persist the precompile cache to disk on shutdown and restore it on startup.
+    persist_precompile_cache: bool,
+    /// Whether to share the precompile cache with `eth_call`, `eth_estimateGas` and tracing RPC
+    /// methods.
+    share_precompile_cache: bool,
 }
 
 impl Default for TreeConfig {
@@ -201,6 +237,13 @@ impl Default for TreeConfig {
             sparse_trie_max_storage_tries: DEFAULT_SPARSE_TRIE_MAX_STORAGE_TRIES,
             disable_sparse_trie_cache_pruning: false,
             state_root_task_timeout: Some(DEFAULT_STATE_ROOT_TASK_TIMEOUT),
+            optimistic_sync: false,
+            optimistic_execution_lag: DEFAULT_OPTIMISTIC_EXECUTION_LAG,
+            optimistic_idle_timeout: DEFAULT_OPTIMISTIC_IDLE_TIMEOUT,
+            parallel_execution: false,
+            speculative_execution: false,
+            persist_precompile_cache: false,
+            share_precompile_cache: false,
         }
     }
 }
@@ -262,6 +305,13 @@ impl TreeConfig {
             sparse_trie_max_storage_tries,
             disable_sparse_trie_cache_pruning: false,
             state_root_task_timeout,
+            optimistic_sync: false,
+            optimistic_execution_lag: DEFAULT_OPTIMISTIC_EXECUTION_LAG,
+            optimistic_idle_timeout: DEFAULT_OPTIMISTIC_IDLE_TIMEOUT,
+            parallel_execution: false,
+            speculative_execution: false,
+            persist_precompile_cache: false,
+            share_precompile_cache: false,
         }
     }
 
@@ -592,4 +642,83 @@ impl TreeConfig {
         self.state_root_task_timeout = timeout;
         self
     }
+
+    /// Returns whether payloads are accepted before they are executed.
+    pub const fn optimistic_sync(&self) -> bool {
+        self.optimistic_sync
+    }
+
+    /// Setter for whether to accept payloads before they are executed.
+    pub const fn with_optimistic_sync(mut self, optimistic_sync: bool) -> Self {
+        self.optimistic_sync = optimistic_sync;
+        self
+    }
+
+    /// Returns the number of optimistically accepted blocks that are kept unexecuted.
+    pub const fn optimistic_execution_lag(&self) -> u64 {
+        self.optimistic_execution_lag
+    }
+
+    /// Setter for the number of optimistically accepted blocks that are kept unexecuted.
+    pub const fn with_optimistic_execution_lag(mut self, optimistic_execution_lag: u64) -> Self {
+        self.optimistic_execution_lag = optimistic_execution_lag;
+        self
+    }
+
+    /// Returns the time without engine messages after which all optimistically accepted blocks
+    /// are executed.
+    pub const fn optimistic_idle_timeout(&self) -> Duration {
+        self.optimistic_idle_timeout
+    }
+
+    /// Setter for the time without engine messages after which all optimistically accepted
+    /// blocks are executed.
+    pub const fn with_optimistic_idle_timeout(mut self, optimistic_idle_timeout: Duration) -> Self {
+        self.optimistic_idle_timeout = optimistic_idle_timeout;
+        self
+    }
+
+    /// Returns whether transactions of blocks with a block access list are executed in parallel.
+    pub const fn parallel_execution(&self) -> bool {
+        self.parallel_execution
+    }
+
+    /// Setter for whether to execute transactions of blocks with a block access list in parallel.
+    pub const fn with_parallel_execution(mut self, parallel_execution: bool) -> Self {
+        self.parallel_execution = parallel_execution;
+        self
+    }
+
+    /// Returns whether a predicted next block is speculatively executed on top of the head.
+    pub const fn speculative_execution(&self) -> bool {
+        self.speculative_execution
+    }
+
+    /// Setter for whether to speculatively execute a predicted next block on top of the head.
+    pub const fn with_speculative_execution(mut self, speculative_execution: bool) -> Self {
+        self.speculative_execution = speculative_execution;
+        self
+    }
+
+    /// Returns whether the precompile cache is persisted across restarts.
+    pub const fn persist_precompile_cache(&self) -> bool {
+        self.persist_precompile_cache
+    }
+
+    /// Setter for whether to persist the precompile cache across restarts.
+    pub const fn with_persist_precompile_cache(mut self, persist_precompile_cache: bool) -> Self {
+        self.persist_precompile_cache = persist_precompile_cache;
+        self
+    }
+
+    /// Returns whether the precompile cache is shared with RPC execution.
+    pub const fn share_precompile_cache(&self) -> bool {
+        self.share_precompile_cache
+    }
+
+    /// Setter for whether to share the precompile cache with RPC execution.
+    pub const fn with_share_precompile_cache(mut self, share_precompile_cache: bool) -> Self {
+        self.share_precompile_cache = share_precompile_cache;
+        self
+    }
 }
@@ -1,7 +1,7 @@
 //! Events emitted by the beacon consensus engine.
 
 use crate::ForkchoiceStatus;
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec::Vec};
 use alloy_consensus::BlockHeader;
 use alloy_eips::BlockNumHash;
 use alloy_rpc_types_engine::ForkchoiceState;
@@ -32,6 +32,9 @@ pub enum ConsensusEngineEvent<N: NodePrimitives = EthPrimitives> {
     CanonicalChainCommitted(Box<SealedHeader<N::BlockHeader>>, Duration),
     /// The consensus engine processed an invalid block.
     InvalidBlock(Box<SealedBlock<N::Block>>),
+    /// An optimistically accepted block failed validation, and the accepted descendants of the
+    /// block that were discarded with it.
+    OptimisticBlockInvalid(Box<SealedBlock<N::Block>>, Vec<BlockNumHash>),
 }
 
 impl<N: NodePrimitives> ConsensusEngineEvent<N> {
@@ -70,6 +73,9 @@ where
             Self::InvalidBlock(block) => {
                 write!(f, "InvalidBlock({:?})", block.num_hash())
             }
+            Self::OptimisticBlockInvalid(block, discarded) => {
+                write!(f, "OptimisticBlockInvalid({:?}, {discarded:?})", block.num_hash())
+            }
             Self::BlockReceived(num_hash) => {
                 write!(f, "BlockReceived({num_hash:?})")
             }
@@ -127,7 +127,8 @@ pub(crate) struct ReceivedForkchoiceState {
 }
 
 /// A simplified representation of [`PayloadStatusEnum`] specifically for FCU.
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum ForkchoiceStatus {
     /// The forkchoice state is valid.
     Valid,
@@ -154,7 +155,7 @@ impl ForkchoiceStatus {
     }
 
     /// Converts the general purpose [`PayloadStatusEnum`] into a [`ForkchoiceStatus`].
-    pub(crate) const fn from_payload_status(status: &PayloadStatusEnum) -> Self {
+    pub const fn from_payload_status(status: &PayloadStatusEnum) -> Self {
         match status {
             PayloadStatusEnum::Valid | PayloadStatusEnum::Accepted => {
                 // `Accepted` is only returned on `newPayload`. It would be a valid state here.
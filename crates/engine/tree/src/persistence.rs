@@ -171,6 +171,10 @@ where
             }
 
             provider_rw.commit()?;
+
+            // Snapshots of the state are written from a read transaction, without holding up the
+            // next blocks.
+            self.provider.spawn_state_snapshots();
         }
 
         debug!(target: "engine::persistence", first=?first_block, last=?last_block, "Saved range of blocks");
@@ -34,6 +34,10 @@ pub struct EngineApiMetrics {
     /// Metrics for EIP-7928 Block-Level Access Lists (BAL).
     #[allow(dead_code)]
     pub(crate) bal: BalMetrics,
+    /// Metrics for the parallel execution of transactions based on the BAL.
+    pub(crate) parallel_execution: ParallelExecutionMetrics,
+    /// Metrics for the speculative execution of predicted blocks.
+    pub(crate) speculative_execution: SpeculativeExecutionMetrics,
     /// Gas-bucketed execution sub-phase metrics.
     pub(crate) execution_gas_buckets: ExecutionGasBucketMetrics,
     /// Gas-bucketed block validation sub-phase metrics.
@@ -111,6 +115,25 @@ impl EngineApiMetrics {
     pub fn record_transaction_execution(&self, elapsed: Duration) {
         self.executor.transaction_execution_histogram.record(elapsed);
     }
+
+    /// Records a block whose transactions were executed in parallel.
+    ///
+    /// The achieved parallelism is the time spent executing the transactions divided by the wall
+    /// time of the parallel execution.
+    pub fn record_parallel_execution(
+        &self,
+        transactions: usize,
+        transaction_time: Duration,
+        elapsed: Duration,
+    ) {
+        let metrics = &self.parallel_execution;
+        metrics.blocks_total.increment(1);
+        metrics.transactions_total.increment(transactions as u64);
+        metrics.duration.record(elapsed);
+        if !elapsed.is_zero() {
+            metrics.parallelism.record(transaction_time.as_secs_f64() / elapsed.as_secs_f64());
+        }
+    }
 }
 
 /// Metrics for the entire blockchain tree
@@ -181,6 +204,10 @@ pub struct EngineMetrics {
     pub(crate) failed_forkchoice_updated_response_deliveries: Counter,
     /// block insert duration
     pub(crate) block_insert_total_duration: Histogram,
+    /// How many optimistically accepted blocks are waiting for execution.
+    pub(crate) optimistic_blocks: Gauge,
+    /// The number of optimistically accepted blocks that failed validation.
+    pub(crate) optimistic_invalid_blocks: Counter,
 }
 
 /// Metrics for engine forkchoiceUpdated responses.
@@ -498,6 +525,38 @@ pub(crate) struct BalMetrics {
     pub(crate) code_changes: Gauge,
 }
 
+/// Metrics for the parallel execution of transactions based on the BAL.
+#[derive(Metrics, Clone)]
+#[metrics(scope = "sync.parallel_execution")]
+pub(crate) struct ParallelExecutionMetrics {
+    /// Total number of blocks whose transactions were executed in parallel.
+    pub(crate) blocks_total: Counter,
+    /// Total number of transactions that were executed in parallel.
+    pub(crate) transactions_total: Counter,
+    /// Total number of blocks that fell back to sequential execution.
+    pub(crate) fallbacks_total: Counter,
+    /// Total number of transactions whose state changes didn't match the BAL.
+    pub(crate) conflicts_total: Counter,
+    /// Achieved parallelism of the parallel execution.
+    pub(crate) parallelism: Histogram,
+    /// Duration of the parallel execution of the transactions.
+    pub(crate) duration: Histogram,
+}
+
+/// Metrics for the speculative execution of predicted blocks on top of the canonical head.
+#[derive(Metrics, Clone)]
+#[metrics(scope = "sync.speculative_execution")]
+pub(crate) struct SpeculativeExecutionMetrics {
+    /// Total number of predicted blocks that were speculatively executed.
+    pub(crate) blocks_total: Counter,
+    /// Number of transactions in the predicted blocks.
+    pub(crate) transactions: Histogram,
+    /// Total number of payloads that built on the block a prediction was executed on.
+    pub(crate) hits_total: Counter,
+    /// Total number of payloads that built on a different block than the prediction.
+    pub(crate) misses_total: Counter,
+}
+
 /// Metrics for non-execution related block validation.
 #[derive(Metrics, Clone)]
 #[metrics(scope = "sync.block_validation")]
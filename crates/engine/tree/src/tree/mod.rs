@@ -57,21 +57,23 @@ pub mod error;
 pub mod instrumented_state;
 mod invalid_headers;
 mod metrics;
+mod optimistic;
 pub mod payload_processor;
 pub mod payload_validator;
 mod persistence_state;
 pub mod precompile_cache;
 #[cfg(test)]
 mod tests;
-mod trie_updates;
+pub mod trie_updates;
 
 use crate::tree::error::AdvancePersistenceError;
 pub use block_buffer::BlockBuffer;
 pub use cached_state::{CachedStateMetrics, CachedStateProvider, ExecutionCache, SavedCache};
 pub use invalid_headers::InvalidHeaderCache;
 pub use metrics::EngineApiMetrics;
+pub use optimistic::OptimisticQueue;
 pub use payload_processor::*;
-pub use payload_validator::{BasicEngineValidator, EngineValidator};
+pub use payload_validator::{BasicEngineValidator, EngineValidator, SpeculativeTransactions};
 pub use persistence_state::PersistenceState;
 pub use hanzo_evm_engine_primitives::TreeConfig;
 
@@ -276,6 +278,10 @@ where
     /// Whether the node uses hashed state as canonical storage (v2 mode).
     /// Cached at construction to avoid threading `StorageSettingsCache` bounds everywhere.
     use_hashed_state: bool,
+    /// Blocks that were accepted optimistically and are waiting for execution.
+    ///
+    /// Only used if [`TreeConfig::optimistic_sync`] is enabled.
+    optimistic_blocks: OptimisticQueue<N::Block>,
 }
 
 impl<N, P: Debug, T: PayloadTypes + Debug, V: Debug, C> std::fmt::Debug
@@ -302,6 +308,7 @@ where
             .field("hanzo_evm_config", &self.hanzo_evm_config)
             .field("changeset_cache", &self.changeset_cache)
             .field("use_hashed_state", &self.use_hashed_state)
+            .field("optimistic_blocks", &self.optimistic_blocks)
             .finish()
     }
 }
@@ -364,6 +371,7 @@ where
             hanzo_evm_config,
             changeset_cache,
             use_hashed_state,
+            optimistic_blocks: OptimisticQueue::default(),
         }
     }
 
@@ -445,6 +453,22 @@ where
                             return
                         }
                     }
+
+                    // The response was already sent, so optimistically accepted blocks can be
+                    // executed now
+                    let lag = self.config.optimistic_execution_lag();
+                    if let Err(fatal) = self.execute_optimistic_blocks(lag, false) {
+                        error!(target: "engine::tree", %fatal, "insert block fatal error");
+                        return
+                    }
+                }
+                LoopEvent::Idle => {
+                    // No payloads arrived for a while, so the consensus client is not ahead of
+                    // the accepted blocks and they can be executed now
+                    if let Err(fatal) = self.execute_optimistic_blocks(0, false) {
+                        error!(target: "engine::tree", %fatal, "insert block fatal error");
+                        return
+                    }
                 }
                 LoopEvent::PersistenceComplete { result, start_time } => {
                     if let Err(err) = self.on_persistence_complete(result, start_time) {
@@ -469,12 +493,19 @@ where
         }
     }
 
-    /// Blocks until the next event is ready: either an incoming engine message or a persistence
-    /// completion (if one is in progress).
+    /// Blocks until the next event is ready: either an incoming engine message, a persistence
+    /// completion (if one is in progress) or the idle timeout (if optimistically accepted blocks
+    /// are waiting for execution).
     ///
     /// Uses biased selection to prioritize persistence completion to update in-memory state and
     /// unblock further writes.
     fn wait_for_event(&mut self) -> LoopEvent<T, N> {
+        let idle = if self.optimistic_blocks.is_empty() {
+            crossbeam_channel::never()
+        } else {
+            crossbeam_channel::after(self.config.optimistic_idle_timeout())
+        };
+
         // Take ownership of persistence rx if present
         let maybe_persistence = self.persistence_state.rx.take();
 
@@ -500,12 +531,19 @@ where
                         Err(_) => LoopEvent::Disconnected,
                     }
                 },
+                recv(idle) -> _ => {
+                    self.persistence_state.rx = Some((persistence_rx, start_time, action));
+                    LoopEvent::Idle
+                },
             }
         } else {
             // No persistence in progress - just wait on incoming
-            match self.incoming.recv() {
-                Ok(m) => LoopEvent::EngineMessage(m),
-                Err(_) => LoopEvent::Disconnected,
+            crossbeam_channel::select_biased! {
+                recv(self.incoming) -> msg => match msg {
+                    Ok(m) => LoopEvent::EngineMessage(m),
+                    Err(_) => LoopEvent::Disconnected,
+                },
+                recv(idle) -> _ => LoopEvent::Idle,
             }
         }
     }
@@ -616,7 +654,11 @@ where
         self.metrics.block_validation.record_payload_validation(start.elapsed().as_secs_f64());
 
         let status = if self.backfill_sync_state.is_idle() {
-            self.try_insert_payload(payload)?
+            if self.config.optimistic_sync() {
+                self.try_accept_payload(payload)?
+            } else {
+                self.try_insert_payload(payload)?
+            }
         } else {
             self.try_buffer_payload(payload)?
         };
@@ -684,6 +726,168 @@ where
         }
     }
 
+    /// Accepts a payload without executing it, used if optimistic sync is enabled.
+    ///
+    /// The payload is pre-validated and queued for execution if its parent is known, either as an
+    /// executed block or as another accepted block. Payloads of already executed blocks and
+    /// payloads with an unknown parent are inserted as usual.
+    ///
+    /// Returns:
+    /// - `Accepted`: Payload queued for execution
+    /// - Error status: Payload is malformed or invalid
+    /// - Otherwise the status of [`Self::try_insert_payload`]
+    fn try_accept_payload(
+        &mut self,
+        payload: T::ExecutionData,
+    ) -> Result<PayloadStatus, InsertBlockFatalError> {
+        let parent_hash = payload.parent_hash();
+        let num_hash = payload.num_hash();
+
+        if self.optimistic_blocks.contains(&num_hash.hash) {
+            return Ok(PayloadStatus::from_status(PayloadStatusEnum::Accepted))
+        }
+
+        let parent_known = self.optimistic_blocks.contains(&parent_hash) ||
+            self.sealed_header_by_hash(parent_hash)?.is_some();
+        if !parent_known || self.sealed_header_by_hash(num_hash.hash)?.is_some() {
+            return self.try_insert_payload(payload)
+        }
+
+        let block = match self.payload_validator.convert_payload_to_block(payload) {
+            Ok(block) => block,
+            Err(error) => return Ok(self.on_new_payload_error(error, num_hash, parent_hash)?),
+        };
+
+        if let Err(error) = self.validate_block(&block) {
+            return Ok(self.on_insert_block_error(InsertBlockError::consensus_error(error, block))?)
+        }
+
+        debug!(target: "engine::tree", block=?num_hash, "Accepted payload optimistically");
+        self.optimistic_blocks.insert_block(block);
+        self.metrics.engine.optimistic_blocks.set(self.optimistic_blocks.len() as f64);
+
+        Ok(PayloadStatus::from_status(PayloadStatusEnum::Accepted))
+    }
+
+    /// Executes the oldest optimistically accepted blocks until at most `lag` blocks are left
+    /// unexecuted.
+    ///
+    /// This is called with [`TreeConfig::optimistic_execution_lag`] after every engine message and
+    /// with `0` to drain the queue if the engine is idle or shutting down. Afterwards, the latest
+    /// executed ancestor of the sync target head is made canonical.
+    ///
+    /// Unless the engine is shutting down, pending engine messages are handled before the next
+    /// block is executed, and the next block that is left queued is executed on the payload
+    /// processor's executor ahead of its validation, see [`EngineValidator::prepare_block`].
+    fn execute_optimistic_blocks(
+        &mut self,
+        lag: u64,
+        shutdown: bool,
+    ) -> Result<(), InsertBlockFatalError> {
+        if self.optimistic_blocks.is_empty() || !self.backfill_sync_state.is_idle() {
+            return Ok(())
+        }
+
+        while self.optimistic_blocks.len() as u64 > lag {
+            // forkchoice updates and new payloads must not wait for the queued blocks, the
+            // remaining blocks are executed once the pending messages are handled
+            if !shutdown && !self.incoming.is_empty() {
+                break
+            }
+            let Some(block) = self.optimistic_blocks.pop_lowest() else { break };
+            self.execute_optimistic_block(block)?;
+        }
+        self.metrics.engine.optimistic_blocks.set(self.optimistic_blocks.len() as f64);
+
+        if !shutdown && let Some(block) = self.optimistic_blocks.lowest() {
+            let ctx = TreeCtx::new(&mut self.state, &self.canonical_in_memory_state);
+            self.payload_validator.prepare_block(block, ctx);
+        }
+
+        let Some(target) = self.state.forkchoice_state_tracker.sync_target_state() else {
+            return Ok(())
+        };
+
+        // the head might still be queued, in which case the parent of its lowest queued ancestor
+        // is the latest executed block of the chain
+        let executed_head = self
+            .optimistic_blocks
+            .lowest_ancestor(&target.head_block_hash)
+            .map(|block| block.parent_hash())
+            .unwrap_or(target.head_block_hash);
+        if self.state.tree_state.canonical_block_hash() == executed_head ||
+            self.state.tree_state.sealed_header_by_hash(&executed_head).is_none()
+        {
+            return Ok(())
+        }
+
+        debug!(target: "engine::tree", head=?executed_head, "Making optimistically accepted blocks canonical");
+        self.make_canonical(executed_head)?;
+
+        // the safe and finalized blocks might not be executed yet
+        let _ = self.ensure_consistent_forkchoice_state(target);
+
+        Ok(())
+    }
+
+    /// Executes an optimistically accepted block.
+    ///
+    /// If the block is invalid, it is marked as invalid and all of its accepted descendants are
+    /// discarded and marked as invalid as well.
+    fn execute_optimistic_block(
+        &mut self,
+        block: SealedBlock<N::Block>,
+    ) -> Result<(), InsertBlockFatalError> {
+        let block_num_hash = block.num_hash();
+        match self.insert_block(block) {
+            Ok(InsertPayloadOk::Inserted(BlockStatus::Valid)) => {
+                trace!(target: "engine::tree", block=?block_num_hash, "executed optimistically accepted block");
+                self.try_connect_buffered_blocks(block_num_hash)?;
+            }
+            Ok(InsertPayloadOk::Inserted(BlockStatus::Disconnected { head, missing_ancestor })) => {
+                // the parent is no longer available, e.g. because it was removed in a reorg
+                if let Some(event) =
+                    self.on_disconnected_downloaded_block(block_num_hash, missing_ancestor, head)
+                {
+                    self.on_tree_event(event)?;
+                }
+            }
+            Ok(InsertPayloadOk::AlreadySeen(_)) => {
+                trace!(target: "engine::tree", block=?block_num_hash, "optimistically accepted block already executed");
+            }
+            Err(err) => {
+                if let InsertPayloadError::Block(err) = err {
+                    let invalid = err.block().clone();
+                    debug!(target: "engine::tree", err=%err.kind(), "optimistically accepted block is invalid");
+                    self.on_insert_block_error(err)?;
+                    self.on_optimistic_block_invalid(invalid);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Discards the accepted descendants of an optimistically accepted block that failed
+    /// validation and marks them as invalid.
+    fn on_optimistic_block_invalid(&mut self, invalid: SealedBlock<N::Block>) {
+        let invalid_ancestor = invalid.block_with_parent();
+        let mut discarded = Vec::new();
+        for block in self.optimistic_blocks.remove_descendants(&invalid_ancestor.block.hash) {
+            self.state.invalid_headers.insert_with_invalid_ancestor(block.hash(), invalid_ancestor);
+            discarded.push(block.num_hash());
+        }
+
+        self.metrics.engine.optimistic_invalid_blocks.increment(1);
+        self.metrics.engine.optimistic_blocks.set(self.optimistic_blocks.len() as f64);
+        warn!(
+            target: "engine::tree",
+            invalid=?invalid_ancestor.block,
+            discarded=discarded.len(),
+            "Optimistically accepted block is invalid, discarding accepted descendants",
+        );
+        self.emit_event(ConsensusEngineEvent::OptimisticBlockInvalid(Box::new(invalid), discarded));
+    }
+
     /// Stores a payload for later processing during backfill sync.
     ///
     /// During backfill, the node lacks the state needed to validate payloads,
@@ -1014,6 +1218,13 @@ where
             return Ok(TreeOutcome::new(early_result));
         }
 
+        // The head was accepted optimistically but is not executed yet, it's made canonical once
+        // it's executed
+        if self.optimistic_blocks.contains(&state.head_block_hash) {
+            trace!(target: "engine::tree", head=?state.head_block_hash, "fcu head is not executed yet");
+            return Ok(TreeOutcome::new(OnForkChoiceUpdated::syncing()));
+        }
+
         // Return early if we are on the correct fork
         if let Some(result) = self.handle_canonical_head(state, &attrs, version)? {
             return Ok(result);
@@ -1439,6 +1650,10 @@ where
                 }
                 FromOrchestrator::Terminate { tx } => {
                     debug!(target: "engine::tree", "received terminate request");
+                    // execute the accepted blocks so they can be persisted before shutdown
+                    if let Err(err) = self.execute_optimistic_blocks(0, true) {
+                        error!(target: "engine::tree", %err, "Executing accepted blocks failed");
+                    }
                     if let Err(err) = self.finish_termination(tx) {
                         error!(target: "engine::tree", %err, "Termination failed");
                     }
@@ -1514,6 +1729,14 @@ where
                                     &output,
                                 );
 
+                                // a valid head without a payload to build means the next payload
+                                // is expected on top of it
+                                let speculate = !has_attrs &&
+                                    self.config.speculative_execution() &&
+                                    output.as_ref().is_ok_and(|res| {
+                                        res.outcome.forkchoice_status().is_valid()
+                                    });
+
                                 if let Err(err) =
                                     tx.send(output.map(|o| o.outcome).map_err(Into::into))
                                 {
@@ -1523,6 +1746,10 @@ where
                                         .increment(1);
                                     warn!(target: "engine::tree", ?state, elapsed=?start.elapsed(), "Failed to deliver forkchoiceUpdated response, receiver dropped (request cancelled): {err:?}");
                                 }
+
+                                if speculate {
+                                    self.speculate_next_payload();
+                                }
                             }
                             BeaconEngineMessage::NewPayload { payload, tx } => {
                                 let start = Instant::now();
@@ -2030,6 +2257,23 @@ where
         )))
     }
 
+    /// Lets the payload validator prepare the execution of the next payload on top of the
+    /// canonical head, see [`TreeConfig::speculative_execution`].
+    fn speculate_next_payload(&mut self) {
+        let head_hash = self.state.tree_state.canonical_block_hash();
+        let head = match self.sealed_header_by_hash(head_hash) {
+            Ok(Some(head)) => head,
+            Ok(None) => return,
+            Err(err) => {
+                debug!(target: "engine::tree", %err, %head_hash, "Failed to fetch canonical head for speculative execution");
+                return
+            }
+        };
+
+        let ctx = TreeCtx::new(&mut self.state, &self.canonical_in_memory_state);
+        self.payload_validator.on_canonical_head(&head, ctx);
+    }
+
     /// Return sealed block header from in-memory state or database by hash.
     fn sealed_header_by_hash(
         &self,
@@ -3094,6 +3338,9 @@ where
         /// When the persistence operation started.
         start_time: Instant,
     },
+    /// Optimistically accepted blocks are waiting for execution and no event arrived within
+    /// [`TreeConfig::optimistic_idle_timeout`].
+    Idle,
     /// A channel was disconnected.
     Disconnected,
 }
@@ -0,0 +1,208 @@
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{BlockHash, BlockNumber};
+use hanzo_evm_primitives_traits::{Block, SealedBlock};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Contains the blocks that were accepted optimistically and are waiting for execution.
+///
+/// If optimistic sync is enabled, payloads with a known parent are only pre-validated and
+/// answered with `ACCEPTED`. The blocks are executed later in ascending block number order, see
+/// [`OptimisticQueue::pop_lowest`]. If a block fails validation, all of its descendants are
+/// discarded with [`OptimisticQueue::remove_descendants`].
+#[derive(Debug)]
+pub struct OptimisticQueue<B: Block> {
+    /// All queued blocks stored by their block hash.
+    pub(crate) blocks: HashMap<BlockHash, SealedBlock<B>>,
+    /// Map of any parent block hash (even the ones not currently in the queue) to the queued
+    /// children.
+    pub(crate) parent_to_child: HashMap<BlockHash, HashSet<BlockHash>>,
+    /// `BTreeMap` tracking the queued blocks by block number, used to execute them in order.
+    pub(crate) blocks_by_number: BTreeMap<BlockNumber, HashSet<BlockHash>>,
+}
+
+impl<B: Block> Default for OptimisticQueue<B> {
+    fn default() -> Self {
+        Self {
+            blocks: Default::default(),
+            parent_to_child: Default::default(),
+            blocks_by_number: Default::default(),
+        }
+    }
+}
+
+impl<B: Block> OptimisticQueue<B> {
+    /// Returns the number of queued blocks.
+    pub fn len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Returns `true` if no block is queued.
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    /// Returns `true` if the block is queued.
+    pub fn contains(&self, hash: &BlockHash) -> bool {
+        self.blocks.contains_key(hash)
+    }
+
+    /// Return reference to the requested block.
+    pub fn block(&self, hash: &BlockHash) -> Option<&SealedBlock<B>> {
+        self.blocks.get(hash)
+    }
+
+    /// Return a reference to the lowest ancestor of the given block in the queue.
+    pub fn lowest_ancestor(&self, hash: &BlockHash) -> Option<&SealedBlock<B>> {
+        let mut current_block = self.blocks.get(hash)?;
+        while let Some(parent) = self.blocks.get(&current_block.parent_hash()) {
+            current_block = parent;
+        }
+        Some(current_block)
+    }
+
+    /// Queues a block for execution.
+    pub fn insert_block(&mut self, block: SealedBlock<B>) {
+        let hash = block.hash();
+        if self.blocks.contains_key(&hash) {
+            return
+        }
+
+        self.parent_to_child.entry(block.parent_hash()).or_default().insert(hash);
+        self.blocks_by_number.entry(block.number()).or_default().insert(hash);
+        self.blocks.insert(hash, block);
+    }
+
+    /// Removes and returns the queued block with the lowest block number.
+    ///
+    /// The parent of the returned block is never queued, so it can be executed right away.
+    pub fn pop_lowest(&mut self) -> Option<SealedBlock<B>> {
+        let hash = self.lowest()?.hash();
+        self.remove_block(&hash)
+    }
+
+    /// Returns the queued block with the lowest block number, which is executed next.
+    pub fn lowest(&self) -> Option<&SealedBlock<B>> {
+        let hash = self.blocks_by_number.first_key_value()?.1.iter().next()?;
+        self.blocks.get(hash)
+    }
+
+    /// Removes all descendants of the given block and returns them, blocks with a lower block
+    /// number come first.
+    ///
+    /// The given block itself is not removed.
+    pub fn remove_descendants(&mut self, hash: &BlockHash) -> Vec<SealedBlock<B>> {
+        let mut parents = vec![*hash];
+        let mut removed = Vec::new();
+        while let Some(parent_hash) = parents.pop() {
+            if let Some(children) = self.parent_to_child.remove(&parent_hash) {
+                for child_hash in &children {
+                    if let Some(block) = self.remove_block(child_hash) {
+                        removed.push(block);
+                    }
+                }
+                parents.extend(children);
+            }
+        }
+        removed.sort_unstable_by_key(|block| block.number());
+        removed
+    }
+
+    /// Removes the block from the inner collections, its children stay connected to it.
+    fn remove_block(&mut self, hash: &BlockHash) -> Option<SealedBlock<B>> {
+        let block = self.blocks.remove(hash)?;
+
+        if let Some(entry) = self.blocks_by_number.get_mut(&block.number()) {
+            entry.remove(hash);
+            if entry.is_empty() {
+                self.blocks_by_number.remove(&block.number());
+            }
+        }
+
+        if let Some(entry) = self.parent_to_child.get_mut(&block.parent_hash()) {
+            entry.remove(hash);
+            if entry.is_empty() {
+                self.parent_to_child.remove(&block.parent_hash());
+            }
+        }
+
+        Some(block)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hanzo_evm_testing_utils::generators::{self, random_block, BlockParams, Rng};
+
+    /// Create random block with specified number and parent hash.
+    fn create_block<R: Rng>(
+        rng: &mut R,
+        number: u64,
+        parent: BlockHash,
+    ) -> SealedBlock<hanzo_evm_ethereum_primitives::Block> {
+        random_block(rng, number, BlockParams { parent: Some(parent), ..Default::default() })
+    }
+
+    #[test]
+    fn pop_in_block_number_order() {
+        let mut rng = generators::rng();
+
+        let parent = rng.random();
+        let block1 = create_block(&mut rng, 10, parent);
+        let block2 = create_block(&mut rng, 11, block1.hash());
+        let block3 = create_block(&mut rng, 12, block2.hash());
+
+        let mut queue = OptimisticQueue::default();
+        queue.insert_block(block3.clone());
+        queue.insert_block(block1.clone());
+        queue.insert_block(block2.clone());
+        queue.insert_block(block2.clone());
+
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.lowest_ancestor(&block3.hash()), Some(&block1));
+        assert_eq!(queue.lowest(), Some(&block1));
+
+        assert_eq!(queue.pop_lowest(), Some(block1));
+        assert_eq!(queue.lowest_ancestor(&block3.hash()), Some(&block2));
+        assert_eq!(queue.pop_lowest(), Some(block2));
+        assert_eq!(queue.pop_lowest(), Some(block3));
+        assert_eq!(queue.pop_lowest(), None);
+        assert_eq!(queue.lowest(), None);
+        assert!(queue.is_empty());
+        assert!(queue.parent_to_child.is_empty());
+        assert!(queue.blocks_by_number.is_empty());
+    }
+
+    #[test]
+    fn remove_descendants_of_executed_block() {
+        let mut rng = generators::rng();
+
+        let parent = rng.random();
+        let other_parent = rng.random();
+        let block1 = create_block(&mut rng, 10, parent);
+        let block2 = create_block(&mut rng, 11, block1.hash());
+        let block3 = create_block(&mut rng, 11, block1.hash());
+        let block4 = create_block(&mut rng, 12, block2.hash());
+        let other = create_block(&mut rng, 11, other_parent);
+
+        let mut queue = OptimisticQueue::default();
+        queue.insert_block(block1.clone());
+        queue.insert_block(block2.clone());
+        queue.insert_block(block3.clone());
+        queue.insert_block(block4.clone());
+        queue.insert_block(other.clone());
+
+        // the invalid block was taken from the queue for execution
+        assert_eq!(queue.pop_lowest(), Some(block1.clone()));
+
+        let removed = queue.remove_descendants(&block1.hash());
+        assert_eq!(removed.len(), 3);
+        assert_eq!(removed.last(), Some(&block4));
+        assert!(removed.contains(&block2));
+        assert!(removed.contains(&block3));
+
+        assert_eq!(queue.len(), 1);
+        assert!(queue.contains(&other.hash()));
+        assert_eq!(queue.block(&other.hash()), Some(&other));
+    }
+}
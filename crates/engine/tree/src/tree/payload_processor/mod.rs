@@ -55,6 +55,7 @@ use tracing::{debug, debug_span, instrument, warn, Span};
 
 pub mod bal;
 pub mod multiproof;
+pub(crate) mod parallel;
 mod preserved_sparse_trie;
 pub mod prewarm;
 pub mod receipt_root_task;
@@ -348,6 +349,94 @@ where
         }
     }
 
+    /// Spawns the speculative execution of a predicted or not yet validated block on top of
+    /// `env.parent_hash`.
+    ///
+    /// The transactions are only executed by the prewarming task, which warms the execution cache
+    /// of the parent block, and the proof targets of their state changes are revealed in the
+    /// sparse trie without updating it. The next payload on top of the same parent reuses the
+    /// warmed caches for every transaction and slot it shares with the predicted block.
+    ///
+    /// Returns a [`SpeculativeHandle`] that must be cancelled before the next payload is spawned.
+    #[instrument(level = "debug", target = "engine::tree::payload_processor", skip_all)]
+    pub fn spawn_speculative<P, F, I: ExecutableTxIterator<Evm>>(
+        &mut self,
+        env: ExecutionEnv<Evm>,
+        transactions: I,
+        provider_builder: StateProviderBuilder<N, P>,
+        multiproof_provider_factory: F,
+        config: &TreeConfig,
+    ) -> SpeculativeHandle<N::Receipt>
+    where
+        P: BlockReader + StateProviderFactory + StateReader + Clone + 'static,
+        F: DatabaseProviderROFactory<Provider: TrieCursorFactory + HashedCursorFactory>
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+    {
+        let block_hash = env.hash;
+        let parent_hash = env.parent_hash;
+
+        // Track a cache for the parent block, otherwise the warmed cache would be discarded when
+        // the next payload looks up the cache of its parent
+        if !self.disable_state_cache {
+            let cross_block_cache_size = self.cross_block_cache_size;
+            let disable_cache_metrics = self.disable_cache_metrics;
+            self.execution_cache.update_with_guard(|cached| {
+                if cached
+                    .as_ref()
+                    .is_some_and(|c| c.executed_block_hash() == parent_hash || !c.is_available())
+                {
+                    return
+                }
+                let cache = SavedCache::new(
+                    parent_hash,
+                    ExecutionCache::new(cross_block_cache_size),
+                    CachedStateMetrics::zeroed(),
+                )
+                .with_disable_cache_metrics(disable_cache_metrics);
+                *cached = Some(cache);
+            });
+        }
+
+        // the transactions are never executed sequentially
+        let (prewarm_rx, _) = self.spawn_tx_iterator(transactions, env.transaction_count);
+
+        let (to_multi_proof, from_multi_proof) = crossbeam_channel::unbounded();
+        let parent_state_root = env.parent_state_root;
+        let chunk_size = Self::adaptive_chunk_size(config, env.gas_used);
+        let prewarm_handle = self.spawn_caching_with(
+            env,
+            prewarm_rx,
+            provider_builder,
+            Some(to_multi_proof.clone()),
+            None,
+        );
+
+        // The sparse trie only receives prefetch targets, so the trie it preserves is still
+        // anchored at the parent state root
+        let task_ctx = ProofTaskCtx::new(multiproof_provider_factory);
+        let proof_handle = ProofWorkerHandle::new(&self.executor, task_ctx, true);
+        let (state_root_tx, state_root_rx) = channel();
+        self.spawn_sparse_trie_task(
+            proof_handle,
+            state_root_tx,
+            from_multi_proof,
+            parent_state_root,
+            chunk_size,
+        );
+
+        SpeculativeHandle {
+            block_hash,
+            parent_hash,
+            to_multi_proof: Some(to_multi_proof),
+            prewarm_handle,
+            state_root: state_root_rx,
+            executor: self.executor.clone(),
+        }
+    }
+
     /// Transaction count threshold below which proof workers are halved, since fewer transactions
     /// produce fewer state changes and most workers would be idle overhead.
     const SMALL_BLOCK_PROOF_WORKER_TX_THRESHOLD: usize = 30;
@@ -846,6 +935,59 @@ impl<Tx, Err, R: Send + Sync + 'static> PayloadHandle<Tx, Err, R> {
     }
 }
 
+/// Handle to the speculative execution of a predicted block.
+///
+/// See [`PayloadProcessor::spawn_speculative`].
+#[derive(Debug)]
+pub struct SpeculativeHandle<R> {
+    /// Hash of the executed block, zero if the block is predicted.
+    block_hash: B256,
+    /// Hash of the block the predicted block builds on.
+    parent_hash: B256,
+    /// Channel to the sparse trie task.
+    to_multi_proof: Option<CrossbeamSender<MultiProofMessage>>,
+    /// Handle to the prewarm task executing the predicted transactions.
+    prewarm_handle: CacheTaskHandle<R>,
+    /// Receiver for the result of the sparse trie task.
+    state_root: mpsc::Receiver<Result<StateRootComputeOutcome, ParallelStateRootError>>,
+    /// Runtime the sparse trie result is awaited on after cancellation.
+    executor: Runtime,
+}
+
+impl<R: Send + Sync + 'static> SpeculativeHandle<R> {
+    /// Returns the hash of the executed block, or zero if the block is predicted.
+    pub const fn block_hash(&self) -> B256 {
+        self.block_hash
+    }
+
+    /// Returns the hash of the block the predicted block builds on.
+    pub const fn parent_hash(&self) -> B256 {
+        self.parent_hash
+    }
+
+    /// Cancels the speculative execution without waiting for it to stop.
+    ///
+    /// The prewarm task is terminated and the sparse trie task is told that no state updates
+    /// follow. Both exit in the background, releasing the execution cache and preserving the
+    /// sparse trie with all proofs that were already fetched. The next payload reuses them if they
+    /// were released by the time it needs them.
+    #[instrument(level = "debug", target = "engine::tree::payload_processor", skip_all)]
+    pub fn cancel(mut self) {
+        self.prewarm_handle.stop_prewarming_execution();
+        let _ = self.prewarm_handle.terminate_caching(None);
+
+        if let Some(to_multi_proof) = self.to_multi_proof.take() {
+            let _ = to_multi_proof.send(MultiProofMessage::FinishedStateUpdates);
+        }
+
+        // the sparse trie task clears the trie if nobody receives its result
+        let state_root = self.state_root;
+        self.executor.spawn_blocking_named("speculative-cancel", move || {
+            let _ = state_root.recv();
+        });
+    }
+}
+
 /// Access to the spawned [`PrewarmCacheTask`].
 ///
 /// Generic over `R` (receipt type) to allow sharing `Arc<ExecutionOutcome<R>>` with the
@@ -1095,22 +1237,25 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::PayloadExecutionCache;
+    use super::{PayloadExecutionCache, SpeculativeHandle, SMALL_BLOCK_TX_THRESHOLD};
     use crate::tree::{
         cached_state::{CachedStateMetrics, ExecutionCache, SavedCache},
         payload_processor::{evm_state_to_hashed_post_state, ExecutionEnv, PayloadProcessor},
         precompile_cache::PrecompileCacheMap,
         StateProviderBuilder, TreeConfig,
     };
+    use alloy_consensus::TxLegacy;
     use alloy_eips::eip1898::{BlockNumHash, BlockWithParent};
     use alloy_evm::block::StateChangeSource;
     use rand::Rng;
     use hanzo_evm_chainspec::ChainSpec;
     use hanzo_evm_db_common::init::init_genesis;
-    use hanzo_evm_ethereum_primitives::TransactionSigned;
+    use hanzo_evm_ethereum_primitives::{Transaction, TransactionSigned};
     use hanzo_evm_execution::OnStateHook;
     use hanzo_evm_eth_execution::EthEvmConfig;
-    use hanzo_evm_primitives_traits::{Account, Recovered, StorageEntry};
+    use hanzo_evm_primitives_traits::{
+        Account, FastInstant as Instant, Recovered, SignerRecoverable, StorageEntry,
+    };
     use hanzo_evm_provider::{
         providers::{BlockchainProvider, OverlayStateProviderFactory},
         test_utils::create_test_provider_factory_with_chain_spec,
@@ -1120,9 +1265,9 @@ mod tests {
     use hanzo_evm_testing_utils::generators;
     use hanzo_evm_trie::{test_utils::state_root, HashedPostState};
     use hanzo_evm_trie_db::ChangesetCache;
-    use revm_primitives::{Address, HashMap, B256, KECCAK_EMPTY, U256};
+    use revm_primitives::{Address, HashMap, TxKind, B256, KECCAK_EMPTY, U256};
     use revm_state::{AccountInfo, AccountStatus, EvmState, EvmStorageSlot};
-    use std::sync::Arc;
+    use std::{sync::Arc, time::Duration};
 
     fn make_saved_cache(hash: B256) -> SavedCache {
         let execution_cache = ExecutionCache::new(1_000);
@@ -1261,6 +1406,96 @@ mod tests {
         assert!(cached3.is_none(), "New block cache should not be created on mismatch");
     }
 
+    /// Spawns the speculative execution of the given transactions on top of a genesis block.
+    fn spawn_speculative_on_genesis(
+        transactions: Vec<Recovered<TransactionSigned>>,
+    ) -> (
+        PayloadProcessor<EthEvmConfig>,
+        SpeculativeHandle<hanzo_evm_ethereum_primitives::Receipt>,
+        B256,
+    ) {
+        let factory = create_test_provider_factory_with_chain_spec(Arc::new(ChainSpec::default()));
+        let genesis_hash = init_genesis(&factory).unwrap();
+
+        let mut payload_processor = PayloadProcessor::new(
+            reth_tasks::Runtime::test(),
+            EthEvmConfig::new(factory.chain_spec()),
+            &TreeConfig::default(),
+            PrecompileCacheMap::default(),
+        );
+
+        let provider_factory = BlockchainProvider::new(factory).unwrap();
+
+        let mut env = ExecutionEnv::test_default();
+        env.parent_hash = genesis_hash;
+        env.transaction_count = transactions.len();
+
+        let handle = payload_processor.spawn_speculative(
+            env,
+            (transactions, Ok::<_, core::convert::Infallible>),
+            StateProviderBuilder::new(provider_factory.clone(), genesis_hash, None),
+            OverlayStateProviderFactory::new(provider_factory, ChangesetCache::new()),
+            &TreeConfig::default(),
+        );
+        assert_eq!(handle.parent_hash(), genesis_hash);
+
+        (payload_processor, handle, genesis_hash)
+    }
+
+    /// Waits until the cache for the given block was released by all tasks.
+    fn wait_for_cache(payload_processor: &PayloadProcessor<EthEvmConfig>, hash: B256) {
+        let start = Instant::now();
+        while payload_processor.execution_cache.get_cache_for(hash).is_none() {
+            assert!(start.elapsed() < Duration::from_secs(10), "cache of {hash} wasn't released");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn spawn_speculative_keeps_parent_cache() {
+        let (payload_processor, handle, genesis_hash) = spawn_speculative_on_genesis(Vec::new());
+
+        // cancelling doesn't wait for the tasks, the cache warmed for the parent is available to
+        // the next payload once they exited
+        handle.cancel();
+        wait_for_cache(&payload_processor, genesis_hash);
+    }
+
+    #[test]
+    fn spawn_speculative_warms_transactions() {
+        let mut rng = generators::rng();
+        let transactions = (0..SMALL_BLOCK_TX_THRESHOLD as u64)
+            .map(|nonce| {
+                let tx = Transaction::Legacy(TxLegacy {
+                    chain_id: Some(1),
+                    nonce,
+                    gas_limit: 21_000,
+                    to: TxKind::Call(Address::random()),
+                    ..Default::default()
+                });
+                let key = generators::generate_key(&mut rng);
+                generators::sign_tx_with_key_pair(key, tx).try_into_recovered().unwrap()
+            })
+            .collect::<Vec<_>>();
+        let senders = transactions.iter().map(|tx| *tx.signer()).collect::<Vec<_>>();
+        let (payload_processor, handle, genesis_hash) = spawn_speculative_on_genesis(transactions);
+
+        // the prewarm task loads the senders into the cache of the parent
+        let cache =
+            payload_processor.execution_cache.inner.read().as_ref().unwrap().cache().clone();
+        let start = Instant::now();
+        while !senders
+            .iter()
+            .all(|sender| cache.get_or_try_insert_account_with(*sender, || Err(())).is_ok())
+        {
+            assert!(start.elapsed() < Duration::from_secs(10), "senders weren't warmed");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        handle.cancel();
+        wait_for_cache(&payload_processor, genesis_hash);
+    }
+
     fn create_mock_state_updates(num_accounts: usize, updates_per_account: usize) -> Vec<EvmState> {
         let mut rng = generators::rng();
         let all_addresses: Vec<Address> = (0..num_accounts).map(|_| rng.random()).collect();
@@ -0,0 +1,788 @@
+//! Parallel execution of transactions based on the BAL (Block Access List, EIP-7928).
+//!
+//! The BAL records every state change of a block together with the index of the transaction that
+//! made it, where index `0` are the pre-execution changes and the transaction at position `i` has
+//! index `i + 1`. The state a transaction is executed on is the parent state with all changes of
+//! lower indices applied, so it is known before the block is executed. This removes the
+//! dependencies between the transactions: they are executed in parallel, each on its own view of
+//! the state, see [`BalStateDatabase`].
+//!
+//! The views are only correct if the BAL is. The results of the parallel execution are therefore
+//! only used if the changes of the pre-execution and of every transaction match the changes the
+//! BAL records at their index, see [`BalState::validate_changes`]. By induction over the
+//! transactions this proves that every view matched the state of the sequential execution, so
+//! committing the results in block order yields the same outcome. If any of them conflicts with
+//! the BAL, the block is executed sequentially instead.
+
+use crate::tree::{
+    cached_state::{CachedStateMetrics, CachedStateProvider, ExecutionCache},
+    StateProviderBuilder,
+};
+use alloy_consensus::constants::KECCAK_EMPTY;
+use alloy_eip7928::{AccountChanges, BlockAccessList, SlotChanges};
+use alloy_evm::block::StateChangeSource;
+use alloy_primitives::{keccak256, Address, Bytes, B256, U256};
+use rayon::prelude::*;
+use reth_evm::{
+    block::BlockExecutor, execute::ExecutableTxFor, ConfigureEvm, Evm, EvmEnvFor,
+    ExecutionCtxFor, HaltReasonFor,
+};
+use reth_primitives_traits::{FastInstant as Instant, NodePrimitives};
+use reth_provider::{BlockReader, StateProviderFactory, StateReader};
+use reth_revm::{
+    database::StateProviderDatabase,
+    db::State,
+    state::{AccountInfo, Bytecode, EvmState},
+};
+use revm::{context::result::ResultAndState, Database};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    sync::{mpsc, Arc},
+    time::Duration,
+};
+
+/// Reason why the parallel execution of a block is discarded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::Display)]
+pub(crate) enum BalConflict {
+    /// The balance, nonce or code of an account doesn't match the BAL.
+    #[display("account {_0} doesn't match the BAL")]
+    Account(Address),
+    /// The value of a storage slot doesn't match the BAL.
+    #[display("storage slot {_1} of account {_0} doesn't match the BAL")]
+    Storage(Address, U256),
+    /// An account was selfdestructed, which isn't supported by the parallel execution.
+    #[display("account {_0} was selfdestructed")]
+    SelfDestruct(Address),
+    /// The transaction at the given BAL index failed to execute on its view of the state.
+    #[display("execution at BAL index {_0} failed")]
+    Execution(u64),
+    /// The state provider for the parallel execution couldn't be created.
+    #[display("failed to create a state provider")]
+    Provider,
+}
+
+impl BalConflict {
+    /// Returns `true` if the conflict is caused by the BAL not matching the execution.
+    pub(crate) const fn is_mismatch(&self) -> bool {
+        !matches!(self, Self::Provider)
+    }
+}
+
+/// Index of the state changes recorded in a [`BlockAccessList`].
+#[derive(Debug)]
+pub(crate) struct BalState {
+    /// The indexed BAL.
+    bal: Arc<BlockAccessList>,
+    /// Position of every account in the BAL.
+    accounts: HashMap<Address, usize>,
+    /// Position of the changes of every storage slot, by account and slot.
+    slots: HashMap<(Address, U256), (usize, usize)>,
+    /// Code deployed in the block, by code hash.
+    codes: HashMap<B256, Bytecode>,
+    /// The accounts and storage slots changed at every BAL index. Account changes have no slot.
+    writes: HashMap<u64, Vec<(Address, Option<U256>)>>,
+}
+
+impl BalState {
+    /// Indexes the given BAL.
+    pub(crate) fn new(bal: Arc<BlockAccessList>) -> Self {
+        let mut accounts = HashMap::with_capacity(bal.len());
+        let mut slots = HashMap::default();
+        let mut codes = HashMap::default();
+        let mut writes: HashMap<u64, Vec<_>> = HashMap::default();
+
+        for (account_idx, account) in bal.iter().enumerate() {
+            let address = account.address;
+            accounts.insert(address, account_idx);
+
+            let mut account_indices = account
+                .balance_changes
+                .iter()
+                .map(|change| u64::from(change.block_access_index))
+                .chain(account.nonce_changes.iter().map(|change| change.block_access_index.into()))
+                .chain(account.code_changes.iter().map(|change| change.block_access_index.into()))
+                .collect::<Vec<_>>();
+            account_indices.sort_unstable();
+            account_indices.dedup();
+            for index in account_indices {
+                writes.entry(index).or_default().push((address, None));
+            }
+
+            for change in &account.code_changes {
+                if let (code_hash, Some(code)) = code_info(&change.new_code) {
+                    codes.insert(code_hash, code);
+                }
+            }
+
+            for (slot_idx, slot) in account.storage_changes.iter().enumerate() {
+                slots.insert((address, slot.slot), (account_idx, slot_idx));
+                for change in &slot.changes {
+                    writes
+                        .entry(change.block_access_index.into())
+                        .or_default()
+                        .push((address, Some(slot.slot)));
+                }
+            }
+        }
+
+        Self { bal, accounts, slots, codes, writes }
+    }
+
+    /// Returns the BAL index of the transaction at the given position in the block.
+    pub(crate) const fn transaction_index(position: usize) -> u64 {
+        position as u64 + 1
+    }
+
+    /// Returns the changes of the given account.
+    fn account(&self, address: Address) -> Option<&AccountChanges> {
+        self.accounts.get(&address).map(|idx| &self.bal[*idx])
+    }
+
+    /// Returns the changes of the given storage slot.
+    fn slot(&self, address: Address, slot: U256) -> Option<&SlotChanges> {
+        self.slots
+            .get(&(address, slot))
+            .map(|(account_idx, slot_idx)| &self.bal[*account_idx].storage_changes[*slot_idx])
+    }
+
+    /// Applies the changes the BAL records before the given index to the account.
+    pub(crate) fn account_before(
+        &self,
+        address: Address,
+        index: u64,
+        info: Option<AccountInfo>,
+    ) -> Option<AccountInfo> {
+        let Some(changes) = self.account(address) else { return info };
+
+        let balance = last_before(&changes.balance_changes, index, |c| c.block_access_index);
+        let nonce = last_before(&changes.nonce_changes, index, |c| c.block_access_index);
+        let code = last_before(&changes.code_changes, index, |c| c.block_access_index);
+        if balance.is_none() && nonce.is_none() && code.is_none() {
+            return info
+        }
+
+        let mut info = info.unwrap_or_default();
+        if let Some(change) = balance {
+            info.balance = change.post_balance;
+        }
+        if let Some(change) = nonce {
+            info.nonce = change.new_nonce;
+        }
+        if let Some(change) = code {
+            (info.code_hash, info.code) = code_info(&change.new_code);
+        }
+        Some(info)
+    }
+
+    /// Returns the value of the storage slot after the last change the BAL records before the
+    /// given index.
+    pub(crate) fn storage_before(&self, address: Address, slot: U256, index: u64) -> Option<U256> {
+        let changes = self.slot(address, slot)?;
+        last_before(&changes.changes, index, |c| c.block_access_index).map(|c| c.new_value)
+    }
+
+    /// Checks that the state changes made at the given BAL index are exactly the changes the BAL
+    /// records at that index.
+    pub(crate) fn validate_changes(&self, index: u64, state: &EvmState) -> Result<(), BalConflict> {
+        for (address, account) in state {
+            if !account.is_touched() {
+                continue
+            }
+            if account.is_selfdestructed() {
+                return Err(BalConflict::SelfDestruct(*address))
+            }
+
+            let original = &account.original_info;
+            let changes = self.account(*address);
+            let balance = changes
+                .and_then(|c| change_at(&c.balance_changes, index, |c| c.block_access_index))
+                .map_or(original.balance, |c| c.post_balance);
+            let nonce = changes
+                .and_then(|c| change_at(&c.nonce_changes, index, |c| c.block_access_index))
+                .map_or(original.nonce, |c| c.new_nonce);
+            let code_hash = changes
+                .and_then(|c| change_at(&c.code_changes, index, |c| c.block_access_index))
+                .map_or(original.code_hash, |c| code_info(&c.new_code).0);
+            if account.info.balance != balance ||
+                account.info.nonce != nonce ||
+                account.info.code_hash != code_hash
+            {
+                return Err(BalConflict::Account(*address))
+            }
+
+            for (slot, value) in &account.storage {
+                let expected = self
+                    .slot(*address, *slot)
+                    .and_then(|c| change_at(&c.changes, index, |c| c.block_access_index))
+                    .map_or(value.original_value, |c| c.new_value);
+                if value.present_value != expected {
+                    return Err(BalConflict::Storage(*address, *slot))
+                }
+            }
+        }
+
+        // every change the BAL records must have been made
+        for (address, slot) in self.writes.get(&index).into_iter().flatten() {
+            let made = state.get(address).filter(|account| account.is_touched()).is_some_and(
+                |account| slot.is_none_or(|slot| account.storage.contains_key(&slot)),
+            );
+            if !made {
+                return Err(slot.map_or(BalConflict::Account(*address), |slot| {
+                    BalConflict::Storage(*address, slot)
+                }))
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A [`Database`] returning the state a transaction of the block is executed on.
+///
+/// Reads return the state of the inner database with all changes the BAL records before the
+/// current index applied, see [`BalStateDatabase::set_index`].
+#[derive(Debug)]
+pub(crate) struct BalStateDatabase<DB> {
+    /// The database with the parent state.
+    inner: DB,
+    /// The indexed BAL.
+    bal: Arc<BalState>,
+    /// The BAL index of the transaction that is executed.
+    index: u64,
+}
+
+impl<DB> BalStateDatabase<DB> {
+    /// Creates a new database returning the parent state, i.e. the state at BAL index `0`.
+    pub(crate) const fn new(inner: DB, bal: Arc<BalState>) -> Self {
+        Self { inner, bal, index: 0 }
+    }
+
+    /// Sets the BAL index of the transaction that is executed next.
+    pub(crate) const fn set_index(&mut self, index: u64) {
+        self.index = index;
+    }
+}
+
+impl<DB: Database> Database for BalStateDatabase<DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let info = self.inner.basic(address)?;
+        Ok(self.bal.account_before(address, self.index, info))
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        if let Some(code) = self.bal.codes.get(&code_hash) {
+            return Ok(code.clone())
+        }
+        self.inner.code_by_hash(code_hash)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        match self.bal.storage_before(address, index, self.index) {
+            Some(value) => Ok(value),
+            None => self.inner.storage(address, index),
+        }
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        self.inner.block_hash(number)
+    }
+}
+
+/// Applies the pre-execution changes of the block on top of the parent state and checks them
+/// against the changes the BAL records at index `0`.
+pub(crate) fn validate_pre_execution<N, P, Evm>(
+    evm_config: &Evm,
+    evm_env: EvmEnvFor<Evm>,
+    ctx: ExecutionCtxFor<'_, Evm>,
+    provider: &StateProviderBuilder<N, P>,
+    bal: &BalState,
+) -> Result<(), BalConflict>
+where
+    N: NodePrimitives,
+    P: BlockReader + StateProviderFactory + StateReader + Clone + 'static,
+    Evm: ConfigureEvm<Primitives = N>,
+{
+    let state_provider = provider.build().map_err(|_| BalConflict::Provider)?;
+    let mut db = State::builder().with_database(StateProviderDatabase::new(state_provider)).build();
+
+    // the pre-execution changes are made by several system calls, collect all of them
+    let (state_tx, state_rx) = mpsc::channel();
+    let evm = evm_config.evm_with_env(&mut db, evm_env);
+    let mut executor = evm_config.create_executor(evm, ctx);
+    executor.set_state_hook(Some(Box::new(move |_: StateChangeSource, state: &EvmState| {
+        let _ = state_tx.send(state.clone());
+    })));
+    executor.apply_pre_execution_changes().map_err(|_| BalConflict::Execution(0))?;
+    drop(executor);
+
+    let mut changes = EvmState::default();
+    for state in state_rx.try_iter() {
+        for (address, account) in state {
+            match changes.entry(address) {
+                Entry::Occupied(mut entry) => {
+                    let existing = entry.get_mut();
+                    existing.info = account.info;
+                    existing.status |= account.status;
+                    existing.storage.extend(account.storage);
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(account);
+                }
+            }
+        }
+    }
+
+    bal.validate_changes(0, &changes)
+}
+
+/// Executes the transactions in parallel, each on top of the state view of its BAL index, and
+/// validates their state changes against the BAL.
+///
+/// Returns the results in block order together with the total time spent executing transactions,
+/// or the first conflict with the BAL.
+pub(crate) fn execute_transactions<N, P, Evm, Tx>(
+    pool: &rayon::ThreadPool,
+    evm_config: &Evm,
+    evm_env: &EvmEnvFor<Evm>,
+    provider: &StateProviderBuilder<N, P>,
+    caches: Option<(ExecutionCache, CachedStateMetrics)>,
+    bal: &Arc<BalState>,
+    transactions: &[Tx],
+) -> Result<(Vec<ResultAndState<HaltReasonFor<Evm>>>, Duration), BalConflict>
+where
+    N: NodePrimitives,
+    P: BlockReader + StateProviderFactory + StateReader + Clone + 'static,
+    Evm: ConfigureEvm<Primitives = N>,
+    Tx: ExecutableTxFor<Evm> + Clone + Sync,
+{
+    let results = pool.install(|| {
+        transactions
+            .par_iter()
+            .enumerate()
+            .map_init(
+                || {
+                    let mut state_provider = provider.build().ok()?;
+                    if let Some((caches, cache_metrics)) = caches.clone() {
+                        state_provider = Box::new(CachedStateProvider::new(
+                            state_provider,
+                            caches,
+                            cache_metrics,
+                        ));
+                    }
+                    let db = BalStateDatabase::new(
+                        StateProviderDatabase::new(state_provider),
+                        bal.clone(),
+                    );
+                    Some(evm_config.evm_with_env(db, evm_env.clone()))
+                },
+                |evm, (position, tx)| {
+                    let evm = evm.as_mut().ok_or(BalConflict::Provider)?;
+                    let index = BalState::transaction_index(position);
+                    evm.db_mut().set_index(index);
+
+                    let start = Instant::now();
+                    let (tx_env, _) = tx.clone().into_parts();
+                    let result =
+                        evm.transact(tx_env).map_err(|_| BalConflict::Execution(index))?;
+                    let elapsed = start.elapsed();
+
+                    bal.validate_changes(index, &result.state)?;
+                    Ok((result, elapsed))
+                },
+            )
+            .collect::<Result<Vec<_>, _>>()
+    })?;
+
+    let transaction_time = results.iter().map(|(_, elapsed)| *elapsed).sum();
+    Ok((results.into_iter().map(|(result, _)| result).collect(), transaction_time))
+}
+
+/// Returns the last change made before the given index, the changes are sorted by index.
+fn last_before<T, I: Into<u64>>(
+    changes: &[T],
+    index: u64,
+    change_index: impl Fn(&T) -> I,
+) -> Option<&T> {
+    changes[..changes.partition_point(|change| change_index(change).into() < index)].last()
+}
+
+/// Returns the change made at the given index, the changes are sorted by index.
+fn change_at<T, I: Into<u64>>(
+    changes: &[T],
+    index: u64,
+    change_index: impl Fn(&T) -> I,
+) -> Option<&T> {
+    last_before(changes, index + 1, &change_index)
+        .filter(|change| change_index(change).into() == index)
+}
+
+/// Returns the code hash and the bytecode of the given code.
+fn code_info(code: &Bytes) -> (B256, Option<Bytecode>) {
+    if code.is_empty() {
+        (KECCAK_EMPTY, None)
+    } else {
+        (keccak256(code), Some(Bytecode::new_raw(code.clone())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Header, TxLegacy};
+    use alloy_eip7928::{BalanceChange, CodeChange, NonceChange, StorageChange};
+    use alloy_evm::block::{BlockExecutionError, BlockValidationError};
+    use alloy_primitives::TxKind;
+    use hanzo_evm_eth_execution::EthEvmConfig;
+    use hanzo_evm_ethereum_primitives::{
+        Block, BlockBody, EthPrimitives, Receipt, Transaction, TransactionSigned,
+    };
+    use hanzo_evm_execution::{execute::WithTxEnv, TransactionResultsCommitter};
+    use hanzo_evm_primitives_traits::{Block as _, RecoveredBlock};
+    use hanzo_evm_provider::{
+        test_utils::{ExtendedAccount, MockEthProvider},
+        BlockExecutionResult, StateProviderFactory,
+    };
+    use hanzo_evm_revm::db::{
+        states::bundle_state::BundleRetention, BundleState, CacheDB, EmptyDB,
+    };
+    use hanzo_evm_testing_utils::generators::{self, generate_key, sign_tx_with_key_pair};
+    use revm_state::{Account, AccountStatus, EvmStorageSlot};
+    use std::collections::BTreeMap;
+
+    fn account_changes(address: Address) -> AccountChanges {
+        AccountChanges {
+            address,
+            storage_changes: vec![],
+            storage_reads: vec![],
+            balance_changes: vec![],
+            nonce_changes: vec![],
+            code_changes: vec![],
+        }
+    }
+
+    /// Returns a block with transfers between two funded accounts and the provider with their
+    /// parent state. The transactions depend on each other, so their views of the state differ.
+    fn transfers_block() -> (MockEthProvider, RecoveredBlock<Block>) {
+        let mut rng = generators::rng();
+        let (alice, bob) = (generate_key(&mut rng), generate_key(&mut rng));
+        let transfer = |key, nonce, to| {
+            sign_tx_with_key_pair(
+                key,
+                Transaction::Legacy(TxLegacy {
+                    chain_id: None,
+                    nonce,
+                    gas_price: 1,
+                    gas_limit: 21_000,
+                    to: TxKind::Call(to),
+                    value: U256::from(1_000),
+                    input: Default::default(),
+                }),
+            )
+        };
+
+        let transactions: Vec<TransactionSigned> = vec![
+            transfer(alice, 0, Address::repeat_byte(0x11)),
+            transfer(bob, 0, Address::repeat_byte(0x22)),
+            transfer(alice, 1, Address::repeat_byte(0x11)),
+        ];
+        let block = Block {
+            header: Header {
+                number: 1,
+                gas_limit: 1_000_000,
+                beneficiary: Address::repeat_byte(0xcc),
+                ..Default::default()
+            },
+            body: BlockBody { transactions, ..Default::default() },
+        }
+        .seal_slow()
+        .try_recover()
+        .unwrap();
+
+        let provider = MockEthProvider::default();
+        for sender in block.senders() {
+            provider.add_account(*sender, ExtendedAccount::new(0, U256::from(1_000_000_000u64)));
+        }
+        (provider, block)
+    }
+
+    /// Executes the block sequentially and returns its result, its bundle state and the BAL of its
+    /// transactions.
+    fn execute_sequentially(
+        evm_config: &EthEvmConfig,
+        provider: &MockEthProvider,
+        block: &RecoveredBlock<Block>,
+    ) -> (BlockExecutionResult<Receipt>, BundleState, BlockAccessList) {
+        let mut db = State::builder()
+            .with_database(StateProviderDatabase::new(provider.latest().unwrap()))
+            .with_bundle_update()
+            .build();
+
+        let (state_tx, state_rx) = mpsc::channel();
+        let result = evm_config
+            .executor_for_block(&mut db, block.sealed_block())
+            .unwrap()
+            .with_state_hook(Some(Box::new(move |source: StateChangeSource, state: &EvmState| {
+                if let StateChangeSource::Transaction(position) = source {
+                    let _ = state_tx.send((position, state.clone()));
+                }
+            })))
+            .execute_block(block.transactions_recovered())
+            .unwrap();
+        db.merge_transitions(BundleRetention::Reverts);
+
+        // record every change of the transactions at their BAL index
+        let mut accounts = BTreeMap::<Address, AccountChanges>::new();
+        for (position, state) in state_rx.try_iter() {
+            let index = position + 1;
+            for (address, account) in state {
+                if !account.is_touched() {
+                    continue
+                }
+                let changes = accounts.entry(address).or_insert_with(|| account_changes(address));
+                if account.info.balance != account.original_info.balance {
+                    changes
+                        .balance_changes
+                        .push(BalanceChange::new(index as _, account.info.balance));
+                }
+                if account.info.nonce != account.original_info.nonce {
+                    changes.nonce_changes.push(NonceChange::new(index as _, account.info.nonce));
+                }
+            }
+        }
+
+        (result, db.take_bundle(), accounts.into_values().collect())
+    }
+
+    /// Executes the transactions of the block in parallel on their views of the BAL.
+    fn execute_parallel(
+        evm_config: &EthEvmConfig,
+        provider: &MockEthProvider,
+        block: &RecoveredBlock<Block>,
+        bal: BlockAccessList,
+    ) -> Result<Vec<ResultAndState<HaltReasonFor<EthEvmConfig>>>, BalConflict> {
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(2).build().unwrap();
+        let builder = StateProviderBuilder::<EthPrimitives, _>::new(
+            provider.clone(),
+            block.header().parent_hash,
+            None,
+        );
+        let bal = Arc::new(BalState::new(Arc::new(bal)));
+        let evm_env = evm_config.evm_env(block.header()).unwrap();
+        let transactions = block
+            .transactions_recovered()
+            .map(|tx| WithTxEnv { tx_env: evm_config.tx_env(tx), tx: Arc::new(tx.cloned()) })
+            .collect::<Vec<_>>();
+
+        let ctx = evm_config.context_for_block(block.sealed_block()).unwrap();
+        validate_pre_execution(evm_config, evm_env.clone(), ctx, &builder, &bal)?;
+        execute_transactions(&pool, evm_config, &evm_env, &builder, None, &bal, &transactions)
+            .map(|(results, _)| results)
+    }
+
+    #[test]
+    fn test_parallel_execution_matches_sequential() {
+        let (provider, block) = transfers_block();
+        let evm_config = EthEvmConfig::mainnet();
+        let (expected, expected_bundle, bal) = execute_sequentially(&evm_config, &provider, &block);
+
+        let results = execute_parallel(&evm_config, &provider, &block, bal).unwrap();
+
+        let mut db = State::builder()
+            .with_database(StateProviderDatabase::new(provider.latest().unwrap()))
+            .with_bundle_update()
+            .build();
+        let evm_env = evm_config.evm_env(block.header()).unwrap();
+        let evm = evm_config.evm_with_env(&mut db, evm_env);
+        let ctx = evm_config.context_for_block(block.sealed_block()).unwrap();
+        let result = evm_config
+            .transaction_results_committer()
+            .unwrap()
+            .commit_transaction_results(
+                evm,
+                ctx,
+                block.body().transactions.iter().zip(results),
+                None,
+            )
+            .unwrap();
+        db.merge_transitions(BundleRetention::Reverts);
+
+        assert_eq!(result, expected);
+        assert_eq!(db.take_bundle(), expected_bundle);
+    }
+
+    #[test]
+    fn test_parallel_execution_conflict() {
+        let (provider, block) = transfers_block();
+        let evm_config = EthEvmConfig::mainnet();
+        let (_, _, mut bal) = execute_sequentially(&evm_config, &provider, &block);
+
+        // the BAL claims a different balance of the first sender after its first transaction
+        let sender = block.senders()[0];
+        let changes = bal.iter_mut().find(|changes| changes.address == sender).unwrap();
+        changes.balance_changes[0].post_balance += U256::from(1);
+
+        // the block is executed sequentially if the parallel execution conflicts with the BAL
+        let conflict = execute_parallel(&evm_config, &provider, &block, bal).unwrap_err();
+        assert_eq!(conflict, BalConflict::Account(sender));
+        assert!(conflict.is_mismatch());
+    }
+
+    #[test]
+    fn test_parallel_execution_exceeds_block_gas_limit() {
+        let (provider, block) = transfers_block();
+        let evm_config = EthEvmConfig::mainnet();
+        let (_, _, bal) = execute_sequentially(&evm_config, &provider, &block);
+
+        // every transaction fits into the block on its own, but the last one exceeds the gas
+        // that is left after the first two
+        let senders = block.senders().to_vec();
+        let mut block = block.into_block();
+        block.header.gas_limit = 50_000;
+        let block = RecoveredBlock::new_unhashed(block, senders);
+
+        let results = execute_parallel(&evm_config, &provider, &block, bal).unwrap();
+
+        let mut db = State::builder()
+            .with_database(StateProviderDatabase::new(provider.latest().unwrap()))
+            .with_bundle_update()
+            .build();
+        let evm_env = evm_config.evm_env(block.header()).unwrap();
+        let evm = evm_config.evm_with_env(&mut db, evm_env);
+        let ctx = evm_config.context_for_block(block.sealed_block()).unwrap();
+        let err = evm_config
+            .transaction_results_committer()
+            .unwrap()
+            .commit_transaction_results(
+                evm,
+                ctx,
+                block.body().transactions.iter().zip(results),
+                None,
+            )
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            BlockExecutionError::Validation(
+                BlockValidationError::TransactionGasLimitMoreThanAvailableBlockGas {
+                    transaction_gas_limit: 21_000,
+                    block_available_gas: 8_000,
+                }
+            )
+        ));
+    }
+
+    /// Returns a touched account of the given state change.
+    fn touched_account(original: AccountInfo, info: AccountInfo) -> Account {
+        Account {
+            info,
+            original_info: Box::new(original),
+            storage: Default::default(),
+            status: AccountStatus::Touched,
+            transaction_id: 0,
+        }
+    }
+
+    #[test]
+    fn test_bal_state_database_views() {
+        let address = Address::random();
+        let slot = U256::from(1);
+        let code = Bytes::from_static(&[0x60, 0x00]);
+
+        let mut changes = account_changes(address);
+        changes.balance_changes =
+            vec![BalanceChange::new(1, U256::from(90)), BalanceChange::new(3, U256::from(80))];
+        changes.nonce_changes = vec![NonceChange::new(1, 1)];
+        changes.code_changes = vec![CodeChange::new(2, code.clone())];
+        changes.storage_changes =
+            vec![SlotChanges { slot, changes: vec![StorageChange::new(2, U256::from(7))] }];
+        let bal = Arc::new(BalState::new(Arc::new(vec![changes])));
+
+        let mut inner = CacheDB::new(EmptyDB::default());
+        inner.insert_account_info(
+            address,
+            AccountInfo { balance: U256::from(100), ..Default::default() },
+        );
+        inner.insert_account_storage(address, slot, U256::from(5)).unwrap();
+        let mut db = BalStateDatabase::new(inner, bal);
+
+        // the first transaction sees the parent state
+        db.set_index(1);
+        let info = db.basic(address).unwrap().unwrap();
+        assert_eq!((info.balance, info.nonce, info.code_hash), (U256::from(100), 0, KECCAK_EMPTY));
+        assert_eq!(db.storage(address, slot).unwrap(), U256::from(5));
+
+        // the third transaction sees the changes of the first two
+        db.set_index(3);
+        let info = db.basic(address).unwrap().unwrap();
+        assert_eq!((info.balance, info.nonce), (U256::from(90), 1));
+        assert_eq!(info.code_hash, keccak256(&code));
+        assert_eq!(db.code_by_hash(info.code_hash).unwrap().original_bytes(), code);
+        assert_eq!(db.storage(address, slot).unwrap(), U256::from(7));
+
+        db.set_index(4);
+        assert_eq!(db.basic(address).unwrap().unwrap().balance, U256::from(80));
+
+        // accounts that aren't in the parent state are created by the BAL
+        let new_address = Address::random();
+        let mut new_changes = account_changes(new_address);
+        new_changes.balance_changes = vec![BalanceChange::new(1, U256::from(1))];
+        let mut db = BalStateDatabase::new(
+            CacheDB::new(EmptyDB::default()),
+            Arc::new(BalState::new(Arc::new(vec![new_changes]))),
+        );
+        db.set_index(1);
+        assert_eq!(db.basic(new_address).unwrap(), None);
+        db.set_index(2);
+        assert_eq!(db.basic(new_address).unwrap().unwrap().balance, U256::from(1));
+    }
+
+    #[test]
+    fn test_validate_changes() {
+        let sender = Address::random();
+        let contract = Address::random();
+        let slot = U256::from(1);
+
+        let mut sender_changes = account_changes(sender);
+        sender_changes.balance_changes = vec![BalanceChange::new(1, U256::from(90))];
+        sender_changes.nonce_changes = vec![NonceChange::new(1, 1)];
+        let mut contract_changes = account_changes(contract);
+        contract_changes.storage_changes =
+            vec![SlotChanges { slot, changes: vec![StorageChange::new(1, U256::from(7))] }];
+        let bal = BalState::new(Arc::new(vec![sender_changes, contract_changes]));
+
+        let sender_account = touched_account(
+            AccountInfo { balance: U256::from(100), ..Default::default() },
+            AccountInfo { balance: U256::from(90), nonce: 1, ..Default::default() },
+        );
+        let mut contract_account =
+            touched_account(AccountInfo::default(), AccountInfo::default());
+        contract_account
+            .storage
+            .insert(slot, EvmStorageSlot::new_changed(U256::ZERO, U256::from(7), 0));
+
+        let state: EvmState =
+            [(sender, sender_account.clone()), (contract, contract_account.clone())]
+                .into_iter()
+                .collect();
+        assert_eq!(bal.validate_changes(1, &state), Ok(()));
+
+        // the changes were recorded for another transaction
+        let sender_state: EvmState = [(sender, sender_account.clone())].into_iter().collect();
+        assert_eq!(bal.validate_changes(2, &sender_state), Err(BalConflict::Account(sender)));
+
+        // a different value was written
+        let mut wrong_value = contract_account.clone();
+        wrong_value.storage.insert(slot, EvmStorageSlot::new_changed(U256::ZERO, U256::from(8), 0));
+        let state: EvmState =
+            [(sender, sender_account.clone()), (contract, wrong_value)].into_iter().collect();
+        assert_eq!(bal.validate_changes(1, &state), Err(BalConflict::Storage(contract, slot)));
+
+        // a change recorded by the BAL wasn't made
+        assert_eq!(
+            bal.validate_changes(1, &sender_state),
+            Err(BalConflict::Storage(contract, slot))
+        );
+    }
+}
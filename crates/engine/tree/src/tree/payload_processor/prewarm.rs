@@ -193,6 +193,10 @@ where
             drop(tx_sender);
             while done_rx.recv().is_ok() {}
 
+            // release the saved cache before the task is notified, so it's available once the
+            // task exits
+            drop(ctx);
+
             let _ = actions_tx
                 .send(PrewarmTaskEvent::FinishedTxExecution { executed_transactions: tx_count });
         });
@@ -515,7 +519,7 @@ where
             evm.precompiles_mut().map_pure_precompiles(|address, precompile| {
                 CachedPrecompile::wrap(
                     precompile,
-                    precompile_cache_map.cache_for_address(*address),
+                    precompile_cache_map.cache_for(*address, &spec_id),
                     spec_id,
                     None, // No metrics for prewarm
                 )
@@ -1,10 +1,13 @@
 //! Types and traits for validating blocks and payloads.
 
 use crate::tree::{
-    cached_state::CachedStateProvider,
+    cached_state::{CachedStateMetrics, CachedStateProvider, ExecutionCache},
     error::{InsertBlockError, InsertBlockErrorKind, InsertPayloadError},
     instrumented_state::InstrumentedStateProvider,
-    payload_processor::PayloadProcessor,
+    payload_processor::{
+        parallel::{self, BalState},
+        PayloadProcessor, SpeculativeHandle,
+    },
     precompile_cache::{CachedPrecompile, CachedPrecompileMetrics, PrecompileCacheMap},
     sparse_trie::StateRootComputeOutcome,
     CacheWaitDurations, EngineApiMetrics, EngineApiTreeState, ExecutionEnv, PayloadHandle,
@@ -13,7 +16,7 @@ use crate::tree::{
 use alloy_consensus::transaction::{Either, TxHashRef};
 use alloy_eip7928::BlockAccessList;
 use alloy_eips::{eip1898::BlockWithParent, eip4895::Withdrawal, NumHash};
-use alloy_evm::Evm;
+use alloy_evm::{Evm, RecoveredTx};
 use alloy_primitives::B256;
 #[cfg(feature = "trie-debug")]
 use reth_trie_sparse::debug_recorder::TrieDebugRecorder;
@@ -27,25 +30,26 @@ use reth_engine_primitives::{
 use hanzo_evm_errors::{BlockExecutionError, ProviderResult};
 use hanzo_evm_execution::{
     block::BlockExecutor, execute::ExecutableTxFor, ConfigureEvm, EvmEnvFor, ExecutionCtxFor,
-    SpecFor,
+    HaltReasonFor, SpecFor, TransactionResultsCommitter,
 };
 use hanzo_evm_payload_primitives::{
     BuiltPayload, InvalidPayloadAttributesError, NewPayloadError, PayloadTypes,
 };
 use reth_primitives_traits::{
     AlloyBlockHeader, BlockBody, BlockTy, FastInstant as Instant, GotExpected, NodePrimitives,
-    RecoveredBlock, SealedBlock, SealedHeader, SignerRecoverable,
+    Recovered, RecoveredBlock, SealedBlock, SealedHeader, SignerRecoverable,
 };
 use hanzo_evm_provider::{
     providers::OverlayStateProviderFactory, BlockExecutionOutput, BlockNumReader, BlockReader,
     ChangeSetReader, DatabaseProviderFactory, DatabaseProviderROFactory, HashedPostStateProvider,
-    ProviderError, PruneCheckpointReader, StageCheckpointReader, StateProvider,
-    StateProviderFactory, StateReader, StorageChangeSetReader, StorageSettingsCache,
+    ProviderError, PruneCheckpointReader, StageCheckpointReader, StateCursorProvider,
+    StateProvider, StateProviderFactory, StateReader, StorageChangeSetReader, StorageSettingsCache,
 };
 use hanzo_evm_revm::db::{states::bundle_state::BundleRetention, State};
 use hanzo_evm_trie::{updates::TrieUpdates, HashedPostState, StateRoot};
 use hanzo_evm_trie_db::ChangesetCache;
 use hanzo_evm_trie_parallel::root::{ParallelStateRoot, ParallelStateRootError};
+use revm::context::result::ResultAndState;
 use revm_primitives::Address;
 use std::{
     collections::HashMap,
@@ -102,6 +106,30 @@ impl<'a, N: NodePrimitives> TreeCtx<'a, N> {
     }
 }
 
+/// Source of the transactions of a predicted next block.
+///
+/// This is used by [`BasicEngineValidator`] to speculatively execute a block on top of the
+/// canonical head while the engine waits for the next payload, see
+/// [`TreeConfig::speculative_execution`].
+pub trait SpeculativeTransactions<N: NodePrimitives>: Send + Sync {
+    /// Returns the transactions of the predicted block on top of `head`, in execution order.
+    fn best_transactions(&self, head: &SealedHeader<N::BlockHeader>)
+        -> Vec<Recovered<N::SignedTx>>;
+}
+
+impl<N, F> SpeculativeTransactions<N> for F
+where
+    N: NodePrimitives,
+    F: Fn(&SealedHeader<N::BlockHeader>) -> Vec<Recovered<N::SignedTx>> + Send + Sync,
+{
+    fn best_transactions(
+        &self,
+        head: &SealedHeader<N::BlockHeader>,
+    ) -> Vec<Recovered<N::SignedTx>> {
+        self(head)
+    }
+}
+
 /// A helper type that provides reusable payload validation logic for network-specific validators.
 ///
 /// This type satisfies [`EngineValidator`] and is responsible for executing blocks/payloads.
@@ -139,6 +167,12 @@ where
     changeset_cache: ChangesetCache,
     /// Task runtime for spawning parallel work.
     runtime: reth_tasks::Runtime,
+    /// Source of the transactions that are speculatively executed on top of the canonical head.
+    #[debug(skip)]
+    speculative_transactions: Option<Box<dyn SpeculativeTransactions<Evm::Primitives>>>,
+    /// The running speculative execution of a predicted block, if any.
+    speculative_execution:
+        Option<SpeculativeHandle<<Evm::Primitives as NodePrimitives>::Receipt>>,
 }
 
 impl<N, P, Evm, V> BasicEngineValidator<P, Evm, V>
@@ -151,7 +185,8 @@ where
                           + ChangeSetReader
                           + StorageChangeSetReader
                           + BlockNumReader
-                          + StorageSettingsCache,
+                          + StorageSettingsCache
+                          + StateCursorProvider,
         > + BlockReader<Header = N::BlockHeader>
         + ChangeSetReader
         + BlockNumReader
@@ -194,9 +229,37 @@ where
             validator,
             changeset_cache,
             runtime,
+            speculative_transactions: None,
+            speculative_execution: None,
         }
     }
 
+    /// Sets the precompile cache, e.g. to share it with RPC execution or to use a cache restored
+    /// from disk.
+    pub fn with_precompile_cache_map(
+        mut self,
+        precompile_cache_map: PrecompileCacheMap<SpecFor<Evm>>,
+    ) -> Self {
+        self.payload_processor = PayloadProcessor::new(
+            self.runtime.clone(),
+            self.hanzo_evm_config.clone(),
+            &self.config,
+            precompile_cache_map.clone(),
+        );
+        self.precompile_cache_map = precompile_cache_map;
+        self
+    }
+
+    /// Sets the source of the transactions that are speculatively executed on top of the
+    /// canonical head, if [`TreeConfig::speculative_execution`] is enabled.
+    pub fn with_speculative_transactions(
+        mut self,
+        speculative_transactions: impl SpeculativeTransactions<N> + 'static,
+    ) -> Self {
+        self.speculative_transactions = Some(Box::new(speculative_transactions));
+        self
+    }
+
     /// Converts a [`BlockOrPayload`] to a recovered block.
     #[instrument(level = "debug", target = "engine::tree::payload_validator", skip_all)]
     pub fn convert_to_block<T: PayloadTypes<BuiltPayload: BuiltPayload<Primitives = N>>>(
@@ -398,6 +461,9 @@ where
 
         let parent_hash = input.parent_hash();
 
+        // Cancel the speculative execution, so the caches it warmed can be used
+        self.cancel_speculative_execution(parent_hash);
+
         trace!(target: "engine::tree::payload_validator", "Fetching block state provider");
         let _enter =
             debug_span!(target: "engine::tree::payload_validator", "state_provider").entered();
@@ -459,6 +525,16 @@ where
             .map_err(Box::<dyn std::error::Error + Send + Sync>::from))
         .map(Arc::new);
 
+        // Execute the transactions in parallel if the block comes with a BAL and the EVM can commit
+        // the results of transactions executed elsewhere
+        let parallel_execution = block_access_list
+            .clone()
+            .filter(|_| {
+                self.config.parallel_execution() &&
+                    self.hanzo_evm_config.transaction_results_committer().is_some()
+            })
+            .map(|bal| (bal, provider_builder.clone()));
+
         // Create lazy overlay from ancestors - this doesn't block, allowing execution to start
         // before the trie data is ready. The overlay will be computed on first access.
         let (lazy_overlay, anchor_hash) = Self::get_parent_lazy_overlay(parent_hash, ctx.state());
@@ -494,11 +570,16 @@ where
         // Execute the block and handle any execution errors.
         // The receipt root task is spawned before execution and receives receipts incrementally
         // as transactions complete, allowing parallel computation during execution.
-        let (output, senders, receipt_root_rx) =
-            match self.execute_block(state_provider, env, &input, &mut handle) {
-                Ok(output) => output,
-                Err(err) => return self.handle_execution_error(input, err, &parent_block),
-            };
+        let (output, senders, receipt_root_rx) = match self.execute_block(
+            state_provider,
+            env,
+            &input,
+            &mut handle,
+            parallel_execution,
+        ) {
+            Ok(output) => output,
+            Err(err) => return self.handle_execution_error(input, err, &parent_block),
+        };
 
         // After executing the block we can stop prewarming transactions
         handle.stop_prewarming_execution();
@@ -754,9 +835,11 @@ where
     /// Executes a block with the given state provider.
     ///
     /// This method orchestrates block execution:
-    /// 1. Sets up the EVM with state database and precompile caching
-    /// 2. Spawns a background task for incremental receipt root computation
-    /// 3. Executes transactions with metrics collection via state hooks
+    /// 1. Spawns a background task for incremental receipt root computation
+    /// 2. Executes the transactions in parallel if a block access list is given, and commits their
+    ///    results if they all match it
+    /// 3. Otherwise sets up the EVM with state database and precompile caching and executes the
+    ///    transactions sequentially with metrics collection via state hooks
     /// 4. Merges state transitions and records execution metrics
     #[instrument(level = "debug", target = "engine::tree::payload_validator", skip_all)]
     #[expect(clippy::type_complexity)]
@@ -765,7 +848,12 @@ where
         state_provider: S,
         env: ExecutionEnv<Evm>,
         input: &BlockOrPayload<T>,
-        handle: &mut PayloadHandle<impl ExecutableTxFor<Evm>, Err, N::Receipt>,
+        handle: &mut PayloadHandle<
+            impl ExecutableTxFor<Evm> + Clone + Send + Sync,
+            Err,
+            N::Receipt,
+        >,
+        parallel_execution: Option<(Arc<BlockAccessList>, StateProviderBuilder<N, P>)>,
     ) -> Result<
         (
             BlockExecutionOutput<N::Receipt>,
@@ -791,34 +879,6 @@ where
                 .build()
         });
 
-        let (spec_id, mut executor) = {
-            let _span = debug_span!(target: "engine::tree", "create_evm").entered();
-            let spec_id = *env.evm_env.spec_id();
-            let evm = self.evm_config.evm_with_env(&mut db, env.evm_env);
-            let ctx = self
-                .execution_ctx_for(input)
-                .map_err(|e| InsertBlockErrorKind::Other(Box::new(e)))?;
-            let executor = self.evm_config.create_executor(evm, ctx);
-            (spec_id, executor)
-        };
-
-        if !self.config.precompile_cache_disabled() {
-            let _span = debug_span!(target: "engine::tree", "setup_precompile_cache").entered();
-            executor.evm_mut().precompiles_mut().map_pure_precompiles(|address, precompile| {
-                let metrics = self
-                    .precompile_cache_metrics
-                    .entry(*address)
-                    .or_insert_with(|| CachedPrecompileMetrics::new_with_address(*address))
-                    .clone();
-                CachedPrecompile::wrap(
-                    precompile,
-                    self.precompile_cache_map.cache_for_address(*address),
-                    spec_id,
-                    Some(metrics),
-                )
-            });
-        }
-
         // Spawn background task to compute receipt root and logs bloom incrementally.
         // Unbounded channel is used since tx count bounds capacity anyway (max ~30k txs per block).
         let receipts_len = input.transaction_count();
@@ -830,25 +890,103 @@ where
             .spawn_blocking_named("receipt-root", move || task_handle.run(receipts_len));
 
         let transaction_count = input.transaction_count();
-        let executor = executor.with_state_hook(Some(Box::new(handle.state_hook())));
-
         let execution_start = Instant::now();
 
-        // Execute all transactions and finalize
-        let (executor, senders) = self.execute_transactions(
-            executor,
-            transaction_count,
-            handle.iter_transactions(),
-            &receipt_tx,
-        )?;
-        drop(receipt_tx);
+        // Execute the transactions in parallel first, the results are only committed if every
+        // transaction matched the BAL
+        let mut transactions = None;
+        let mut parallel_results = None;
+        if let Some((block_access_list, provider_builder)) = parallel_execution {
+            let txs = handle
+                .iter_transactions()
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(BlockExecutionError::other)?;
+            parallel_results = self.execute_transactions_parallel(
+                &env,
+                input,
+                &txs,
+                block_access_list,
+                &provider_builder,
+                handle.caches().zip(handle.cache_metrics()),
+            );
+            transactions = Some(txs);
+        }
 
-        // Finish execution and get the result
-        let post_exec_start = Instant::now();
-        let (_evm, result) = debug_span!(target: "engine::tree", "BlockExecutor::finish")
-            .in_scope(|| executor.finish())
-            .map(|(evm, result)| (evm.into_db(), result))?;
-        self.metrics.record_post_execution(post_exec_start.elapsed());
+        let committer = self.hanzo_evm_config.transaction_results_committer();
+        let (result, senders) = if let Some(((txs, results), committer)) =
+            transactions.as_ref().zip(parallel_results).zip(committer)
+        {
+            let _span = debug_span!(target: "engine::tree", "commit_transaction_results").entered();
+            let evm = self.hanzo_evm_config.evm_with_env(&mut db, env.evm_env);
+            let ctx = self
+                .execution_ctx_for(input)
+                .map_err(|e| InsertBlockErrorKind::Other(Box::new(e)))?;
+            let result = committer.commit_transaction_results(
+                evm,
+                ctx,
+                txs.iter().map(|tx| tx.tx()).zip(results),
+                Some(Box::new(handle.state_hook())),
+            )?;
+
+            for (tx_index, receipt) in result.receipts.iter().enumerate() {
+                let _ = receipt_tx.send(IndexedReceipt::new(tx_index, receipt.clone()));
+            }
+            let senders = txs.iter().map(|tx| *tx.signer()).collect();
+
+            (result, senders)
+        } else {
+            let (spec_id, mut executor) = {
+                let _span = debug_span!(target: "engine::tree", "create_evm").entered();
+                let spec_id = *env.evm_env.spec_id();
+                let evm = self.evm_config.evm_with_env(&mut db, env.evm_env);
+                let ctx = self
+                    .execution_ctx_for(input)
+                    .map_err(|e| InsertBlockErrorKind::Other(Box::new(e)))?;
+                let executor = self.evm_config.create_executor(evm, ctx);
+                (spec_id, executor)
+            };
+
+            if !self.config.precompile_cache_disabled() {
+                let _span =
+                    debug_span!(target: "engine::tree", "setup_precompile_cache").entered();
+                executor.evm_mut().precompiles_mut().map_pure_precompiles(|address, precompile| {
+                    let metrics = self
+                        .precompile_cache_metrics
+                        .entry(*address)
+                        .or_insert_with(|| CachedPrecompileMetrics::new_with_address(*address))
+                        .clone();
+                    CachedPrecompile::wrap(
+                        precompile,
+                        self.precompile_cache_map.cache_for(*address, &spec_id),
+                        spec_id,
+                        Some(metrics),
+                    )
+                });
+            }
+
+            let executor = executor.with_state_hook(Some(Box::new(handle.state_hook())));
+
+            // Transactions that were already collected for the parallel execution are executed
+            // again from the start
+            let transactions = match transactions {
+                Some(txs) => Either::Left(txs.into_iter().map(Ok::<_, Err>)),
+                None => Either::Right(handle.iter_transactions()),
+            };
+
+            // Execute all transactions and finalize
+            let (executor, senders) =
+                self.execute_transactions(executor, transaction_count, transactions, &receipt_tx)?;
+
+            // Finish execution and get the result
+            let post_exec_start = Instant::now();
+            let (_evm, result) = debug_span!(target: "engine::tree", "BlockExecutor::finish")
+                .in_scope(|| executor.finish())
+                .map(|(evm, result)| (evm.into_db(), result))?;
+            self.metrics.record_post_execution(post_exec_start.elapsed());
+
+            (result, senders)
+        };
+        drop(receipt_tx);
 
         // Merge transitions into bundle state
         debug_span!(target: "engine::tree", "merge_transitions")
@@ -864,6 +1002,75 @@ where
         Ok((output, senders, result_rx))
     }
 
+    /// Executes the transactions of a block in parallel based on its block access list.
+    ///
+    /// Returns `None` if the execution diverged from the block access list, in which case the
+    /// transactions need to be executed sequentially.
+    fn execute_transactions_parallel<T, Tx>(
+        &self,
+        env: &ExecutionEnv<Evm>,
+        input: &BlockOrPayload<T>,
+        transactions: &[Tx],
+        block_access_list: Arc<BlockAccessList>,
+        provider_builder: &StateProviderBuilder<N, P>,
+        caches: Option<(ExecutionCache, CachedStateMetrics)>,
+    ) -> Option<Vec<ResultAndState<HaltReasonFor<Evm>>>>
+    where
+        V: PayloadValidator<T, Block = N::Block>,
+        T: PayloadTypes<BuiltPayload: BuiltPayload<Primitives = N>>,
+        Evm: ConfigureEngineEvm<T::ExecutionData, Primitives = N>,
+        Tx: ExecutableTxFor<Evm> + Clone + Sync,
+    {
+        let _span = debug_span!(target: "engine::tree", "parallel_execution").entered();
+        let start = Instant::now();
+        let bal = Arc::new(BalState::new(block_access_list));
+
+        // If the context can't be created the sequential execution reports the error
+        let ctx = self.execution_ctx_for(input).ok()?;
+        let result = parallel::validate_pre_execution(
+            &self.hanzo_evm_config,
+            env.evm_env.clone(),
+            ctx,
+            provider_builder,
+            &bal,
+        )
+        .and_then(|()| {
+            parallel::execute_transactions(
+                self.runtime.cpu_pool(),
+                &self.hanzo_evm_config,
+                &env.evm_env,
+                provider_builder,
+                caches,
+                &bal,
+                transactions,
+            )
+        });
+
+        match result {
+            Ok((results, transaction_time)) => {
+                self.metrics.record_parallel_execution(
+                    transactions.len(),
+                    transaction_time,
+                    start.elapsed(),
+                );
+                Some(results)
+            }
+            Err(conflict) => {
+                debug!(
+                    target: "engine::tree::payload_validator",
+                    %conflict,
+                    "Parallel execution diverged from the block access list, executing sequentially"
+                );
+                let metrics = &self.metrics.parallel_execution;
+                if conflict.is_mismatch() {
+                    metrics.conflicts_total.increment(1);
+                }
+                metrics.fallbacks_total.increment(1);
+                None
+            }
+        }
+    }
+
     /// Executes transactions and collects senders, streaming receipts to a background task.
     ///
     /// This method handles:
@@ -1302,7 +1509,7 @@ where
         block_access_list: Option<Arc<BlockAccessList>>,
     ) -> Result<
         PayloadHandle<
-            impl ExecutableTxFor<Evm> + use<N, P, Evm, V, T>,
+            impl ExecutableTxFor<Evm> + Clone + Send + Sync + use<N, P, Evm, V, T>,
             impl core::error::Error + Send + Sync + 'static + use<N, P, Evm, V, T>,
             N::Receipt,
         >,
@@ -1350,6 +1557,174 @@ where
         }
     }
 
+    /// Speculatively executes a predicted block on top of the canonical `head`.
+    ///
+    /// The predicted block is executed with the environment of the head block, since the
+    /// attributes of the next block are only known once its payload arrives.
+    fn speculate_on_head(
+        &mut self,
+        head: &SealedHeader<N::BlockHeader>,
+        state: &EngineApiTreeState<N>,
+    ) {
+        if !self.config.speculative_execution() ||
+            self.speculative_execution.as_ref().is_some_and(|s| s.parent_hash() == head.hash())
+        {
+            return
+        }
+        let Some(speculative_transactions) = &self.speculative_transactions else { return };
+
+        // a prediction on top of a previous head can't be reused anymore
+        if let Some(speculative_execution) = self.speculative_execution.take() {
+            speculative_execution.cancel();
+        }
+
+        let transactions = speculative_transactions.best_transactions(head);
+        if transactions.is_empty() {
+            return
+        }
+
+        let evm_env = match self.hanzo_evm_config.evm_env(head.header()) {
+            Ok(evm_env) => evm_env,
+            Err(err) => {
+                debug!(target: "engine::tree::payload_validator", %err, "Failed to create EVM environment for speculative execution");
+                return
+            }
+        };
+        let env = ExecutionEnv {
+            evm_env,
+            hash: B256::ZERO,
+            parent_hash: head.hash(),
+            parent_state_root: head.state_root(),
+            transaction_count: transactions.len(),
+            gas_used: head.gas_used(),
+            withdrawals: None,
+        };
+
+        debug!(
+            target: "engine::tree::payload_validator",
+            head = ?head.num_hash(),
+            transactions = transactions.len(),
+            "Speculatively executing predicted block"
+        );
+        let transaction_count = transactions.len();
+        if self.spawn_speculative_execution(
+            env,
+            (transactions, Ok::<_, core::convert::Infallible>),
+            state,
+        ) {
+            let metrics = &self.metrics.speculative_execution;
+            metrics.blocks_total.increment(1);
+            metrics.transactions.record(transaction_count as f64);
+        }
+    }
+
+    /// Executes a block that is validated next on the payload processor's executor.
+    ///
+    /// Unlike [`Self::speculate_on_head`], the environment and transactions of the block are
+    /// known, so its validation finds the state it reads in the warmed caches and its proofs
+    /// revealed in the sparse trie.
+    fn prepare_block_execution(
+        &mut self,
+        block: &SealedBlock<N::Block>,
+        state: &EngineApiTreeState<N>,
+    ) {
+        if self.speculative_execution.as_ref().is_some_and(|s| s.block_hash() == block.hash()) {
+            return
+        }
+
+        let parent_hash = block.parent_hash();
+        let parent = match self.sealed_header_by_hash(parent_hash, state) {
+            Ok(Some(parent)) => parent,
+            Ok(None) => return,
+            Err(err) => {
+                debug!(target: "engine::tree::payload_validator", %err, "Failed to find parent for block execution");
+                return
+            }
+        };
+        let evm_env = match self.hanzo_evm_config.evm_env(block.header()) {
+            Ok(evm_env) => evm_env,
+            Err(err) => {
+                debug!(target: "engine::tree::payload_validator", %err, "Failed to create EVM environment for block execution");
+                return
+            }
+        };
+        let env = ExecutionEnv {
+            evm_env,
+            hash: block.hash(),
+            parent_hash,
+            parent_state_root: parent.state_root(),
+            transaction_count: block.transaction_count(),
+            gas_used: block.gas_used(),
+            withdrawals: block.body().withdrawals().map(|w| w.to_vec()),
+        };
+
+        // a prediction or an execution on top of another parent can't be reused anymore
+        if let Some(speculative_execution) = self.speculative_execution.take() {
+            speculative_execution.cancel();
+        }
+
+        debug!(
+            target: "engine::tree::payload_validator",
+            block = ?block.num_hash(),
+            "Executing block ahead of its validation"
+        );
+        let convert = |tx: N::SignedTx| tx.try_into_recovered();
+        self.spawn_speculative_execution(env, (block.body().clone_transactions(), convert), state);
+    }
+
+    /// Spawns the execution of `transactions` on top of `env.parent_hash` on the payload
+    /// processor's executor.
+    ///
+    /// Returns `false` if the state of the parent is not available.
+    fn spawn_speculative_execution<I: ExecutableTxIterator<Evm>>(
+        &mut self,
+        env: ExecutionEnv<Evm>,
+        transactions: I,
+        state: &EngineApiTreeState<N>,
+    ) -> bool {
+        let parent_hash = env.parent_hash;
+        let provider_builder = match self.state_provider_builder(parent_hash, state) {
+            Ok(Some(provider_builder)) => provider_builder,
+            Ok(None) => return false,
+            Err(err) => {
+                debug!(target: "engine::tree::payload_validator", %err, "Failed to create state provider for speculative execution");
+                return false
+            }
+        };
+
+        let (lazy_overlay, anchor_hash) = Self::get_parent_lazy_overlay(parent_hash, state);
+        let overlay_factory =
+            OverlayStateProviderFactory::new(self.provider.clone(), self.changeset_cache.clone())
+                .with_block_hash(Some(anchor_hash))
+                .with_lazy_overlay(lazy_overlay);
+
+        self.speculative_execution = Some(self.payload_processor.spawn_speculative(
+            env,
+            transactions,
+            provider_builder,
+            overlay_factory,
+            &self.config,
+        ));
+        true
+    }
+
+    /// Cancels the speculative execution before a block on top of `parent_hash` is executed.
+    fn cancel_speculative_execution(&mut self, parent_hash: B256) {
+        let Some(speculative_execution) = self.speculative_execution.take() else { return };
+
+        // executions of known blocks are not predictions
+        if speculative_execution.block_hash().is_zero() {
+            let metrics = &self.metrics.speculative_execution;
+            if speculative_execution.parent_hash() == parent_hash {
+                metrics.hits_total.increment(1);
+            } else {
+                metrics.misses_total.increment(1);
+            }
+        }
+
+        speculative_execution.cancel();
+    }
+
     /// Creates a `StateProviderBuilder` for the given parent hash.
     ///
     /// This method checks if the parent is in the tree state (in-memory) or persisted to disk,
@@ -1668,7 +2043,20 @@ pub trait EngineValidator<
     ///
     /// This is invoked when blocks are inserted via `InsertExecutedBlock` (e.g., locally built
     /// blocks by sequencers) to allow implementations to update internal state such as caches.
-    fn on_inserted_executed_block(&self, block: ExecutedBlock<N>);
+    fn on_inserted_executed_block(&mut self, block: ExecutedBlock<N>);
+
+    /// Hook called when a forkchoice update without payload attributes confirmed the canonical
+    /// head.
+    ///
+    /// Implementations may use the time until the next payload arrives to prepare its execution.
+    fn on_canonical_head(&mut self, _head: &SealedHeader<N::BlockHeader>, _ctx: TreeCtx<'_, N>) {}
+
+    /// Hook called when a block whose parent is executed is going to be validated later, e.g. an
+    /// optimistically accepted block.
+    ///
+    /// Implementations may start executing it in the background, so that its validation blocks
+    /// the engine for less time.
+    fn prepare_block(&mut self, _block: &SealedBlock<N::Block>, _ctx: TreeCtx<'_, N>) {}
 }
 
 impl<N, Types, P, Evm, V> EngineValidator<Types> for BasicEngineValidator<P, Evm, V>
@@ -1680,7 +2068,8 @@ where
                           + ChangeSetReader
                           + StorageChangeSetReader
                           + BlockNumReader
-                          + StorageSettingsCache,
+                          + StorageSettingsCache
+                          + StateCursorProvider,
         > + BlockReader<Header = N::BlockHeader>
         + StateProviderFactory
         + StateReader
@@ -1726,12 +2115,21 @@ where
         self.validate_block_with_state(BlockOrPayload::Block(block), ctx)
     }
 
-    fn on_inserted_executed_block(&self, block: ExecutedBlock<N>) {
+    fn on_inserted_executed_block(&mut self, block: ExecutedBlock<N>) {
+        self.cancel_speculative_execution(block.recovered_block().parent_hash());
         self.payload_processor.on_inserted_executed_block(
             block.recovered_block.block_with_parent(),
             &block.execution_output.state,
         );
     }
+
+    fn on_canonical_head(&mut self, head: &SealedHeader<N::BlockHeader>, ctx: TreeCtx<'_, N>) {
+        self.speculate_on_head(head, ctx.state());
+    }
+
+    fn prepare_block(&mut self, block: &SealedBlock<N::Block>, ctx: TreeCtx<'_, N>) {
+        self.prepare_block_execution(block, ctx.state());
+    }
 }
 
 impl<P, Evm, V> WaitForCaches for BasicEngineValidator<P, Evm, V>
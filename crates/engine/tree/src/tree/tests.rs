@@ -1266,6 +1266,112 @@ fn test_on_new_payload_backfill_buffering() {
     assert_eq!(*buffered_block, sealed, "Buffered block should match submitted payload");
 }
 
+/// Test that payloads with a known parent are accepted without execution in optimistic sync mode
+#[test]
+fn test_on_new_payload_optimistic_sync() {
+    hanzo_evm_tracing::init_test_tracing();
+
+    let s = include_str!("../../test-data/holesky/1.rlp");
+    let data = Bytes::from_str(s).unwrap();
+    let block = Block::decode(&mut data.as_ref()).unwrap();
+    let sealed = block.seal_slow();
+    let hash = sealed.hash();
+    let payload = ExecutionPayloadV1::from_block_unchecked(hash, &sealed.clone().into_block());
+
+    let mut test_harness = TestHarness::new(HOLESKY.clone());
+    test_harness.tree.config = test_harness
+        .tree
+        .config
+        .clone()
+        .with_optimistic_sync(true)
+        .with_optimistic_execution_lag(1);
+
+    // make the parent of the payload known
+    let genesis = SealedHeader::seal_slow(HOLESKY.genesis_header().clone());
+    assert_eq!(sealed.parent_hash(), genesis.hash(), "Block 1 should have genesis as parent");
+    test_harness.provider.add_header(genesis.hash(), genesis.clone_header());
+
+    let outcome = test_harness
+        .tree
+        .on_new_payload(ExecutionData {
+            payload: payload.into(),
+            sidecar: ExecutionPayloadSidecar::none(),
+        })
+        .unwrap();
+
+    // Verify response is ACCEPTED and the block is queued for execution
+    assert_eq!(outcome.outcome.status, PayloadStatusEnum::Accepted);
+    assert!(outcome.event.is_none(), "Should not trigger canonicalization before execution");
+    assert_eq!(test_harness.tree.optimistic_blocks.block(&hash), Some(&sealed));
+
+    // Verify a forkchoice update to the unexecuted block is syncing without a download
+    let state = test_harness.fcu_state(hash);
+    let fcu = test_harness
+        .tree
+        .on_forkchoice_updated(state, None, EngineApiMessageVersion::default())
+        .unwrap();
+    assert!(fcu.outcome.forkchoice_status().is_syncing());
+    assert!(fcu.event.is_none(), "Accepted blocks should not be downloaded");
+
+    // Verify the block stays unexecuted within the execution lag
+    test_harness.tree.execute_optimistic_blocks(1, false).unwrap();
+    assert!(test_harness.tree.optimistic_blocks.contains(&hash));
+
+    // Verify pending engine messages are handled before the block is executed
+    test_harness.to_tree_tx.send(FromEngine::DownloadedBlocks(vec![])).unwrap();
+    test_harness.tree.execute_optimistic_blocks(0, false).unwrap();
+    assert!(test_harness.tree.optimistic_blocks.contains(&hash));
+}
+
+/// Test that accepted payloads within the execution lag are drained if the engine is idle or
+/// shutting down
+#[test]
+fn test_optimistic_sync_drains_lagged_blocks() {
+    hanzo_evm_tracing::init_test_tracing();
+
+    let s = include_str!("../../test-data/holesky/1.rlp");
+    let data = Bytes::from_str(s).unwrap();
+    let block = Block::decode(&mut data.as_ref()).unwrap();
+    let sealed = block.seal_slow();
+    let hash = sealed.hash();
+    let payload = ExecutionPayloadV1::from_block_unchecked(hash, &sealed.clone().into_block());
+
+    let mut test_harness = TestHarness::new(HOLESKY.clone());
+    test_harness.tree.config = test_harness
+        .tree
+        .config
+        .clone()
+        .with_optimistic_sync(true)
+        .with_optimistic_execution_lag(1)
+        .with_optimistic_idle_timeout(Duration::from_millis(1));
+
+    let genesis = SealedHeader::seal_slow(HOLESKY.genesis_header().clone());
+    test_harness.provider.add_header(genesis.hash(), genesis.clone_header());
+
+    let outcome = test_harness
+        .tree
+        .on_new_payload(ExecutionData {
+            payload: payload.into(),
+            sidecar: ExecutionPayloadSidecar::none(),
+        })
+        .unwrap();
+    assert_eq!(outcome.outcome.status, PayloadStatusEnum::Accepted);
+    assert!(test_harness.tree.optimistic_blocks.contains(&hash));
+
+    // Verify the engine wakes up without a message while a block is queued
+    assert_matches!(test_harness.tree.wait_for_event(), LoopEvent::Idle);
+    assert!(test_harness.tree.optimistic_blocks.contains(&hash));
+
+    // Verify the queue is drained before the engine terminates
+    let (tx, _rx) = oneshot::channel();
+    let flow = test_harness
+        .tree
+        .on_engine_message(FromEngine::Event(FromOrchestrator::Terminate { tx }))
+        .unwrap();
+    assert!(flow.is_break());
+    assert!(test_harness.tree.optimistic_blocks.is_empty());
+}
+
 /// Test that captures the Engine-API rule where malformed payloads report latestValidHash = None
 #[test]
 fn test_on_new_payload_malformed_payload() {
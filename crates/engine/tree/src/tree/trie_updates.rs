@@ -1,3 +1,5 @@
+//! Comparison of the trie updates computed by different state root strategies.
+
 use alloy_primitives::{
     map::{B256Map, HashMap},
     B256,
@@ -18,21 +20,54 @@ struct EntryDiff<T> {
     database: T,
 }
 
+/// The differences between the trie updates of the state root task and the regular state root
+/// computation.
 #[derive(Debug, Default)]
-struct TrieUpdatesDiff {
+pub struct TrieUpdatesDiff {
     account_nodes: HashMap<Nibbles, EntryDiff<Option<BranchNodeCompact>>>,
     removed_nodes: HashMap<Nibbles, EntryDiff<bool>>,
     storage_tries: B256Map<StorageTrieUpdatesDiff>,
 }
 
 impl TrieUpdatesDiff {
-    fn has_differences(&self) -> bool {
+    /// Returns `true` if the trie updates differ.
+    pub fn has_differences(&self) -> bool {
         !self.account_nodes.is_empty() ||
             !self.removed_nodes.is_empty() ||
             !self.storage_tries.is_empty()
     }
 
-    pub(super) fn log_differences(mut self) {
+    /// Returns the sorted trie paths at which the trie updates differ.
+    ///
+    /// Paths of the account trie have no hashed address, paths of storage tries are returned with
+    /// the hashed address of the account. A storage trie that is only deleted by one of the
+    /// computations is reported with its root path.
+    pub fn diverging_paths(&self) -> Vec<(Option<B256>, Nibbles)> {
+        let mut paths = self
+            .account_nodes
+            .keys()
+            .chain(self.removed_nodes.keys())
+            .map(|path| (None, *path))
+            .collect::<BTreeSet<_>>();
+
+        for (address, storage_diff) in &self.storage_tries {
+            if storage_diff.is_deleted.is_some() {
+                paths.insert((Some(*address), Nibbles::default()));
+            }
+            paths.extend(
+                storage_diff
+                    .storage_nodes
+                    .keys()
+                    .chain(storage_diff.removed_nodes.keys())
+                    .map(|path| (Some(*address), *path)),
+            );
+        }
+
+        paths.into_iter().collect()
+    }
+
+    /// Logs all differences as warnings.
+    pub fn log_differences(mut self) {
         if self.has_differences() {
             for (path, EntryDiff { task, regular, database }) in &mut self.account_nodes {
                 warn!(target: "engine::tree", ?path, ?task, ?regular, ?database, "Difference in account trie updates");
@@ -108,6 +143,23 @@ pub(crate) fn compare_trie_updates(
     task: TrieUpdates,
     regular: TrieUpdates,
 ) -> Result<bool, DatabaseError> {
+    let diff = diff_trie_updates(trie_cursor_factory, task, regular)?;
+    let has_differences = diff.has_differences();
+    diff.log_differences();
+
+    Ok(has_differences)
+}
+
+/// Compares the trie updates from state root task, regular state root calculation and database,
+/// and returns the differences.
+///
+/// Entries that only differ in a way that is a no-op against the database, e.g. removing a node
+/// that does not exist, are not reported.
+pub fn diff_trie_updates(
+    trie_cursor_factory: impl TrieCursorFactory,
+    task: TrieUpdates,
+    regular: TrieUpdates,
+) -> Result<TrieUpdatesDiff, DatabaseError> {
     let mut task = adjust_trie_updates(task);
     let mut regular = adjust_trie_updates(regular);
 
@@ -180,11 +232,7 @@ pub(crate) fn compare_trie_updates(
         }
     }
 
-    // log differences
-    let has_differences = diff.has_differences();
-    diff.log_differences();
-
-    Ok(has_differences)
+    Ok(diff)
 }
 
 fn compare_storage_trie_updates<C: TrieCursor>(
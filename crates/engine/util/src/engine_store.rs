@@ -1,20 +1,25 @@
 //! Stores engine API messages to disk for later inspection and replay.
 
-use alloy_rpc_types_engine::ForkchoiceState;
+use alloy_rpc_types_engine::{ForkchoiceState, PayloadStatus};
 use futures::{Stream, StreamExt};
-use hanzo_evm_engine_primitives::{BeaconEngineMessage, ExecutionPayload};
+use hanzo_evm_engine_primitives::{BeaconEngineMessage, ExecutionPayload, ForkchoiceStatus};
 use hanzo_evm_fs_util as fs;
 use hanzo_evm_payload_primitives::PayloadTypes;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::BTreeMap,
-    path::PathBuf,
+    path::{Path, PathBuf},
     pin::Pin,
     task::{ready, Context, Poll},
     time::SystemTime,
 };
+use tokio::sync::oneshot;
 use tracing::*;
 
+/// The extension of the file that stores the status returned for a stored message, replacing the
+/// `json` extension of the message file.
+const STATUS_FILE_EXTENSION: &str = "status.json";
+
 /// A message from the engine API that has been stored to disk.
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -34,8 +39,27 @@ pub enum StoredEngineApiMessage<T: PayloadTypes> {
     },
 }
 
+/// The status that the engine returned for a stored engine API message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StoredEngineApiStatus {
+    /// The status returned for an `engine_newPayload` method call.
+    NewPayload {
+        /// The returned [`PayloadStatus`].
+        status: PayloadStatus,
+    },
+    /// The status returned for an `engine_forkchoiceUpdated` method call.
+    ForkchoiceUpdated {
+        /// The [`ForkchoiceStatus`] of the returned payload status.
+        status: ForkchoiceStatus,
+    },
+}
+
 /// This can read and write engine API messages in a specific directory.
-#[derive(Debug)]
+///
+/// Next to every message, the status that the engine returned for it is stored once it is
+/// available, see [`EngineMessageStore::stored_status`].
+#[derive(Debug, Clone)]
 pub struct EngineMessageStore {
     /// The path to the directory that stores the engine API messages.
     path: PathBuf,
@@ -51,52 +75,145 @@ impl EngineMessageStore {
 
     /// Stores the received [`BeaconEngineMessage`] to disk, appending the `received_at` time to the
     /// path.
+    ///
+    /// Returns the path of the stored message.
     pub fn on_message<T>(
         &self,
         msg: &BeaconEngineMessage<T>,
         received_at: SystemTime,
-    ) -> eyre::Result<()>
+    ) -> eyre::Result<PathBuf>
     where
         T: PayloadTypes,
     {
         fs::create_dir_all(&self.path)?; // ensure that store path had been created
         let timestamp = received_at.duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis();
-        match msg {
+        let path = match msg {
             BeaconEngineMessage::ForkchoiceUpdated {
                 state,
                 payload_attrs,
                 tx: _tx,
                 version: _version,
             } => {
-                let filename = format!("{}-fcu-{}.json", timestamp, state.head_block_hash);
+                let path =
+                    self.path.join(format!("{}-fcu-{}.json", timestamp, state.head_block_hash));
                 fs::write(
-                    self.path.join(filename),
+                    &path,
                     serde_json::to_vec(&StoredEngineApiMessage::<T>::ForkchoiceUpdated {
                         state: *state,
                         payload_attrs: payload_attrs.clone(),
                     })?,
                 )?;
+                path
             }
             BeaconEngineMessage::NewPayload { payload, .. } |
             BeaconEngineMessage::RethNewPayload { payload, .. } => {
-                let filename = format!("{}-new_payload-{}.json", timestamp, payload.block_hash());
+                let path = self.path.join(format!(
+                    "{}-new_payload-{}.json",
+                    timestamp,
+                    payload.block_hash()
+                ));
                 fs::write(
-                    self.path.join(filename),
+                    &path,
                     serde_json::to_vec(&StoredEngineApiMessage::<T>::NewPayload {
                         payload: payload.clone(),
                     })?,
                 )?;
+                path
             }
         };
+        Ok(path)
+    }
+
+    /// Stores the status that the engine returned for the message at the given path.
+    pub fn on_status(
+        &self,
+        message_path: &Path,
+        status: &StoredEngineApiStatus,
+    ) -> eyre::Result<()> {
+        fs::write(message_path.with_extension(STATUS_FILE_EXTENSION), serde_json::to_vec(status)?)?;
         Ok(())
     }
 
+    /// Returns the status that the engine returned for the message at the given path, or `None`
+    /// if no status was stored, e.g. because the node shut down before it responded.
+    pub fn stored_status(
+        &self,
+        message_path: &Path,
+    ) -> eyre::Result<Option<StoredEngineApiStatus>> {
+        let path = message_path.with_extension(STATUS_FILE_EXTENSION);
+        if !path.exists() {
+            return Ok(None)
+        }
+        Ok(Some(serde_json::from_slice(&fs::read(path)?)?))
+    }
+
+    /// Replaces the response channel of the message, so that the response is stored with
+    /// [`EngineMessageStore::on_status`] before it is forwarded to the original channel.
+    fn record_status<T>(
+        &self,
+        msg: BeaconEngineMessage<T>,
+        message_path: PathBuf,
+    ) -> BeaconEngineMessage<T>
+    where
+        T: PayloadTypes,
+    {
+        match msg {
+            BeaconEngineMessage::NewPayload { payload, tx } => {
+                let tx = self.forward_response(message_path, tx, |response| {
+                    let status = response.as_ref().ok()?.clone();
+                    Some(StoredEngineApiStatus::NewPayload { status })
+                });
+                BeaconEngineMessage::NewPayload { payload, tx }
+            }
+            BeaconEngineMessage::RethNewPayload { payload, tx } => {
+                let tx = self.forward_response(message_path, tx, |response| {
+                    let (status, _) = response.as_ref().ok()?;
+                    Some(StoredEngineApiStatus::NewPayload { status: status.clone() })
+                });
+                BeaconEngineMessage::RethNewPayload { payload, tx }
+            }
+            BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, version, tx } => {
+                let tx = self.forward_response(message_path, tx, |response| {
+                    let status = response.as_ref().ok()?.forkchoice_status();
+                    Some(StoredEngineApiStatus::ForkchoiceUpdated { status })
+                });
+                BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, version, tx }
+            }
+        }
+    }
+
+    /// Spawns a task that forwards the response to the given channel and stores the status that
+    /// is extracted from it. Returns the channel that the response should be sent to.
+    fn forward_response<R: Send + 'static>(
+        &self,
+        message_path: PathBuf,
+        tx: oneshot::Sender<R>,
+        status: impl FnOnce(&R) -> Option<StoredEngineApiStatus> + Send + 'static,
+    ) -> oneshot::Sender<R> {
+        let (response_tx, response_rx) = oneshot::channel();
+        let store = self.clone();
+        tokio::spawn(async move {
+            let Ok(response) = response_rx.await else { return };
+            let status = status(&response);
+            let _ = tx.send(response);
+            if let Some(status) = status &&
+                let Err(error) = store.on_status(&message_path, &status)
+            {
+                error!(target: "engine::store", %error, ?message_path, "Error storing Engine API status");
+            }
+        });
+        response_tx
+    }
+
     /// Finds and iterates through any stored engine API message files, ordered by timestamp.
     pub fn engine_messages_iter(&self) -> eyre::Result<impl Iterator<Item = PathBuf>> {
         let mut filenames_by_ts = BTreeMap::<u64, Vec<PathBuf>>::default();
         for entry in fs::read_dir(&self.path)? {
             let entry = entry?;
             let filename = entry.file_name();
+            if filename.to_str().is_some_and(|n| n.ends_with(STATUS_FILE_EXTENSION)) {
+                continue
+            }
             if let Some(filename) = filename.to_str().filter(|n| n.ends_with(".json")) {
                 if let Some(Ok(timestamp)) = filename.split('-').next().map(|n| n.parse::<u64>()) {
                     filenames_by_ts.entry(timestamp).or_default().push(entry.path());
@@ -140,12 +257,13 @@ where
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let mut this = self.project();
-        let next = ready!(this.stream.poll_next_unpin(cx));
-        if let Some(msg) = &next &&
-            let Err(error) = this.store.on_message(msg, SystemTime::now())
-        {
-            error!(target: "engine::stream::store", ?msg, %error, "Error handling Engine API message");
+        let Some(msg) = ready!(this.stream.poll_next_unpin(cx)) else { return Poll::Ready(None) };
+        match this.store.on_message(&msg, SystemTime::now()) {
+            Ok(path) => Poll::Ready(Some(this.store.record_status(msg, path))),
+            Err(error) => {
+                error!(target: "engine::stream::store", ?msg, %error, "Error handling Engine API message");
+                Poll::Ready(Some(msg))
+            }
         }
-        Poll::Ready(next)
     }
 }
@@ -9,6 +9,7 @@ use hanzo_evm_cli::chainspec::ChainSpecParser;
 use hanzo_evm_cli_commands::{
     common::{CliComponentsBuilder, CliNodeTypes, HeaderMut},
     launcher::{FnLauncher, Launcher},
+    reader::{ReaderNodeTypes, ReaderRpcModules},
 };
 use reth_cli_runner::CliRunner;
 use reth_db::DatabaseEnv;
@@ -73,9 +74,11 @@ where
             (EthEvmConfig::ethereum(spec.clone()), Arc::new(EthBeaconConsensus::new(spec)))
         };
 
-        self.run_with_components::<EthereumNode>(components, |builder, ext| async move {
-            launcher.entrypoint(builder, ext).await
-        })
+        self.run_with_components_and_reader::<EthereumNode>(
+            components,
+            Some(EthereumNode::reader_rpc_modules),
+            |builder, ext| async move { launcher.entrypoint(builder, ext).await },
+        )
     }
 
     /// Execute the configured cli command with the provided [`CliComponentsBuilder`].
@@ -83,9 +86,31 @@ where
     /// This accepts a closure that is used to launch the node via the
     /// [`NodeCommand`](hanzo_evm_cli_commands::node::NodeCommand) and allows providing custom
     /// components.
+    ///
+    /// The [`ReaderCommand`](hanzo_evm_cli_commands::reader::ReaderCommand) is not supported with
+    /// custom components.
     pub fn run_with_components<N>(
+        self,
+        components: impl CliComponentsBuilder<N>,
+        launcher: impl AsyncFnOnce(
+            WithLaunchContext<NodeBuilder<DatabaseEnv, C::ChainSpec>>,
+            Ext,
+        ) -> Result<()>,
+    ) -> Result<()>
+    where
+        N: CliNodeTypes<Primitives: NodePrimitives<BlockHeader: HeaderMut>, ChainSpec: Hardforks>,
+        C: ChainSpecParser<ChainSpec = N::ChainSpec>,
+    {
+        self.run_with_components_and_reader(components, None, launcher)
+    }
+
+    /// Execute the configured cli command with the provided [`CliComponentsBuilder`] and the
+    /// builder of the RPC modules served by the
+    /// [`ReaderCommand`](hanzo_evm_cli_commands::reader::ReaderCommand), if supported.
+    fn run_with_components_and_reader<N>(
         mut self,
         components: impl CliComponentsBuilder<N>,
+        reader_rpc_modules: Option<ReaderRpcModules<N>>,
         launcher: impl AsyncFnOnce(
             WithLaunchContext<NodeBuilder<DatabaseEnv, C::ChainSpec>>,
             Ext,
@@ -116,7 +141,13 @@ where
         // Install the prometheus recorder to be sure to record all metrics
         install_prometheus_recorder();
 
-        run_commands_with::<C, Ext, Rpc, N, SubCmd>(self.cli, runner, components, launcher)
+        run_commands_with::<C, Ext, Rpc, N, SubCmd>(
+            self.cli,
+            runner,
+            components,
+            reader_rpc_modules,
+            launcher,
+        )
     }
 
     /// Initializes tracing with the configured options.
@@ -133,10 +164,13 @@ where
 
 /// Run CLI commands with the provided runner, components and launcher.
 /// This is the shared implementation used by both `CliApp` and Cli methods.
+///
+/// The reader command is rejected if `reader_rpc_modules` is not set.
 pub(crate) fn run_commands_with<C, Ext, Rpc, N, SubCmd>(
     cli: Cli<C, Ext, Rpc, SubCmd>,
     runner: CliRunner,
     components: impl CliComponentsBuilder<N>,
+    reader_rpc_modules: Option<ReaderRpcModules<N>>,
     launcher: impl AsyncFnOnce(
         WithLaunchContext<NodeBuilder<DatabaseEnv, C::ChainSpec>>,
         Ext,
@@ -188,6 +222,7 @@ where
             runner.run_blocking_command_until_exit(|ctx| command.execute::<N>(ctx))
         }
         Commands::Download(command) => runner.run_blocking_until_ctrl_c(command.execute::<N>()),
+        Commands::Snapshot(command) => runner.run_blocking_until_ctrl_c(command.execute()),
         Commands::Stage(command) => {
             runner.run_command_until_exit(|ctx| command.execute::<N, _>(ctx, components))
         }
@@ -199,6 +234,15 @@ where
         Commands::ReExecute(command) => {
             runner.run_until_ctrl_c(command.execute::<N>(components, rt))
         }
+        Commands::CompareStateRoots(command) => {
+            runner.run_until_ctrl_c(command.execute::<N>(components, rt))
+        }
+        Commands::Reader(command) => {
+            let rpc_modules = reader_rpc_modules.ok_or_else(|| {
+                eyre!("The reader command is not supported with custom components")
+            })?;
+            runner.run_command_until_exit(|ctx| command.execute::<N>(ctx, components, rpc_modules))
+        }
         Commands::Ext(command) => command.execute(runner),
     }
 }
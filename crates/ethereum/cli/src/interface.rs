@@ -9,10 +9,11 @@ use hanzo_evm_chainspec::{ChainSpec, EthChainSpec, Hardforks};
 use hanzo_evm_cli::chainspec::ChainSpecParser;
 use hanzo_evm_cli_commands::{
     common::{CliComponentsBuilder, CliNodeTypes, HeaderMut},
-    config_cmd, db, download, dump_genesis, export_era, import, import_era, init_cmd, init_state,
+    compare_state_roots, config_cmd, db, download, dump_genesis, export_era, import, import_era,
+    init_cmd, init_state,
     launcher::FnLauncher,
     node::{self, NoArgs},
-    p2p, prune, re_execute, stage,
+    p2p, prune, re_execute, reader, snapshot, stage,
 };
 use hanzo_evm_cli_runner::CliRunner;
 use hanzo_evm_db::DatabaseEnv;
@@ -221,7 +222,7 @@ impl<
         install_prometheus_recorder();
 
         // Use the shared standalone function to avoid duplication
-        run_commands_with::<C, Ext, Rpc, N, SubCmd>(self, runner, components, launcher)
+        run_commands_with::<C, Ext, Rpc, N, SubCmd>(self, runner, components, None, launcher)
     }
 
     /// Initializes tracing with the configured options.
@@ -299,6 +300,9 @@ pub enum Commands<
     /// Download public node snapshots
     #[command(name = "download")]
     Download(download::DownloadCommand<C>),
+    /// Package a storage snapshot into archives that `download` can fetch
+    #[command(name = "snapshot")]
+    Snapshot(snapshot::Command),
     /// Manipulate individual stages.
     #[command(name = "stage")]
     Stage(stage::Command<C>),
@@ -318,6 +322,12 @@ pub enum Commands<
     /// Re-execute blocks in parallel to verify historical sync correctness.
     #[command(name = "re-execute")]
     ReExecute(re_execute::Command<C>),
+    /// Re-execute blocks and compare the state root strategies of the engine.
+    #[command(name = "compare-state-roots")]
+    CompareStateRoots(compare_state_roots::Command<C>),
+    /// Serve RPC requests read-only from the datadir of a running primary node.
+    #[command(name = "reader")]
+    Reader(Box<reader::ReaderCommand<C>>),
     /// Extension subcommands provided by consumers.
     #[command(flatten)]
     Ext(SubCmd),
@@ -351,6 +361,7 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug, SubCmd: Subcommand + fmt:
             Self::DumpGenesis(cmd) => cmd.chain_spec(),
             Self::Db(cmd) => cmd.chain_spec(),
             Self::Download(cmd) => cmd.chain_spec(),
+            Self::Snapshot(_) => None,
             Self::Stage(cmd) => cmd.chain_spec(),
             Self::P2P(cmd) => cmd.chain_spec(),
             #[cfg(feature = "dev")]
@@ -358,6 +369,8 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug, SubCmd: Subcommand + fmt:
             Self::Config(_) => None,
             Self::Prune(cmd) => cmd.chain_spec(),
             Self::ReExecute(cmd) => cmd.chain_spec(),
+            Self::CompareStateRoots(cmd) => cmd.chain_spec(),
+            Self::Reader(cmd) => cmd.chain_spec(),
             Self::Ext(_) => None,
         }
     }
@@ -16,7 +16,7 @@ use alloy_rpc_types_engine::{
 };
 use core::convert::Infallible;
 use hanzo_evm_ethereum_primitives::EthPrimitives;
-use hanzo_evm_payload_primitives::{BuiltPayload, PayloadBuilderAttributes};
+use hanzo_evm_payload_primitives::{BuiltPayload, PayloadBlobsBundle, PayloadBuilderAttributes};
 use hanzo_evm_primitives_traits::{NodePrimitives, SealedBlock};
 
 use crate::BuiltPayloadConversionError;
@@ -183,6 +183,18 @@ impl<N: NodePrimitives> BuiltPayload for EthBuiltPayload<N> {
     fn requests(&self) -> Option<Requests> {
         self.requests.clone()
     }
+
+    fn blobs_bundle(&self) -> Option<PayloadBlobsBundle> {
+        match &self.sidecars {
+            BlobSidecars::Empty => None,
+            BlobSidecars::Eip4844(sidecars) => {
+                Some(PayloadBlobsBundle::V1(BlobsBundleV1::from(sidecars.clone())))
+            }
+            BlobSidecars::Eip7594(sidecars) => {
+                Some(PayloadBlobsBundle::V2(BlobsBundleV2::from(sidecars.clone())))
+            }
+        }
+    }
 }
 
 // V1 engine_getPayloadV1 response
@@ -554,4 +566,39 @@ mod tests {
             PayloadId(B64::from_str("0x0fc49cd532094cce").unwrap())
         );
     }
+
+    #[test]
+    fn test_blobs_bundle() {
+        use hanzo_evm_primitives_traits::Block as _;
+
+        let payload = EthBuiltPayload::new(
+            PayloadId::new([0; 8]),
+            Arc::new(hanzo_evm_ethereum_primitives::Block::default().seal_slow()),
+            U256::ZERO,
+            None,
+        );
+        assert_eq!(payload.blobs_bundle(), None);
+
+        let sidecar = BlobTransactionSidecar::new(
+            vec![Default::default()],
+            vec![Default::default()],
+            vec![Default::default()],
+        );
+        let eip4844 = payload.clone().with_sidecars(BlobSidecars::eip4844(vec![sidecar.clone()]));
+        assert_eq!(
+            eip4844.blobs_bundle(),
+            Some(PayloadBlobsBundle::V1(BlobsBundleV1::from(vec![sidecar])))
+        );
+
+        let sidecar = BlobTransactionSidecarEip7594::new(
+            vec![Default::default()],
+            vec![Default::default()],
+            vec![Default::default(); 128],
+        );
+        let eip7594 = payload.with_sidecars(BlobSidecars::eip7594(vec![sidecar.clone()]));
+        assert_eq!(
+            eip7594.blobs_bundle(),
+            Some(PayloadBlobsBundle::V2(BlobsBundleV2::from(vec![sidecar])))
+        );
+    }
 }
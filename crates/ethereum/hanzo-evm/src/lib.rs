@@ -17,21 +17,27 @@
 
 extern crate alloc;
 
-use alloc::{borrow::Cow, sync::Arc};
-use alloy_consensus::Header;
+use alloc::{borrow::Cow, boxed::Box, format, sync::Arc};
+use alloy_consensus::{Header, Transaction};
 use alloy_evm::{
-    eth::{EthBlockExecutionCtx, EthBlockExecutorFactory},
-    EthEvmFactory, FromRecoveredTx, FromTxWithEncoded,
+    block::{BlockExecutionError, BlockExecutor, BlockValidationError, OnStateHook},
+    eth::{EthBlockExecutionCtx, EthBlockExecutor, EthBlockExecutorFactory, EthTxResult},
+    Database, EthEvmFactory, Evm, FromRecoveredTx, FromTxWithEncoded,
 };
 use core::{convert::Infallible, fmt::Debug};
 use hanzo_evm_chainspec::{ChainSpec, EthChainSpec, MAINNET};
-use hanzo_evm_ethereum_primitives::{Block, EthPrimitives, TransactionSigned};
+use hanzo_evm_ethereum_primitives::{Block, EthPrimitives, Receipt, TransactionSigned};
 use hanzo_evm_execution::{
     eth::NextEvmEnvAttributes, precompiles::PrecompilesMap, ConfigureEvm, EvmEnv, EvmFactory,
-    NextBlockEnvAttributes, TransactionEnv,
+    EvmFor, NextBlockEnvAttributes, TransactionEnv, TransactionResultsCommitter,
 };
+use hanzo_evm_execution_types::BlockExecutionResult;
 use hanzo_evm_primitives_traits::{SealedBlock, SealedHeader};
-use revm::{context::BlockEnv, primitives::hardfork::SpecId};
+use revm::{
+    context::{result::ResultAndState, BlockEnv},
+    database::State,
+    primitives::hardfork::SpecId,
+};
 
 #[cfg(feature = "std")]
 use hanzo_evm_execution::{ConfigureEngineEvm, ExecutableTxIterator};
@@ -211,6 +217,86 @@ where
             extra_data: attributes.extra_data,
         })
     }
+
+    fn transaction_results_committer(&self) -> Option<&impl TransactionResultsCommitter<Self>> {
+        Some(self)
+    }
+}
+
+impl<ChainSpec, EvmF> TransactionResultsCommitter<Self> for EthEvmConfig<ChainSpec, EvmF>
+where
+    ChainSpec: EthExecutorSpec + EthChainSpec<Header = Header> + Hardforks + 'static,
+    EvmF: EvmFactory<
+            Tx: TransactionEnv
+                    + FromRecoveredTx<TransactionSigned>
+                    + FromTxWithEncoded<TransactionSigned>,
+            Spec = SpecId,
+            BlockEnv = BlockEnv,
+            Precompiles = PrecompilesMap,
+        > + Clone
+        + Debug
+        + Send
+        + Sync
+        + Unpin
+        + 'static,
+{
+    fn commit_transaction_results<'a, 'b, DB, I>(
+        &self,
+        evm: EvmFor<Self, &'a mut State<DB>>,
+        ctx: EthBlockExecutionCtx<'a>,
+        results: I,
+        state_hook: Option<Box<dyn OnStateHook>>,
+    ) -> Result<BlockExecutionResult<Receipt>, BlockExecutionError>
+    where
+        DB: Database + 'a,
+        I: IntoIterator<Item = (&'b TransactionSigned, ResultAndState<EvmF::HaltReason>)>,
+    {
+        let block_gas_limit = evm.block().gas_limit;
+        let max_blob_gas = self
+            .chain_spec()
+            .blob_params_at_timestamp(evm.block().timestamp.saturating_to())
+            .map(|params| params.max_blob_gas_per_block());
+
+        let mut executor = EthBlockExecutor::new(
+            evm,
+            ctx,
+            self.executor_factory.spec(),
+            self.executor_factory.receipt_builder(),
+        );
+        executor.set_state_hook(state_hook);
+
+        executor.apply_pre_execution_changes()?;
+        let mut gas_used = 0u64;
+        let mut blob_gas_used = 0u64;
+        for (tx, result) in results {
+            // The results were executed independently of each other, so the block level limits
+            // that the sequential execution enforces before every transaction are checked here
+            let block_available_gas = block_gas_limit - gas_used;
+            if tx.gas_limit() > block_available_gas {
+                return Err(BlockValidationError::TransactionGasLimitMoreThanAvailableBlockGas {
+                    transaction_gas_limit: tx.gas_limit(),
+                    block_available_gas,
+                }
+                .into())
+            }
+            if let Some(tx_blob_gas_used) = tx.blob_gas_used() {
+                blob_gas_used += tx_blob_gas_used;
+                if let Some(max_blob_gas) = max_blob_gas.filter(|max| blob_gas_used > *max) {
+                    return Err(BlockExecutionError::msg(format!(
+                        "blob gas used {blob_gas_used} exceeds the maximum of {max_blob_gas}"
+                    )))
+                }
+            }
+            gas_used += result.result.gas_used();
+
+            executor.commit_transaction(EthTxResult {
+                result,
+                blob_gas_used: tx.blob_gas_used().unwrap_or_default(),
+                tx_type: tx.tx_type(),
+            })?;
+        }
+        executor.finish().map(|(_, result)| result)
+    }
 }
 
 #[cfg(feature = "std")]
@@ -7,7 +7,9 @@ use reth_node_api::{BlockBody, FullNodeComponents};
 use reth_node_builder::{rpc::RethRpcAddOns, FullNode, NodeBuilder, NodeConfig, NodeHandle};
 use reth_node_core::args::DevArgs;
 use reth_node_ethereum::{node::EthereumAddOns, EthereumNode};
-use reth_provider::{providers::BlockchainProvider, CanonStateSubscriptions};
+use reth_provider::{
+    providers::BlockchainProvider, BlockHashReader, BlockNumReader, CanonStateSubscriptions,
+};
 use reth_rpc_eth_api::{helpers::EthTransactions, EthApiServer};
 use reth_tasks::Runtime;
 use std::sync::Arc;
@@ -75,6 +77,37 @@ async fn can_run_dev_node_custom_attributes() -> eyre::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn can_reorg_dev_node() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let runtime = Runtime::test();
+
+    let mut node_config = NodeConfig::test()
+        .with_chain(custom_chain())
+        .with_dev(DevArgs { dev: true, ..Default::default() });
+    node_config.engine.always_process_payload_attributes_on_canonical_head = true;
+    let NodeHandle { node, .. } = NodeBuilder::new(node_config.clone())
+        .testing_node(runtime.clone())
+        .with_types_and_provider::<EthereumNode, BlockchainProvider<_>>()
+        .with_components(EthereumNode::components())
+        .with_add_ons(EthereumAddOns::default())
+        .launch_with_debug_capabilities()
+        .await?;
+
+    assert_chain_advances(&node).await;
+    let head = node.provider.best_block_number()?;
+    let reorged_hash = node.provider.block_hash(head)?.unwrap();
+
+    let reorg = node.add_ons_handle.local_miner.reorg(1, vec![]).await?;
+    assert_eq!(reorg.reorged.len(), 1);
+    assert_eq!(reorg.reorged[0].hash, reorged_hash);
+    assert_eq!(reorg.blocks.len(), 1);
+    assert_eq!(reorg.blocks[0].number, head);
+    assert_ne!(reorg.blocks[0].hash, reorged_hash);
+
+    Ok(())
+}
+
 async fn assert_chain_advances<N, AddOns>(node: &FullNode<N, AddOns>)
 where
     N: FullNodeComponents<Provider: CanonStateSubscriptions>,
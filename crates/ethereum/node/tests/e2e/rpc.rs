@@ -9,6 +9,12 @@ use alloy_rpc_types_beacon::relay::{
 };
 use alloy_rpc_types_engine::{BlobsBundleV1, ExecutionPayloadV3};
 use alloy_rpc_types_eth::TransactionRequest;
+use alloy_rpc_types_trace::{
+    filter::TraceFilter,
+    geth::{GethDebugBuiltInTracerType, GethDebugTracingOptions},
+    parity::LocalizedTransactionTrace,
+};
+use alloy_signer::Signer;
 use rand::{rngs::StdRng, Rng, SeedableRng};
 use hanzo_evm_chainspec::{ChainSpecBuilder, EthChainSpec, MAINNET};
 use hanzo_evm_e2e_test_utils::setup_engine;
@@ -18,13 +24,18 @@ use hanzo_evm_node_core::{
     args::{NetworkArgs, RpcServerArgs},
     node_config::NodeConfig,
 };
+use hanzo_evm_rpc::TraceApi;
+use hanzo_evm_rpc_api::{FilteredBlockTraces, TraceApiServer};
+use hanzo_evm_rpc_eth_types::EthConfig;
+use hanzo_evm_tasks::pool::BlockingTaskGuard;
+use jsonrpsee_core::rpc_params;
 use reth_node_ethereum::EthereumNode;
 use reth_payload_primitives::BuiltPayload;
 use reth_rpc_api::servers::AdminApiServer;
 use reth_tasks::Runtime;
 use std::{
     sync::Arc,
-    time::{SystemTime, UNIX_EPOCH},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 alloy_sol_types::sol! {
@@ -375,3 +386,108 @@ async fn test_admin_external_ip() -> eyre::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_trace_subscribe_filter() -> eyre::Result<()> {
+    hanzo_evm_tracing::init_test_tracing();
+
+    let chain_spec = Arc::new(
+        ChainSpecBuilder::default()
+            .chain(MAINNET.chain)
+            .genesis(serde_json::from_str(include_str!("../assets/genesis.json")).unwrap())
+            .cancun_activated()
+            .build(),
+    );
+
+    let (mut nodes, wallet) = setup_engine::<EthereumNode>(
+        1,
+        chain_spec,
+        false,
+        Default::default(),
+        eth_payload_attributes,
+    )
+    .await?;
+    let mut node = nodes.pop().unwrap();
+    let signer = wallet.wallet_gen().swap_remove(0);
+    let sender = signer.address();
+    let provider =
+        ProviderBuilder::new().wallet(EthereumWallet::new(signer)).connect_http(node.rpc_url());
+
+    // block 1 executes code, block 2 is a plain transfer and block 3 is empty
+    let builder = GasWaster::deploy_builder(&provider, U256::from(10)).send().await?;
+    node.advance_block().await?;
+    assert!(builder.get_receipt().await?.status());
+    let _ = provider
+        .send_transaction(TransactionRequest::default().to(Address::random()).value(U256::from(1)))
+        .await?;
+    node.advance_block().await?;
+    node.advance_block().await?;
+
+    let eth_api = node.rpc.inner.eth_api().clone();
+    let trace_api = |max_trace_filter_blocks| {
+        TraceApi::new(
+            eth_api.clone(),
+            BlockingTaskGuard::new(10),
+            EthConfig::default().max_trace_filter_blocks(max_trace_filter_blocks),
+        )
+        .into_rpc()
+    };
+    let filter = TraceFilter {
+        from_block: Some(1),
+        to_block: Some(3),
+        from_address: vec![sender],
+        to_address: Default::default(),
+        mode: Default::default(),
+        after: None,
+        count: None,
+    };
+
+    // the streamed traces match the traces of `trace_filter`, blocks without traces are skipped
+    let module = trace_api(100);
+    let expected: Vec<LocalizedTransactionTrace> =
+        module.call("trace_filter", rpc_params![filter.clone()]).await?;
+    let mut sub =
+        module.subscribe_unbounded("trace_subscribeFilter", rpc_params![filter.clone()]).await?;
+    let mut blocks = Vec::new();
+    while let Some(res) = sub.next::<FilteredBlockTraces>().await {
+        blocks.push(res?.0);
+    }
+    assert_eq!(blocks.iter().map(|block| block.block_number).collect::<Vec<_>>(), vec![1, 2]);
+    assert_eq!(blocks.into_iter().flat_map(|block| block.traces).collect::<Vec<_>>(), expected);
+
+    // geth traces are emitted for every matching transaction
+    let call_tracer = GethDebugTracingOptions::default()
+        .with_tracer(GethDebugBuiltInTracerType::CallTracer.into());
+    let mut sub = module
+        .subscribe_unbounded("trace_subscribeFilter", rpc_params![filter.clone(), call_tracer])
+        .await?;
+    let mut geth_traces = 0;
+    while let Some(res) = sub.next::<FilteredBlockTraces>().await {
+        let block = res?.0;
+        assert!(block.traces.is_empty());
+        geth_traces += block.geth_traces.len();
+    }
+    assert_eq!(geth_traces, 2);
+
+    // the block range limit of `trace_filter` applies
+    let module = trace_api(1);
+    assert!(module
+        .call::<_, Vec<LocalizedTransactionTrace>>("trace_filter", rpc_params![filter.clone()])
+        .await
+        .is_err());
+    assert!(module
+        .subscribe_unbounded("trace_subscribeFilter", rpc_params![filter.clone()])
+        .await
+        .is_err());
+
+    // the tracer timeout applies, no traces are emitted once it is exceeded
+    let module = trace_api(100);
+    let timed_out = GethDebugTracingOptions::default()
+        .with_tracer(GethDebugBuiltInTracerType::CallTracer.into())
+        .with_timeout(Duration::ZERO);
+    let mut sub =
+        module.subscribe_unbounded("trace_subscribeFilter", rpc_params![filter, timed_out]).await?;
+    assert!(sub.next::<FilteredBlockTraces>().await.is_none_or(|res| res.is_err()));
+
+    Ok(())
+}
@@ -18,7 +18,7 @@
 extern crate alloc;
 
 use crate::execute::{BasicBlockBuilder, Executor};
-use alloc::vec::Vec;
+use alloc::{boxed::Box, vec::Vec};
 use alloy_eips::{
     eip2718::{EIP2930_TX_TYPE_ID, LEGACY_TX_TYPE_ID},
     eip2930::AccessList,
@@ -29,13 +29,18 @@ use alloy_evm::{
     precompiles::PrecompilesMap,
 };
 use alloy_primitives::{Address, Bytes, B256};
-use core::{error::Error, fmt::Debug};
+use core::{convert::Infallible, error::Error, fmt::Debug};
 use execute::{BasicBlockExecutor, BlockAssembler, BlockBuilder};
 use hanzo_evm_execution_errors::BlockExecutionError;
+use hanzo_evm_execution_types::BlockExecutionResult;
 use hanzo_evm_primitives_traits::{
     BlockTy, HeaderTy, NodePrimitives, ReceiptTy, SealedBlock, SealedHeader, TxTy,
 };
-use revm::{context::TxEnv, database::State, primitives::hardfork::SpecId};
+use revm::{
+    context::{result::ResultAndState, TxEnv},
+    database::State,
+    primitives::hardfork::SpecId,
+};
 
 pub mod either;
 /// EVM environment configuration.
@@ -52,6 +57,8 @@ pub use engine::{ConfigureEngineEvm, ConvertTx, ExecutableTxIterator, Executable
 #[cfg(feature = "metrics")]
 pub mod metrics;
 pub mod noop;
+#[cfg(feature = "precompile-cache")]
+pub mod precompile_cache;
 #[cfg(any(test, feature = "test-utils"))]
 /// test helpers for mocking executor
 pub mod test_utils;
@@ -454,6 +461,54 @@ pub trait ConfigureEvm: Clone + Debug + Send + Sync + Unpin {
     ) -> impl Executor<DB, Primitives = Self::Primitives, Error = BlockExecutionError> {
         BasicBlockExecutor::new(self, db)
     }
+
+    /// Returns the [`TransactionResultsCommitter`] of the configuration, or `None` if it can't
+    /// finish a block from transactions that were executed outside of a block executor.
+    #[auto_impl(keep_default_for(&, Arc))]
+    fn transaction_results_committer(&self) -> Option<&impl TransactionResultsCommitter<Self>> {
+        None::<&Infallible>
+    }
+}
+
+/// Finishes blocks from transactions that were executed outside of a block executor, e.g. in
+/// parallel.
+///
+/// This is an optional capability of a [`ConfigureEvm`], see
+/// [`ConfigureEvm::transaction_results_committer`].
+pub trait TransactionResultsCommitter<Evm: ConfigureEvm> {
+    /// Finishes a block from the results of its transactions.
+    ///
+    /// The results are expected in block order and each of them must have been computed on top
+    /// of the state the block executor would have executed the transaction on. This applies the
+    /// pre-execution changes, commits the results and their receipts, and applies the
+    /// post-execution changes, so the outcome is the same as executing the block with an executor
+    /// created by [`ConfigureEvm::create_executor`].
+    fn commit_transaction_results<'a, 'b, DB, I>(
+        &self,
+        evm: EvmFor<Evm, &'a mut State<DB>>,
+        ctx: ExecutionCtxFor<'a, Evm>,
+        results: I,
+        state_hook: Option<Box<dyn OnStateHook>>,
+    ) -> Result<BlockExecutionResult<ReceiptTy<Evm::Primitives>>, BlockExecutionError>
+    where
+        DB: Database + 'a,
+        I: IntoIterator<Item = (&'b TxTy<Evm::Primitives>, ResultAndState<HaltReasonFor<Evm>>)>;
+}
+
+impl<Evm: ConfigureEvm> TransactionResultsCommitter<Evm> for Infallible {
+    fn commit_transaction_results<'a, 'b, DB, I>(
+        &self,
+        _evm: EvmFor<Evm, &'a mut State<DB>>,
+        _ctx: ExecutionCtxFor<'a, Evm>,
+        _results: I,
+        _state_hook: Option<Box<dyn OnStateHook>>,
+    ) -> Result<BlockExecutionResult<ReceiptTy<Evm::Primitives>>, BlockExecutionError>
+    where
+        DB: Database + 'a,
+        I: IntoIterator<Item = (&'b TxTy<Evm::Primitives>, ResultAndState<HaltReasonFor<Evm>>)>,
+    {
+        match *self {}
+    }
 }
 
 /// Represents additional attributes required to configure the next block.
@@ -0,0 +1,702 @@
+//! Contains a precompile cache backed by `moka` (LRU by length).
+//!
+//! The cache is shared by block execution and RPC, and can be persisted across restarts with
+//! [`PrecompileCacheMap::save`] and [`PrecompileCacheMap::load`]. A persisted cache is only
+//! restored by the same client version, for the same chain and spec.
+
+use alloy_evm::precompiles::{DynPrecompile, Precompile, PrecompileInput, PrecompilesMap};
+use alloy_primitives::{keccak256, Address, Bytes, B256};
+use hanzo_evm_primitives_traits::dashmap::DashMap;
+use moka::policy::EvictionPolicy;
+use revm::precompile::{PrecompileId, PrecompileOutput, PrecompileResult};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    hash::Hash,
+    io::{self, BufWriter, Read},
+    path::Path,
+    sync::Arc,
+};
+
+/// Default max cache size for [`PrecompileCache`]
+const MAX_CACHE_SIZE: u32 = 10_000;
+
+/// Version of the persisted cache format, bumped on any change to [`PersistedHeader`] or
+/// [`PersistedEntry`].
+const PERSISTED_FORMAT_VERSION: u32 = 1;
+
+/// Stores caches for each precompile.
+#[derive(Debug, Clone, Default)]
+pub struct PrecompileCacheMap<S>
+where
+    S: Eq + Hash + std::fmt::Debug + Send + Sync + Clone + 'static,
+{
+    /// Caches by precompile address.
+    caches: Arc<DashMap<Address, PrecompileCache<S>>>,
+    /// Entries restored from disk that were not yet added to a cache, by precompile address and
+    /// spec name.
+    ///
+    /// Specs are persisted by name, so restored entries are added to the cache of a precompile
+    /// once it is first used with a matching spec.
+    restored: Arc<DashMap<(Address, String), Vec<(B256, PrecompileOutput)>>>,
+}
+
+impl<S> PrecompileCacheMap<S>
+where
+    S: Eq + Hash + std::fmt::Debug + Send + Sync + Clone + 'static,
+{
+    /// Get the precompile cache for the given address.
+    pub fn cache_for_address(&self, address: Address) -> PrecompileCache<S> {
+        // Try just using `.get` first to avoid acquiring a write lock.
+        if let Some(cache) = self.caches.get(&address) {
+            return cache.clone();
+        }
+        // Otherwise, fallback to `.entry` and initialize the cache.
+        //
+        // This should be very rare as caches for all precompiles will be initialized as soon as
+        // first EVM is created.
+        self.caches.entry(address).or_default().clone()
+    }
+
+    /// Get the precompile cache for the given address, adding the entries restored from disk for
+    /// the given spec.
+    pub fn cache_for(&self, address: Address, spec: &S) -> PrecompileCache<S> {
+        let cache = self.cache_for_address(address);
+        if !self.restored.is_empty() {
+            if let Some((_, entries)) = self.restored.remove(&(address, spec_name(spec))) {
+                for (input_hash, output) in entries {
+                    cache.insert((input_hash, spec.clone()), CacheEntry { output });
+                }
+            }
+        }
+        cache
+    }
+
+    /// Wraps all pure precompiles in `precompiles` with their caches.
+    ///
+    /// Hits and misses are recorded per precompile with the given `source` label, e.g. `rpc`.
+    pub fn cache_pure_precompiles(
+        &self,
+        precompiles: &mut PrecompilesMap,
+        spec: S,
+        source: &'static str,
+    ) {
+        precompiles.map_pure_precompiles(|address, precompile| {
+            CachedPrecompile::wrap(
+                precompile,
+                self.cache_for(*address, &spec),
+                spec.clone(),
+                Some(CachedPrecompileMetrics::new_with_source(*address, source)),
+            )
+        });
+    }
+
+    /// Restores a cache map from the file at `path` that was written by [`Self::save`].
+    ///
+    /// The whole file is discarded with an [`io::ErrorKind::InvalidData`] error wrapping a
+    /// [`PersistedCacheMismatch`] if it was written in another format, by another client version,
+    /// for another chain or spec, or if its entries don't match the checksum.
+    pub fn load(path: &Path, client_version: &str, chain_id: u64, spec: &S) -> io::Result<Self> {
+        let mut data = Vec::new();
+        fs::File::open(path)?.read_to_end(&mut data)?;
+
+        let mut reader = data.as_slice();
+        let header: PersistedHeader =
+            bincode::deserialize_from(&mut reader).map_err(io::Error::other)?;
+        header
+            .check(client_version, chain_id, &spec_name(spec), reader)
+            .map_err(|mismatch| io::Error::new(io::ErrorKind::InvalidData, mismatch))?;
+        let entries: Vec<PersistedEntry> =
+            bincode::deserialize(reader).map_err(io::Error::other)?;
+
+        let restored = DashMap::<(Address, String), Vec<_>>::default();
+        for entry in entries {
+            let output = PrecompileOutput {
+                gas_used: entry.gas_used,
+                gas_refunded: entry.gas_refunded,
+                bytes: entry.bytes,
+                reverted: entry.reverted,
+            };
+            restored
+                .entry((entry.address, entry.spec))
+                .or_default()
+                .push((entry.input_hash, output));
+        }
+
+        Ok(Self { caches: Default::default(), restored: Arc::new(restored) })
+    }
+
+    /// Writes all cached results to the file at `path`, replacing it atomically.
+    ///
+    /// The file is tagged with the given client version, chain id and spec, which must match
+    /// when it is loaded again.
+    ///
+    /// Restored entries that were not used since [`Self::load`] are dropped, so results of specs
+    /// that are no longer active do not accumulate.
+    pub fn save(
+        &self,
+        path: &Path,
+        client_version: &str,
+        chain_id: u64,
+        spec: &S,
+    ) -> io::Result<()> {
+        let mut entries = Vec::new();
+        for cache in self.caches.iter() {
+            for (key, entry) in cache.value().0.iter() {
+                let (input_hash, spec) = &*key;
+                entries.push(PersistedEntry {
+                    address: *cache.key(),
+                    spec: spec_name(spec),
+                    input_hash: *input_hash,
+                    gas_used: entry.output.gas_used,
+                    gas_refunded: entry.output.gas_refunded,
+                    bytes: entry.output.bytes.clone(),
+                    reverted: entry.output.reverted,
+                });
+            }
+        }
+
+        let entries = bincode::serialize(&entries).map_err(io::Error::other)?;
+        let header = PersistedHeader {
+            format_version: PERSISTED_FORMAT_VERSION,
+            client_version: client_version.to_string(),
+            chain_id,
+            spec: spec_name(spec),
+            checksum: keccak256(&entries),
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp_path = path.with_extension("tmp");
+        let mut file = BufWriter::new(fs::File::create(&tmp_path)?);
+        bincode::serialize_into(&mut file, &header).map_err(io::Error::other)?;
+        io::Write::write_all(&mut file, &entries)?;
+        file.into_inner().map_err(io::IntoInnerError::into_error)?.sync_all()?;
+        fs::rename(tmp_path, path)
+    }
+}
+
+/// Returns the name a spec is persisted with.
+fn spec_name<S: std::fmt::Debug>(spec: &S) -> String {
+    format!("{spec:?}")
+}
+
+/// Header of a persisted cache, written before the entries.
+#[derive(Debug, Serialize, Deserialize)]
+struct PersistedHeader {
+    format_version: u32,
+    client_version: String,
+    chain_id: u64,
+    spec: String,
+    /// Hash of the encoded entries.
+    checksum: B256,
+}
+
+impl PersistedHeader {
+    /// Checks that the header matches the running client and the encoded `entries` that follow
+    /// it.
+    fn check(
+        &self,
+        client_version: &str,
+        chain_id: u64,
+        spec: &str,
+        entries: &[u8],
+    ) -> Result<(), PersistedCacheMismatch> {
+        if self.format_version != PERSISTED_FORMAT_VERSION {
+            return Err(PersistedCacheMismatch::FormatVersion(self.format_version))
+        }
+        if self.client_version != client_version {
+            return Err(PersistedCacheMismatch::ClientVersion(self.client_version.clone()))
+        }
+        if self.chain_id != chain_id {
+            return Err(PersistedCacheMismatch::ChainId(self.chain_id))
+        }
+        if self.spec != spec {
+            return Err(PersistedCacheMismatch::Spec(self.spec.clone()))
+        }
+        if self.checksum != keccak256(entries) {
+            return Err(PersistedCacheMismatch::Checksum)
+        }
+        Ok(())
+    }
+}
+
+/// Reason a persisted cache was discarded by [`PrecompileCacheMap::load`].
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display, derive_more::Error)]
+pub enum PersistedCacheMismatch {
+    /// The cache was written in another format version.
+    #[display("unsupported format version {_0}")]
+    FormatVersion(#[error(not(source))] u32),
+    /// The cache was written by another client version.
+    #[display("written by client version {_0}")]
+    ClientVersion(#[error(not(source))] String),
+    /// The cache was written for another chain.
+    #[display("written for chain id {_0}")]
+    ChainId(#[error(not(source))] u64),
+    /// The cache was written for another spec.
+    #[display("written for spec {_0}")]
+    Spec(#[error(not(source))] String),
+    /// The entries don't match the checksum of the header.
+    #[display("checksum mismatch")]
+    Checksum,
+}
+
+/// A cached precompile result as written to disk.
+#[derive(Debug, Serialize, Deserialize)]
+struct PersistedEntry {
+    address: Address,
+    spec: String,
+    input_hash: B256,
+    gas_used: u64,
+    gas_refunded: i64,
+    bytes: Bytes,
+    reverted: bool,
+}
+
+/// Cache for precompiles, for each input hash and spec stores the result.
+#[derive(Debug, Clone)]
+pub struct PrecompileCache<S>(
+    moka::sync::Cache<(B256, S), CacheEntry, alloy_primitives::map::DefaultHashBuilder>,
+)
+where
+    S: Eq + Hash + std::fmt::Debug + Send + Sync + Clone + 'static;
+
+impl<S> Default for PrecompileCache<S>
+where
+    S: Eq + Hash + std::fmt::Debug + Send + Sync + Clone + 'static,
+{
+    fn default() -> Self {
+        Self(
+            moka::sync::CacheBuilder::new(MAX_CACHE_SIZE as u64)
+                .initial_capacity(MAX_CACHE_SIZE as usize)
+                .eviction_policy(EvictionPolicy::lru())
+                .build_with_hasher(Default::default()),
+        )
+    }
+}
+
+impl<S> PrecompileCache<S>
+where
+    S: Eq + Hash + std::fmt::Debug + Send + Sync + Clone + 'static,
+{
+    fn get(&self, key: &(B256, S)) -> Option<CacheEntry> {
+        self.0.get(key)
+    }
+
+    /// Inserts the given key and value into the cache, returning the new cache size.
+    fn insert(&self, key: (B256, S), value: CacheEntry) -> usize {
+        self.0.insert(key, value);
+        self.0.entry_count() as usize
+    }
+}
+
+/// Cache entry, precompile successful output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheEntry {
+    output: PrecompileOutput,
+}
+
+impl CacheEntry {
+    const fn gas_used(&self) -> u64 {
+        self.output.gas_used
+    }
+
+    fn to_precompile_result(&self) -> PrecompileResult {
+        Ok(self.output.clone())
+    }
+}
+
+/// A cache for precompile inputs / outputs.
+#[derive(Debug)]
+pub struct CachedPrecompile<S>
+where
+    S: Eq + Hash + std::fmt::Debug + Send + Sync + Clone + 'static,
+{
+    /// Cache for precompile results and gas bounds.
+    cache: PrecompileCache<S>,
+    /// The precompile.
+    precompile: DynPrecompile,
+    /// Cache metrics.
+    metrics: Option<CachedPrecompileMetrics>,
+    /// Spec id associated to the EVM from which this cached precompile was created.
+    spec_id: S,
+}
+
+impl<S> CachedPrecompile<S>
+where
+    S: Eq + Hash + std::fmt::Debug + Send + Sync + Clone + 'static,
+{
+    /// `CachedPrecompile` constructor.
+    pub const fn new(
+        precompile: DynPrecompile,
+        cache: PrecompileCache<S>,
+        spec_id: S,
+        metrics: Option<CachedPrecompileMetrics>,
+    ) -> Self {
+        Self { precompile, cache, spec_id, metrics }
+    }
+
+    /// Wrap the given precompile in a cached precompile.
+    pub fn wrap(
+        precompile: DynPrecompile,
+        cache: PrecompileCache<S>,
+        spec_id: S,
+        metrics: Option<CachedPrecompileMetrics>,
+    ) -> DynPrecompile {
+        let precompile_id = precompile.precompile_id().clone();
+        let wrapped = Self::new(precompile, cache, spec_id, metrics);
+        (precompile_id, move |input: PrecompileInput<'_>| -> PrecompileResult {
+            wrapped.call(input)
+        })
+            .into()
+    }
+
+    fn increment_by_one_precompile_cache_hits(&self) {
+        if let Some(metrics) = &self.metrics {
+            metrics.precompile_cache_hits.increment(1);
+        }
+    }
+
+    fn increment_by_one_precompile_cache_misses(&self) {
+        if let Some(metrics) = &self.metrics {
+            metrics.precompile_cache_misses.increment(1);
+        }
+    }
+
+    fn set_precompile_cache_size_metric(&self, to: f64) {
+        if let Some(metrics) = &self.metrics {
+            metrics.precompile_cache_size.set(to);
+        }
+    }
+
+    fn increment_by_one_precompile_errors(&self) {
+        if let Some(metrics) = &self.metrics {
+            metrics.precompile_errors.increment(1);
+        }
+    }
+}
+
+impl<S> Precompile for CachedPrecompile<S>
+where
+    S: Eq + Hash + std::fmt::Debug + Send + Sync + Clone + 'static,
+{
+    fn precompile_id(&self) -> &PrecompileId {
+        self.precompile.precompile_id()
+    }
+
+    fn call(&self, input: PrecompileInput<'_>) -> PrecompileResult {
+        let key = (keccak256(input.data), self.spec_id.clone());
+        if let Some(entry) = &self.cache.get(&key) {
+            self.increment_by_one_precompile_cache_hits();
+            if input.gas >= entry.gas_used() {
+                return entry.to_precompile_result()
+            }
+        }
+
+        let result = self.precompile.call(input);
+
+        match &result {
+            Ok(output) => {
+                let size = self.cache.insert(key, CacheEntry { output: output.clone() });
+                self.set_precompile_cache_size_metric(size as f64);
+                self.increment_by_one_precompile_cache_misses();
+            }
+            _ => {
+                self.increment_by_one_precompile_errors();
+            }
+        }
+        result
+    }
+}
+
+/// Metrics for the cached precompile.
+#[derive(hanzo_evm_metrics::Metrics, Clone)]
+#[metrics(scope = "sync.caching")]
+pub struct CachedPrecompileMetrics {
+    /// Precompile cache hits
+    pub precompile_cache_hits: metrics::Counter,
+
+    /// Precompile cache misses
+    pub precompile_cache_misses: metrics::Counter,
+
+    /// Precompile cache size. Uses the LRU cache length as the size metric.
+    pub precompile_cache_size: metrics::Gauge,
+
+    /// Precompile execution errors.
+    pub precompile_errors: metrics::Counter,
+}
+
+impl CachedPrecompileMetrics {
+    /// Creates a new instance of [`CachedPrecompileMetrics`] with the given address.
+    ///
+    /// Adds address as an `address` label padded with zeros to at least two hex symbols, prefixed
+    /// by `0x`.
+    pub fn new_with_address(address: Address) -> Self {
+        Self::new_with_labels(&[("address", format!("0x{address:02x}"))])
+    }
+
+    /// Creates a new instance of [`CachedPrecompileMetrics`] with the given address and an
+    /// additional `source` label, for caches used outside of block execution.
+    pub fn new_with_source(address: Address, source: &'static str) -> Self {
+        Self::new_with_labels(&[
+            ("address", format!("0x{address:02x}")),
+            ("source", source.to_string()),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EthEvmFactory, Evm, EvmEnv, EvmFactory};
+    use revm::{
+        context::TxEnv, database::EmptyDB, precompile::PrecompileOutput,
+        primitives::hardfork::SpecId,
+    };
+
+    #[test]
+    fn test_precompile_cache_basic() {
+        let dyn_precompile: DynPrecompile = (|_input: PrecompileInput<'_>| -> PrecompileResult {
+            Ok(PrecompileOutput {
+                gas_used: 0,
+                gas_refunded: 0,
+                bytes: Bytes::default(),
+                reverted: false,
+            })
+        })
+        .into();
+
+        let cache =
+            CachedPrecompile::new(dyn_precompile, PrecompileCache::default(), SpecId::PRAGUE, None);
+
+        let output = PrecompileOutput {
+            gas_used: 50,
+            gas_refunded: 0,
+            bytes: alloy_primitives::Bytes::copy_from_slice(b"cached_result"),
+            reverted: false,
+        };
+
+        let key = (keccak256(b"test_input"), SpecId::PRAGUE);
+        let expected = CacheEntry { output };
+        cache.cache.insert(key, expected.clone());
+
+        let actual = cache.cache.get(&key).unwrap();
+
+        assert_eq!(actual, expected);
+        assert!(cache.cache.get(&(key.0, SpecId::OSAKA)).is_none());
+    }
+
+    #[test]
+    fn test_precompile_cache_map_separate_addresses() {
+        let mut evm = EthEvmFactory::default().create_evm(EmptyDB::default(), EvmEnv::default());
+        let input_data = b"same_input";
+        let gas_limit = 100_000;
+
+        let address1 = Address::repeat_byte(1);
+        let address2 = Address::repeat_byte(2);
+
+        let cache_map = PrecompileCacheMap::default();
+
+        // create the first precompile with a specific output
+        let precompile1: DynPrecompile = (PrecompileId::custom("custom"), {
+            move |input: PrecompileInput<'_>| -> PrecompileResult {
+                assert_eq!(input.data, input_data);
+
+                Ok(PrecompileOutput {
+                    gas_used: 5000,
+                    gas_refunded: 0,
+                    bytes: alloy_primitives::Bytes::copy_from_slice(b"output_from_precompile_1"),
+                    reverted: false,
+                })
+            }
+        })
+            .into();
+
+        // create the second precompile with a different output
+        let precompile2: DynPrecompile = (PrecompileId::custom("custom"), {
+            move |input: PrecompileInput<'_>| -> PrecompileResult {
+                assert_eq!(input.data, input_data);
+
+                Ok(PrecompileOutput {
+                    gas_used: 7000,
+                    gas_refunded: 0,
+                    bytes: alloy_primitives::Bytes::copy_from_slice(b"output_from_precompile_2"),
+                    reverted: false,
+                })
+            }
+        })
+            .into();
+
+        let wrapped_precompile1 = CachedPrecompile::wrap(
+            precompile1,
+            cache_map.cache_for_address(address1),
+            SpecId::PRAGUE,
+            None,
+        );
+        let wrapped_precompile2 = CachedPrecompile::wrap(
+            precompile2,
+            cache_map.cache_for_address(address2),
+            SpecId::PRAGUE,
+            None,
+        );
+
+        let precompile1_address = Address::with_last_byte(1);
+        let precompile2_address = Address::with_last_byte(2);
+
+        evm.precompiles_mut().apply_precompile(&precompile1_address, |_| Some(wrapped_precompile1));
+        evm.precompiles_mut().apply_precompile(&precompile2_address, |_| Some(wrapped_precompile2));
+
+        // first invocation of precompile1 (cache miss)
+        let result1 = evm
+            .transact_raw(TxEnv {
+                caller: Address::ZERO,
+                gas_limit,
+                data: input_data.into(),
+                kind: precompile1_address.into(),
+                ..Default::default()
+            })
+            .unwrap()
+            .result
+            .into_output()
+            .unwrap();
+        assert_eq!(result1.as_ref(), b"output_from_precompile_1");
+
+        // first invocation of precompile2 with the same input (should be a cache miss)
+        // if cache was incorrectly shared, we'd get precompile1's result
+        let result2 = evm
+            .transact_raw(TxEnv {
+                caller: Address::ZERO,
+                gas_limit,
+                data: input_data.into(),
+                kind: precompile2_address.into(),
+                ..Default::default()
+            })
+            .unwrap()
+            .result
+            .into_output()
+            .unwrap();
+        assert_eq!(result2.as_ref(), b"output_from_precompile_2");
+
+        // second invocation of precompile1 (should be a cache hit)
+        let result3 = evm
+            .transact_raw(TxEnv {
+                caller: Address::ZERO,
+                gas_limit,
+                data: input_data.into(),
+                kind: precompile1_address.into(),
+                ..Default::default()
+            })
+            .unwrap()
+            .result
+            .into_output()
+            .unwrap();
+        assert_eq!(result3.as_ref(), b"output_from_precompile_1");
+    }
+
+    const CLIENT_VERSION: &str = "1.0.0";
+    const CHAIN_ID: u64 = 1;
+
+    /// Saves a cache map with a single `PRAGUE` entry to `path`, returning the entry's key and
+    /// value.
+    fn save_cache_map(path: &Path) -> ((B256, SpecId), CacheEntry) {
+        let key = (keccak256(b"test_input"), SpecId::PRAGUE);
+        let entry = CacheEntry {
+            output: PrecompileOutput {
+                gas_used: 50,
+                gas_refunded: 0,
+                bytes: alloy_primitives::Bytes::copy_from_slice(b"cached_result"),
+                reverted: false,
+            },
+        };
+
+        let cache_map = PrecompileCacheMap::default();
+        cache_map.cache_for(Address::with_last_byte(1), &SpecId::PRAGUE).insert(key, entry.clone());
+        cache_map.save(path, CLIENT_VERSION, CHAIN_ID, &SpecId::PRAGUE).unwrap();
+        (key, entry)
+    }
+
+    /// Loads the cache map at `path` expecting it to be discarded, returning the mismatch.
+    fn load_mismatch(
+        path: &Path,
+        client_version: &str,
+        chain_id: u64,
+        spec: SpecId,
+    ) -> PersistedCacheMismatch {
+        let err = PrecompileCacheMap::load(path, client_version, chain_id, &spec).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        err.into_inner().unwrap().downcast_ref::<PersistedCacheMismatch>().unwrap().clone()
+    }
+
+    #[test]
+    fn test_precompile_cache_map_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("precompile-cache");
+        let address = Address::with_last_byte(1);
+        let (key, expected) = save_cache_map(&path);
+
+        let restored =
+            PrecompileCacheMap::load(&path, CLIENT_VERSION, CHAIN_ID, &SpecId::PRAGUE).unwrap();
+
+        // restored entries are only used for the spec they were cached with
+        let osaka_key = (key.0, SpecId::OSAKA);
+        assert!(restored.cache_for(address, &SpecId::OSAKA).get(&osaka_key).is_none());
+        assert_eq!(restored.cache_for(address, &SpecId::PRAGUE).get(&key), Some(expected));
+    }
+
+    #[test]
+    fn test_precompile_cache_map_load_discards_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("precompile-cache");
+        save_cache_map(&path);
+
+        assert_eq!(
+            load_mismatch(&path, "1.0.1", CHAIN_ID, SpecId::PRAGUE),
+            PersistedCacheMismatch::ClientVersion(CLIENT_VERSION.to_string())
+        );
+        assert_eq!(
+            load_mismatch(&path, CLIENT_VERSION, 11155111, SpecId::PRAGUE),
+            PersistedCacheMismatch::ChainId(CHAIN_ID)
+        );
+        assert_eq!(
+            load_mismatch(&path, CLIENT_VERSION, CHAIN_ID, SpecId::OSAKA),
+            PersistedCacheMismatch::Spec(spec_name(&SpecId::PRAGUE))
+        );
+    }
+
+    #[test]
+    fn test_precompile_cache_map_load_discards_other_format_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("precompile-cache");
+        save_cache_map(&path);
+
+        let data = fs::read(&path).unwrap();
+        let mut reader = data.as_slice();
+        let mut header: PersistedHeader = bincode::deserialize_from(&mut reader).unwrap();
+        header.format_version = PERSISTED_FORMAT_VERSION + 1;
+        let mut rewritten = bincode::serialize(&header).unwrap();
+        rewritten.extend_from_slice(reader);
+        fs::write(&path, rewritten).unwrap();
+
+        assert_eq!(
+            load_mismatch(&path, CLIENT_VERSION, CHAIN_ID, SpecId::PRAGUE),
+            PersistedCacheMismatch::FormatVersion(PERSISTED_FORMAT_VERSION + 1)
+        );
+    }
+
+    #[test]
+    fn test_precompile_cache_map_load_discards_corrupted_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("precompile-cache");
+        save_cache_map(&path);
+
+        let mut data = fs::read(&path).unwrap();
+        *data.last_mut().unwrap() ^= 1;
+        fs::write(&path, data).unwrap();
+
+        assert_eq!(
+            load_mismatch(&path, CLIENT_VERSION, CHAIN_ID, SpecId::PRAGUE),
+            PersistedCacheMismatch::Checksum
+        );
+    }
+}
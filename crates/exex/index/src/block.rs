@@ -0,0 +1,137 @@
+use alloy_primitives::{Address, FixedBytes, B256};
+use core::fmt::Debug;
+use hanzo_evm_db::{
+    table::{Compress, Decompress},
+    DatabaseError,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// A fixed-size key of a block index.
+pub trait IndexKey:
+    Copy + Ord + Debug + Send + Sync + Serialize + DeserializeOwned + 'static
+{
+    /// The length of the encoded key.
+    const LEN: usize;
+
+    /// Returns the encoded key.
+    fn as_slice(&self) -> &[u8];
+
+    /// Decodes the key, the slice must be exactly [`IndexKey::LEN`] bytes long.
+    fn from_slice(slice: &[u8]) -> Self;
+}
+
+impl IndexKey for Address {
+    const LEN: usize = 20;
+
+    fn as_slice(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+
+    fn from_slice(slice: &[u8]) -> Self {
+        Self(FixedBytes::from_slice(slice))
+    }
+}
+
+impl IndexKey for B256 {
+    const LEN: usize = 32;
+
+    fn as_slice(&self) -> &[u8] {
+        FixedBytes::as_slice(self)
+    }
+
+    fn from_slice(slice: &[u8]) -> Self {
+        FixedBytes::from_slice(slice)
+    }
+}
+
+/// An indexed block, the value of the blocks table of a block index.
+///
+/// The keys are required to unwind the history tables without re-reading the unwound blocks.
+/// Encoded as the block hash, followed by the number of `first` keys as a big-endian `u32` and the
+/// concatenated `first` and `second` keys.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredIndexedBlock<A, B> {
+    /// The hash of the block.
+    pub hash: B256,
+    /// The keys that were indexed in the first history table for this block.
+    pub first: Vec<A>,
+    /// The keys that were indexed in the second history table for this block.
+    pub second: Vec<B>,
+}
+
+impl<A, B> Default for StoredIndexedBlock<A, B> {
+    fn default() -> Self {
+        Self { hash: B256::ZERO, first: Vec::new(), second: Vec::new() }
+    }
+}
+
+impl<A: IndexKey, B: IndexKey> Compress for StoredIndexedBlock<A, B> {
+    type Compressed = Vec<u8>;
+
+    fn compress_to_buf<BUF: bytes::BufMut + AsMut<[u8]>>(&self, buf: &mut BUF) {
+        buf.put_slice(self.hash.as_slice());
+        buf.put_u32(self.first.len() as u32);
+        for key in &self.first {
+            buf.put_slice(key.as_slice());
+        }
+        for key in &self.second {
+            buf.put_slice(key.as_slice());
+        }
+    }
+}
+
+impl<A: IndexKey, B: IndexKey> Decompress for StoredIndexedBlock<A, B> {
+    fn decompress(value: &[u8]) -> Result<Self, DatabaseError> {
+        if value.len() < 36 {
+            return Err(DatabaseError::Decode)
+        }
+        let hash = B256::from_slice(&value[..32]);
+        let first_len = u32::from_be_bytes(value[32..36].try_into().unwrap()) as usize;
+        let (first, second) = first_len
+            .checked_mul(A::LEN)
+            .and_then(|len| value[36..].split_at_checked(len))
+            .ok_or(DatabaseError::Decode)?;
+        if !second.len().is_multiple_of(B::LEN) {
+            return Err(DatabaseError::Decode)
+        }
+        let first = first.chunks_exact(A::LEN).map(A::from_slice).collect();
+        let second = second.chunks_exact(B::LEN).map(B::from_slice).collect();
+        Ok(Self { hash, first, second })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stored_indexed_block_roundtrip() {
+        let block = StoredIndexedBlock {
+            hash: B256::repeat_byte(1),
+            first: vec![Address::repeat_byte(2), Address::repeat_byte(3)],
+            second: vec![B256::repeat_byte(4)],
+        };
+        let encoded = block.clone().compress();
+        assert_eq!(StoredIndexedBlock::decompress(&encoded).unwrap(), block);
+
+        let empty = StoredIndexedBlock::<Address, B256>::default();
+        assert_eq!(StoredIndexedBlock::decompress(&empty.clone().compress()).unwrap(), empty);
+
+        assert!(StoredIndexedBlock::<Address, B256>::decompress(&encoded[..60]).is_err());
+        assert!(
+            StoredIndexedBlock::<Address, B256>::decompress(&encoded[..encoded.len() - 1]).is_err()
+        );
+    }
+
+    #[test]
+    fn stored_indexed_block_same_key_type() {
+        let block = StoredIndexedBlock {
+            hash: B256::repeat_byte(1),
+            first: vec![Address::repeat_byte(2)],
+            second: vec![Address::repeat_byte(3), Address::repeat_byte(4)],
+        };
+        let encoded = block.clone().compress();
+        assert_eq!(StoredIndexedBlock::decompress(&encoded).unwrap(), block);
+        assert!(StoredIndexedBlock::<Address, Address>::decompress(&encoded[..40]).is_err());
+    }
+}
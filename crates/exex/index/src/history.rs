@@ -0,0 +1,129 @@
+//! Helpers for the history tables of a block index.
+//!
+//! A history table maps a key to the blocks in which it appeared. The blocks of a key are split
+//! into shards of at most [`NUM_OF_INDICES_IN_SHARD`] blocks, keyed by the highest block of the
+//! shard, and the last shard is keyed by `u64::MAX`, like the `AccountsHistory` table of the node
+//! database.
+
+use alloy_primitives::BlockNumber;
+use hanzo_evm_db::{
+    cursor::{DbCursorRO, DbCursorRW},
+    models::{sharded_key::NUM_OF_INDICES_IN_SHARD, ShardedKey},
+    table::{Key, Table},
+    transaction::{DbTx, DbTxMut},
+    BlockNumberList,
+};
+use hanzo_evm_provider::{ProviderError, ProviderResult};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::RangeInclusive,
+};
+
+/// Appends the block numbers to the last shard of each key, splitting it if it exceeds
+/// [`NUM_OF_INDICES_IN_SHARD`].
+pub fn append_history<TX, T, K>(
+    tx: &TX,
+    updates: BTreeMap<K, Vec<BlockNumber>>,
+) -> ProviderResult<()>
+where
+    TX: DbTxMut + DbTx,
+    T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+    K: Copy,
+    ShardedKey<K>: Key,
+{
+    let mut cursor = tx.cursor_write::<T>()?;
+    for (key, blocks) in updates {
+        let last_key = ShardedKey::last(key);
+        let mut shard = cursor
+            .seek_exact(last_key.clone())?
+            .map(|(_, list)| list)
+            .unwrap_or_else(BlockNumberList::empty);
+        shard.append(blocks).map_err(ProviderError::other)?;
+
+        if shard.len() <= NUM_OF_INDICES_IN_SHARD as u64 {
+            cursor.upsert(last_key, &shard)?;
+            continue
+        }
+
+        let blocks = shard.iter().collect::<Vec<_>>();
+        let mut chunks = blocks.chunks(NUM_OF_INDICES_IN_SHARD).peekable();
+        while let Some(chunk) = chunks.next() {
+            let highest = if chunks.peek().is_some() { chunk[chunk.len() - 1] } else { u64::MAX };
+            cursor.upsert(
+                ShardedKey::new(key, highest),
+                &BlockNumberList::new_pre_sorted(chunk.iter().copied()),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Removes all block numbers at or above `first_removed` from the shards of the given keys.
+pub fn unwind_history<TX, T, K>(
+    tx: &TX,
+    keys: BTreeSet<K>,
+    first_removed: BlockNumber,
+) -> ProviderResult<()>
+where
+    TX: DbTxMut + DbTx,
+    T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+    K: Copy + PartialEq,
+    ShardedKey<K>: Key,
+{
+    let mut cursor = tx.cursor_write::<T>()?;
+    for key in keys {
+        // walk the shards of the key backwards, starting with the last one, until a shard that
+        // only contains retained blocks is found
+        let mut retained = Vec::new();
+        let mut entry = cursor.seek_exact(ShardedKey::last(key))?;
+        while let Some((sharded_key, list)) = entry {
+            if sharded_key.key != key {
+                break
+            }
+            cursor.delete_current()?;
+            if list.iter().next().is_some_and(|first| first < first_removed) {
+                retained = list.iter().take_while(|block| *block < first_removed).collect();
+                break
+            }
+            entry = cursor.prev()?;
+        }
+
+        if !retained.is_empty() {
+            cursor.upsert(ShardedKey::last(key), &BlockNumberList::new_pre_sorted(retained))?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the block numbers of the key that are in the given range.
+pub fn read_history<TX, T, K>(
+    tx: &TX,
+    key: K,
+    range: RangeInclusive<BlockNumber>,
+) -> ProviderResult<Vec<BlockNumber>>
+where
+    TX: DbTx,
+    T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+    K: Copy + PartialEq,
+    ShardedKey<K>: Key,
+{
+    let mut blocks = Vec::new();
+    let mut cursor = tx.cursor_read::<T>()?;
+    // the first shard whose highest block is at or above the start of the range
+    let mut entry = cursor.seek(ShardedKey::new(key, *range.start()))?;
+    while let Some((sharded_key, list)) = entry {
+        if sharded_key.key != key {
+            break
+        }
+        for block in list.iter() {
+            if block > *range.end() {
+                return Ok(blocks)
+            }
+            if block >= *range.start() {
+                blocks.push(block);
+            }
+        }
+        entry = cursor.next()?;
+    }
+    Ok(blocks)
+}
@@ -0,0 +1,172 @@
+use crate::{
+    history::{append_history, read_history, unwind_history},
+    IndexKey, StoredIndexedBlock,
+};
+use alloy_eips::BlockNumHash;
+use alloy_primitives::BlockNumber;
+use core::{fmt::Debug, marker::PhantomData};
+use hanzo_evm_db::{
+    create_db,
+    cursor::{DbCursorRO, DbCursorRW},
+    database::Database,
+    mdbx::DatabaseArguments,
+    models::ShardedKey,
+    table::{Key, Table},
+    transaction::{DbTx, DbTxMut},
+    BlockNumberList, ClientVersion, DatabaseEnv, DatabaseError, TableSet,
+};
+use hanzo_evm_provider::{ProviderError, ProviderResult};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::RangeInclusive,
+    path::Path,
+    sync::Arc,
+};
+
+/// The tables of a block index database.
+pub trait BlockIndexTables: TableSet + Debug + Send + Sync + 'static {
+    /// The keys of the first history table.
+    type First: IndexKey;
+    /// The keys of the second history table.
+    type Second: IndexKey;
+    /// Stores the hash and the indexed keys of every indexed block. The first entry is the lower
+    /// bound and the last entry is the head of the index.
+    type Blocks: Table<Key = BlockNumber, Value = StoredIndexedBlock<Self::First, Self::Second>>;
+    /// Stores the blocks in which a key of the first kind appeared.
+    type FirstHistory: Table<Key = ShardedKey<Self::First>, Value = BlockNumberList>;
+    /// Stores the blocks in which a key of the second kind appeared.
+    type SecondHistory: Table<Key = ShardedKey<Self::Second>, Value = BlockNumberList>;
+}
+
+/// A persistent index of the blocks in which a key appeared.
+///
+/// Blocks must be inserted in ascending order without gaps, see [`BlockIndex::insert_blocks`].
+#[derive(Debug, Clone)]
+pub struct BlockIndex<T> {
+    db: Arc<DatabaseEnv>,
+    _tables: PhantomData<T>,
+}
+
+impl<T> BlockIndex<T>
+where
+    T: BlockIndexTables,
+    ShardedKey<T::First>: Key,
+    ShardedKey<T::Second>: Key,
+{
+    /// Opens the index database at the given path, creating it if it doesn't exist.
+    pub fn open(path: impl AsRef<Path>) -> eyre::Result<Self> {
+        let mut db = create_db(path, DatabaseArguments::new(ClientVersion::default()))?;
+        db.create_and_track_tables_for::<T>()?;
+        Ok(Self { db: Arc::new(db), _tables: PhantomData })
+    }
+
+    /// Returns the highest indexed block, or `None` if the index is empty.
+    pub fn head(&self) -> ProviderResult<Option<BlockNumHash>> {
+        let tx = self.db.tx()?;
+        let head = tx
+            .cursor_read::<T::Blocks>()?
+            .last()?
+            .map(|(number, block)| BlockNumHash::new(number, block.hash));
+        Ok(head)
+    }
+
+    /// Returns the range of indexed blocks, or `None` if the index is empty.
+    pub fn range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        let tx = self.db.tx()?;
+        let mut cursor = tx.cursor_read::<T::Blocks>()?;
+        let Some((first, _)) = cursor.first()? else { return Ok(None) };
+        let Some((last, _)) = cursor.last()? else { return Ok(None) };
+        Ok(Some(first..=last))
+    }
+
+    /// Inserts the given blocks with their keys into the index.
+    ///
+    /// Blocks at or below the current head are skipped, so a block that is delivered twice is
+    /// only indexed once. The first block of an empty index becomes its lower bound, after that
+    /// every block must directly follow the head.
+    pub fn insert_blocks(
+        &self,
+        blocks: impl IntoIterator<Item = (BlockNumHash, BTreeSet<T::First>, BTreeSet<T::Second>)>,
+    ) -> ProviderResult<()> {
+        let tx = self.db.tx_mut()?;
+        let mut blocks_cursor = tx.cursor_write::<T::Blocks>()?;
+        let mut next = blocks_cursor.last()?.map(|(number, _)| number + 1);
+
+        let mut first_updates = BTreeMap::<T::First, Vec<BlockNumber>>::new();
+        let mut second_updates = BTreeMap::<T::Second, Vec<BlockNumber>>::new();
+        for (block, first, second) in blocks {
+            if next.is_some_and(|next| block.number < next) {
+                continue
+            }
+            if let Some(next) = next.filter(|next| block.number > *next) {
+                return Err(ProviderError::Database(DatabaseError::Other(format!(
+                    "block index gap: expected block {next}, got {}",
+                    block.number
+                ))))
+            }
+            next = Some(block.number + 1);
+
+            for key in &first {
+                first_updates.entry(*key).or_default().push(block.number);
+            }
+            for key in &second {
+                second_updates.entry(*key).or_default().push(block.number);
+            }
+            blocks_cursor.append(
+                block.number,
+                &StoredIndexedBlock {
+                    hash: block.hash,
+                    first: first.into_iter().collect(),
+                    second: second.into_iter().collect(),
+                },
+            )?;
+        }
+        drop(blocks_cursor);
+
+        append_history::<_, T::FirstHistory, _>(&tx, first_updates)?;
+        append_history::<_, T::SecondHistory, _>(&tx, second_updates)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Removes all blocks above the given block from the index.
+    pub fn unwind_above(&self, block: BlockNumber) -> ProviderResult<()> {
+        let tx = self.db.tx_mut()?;
+
+        let mut first = BTreeSet::new();
+        let mut second = BTreeSet::new();
+        let mut blocks_cursor = tx.cursor_write::<T::Blocks>()?;
+        let mut walker = blocks_cursor.walk(Some(block + 1))?;
+        while let Some((_, indexed)) = walker.next().transpose()? {
+            first.extend(indexed.first);
+            second.extend(indexed.second);
+            walker.delete_current()?;
+        }
+        drop(blocks_cursor);
+
+        unwind_history::<_, T::FirstHistory, _>(&tx, first, block + 1)?;
+        unwind_history::<_, T::SecondHistory, _>(&tx, second, block + 1)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Returns the blocks in the given range in which the key of the first kind appeared, in
+    /// ascending order.
+    pub fn first_key_blocks(
+        &self,
+        key: T::First,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        read_history::<_, T::FirstHistory, _>(&self.db.tx()?, key, range)
+    }
+
+    /// Returns the blocks in the given range in which the key of the second kind appeared, in
+    /// ascending order.
+    pub fn second_key_blocks(
+        &self,
+        key: T::Second,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        read_history::<_, T::SecondHistory, _>(&self.db.tx()?, key, range)
+    }
+}
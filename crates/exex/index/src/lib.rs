@@ -0,0 +1,28 @@
+//! Storage of the block indexes that are maintained by execution extensions.
+//!
+//! A block index stores, for two kinds of keys, the blocks in which a key appeared, for example the
+//! callers and callees of call frames or the emitters and topics of logs. [`BlockIndex`] keeps the
+//! index in a separate database with a table of the indexed blocks and two history tables that use
+//! the same sharding as the `AccountsHistory` table of the node database.
+//!
+//! The indexed blocks are contiguous: the first block is the lower bound of the index, which is
+//! above genesis if the index was started on a node with pruned history, see [`prune_boundary`].
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/hanzoai/evm/main/assets/evm-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/hanzoai/evm/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+mod block;
+pub use block::{IndexKey, StoredIndexedBlock};
+
+pub mod history;
+
+mod index;
+pub use index::{BlockIndex, BlockIndexTables};
+
+mod prune;
+pub use prune::prune_boundary;
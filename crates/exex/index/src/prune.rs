@@ -0,0 +1,26 @@
+use alloy_eips::BlockNumHash;
+use hanzo_evm_provider::ProviderError;
+use hanzo_evm_prune_types::PruneSegment;
+use hanzo_evm_storage_api::{BlockHashReader, DatabaseProviderFactory, PruneCheckpointReader};
+
+/// Returns the highest block that is pruned in any of the given segments, or genesis if none of
+/// them is pruned.
+///
+/// A block index that depends on the data of these segments can only index the blocks above the
+/// returned block, so an empty index starts after it.
+pub fn prune_boundary<P>(provider: &P, segments: &[PruneSegment]) -> eyre::Result<BlockNumHash>
+where
+    P: DatabaseProviderFactory<Provider: PruneCheckpointReader> + BlockHashReader,
+{
+    let prune_provider = provider.database_provider_ro()?;
+    let mut number = 0;
+    for segment in segments {
+        if let Some(checkpoint) = prune_provider.get_prune_checkpoint(*segment)? {
+            number = number.max(checkpoint.block_number.unwrap_or_default());
+        }
+    }
+
+    let hash =
+        provider.block_hash(number)?.ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?;
+    Ok(BlockNumHash::new(number, hash))
+}
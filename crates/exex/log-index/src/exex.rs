@@ -0,0 +1,166 @@
+use crate::{LogIndex, LogKeys};
+use alloy_consensus::{BlockHeader, TxReceipt};
+use alloy_eips::BlockNumHash;
+use alloy_primitives::Log;
+use futures::TryStreamExt;
+use hanzo_evm_exex::{ExExContext, ExExEvent, ExExHead};
+use hanzo_evm_exex_index::prune_boundary;
+use hanzo_evm_node_api::FullNodeComponents;
+use hanzo_evm_prune_types::PruneSegment;
+use hanzo_evm_storage_api::{
+    BlockBodyIndicesProvider, BlockNumReader, DatabaseProviderFactory, HeaderProvider,
+    PruneCheckpointReader, ReceiptProvider,
+};
+use tracing::{debug, info};
+
+/// The number of blocks that are read from the node database at once while catching up.
+const CATCH_UP_BATCH_SIZE: u64 = 10_000;
+
+/// Runs the log index `ExEx`.
+///
+/// On start, the blocks that are already persisted in the node database are indexed from their
+/// stored receipts, see [`catch_up`]. The notifications start after the head of the index, so
+/// blocks that are not persisted yet are backfilled. Reverted blocks are unwound before committed
+/// blocks are inserted.
+pub async fn log_index_exex<Node>(mut ctx: ExExContext<Node>, index: LogIndex) -> eyre::Result<()>
+where
+    Node: FullNodeComponents,
+{
+    // reading the receipts of the whole chain can take a while on the first start
+    let head = tokio::task::block_in_place(|| catch_up(ctx.provider(), &index))?;
+    ctx.set_notifications_with_head(ExExHead::new(head));
+
+    while let Some(notification) = ctx.notifications.try_next().await? {
+        if let Some(reverted) = notification.reverted_chain() {
+            let unwind_to = reverted.first().number().saturating_sub(1);
+            debug!(target: "exex::log_index", range = ?reverted.range(), "Unwinding log index");
+            index.unwind_above(unwind_to)?;
+        }
+
+        if let Some(committed) = notification.committed_chain() {
+            index.insert_blocks(committed.blocks_and_receipts().map(|(block, receipts)| {
+                (block.num_hash(), LogKeys::from_logs(receipts.iter().flat_map(TxReceipt::logs)))
+            }))?;
+            info!(target: "exex::log_index", range = ?committed.range(), "Indexed blocks");
+            ctx.events.send(ExExEvent::FinishedHeight(committed.tip().num_hash()))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Indexes all blocks above the head of the index that are persisted in the node database and
+/// returns the new head of the index.
+///
+/// The logs are read from the stored receipts, which is much cheaper than the backfill of the
+/// `ExEx` notifications that re-executes the blocks. An empty index starts at genesis, or above the
+/// highest block whose receipts are pruned, which is returned as the head if no block is persisted
+/// above it.
+pub fn catch_up<P>(provider: &P, index: &LogIndex) -> eyre::Result<BlockNumHash>
+where
+    P: DatabaseProviderFactory<Provider: PruneCheckpointReader>
+        + BlockNumReader
+        + HeaderProvider
+        + BlockBodyIndicesProvider
+        + ReceiptProvider<Receipt: TxReceipt<Log = Log>>,
+{
+    let (mut head, start) = match index.head()? {
+        Some(head) => (head, head.number + 1),
+        None => {
+            let boundary =
+                prune_boundary(provider, &[PruneSegment::Receipts, PruneSegment::ContractLogs])?;
+            // genesis is indexed as well if nothing is pruned
+            let start = if boundary.number == 0 { 0 } else { boundary.number + 1 };
+            (boundary, start)
+        }
+    };
+    let tip = provider.last_block_number()?;
+
+    for batch_start in (start..=tip).step_by(CATCH_UP_BATCH_SIZE as usize) {
+        let range = batch_start..=tip.min(batch_start + CATCH_UP_BATCH_SIZE - 1);
+        let headers = provider.sealed_headers_range(range.clone())?;
+        let body_indices = provider.block_body_indices_range(range.clone())?;
+        let receipts = provider.receipts_by_block_range(range.clone())?;
+
+        let block_count = range.end() - range.start() + 1;
+        if [headers.len(), body_indices.len(), receipts.len()]
+            .into_iter()
+            .any(|len| len as u64 != block_count)
+        {
+            eyre::bail!("blocks {range:?} are missing in the node database");
+        }
+        for ((header, indices), receipts) in headers.iter().zip(&body_indices).zip(&receipts) {
+            if receipts.len() as u64 != indices.tx_count() {
+                eyre::bail!("receipts of block {} are missing", header.number());
+            }
+        }
+        if let Some(last) = headers.last() {
+            head = last.num_hash();
+        }
+
+        index.insert_blocks(headers.iter().zip(&receipts).map(|(header, receipts)| {
+            (header.num_hash(), LogKeys::from_logs(receipts.iter().flat_map(TxReceipt::logs)))
+        }))?;
+        info!(target: "exex::log_index", ?range, "Indexed persisted blocks");
+    }
+
+    Ok(head)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hanzo_evm_db_common::init::init_genesis;
+    use hanzo_evm_provider::{
+        test_utils::create_test_provider_factory, BlockWriter, DBProvider, PruneCheckpointWriter,
+    };
+    use hanzo_evm_prune_types::{PruneCheckpoint, PruneMode};
+    use hanzo_evm_testing_utils::generators::{self, random_block, BlockParams};
+
+    #[test]
+    fn catch_up_from_prune_boundary() {
+        let mut rng = generators::rng();
+        let factory = create_test_provider_factory();
+        let mut parent = init_genesis(&factory).unwrap();
+
+        let provider_rw = factory.database_provider_rw().unwrap();
+        for number in 1..=3 {
+            let params =
+                BlockParams { parent: Some(parent), tx_count: Some(0), ..Default::default() };
+            let block = random_block(&mut rng, number, params).try_recover().unwrap();
+            parent = block.hash();
+            provider_rw.insert_block(&block).unwrap();
+        }
+        provider_rw.commit().unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        // nothing is pruned, so the index starts at genesis
+        let index = LogIndex::open(dir.path().join("archive")).unwrap();
+        let head = catch_up(&factory, &index).unwrap();
+        assert_eq!(head, BlockNumHash::new(3, parent));
+        assert_eq!(index.range().unwrap(), Some(0..=3));
+
+        // the receipts of the first two blocks are pruned
+        let provider_rw = factory.database_provider_rw().unwrap();
+        provider_rw
+            .save_prune_checkpoint(
+                PruneSegment::Receipts,
+                PruneCheckpoint {
+                    block_number: Some(1),
+                    tx_number: None,
+                    prune_mode: PruneMode::Before(2),
+                },
+            )
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        let index = LogIndex::open(dir.path().join("pruned")).unwrap();
+        let head = catch_up(&factory, &index).unwrap();
+        assert_eq!(head, BlockNumHash::new(3, parent));
+        assert_eq!(index.range().unwrap(), Some(2..=3));
+
+        // catching up again doesn't index anything
+        assert_eq!(catch_up(&factory, &index).unwrap(), head);
+        assert_eq!(index.range().unwrap(), Some(2..=3));
+    }
+}
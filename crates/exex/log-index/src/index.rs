@@ -0,0 +1,188 @@
+use crate::tables::LogIndexTables;
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{Address, BlockNumber, Log, B256};
+use hanzo_evm_exex_index::BlockIndex;
+use hanzo_evm_provider::ProviderResult;
+use hanzo_evm_storage_api::LogIndexReader;
+use std::{collections::BTreeSet, ops::RangeInclusive, path::Path};
+
+/// The addresses and topics of the logs of a block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LogKeys {
+    /// The addresses that emitted a log.
+    pub addresses: BTreeSet<Address>,
+    /// The topics of the logs, regardless of their position.
+    pub topics: BTreeSet<B256>,
+}
+
+impl LogKeys {
+    /// Collects the addresses and topics of the given logs.
+    pub fn from_logs<'a>(logs: impl IntoIterator<Item = &'a Log>) -> Self {
+        let mut keys = Self::default();
+        for log in logs {
+            keys.addresses.insert(log.address);
+            keys.topics.extend(log.topics());
+        }
+        keys
+    }
+}
+
+/// A persistent index of the blocks in which an address emitted a log or a topic appeared in a
+/// log.
+///
+/// Blocks must be inserted in ascending order without gaps, see [`LogIndex::insert_blocks`]. The
+/// first indexed block is the lower bound of the index, it is above genesis if the index was
+/// started on a node with pruned receipts.
+#[derive(Debug, Clone)]
+pub struct LogIndex {
+    index: BlockIndex<LogIndexTables>,
+}
+
+impl LogIndex {
+    /// Opens the index database at the given path, creating it if it doesn't exist.
+    pub fn open(path: impl AsRef<Path>) -> eyre::Result<Self> {
+        Ok(Self { index: BlockIndex::open(path)? })
+    }
+
+    /// Returns the highest indexed block, or `None` if the index is empty.
+    pub fn head(&self) -> ProviderResult<Option<BlockNumHash>> {
+        self.index.head()
+    }
+
+    /// Returns the range of indexed blocks, or `None` if the index is empty.
+    pub fn range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.index.range()
+    }
+
+    /// Inserts the given blocks into the index.
+    ///
+    /// Blocks at or below the current head are skipped, so a block that is delivered twice is
+    /// only indexed once. The first block of an empty index becomes its lower bound, after that
+    /// every block must directly follow the head.
+    pub fn insert_blocks(
+        &self,
+        blocks: impl IntoIterator<Item = (BlockNumHash, LogKeys)>,
+    ) -> ProviderResult<()> {
+        self.index.insert_blocks(
+            blocks.into_iter().map(|(block, keys)| (block, keys.addresses, keys.topics)),
+        )
+    }
+
+    /// Removes all blocks above the given block from the index.
+    pub fn unwind_above(&self, block: BlockNumber) -> ProviderResult<()> {
+        self.index.unwind_above(block)
+    }
+}
+
+impl LogIndexReader for LogIndex {
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.range()
+    }
+
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.index.first_key_blocks(address, range)
+    }
+
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.index.second_key_blocks(topic, range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::LogData;
+    use hanzo_evm_db::models::sharded_key::NUM_OF_INDICES_IN_SHARD;
+
+    fn block(number: BlockNumber) -> BlockNumHash {
+        BlockNumHash::new(number, B256::with_last_byte(number as u8))
+    }
+
+    fn log(address: Address, topics: &[B256]) -> Log {
+        Log { address, data: LogData::new_unchecked(topics.to_vec(), Default::default()) }
+    }
+
+    #[test]
+    fn insert_and_unwind() {
+        let dir = tempfile::tempdir().unwrap();
+        let index = LogIndex::open(dir.path()).unwrap();
+        let (token, pool) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let (transfer, swap) = (B256::repeat_byte(3), B256::repeat_byte(4));
+        assert_eq!(index.head().unwrap(), None);
+
+        index
+            .insert_blocks([
+                (block(0), LogKeys::default()),
+                (block(1), LogKeys::from_logs(&[log(token, &[transfer])])),
+                (block(2), LogKeys::from_logs(&[log(pool, &[swap, transfer])])),
+                (block(3), LogKeys::from_logs(&[log(token, &[transfer]), log(pool, &[])])),
+            ])
+            .unwrap();
+        assert_eq!(index.head().unwrap(), Some(block(3)));
+        assert_eq!(index.log_address_blocks(token, 0..=3).unwrap(), vec![1, 3]);
+        assert_eq!(index.log_address_blocks(pool, 0..=2).unwrap(), vec![2]);
+        assert_eq!(index.log_topic_blocks(transfer, 2..=10).unwrap(), vec![2, 3]);
+        assert_eq!(index.log_topic_blocks(swap, 0..=1).unwrap(), Vec::<BlockNumber>::new());
+
+        // already indexed blocks are skipped
+        index.insert_blocks([(block(3), LogKeys::from_logs(&[log(pool, &[swap])]))]).unwrap();
+        assert_eq!(index.log_topic_blocks(swap, 0..=3).unwrap(), vec![2]);
+
+        index.unwind_above(1).unwrap();
+        assert_eq!(index.head().unwrap(), Some(block(1)));
+        assert_eq!(index.log_address_blocks(token, 0..=3).unwrap(), vec![1]);
+        assert_eq!(index.log_address_blocks(pool, 0..=3).unwrap(), Vec::<BlockNumber>::new());
+        assert_eq!(index.log_topic_blocks(transfer, 0..=3).unwrap(), vec![1]);
+        assert_eq!(index.log_topic_blocks(swap, 0..=3).unwrap(), Vec::<BlockNumber>::new());
+    }
+
+    #[test]
+    fn lower_bound() {
+        let dir = tempfile::tempdir().unwrap();
+        let index = LogIndex::open(dir.path()).unwrap();
+        let token = Address::repeat_byte(1);
+        let keys = LogKeys { addresses: BTreeSet::from([token]), ..Default::default() };
+
+        // the index starts above the pruned receipts
+        index.insert_blocks((5..=7).map(|number| (block(number), keys.clone()))).unwrap();
+        assert_eq!(index.log_index_range().unwrap(), Some(5..=7));
+        assert!(index.insert_blocks([(block(9), keys.clone())]).is_err());
+        assert_eq!(index.log_address_blocks(token, 0..=10).unwrap(), vec![5, 6, 7]);
+
+        index.unwind_above(4).unwrap();
+        assert_eq!(index.log_index_range().unwrap(), None);
+    }
+
+    #[test]
+    fn sharded_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let index = LogIndex::open(dir.path()).unwrap();
+        let topic = B256::repeat_byte(1);
+
+        let total = NUM_OF_INDICES_IN_SHARD as u64 * 2 + 10;
+        let keys = LogKeys { topics: BTreeSet::from([topic]), ..Default::default() };
+        index.insert_blocks((0..total).map(|number| (block(number), keys.clone()))).unwrap();
+        assert_eq!(
+            index.log_topic_blocks(topic, 0..=total).unwrap(),
+            (0..total).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            index.log_topic_blocks(topic, 1990..=2010).unwrap(),
+            (1990..=2010).collect::<Vec<_>>()
+        );
+
+        index.unwind_above(1500).unwrap();
+        assert_eq!(
+            index.log_topic_blocks(topic, 0..=total).unwrap(),
+            (0..=1500).collect::<Vec<_>>()
+        );
+    }
+}
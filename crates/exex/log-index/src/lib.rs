@@ -0,0 +1,57 @@
+//! An execution extension that maintains a persistent index of the blocks in which an address
+//! emitted a log or a topic appeared in a log.
+//!
+//! `eth_getLogs` has to check the bloom filter of every header of the requested range and load
+//! the receipts of every block whose bloom matches, which includes false positives. With the index
+//! it only loads the headers and receipts of the blocks that contain the requested addresses and
+//! topics, similar in spirit to the log index of [EIP-7745].
+//!
+//! The index is stored in a separate database and is kept in sync with the canonical chain by
+//! [`log_index_exex`]. Blocks that are already persisted are indexed from their stored receipts.
+//! If the node prunes receipts, the index starts above the prune boundary and the blocks below it
+//! are checked against their bloom filter by the RPC handler. [`LogIndex`] implements
+//! [`LogIndexReader`] and can be handed to the `eth` filter RPC handler.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! let index = LogIndex::open(data_dir.data_dir().join("log-index"))?;
+//!
+//! let handle = builder
+//!     .node(EthereumNode::default())
+//!     .install_exex("log-index", {
+//!         let index = index.clone();
+//!         async move |ctx| Ok(log_index_exex(ctx, index))
+//!     })
+//!     .extend_rpc_modules(move |ctx| {
+//!         let filter = EthFilter::new_with_log_index(
+//!             ctx.registry.eth_api().clone(),
+//!             ctx.config().rpc.eth_config().filter_config(),
+//!             ctx.node().task_executor().clone(),
+//!             Arc::new(index),
+//!         );
+//!         ctx.modules.replace_configured(filter.into_rpc())?;
+//!         Ok(())
+//!     })
+//!     .launch()
+//!     .await?;
+//! ```
+//!
+//! [EIP-7745]: https://eips.ethereum.org/EIPS/eip-7745
+//! [`LogIndexReader`]: hanzo_evm_storage_api::LogIndexReader
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/hanzoai/evm/main/assets/evm-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/hanzoai/evm/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+mod exex;
+pub use exex::{catch_up, log_index_exex};
+
+mod index;
+pub use index::{LogIndex, LogKeys};
+
+pub mod tables;
@@ -0,0 +1,104 @@
+//! Tables of the log index database.
+
+use alloy_primitives::{Address, BlockNumber, B256};
+use hanzo_evm_db::{
+    models::ShardedKey,
+    table::{Table, TableInfo},
+    BlockNumberList, TableSet,
+};
+use hanzo_evm_exex_index::{BlockIndexTables, StoredIndexedBlock};
+
+/// Stores the blocks in which an address emitted a log.
+///
+/// Uses the same sharding as the `AccountsHistory` table of the node database.
+#[derive(Debug)]
+pub struct LogAddressHistory;
+
+impl Table for LogAddressHistory {
+    const NAME: &'static str = "LogAddressHistory";
+    const DUPSORT: bool = false;
+
+    type Key = ShardedKey<Address>;
+    type Value = BlockNumberList;
+}
+
+/// Stores the blocks in which a topic appeared at any position of a log.
+///
+/// Uses the same sharding as the `AccountsHistory` table of the node database.
+#[derive(Debug)]
+pub struct LogTopicHistory;
+
+impl Table for LogTopicHistory {
+    const NAME: &'static str = "LogTopicHistory";
+    const DUPSORT: bool = false;
+
+    type Key = ShardedKey<B256>;
+    type Value = BlockNumberList;
+}
+
+/// Stores the hash and the indexed addresses and topics of every indexed block.
+///
+/// The first entry is the lower bound and the last entry is the head of the index. The addresses
+/// and topics are required to unwind the history tables without reading the receipts of the
+/// unwound blocks.
+#[derive(Debug)]
+pub struct LogIndexedBlocks;
+
+impl Table for LogIndexedBlocks {
+    const NAME: &'static str = "LogIndexedBlocks";
+    const DUPSORT: bool = false;
+
+    type Key = BlockNumber;
+    type Value = StoredLogBlock;
+}
+
+/// A table of the log index database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogIndexTables {
+    /// The [`LogAddressHistory`] table.
+    LogAddressHistory,
+    /// The [`LogTopicHistory`] table.
+    LogTopicHistory,
+    /// The [`LogIndexedBlocks`] table.
+    LogIndexedBlocks,
+}
+
+impl LogIndexTables {
+    /// All the tables of the log index database.
+    pub const ALL: &'static [Self] =
+        &[Self::LogAddressHistory, Self::LogTopicHistory, Self::LogIndexedBlocks];
+}
+
+impl TableInfo for LogIndexTables {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::LogAddressHistory => LogAddressHistory::NAME,
+            Self::LogTopicHistory => LogTopicHistory::NAME,
+            Self::LogIndexedBlocks => LogIndexedBlocks::NAME,
+        }
+    }
+
+    fn is_dupsort(&self) -> bool {
+        false
+    }
+}
+
+impl TableSet for LogIndexTables {
+    fn tables() -> Box<dyn Iterator<Item = Box<dyn TableInfo>>> {
+        Box::new(Self::ALL.iter().map(|table| Box::new(*table) as Box<dyn TableInfo>))
+    }
+}
+
+impl BlockIndexTables for LogIndexTables {
+    type First = Address;
+    type Second = B256;
+    type Blocks = LogIndexedBlocks;
+    type FirstHistory = LogAddressHistory;
+    type SecondHistory = LogTopicHistory;
+}
+
+/// An indexed block, the value of the [`LogIndexedBlocks`] table.
+///
+/// The `first` addresses were indexed in [`LogAddressHistory`] and the `second` topics in
+/// [`LogTopicHistory`] for this block.
+pub type StoredLogBlock = StoredIndexedBlock<Address, B256>;
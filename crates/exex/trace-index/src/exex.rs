@@ -0,0 +1,129 @@
+use crate::{TraceIndex, TracedAddresses};
+use alloy_consensus::BlockHeader;
+use alloy_eips::BlockNumHash;
+use futures::TryStreamExt;
+use hanzo_evm_execution::{block::BlockExecutor, ConfigureEvm, Database, Evm, InspectorFor};
+use hanzo_evm_exex::{ExExContext, ExExEvent, ExExHead};
+use hanzo_evm_exex_index::prune_boundary;
+use hanzo_evm_node_api::{FullNodeComponents, PrimitivesTy};
+use hanzo_evm_primitives_traits::{NodePrimitives, RecoveredBlock};
+use hanzo_evm_provider::Chain;
+use hanzo_evm_prune_types::PruneSegment;
+use hanzo_evm_revm::{database::StateProviderDatabase, db::State};
+use hanzo_evm_storage_api::{StateProviderBox, StateProviderFactory};
+use revm_inspectors::tracing::{TracingInspector, TracingInspectorConfig};
+use tracing::{debug, info};
+
+/// The database the blocks of a committed chain are traced on.
+type TraceDb = State<StateProviderDatabase<StateProviderBox>>;
+
+/// Runs the trace index `ExEx`.
+///
+/// The notifications start after the head of the index, so the index catches up after a restart.
+/// On the first start, the index is backfilled from genesis, or from the prune boundary if the
+/// history of the node is pruned. Reverted blocks are unwound before committed blocks are traced
+/// and inserted.
+pub async fn trace_index_exex<Node>(
+    mut ctx: ExExContext<Node>,
+    index: TraceIndex,
+) -> eyre::Result<()>
+where
+    Node: FullNodeComponents,
+    TracingInspector: for<'a> InspectorFor<Node::Evm, &'a mut TraceDb>,
+{
+    let head = match index.head()? {
+        Some(head) => head,
+        // tracing a block requires the state of its parent
+        None => prune_boundary(
+            ctx.provider(),
+            &[PruneSegment::AccountHistory, PruneSegment::StorageHistory],
+        )?,
+    };
+    ctx.set_notifications_with_head(ExExHead::new(head));
+
+    while let Some(notification) = ctx.notifications.try_next().await? {
+        if let Some(reverted) = notification.reverted_chain() {
+            let unwind_to = reverted.first().number().saturating_sub(1);
+            debug!(target: "exex::trace_index", range = ?reverted.range(), "Unwinding trace index");
+            index.unwind_above(unwind_to)?;
+        }
+
+        if let Some(committed) = notification.committed_chain() {
+            let blocks = trace_chain(ctx.hanzo_evm_config(), ctx.provider(), &committed)?;
+            index.insert_blocks(blocks)?;
+            info!(target: "exex::trace_index", range = ?committed.range(), "Indexed blocks");
+            ctx.events.send(ExExEvent::FinishedHeight(committed.tip().num_hash()))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Traces all blocks of the chain on top of the state of the parent of its first block.
+fn trace_chain<Node>(
+    evm_config: &Node::Evm,
+    provider: &Node::Provider,
+    chain: &Chain<PrimitivesTy<Node::Types>>,
+) -> eyre::Result<Vec<(BlockNumHash, TracedAddresses)>>
+where
+    Node: FullNodeComponents,
+    TracingInspector: for<'a> InspectorFor<Node::Evm, &'a mut TraceDb>,
+{
+    let state = provider.state_by_block_hash(chain.first().parent_hash())?;
+    let mut db = State::builder().with_database(StateProviderDatabase::new(state)).build();
+
+    chain
+        .blocks_iter()
+        .map(|block| Ok((block.num_hash(), trace_block_addresses(evm_config, &mut db, block)?)))
+        .collect()
+}
+
+/// Executes the block with a [`TracingInspector`] and returns the addresses of all call frames of
+/// its transactions.
+///
+/// The block beneficiary is always included because it is the target of the block reward traces.
+/// The state changes of the block are committed to the database, so consecutive blocks can be
+/// traced on the same database.
+pub fn trace_block_addresses<E, DB>(
+    evm_config: &E,
+    db: &mut State<DB>,
+    block: &RecoveredBlock<<E::Primitives as NodePrimitives>::Block>,
+) -> eyre::Result<TracedAddresses>
+where
+    E: ConfigureEvm,
+    DB: Database,
+    TracingInspector: for<'a> InspectorFor<E, &'a mut State<DB>>,
+{
+    let mut addresses = TracedAddresses::default();
+    addresses.from.insert(block.header().beneficiary());
+    addresses.to.insert(block.header().beneficiary());
+
+    let evm_env = evm_config.evm_env(block.header())?;
+    let inspector = TracingInspector::new(TracingInspectorConfig::default_parity());
+    let evm = evm_config.evm_with_env_and_inspector(&mut *db, evm_env, inspector);
+    let ctx = evm_config.context_for_block(block.sealed_block())?;
+    let mut executor = evm_config.create_executor(evm, ctx);
+
+    executor.apply_pre_execution_changes()?;
+    // system calls are not part of any transaction trace
+    executor.evm_mut().inspector_mut().fuse();
+
+    for tx in block.transactions_recovered() {
+        executor.execute_transaction(tx)?;
+
+        let inspector = executor.evm_mut().inspector_mut();
+        for node in inspector.traces().nodes() {
+            let trace = &node.trace;
+            addresses.from.insert(trace.caller);
+            addresses.to.insert(trace.address);
+            if let Some(refund_target) = trace.selfdestruct_refund_target {
+                addresses.from.insert(trace.address);
+                addresses.to.insert(refund_target);
+            }
+        }
+        inspector.fuse();
+    }
+
+    executor.apply_post_execution_changes()?;
+    Ok(addresses)
+}
@@ -0,0 +1,206 @@
+use crate::tables::TraceIndexTables;
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{Address, BlockNumber};
+use hanzo_evm_exex_index::BlockIndex;
+use hanzo_evm_provider::ProviderResult;
+use hanzo_evm_storage_api::{TraceIndexReader, TracedAddressRole};
+use std::{collections::BTreeSet, ops::RangeInclusive, path::Path};
+
+/// The addresses that appeared in the call frames of a block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TracedAddresses {
+    /// Addresses indexed with [`TracedAddressRole::From`].
+    pub from: BTreeSet<Address>,
+    /// Addresses indexed with [`TracedAddressRole::To`].
+    pub to: BTreeSet<Address>,
+}
+
+/// A persistent index of the blocks in which an address appeared in any call frame.
+///
+/// Blocks must be inserted in ascending order without gaps, see [`TraceIndex::insert_blocks`]. The
+/// first indexed block is the lower bound of the index, it is above genesis if the index was
+/// started on a node with pruned history.
+#[derive(Debug, Clone)]
+pub struct TraceIndex {
+    index: BlockIndex<TraceIndexTables>,
+}
+
+impl TraceIndex {
+    /// Opens the index database at the given path, creating it if it doesn't exist.
+    pub fn open(path: impl AsRef<Path>) -> eyre::Result<Self> {
+        Ok(Self { index: BlockIndex::open(path)? })
+    }
+
+    /// Returns the highest indexed block, or `None` if the index is empty.
+    pub fn head(&self) -> ProviderResult<Option<BlockNumHash>> {
+        self.index.head()
+    }
+
+    /// Returns the range of indexed blocks, or `None` if the index is empty.
+    pub fn range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.index.range()
+    }
+
+    /// Inserts the given blocks into the index.
+    ///
+    /// Blocks at or below the current head are skipped, so a block that is delivered twice is
+    /// only indexed once. The first block of an empty index becomes its lower bound, after that
+    /// every block must directly follow the head.
+    pub fn insert_blocks(
+        &self,
+        blocks: impl IntoIterator<Item = (BlockNumHash, TracedAddresses)>,
+    ) -> ProviderResult<()> {
+        self.index.insert_blocks(
+            blocks.into_iter().map(|(block, addresses)| (block, addresses.from, addresses.to)),
+        )
+    }
+
+    /// Removes all blocks above the given block from the index.
+    pub fn unwind_above(&self, block: BlockNumber) -> ProviderResult<()> {
+        self.index.unwind_above(block)
+    }
+}
+
+impl TraceIndexReader for TraceIndex {
+    fn trace_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.range()
+    }
+
+    fn traced_address_blocks(
+        &self,
+        address: Address,
+        role: TracedAddressRole,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        match role {
+            TracedAddressRole::From => self.index.first_key_blocks(address, range),
+            TracedAddressRole::To => self.index.second_key_blocks(address, range),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use hanzo_evm_db::models::sharded_key::NUM_OF_INDICES_IN_SHARD;
+
+    fn block(number: BlockNumber) -> BlockNumHash {
+        BlockNumHash::new(number, B256::with_last_byte(number as u8))
+    }
+
+    fn addresses(from: &[Address], to: &[Address]) -> TracedAddresses {
+        TracedAddresses { from: from.iter().copied().collect(), to: to.iter().copied().collect() }
+    }
+
+    #[test]
+    fn insert_and_unwind() {
+        let dir = tempfile::tempdir().unwrap();
+        let index = TraceIndex::open(dir.path()).unwrap();
+        let (alice, bob) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        assert_eq!(index.head().unwrap(), None);
+
+        index
+            .insert_blocks([
+                (block(0), addresses(&[], &[])),
+                (block(1), addresses(&[alice], &[bob])),
+                (block(2), addresses(&[bob], &[alice])),
+                (block(3), addresses(&[alice], &[alice])),
+            ])
+            .unwrap();
+        assert_eq!(index.head().unwrap(), Some(block(3)));
+        assert_eq!(
+            index.traced_address_blocks(alice, TracedAddressRole::From, 0..=3).unwrap(),
+            vec![1, 3]
+        );
+        assert_eq!(
+            index.traced_address_blocks(alice, TracedAddressRole::To, 0..=2).unwrap(),
+            vec![2]
+        );
+        assert_eq!(
+            index.traced_address_blocks(bob, TracedAddressRole::From, 3..=10).unwrap(),
+            Vec::<BlockNumber>::new()
+        );
+
+        // already indexed blocks are skipped
+        index.insert_blocks([(block(3), addresses(&[bob], &[]))]).unwrap();
+        assert_eq!(
+            index.traced_address_blocks(bob, TracedAddressRole::From, 0..=3).unwrap(),
+            vec![2]
+        );
+
+        index.unwind_above(1).unwrap();
+        assert_eq!(index.head().unwrap(), Some(block(1)));
+        assert_eq!(
+            index.traced_address_blocks(alice, TracedAddressRole::From, 0..=3).unwrap(),
+            vec![1]
+        );
+        assert_eq!(
+            index.traced_address_blocks(alice, TracedAddressRole::To, 0..=3).unwrap(),
+            Vec::<BlockNumber>::new()
+        );
+        assert_eq!(
+            index.traced_address_blocks(bob, TracedAddressRole::To, 0..=3).unwrap(),
+            vec![1]
+        );
+    }
+
+    #[test]
+    fn lower_bound() {
+        let dir = tempfile::tempdir().unwrap();
+        let index = TraceIndex::open(dir.path()).unwrap();
+        let alice = Address::repeat_byte(1);
+        assert_eq!(index.range().unwrap(), None);
+
+        // the index starts at the prune boundary
+        index
+            .insert_blocks((5..=7).map(|number| (block(number), addresses(&[alice], &[]))))
+            .unwrap();
+        assert_eq!(index.range().unwrap(), Some(5..=7));
+        assert_eq!(index.trace_index_range().unwrap(), Some(5..=7));
+
+        // a gap would break the coverage of the index
+        assert!(index.insert_blocks([(block(9), addresses(&[alice], &[]))]).is_err());
+        assert_eq!(index.range().unwrap(), Some(5..=7));
+
+        index.insert_blocks([(block(8), addresses(&[alice], &[]))]).unwrap();
+        assert_eq!(index.range().unwrap(), Some(5..=8));
+
+        index.unwind_above(6).unwrap();
+        assert_eq!(index.range().unwrap(), Some(5..=6));
+
+        // unwinding below the lower bound empties the index
+        index.unwind_above(4).unwrap();
+        assert_eq!(index.range().unwrap(), None);
+        assert_eq!(
+            index.traced_address_blocks(alice, TracedAddressRole::From, 0..=10).unwrap(),
+            Vec::<BlockNumber>::new()
+        );
+    }
+
+    #[test]
+    fn sharded_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let index = TraceIndex::open(dir.path()).unwrap();
+        let alice = Address::repeat_byte(1);
+
+        let total = NUM_OF_INDICES_IN_SHARD as u64 * 2 + 10;
+        index
+            .insert_blocks((0..total).map(|number| (block(number), addresses(&[alice], &[]))))
+            .unwrap();
+        assert_eq!(
+            index.traced_address_blocks(alice, TracedAddressRole::From, 0..=total).unwrap(),
+            (0..total).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            index.traced_address_blocks(alice, TracedAddressRole::From, 1990..=2010).unwrap(),
+            (1990..=2010).collect::<Vec<_>>()
+        );
+
+        index.unwind_above(1500).unwrap();
+        assert_eq!(
+            index.traced_address_blocks(alice, TracedAddressRole::From, 0..=total).unwrap(),
+            (0..=1500).collect::<Vec<_>>()
+        );
+    }
+}
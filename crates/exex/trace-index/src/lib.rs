@@ -0,0 +1,53 @@
+//! An execution extension that maintains a persistent index of the blocks in which an address
+//! appeared in any call frame, including internal calls.
+//!
+//! `trace_filter` and otterscan's `ots_searchTransactionsBefore`/`ots_searchTransactionsAfter`
+//! have to re-execute every block of the requested range to find the relevant traces. With the
+//! index they can jump straight to the blocks in which the requested addresses appeared and only
+//! trace those.
+//!
+//! The index is stored in a separate database and is kept in sync with the canonical chain by
+//! [`trace_index_exex`]. It covers the blocks from the first block whose parent state is still
+//! available, blocks below that bound are traced in full by the RPC handlers. [`TraceIndex`]
+//! implements [`TraceIndexReader`] and can be handed to the `trace` and `ots` RPC handlers.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! let index = TraceIndex::open(data_dir.data_dir().join("trace-index"))?;
+//!
+//! let handle = builder
+//!     .node(EthereumNode::default())
+//!     .install_exex("trace-index", {
+//!         let index = index.clone();
+//!         async move |ctx| Ok(trace_index_exex(ctx, index))
+//!     })
+//!     .extend_rpc_modules(move |ctx| {
+//!         let index = Arc::new(index);
+//!         let trace = ctx.registry.trace_api().with_trace_index(index.clone());
+//!         let ots = ctx.registry.otterscan_api().with_trace_index(index);
+//!         ctx.modules.replace_configured(trace.into_rpc())?;
+//!         ctx.modules.replace_configured(ots.into_rpc())?;
+//!         Ok(())
+//!     })
+//!     .launch()
+//!     .await?;
+//! ```
+//!
+//! [`TraceIndexReader`]: hanzo_evm_storage_api::TraceIndexReader
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/hanzoai/evm/main/assets/evm-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/hanzoai/evm/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+mod exex;
+pub use exex::{trace_block_addresses, trace_index_exex};
+
+mod index;
+pub use index::{TraceIndex, TracedAddresses};
+
+pub mod tables;
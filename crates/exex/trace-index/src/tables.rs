@@ -0,0 +1,105 @@
+//! Tables of the trace index database.
+
+use alloy_primitives::{Address, BlockNumber};
+use hanzo_evm_db::{
+    models::ShardedKey,
+    table::{Table, TableInfo},
+    BlockNumberList, TableSet,
+};
+use hanzo_evm_exex_index::{BlockIndexTables, StoredIndexedBlock};
+
+/// Stores the blocks in which an address was the caller of a call frame, the creator of a
+/// contract or a self-destructed contract.
+///
+/// Uses the same sharding as the `AccountsHistory` table of the node database.
+#[derive(Debug)]
+pub struct CallFromHistory;
+
+impl Table for CallFromHistory {
+    const NAME: &'static str = "CallFromHistory";
+    const DUPSORT: bool = false;
+
+    type Key = ShardedKey<Address>;
+    type Value = BlockNumberList;
+}
+
+/// Stores the blocks in which an address was the callee of a call frame, a created contract or
+/// the refund target of a self-destruct.
+///
+/// Uses the same sharding as the `AccountsHistory` table of the node database.
+#[derive(Debug)]
+pub struct CallToHistory;
+
+impl Table for CallToHistory {
+    const NAME: &'static str = "CallToHistory";
+    const DUPSORT: bool = false;
+
+    type Key = ShardedKey<Address>;
+    type Value = BlockNumberList;
+}
+
+/// Stores the hash and the indexed addresses of every indexed block.
+///
+/// The first entry is the lower bound and the last entry is the head of the index. The addresses
+/// are required to unwind the history tables without re-executing the unwound blocks.
+#[derive(Debug)]
+pub struct TracedBlocks;
+
+impl Table for TracedBlocks {
+    const NAME: &'static str = "TracedBlocks";
+    const DUPSORT: bool = false;
+
+    type Key = BlockNumber;
+    type Value = StoredTracedBlock;
+}
+
+/// A table of the trace index database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceIndexTables {
+    /// The [`CallFromHistory`] table.
+    CallFromHistory,
+    /// The [`CallToHistory`] table.
+    CallToHistory,
+    /// The [`TracedBlocks`] table.
+    TracedBlocks,
+}
+
+impl TraceIndexTables {
+    /// All the tables of the trace index database.
+    pub const ALL: &'static [Self] =
+        &[Self::CallFromHistory, Self::CallToHistory, Self::TracedBlocks];
+}
+
+impl TableInfo for TraceIndexTables {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::CallFromHistory => CallFromHistory::NAME,
+            Self::CallToHistory => CallToHistory::NAME,
+            Self::TracedBlocks => TracedBlocks::NAME,
+        }
+    }
+
+    fn is_dupsort(&self) -> bool {
+        false
+    }
+}
+
+impl TableSet for TraceIndexTables {
+    fn tables() -> Box<dyn Iterator<Item = Box<dyn TableInfo>>> {
+        Box::new(Self::ALL.iter().map(|table| Box::new(*table) as Box<dyn TableInfo>))
+    }
+}
+
+impl BlockIndexTables for TraceIndexTables {
+    type First = Address;
+    type Second = Address;
+    type Blocks = TracedBlocks;
+    type FirstHistory = CallFromHistory;
+    type SecondHistory = CallToHistory;
+}
+
+/// An indexed block, the value of the [`TracedBlocks`] table.
+///
+/// The `first` addresses were indexed in [`CallFromHistory`] and the `second` addresses in
+/// [`CallToHistory`] for this block.
+pub type StoredTracedBlock = StoredIndexedBlock<Address, Address>;
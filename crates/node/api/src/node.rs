@@ -6,7 +6,7 @@ use hanzo_evm_basic_payload_builder::PayloadBuilder;
 use hanzo_evm_consensus::FullConsensus;
 use hanzo_evm_db_api::{database_metrics::DatabaseMetrics, Database};
 use hanzo_evm_engine_primitives::{ConsensusEngineEvent, ConsensusEngineHandle};
-use hanzo_evm_execution::ConfigureEvm;
+use hanzo_evm_execution::{precompile_cache::PrecompileCacheMap, ConfigureEvm, SpecFor};
 use hanzo_evm_network_api::FullNetwork;
 use hanzo_evm_node_core::node_config::NodeConfig;
 use hanzo_evm_node_types::{NodeTypes, NodeTypesWithDBAdapter, TxTy};
@@ -115,6 +115,8 @@ pub struct AddOnsContext<'a, N: FullNodeComponents> {
     pub engine_events: EventSender<ConsensusEngineEvent<<N::Types as NodeTypes>::Primitives>>,
     /// JWT secret for the node.
     pub jwt_secret: JwtSecret,
+    /// Precompile cache of the engine, which can be shared with RPC execution.
+    pub precompile_cache: PrecompileCacheMap<SpecFor<N::Evm>>,
 }
 
 /// Customizable node add-on types.
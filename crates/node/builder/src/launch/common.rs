@@ -65,7 +65,10 @@ use hanzo_evm_node_metrics::{
     version::VersionInfo,
 };
 use hanzo_evm_provider::{
-    providers::{NodeTypesForProvider, ProviderNodeTypes, RocksDBProvider, StaticFileProvider},
+    providers::{
+        NodeTypesForProvider, ProviderNodeTypes, RocksDBProvider, StaticFileProvider,
+        StaticFileTier,
+    },
     BlockHashReader, BlockNumReader, ProviderError, ProviderFactory, ProviderResult,
     RocksDBProviderFactory, StageCheckpointReader, StaticFileProviderBuilder,
     StaticFileProviderFactory,
@@ -453,7 +456,7 @@ impl<R, ChainSpec: EthChainSpec> LaunchContextWith<Attached<WithConfigs<ChainSpe
     }
 
     /// Returns the [`MiningMode`] intended for --dev mode.
-    pub fn dev_mining_mode<Pool>(&self, pool: Pool) -> MiningMode<Pool>
+    pub fn dev_mining_mode<Pool>(&self, pool: Pool) -> eyre::Result<MiningMode<Pool>>
     where
         Pool: TransactionPool + Unpin,
     {
@@ -481,12 +484,20 @@ where
         let static_files_config = &self.toml_config().static_files;
         static_files_config.validate()?;
 
-        // Apply per-segment blocks_per_file configuration
+        // Apply per-segment blocks_per_file, state snapshot and storage tier configuration
         let static_file_provider =
             StaticFileProviderBuilder::read_write(self.data_dir().static_files())
                 .with_metrics()
                 .with_blocks_per_file_for_segments(&static_files_config.as_blocks_per_file_map())
                 .with_genesis_block_number(self.chain_spec().genesis().number.unwrap_or_default())
+                .with_state_snapshot_interval(static_files_config.state_snapshot_interval)
+                .with_tiers(
+                    static_files_config
+                        .tiers
+                        .iter()
+                        .map(|tier| StaticFileTier::new(&tier.path, tier.min_age))
+                        .collect(),
+                )
                 .build()?;
 
         // Initialize RocksDB provider with metrics, statistics, and default tables
@@ -292,6 +292,7 @@ where
             let beacon_engine_handle = handle.node.add_ons_handle.beacon_engine_handle.clone();
             let pool = handle.node.pool.clone();
             let payload_builder_handle = handle.node.payload_builder_handle.clone();
+            let local_miner = handle.node.add_ons_handle.local_miner.clone();
 
             let builder = if let Some(builder) = local_payload_attributes_builder {
                 Either::Left(builder)
@@ -305,19 +306,22 @@ where
                 Either::Right(builder)
             };
 
-            let dev_mining_mode =
-                mining_mode.unwrap_or_else(|| handle.node.config.dev_mining_mode(pool));
-            handle.node.task_executor.spawn_critical_task("local engine", async move {
-                LocalMiner::new(
-                    blockchain_db,
-                    builder,
-                    beacon_engine_handle,
-                    dev_mining_mode,
-                    payload_builder_handle,
-                )
-                .run()
-                .await
-            });
+            let dev_mining_mode = match mining_mode {
+                Some(mining_mode) => mining_mode,
+                None => handle.node.config.dev_mining_mode(pool.clone())?,
+            };
+            let mut miner = LocalMiner::new(
+                blockchain_db,
+                builder,
+                beacon_engine_handle,
+                dev_mining_mode,
+                payload_builder_handle,
+            )
+            .with_handle(&local_miner, pool);
+
+            // A failed scenario aborts the launch, afterwards the miner only logs its errors
+            miner.run_scenario().await?;
+            handle.node.task_executor.spawn_critical_task("local engine", miner.run());
         }
 
         Ok(handle)
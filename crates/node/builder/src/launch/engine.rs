@@ -15,28 +15,30 @@ use reth_engine_tree::{
     chain::{ChainEvent, FromOrchestrator},
     engine::{EngineApiKind, EngineApiRequest, EngineRequestHandler},
     launch::build_engine_orchestrator,
-    tree::TreeConfig,
+    tree::{precompile_cache::PrecompileCacheMap, TreeConfig},
 };
 use hanzo_evm_engine_util::EngineMessageStreamExt;
 use hanzo_evm_exex::ExExManagerHandle;
 use hanzo_evm_network::{types::BlockRangeUpdate, NetworkSyncUpdater, SyncState};
 use hanzo_evm_network_api::BlockDownloaderProvider;
 use hanzo_evm_node_api::{
-    BuiltPayload, ConsensusEngineHandle, FullNodeTypes, NodeTypes, NodeTypesWithDBAdapter,
+    BuiltPayload, ConfigureEvm, ConsensusEngineHandle, FullNodeTypes, NodeTypes,
+    NodeTypesWithDBAdapter,
 };
 use hanzo_evm_node_core::{
     dirs::{ChainPath, DataDirPath},
     exit::NodeExitFuture,
     primitives::Head,
+    version::version_metadata,
 };
 use hanzo_evm_node_events::node;
 use hanzo_evm_provider::{
     providers::{BlockchainProvider, NodeTypesForProvider},
-    BlockNumReader, StorageSettingsCache,
+    BlockNumReader, HeaderProvider, ProviderError, StorageSettingsCache,
 };
 use hanzo_evm_tasks::TaskExecutor;
 use hanzo_evm_tokio_util::EventSender;
-use hanzo_evm_tracing::tracing::{debug, error, info};
+use hanzo_evm_tracing::tracing::{debug, error, info, warn};
 use hanzo_evm_trie_db::ChangesetCache;
 use std::{future::Future, pin::Pin, sync::Arc};
 use tokio::sync::{mpsc::unbounded_channel, oneshot};
@@ -181,12 +183,63 @@ impl EngineNodeLauncher {
         // extract the jwt secret from the args if possible
         let jwt_secret = ctx.auth_jwt_secret()?;
 
+        // The precompile cache is shared by all engine validators, and optionally the RPC
+        let precompile_cache = if engine_tree_config.persist_precompile_cache() &&
+            !engine_tree_config.precompile_cache_disabled()
+        {
+            let path = ctx.data_dir().precompile_cache();
+            let client_version = version_metadata().short_version.to_string();
+            let chain_id = ctx.chain_spec().chain().id();
+
+            // The persisted cache is tagged with the spec of the best block
+            let provider = ctx.blockchain_db().clone();
+            let evm_config = ctx.components().hanzo_evm_config().clone();
+            let best_spec = move || -> eyre::Result<_> {
+                let number = provider.best_block_number()?;
+                let header = provider
+                    .header_by_number(number)?
+                    .ok_or(ProviderError::HeaderNotFound(number.into()))?;
+                Ok(evm_config.evm_env(&header)?.cfg_env.spec)
+            };
+
+            let loaded = PrecompileCacheMap::load(&path, &client_version, chain_id, &best_spec()?);
+            let precompile_cache = match loaded {
+                Ok(precompile_cache) => precompile_cache,
+                Err(err) => {
+                    if err.kind() != std::io::ErrorKind::NotFound {
+                        warn!(target: "evm::cli", %err, ?path, "Discarding persisted precompile cache");
+                    }
+                    PrecompileCacheMap::default()
+                }
+            };
+
+            let cache = precompile_cache.clone();
+            ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
+                "precompile cache persistence task",
+                |shutdown| async move {
+                    let graceful_guard = shutdown.await;
+                    let saved = best_spec().and_then(|spec| {
+                        Ok(cache.save(&path, &client_version, chain_id, &spec)?)
+                    });
+                    if let Err(err) = saved {
+                        error!(target: "evm::cli", %err, ?path, "Failed to persist precompile cache");
+                    }
+                    drop(graceful_guard)
+                },
+            );
+
+            precompile_cache
+        } else {
+            PrecompileCacheMap::default()
+        };
+
         let add_ons_ctx = AddOnsContext {
             node: ctx.node_adapter().clone(),
             config: ctx.node_config(),
             beacon_engine_handle: beacon_engine_handle.clone(),
             jwt_secret,
             engine_events: event_sender.clone(),
+            precompile_cache,
         };
         let validator_builder = add_ons.engine_validator_builder();
 
@@ -269,6 +322,7 @@ impl EngineNodeLauncher {
             engine_events,
             beacon_engine_handle,
             engine_shutdown: _,
+            local_miner,
         } = add_ons.launch_add_ons(add_ons_ctx).await?;
 
         // Create engine shutdown handle
@@ -394,6 +448,7 @@ impl EngineNodeLauncher {
                 engine_events,
                 beacon_engine_handle,
                 engine_shutdown,
+                local_miner,
             },
         };
         // Notify on node started
@@ -56,6 +56,9 @@ where
 /// configuration in the node config. It supports:
 /// - Witness hooks for capturing block witness data
 /// - Healthy node verification via RPC
+/// - Trace hooks for writing geth-style call and prestate traces
+/// - Webhook hooks for posting a report to `--debug.invalid-block-webhook-url`
+/// - Fixture hooks for writing a blockchain test fixture for `testing/ef-tests`
 ///
 /// # Arguments
 /// * `config` - The node configuration containing debug settings
@@ -81,7 +84,10 @@ where
     E: hanzo_evm_execution::ConfigureEvm<Primitives = N> + Clone + 'static,
 {
     use hanzo_evm_engine_primitives::{InvalidBlockHooks, NoopInvalidBlockHook};
-    use hanzo_evm_invalid_block_hooks::InvalidBlockWitnessHook;
+    use hanzo_evm_invalid_block_hooks::{
+        InvalidBlockFixtureHook, InvalidBlockTraceHook, InvalidBlockWebhookHook,
+        InvalidBlockWitnessHook,
+    };
 
     let Some(ref hook) = config.debug.invalid_block_hook else {
         return Ok(Box::new(NoopInvalidBlockHook::default()))
@@ -104,6 +110,22 @@ where
                     output_directory,
                     healthy_node_rpc_client.clone(),
                 )),
+                InvalidBlockHookType::Trace => Box::new(InvalidBlockTraceHook::new(
+                    provider.clone(),
+                    hanzo_evm_config.clone(),
+                    output_directory,
+                )),
+                InvalidBlockHookType::Webhook => {
+                    let url = config.debug.invalid_block_webhook_url.clone().ok_or_eyre(
+                        "the webhook invalid block hook requires --debug.invalid-block-webhook-url",
+                    )?;
+                    Box::new(InvalidBlockWebhookHook::new(url, tokio::runtime::Handle::current()))
+                }
+                InvalidBlockHookType::Fixture => Box::new(InvalidBlockFixtureHook::new(
+                    provider.clone(),
+                    hanzo_evm_config.clone(),
+                    output_directory,
+                )),
                 InvalidBlockHookType::PreState | InvalidBlockHookType::Opcode => {
                     eyre::bail!("invalid block hook {hook:?} is not implemented yet")
                 }
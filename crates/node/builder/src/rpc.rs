@@ -1,7 +1,8 @@
 //! Builder support for rpc components.
 
 pub use jsonrpsee::server::middleware::rpc::{RpcService, RpcServiceBuilder};
-use reth_engine_tree::tree::WaitForCaches;
+use reth_engine_tree::tree::{precompile_cache::PrecompileCacheMap, WaitForCaches};
+use reth_primitives_traits::{Recovered, SealedHeader};
 pub use reth_engine_tree::tree::{BasicEngineValidator, EngineValidator};
 pub use reth_rpc_builder::{middleware::RethRpcMiddleware, Identity, Stack};
 pub use reth_trie_db::ChangesetCache;
@@ -10,15 +11,18 @@ use crate::{
     invalid_block_hook::InvalidBlockHookExt, ConfigureEngineEvm, ConsensusEngineEvent,
     ConsensusEngineHandle,
 };
+use alloy_consensus::BlockHeader;
 use alloy_rpc_types::engine::ClientVersionV1;
 use alloy_rpc_types_engine::ExecutionData;
 use jsonrpsee::{core::middleware::layer::Either, RpcModule};
 use parking_lot::Mutex;
 use hanzo_evm_chain_state::CanonStateSubscriptions;
+use hanzo_evm_engine_local::LocalMinerHandle;
+use hanzo_evm_execution::SpecFor;
 use hanzo_evm_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks, Hardforks};
 use hanzo_evm_node_api::{
     AddOnsContext, BlockTy, EngineApiValidator, EngineTypes, FullNodeComponents, FullNodeTypes,
-    NodeAddOns, NodeTypes, PayloadTypes, PayloadValidator, PrimitivesTy, TreeConfig,
+    HeaderTy, NodeAddOns, NodeTypes, PayloadTypes, PayloadValidator, PrimitivesTy, TreeConfig,
 };
 use hanzo_evm_node_core::{
     cli::config::EvmTransactionPoolConfig,
@@ -28,17 +32,23 @@ use hanzo_evm_node_core::{
 use hanzo_evm_payload_builder::{PayloadBuilderHandle, PayloadStore};
 use hanzo_evm_rpc::{
     eth::{core::EthRpcConverterFor, DevSigner, EthApiTypes, FullEthApiServer},
-    AdminApi,
+    AdminApi, AdminStorageApi, DevApi,
+};
+use hanzo_evm_rpc_api::{
+    eth::helpers::EthTransactions, AdminStorageApiServer, DevApiServer, IntoEngineApiRpcModule,
 };
-use hanzo_evm_rpc_api::{eth::helpers::EthTransactions, IntoEngineApiRpcModule};
 use hanzo_evm_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
     config::EvmRpcServerConfig,
-    RpcModuleBuilder, RpcRegistryInner, RpcServerConfig, RpcServerHandle, TransportRpcModules,
+    EvmRpcModule, RpcModuleBuilder, RpcRegistryInner, RpcServerConfig, RpcServerHandle,
+    TransportRpcModules,
 };
 use hanzo_evm_rpc_engine_api::{capabilities::EngineCapabilities, EngineApi};
 use hanzo_evm_rpc_eth_types::{cache::cache_new_blocks_task, EthConfig, EthStateCache};
 use hanzo_evm_tokio_util::EventSender;
+use hanzo_evm_transaction_pool::{
+    error::InvalidPoolTransactionError, BestTransactionsAttributes, PoolConsensusTx, TransactionPool,
+};
 use hanzo_evm_tracing::tracing::{debug, info};
 use std::{
     fmt::{self, Debug},
@@ -339,6 +349,10 @@ pub struct RpcHandle<Node: FullNodeComponents, EthApi: EthApiTypes> {
     pub beacon_engine_handle: ConsensusEngineHandle<<Node::Types as NodeTypes>::Payload>,
     /// Handle to trigger engine shutdown.
     pub engine_shutdown: EngineShutdown,
+    /// Handle to the local miner of a node in dev mode, used by the `dev_` namespace.
+    ///
+    /// The handle is only connected once the local miner is spawned.
+    pub local_miner: LocalMinerHandle,
 }
 
 impl<Node: FullNodeComponents, EthApi: EthApiTypes> Clone for RpcHandle<Node, EthApi> {
@@ -349,6 +363,7 @@ impl<Node: FullNodeComponents, EthApi: EthApiTypes> Clone for RpcHandle<Node, Et
             engine_events: self.engine_events.clone(),
             beacon_engine_handle: self.beacon_engine_handle.clone(),
             engine_shutdown: self.engine_shutdown.clone(),
+            local_miner: self.local_miner.clone(),
         }
     }
 }
@@ -389,6 +404,11 @@ impl<Node: FullNodeComponents, EthApi: EthApiTypes> RpcHandle<Node, EthApi> {
         &self.beacon_engine_handle
     }
 
+    /// Returns the handle to the local miner of a node in dev mode.
+    pub const fn local_miner(&self) -> &LocalMinerHandle {
+        &self.local_miner
+    }
+
     /// Returns the consensus engine events sender.
     pub const fn consensus_engine_events(
         &self,
@@ -498,6 +518,7 @@ struct RpcSetupContext<'a, Node: FullNodeComponents, EthApi: EthApiTypes> {
     on_rpc_started: Box<dyn OnRpcStarted<Node, EthApi>>,
     engine_events: EventSender<ConsensusEngineEvent<<Node::Types as NodeTypes>::Primitives>>,
     engine_handle: ConsensusEngineHandle<<Node::Types as NodeTypes>::Payload>,
+    local_miner: LocalMinerHandle,
 }
 
 /// Node add-ons containing RPC server configuration, with customizable eth API handler.
@@ -854,6 +875,7 @@ where
             on_rpc_started,
             engine_events,
             engine_handle,
+            local_miner: _,
         } = setup_ctx;
 
         let server_config = config
@@ -925,6 +947,7 @@ where
             on_rpc_started,
             engine_events,
             engine_handle,
+            local_miner,
         } = setup_ctx;
 
         let server_config = config
@@ -966,6 +989,7 @@ where
             engine_events,
             beacon_engine_handle: engine_handle,
             engine_shutdown: EngineShutdown::default(),
+            local_miner,
         })
     }
 
@@ -981,7 +1005,14 @@ where
         let Self { eth_api_builder, engine_api_builder, hooks, .. } = self;
 
         let engine_api = engine_api_builder.build_engine_api(&ctx).await?;
-        let AddOnsContext { node, config, beacon_engine_handle, jwt_secret, engine_events } = ctx;
+        let AddOnsContext {
+            node,
+            config,
+            beacon_engine_handle,
+            jwt_secret,
+            engine_events,
+            precompile_cache,
+        } = ctx;
 
         info!(target: "evm::cli", "Engine API handler initialized");
 
@@ -1001,11 +1032,14 @@ where
         );
 
         let eth_config = config.rpc.eth_config().max_batch_size(config.txpool.max_batch_size());
+        let share_precompile_cache =
+            config.engine.share_precompile_cache && !config.engine.precompile_cache_disabled;
         let ctx = EthApiCtx {
             components: &node,
             config: eth_config,
             cache,
             engine_handle: beacon_engine_handle.clone(),
+            precompile_cache: share_precompile_cache.then_some(precompile_cache),
         };
         let eth_api = eth_api_builder.build_eth_api(ctx).await?;
 
@@ -1022,10 +1056,32 @@ where
             .with_consensus(node.consensus().clone())
             .build_with_auth_server(module_config, engine_api, eth_api, engine_events.clone());
 
-        // in dev mode we generate 20 random dev-signer accounts
+        // in dev mode we generate 20 random dev-signer accounts and control the local miner
+        // through the `dev_` namespace
+        let local_miner = LocalMinerHandle::new();
         if config.dev.dev {
             let signers = DevSigner::from_mnemonic(config.dev.dev_mnemonic.as_str(), 20);
             registry.eth_api().signers().write().extend(signers);
+
+            modules.merge_if_module_configured(
+                EvmRpcModule::Dev,
+                DevApi::new(local_miner.clone()).into_rpc(),
+            )?;
+        }
+
+        // storage snapshots are written to the node's filesystem, so they are only served next to
+        // the other `admin_` methods on the local IPC transport
+        if modules.module_config().contains_ipc(&EvmRpcModule::Admin) {
+            let snapshot_dir =
+                config.rpc.rpc_snapshot_dir.clone().unwrap_or_else(|| config.datadir().snapshots());
+            modules.merge_ipc(
+                AdminStorageApi::new(
+                    node.provider().clone(),
+                    node.task_executor().clone(),
+                    snapshot_dir,
+                )
+                .into_rpc(),
+            )?;
         }
 
         let mut registry = RpcRegistry { registry };
@@ -1056,6 +1112,7 @@ where
             on_rpc_started,
             engine_events,
             engine_handle: beacon_engine_handle,
+            local_miner,
         })
     }
 
@@ -1163,7 +1220,7 @@ where
 /// `EthApiCtx` struct
 /// This struct is used to pass the necessary context to the `EthApiBuilder` to build the `EthApi`.
 #[derive(Debug)]
-pub struct EthApiCtx<'a, N: FullNodeTypes> {
+pub struct EthApiCtx<'a, N: FullNodeComponents> {
     /// Reference to the node components
     pub components: &'a N,
     /// Eth API configuration
@@ -1172,6 +1229,8 @@ pub struct EthApiCtx<'a, N: FullNodeTypes> {
     pub cache: EthStateCache<PrimitivesTy<N::Types>>,
     /// Handle to the beacon consensus engine
     pub engine_handle: ConsensusEngineHandle<<N::Types as NodeTypes>::Payload>,
+    /// Precompile cache of the engine, if it is shared with RPC execution
+    pub precompile_cache: Option<PrecompileCacheMap<SpecFor<N::Evm>>>,
 }
 
 impl<'a, N: FullNodeComponents<Types: NodeTypes<ChainSpec: Hardforks + EthereumHardforks>>>
@@ -1194,6 +1253,10 @@ impl<'a, N: FullNodeComponents<Types: NodeTypes<ChainSpec: Hardforks + EthereumH
             .raw_tx_forwarder(self.config.raw_tx_forwarder)
             .evm_memory_limit(self.config.rpc_evm_memory_limit)
             .force_blob_sidecar_upcasting(self.config.force_blob_sidecar_upcasting)
+            .apply(|builder| match self.precompile_cache {
+                Some(precompile_cache) => builder.precompile_cache(precompile_cache),
+                None => builder,
+            })
     }
 }
 
@@ -1342,8 +1405,9 @@ where
         let validator = self.payload_validator_builder.build(ctx).await?;
         let data_dir = ctx.config.datadir.clone().resolve_datadir(ctx.config.chain.chain());
         let invalid_block_hook = ctx.create_invalid_block_hook(&data_dir).await?;
+        let speculative_execution = tree_config.speculative_execution();
 
-        Ok(BasicEngineValidator::new(
+        let mut engine_validator = BasicEngineValidator::new(
             ctx.node.provider().clone(),
             std::sync::Arc::new(ctx.node.consensus().clone()),
             ctx.node.hanzo_evm_config().clone(),
@@ -1352,10 +1416,55 @@ where
             invalid_block_hook,
             changeset_cache,
             ctx.node.task_executor().clone(),
-        ))
+        )
+        .with_precompile_cache_map(ctx.precompile_cache.clone());
+
+        if speculative_execution {
+            // predict the next block from the best pending transactions that fit into the gas
+            // limit of the head
+            let pool = ctx.node.pool().clone();
+            engine_validator = engine_validator.with_speculative_transactions(
+                move |head: &SealedHeader<HeaderTy<Node::Types>>| {
+                    best_transactions_within(&pool, head.gas_limit())
+                },
+            );
+        }
+
+        Ok(engine_validator)
     }
 }
 
+/// Returns the best pending transactions of the pool that fit into the given gas limit.
+///
+/// A transaction that doesn't fit is skipped together with its descendants, smaller transactions
+/// after it may still fit.
+fn best_transactions_within<Pool: TransactionPool>(
+    pool: &Pool,
+    gas_limit: u64,
+) -> Vec<Recovered<PoolConsensusTx<Pool>>> {
+    let info = pool.block_info();
+    let attributes = BestTransactionsAttributes::new(
+        info.pending_basefee,
+        info.pending_blob_fee.map(|fee| u64::try_from(fee).unwrap_or(u64::MAX)),
+    );
+
+    let mut gas_left = gas_limit;
+    let mut transactions = Vec::new();
+    let mut best = pool.best_transactions_with_attributes(attributes);
+    while let Some(tx) = best.next() {
+        let Some(remaining) = gas_left.checked_sub(tx.gas_limit()) else {
+            best.mark_invalid(
+                &tx,
+                &InvalidPoolTransactionError::ExceedsGasLimit(tx.gas_limit(), gas_left),
+            );
+            continue
+        };
+        gas_left = remaining;
+        transactions.push(tx.to_consensus());
+    }
+    transactions
+}
+
 /// Builder for basic [`EngineApi`] implementation.
 ///
 /// This provides a basic default implementation for opstack and ethereum engine API via
@@ -1487,3 +1596,29 @@ pub struct EngineShutdownRequest {
     /// Channel to signal shutdown completion.
     pub done_tx: oneshot::Sender<()>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hanzo_evm_transaction_pool::{
+        test_utils::{testing_pool, MockTransaction},
+        PoolTransaction,
+    };
+
+    #[tokio::test]
+    async fn best_transactions_within_skips_transactions_that_dont_fit() {
+        let pool = testing_pool();
+        let fits = MockTransaction::legacy().with_gas_price(300).with_gas_limit(30_000);
+        let too_large = MockTransaction::legacy().with_gas_price(200).with_gas_limit(30_000);
+        let fits_remaining = MockTransaction::legacy().with_gas_price(100).with_gas_limit(20_000);
+        for tx in [fits.clone(), too_large, fits_remaining.clone()] {
+            pool.add_external_transaction(tx).await.unwrap();
+        }
+
+        let hashes = best_transactions_within(&pool, 50_000)
+            .iter()
+            .map(|tx| *tx.tx_hash())
+            .collect::<Vec<_>>();
+        assert_eq!(hashes, vec![*fits.hash(), *fits_remaining.hash()]);
+    }
+}
@@ -97,6 +97,11 @@ pub struct DebugArgs {
     )]
     pub healthy_node_rpc_url: Option<String>,
 
+    /// The URL that the `webhook` invalid block hook posts a JSON report of every invalid block
+    /// to.
+    #[arg(long = "debug.invalid-block-webhook-url", help_heading = "Debug", value_name = "URL")]
+    pub invalid_block_webhook_url: Option<String>,
+
     /// The URL of the ethstats server to connect to.
     /// Example: `nodename:secret@host:port`
     #[arg(long = "ethstats", help_heading = "Debug")]
@@ -125,6 +130,7 @@ impl Default for DebugArgs {
             engine_api_store: None,
             invalid_block_hook: Some(InvalidBlockSelection::default()),
             healthy_node_rpc_url: None,
+            invalid_block_webhook_url: None,
             ethstats: None,
             startup_sync_state_idle: false,
         }
@@ -303,6 +309,12 @@ pub enum InvalidBlockHookType {
     PreState,
     /// An opcode trace value enum
     Opcode,
+    /// A geth-style call and prestate trace value enum
+    Trace,
+    /// A webhook report value enum
+    Webhook,
+    /// A blockchain test fixture value enum
+    Fixture,
 }
 
 impl FromStr for InvalidBlockHookType {
@@ -313,6 +325,9 @@ impl FromStr for InvalidBlockHookType {
             "witness" => Self::Witness,
             "prestate" => Self::PreState,
             "opcode" => Self::Opcode,
+            "trace" => Self::Trace,
+            "webhook" => Self::Webhook,
+            "fixture" => Self::Fixture,
             _ => return Err(ParseError::VariantNotFound),
         })
     }
@@ -421,4 +436,26 @@ mod tests {
         .args;
         assert_eq!(args, expected_args);
     }
+
+    #[test]
+    fn test_parse_invalid_block_webhook_args() {
+        let expected_args = DebugArgs {
+            invalid_block_hook: Some(InvalidBlockSelection::from([
+                InvalidBlockHookType::Trace,
+                InvalidBlockHookType::Webhook,
+                InvalidBlockHookType::Fixture,
+            ])),
+            invalid_block_webhook_url: Some("http://localhost:9000/invalid".to_string()),
+            ..Default::default()
+        };
+        let args = CommandParser::<DebugArgs>::parse_from([
+            "evm",
+            "--debug.invalid-block-hook",
+            "trace,webhook,fixture",
+            "--debug.invalid-block-webhook-url",
+            "http://localhost:9000/invalid",
+        ])
+        .args;
+        assert_eq!(args, expected_args);
+    }
 }
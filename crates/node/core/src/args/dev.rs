@@ -1,6 +1,6 @@
 //! clap [Args](clap::Args) for Dev testnet configuration
 
-use std::time::Duration;
+use std::{path::PathBuf, time::Duration};
 
 use clap::Args;
 use humantime::parse_duration;
@@ -25,7 +25,7 @@ pub struct DevArgs {
     #[arg(
         long = "dev.block-max-transactions",
         help_heading = "Dev testnet",
-        conflicts_with = "block_time"
+        conflicts_with_all = ["block_time", "scenario"]
     )]
     pub block_max_transactions: Option<usize>,
 
@@ -36,12 +36,26 @@ pub struct DevArgs {
     #[arg(
         long = "dev.block-time",
         help_heading = "Dev testnet",
-        conflicts_with = "block_max_transactions",
+        conflicts_with_all = ["block_max_transactions", "scenario"],
         value_parser = parse_duration,
         verbatim_doc_comment
     )]
     pub block_time: Option<Duration>,
 
+    /// Build the blocks of a scripted scenario instead of mining blocks.
+    ///
+    /// The scenario is a JSON file with a list of steps: blocks with exact timestamps and
+    /// transactions, empty blocks and reorgs of a given depth.
+    /// --dev.scenario scenario.json
+    #[arg(
+        long = "dev.scenario",
+        help_heading = "Dev testnet",
+        value_name = "PATH",
+        requires = "dev",
+        verbatim_doc_comment
+    )]
+    pub scenario: Option<PathBuf>,
+
     /// Derive dev accounts from a fixed mnemonic instead of random ones.
     #[arg(
         long = "dev.mnemonic",
@@ -60,6 +74,7 @@ impl Default for DevArgs {
             dev: false,
             block_max_transactions: None,
             block_time: None,
+            scenario: None,
             dev_mnemonic: DEFAULT_MNEMONIC.to_string(),
         }
     }
@@ -86,6 +101,7 @@ mod tests {
                 dev: false,
                 block_max_transactions: None,
                 block_time: None,
+                scenario: None,
                 dev_mnemonic: DEFAULT_MNEMONIC.to_string(),
             }
         );
@@ -97,6 +113,7 @@ mod tests {
                 dev: true,
                 block_max_transactions: None,
                 block_time: None,
+                scenario: None,
                 dev_mnemonic: DEFAULT_MNEMONIC.to_string(),
             }
         );
@@ -108,6 +125,7 @@ mod tests {
                 dev: true,
                 block_max_transactions: None,
                 block_time: None,
+                scenario: None,
                 dev_mnemonic: DEFAULT_MNEMONIC.to_string(),
             }
         );
@@ -125,6 +143,7 @@ mod tests {
                 dev: true,
                 block_max_transactions: Some(2),
                 block_time: None,
+                scenario: None,
                 dev_mnemonic: DEFAULT_MNEMONIC.to_string(),
             }
         );
@@ -137,6 +156,7 @@ mod tests {
                 dev: true,
                 block_max_transactions: None,
                 block_time: Some(std::time::Duration::from_secs(1)),
+                scenario: None,
                 dev_mnemonic: DEFAULT_MNEMONIC.to_string(),
             }
         );
@@ -153,6 +173,33 @@ mod tests {
             "1s",
         ]);
         assert!(args.is_err());
+
+        let args = CommandParser::<DevArgs>::try_parse_from([
+            "evm",
+            "--dev",
+            "--dev.block-time",
+            "1s",
+            "--dev.scenario",
+            "scenario.json",
+        ]);
+        assert!(args.is_err());
+    }
+
+    #[test]
+    fn test_parse_dev_scenario() {
+        let args = CommandParser::<DevArgs>::parse_from([
+            "evm",
+            "--dev",
+            "--dev.scenario",
+            "scenario.json",
+        ])
+        .args;
+        assert_eq!(args.scenario, Some(PathBuf::from("scenario.json")));
+
+        // a scenario requires dev mode
+        let args =
+            CommandParser::<DevArgs>::try_parse_from(["evm", "--dev.scenario", "scenario.json"]);
+        assert!(args.is_err());
     }
 
     #[test]
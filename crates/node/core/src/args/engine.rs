@@ -2,8 +2,8 @@
 
 use clap::{builder::Resettable, Args};
 use hanzo_evm_engine_primitives::{
-    TreeConfig, DEFAULT_MULTIPROOF_TASK_CHUNK_SIZE, DEFAULT_SPARSE_TRIE_MAX_STORAGE_TRIES,
-    DEFAULT_SPARSE_TRIE_PRUNE_DEPTH,
+    TreeConfig, DEFAULT_MULTIPROOF_TASK_CHUNK_SIZE, DEFAULT_OPTIMISTIC_EXECUTION_LAG,
+    DEFAULT_SPARSE_TRIE_MAX_STORAGE_TRIES, DEFAULT_SPARSE_TRIE_PRUNE_DEPTH,
 };
 use std::{sync::OnceLock, time::Duration};
 
@@ -44,6 +44,12 @@ pub struct DefaultEngineValues {
     sparse_trie_max_storage_tries: usize,
     disable_sparse_trie_cache_pruning: bool,
     state_root_task_timeout: Option<String>,
+    optimistic_sync: bool,
+    optimistic_execution_lag: u64,
+    parallel_execution: bool,
+    speculative_execution: bool,
+    persist_precompile_cache: bool,
+    share_precompile_cache: bool,
 }
 
 impl DefaultEngineValues {
@@ -203,6 +209,42 @@ impl DefaultEngineValues {
         self.state_root_task_timeout = v;
         self
     }
+
+    /// Set whether to enable optimistic sync by default
+    pub const fn with_optimistic_sync(mut self, v: bool) -> Self {
+        self.optimistic_sync = v;
+        self
+    }
+
+    /// Set the default optimistic execution lag
+    pub const fn with_optimistic_execution_lag(mut self, v: u64) -> Self {
+        self.optimistic_execution_lag = v;
+        self
+    }
+
+    /// Set whether to enable parallel execution by default
+    pub const fn with_parallel_execution(mut self, v: bool) -> Self {
+        self.parallel_execution = v;
+        self
+    }
+
+    /// Set whether to enable speculative execution by default
+    pub const fn with_speculative_execution(mut self, v: bool) -> Self {
+        self.speculative_execution = v;
+        self
+    }
+
+    /// Set whether to persist the precompile cache across restarts by default
+    pub const fn with_persist_precompile_cache(mut self, v: bool) -> Self {
+        self.persist_precompile_cache = v;
+        self
+    }
+
+    /// Set whether to share the precompile cache with RPC by default
+    pub const fn with_share_precompile_cache(mut self, v: bool) -> Self {
+        self.share_precompile_cache = v;
+        self
+    }
 }
 
 impl Default for DefaultEngineValues {
@@ -232,6 +274,12 @@ impl Default for DefaultEngineValues {
             sparse_trie_max_storage_tries: DEFAULT_SPARSE_TRIE_MAX_STORAGE_TRIES,
             disable_sparse_trie_cache_pruning: false,
             state_root_task_timeout: Some("1s".to_string()),
+            optimistic_sync: false,
+            optimistic_execution_lag: DEFAULT_OPTIMISTIC_EXECUTION_LAG,
+            parallel_execution: false,
+            speculative_execution: false,
+            persist_precompile_cache: false,
+            share_precompile_cache: false,
         }
     }
 }
@@ -389,6 +437,47 @@ pub struct EngineArgs {
         default_value = DefaultEngineValues::get_global().state_root_task_timeout.as_deref().unwrap_or("1s"),
     )]
     pub state_root_task_timeout: Option<Duration>,
+
+    /// Enable optimistic sync. Payloads with a known parent are answered with `ACCEPTED` before
+    /// they are executed and forkchoice updates to an unexecuted head are answered with
+    /// `SYNCING`. The accepted blocks are executed in the background and discarded if they turn
+    /// out to be invalid.
+    ///
+    /// CAUTION: Only use this behind a trusted consensus client.
+    #[arg(long = "engine.optimistic-sync", default_value_t = DefaultEngineValues::get_global().optimistic_sync)]
+    pub optimistic_sync: bool,
+
+    /// Configure the number of optimistically accepted blocks that are kept unexecuted. The
+    /// oldest accepted blocks are executed once this is exceeded. All accepted blocks are executed
+    /// once no engine message arrived for a second and before shutdown.
+    #[arg(
+        long = "engine.optimistic-execution-lag",
+        requires = "optimistic_sync",
+        default_value_t = DefaultEngineValues::get_global().optimistic_execution_lag
+    )]
+    pub optimistic_execution_lag: u64,
+
+    /// Enable parallel execution of the transactions of blocks that come with a block access
+    /// list. Blocks whose execution doesn't match their block access list are re-executed
+    /// sequentially.
+    #[arg(long = "engine.parallel-execution", default_value_t = DefaultEngineValues::get_global().parallel_execution)]
+    pub parallel_execution: bool,
+
+    /// Enable speculative execution of a predicted next block. While waiting for the next payload,
+    /// the best pending transactions are executed on top of the canonical head to warm the
+    /// execution cache and the sparse trie for the payload that builds on it.
+    #[arg(long = "engine.speculative-execution", default_value_t = DefaultEngineValues::get_global().speculative_execution)]
+    pub speculative_execution: bool,
+
+    /// Persist the precompile cache to the data directory on shutdown and restore it on startup.
+    /// Has no effect if the precompile cache is disabled.
+    #[arg(long = "engine.persist-precompile-cache", default_value_t = DefaultEngineValues::get_global().persist_precompile_cache)]
+    pub persist_precompile_cache: bool,
+
+    /// Share the precompile cache with the `eth_call`, `eth_estimateGas` and tracing RPC methods.
+    /// Has no effect if the precompile cache is disabled.
+    #[arg(long = "engine.share-precompile-cache", default_value_t = DefaultEngineValues::get_global().share_precompile_cache)]
+    pub share_precompile_cache: bool,
 }
 
 #[allow(deprecated)]
@@ -419,6 +508,12 @@ impl Default for EngineArgs {
             sparse_trie_max_storage_tries,
             disable_sparse_trie_cache_pruning,
             state_root_task_timeout,
+            optimistic_sync,
+            optimistic_execution_lag,
+            parallel_execution,
+            speculative_execution,
+            persist_precompile_cache,
+            share_precompile_cache,
         } = DefaultEngineValues::get_global().clone();
         Self {
             persistence_threshold,
@@ -451,6 +546,12 @@ impl Default for EngineArgs {
             state_root_task_timeout: state_root_task_timeout
                 .as_deref()
                 .map(|s| humantime::parse_duration(s).expect("valid default duration")),
+            optimistic_sync,
+            optimistic_execution_lag,
+            parallel_execution,
+            speculative_execution,
+            persist_precompile_cache,
+            share_precompile_cache,
         }
     }
 }
@@ -483,6 +584,12 @@ impl EngineArgs {
             .with_sparse_trie_max_storage_tries(self.sparse_trie_max_storage_tries)
             .with_disable_sparse_trie_cache_pruning(self.disable_sparse_trie_cache_pruning)
             .with_state_root_task_timeout(self.state_root_task_timeout.filter(|d| !d.is_zero()))
+            .with_optimistic_sync(self.optimistic_sync)
+            .with_optimistic_execution_lag(self.optimistic_execution_lag)
+            .with_parallel_execution(self.parallel_execution)
+            .with_speculative_execution(self.speculative_execution)
+            .with_persist_precompile_cache(self.persist_precompile_cache)
+            .with_share_precompile_cache(self.share_precompile_cache)
     }
 }
 
@@ -537,6 +644,12 @@ mod tests {
             sparse_trie_max_storage_tries: 100,
             disable_sparse_trie_cache_pruning: true,
             state_root_task_timeout: Some(Duration::from_secs(2)),
+            optimistic_sync: true,
+            optimistic_execution_lag: 16,
+            parallel_execution: true,
+            speculative_execution: true,
+            persist_precompile_cache: true,
+            share_precompile_cache: true,
         };
 
         let parsed_args = CommandParser::<EngineArgs>::parse_from([
@@ -576,9 +689,26 @@ mod tests {
             "--engine.disable-sparse-trie-cache-pruning",
             "--engine.state-root-task-timeout",
             "2s",
+            "--engine.optimistic-sync",
+            "--engine.optimistic-execution-lag",
+            "16",
+            "--engine.parallel-execution",
+            "--engine.speculative-execution",
+            "--engine.persist-precompile-cache",
+            "--engine.share-precompile-cache",
         ])
         .args;
 
         assert_eq!(parsed_args, args);
     }
+
+    #[test]
+    fn test_optimistic_execution_lag_requires_optimistic_sync() {
+        let result = CommandParser::<EngineArgs>::try_parse_from([
+            "evm",
+            "--engine.optimistic-execution-lag",
+            "16",
+        ]);
+        assert!(result.is_err());
+    }
 }
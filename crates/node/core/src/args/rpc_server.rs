@@ -78,6 +78,8 @@ pub struct DefaultRpcServerArgs {
     rpc_max_tracing_requests: usize,
     rpc_max_blocking_io_requests: usize,
     rpc_max_trace_filter_blocks: u64,
+    rpc_max_ots_search_blocks: u64,
+    rpc_snapshot_dir: Option<PathBuf>,
     rpc_max_blocks_per_filter: ZeroAsNoneU64,
     rpc_max_logs_per_response: ZeroAsNoneU64,
     rpc_gas_cap: u64,
@@ -273,6 +275,18 @@ impl DefaultRpcServerArgs {
         self
     }
 
+    /// Set the default max otterscan search blocks
+    pub const fn with_rpc_max_ots_search_blocks(mut self, v: u64) -> Self {
+        self.rpc_max_ots_search_blocks = v;
+        self
+    }
+
+    /// Set the default snapshot directory
+    pub fn with_rpc_snapshot_dir(mut self, v: Option<PathBuf>) -> Self {
+        self.rpc_snapshot_dir = v;
+        self
+    }
+
     /// Set the default max blocks per filter
     pub const fn with_rpc_max_blocks_per_filter(mut self, v: ZeroAsNoneU64) -> Self {
         self.rpc_max_blocks_per_filter = v;
@@ -389,6 +403,8 @@ impl Default for DefaultRpcServerArgs {
             rpc_max_tracing_requests: constants::default_max_tracing_requests(),
             rpc_max_blocking_io_requests: constants::DEFAULT_MAX_BLOCKING_IO_REQUEST,
             rpc_max_trace_filter_blocks: constants::DEFAULT_MAX_TRACE_FILTER_BLOCKS,
+            rpc_max_ots_search_blocks: constants::DEFAULT_MAX_OTS_SEARCH_BLOCKS,
+            rpc_snapshot_dir: None,
             rpc_max_blocks_per_filter: constants::DEFAULT_MAX_BLOCKS_PER_FILTER.into(),
             rpc_max_logs_per_response: (constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64).into(),
             rpc_gas_cap: constants::gas_oracle::RPC_DEFAULT_GAS_CAP,
@@ -548,6 +564,16 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.max-trace-filter-blocks", alias = "rpc-max-trace-filter-blocks", value_name = "COUNT", default_value_t = DefaultRpcServerArgs::get_global().rpc_max_trace_filter_blocks)]
     pub rpc_max_trace_filter_blocks: u64,
 
+    /// Maximum number of blocks that are traced by an otterscan transaction search.
+    #[arg(long = "rpc.max-ots-search-blocks", value_name = "COUNT", default_value_t = DefaultRpcServerArgs::get_global().rpc_max_ots_search_blocks)]
+    pub rpc_max_ots_search_blocks: u64,
+
+    /// Directory that `admin_createSnapshot` writes snapshots into.
+    ///
+    /// Defaults to `<DIR>/<CHAIN_ID>/snapshots`.
+    #[arg(long = "rpc.snapshot-dir", value_name = "PATH", required = false, default_value = Resettable::from(DefaultRpcServerArgs::get_global().rpc_snapshot_dir.as_ref().map(|v| v.to_string_lossy().into())))]
+    pub rpc_snapshot_dir: Option<PathBuf>,
+
     /// Maximum number of blocks that could be scanned per filter request. (0 = entire chain)
     #[arg(long = "rpc.max-blocks-per-filter", alias = "rpc-max-blocks-per-filter", value_name = "COUNT", default_value_t = DefaultRpcServerArgs::get_global().rpc_max_blocks_per_filter)]
     pub rpc_max_blocks_per_filter: ZeroAsNoneU64,
@@ -815,6 +841,8 @@ impl Default for RpcServerArgs {
             rpc_max_tracing_requests,
             rpc_max_blocking_io_requests,
             rpc_max_trace_filter_blocks,
+            rpc_max_ots_search_blocks,
+            rpc_snapshot_dir,
             rpc_max_blocks_per_filter,
             rpc_max_logs_per_response,
             rpc_gas_cap,
@@ -859,6 +887,8 @@ impl Default for RpcServerArgs {
             rpc_max_tracing_requests,
             rpc_max_blocking_io_requests,
             rpc_max_trace_filter_blocks,
+            rpc_max_ots_search_blocks,
+            rpc_snapshot_dir,
             rpc_max_blocks_per_filter,
             rpc_max_logs_per_response,
             rpc_gas_cap,
@@ -1024,6 +1054,8 @@ mod tests {
             rpc_max_tracing_requests: 16,
             rpc_max_blocking_io_requests: 256,
             rpc_max_trace_filter_blocks: 4000,
+            rpc_max_ots_search_blocks: 2000,
+            rpc_snapshot_dir: Some(std::path::PathBuf::from("/tmp/snapshots")),
             rpc_max_blocks_per_filter: 1000u64.into(),
             rpc_max_logs_per_response: 10000u64.into(),
             rpc_gas_cap: 50_000_000,
@@ -1102,6 +1134,10 @@ mod tests {
             "256",
             "--rpc.max-trace-filter-blocks",
             "4000",
+            "--rpc.max-ots-search-blocks",
+            "2000",
+            "--rpc.snapshot-dir",
+            "/tmp/snapshots",
             "--rpc.max-blocks-per-filter",
             "1000",
             "--rpc.max-logs-per-response",
@@ -1,7 +1,8 @@
 //! clap [Args](clap::Args) for static files configuration
 
 use clap::Args;
-use hanzo_evm_config::config::{BlocksPerFileConfig, StaticFilesConfig};
+use hanzo_evm_config::config::{BlocksPerFileConfig, StaticFileTierConfig, StaticFilesConfig};
+use std::path::PathBuf;
 
 /// Blocks per static file when running in `--minimal` node.
 ///
@@ -9,7 +10,7 @@ use hanzo_evm_config::config::{BlocksPerFileConfig, StaticFilesConfig};
 pub const MINIMAL_BLOCKS_PER_FILE: u64 = 10000;
 
 /// Parameters for static files configuration
-#[derive(Debug, Args, PartialEq, Eq, Clone, Copy, Default)]
+#[derive(Debug, Args, PartialEq, Eq, Clone, Default)]
 #[command(next_help_heading = "Static Files")]
 pub struct StaticFilesArgs {
     /// Number of blocks per file for the headers segment.
@@ -35,6 +36,29 @@ pub struct StaticFilesArgs {
     /// Number of blocks per file for the storage changesets segment.
     #[arg(long = "static-files.blocks-per-file.storage-change-sets")]
     pub blocks_per_file_storage_change_sets: Option<u64>,
+
+    /// Number of blocks per file for the state snapshots segment.
+    #[arg(long = "static-files.blocks-per-file.state-snapshots")]
+    pub blocks_per_file_state_snapshots: Option<u64>,
+
+    /// Minimum number of blocks between two snapshots of the full state in static files.
+    ///
+    /// Historical state reads below the pruned history fall back to the nearest snapshot at or
+    /// after the requested block. Requires the v2 storage layout.
+    #[arg(long = "static-files.state-snapshot-interval", value_name = "BLOCKS")]
+    pub state_snapshot_interval: Option<u64>,
+
+    /// Directory on a slower, larger volume that old static files are moved to.
+    ///
+    /// Moved files are symlinked from the static files directory and stay readable. Overrides the
+    /// tiers of the configuration file.
+    #[arg(long = "static-files.cold-dir", value_name = "PATH", requires = "cold_after")]
+    pub cold_dir: Option<PathBuf>,
+
+    /// Number of blocks between the end of a finished static file and the highest block of its
+    /// segment before the file is moved to `--static-files.cold-dir`.
+    #[arg(long = "static-files.cold-after", value_name = "BLOCKS", requires = "cold_dir")]
+    pub cold_after: Option<u64>,
 }
 
 impl StaticFilesArgs {
@@ -71,6 +95,19 @@ impl StaticFilesArgs {
                     .blocks_per_file_storage_change_sets
                     .or(minimal_blocks_per_file)
                     .or(config.blocks_per_file.storage_change_sets),
+                state_snapshots: self
+                    .blocks_per_file_state_snapshots
+                    .or(minimal_blocks_per_file)
+                    .or(config.blocks_per_file.state_snapshots),
+            },
+            state_snapshot_interval: self
+                .state_snapshot_interval
+                .or(config.state_snapshot_interval),
+            tiers: match (&self.cold_dir, self.cold_after) {
+                (Some(path), Some(min_age)) => {
+                    vec![StaticFileTierConfig { path: path.clone(), min_age }]
+                }
+                _ => config.tiers,
             },
         }
     }
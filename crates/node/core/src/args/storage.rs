@@ -18,13 +18,21 @@ pub struct StorageArgs {
     /// - History indices in `RocksDB` (accounts, storages, transaction hashes)
     /// - Account and storage changesets in static files
     ///
-    /// This is a genesis-initialization-only setting: changing it after genesis requires a
+    /// This is a genesis-initialization-only setting: an existing v1 database can be converted
+    /// in place with `evm db migrate-storage`, otherwise changing it after genesis requires a
     /// re-sync.
     ///
     /// Individual settings can still be overridden with `--static-files.*` and `--rocksdb.*`
     /// flags.
     #[arg(long = "storage.v2", action = ArgAction::SetTrue)]
     pub v2: bool,
+
+    /// Store plain state, hashed state and trie tables in `RocksDB` instead of MDBX.
+    ///
+    /// Intended for benchmarking an LSM-backed state store. This is a
+    /// genesis-initialization-only setting and can be combined with `--storage.v2`.
+    #[arg(long = "storage.state-in-rocksdb", action = ArgAction::SetTrue)]
+    pub state_in_rocksdb: bool,
 }
 
 #[cfg(test)]
@@ -45,6 +53,7 @@ mod tests {
         let args = CommandParser::<StorageArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
         assert!(!args.v2);
+        assert!(!args.state_in_rocksdb);
     }
 
     #[test]
@@ -52,4 +61,16 @@ mod tests {
         let args = CommandParser::<StorageArgs>::parse_from(["reth", "--storage.v2"]).args;
         assert!(args.v2);
     }
+
+    #[test]
+    fn test_parse_state_in_rocksdb_flag() {
+        let args = CommandParser::<StorageArgs>::parse_from([
+            "reth",
+            "--storage.v2",
+            "--storage.state-in-rocksdb",
+        ])
+        .args;
+        assert!(args.v2);
+        assert!(args.state_in_rocksdb);
+    }
 }
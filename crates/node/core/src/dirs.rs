@@ -354,6 +354,13 @@ impl<D> ChainPath<D> {
         self.data_dir().join("txpool-transactions-backup.rlp")
     }
 
+    /// Returns the path to the persisted precompile cache file
+    ///
+    /// `<DIR>/<CHAIN_ID>/precompile-cache.bin`
+    pub fn precompile_cache(&self) -> PathBuf {
+        self.data_dir().join("precompile-cache.bin")
+    }
+
     /// Returns the path to the config file for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/evm.toml`
@@ -375,6 +382,13 @@ impl<D> ChainPath<D> {
         self.data_dir().join("invalid_block_hooks")
     }
 
+    /// Returns the path to the directory that `admin_createSnapshot` writes snapshots into.
+    ///
+    /// `<DIR>/<CHAIN_ID>/snapshots`
+    pub fn snapshots(&self) -> PathBuf {
+        self.data_dir().join("snapshots")
+    }
+
     /// Returns the path to the ExEx WAL directory for this chain.
     pub fn exex_wal(&self) -> PathBuf {
         self.data_dir().join("exex/wal")
@@ -14,7 +14,8 @@ use alloy_primitives::{BlockNumber, B256, U256};
 use eyre::eyre;
 use hanzo_evm_chainspec::{ChainSpec, EthChainSpec, MAINNET};
 use hanzo_evm_config::config::PruneConfig;
-use hanzo_evm_engine_local::MiningMode;
+use hanzo_evm_engine_local::{MiningMode, Scenario};
+use hanzo_evm_engine_primitives::TreeConfig;
 use hanzo_evm_ethereum_forks::{EthereumHardforks, Head};
 use hanzo_evm_network_p2p::headers::client::HeadersClient;
 use hanzo_evm_primitives_traits::SealedHeader;
@@ -376,11 +377,25 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
     /// The base storage mode is determined by `--storage.v2`:
     /// - When `--storage.v2` is set: uses [`StorageSettings::v2()`] defaults
     /// - Otherwise: uses [`StorageSettings::base()`] defaults
+    ///
+    /// State tables are additionally routed to `RocksDB` when `--storage.state-in-rocksdb` is set.
     pub const fn storage_settings(&self) -> StorageSettings {
-        if self.storage.v2 {
-            StorageSettings::v2()
+        let settings =
+            if self.storage.v2 { StorageSettings::v2() } else { StorageSettings::base() };
+        settings.with_state_in_rocksdb(self.storage.state_in_rocksdb)
+    }
+
+    /// Returns the [`TreeConfig`] of the engine.
+    ///
+    /// In `--dev` mode payload attributes are always processed on the canonical head, since the
+    /// reorgs of the local miner and of `--dev.scenario` build their blocks on an ancestor of the
+    /// head, which is already canonical.
+    pub fn engine_tree_config(&self) -> TreeConfig {
+        let config = self.engine.tree_config();
+        if self.dev.dev {
+            config.with_always_process_payload_attributes_on_canonical_head(true)
         } else {
-            StorageSettings::base()
+            config
         }
     }
 
@@ -583,15 +598,20 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
     }
 
     /// Returns the [`MiningMode`] intended for --dev mode.
-    pub fn dev_mining_mode<Pool>(&self, pool: Pool) -> MiningMode<Pool>
+    ///
+    /// Fails if the scenario file of `--dev.scenario` can't be read.
+    pub fn dev_mining_mode<Pool>(&self, pool: Pool) -> eyre::Result<MiningMode<Pool>>
     where
         Pool: TransactionPool + Unpin,
     {
-        if let Some(interval) = self.dev.block_time {
+        let mode = if let Some(path) = &self.dev.scenario {
+            MiningMode::scripted(pool, Scenario::from_path(path)?)
+        } else if let Some(interval) = self.dev.block_time {
             MiningMode::interval(interval)
         } else {
             MiningMode::instant(pool, self.dev.block_max_transactions)
-        }
+        };
+        Ok(mode)
     }
 }
 
@@ -619,7 +639,7 @@ impl<ChainSpec> Clone for NodeConfig<ChainSpec> {
             datadir: self.datadir.clone(),
             engine: self.engine.clone(),
             era: self.era.clone(),
-            static_files: self.static_files,
+            static_files: self.static_files.clone(),
             storage: self.storage,
         }
     }
@@ -268,6 +268,14 @@ impl NodeState {
             ConsensusEngineEvent::InvalidBlock(block) => {
                 warn!(number=block.number(), hash=?block.hash(), "Encountered invalid block");
             }
+            ConsensusEngineEvent::OptimisticBlockInvalid(block, discarded) => {
+                warn!(
+                    number=block.number(),
+                    hash=?block.hash(),
+                    discarded=discarded.len(),
+                    "Optimistically accepted block is invalid, discarded its accepted descendants"
+                );
+            }
             ConsensusEngineEvent::BlockReceived(num_hash) => {
                 info!(number=num_hash.number, hash=?num_hash.hash, "Received new payload from consensus engine");
             }
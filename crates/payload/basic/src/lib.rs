@@ -170,6 +170,7 @@ where
             // ticks immediately
             interval: tokio::time::interval(self.config.interval),
             best_payload: PayloadState::Missing,
+            improved: false,
             pending_block: None,
             cached_reads,
             payload_task_guard: self.payload_task_guard.clone(),
@@ -311,6 +312,9 @@ where
     interval: Interval,
     /// The best payload so far and its state.
     best_payload: PayloadState<Builder::BuiltPayload>,
+    /// Whether the best payload improved since it was last reported through
+    /// [`PayloadJob::take_better_payload`].
+    improved: bool,
     /// Receiver for the block that is currently being built.
     pending_block: Option<PendingPayload<Builder::BuiltPayload>>,
     /// Restricts how many generator tasks can be executed at once.
@@ -393,10 +397,12 @@ where
                         this.cached_reads = Some(cached_reads);
                         debug!(target: "payload_builder", value = %payload.fees(), "built better payload");
                         this.best_payload = PayloadState::Best(payload);
+                        this.improved = true;
                     }
                     BuildOutcome::Freeze(payload) => {
                         debug!(target: "payload_builder", "payload frozen, no further building will occur");
                         this.best_payload = PayloadState::Frozen(payload);
+                        this.improved = true;
                     }
                     BuildOutcome::Aborted { fees, cached_reads } => {
                         this.cached_reads = Some(cached_reads);
@@ -517,6 +523,13 @@ where
 
         (fut, KeepPayloadJobAlive::No)
     }
+
+    fn take_better_payload(&mut self) -> Option<Self::BuiltPayload> {
+        if !std::mem::take(&mut self.improved) {
+            return None
+        }
+        self.best_payload.payload().cloned()
+    }
 }
 
 /// Represents the current state of a payload being built.
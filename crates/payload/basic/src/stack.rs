@@ -6,7 +6,7 @@ use crate::{
 use alloy_eips::eip4895::Withdrawals;
 use alloy_primitives::{Address, B256, U256};
 use hanzo_evm_payload_builder::PayloadId;
-use hanzo_evm_payload_primitives::BuiltPayload;
+use hanzo_evm_payload_primitives::{BuiltPayload, PayloadBlobsBundle};
 use hanzo_evm_primitives_traits::{NodePrimitives, SealedBlock};
 
 use alloy_eips::eip7685::Requests;
@@ -175,6 +175,13 @@ where
             Self::Right(r) => r.requests(),
         }
     }
+
+    fn blobs_bundle(&self) -> Option<PayloadBlobsBundle> {
+        match self {
+            Self::Left(l) => l.blobs_bundle(),
+            Self::Right(r) => r.blobs_bundle(),
+        }
+    }
 }
 
 impl<L, R> PayloadBuilder for PayloadBuilderStack<L, R>
@@ -1,3 +1,4 @@
+use alloy_rpc_types_engine::PayloadId;
 use hanzo_evm_payload_primitives::PayloadTypes;
 use std::{
     pin::Pin,
@@ -20,6 +21,18 @@ pub enum Events<T: PayloadTypes> {
     /// Triggered by the CL whenever it asks for an execution payload.
     /// This event is only thrown if the CL is a validator.
     BuiltPayload(T::BuiltPayload),
+    /// A payload that improved on the best payload of a job that is still in progress.
+    ///
+    /// Emitted for every better payload the job builds before it is resolved.
+    BetterPayload {
+        /// The identifier of the payload job.
+        id: PayloadId,
+        /// The improved payload.
+        payload: T::BuiltPayload,
+    },
+    /// A payload job finished or was terminated after it was resolved, and will not build any
+    /// more payloads.
+    PayloadJobFinished(PayloadId),
 }
 
 /// Represents a receiver for various payload events.
@@ -49,6 +62,11 @@ impl<T: PayloadTypes> PayloadEvents<T> {
     pub fn into_attributes_stream(self) -> PayloadAttributeStream<T> {
         PayloadAttributeStream { st: self.into_stream() }
     }
+
+    /// Returns a new stream that yields all improved payloads of in-progress payload jobs.
+    pub fn into_better_payload_stream(self) -> BetterPayloadStream<T> {
+        BetterPayloadStream { st: self.into_stream() }
+    }
 }
 
 /// A stream that yields built payloads.
@@ -67,8 +85,12 @@ impl<T: PayloadTypes> Stream for BuiltPayloadStream<T> {
         loop {
             return match ready!(self.as_mut().project().st.poll_next(cx)) {
                 Some(Ok(Events::BuiltPayload(payload))) => Poll::Ready(Some(payload)),
-                Some(Ok(Events::Attributes(_))) => {
-                    // ignoring attributes
+                Some(Ok(
+                    Events::Attributes(_) |
+                    Events::BetterPayload { .. } |
+                    Events::PayloadJobFinished(_),
+                )) => {
+                    // ignoring attributes and in-progress payloads
                     continue
                 }
                 Some(Err(err)) => {
@@ -97,7 +119,11 @@ impl<T: PayloadTypes> Stream for PayloadAttributeStream<T> {
         loop {
             return match ready!(self.as_mut().project().st.poll_next(cx)) {
                 Some(Ok(Events::Attributes(attr))) => Poll::Ready(Some(attr)),
-                Some(Ok(Events::BuiltPayload(_))) => {
+                Some(Ok(
+                    Events::BuiltPayload(_) |
+                    Events::BetterPayload { .. } |
+                    Events::PayloadJobFinished(_),
+                )) => {
                     // ignoring payloads
                     continue
                 }
@@ -110,3 +136,36 @@ impl<T: PayloadTypes> Stream for PayloadAttributeStream<T> {
         }
     }
 }
+
+/// A stream that yields improved payloads of in-progress payload jobs, together with the
+/// identifier of the job.
+#[derive(Debug)]
+#[pin_project::pin_project]
+pub struct BetterPayloadStream<T: PayloadTypes> {
+    /// The stream of events.
+    #[pin]
+    st: BroadcastStream<Events<T>>,
+}
+
+impl<T: PayloadTypes> Stream for BetterPayloadStream<T> {
+    type Item = (PayloadId, T::BuiltPayload);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            return match ready!(self.as_mut().project().st.poll_next(cx)) {
+                Some(Ok(Events::BetterPayload { id, payload })) => Poll::Ready(Some((id, payload))),
+                Some(Ok(
+                    Events::Attributes(_) | Events::BuiltPayload(_) | Events::PayloadJobFinished(_),
+                )) => {
+                    // ignoring attributes and resolved payloads
+                    continue
+                }
+                Some(Err(err)) => {
+                    debug!(%err, "payload event stream lagging behind");
+                    continue
+                }
+                None => Poll::Ready(None),
+            }
+        }
+    }
+}
@@ -9,6 +9,8 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
 mod events;
-pub use crate::events::{Events, PayloadEvents};
+pub use crate::events::{
+    BetterPayloadStream, BuiltPayloadStream, Events, PayloadAttributeStream, PayloadEvents,
+};
 
 pub use hanzo_evm_payload_primitives::PayloadBuilderError;
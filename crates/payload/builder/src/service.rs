@@ -84,6 +84,14 @@ where
     ) -> Option<Result<u64, PayloadBuilderError>> {
         self.inner.payload_timestamp(id).await
     }
+
+    /// Subscribes to the events of the payload builder service.
+    ///
+    /// This includes every improved payload of in-progress payload jobs, see
+    /// [`Events::BetterPayload`].
+    pub async fn subscribe(&self) -> Result<PayloadEvents<T>, PayloadBuilderError> {
+        self.inner.subscribe().await
+    }
 }
 
 impl<T> PayloadStore<T>
@@ -318,6 +326,7 @@ where
         if keep_alive == KeepPayloadJobAlive::No {
             let (_, id) = self.payload_jobs.swap_remove(job);
             debug!(target: "payload_builder", %id, "terminated resolved job");
+            self.payload_events.send(Events::PayloadJobFinished(id)).ok();
         }
 
         // Since the fees will not be known until the payload future is resolved / awaited, we wrap
@@ -402,16 +411,29 @@ where
             for idx in (0..this.payload_jobs.len()).rev() {
                 let (mut job, id) = this.payload_jobs.swap_remove(idx);
 
+                let poll = job.poll_unpin(cx);
+
                 // drain better payloads from the job
-                match job.poll_unpin(cx) {
+                if let Some(payload) = job.take_better_payload() &&
+                    this.payload_events.receiver_count() > 0
+                {
+                    trace!(target: "payload_builder", %id, value = %payload.fees(), "emitting better payload");
+                    this.payload_events
+                        .send(Events::BetterPayload { id, payload: payload.into() })
+                        .ok();
+                }
+
+                match poll {
                     Poll::Ready(Ok(_)) => {
                         this.metrics.set_active_jobs(this.payload_jobs.len());
                         trace!(target: "payload_builder", %id, "payload job finished");
+                        this.payload_events.send(Events::PayloadJobFinished(id)).ok();
                     }
                     Poll::Ready(Err(err)) => {
                         warn!(target: "payload_builder",%err, ?id, "Payload builder job failed; resolving payload");
                         this.metrics.inc_failed_jobs();
                         this.metrics.set_active_jobs(this.payload_jobs.len());
+                        this.payload_events.send(Events::PayloadJobFinished(id)).ok();
                     }
                     Poll::Pending => {
                         // still pending, put it back
@@ -61,14 +61,18 @@ impl PayloadJobGenerator for TestPayloadJobGenerator {
         &self,
         attr: EthPayloadBuilderAttributes,
     ) -> Result<Self::Job, PayloadBuilderError> {
-        Ok(TestPayloadJob { attr })
+        Ok(TestPayloadJob { attr, improvements: 0 })
     }
 }
 
 /// A [`PayloadJob`] for testing purposes
+///
+/// Every time the job is polled it reports a better payload, with fees equal to the number of
+/// reported payloads.
 #[derive(Debug)]
 pub struct TestPayloadJob {
     attr: EthPayloadBuilderAttributes,
+    improvements: u64,
 }
 
 impl Future for TestPayloadJob {
@@ -109,4 +113,14 @@ impl PayloadJob for TestPayloadJob {
         let fut = futures_util::future::ready(self.best_payload());
         (fut, KeepPayloadJobAlive::No)
     }
+
+    fn take_better_payload(&mut self) -> Option<EthBuiltPayload> {
+        self.improvements += 1;
+        Some(EthBuiltPayload::new(
+            self.attr.payload_id(),
+            Arc::new(Block::<_>::default().seal_slow()),
+            U256::from(self.improvements),
+            Some(Default::default()),
+        ))
+    }
 }
@@ -79,6 +79,17 @@ pub trait PayloadJob: Future<Output = Result<(), PayloadBuilderError>> {
     fn resolve(&mut self) -> (Self::ResolvePayloadFuture, KeepPayloadJobAlive) {
         self.resolve_kind(PayloadKind::Earliest)
     }
+
+    /// Returns the new best payload if the job built a better payload since the last call.
+    ///
+    /// This is called by the [`PayloadBuilderService`](crate::PayloadBuilderService) every time
+    /// the job was polled, so that every improved payload can be streamed to subscribers before
+    /// the job is resolved.
+    ///
+    /// The default implementation never reports improved payloads.
+    fn take_better_payload(&mut self) -> Option<Self::BuiltPayload> {
+        None
+    }
 }
 
 /// Whether the payload job should be kept alive or terminated after the payload was requested by
@@ -26,7 +26,7 @@ pub use error::{
 mod traits;
 pub use traits::{
     BuildNextEnv, BuiltPayload, BuiltPayloadExecutedBlock, PayloadAttributes,
-    PayloadAttributesBuilder, PayloadBuilderAttributes,
+    PayloadAttributesBuilder, PayloadBlobsBundle, PayloadBuilderAttributes,
 };
 
 mod payload;
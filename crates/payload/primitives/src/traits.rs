@@ -7,7 +7,9 @@ use alloy_eips::{
     eip7685::Requests,
 };
 use alloy_primitives::{Address, B256, U256};
-use alloy_rpc_types_engine::{PayloadAttributes as EthPayloadAttributes, PayloadId};
+use alloy_rpc_types_engine::{
+    BlobsBundleV1, BlobsBundleV2, PayloadAttributes as EthPayloadAttributes, PayloadId,
+};
 use core::fmt;
 use either::Either;
 use hanzo_evm_chain_state::ComputedTrieData;
@@ -95,6 +97,27 @@ pub trait BuiltPayload: Send + Sync + fmt::Debug {
     /// These are requests generated by the execution layer that need to be
     /// processed by the consensus layer (e.g., validator deposits, withdrawals).
     fn requests(&self) -> Option<Requests>;
+
+    /// Returns the blobs, commitments and proofs of the blob transactions included in this block.
+    ///
+    /// Returns `None` if the block has no blobs or the payload doesn't track them.
+    fn blobs_bundle(&self) -> Option<PayloadBlobsBundle> {
+        None
+    }
+}
+
+/// The blobs bundle of a built payload, in the format returned by the engine API.
+///
+/// Both versions have the same fields, so a serialized bundle is always deserialized as
+/// [`PayloadBlobsBundle::V1`].
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum PayloadBlobsBundle {
+    /// Bundle with EIP-4844 blob proofs, as returned by `engine_getPayloadV3` and
+    /// `engine_getPayloadV4`.
+    V1(BlobsBundleV1),
+    /// Bundle with EIP-7594 cell proofs, as returned by `engine_getPayloadV5`.
+    V2(BlobsBundleV2),
 }
 
 /// Attributes used to guide the construction of a new execution payload.
@@ -202,6 +225,17 @@ pub trait PayloadAttributesBuilder<Attributes, Header = alloy_consensus::Header>
 {
     /// Constructs new payload attributes for the given timestamp.
     fn build(&self, parent: &SealedHeader<Header>) -> Attributes;
+
+    /// Constructs new payload attributes for the given parent with an exact timestamp.
+    ///
+    /// Returns `None` if the builder can't build attributes for a fixed timestamp.
+    fn build_with_timestamp(
+        &self,
+        _parent: &SealedHeader<Header>,
+        _timestamp: u64,
+    ) -> Option<Attributes> {
+        None
+    }
 }
 
 impl<Attributes, Header, F> PayloadAttributesBuilder<Attributes, Header> for F
@@ -225,6 +259,17 @@ where
             Self::Right(r) => r.build(parent),
         }
     }
+
+    fn build_with_timestamp(
+        &self,
+        parent: &SealedHeader<Header>,
+        timestamp: u64,
+    ) -> Option<Attributes> {
+        match self {
+            Self::Left(l) => l.build_with_timestamp(parent, timestamp),
+            Self::Right(r) => r.build_with_timestamp(parent, timestamp),
+        }
+    }
 }
 
 impl<Attributes, Header> PayloadAttributesBuilder<Attributes, Header>
@@ -236,6 +281,14 @@ where
     fn build(&self, parent: &SealedHeader<Header>) -> Attributes {
         self.as_ref().build(parent)
     }
+
+    fn build_with_timestamp(
+        &self,
+        parent: &SealedHeader<Header>,
+        timestamp: u64,
+    ) -> Option<Attributes> {
+        self.as_ref().build_with_timestamp(parent, timestamp)
+    }
 }
 
 /// Trait to build the EVM environment for the next block from the given payload attributes.
@@ -1,6 +1,25 @@
+use alloy_primitives::{BlockHash, BlockNumber};
 use alloy_rpc_types_admin::{NodeInfo, PeerInfo};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use hanzo_evm_network_peers::{AnyNode, NodeRecord};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// The outcome of an `admin_createSnapshot` call.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminSnapshot {
+    /// Directory the snapshot was written to.
+    pub path: PathBuf,
+    /// Block the snapshot was pinned at.
+    pub block_number: BlockNumber,
+    /// Hash of the pinned block.
+    pub block_hash: BlockHash,
+    /// Number of files in the snapshot, excluding the manifest.
+    pub files: usize,
+    /// Total size of the snapshot files in bytes.
+    pub size: u64,
+}
 
 /// Admin namespace rpc interface that gives access to several non-standard RPC methods.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
@@ -51,3 +70,18 @@ pub trait AdminApi {
     #[method(name = "clearTxpool")]
     async fn clear_txpool(&self) -> RpcResult<u64>;
 }
+
+/// Admin namespace rpc interface for managing the node's storage.
+///
+/// Served as part of the `admin` namespace, on the IPC transport only.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "admin"))]
+pub trait AdminStorageApi {
+    /// Copies the node's database, static files and `RocksDB` into `path` while the node keeps
+    /// running, and writes a manifest that `evm download` can restore from.
+    ///
+    /// `path` is resolved relative to the snapshot directory of the node (`--rpc.snapshot-dir`),
+    /// must not leave it and must not exist yet.
+    #[method(name = "createSnapshot")]
+    async fn create_snapshot(&self, path: PathBuf) -> RpcResult<AdminSnapshot>;
+}
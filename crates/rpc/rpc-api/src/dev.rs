@@ -0,0 +1,30 @@
+use alloy_eips::BlockNumHash;
+use alloy_primitives::Bytes;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
+
+/// The outcome of a `dev_reorg` call.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DevReorg {
+    /// The blocks that were removed from the canonical chain, in ascending order.
+    pub reorged_blocks: Vec<BlockNumHash>,
+    /// The blocks of the alternative chain that replaced them, in ascending order.
+    pub new_blocks: Vec<BlockNumHash>,
+}
+
+/// Dev namespace rpc interface that controls the local miner of a node in dev mode.
+///
+/// The namespace is only available with `--dev` and has to be enabled explicitly, e.g. with
+/// `--http.api eth,dev`.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "dev"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "dev"))]
+pub trait DevApi {
+    /// Replaces the last `depth` blocks of the chain with an alternative chain of the same length
+    /// and makes it canonical.
+    ///
+    /// The given EIP-2718 encoded transactions are included in the first block of the
+    /// alternative chain. Requires `--engine.always-process-payload-attributes-on-canonical-head`.
+    #[method(name = "reorg")]
+    async fn reorg(&self, depth: usize, transactions: Option<Vec<Bytes>>) -> RpcResult<DevReorg>;
+}
@@ -21,6 +21,8 @@ use alloy_rpc_types_eth::{
 use alloy_serde::JsonStorageKey;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc, RpcModule};
 use hanzo_evm_engine_primitives::EngineTypes;
+use hanzo_evm_payload_primitives::PayloadBlobsBundle;
+use serde::{Deserialize, Serialize};
 
 /// Helper trait for the engine api server.
 ///
@@ -32,6 +34,32 @@ pub trait IntoEngineApiRpcModule {
     fn into_rpc_module(self) -> RpcModule<()>;
 }
 
+/// An improved version of a payload that is still being built.
+///
+/// Emitted by `engine_subscribePayloadUpdates` every time the payload job built a better payload,
+/// so that the caller can pick the version it wants to propose instead of polling
+/// `engine_getPayload`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadUpdate<T> {
+    /// The identifier of the payload job.
+    pub payload_id: PayloadId,
+    /// The hash of the built block.
+    pub block_hash: BlockHash,
+    /// The number of the built block.
+    pub block_number: U64,
+    /// The value of the payload, in wei, that is paid to the fee recipient.
+    pub block_value: U256,
+    /// The gas used by the built block.
+    pub gas_used: U64,
+    /// The built payload.
+    pub execution_payload: T,
+    /// The blobs, commitments and proofs of the blob transactions included in the payload, if
+    /// any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blobs_bundle: Option<PayloadBlobsBundle>,
+}
+
 // NOTE: We can't use associated types in the `EngineApi` trait because of jsonrpsee, so we use a
 // generic here. It would be nice if the rpc macro would understand which types need to have serde.
 // By default, if the trait has a generic, the rpc macro will add e.g. `Engine: DeserializeOwned` to
@@ -309,6 +337,27 @@ pub trait EngineApi<Engine: EngineTypes> {
         &self,
         versioned_hashes: Vec<B256>,
     ) -> RpcResult<Option<Vec<Option<BlobAndProofV2>>>>;
+
+    /// Subscribe to the improved payloads of an in-progress payload job.
+    ///
+    /// Every payload that improves on the best payload of the job with the given id is emitted
+    /// with its value, gas used and blobs bundle. The subscription is closed once the job finishes
+    /// or is terminated after it was resolved, and is rejected if there is no payload job in
+    /// progress for the given id. It's also closed if the subscriber falls behind the payload
+    /// builder events, in which case updates may have been missed and the best payload should be
+    /// fetched with `engine_getPayload`.
+    ///
+    /// Note: this is a Hanzo EVM extension and not part of the engine API specification, so it
+    /// isn't advertised by `engine_exchangeCapabilities`.
+    #[subscription(
+        name = "subscribePayloadUpdates",
+        unsubscribe = "unsubscribePayloadUpdates",
+        item = PayloadUpdate<Engine::ExecutionData>
+    )]
+    async fn subscribe_payload_updates(
+        &self,
+        payload_id: PayloadId,
+    ) -> jsonrpsee::core::SubscriptionResult;
 }
 
 /// A subset of the ETH rpc interface: <https://ethereum.github.io/execution-apis/api-documentation>
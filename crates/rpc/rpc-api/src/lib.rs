@@ -17,6 +17,7 @@
 mod admin;
 mod anvil;
 mod debug;
+mod dev;
 mod engine;
 mod hardhat;
 mod mev;
@@ -32,6 +33,8 @@ mod txpool;
 mod validation;
 mod web3;
 
+pub use admin::AdminSnapshot;
+pub use dev::DevReorg;
 pub use testing::{TestingBuildBlockRequestV1, TESTING_BUILD_BLOCK_V1};
 
 /// re-export of all server traits
@@ -40,9 +43,10 @@ pub use servers::*;
 /// Aggregates all server traits.
 pub mod servers {
     pub use crate::{
-        admin::AdminApiServer,
+        admin::{AdminApiServer, AdminStorageApiServer},
         debug::{DebugApiServer, DebugExecutionWitnessApiServer},
-        engine::{EngineApiServer, EngineEthApiServer, IntoEngineApiRpcModule},
+        dev::DevApiServer,
+        engine::{EngineApiServer, EngineEthApiServer, IntoEngineApiRpcModule, PayloadUpdate},
         mev::{MevFullApiServer, MevSimApiServer},
         miner::MinerApiServer,
         net::NetApiServer,
@@ -51,7 +55,7 @@ pub mod servers {
         reth_engine::{RethEngineApiServer, RethPayloadStatus},
         rpc::RpcApiServer,
         testing::TestingApiServer,
-        trace::TraceApiServer,
+        trace::{FilteredBlockTraces, TraceApiServer},
         txpool::TxPoolApiServer,
         validation::BlockSubmissionValidationApiServer,
         web3::Web3ApiServer,
@@ -70,9 +74,10 @@ pub use clients::*;
 #[cfg(feature = "client")]
 pub mod clients {
     pub use crate::{
-        admin::AdminApiClient,
+        admin::{AdminApiClient, AdminStorageApiClient},
         anvil::AnvilApiClient,
         debug::{DebugApiClient, DebugExecutionWitnessApiClient},
+        dev::DevApiClient,
         engine::{EngineApiClient, EngineEthApiClient},
         hardhat::HardhatApiClient,
         mev::{MevFullApiClient, MevSimApiClient},
@@ -1,12 +1,34 @@
 use alloy_eips::BlockId;
-use alloy_primitives::{map::HashSet, Bytes, B256};
+use alloy_primitives::{map::HashSet, BlockHash, Bytes, B256};
 use alloy_rpc_types_eth::{state::StateOverride, BlockOverrides, Index};
 use alloy_rpc_types_trace::{
     filter::TraceFilter,
+    geth::{GethDebugTracingOptions, TraceResult},
     opcode::{BlockOpcodeGas, TransactionOpcodeGas},
     parity::*,
 };
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
+
+/// The traces of a single block that match a [`TraceFilter`], emitted by `trace_subscribeFilter`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FilteredBlockTraces {
+    /// Number of the traced block.
+    pub block_number: u64,
+    /// Hash of the traced block.
+    pub block_hash: BlockHash,
+    /// The parity traces of the block that match the filter, including reward traces.
+    ///
+    /// Empty if a geth tracer was requested.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub traces: Vec<LocalizedTransactionTrace>,
+    /// The geth traces of all transactions of the block with at least one matching trace.
+    ///
+    /// Only populated if a geth tracer was requested.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub geth_traces: Vec<TraceResult>,
+}
 
 /// Ethereum trace API
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "trace"))]
@@ -73,6 +95,28 @@ pub trait TraceApi<TxReq> {
     #[method(name = "filter")]
     async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<LocalizedTransactionTrace>>;
 
+    /// Subscribe to the traces matching the given filter.
+    ///
+    /// Unlike `trace_filter`, the traces are not collected upfront. Blocks are traced one after
+    /// another and every block with matching traces is emitted as a separate notification, in
+    /// ascending block order. The next block is only traced once the previous notification was
+    /// accepted by the connection, so slow consumers are not buffered. The subscription ends
+    /// after the last block of the range.
+    ///
+    /// If `tracer` options are given, the geth traces (e.g. `callTracer` or `prestateTracer`) of
+    /// every transaction with at least one matching call are emitted instead of the parity traces.
+    /// The `after` and `count` fields of the filter then apply to transactions instead of traces.
+    #[subscription(
+        name = "subscribeFilter",
+        unsubscribe = "unsubscribeFilter",
+        item = FilteredBlockTraces
+    )]
+    async fn trace_subscribe_filter(
+        &self,
+        filter: TraceFilter,
+        tracer: Option<GethDebugTracingOptions>,
+    ) -> jsonrpsee::core::SubscriptionResult;
+
     /// Returns transaction trace at given index.
     ///
     /// `indices` represent the index positions of the traces.
@@ -1,7 +1,6 @@
-use crate::{cors::CorsDomainError, EvmRpcModule};
+use crate::cors::CorsDomainError;
 use hanzo_evm_ipc::server::IpcServerStartError;
 use std::{
-    collections::HashSet,
     io::{self, ErrorKind},
     net::SocketAddr,
 };
@@ -85,31 +84,6 @@ impl RpcError {
     }
 }
 
-/// Conflicting modules between http and ws servers.
-#[derive(Debug)]
-pub struct ConflictingModules {
-    /// Modules present in both http and ws.
-    pub overlap: HashSet<EvmRpcModule>,
-    /// Modules present in http but not in ws.
-    pub http_not_ws: HashSet<EvmRpcModule>,
-    /// Modules present in ws but not in http.
-    pub ws_not_http: HashSet<EvmRpcModule>,
-}
-
-impl std::fmt::Display for ConflictingModules {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "different API modules for HTTP and WS on the same port is currently not supported: \
-            Overlap: {:?}, \
-            HTTP modules not present in WS: {:?} \
-            WS modules not present in HTTP: {:?}
-            ",
-            self.overlap, self.http_not_ws, self.ws_not_http
-        )
-    }
-}
-
 /// Errors when trying to launch ws and http server on the same port.
 #[derive(Debug, thiserror::Error)]
 pub enum WsHttpSamePortError {
@@ -124,9 +98,6 @@ pub enum WsHttpSamePortError {
         /// Ws cors domains.
         ws_cors_domains: Option<String>,
     },
-    /// Ws and http server configured on same port but with different modules.
-    #[error("{0}")]
-    ConflictingModules(Box<ConflictingModules>),
 }
 
 #[cfg(test)]
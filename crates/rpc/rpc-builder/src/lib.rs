@@ -19,11 +19,16 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
-use crate::{auth::AuthRpcModule, error::WsHttpSamePortError, metrics::RpcRequestMetrics};
+use crate::{
+    auth::AuthRpcModule,
+    error::WsHttpSamePortError,
+    metrics::RpcRequestMetrics,
+    same_port::{SamePortModuleFilter, SamePortTransportLayer},
+};
 use alloy_network::{Ethereum, IntoWallet};
 use alloy_provider::{fillers::RecommendedFillers, Provider, ProviderBuilder};
 use core::marker::PhantomData;
-use error::{ConflictingModules, RpcError, ServerKind};
+use error::{RpcError, ServerKind};
 use http::{header::AUTHORIZATION, HeaderMap};
 use jsonrpsee::{
     core::RegisterMethodError,
@@ -62,7 +67,7 @@ use reth_tokio_util::EventSender;
 use reth_transaction_pool::{noop::NoopTransactionPool, TransactionPool};
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fmt::Debug,
     net::{Ipv4Addr, SocketAddr, SocketAddrV4},
     time::{Duration, SystemTime, UNIX_EPOCH},
@@ -111,6 +116,10 @@ use reth_rpc::eth::sim_bundle::EthSimBundle;
 // Rpc rate limiter
 pub mod rate_limiter;
 
+// Per transport module routing for http and ws on the same port
+mod same_port;
+pub use same_port::{SamePortModuleFilterService, SamePortTransportService};
+
 /// A builder type to configure the RPC module: See [`RpcModule`]
 ///
 /// This is the main entrypoint and the easiest way to configure an RPC server.
@@ -771,7 +780,7 @@ where
     /// If called outside of the tokio runtime. See also [`Self::eth_api`]
     pub fn otterscan_api(&self) -> OtterscanApi<EthApi> {
         let eth_api = self.eth_api().clone();
-        OtterscanApi::new(eth_api)
+        OtterscanApi::new(eth_api).with_max_search_blocks(self.eth_config.max_ots_search_blocks)
     }
 }
 
@@ -1005,7 +1014,10 @@ where
                         )
                         .into_rpc()
                         .into(),
-                        RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
+                        RethRpcModule::Ots => OtterscanApi::new(eth_api.clone())
+                            .with_max_search_blocks(self.eth_config.max_ots_search_blocks)
+                            .into_rpc()
+                            .into(),
                         RethRpcModule::Reth => RethApi::new(
                             self.provider.clone(),
                             self.evm_config.clone(),
@@ -1025,6 +1037,7 @@ where
                         // nodebuilder rpc addon stack
                         EvmRpcModule::Flashbots |
                         EvmRpcModule::Testing |
+                        EvmRpcModule::Dev |
                         EvmRpcModule::Other(_) => Default::default(),
                     })
                     .clone()
@@ -1371,13 +1384,16 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             }
             .cloned();
 
-            // we merge this into one server using the http setup
-            modules.config.ensure_ws_http_identical()?;
+            // we merge this into one server using the http setup, if the modules differ the
+            // server is started with the union of both and each transport is restricted to its
+            // own methods
+            let (module, filter) = modules.same_port_module();
 
             if let Some(config) = self.http_server_config {
                 let server = ServerBuilder::new()
                     .set_http_middleware(
                         tower::ServiceBuilder::new()
+                            .layer(SamePortTransportLayer)
                             .option_layer(Self::maybe_cors_layer(cors)?)
                             .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
                             .option_layer(Self::maybe_compression_layer(
@@ -1386,11 +1402,10 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                     )
                     .set_rpc_middleware(
                         RpcServiceBuilder::default()
+                            .option_layer(filter)
                             .layer(
-                                modules
-                                    .http
+                                module
                                     .as_ref()
-                                    .or(modules.ws.as_ref())
                                     .map(RpcRequestMetrics::same_port)
                                     .unwrap_or_default(),
                             )
@@ -1405,8 +1420,8 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                 let addr = server.local_addr().map_err(|err| {
                     RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
                 })?;
-                if let Some(module) = modules.http.as_ref().or(modules.ws.as_ref()) {
-                    let handle = server.start(module.clone());
+                if let Some(module) = module {
+                    let handle = server.start(module);
                     http_handle = Some(handle.clone());
                     ws_handle = Some(handle);
                 }
@@ -1623,29 +1638,6 @@ impl TransportRpcModuleConfig {
     pub fn contains_ipc(&self, module: &EvmRpcModule) -> bool {
         self.ipc.as_ref().is_some_and(|ipc| ipc.contains(module))
     }
-
-    /// Ensures that both http and ws are configured and that they are configured to use the same
-    /// port.
-    fn ensure_ws_http_identical(&self) -> Result<(), WsHttpSamePortError> {
-        if RpcModuleSelection::are_identical(self.http.as_ref(), self.ws.as_ref()) {
-            Ok(())
-        } else {
-            let http_modules =
-                self.http.as_ref().map(RpcModuleSelection::to_selection).unwrap_or_default();
-            let ws_modules =
-                self.ws.as_ref().map(RpcModuleSelection::to_selection).unwrap_or_default();
-
-            let http_not_ws = http_modules.difference(&ws_modules).cloned().collect();
-            let ws_not_http = ws_modules.difference(&http_modules).cloned().collect();
-            let overlap = http_modules.intersection(&ws_modules).cloned().collect();
-
-            Err(WsHttpSamePortError::ConflictingModules(Box::new(ConflictingModules {
-                overlap,
-                http_not_ws,
-                ws_not_http,
-            })))
-        }
-    }
 }
 
 /// Holds installed modules per transport type.
@@ -1697,6 +1689,26 @@ impl TransportRpcModules {
         &self.config
     }
 
+    /// Returns the module to serve when http and ws are configured on the same port.
+    ///
+    /// If the http and ws modules install the same methods, the http module is served as is.
+    /// Otherwise this returns the union of both modules, together with a [`SamePortModuleFilter`]
+    /// that restricts each transport to the methods of its own module.
+    fn same_port_module(&self) -> (Option<RpcModule<()>>, Option<SamePortModuleFilter>) {
+        let (Some(http), Some(ws)) = (&self.http, &self.ws) else {
+            return (self.http.clone().or_else(|| self.ws.clone()), None)
+        };
+
+        let http_methods = http.method_names().collect::<HashSet<_>>();
+        if ws.method_names().collect::<HashSet<_>>() == http_methods {
+            return (Some(http.clone()), None)
+        }
+
+        let mut module = http.clone();
+        let _ = module.merge(methods_by(ws, |name| !http_methods.contains(name)));
+        (Some(module), Some(SamePortModuleFilter::new(http, ws)))
+    }
+
     /// Merge the given [`Methods`] in all configured transport modules if the given
     /// [`EvmRpcModule`] is configured for the transport.
     ///
@@ -0,0 +1,170 @@
+//! Helpers for serving different modules over http and ws on the same port.
+//!
+//! When http and ws share a listener, jsonrpsee serves a single [`RpcModule`] for both transports.
+//! The server is started with the union of the http and ws modules and the transport of each
+//! request is recorded by [`SamePortTransportLayer`], so that [`SamePortModuleFilter`] can reject
+//! calls to methods that are not configured for that transport.
+
+use crate::metrics::RpcTransport;
+use jsonrpsee::{
+    core::middleware::{Batch, BatchEntry, BatchEntryErr, Notification, ResponseFuture},
+    server::{middleware::rpc::RpcServiceT, ws::is_upgrade_request},
+    types::{ErrorCode, ErrorObject, Id, Request},
+    MethodResponse, RpcModule,
+};
+use std::{
+    collections::HashSet,
+    future::Future,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tower::Layer;
+
+/// A http middleware that marks every request with the [`RpcTransport`] it arrived on.
+///
+/// Websocket upgrade requests are marked as [`RpcTransport::WebSocket`], everything else as
+/// [`RpcTransport::Http`]. jsonrpsee forwards the http request extensions to every rpc call made
+/// over the connection, which is what [`SamePortModuleFilter`] relies on.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct SamePortTransportLayer;
+
+impl<S> Layer<S> for SamePortTransportLayer {
+    type Service = SamePortTransportService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        SamePortTransportService { inner }
+    }
+}
+
+/// The http service created by [`SamePortTransportLayer`].
+#[derive(Debug, Clone)]
+pub struct SamePortTransportService<S> {
+    inner: S,
+}
+
+impl<S, B> tower::Service<http::Request<B>> for SamePortTransportService<S>
+where
+    S: tower::Service<http::Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: http::Request<B>) -> Self::Future {
+        let transport =
+            if is_upgrade_request(&req) { RpcTransport::WebSocket } else { RpcTransport::Http };
+        req.extensions_mut().insert(transport);
+        self.inner.call(req)
+    }
+}
+
+/// Restricts the methods that can be called over http and ws on a shared port.
+#[derive(Debug, Clone)]
+pub(crate) struct SamePortModuleFilter {
+    inner: Arc<SamePortModuleFilterInner>,
+}
+
+impl SamePortModuleFilter {
+    /// Creates a new filter that only allows the methods of the given http and ws modules on their
+    /// respective transport.
+    pub(crate) fn new(http: &RpcModule<()>, ws: &RpcModule<()>) -> Self {
+        Self {
+            inner: Arc::new(SamePortModuleFilterInner {
+                http: http.method_names().collect(),
+                ws: ws.method_names().collect(),
+            }),
+        }
+    }
+
+    /// Returns true if the method may be called over the transport recorded in the extensions.
+    ///
+    /// Requests without a recorded transport are always allowed.
+    fn is_allowed(&self, extensions: &http::Extensions, method: &str) -> bool {
+        match extensions.get::<RpcTransport>() {
+            Some(RpcTransport::Http) => self.inner.http.contains(method),
+            Some(RpcTransport::WebSocket) => self.inner.ws.contains(method),
+            _ => true,
+        }
+    }
+}
+
+impl<S> Layer<S> for SamePortModuleFilter {
+    type Service = SamePortModuleFilterService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        SamePortModuleFilterService { filter: self.clone(), inner }
+    }
+}
+
+#[derive(Debug)]
+struct SamePortModuleFilterInner {
+    /// Methods that are allowed over http.
+    http: HashSet<&'static str>,
+    /// Methods that are allowed over ws.
+    ws: HashSet<&'static str>,
+}
+
+/// A [`RpcServiceT`] middleware that responds with "method not found" to calls of methods that
+/// are not configured for the transport of the request.
+#[derive(Debug, Clone)]
+pub struct SamePortModuleFilterService<S> {
+    /// The method filter
+    filter: SamePortModuleFilter,
+    /// The inner service being wrapped
+    inner: S,
+}
+
+impl<S> RpcServiceT for SamePortModuleFilterService<S>
+where
+    S: RpcServiceT<MethodResponse = MethodResponse, NotificationResponse = MethodResponse>
+        + Send
+        + Sync
+        + Clone
+        + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = Self::MethodResponse> + Send + 'a {
+        if self.filter.is_allowed(req.extensions(), req.method_name()) {
+            ResponseFuture::future(self.inner.call(req))
+        } else {
+            ResponseFuture::ready(MethodResponse::error(
+                req.id,
+                ErrorObject::from(ErrorCode::MethodNotFound),
+            ))
+        }
+    }
+
+    fn batch<'a>(&self, mut req: Batch<'a>) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        for entry in req.iter_mut() {
+            let Ok(batch_entry) = entry else { continue };
+            if self.filter.is_allowed(batch_entry.extensions(), batch_entry.method_name()) {
+                continue
+            }
+            // notifications don't have a response, but they must not reach the handler either
+            let id = match batch_entry {
+                BatchEntry::Call(call) => call.id.clone(),
+                BatchEntry::Notification(_) => Id::Null,
+            };
+            *entry = Err(BatchEntryErr::new(id, ErrorCode::MethodNotFound.into()));
+        }
+        self.inner.batch(req)
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        if self.filter.is_allowed(n.extensions(), n.method_name()) {
+            ResponseFuture::future(self.inner.notification(n))
+        } else {
+            ResponseFuture::ready(MethodResponse::notification())
+        }
+    }
+}
@@ -8,11 +8,20 @@ use hanzo_evm_rpc_builder::{
 };
 use hanzo_evm_rpc_server_types::EvmRpcModule;
 use hanzo_evm_tokio_util::EventSender;
+use jsonrpsee::{core::client::ClientT, rpc_params, types::error::ErrorCode};
 
 use crate::utils::{
     launch_http, launch_http_ws_same_port, launch_ws, test_address, test_rpc_builder,
 };
 
+fn is_method_not_found(err: jsonrpsee::core::client::Error) -> bool {
+    matches!(
+        err,
+        jsonrpsee::core::client::Error::Call(error_obj)
+            if error_obj.code() == ErrorCode::MethodNotFound.code()
+    )
+}
+
 fn is_addr_in_use_kind(err: &RpcError, kind: ServerKind) -> bool {
     match err {
         RpcError::AddressAlreadyInUse { kind: k, error } => {
@@ -68,23 +77,31 @@ async fn test_launch_same_port_different_modules() {
     let builder = test_rpc_builder();
     let eth_api = builder.bootstrap_eth_api();
     let server = builder.build(
-        TransportRpcModuleConfig::set_ws(vec![EvmRpcModule::Admin])
-            .with_http(vec![EvmRpcModule::Eth]),
+        TransportRpcModuleConfig::set_ws(vec![EvmRpcModule::Net])
+            .with_http(vec![EvmRpcModule::Web3]),
         eth_api,
         EventSender::new(1),
     );
     let addr = test_address();
-    let res = RpcServerConfig::ws(Default::default())
+    let handle = RpcServerConfig::ws(Default::default())
         .with_ws_address(addr)
         .with_http(Default::default())
         .with_http_address(addr)
         .start(&server)
-        .await;
-    let err = res.unwrap_err();
-    assert!(matches!(
-        err,
-        RpcError::WsHttpSamePortError(WsHttpSamePortError::ConflictingModules { .. })
-    ));
+        .await
+        .unwrap();
+    assert_eq!(handle.ws_local_addr(), handle.http_local_addr());
+
+    let http = handle.http_client().unwrap();
+    let ws = handle.ws_client().await.unwrap();
+
+    http.request::<String, _>("web3_clientVersion", rpc_params![]).await.unwrap();
+    let err = http.request::<String, _>("net_version", rpc_params![]).await.unwrap_err();
+    assert!(is_method_not_found(err));
+
+    ws.request::<String, _>("net_version", rpc_params![]).await.unwrap();
+    let err = ws.request::<String, _>("web3_clientVersion", rpc_params![]).await.unwrap_err();
+    assert!(is_method_not_found(err));
 }
 
 #[tokio::test(flavor = "multi_thread")]
@@ -16,18 +16,23 @@ use alloy_rpc_types_engine::{
     PraguePayloadFields,
 };
 use async_trait::async_trait;
-use jsonrpsee_core::{server::RpcModule, RpcResult};
+use jsonrpsee_core::{
+    server::{PendingSubscriptionSink, RpcModule, SubscriptionMessage, SubscriptionSink},
+    RpcResult, SubscriptionResult,
+};
 use hanzo_evm_chainspec::EthereumHardforks;
 use hanzo_evm_engine_primitives::{ConsensusEngineHandle, EngineApiValidator, EngineTypes};
 use hanzo_evm_network_api::NetworkInfo;
 use hanzo_evm_payload_builder::PayloadStore;
+use hanzo_evm_payload_builder_primitives::{Events, PayloadEvents};
 use hanzo_evm_payload_primitives::{
-    validate_payload_timestamp, EngineApiMessageVersion, MessageValidationKind,
-    PayloadOrAttributes, PayloadTypes,
+    validate_payload_timestamp, BuiltPayload, EngineApiMessageVersion, ExecutionPayload,
+    MessageValidationKind, PayloadOrAttributes, PayloadTypes,
 };
 use reth_primitives_traits::{Block, BlockBody};
 use reth_rpc_api::{
-    EngineApiServer, IntoEngineApiRpcModule, RethEngineApiServer, RethPayloadStatus,
+    EngineApiServer, IntoEngineApiRpcModule, PayloadUpdate, RethEngineApiServer,
+    RethPayloadStatus,
 };
 use reth_storage_api::{BlockReader, HeaderProvider, StateProviderFactory};
 use reth_tasks::Runtime;
@@ -36,7 +41,7 @@ use std::{
     sync::Arc,
     time::{Instant, SystemTime},
 };
-use tokio::sync::oneshot;
+use tokio::sync::{broadcast::error::RecvError, oneshot};
 use tracing::{debug, trace, warn};
 
 /// The Engine API response sender.
@@ -1311,6 +1316,89 @@ where
         trace!(target: "rpc::engine", "Serving engine_getBlobsV3");
         Ok(self.get_blobs_v3_metered(versioned_hashes)?)
     }
+
+    /// Handler for `engine_subscribePayloadUpdates`
+    async fn subscribe_payload_updates(
+        &self,
+        pending: PendingSubscriptionSink,
+        payload_id: PayloadId,
+    ) -> SubscriptionResult {
+        trace!(target: "rpc::engine", %payload_id, "Serving engine_subscribePayloadUpdates");
+
+        // subscribe before looking up the job, so no improved payload and no termination of the
+        // job is missed in between
+        let events = match self.inner.payload_store.subscribe().await {
+            Ok(events) => events,
+            Err(err) => {
+                pending.reject(EngineApiError::from(err)).await;
+                return Ok(())
+            }
+        };
+        // unlike the payload timestamp, the best payload is only known while the job is in
+        // progress
+        if self.inner.payload_store.best_payload(payload_id).await.is_none() {
+            pending.reject(EngineApiError::UnknownPayload).await;
+            return Ok(())
+        }
+
+        let sink = pending.accept().await?;
+        self.inner.task_spawner.spawn_task(pipe_payload_updates(sink, events, payload_id));
+
+        Ok(())
+    }
+}
+
+/// Forwards every improved payload of the payload job with the given id to the sink, until the job
+/// finishes, the subscriber goes away, the subscriber lags behind the payload events or the payload
+/// builder service shuts down.
+///
+/// A lagging subscriber may have missed the end of the job, so it can't tell whether more updates
+/// follow. Returning drops the sink, which closes the subscription.
+async fn pipe_payload_updates<T: PayloadTypes>(
+    sink: SubscriptionSink,
+    mut events: PayloadEvents<T>,
+    payload_id: PayloadId,
+) {
+    loop {
+        let payload = tokio::select! {
+            _ = sink.closed() => break,
+            event = events.receiver.recv() => match event {
+                Ok(Events::BetterPayload { id, payload }) if id == payload_id => payload,
+                Ok(Events::PayloadJobFinished(id)) if id == payload_id => break,
+                Ok(_) => continue,
+                Err(RecvError::Lagged(skipped)) => {
+                    debug!(target: "rpc::engine", %payload_id, skipped, "payload update subscriber lagging behind, closing subscription");
+                    break
+                }
+                Err(RecvError::Closed) => break,
+            },
+        };
+
+        let execution_payload = T::block_to_payload(payload.block().clone());
+        let update = PayloadUpdate {
+            payload_id,
+            block_hash: execution_payload.block_hash(),
+            block_number: U64::from(execution_payload.block_number()),
+            block_value: payload.fees(),
+            gas_used: U64::from(execution_payload.gas_used()),
+            execution_payload,
+            blobs_bundle: payload.blobs_bundle(),
+        };
+        let msg = match SubscriptionMessage::new(
+            sink.method_name(),
+            sink.subscription_id(),
+            &update,
+        ) {
+            Ok(msg) => msg,
+            Err(err) => {
+                warn!(target: "rpc::engine", %err, "Failed to serialize payload update");
+                break
+            }
+        };
+        if sink.send(msg).await.is_err() {
+            break
+        }
+    }
 }
 
 /// Implementation of `RethEngineApiServer` under the `reth_` namespace.
@@ -1410,7 +1498,7 @@ mod tests {
         noop::NoopNetwork, EthProtocolInfo, NetworkError, NetworkInfo, NetworkStatus,
     };
     use reth_node_ethereum::EthereumEngineValidator;
-    use reth_payload_builder::test_utils::spawn_test_payload_service;
+    use reth_payload_builder::{test_utils::spawn_test_payload_service, PayloadBuilderHandle};
     use reth_provider::test_utils::MockEthProvider;
     use reth_tasks::Runtime;
     use reth_transaction_pool::noop::NoopTransactionPool;
@@ -1435,14 +1523,14 @@ mod tests {
 
         let chain_spec: Arc<ChainSpec> = MAINNET.clone();
         let provider = Arc::new(MockEthProvider::default());
-        let payload_store = spawn_test_payload_service();
+        let payload_builder = spawn_test_payload_service();
         let (to_engine, engine_rx) = unbounded_channel();
         let task_executor = Runtime::test();
         let api = EngineApi::new(
             provider.clone(),
             chain_spec.clone(),
             ConsensusEngineHandle::new(to_engine),
-            payload_store.into(),
+            payload_builder.clone().into(),
             NoopTransactionPool::default(),
             task_executor,
             client,
@@ -1451,7 +1539,8 @@ mod tests {
             false,
             NoopNetwork::default(),
         );
-        let handle = EngineApiTestHandle { chain_spec, provider, from_api: engine_rx };
+        let handle =
+            EngineApiTestHandle { chain_spec, provider, payload_builder, from_api: engine_rx };
         (handle, api)
     }
 
@@ -1472,6 +1561,7 @@ mod tests {
         #[allow(dead_code)]
         chain_spec: Arc<ChainSpec>,
         provider: Arc<MockEthProvider>,
+        payload_builder: PayloadBuilderHandle<EthEngineTypes>,
         from_api: UnboundedReceiver<BeaconEngineMessage<EthEngineTypes>>,
     }
 
@@ -1561,6 +1651,102 @@ mod tests {
         assert_matches!(res, Ok(None));
     }
 
+    // tests covering `engine_subscribePayloadUpdates`
+    mod subscribe_payload_updates {
+        use super::*;
+        use alloy_primitives::{Address, U256};
+        use alloy_rpc_types_engine::PayloadAttributes;
+        use crate::UNKNOWN_PAYLOAD_CODE;
+        use jsonrpsee_core::server::{MethodsError, Subscription};
+        use reth_payload_builder::{EthPayloadBuilderAttributes, PayloadKind};
+        use std::time::Duration;
+
+        fn setup() -> (PayloadBuilderHandle<EthEngineTypes>, RpcModule<()>) {
+            let (handle, api) = setup_engine_api();
+            (handle.payload_builder, EngineApiServer::into_rpc(api).remove_context())
+        }
+
+        async fn new_payload_job(
+            payload_builder: &PayloadBuilderHandle<EthEngineTypes>,
+        ) -> PayloadId {
+            let attributes = PayloadAttributes {
+                timestamp: 1,
+                prev_randao: B256::ZERO,
+                suggested_fee_recipient: Address::ZERO,
+                withdrawals: None,
+                parent_beacon_block_root: None,
+            };
+            payload_builder
+                .send_new_payload(EthPayloadBuilderAttributes::new(B256::ZERO, attributes))
+                .await
+                .unwrap()
+                .unwrap()
+        }
+
+        async fn next_update(
+            subscription: &mut Subscription,
+        ) -> Option<PayloadUpdate<ExecutionData>> {
+            tokio::time::timeout(Duration::from_secs(5), subscription.next())
+                .await
+                .expect("timed out waiting for payload update")
+                .map(|update| update.unwrap().0)
+        }
+
+        #[tokio::test]
+        async fn emits_better_payloads() {
+            let (payload_builder, module) = setup();
+            let payload_id = new_payload_job(&payload_builder).await;
+
+            let mut subscription = module
+                .subscribe_unbounded("engine_subscribePayloadUpdates", [payload_id])
+                .await
+                .unwrap();
+
+            // the test job reports a better payload every time the service polls it
+            let best = payload_builder.best_payload(payload_id).await.unwrap().unwrap();
+            let update = next_update(&mut subscription).await.unwrap();
+            assert_eq!(update.payload_id, payload_id);
+            assert_eq!(update.block_hash, best.block().hash());
+            assert_eq!(update.block_number, U64::from(best.block().number));
+            assert!(update.block_value > U256::ZERO);
+            assert_eq!(update.execution_payload.block_hash(), best.block().hash());
+            assert_eq!(update.blobs_bundle, best.blobs_bundle());
+        }
+
+        #[tokio::test]
+        async fn closes_when_job_is_resolved() {
+            let (payload_builder, module) = setup();
+            let payload_id = new_payload_job(&payload_builder).await;
+
+            let mut subscription = module
+                .subscribe_unbounded("engine_subscribePayloadUpdates", [payload_id])
+                .await
+                .unwrap();
+
+            // the test job is terminated once it's resolved
+            payload_builder.resolve_kind(payload_id, PayloadKind::Earliest).await.unwrap().unwrap();
+            while next_update(&mut subscription).await.is_some() {}
+
+            // the job is gone, so new subscriptions are rejected
+            let err = module
+                .subscribe_unbounded("engine_subscribePayloadUpdates", [payload_id])
+                .await
+                .unwrap_err();
+            assert_matches!(err, MethodsError::JsonRpc(err) if err.code() == UNKNOWN_PAYLOAD_CODE);
+        }
+
+        #[tokio::test]
+        async fn rejects_unknown_payload_id() {
+            let (_, module) = setup();
+
+            let err = module
+                .subscribe_unbounded("engine_subscribePayloadUpdates", [PayloadId::new([1; 8])])
+                .await
+                .unwrap_err();
+            assert_matches!(err, MethodsError::JsonRpc(err) if err.code() == UNKNOWN_PAYLOAD_CODE);
+        }
+    }
+
     // tests covering `engine_getPayloadBodiesByRange` and `engine_getPayloadBodiesByHash`
     mod get_payload_bodies {
         use super::*;
@@ -20,8 +20,9 @@ use alloy_rpc_types_eth::{
 use futures::Future;
 use hanzo_evm_errors::{ProviderError, EvmError};
 use hanzo_evm_execution::{
-    env::BlockEnvironment, execute::BlockBuilder, ConfigureEvm, Evm, EvmEnvFor, HaltReasonFor,
-    InspectorFor, TransactionEnv, TxEnvFor,
+    env::BlockEnvironment, execute::BlockBuilder, precompile_cache::PrecompileCacheMap,
+    precompiles::PrecompilesMap, ConfigureEvm, Evm, EvmEnvFor, HaltReasonFor, InspectorFor,
+    SpecFor, TransactionEnv, TxEnvFor,
 };
 use hanzo_evm_node_api::BlockBody;
 use hanzo_evm_primitives_traits::Recovered;
@@ -544,6 +545,18 @@ pub trait Call:
     /// Returns the maximum memory the EVM can allocate per RPC request.
     fn evm_memory_limit(&self) -> u64;
 
+    /// Returns the precompile cache shared with the engine, if any.
+    fn precompile_cache(&self) -> Option<&PrecompileCacheMap<SpecFor<Self::Evm>>> {
+        None
+    }
+
+    /// Wraps the pure precompiles of an EVM with the [`Call::precompile_cache`], if any.
+    fn cache_precompiles(&self, precompiles: &mut PrecompilesMap, spec: SpecFor<Self::Evm>) {
+        if let Some(precompile_cache) = self.precompile_cache() {
+            precompile_cache.cache_pure_precompiles(precompiles, spec, "rpc");
+        }
+    }
+
     /// Returns the max gas limit that the caller can afford given a transaction environment.
     fn caller_gas_allowance(
         &self,
@@ -581,7 +594,9 @@ pub trait Call:
     where
         DB: Database<Error = EvmDatabaseError<ProviderError>> + fmt::Debug,
     {
+        let spec = *evm_env.spec_id();
         let mut evm = self.hanzo_evm_config().evm_with_env(db, evm_env);
+        self.cache_precompiles(evm.precompiles_mut(), spec);
         let res = evm.transact(tx_env).map_err(Self::Error::from_evm_err)?;
 
         Ok(res)
@@ -600,7 +615,9 @@ pub trait Call:
         DB: Database<Error = EvmDatabaseError<ProviderError>> + fmt::Debug,
         I: InspectorFor<Self::Evm, DB>,
     {
+        let spec = *evm_env.spec_id();
         let mut evm = self.hanzo_evm_config().evm_with_env_and_inspector(db, evm_env, inspector);
+        self.cache_precompiles(evm.precompiles_mut(), spec);
         let res = evm.transact(tx_env).map_err(Self::Error::from_evm_err)?;
 
         Ok(res)
@@ -777,7 +794,9 @@ pub trait Call:
         DB: Database<Error = EvmDatabaseError<ProviderError>> + DatabaseCommit + core::fmt::Debug,
         I: IntoIterator<Item = Recovered<&'a ProviderTx<Self::Provider>>>,
     {
+        let spec = *evm_env.spec_id();
         let mut evm = self.hanzo_evm_config().evm_with_env(db, evm_env);
+        self.cache_precompiles(evm.precompiles_mut(), spec);
         let mut index = 0;
         for tx in transactions {
             if *tx.tx_hash() == target_tx_hash {
@@ -123,7 +123,9 @@ pub trait EstimateCall: Call {
         tx_env.set_gas_limit(tx_env.gas_limit().min(highest_gas_limit));
 
         // Create EVM instance once and reuse it throughout the entire estimation process
+        let spec = *evm_env.spec_id();
         let mut evm = self.hanzo_evm_config().evm_with_env(&mut db, evm_env);
+        self.cache_precompiles(evm.precompiles_mut(), spec);
 
         // For basic transfers, try using minimum gas before running full binary search
         if is_basic_transfer {
@@ -37,7 +37,9 @@ pub trait Trace: LoadState<Error: FromEvmError<Self::Evm>> + Call {
         DB: Database<Error = EvmDatabaseError<ProviderError>>,
         I: InspectorFor<Self::Evm, DB>,
     {
+        let spec = *evm_env.spec_id();
         let mut evm = self.hanzo_evm_config().evm_with_env_and_inspector(db, evm_env, inspector);
+        self.cache_precompiles(evm.precompiles_mut(), spec);
         evm.transact(tx_env).map_err(Self::Error::from_evm_err)
     }
 
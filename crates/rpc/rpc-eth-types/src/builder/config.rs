@@ -9,8 +9,8 @@ use crate::{
 use reqwest::Url;
 use hanzo_evm_rpc_server_types::constants::{
     default_max_tracing_requests, DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_BLOCKING_IO_REQUEST,
-    DEFAULT_MAX_BLOCKS_PER_FILTER, DEFAULT_MAX_LOGS_PER_RESPONSE, DEFAULT_MAX_SIMULATE_BLOCKS,
-    DEFAULT_MAX_TRACE_FILTER_BLOCKS, DEFAULT_PROOF_PERMITS,
+    DEFAULT_MAX_BLOCKS_PER_FILTER, DEFAULT_MAX_LOGS_PER_RESPONSE, DEFAULT_MAX_OTS_SEARCH_BLOCKS,
+    DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_MAX_TRACE_FILTER_BLOCKS, DEFAULT_PROOF_PERMITS,
     RPC_DEFAULT_SEND_RAW_TX_SYNC_TIMEOUT_SECS,
 };
 use serde::{Deserialize, Serialize};
@@ -80,6 +80,9 @@ pub struct EthConfig {
     pub max_blocking_io_requests: usize,
     /// Maximum number of blocks for `trace_filter` requests.
     pub max_trace_filter_blocks: u64,
+    /// Maximum number of blocks that are traced by `ots_searchTransactionsBefore` and
+    /// `ots_searchTransactionsAfter` requests.
+    pub max_ots_search_blocks: u64,
     /// Maximum number of blocks that could be scanned per filter request in `eth_getLogs` calls.
     pub max_blocks_per_filter: u64,
     /// Maximum number of logs that can be returned in a single response in `eth_getLogs` calls.
@@ -133,6 +136,7 @@ impl Default for EthConfig {
             max_tracing_requests: default_max_tracing_requests(),
             max_blocking_io_requests: DEFAULT_MAX_BLOCKING_IO_REQUEST,
             max_trace_filter_blocks: DEFAULT_MAX_TRACE_FILTER_BLOCKS,
+            max_ots_search_blocks: DEFAULT_MAX_OTS_SEARCH_BLOCKS,
             max_blocks_per_filter: DEFAULT_MAX_BLOCKS_PER_FILTER,
             max_logs_per_response: DEFAULT_MAX_LOGS_PER_RESPONSE,
             rpc_gas_cap: RPC_DEFAULT_GAS_CAP.into(),
@@ -187,6 +191,12 @@ impl EthConfig {
         self
     }
 
+    /// Configures the maximum number of blocks that are traced by otterscan transaction searches
+    pub const fn max_ots_search_blocks(mut self, max_blocks: u64) -> Self {
+        self.max_ots_search_blocks = max_blocks;
+        self
+    }
+
     /// Configures the maximum number of logs per response
     pub const fn max_logs_per_response(mut self, max_logs: usize) -> Self {
         self.max_logs_per_response = max_logs;
@@ -31,7 +31,8 @@ impl RpcModuleSelection {
     pub const STANDARD_MODULES: [EvmRpcModule; 3] =
         [EvmRpcModule::Eth, EvmRpcModule::Net, EvmRpcModule::Web3];
 
-    /// Returns a selection of [`EvmRpcModule`] with all [`EvmRpcModule::all_variants`].
+    /// Returns a selection of [`EvmRpcModule`] with all [`EvmRpcModule::all_variants`] except the
+    /// opt-in modules, see [`EvmRpcModule::is_opt_in`].
     pub fn all_modules() -> HashSet<EvmRpcModule> {
         EvmRpcModule::modules().into_iter().collect()
     }
@@ -149,7 +150,7 @@ impl RpcModuleSelection {
     /// Returns true if the selection contains the given module.
     pub fn contains(&self, module: &EvmRpcModule) -> bool {
         match self {
-            Self::All => true,
+            Self::All => !module.is_opt_in(),
             Self::Standard => Self::STANDARD_MODULES.contains(module),
             Self::Selection(s) => s.contains(module),
         }
@@ -157,10 +158,10 @@ impl RpcModuleSelection {
 
     /// Adds a module to the selection.
     ///
-    /// If the selection is `All`, this is a no-op.
+    /// If the selection is `All` and the module is not opt-in, this is a no-op.
     /// Otherwise, converts to a `Selection` and adds the module.
     pub fn push(&mut self, module: EvmRpcModule) {
-        if !self.is_all() {
+        if !self.is_all() || module.is_opt_in() {
             let mut modules = self.to_selection();
             modules.insert(module);
             *self = Self::Selection(modules);
@@ -169,10 +170,10 @@ impl RpcModuleSelection {
 
     /// Returns a new selection with the given module added.
     ///
-    /// If the selection is `All`, returns `All`.
+    /// If the selection is `All` and the module is not opt-in, returns `All`.
     /// Otherwise, converts to a `Selection` and adds the module.
     pub fn append(self, module: EvmRpcModule) -> Self {
-        if self.is_all() {
+        if self.is_all() && !module.is_opt_in() {
             Self::All
         } else {
             let mut modules = self.into_selection();
@@ -183,29 +184,31 @@ impl RpcModuleSelection {
 
     /// Extends the selection with modules from an iterator.
     ///
-    /// If the selection is `All`, this is a no-op.
+    /// If the selection is `All`, only opt-in modules are added.
     /// Otherwise, converts to a `Selection` and adds the modules.
     pub fn extend<I>(&mut self, iter: I)
     where
         I: IntoIterator<Item = EvmRpcModule>,
     {
-        if !self.is_all() {
-            let mut modules = self.to_selection();
-            modules.extend(iter);
-            *self = Self::Selection(modules);
-        }
+        *self = std::mem::take(self).extended(iter);
     }
 
     /// Returns a new selection with modules from an iterator added.
     ///
-    /// If the selection is `All`, returns `All`.
+    /// If the selection is `All`, returns `All` unless an opt-in module is added.
     /// Otherwise, converts to a `Selection` and adds the modules.
     pub fn extended<I>(self, iter: I) -> Self
     where
         I: IntoIterator<Item = EvmRpcModule>,
     {
         if self.is_all() {
-            Self::All
+            let opt_in = iter.into_iter().filter(EvmRpcModule::is_opt_in).collect::<Vec<_>>();
+            if opt_in.is_empty() {
+                return Self::All
+            }
+            let mut modules = Self::all_modules();
+            modules.extend(opt_in);
+            Self::Selection(modules)
         } else {
             let mut modules = self.into_selection();
             modules.extend(iter);
@@ -275,7 +278,10 @@ impl FromStr for RpcModuleSelection {
         //
         // This is a way to allow typing "all" and "ALL" and "All" and "aLl" etc.
         match first.to_lowercase().as_str() {
-            "all" => Ok(Self::All),
+            // opt-in modules can be added to `all` by name, e.g. `all,dev`
+            "all" => Ok(Self::All.extended(
+                modules.skip(1).map(EvmRpcModule::from_str).collect::<Result<Vec<_>, _>>()?,
+            )),
             "none" => Ok(Self::Selection(Default::default())),
             _ => Self::try_from_selection(modules),
         }
@@ -325,6 +331,8 @@ pub enum EvmRpcModule {
     Mev,
     /// `testing_` module
     Testing,
+    /// `dev_` module
+    Dev,
     /// Custom RPC module not part of the standard set
     #[strum(default)]
     #[serde(untagged)]
@@ -350,11 +358,17 @@ impl EvmRpcModule {
         Self::Miner,
         Self::Mev,
         Self::Testing,
+        Self::Dev,
     ];
 
-    /// Returns the number of standard variants (excludes Other)
+    /// Standard variants that are not part of [`RpcModuleSelection::All`] and have to be selected
+    /// by name.
+    const OPT_IN_VARIANTS: &'static [Self] = &[Self::Dev];
+
+    /// Returns the number of standard variants selected by [`RpcModuleSelection::All`] (excludes
+    /// Other and the opt-in variants)
     pub const fn variant_count() -> usize {
-        Self::STANDARD_VARIANTS.len()
+        Self::STANDARD_VARIANTS.len() - Self::OPT_IN_VARIANTS.len()
     }
 
     /// Returns all variant names including Other (for parsing)
@@ -372,9 +386,9 @@ impl EvmRpcModule {
         Self::STANDARD_VARIANTS
     }
 
-    /// Returns iterator over standard modules only
+    /// Returns iterator over the standard modules selected by [`RpcModuleSelection::All`]
     pub fn modules() -> impl IntoIterator<Item = Self> + Clone {
-        Self::STANDARD_VARIANTS.iter().cloned()
+        Self::STANDARD_VARIANTS.iter().filter(|module| !module.is_opt_in()).cloned()
     }
 
     /// Returns the string representation of the module.
@@ -389,6 +403,12 @@ impl EvmRpcModule {
     pub const fn is_other(&self) -> bool {
         matches!(self, Self::Other(_))
     }
+
+    /// Returns true if the module is not part of [`RpcModuleSelection::All`] and is only enabled
+    /// when selected by name, e.g. the `dev_` namespace that can rewrite the local chain.
+    pub const fn is_opt_in(&self) -> bool {
+        matches!(self, Self::Dev)
+    }
 }
 
 impl AsRef<str> for EvmRpcModule {
@@ -410,6 +430,7 @@ impl AsRef<str> for EvmRpcModule {
             Self::Miner => "miner",
             Self::Mev => "mev",
             Self::Testing => "testing",
+            Self::Dev => "dev",
         }
     }
 }
@@ -433,6 +454,7 @@ impl FromStr for EvmRpcModule {
             "miner" => Self::Miner,
             "mev" => Self::Mev,
             "testing" => Self::Testing,
+            "dev" => Self::Dev,
             // Any unknown module becomes Other
             other => Self::Other(other.to_string()),
         })
@@ -853,6 +875,27 @@ mod test {
         assert_eq!(all_selection.len(), EvmRpcModule::variant_count());
     }
 
+    #[test]
+    fn test_rpc_module_all_excludes_opt_in() {
+        let all_selection = RpcModuleSelection::All;
+        assert!(!all_selection.contains(&EvmRpcModule::Dev));
+        assert!(!all_selection.to_selection().contains(&EvmRpcModule::Dev));
+        assert!(all_selection.iter_selection().all(|module| !module.is_opt_in()));
+        assert!(!RpcModuleSelection::default_ipc_modules().contains(&EvmRpcModule::Dev));
+
+        // opt-in modules are enabled when selected by name
+        let selection = RpcModuleSelection::from_str("eth,dev").unwrap();
+        assert!(selection.contains(&EvmRpcModule::Dev));
+        let mut expected = RpcModuleSelection::all_modules();
+        expected.insert(EvmRpcModule::Dev);
+        assert_eq!(RpcModuleSelection::from_str("all,dev").unwrap().into_selection(), expected);
+        assert_eq!(
+            RpcModuleSelection::All.append(EvmRpcModule::Dev),
+            RpcModuleSelection::Selection(expected)
+        );
+        assert_eq!(RpcModuleSelection::All.append(EvmRpcModule::Eth), RpcModuleSelection::All);
+    }
+
     #[test]
     fn test_rpc_module_equality_with_other() {
         let other1 = EvmRpcModule::Other("custom".to_string());
@@ -1,4 +1,7 @@
-use std::sync::Arc;
+use std::{
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
 
 use alloy_genesis::ChainConfig;
 use alloy_rpc_types_admin::{
@@ -11,10 +14,16 @@ use hanzo_evm_chainspec::{EthChainSpec, EthereumHardfork, EthereumHardforks, For
 use hanzo_evm_network_api::{NetworkInfo, Peers};
 use hanzo_evm_network_peers::{id2pk, AnyNode, NodeRecord};
 use hanzo_evm_network_types::PeerKind;
-use hanzo_evm_rpc_api::AdminApiServer;
-use hanzo_evm_rpc_server_types::ToRpcResult;
+use hanzo_evm_rpc_api::{AdminApiServer, AdminSnapshot, AdminStorageApiServer};
+use hanzo_evm_rpc_server_types::{
+    result::{internal_rpc_err, invalid_params_rpc_err},
+    ToRpcResult,
+};
 use hanzo_evm_transaction_pool::TransactionPool;
+use reth_storage_api::StorageSnapshotProvider;
+use reth_tasks::Runtime;
 use revm_primitives::keccak256;
+use tokio::sync::oneshot;
 
 /// `admin` API implementation.
 ///
@@ -200,3 +209,94 @@ impl<N, ChainSpec, Pool> std::fmt::Debug for AdminApi<N, ChainSpec, Pool> {
         f.debug_struct("AdminApi").finish_non_exhaustive()
     }
 }
+
+/// `admin` API implementation for managing the node's storage.
+#[derive(Clone)]
+pub struct AdminStorageApi<Provider> {
+    /// The provider used to access the node's storage.
+    provider: Provider,
+    /// Spawner for the blocking snapshot tasks.
+    task_spawner: Runtime,
+    /// The directory that snapshots are written into.
+    snapshot_dir: PathBuf,
+}
+
+impl<Provider> AdminStorageApi<Provider> {
+    /// Creates a new instance of `AdminStorageApi` that writes snapshots into `snapshot_dir`.
+    pub const fn new(provider: Provider, task_spawner: Runtime, snapshot_dir: PathBuf) -> Self {
+        Self { provider, task_spawner, snapshot_dir }
+    }
+}
+
+/// Resolves the requested snapshot `path` in `snapshot_dir`.
+///
+/// Fails if `path` is empty, absolute or leaves `snapshot_dir`.
+fn snapshot_path(snapshot_dir: &Path, path: &Path) -> RpcResult<PathBuf> {
+    if path.as_os_str().is_empty() ||
+        !path.components().all(|component| matches!(component, Component::Normal(_)))
+    {
+        return Err(invalid_params_rpc_err(format!(
+            "snapshot path {} must be relative to the snapshot directory",
+            path.display()
+        )))
+    }
+    Ok(snapshot_dir.join(path))
+}
+
+#[async_trait]
+impl<Provider> AdminStorageApiServer for AdminStorageApi<Provider>
+where
+    Provider: StorageSnapshotProvider + Clone + 'static,
+{
+    /// Handler for `admin_createSnapshot`
+    async fn create_snapshot(&self, path: PathBuf) -> RpcResult<AdminSnapshot> {
+        let path = snapshot_path(&self.snapshot_dir, &path)?;
+        let snapshot_dir = self.snapshot_dir.clone();
+        let provider = self.provider.clone();
+        let (tx, rx) = oneshot::channel();
+
+        self.task_spawner.spawn_blocking_task(async move {
+            let result = std::fs::create_dir_all(&snapshot_dir)
+                .map_err(|err| internal_rpc_err(err.to_string()))
+                .and_then(|()| {
+                    provider
+                        .create_storage_snapshot(&path)
+                        .map_err(|err| internal_rpc_err(err.to_string()))
+                })
+                .map(|manifest| AdminSnapshot {
+                    path,
+                    block_number: manifest.block_number,
+                    block_hash: manifest.block_hash,
+                    files: manifest.files.len(),
+                    size: manifest.files.iter().map(|file| file.size).sum(),
+                });
+            let _ = tx.send(result);
+        });
+
+        rx.await.map_err(|_| internal_rpc_err("Internal blocking task error"))?
+    }
+}
+
+impl<Provider> std::fmt::Debug for AdminStorageApi<Provider> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AdminStorageApi").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_path_stays_in_snapshot_dir() {
+        let dir = Path::new("/data/snapshots");
+        assert_eq!(
+            snapshot_path(dir, Path::new("daily/1")).unwrap(),
+            PathBuf::from("/data/snapshots/daily/1")
+        );
+
+        for path in ["", "/tmp/snapshot", "../db", "daily/../../db", "./daily"] {
+            assert!(snapshot_path(dir, Path::new(path)).is_err(), "{path}");
+        }
+    }
+}
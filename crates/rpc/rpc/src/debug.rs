@@ -1,3 +1,4 @@
+use crate::tracer_timeout::{tracer_timeout, TracerDeadline};
 use alloy_consensus::{transaction::TxHashRef, BlockHeader};
 use alloy_eip7928::BlockAccessList;
 use alloy_eips::{eip2718::Encodable2718, BlockId, BlockNumberOrTag};
@@ -113,6 +114,7 @@ where
         evm_env: EvmEnvFor<Eth::Evm>,
         opts: GethDebugTracingOptions,
     ) -> Result<Vec<TraceResult>, Eth::Error> {
+        let timeout = tracer_timeout(&opts).map_err(Eth::Error::from_eth_err)?;
         self.eth_api()
             .spawn_with_state_at_block(block.parent_hash(), move |eth_api, mut db| {
                 let mut results = Vec::with_capacity(block.body().transactions().len());
@@ -120,18 +122,25 @@ where
                 eth_api.apply_pre_execution_changes(&block, &mut db)?;
 
                 let mut transactions = block.transactions_recovered().enumerate().peekable();
-                let mut inspector = DebugInspector::new(opts).map_err(Eth::Error::from_eth_err)?;
+                let mut inspector = TracerDeadline::new(
+                    DebugInspector::new(opts).map_err(Eth::Error::from_eth_err)?,
+                    timeout,
+                );
                 while let Some((index, tx)) = transactions.next() {
                     let tx_hash = *tx.tx_hash();
                     let tx_env = eth_api.hanzo_evm_config().tx_env(tx);
 
+                    // the timeout applies to every transaction on its own
+                    inspector.restart();
                     let res = eth_api.inspect(
                         &mut db,
                         evm_env.clone(),
                         tx_env.clone(),
                         &mut inspector,
                     )?;
+                    inspector.ensure_not_timed_out().map_err(Eth::Error::from_eth_err)?;
                     let result = inspector
+                        .inner_mut()
                         .get_result(
                             Some(TransactionContext {
                                 block_hash: Some(block.hash()),
@@ -144,10 +153,11 @@ where
                             &mut db,
                         )
                         .map_err(Eth::Error::from_eth_err)?;
+                    inspector.ensure_not_timed_out().map_err(Eth::Error::from_eth_err)?;
 
                     results.push(TraceResult::Success { result, tx_hash: Some(tx_hash) });
                     if transactions.peek().is_some() {
-                        inspector.fuse().map_err(Eth::Error::from_eth_err)?;
+                        inspector.inner_mut().fuse().map_err(Eth::Error::from_eth_err)?;
                         // need to apply the state changes of this transaction before executing the
                         // next transaction
                         db.commit(res.state)
@@ -227,6 +237,7 @@ where
             Some(res) => res,
         };
         let (evm_env, _) = self.eth_api().evm_env_at(block.hash().into()).await?;
+        let timeout = tracer_timeout(&opts).map_err(Eth::Error::from_eth_err)?;
 
         // we need to get the state of the parent block because we're essentially replaying the
         // block the transaction is included in
@@ -252,10 +263,15 @@ where
 
                 let tx_env = eth_api.hanzo_evm_config().tx_env(&tx);
 
-                let mut inspector = DebugInspector::new(opts).map_err(Eth::Error::from_eth_err)?;
+                let mut inspector = TracerDeadline::new(
+                    DebugInspector::new(opts).map_err(Eth::Error::from_eth_err)?,
+                    timeout,
+                );
                 let res =
                     eth_api.inspect(&mut db, evm_env.clone(), tx_env.clone(), &mut inspector)?;
+                inspector.ensure_not_timed_out().map_err(Eth::Error::from_eth_err)?;
                 let trace = inspector
+                    .inner_mut()
                     .get_result(
                         Some(TransactionContext {
                             block_hash: Some(block_hash),
@@ -268,6 +284,7 @@ where
                         &mut db,
                     )
                     .map_err(Eth::Error::from_eth_err)?;
+                inspector.ensure_not_timed_out().map_err(Eth::Error::from_eth_err)?;
 
                 Ok(trace)
             })
@@ -305,20 +322,26 @@ where
                 .await;
         }
 
+        let timeout = tracer_timeout(&tracing_options).map_err(Eth::Error::from_eth_err)?;
         let this = self.clone();
         self.eth_api()
             .spawn_with_call_at(call, at, overrides, move |db, evm_env, tx_env| {
-                let mut inspector =
-                    DebugInspector::new(tracing_options).map_err(Eth::Error::from_eth_err)?;
+                let mut inspector = TracerDeadline::new(
+                    DebugInspector::new(tracing_options).map_err(Eth::Error::from_eth_err)?,
+                    timeout,
+                );
                 let res = this.eth_api().inspect(
                     &mut *db,
                     evm_env.clone(),
                     tx_env.clone(),
                     &mut inspector,
                 )?;
+                inspector.ensure_not_timed_out().map_err(Eth::Error::from_eth_err)?;
                 let trace = inspector
+                    .inner_mut()
                     .get_result(None, &tx_env, &evm_env.block_env, &res, db)
                     .map_err(Eth::Error::from_eth_err)?;
+                inspector.ensure_not_timed_out().map_err(Eth::Error::from_eth_err)?;
                 Ok(trace)
             })
             .await
@@ -353,6 +376,7 @@ where
         }
 
         let (evm_env, _) = self.eth_api().evm_env_at(block.hash().into()).await?;
+        let timeout = tracer_timeout(&tracing_options).map_err(Eth::Error::from_eth_err)?;
 
         // execute after the parent block, replaying `tx_index` transactions
         let state_at = block.parent_hash();
@@ -373,13 +397,18 @@ where
                 let (evm_env, tx_env) =
                     eth_api.prepare_call_env(evm_env, call, &mut db, overrides)?;
 
-                let mut inspector =
-                    DebugInspector::new(tracing_options).map_err(Eth::Error::from_eth_err)?;
+                let mut inspector = TracerDeadline::new(
+                    DebugInspector::new(tracing_options).map_err(Eth::Error::from_eth_err)?,
+                    timeout,
+                );
                 let res =
                     eth_api.inspect(&mut db, evm_env.clone(), tx_env.clone(), &mut inspector)?;
+                inspector.ensure_not_timed_out().map_err(Eth::Error::from_eth_err)?;
                 let trace = inspector
+                    .inner_mut()
                     .get_result(None, &tx_env, &evm_env.block_env, &res, &mut db)
                     .map_err(Eth::Error::from_eth_err)?;
+                inspector.ensure_not_timed_out().map_err(Eth::Error::from_eth_err)?;
 
                 Ok(trace)
             })
@@ -411,6 +440,7 @@ where
         let opts = opts.unwrap_or_default();
         let block = block.ok_or(EthApiError::HeaderNotFound(target_block))?;
         let GethDebugTracingCallOptions { tracing_options, mut state_overrides, .. } = opts;
+        let timeout = tracer_timeout(&tracing_options).map_err(Eth::Error::from_eth_err)?;
 
         // we're essentially replaying the transactions in the block here, hence we need the state
         // that points to the beginning of the block, which is the state at the parent block
@@ -448,8 +478,11 @@ where
 
                 // Trace all bundles
                 let mut bundles = bundles.into_iter().peekable();
-                let mut inspector = DebugInspector::new(tracing_options.clone())
-                    .map_err(Eth::Error::from_eth_err)?;
+                let mut inspector = TracerDeadline::new(
+                    DebugInspector::new(tracing_options.clone())
+                        .map_err(Eth::Error::from_eth_err)?,
+                    timeout,
+                );
                 while let Some(bundle) = bundles.next() {
                     let mut results = Vec::with_capacity(bundle.transactions.len());
                     let Bundle { transactions, block_override } = bundle;
@@ -465,20 +498,24 @@ where
                         let (evm_env, tx_env) =
                             eth_api.prepare_call_env(evm_env.clone(), tx, &mut db, overrides)?;
 
+                        inspector.restart();
                         let res = eth_api.inspect(
                             &mut db,
                             evm_env.clone(),
                             tx_env.clone(),
                             &mut inspector,
                         )?;
+                        inspector.ensure_not_timed_out().map_err(Eth::Error::from_eth_err)?;
                         let trace = inspector
+                            .inner_mut()
                             .get_result(None, &tx_env, &evm_env.block_env, &res, &mut db)
                             .map_err(Eth::Error::from_eth_err)?;
+                        inspector.ensure_not_timed_out().map_err(Eth::Error::from_eth_err)?;
 
                         // If there is more transactions, commit the database
                         // If there is no transactions, but more bundles, commit to the database too
                         if transactions.peek().is_some() || bundles.peek().is_some() {
-                            inspector.fuse().map_err(Eth::Error::from_eth_err)?;
+                            inspector.inner_mut().fuse().map_err(Eth::Error::from_eth_err)?;
                             db.commit(res.state);
                         }
                         results.push(trace);
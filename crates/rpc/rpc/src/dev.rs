@@ -0,0 +1,35 @@
+use alloy_primitives::Bytes;
+use async_trait::async_trait;
+use hanzo_evm_engine_local::LocalMinerHandle;
+use hanzo_evm_rpc_api::{DevApiServer, DevReorg};
+use hanzo_evm_rpc_server_types::result::internal_rpc_err;
+use jsonrpsee::core::RpcResult;
+
+/// `dev` API implementation.
+///
+/// This type controls the local miner of a node in dev mode.
+#[derive(Clone, Debug)]
+pub struct DevApi {
+    /// Handle to the local miner.
+    miner: LocalMinerHandle,
+}
+
+impl DevApi {
+    /// Creates a new instance of `DevApi`.
+    pub const fn new(miner: LocalMinerHandle) -> Self {
+        Self { miner }
+    }
+}
+
+#[async_trait]
+impl DevApiServer for DevApi {
+    /// Handler for `dev_reorg`
+    async fn reorg(&self, depth: usize, transactions: Option<Vec<Bytes>>) -> RpcResult<DevReorg> {
+        let reorg = self
+            .miner
+            .reorg(depth, transactions.unwrap_or_default())
+            .await
+            .map_err(|err| internal_rpc_err(format!("{err:#}")))?;
+        Ok(DevReorg { reorged_blocks: reorg.reorged, new_blocks: reorg.blocks })
+    }
+}
@@ -4,6 +4,7 @@ use crate::{eth::core::EthApiInner, EthApi};
 use alloy_network::Ethereum;
 use hanzo_evm_chain_state::CanonStateSubscriptions;
 use hanzo_evm_chainspec::ChainSpecProvider;
+use hanzo_evm_execution::{precompile_cache::PrecompileCacheMap, SpecFor};
 use hanzo_evm_primitives_traits::HeaderTy;
 use hanzo_evm_rpc_convert::{RpcConvert, RpcConverter};
 use hanzo_evm_rpc_eth_api::{
@@ -48,6 +49,7 @@ pub struct EthApiBuilder<N: RpcNodeCore, Rpc, NextEnv = ()> {
     send_raw_transaction_sync_timeout: Duration,
     evm_memory_limit: u64,
     force_blob_sidecar_upcasting: bool,
+    precompile_cache: Option<PrecompileCacheMap<SpecFor<N::Evm>>>,
 }
 
 impl<Provider, Pool, Network, EvmConfig, ChainSpec>
@@ -101,6 +103,7 @@ impl<N: RpcNodeCore, Rpc, NextEnv> EthApiBuilder<N, Rpc, NextEnv> {
             send_raw_transaction_sync_timeout,
             evm_memory_limit,
             force_blob_sidecar_upcasting,
+            precompile_cache,
         } = self;
         EthApiBuilder {
             components,
@@ -124,6 +127,7 @@ impl<N: RpcNodeCore, Rpc, NextEnv> EthApiBuilder<N, Rpc, NextEnv> {
             send_raw_transaction_sync_timeout,
             evm_memory_limit,
             force_blob_sidecar_upcasting,
+            precompile_cache,
         }
     }
 }
@@ -158,6 +162,7 @@ where
             send_raw_transaction_sync_timeout: Duration::from_secs(30),
             evm_memory_limit: (1 << 32) - 1,
             force_blob_sidecar_upcasting: false,
+            precompile_cache: None,
         }
     }
 }
@@ -199,6 +204,7 @@ where
             send_raw_transaction_sync_timeout,
             evm_memory_limit,
             force_blob_sidecar_upcasting,
+            precompile_cache,
         } = self;
         EthApiBuilder {
             components,
@@ -222,6 +228,7 @@ where
             send_raw_transaction_sync_timeout,
             evm_memory_limit,
             force_blob_sidecar_upcasting,
+            precompile_cache,
         }
     }
 
@@ -252,6 +259,7 @@ where
             send_raw_transaction_sync_timeout,
             evm_memory_limit,
             force_blob_sidecar_upcasting,
+            precompile_cache,
         } = self;
         EthApiBuilder {
             components,
@@ -275,6 +283,7 @@ where
             send_raw_transaction_sync_timeout,
             evm_memory_limit,
             force_blob_sidecar_upcasting,
+            precompile_cache,
         }
     }
 
@@ -511,6 +520,7 @@ where
             send_raw_transaction_sync_timeout,
             evm_memory_limit,
             force_blob_sidecar_upcasting,
+            precompile_cache,
         } = self;
 
         let provider = components.provider().clone();
@@ -563,6 +573,7 @@ where
             send_raw_transaction_sync_timeout,
             evm_memory_limit,
             force_blob_sidecar_upcasting,
+            precompile_cache,
         )
     }
 
@@ -599,4 +610,14 @@ where
         self.force_blob_sidecar_upcasting = force;
         self
     }
+
+    /// Sets the precompile cache of the engine, which is then used for `eth_call`,
+    /// `eth_estimateGas` and tracing.
+    pub fn precompile_cache(
+        mut self,
+        precompile_cache: PrecompileCacheMap<SpecFor<N::Evm>>,
+    ) -> Self {
+        self.precompile_cache = Some(precompile_cache);
+        self
+    }
 }
@@ -12,6 +12,7 @@ use alloy_rpc_client::RpcClient;
 use derive_more::Deref;
 use hanzo_evm_chainspec::{ChainSpec, ChainSpecProvider};
 use hanzo_evm_eth_execution::EthEvmConfig;
+use hanzo_evm_execution::{precompile_cache::PrecompileCacheMap, SpecFor};
 use hanzo_evm_network_api::noop::NoopNetwork;
 use hanzo_evm_node_api::{FullNodeComponents, FullNodeTypes};
 use hanzo_evm_rpc_convert::{RpcConvert, RpcConverter};
@@ -285,6 +286,9 @@ pub struct EthApiInner<N: RpcNodeCore, Rpc: RpcConvert> {
 
     /// Whether to force upcasting EIP-4844 blob sidecars to EIP-7594 format when Osaka is active.
     force_blob_sidecar_upcasting: bool,
+
+    /// Precompile cache shared with the engine, if any.
+    precompile_cache: Option<PrecompileCacheMap<SpecFor<N::Evm>>>,
 }
 
 impl<N, Rpc> EthApiInner<N, Rpc>
@@ -314,6 +318,7 @@ where
         send_raw_transaction_sync_timeout: Duration,
         evm_memory_limit: u64,
         force_blob_sidecar_upcasting: bool,
+        precompile_cache: Option<PrecompileCacheMap<SpecFor<N::Evm>>>,
     ) -> Self {
         let signers = parking_lot::RwLock::new(Default::default());
         // get the block number of the latest block
@@ -359,6 +364,7 @@ where
             blob_sidecar_converter: BlobSidecarConverter::new(),
             evm_memory_limit,
             force_blob_sidecar_upcasting,
+            precompile_cache,
         }
     }
 }
@@ -557,6 +563,12 @@ where
     pub const fn force_blob_sidecar_upcasting(&self) -> bool {
         self.force_blob_sidecar_upcasting
     }
+
+    /// Returns the precompile cache shared with the engine, if any.
+    #[inline]
+    pub const fn precompile_cache(&self) -> Option<&PrecompileCacheMap<SpecFor<N::Evm>>> {
+        self.precompile_cache.as_ref()
+    }
 }
 
 #[cfg(test)]
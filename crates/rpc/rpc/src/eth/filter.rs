@@ -28,13 +28,13 @@ use hanzo_evm_rpc_eth_types::{
 };
 use hanzo_evm_rpc_server_types::{result::rpc_error_with_code, ToRpcResult};
 use hanzo_evm_storage_api::{
-    BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, HeaderProvider, ProviderBlock,
-    ProviderReceipt, ReceiptProvider,
+    errors::provider::ProviderResult, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader,
+    HeaderProvider, LogIndexReader, ProviderBlock, ProviderReceipt, ReceiptProvider,
 };
 use reth_tasks::Runtime;
 use reth_transaction_pool::{NewSubpoolTransactionStream, PoolTransaction, TransactionPool};
 use std::{
-    collections::{HashMap, VecDeque},
+    collections::{BTreeSet, HashMap, VecDeque},
     fmt,
     iter::{Peekable, StepBy},
     ops::RangeInclusive,
@@ -137,6 +137,28 @@ where
     /// let filter = EthFilter::new(eth_api, Default::default(), Runtime::test());
     /// ```
     pub fn new(eth_api: Eth, config: EthFilterConfig, task_spawner: Runtime) -> Self {
+        Self::with_inner(eth_api, config, task_spawner, None)
+    }
+
+    /// Creates a new, shareable instance that uses the given [`LogIndexReader`] to find the blocks
+    /// with matching logs for `eth_getLogs`, instead of checking the bloom filter of every header.
+    ///
+    /// See also [`EthFilter::new`].
+    pub fn new_with_log_index(
+        eth_api: Eth,
+        config: EthFilterConfig,
+        task_spawner: Runtime,
+        log_index: Arc<dyn LogIndexReader>,
+    ) -> Self {
+        Self::with_inner(eth_api, config, task_spawner, Some(log_index))
+    }
+
+    fn with_inner(
+        eth_api: Eth,
+        config: EthFilterConfig,
+        task_spawner: Runtime,
+        log_index: Option<Arc<dyn LogIndexReader>>,
+    ) -> Self {
         let EthFilterConfig { max_blocks_per_filter, max_logs_per_response, stale_filter_ttl } =
             config;
         let inner = EthFilterInner {
@@ -147,6 +169,7 @@ where
             task_spawner,
             stale_filter_ttl,
             query_limits: QueryLimits { max_blocks_per_filter, max_logs_per_response },
+            log_index,
         };
 
         let eth_filter = Self { inner: Arc::new(inner) };
@@ -443,6 +466,8 @@ struct EthFilterInner<Eth: EthApiTypes> {
     task_spawner: Runtime,
     /// Duration since the last filter poll, after which the filter is considered stale
     stale_filter_ttl: Duration,
+    /// Optional index of the blocks with logs of an address or topic
+    log_index: Option<Arc<dyn LogIndexReader>>,
 }
 
 impl<Eth> EthFilterInner<Eth>
@@ -668,33 +693,48 @@ where
         // get current chain tip to determine processing mode
         let chain_tip = self.provider().best_block_number()?;
 
-        // first collect all headers that match the bloom filter for cached mode decision
-        for (from, to) in
-            BlockRangeInclusiveIter::new(from_block..=to_block, self.max_headers_range)
-        {
-            let headers = self.provider().headers_range(from..=to)?;
-
-            let mut headers_iter = headers.into_iter().peekable();
+        let indexed_blocks = match &self.log_index {
+            Some(index) => indexed_log_blocks(index.as_ref(), filter, from_block..=to_block)?,
+            None => None,
+        };
 
-            while let Some(header) = headers_iter.next() {
-                if !filter.matches_bloom(header.logs_bloom()) {
-                    continue
+        // first collect all headers that match the bloom filter for cached mode decision
+        if let Some(blocks) = indexed_blocks {
+            // the index doesn't consider the topic positions, so the bloom filter is still checked
+            for number in blocks {
+                let Some(header) = self.provider().sealed_header(number)? else { continue };
+                if filter.matches_bloom(header.logs_bloom()) {
+                    matching_headers.push(header);
                 }
+            }
+        } else {
+            for (from, to) in
+                BlockRangeInclusiveIter::new(from_block..=to_block, self.max_headers_range)
+            {
+                let headers = self.provider().headers_range(from..=to)?;
 
-                let current_number = header.number();
+                let mut headers_iter = headers.into_iter().peekable();
 
-                let block_hash = match headers_iter.peek() {
-                    Some(next_header) if next_header.number() == current_number + 1 => {
-                        // Headers are consecutive, use the more efficient parent_hash
-                        next_header.parent_hash()
-                    }
-                    _ => {
-                        // Headers not consecutive or last header, calculate hash
-                        header.hash_slow()
+                while let Some(header) = headers_iter.next() {
+                    if !filter.matches_bloom(header.logs_bloom()) {
+                        continue
                     }
-                };
 
-                matching_headers.push(SealedHeader::new(header, block_hash));
+                    let current_number = header.number();
+
+                    let block_hash = match headers_iter.peek() {
+                        Some(next_header) if next_header.number() == current_number + 1 => {
+                            // Headers are consecutive, use the more efficient parent_hash
+                            next_header.parent_hash()
+                        }
+                        _ => {
+                            // Headers not consecutive or last header, calculate hash
+                            header.hash_slow()
+                        }
+                    };
+
+                    matching_headers.push(SealedHeader::new(header, block_hash));
+                }
             }
         }
 
@@ -930,6 +970,56 @@ impl Iterator for BlockRangeInclusiveIter {
     }
 }
 
+/// Returns the blocks of the range that can contain logs matching the address and topic
+/// restrictions of the filter, in ascending order.
+///
+/// Blocks outside of the range of the index are always included because they are not indexed:
+/// blocks above it are not indexed yet, blocks below it have pruned receipts.
+///
+/// Returns `None` if the index can't narrow down the range, in which case the bloom filter of
+/// every header of the range has to be checked.
+fn indexed_log_blocks(
+    index: &dyn LogIndexReader,
+    filter: &Filter,
+    range: RangeInclusive<u64>,
+) -> ProviderResult<Option<Vec<u64>>> {
+    let (start, end) = range.into_inner();
+    let Some(indexed) = index.log_index_range()? else { return Ok(None) };
+    if start > *indexed.end() || end < *indexed.start() {
+        return Ok(None)
+    }
+    let indexed_start = start.max(*indexed.start());
+    let indexed_end = end.min(*indexed.end());
+    let indexed = indexed_start..=indexed_end;
+
+    // every restriction must be met, so the blocks of all restrictions are intersected
+    let mut blocks: Option<BTreeSet<u64>> = None;
+    let mut restrict = |matching: BTreeSet<u64>| {
+        blocks = Some(match blocks.take() {
+            Some(blocks) => &blocks & &matching,
+            None => matching,
+        });
+    };
+
+    if !filter.address.is_empty() {
+        let mut matching = BTreeSet::new();
+        for address in filter.address.iter() {
+            matching.extend(index.log_address_blocks(*address, indexed.clone())?);
+        }
+        restrict(matching);
+    }
+    for topic in filter.topics.iter().filter(|topic| !topic.is_empty()) {
+        let mut matching = BTreeSet::new();
+        for value in topic.iter() {
+            matching.extend(index.log_topic_blocks(*value, indexed.clone())?);
+        }
+        restrict(matching);
+    }
+
+    let Some(blocks) = blocks else { return Ok(None) };
+    Ok(Some((start..indexed_start).chain(blocks).chain(indexed_end + 1..=end).collect()))
+}
+
 /// Errors that can occur in the handler implementation
 #[derive(Debug, thiserror::Error)]
 pub enum EthFilterError {
@@ -1334,7 +1424,7 @@ mod tests {
     use super::*;
     use crate::{eth::EthApi, EthApiBuilder};
     use alloy_network::Ethereum;
-    use alloy_primitives::FixedBytes;
+    use alloy_primitives::{Address, FixedBytes, B256};
     use rand::Rng;
     use reth_chainspec::{ChainSpec, ChainSpecProvider};
     use reth_ethereum_primitives::TxType;
@@ -1371,6 +1461,87 @@ mod tests {
         assert_eq!(end, *range.end());
     }
 
+    #[derive(Debug, Default)]
+    struct MockLogIndex {
+        range: Option<RangeInclusive<u64>>,
+        addresses: HashMap<Address, Vec<u64>>,
+        topics: HashMap<B256, Vec<u64>>,
+    }
+
+    impl LogIndexReader for MockLogIndex {
+        fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<u64>>> {
+            Ok(self.range.clone())
+        }
+
+        fn log_address_blocks(
+            &self,
+            address: Address,
+            range: RangeInclusive<u64>,
+        ) -> ProviderResult<Vec<u64>> {
+            let blocks = self.addresses.get(&address).cloned().unwrap_or_default();
+            Ok(blocks.into_iter().filter(|block| range.contains(block)).collect())
+        }
+
+        fn log_topic_blocks(
+            &self,
+            topic: B256,
+            range: RangeInclusive<u64>,
+        ) -> ProviderResult<Vec<u64>> {
+            let blocks = self.topics.get(&topic).cloned().unwrap_or_default();
+            Ok(blocks.into_iter().filter(|block| range.contains(block)).collect())
+        }
+    }
+
+    #[test]
+    fn test_indexed_log_blocks() {
+        let (token, pool) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let (transfer, swap) = (B256::repeat_byte(3), B256::repeat_byte(4));
+        let index = MockLogIndex {
+            range: Some(0..=10),
+            addresses: HashMap::from([(token, vec![1, 4, 8]), (pool, vec![4, 5, 9])]),
+            topics: HashMap::from([(transfer, vec![1, 4, 5, 8]), (swap, vec![9])]),
+        };
+
+        let by_address = Filter::new().address(vec![token, pool]);
+        assert_eq!(
+            indexed_log_blocks(&index, &by_address, 0..=12).unwrap(),
+            Some(vec![1, 4, 5, 8, 9, 11, 12])
+        );
+
+        let by_address_and_topic = Filter::new().address(pool).event_signature(transfer);
+        assert_eq!(
+            indexed_log_blocks(&index, &by_address_and_topic, 0..=10).unwrap(),
+            Some(vec![4, 5])
+        );
+
+        let by_topics = Filter::new().event_signature(vec![transfer, swap]).topic1(swap);
+        assert_eq!(indexed_log_blocks(&index, &by_topics, 2..=9).unwrap(), Some(vec![9]));
+
+        // no restriction on the logs
+        assert_eq!(indexed_log_blocks(&index, &Filter::new(), 0..=5).unwrap(), None);
+
+        // the range is above the index
+        assert_eq!(indexed_log_blocks(&index, &by_address, 11..=12).unwrap(), None);
+    }
+
+    #[test]
+    fn test_indexed_log_blocks_below_lower_bound() {
+        let token = Address::repeat_byte(1);
+        let index = MockLogIndex {
+            range: Some(5..=10),
+            addresses: HashMap::from([(token, vec![6, 9])]),
+            ..Default::default()
+        };
+        let by_address = Filter::new().address(token);
+
+        // the blocks with pruned receipts are checked against their bloom filter
+        assert_eq!(
+            indexed_log_blocks(&index, &by_address, 3..=12).unwrap(),
+            Some(vec![3, 4, 6, 9, 11, 12])
+        );
+        assert_eq!(indexed_log_blocks(&index, &by_address, 0..=4).unwrap(), None);
+    }
+
     // Helper function to create a test EthApi instance
     #[expect(clippy::type_complexity)]
     fn build_test_eth_api(
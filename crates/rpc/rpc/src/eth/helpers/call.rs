@@ -1,6 +1,7 @@
 //! Contains RPC handler implementations specific to endpoints that call/execute within evm.
 
 use crate::EthApi;
+use hanzo_evm_execution::{precompile_cache::PrecompileCacheMap, SpecFor};
 use hanzo_evm_rpc_convert::RpcConvert;
 use hanzo_evm_rpc_eth_api::{
     helpers::{estimate::EstimateCall, Call, EthCall},
@@ -36,6 +37,11 @@ where
     fn evm_memory_limit(&self) -> u64 {
         self.inner.evm_memory_limit()
     }
+
+    #[inline]
+    fn precompile_cache(&self) -> Option<&PrecompileCacheMap<SpecFor<N::Evm>>> {
+        self.inner.precompile_cache()
+    }
 }
 
 impl<N, Rpc> EstimateCall for EthApi<N, Rpc>
@@ -28,6 +28,7 @@
 mod admin;
 mod aliases;
 mod debug;
+mod dev;
 mod engine;
 pub mod eth;
 mod miner;
@@ -37,13 +38,16 @@ mod evm;
 mod rpc;
 mod testing;
 mod trace;
+mod trace_index;
+mod tracer_timeout;
 mod txpool;
 mod validation;
 mod web3;
 
-pub use admin::AdminApi;
+pub use admin::{AdminApi, AdminStorageApi};
 pub use aliases::*;
 pub use debug::DebugApi;
+pub use dev::DevApi;
 pub use engine::{EngineApi, EngineEthApi};
 pub use eth::{helpers::SyncListener, EthApi, EthApiBuilder, EthBundle, EthFilter, EthPubSub};
 pub use miner::MinerApi;
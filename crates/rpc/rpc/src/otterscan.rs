@@ -1,9 +1,11 @@
+use crate::trace_index::{indexed_blocks, IndexedBlocks};
 use alloy_consensus::{BlockHeader, Typed2718};
 use alloy_eips::{eip1898::LenientBlockNumberOrTag, BlockId};
 use alloy_network::{ReceiptResponse, TransactionResponse};
-use alloy_primitives::{Address, Bytes, TxHash, B256, U256};
-use alloy_rpc_types_eth::{BlockTransactions, TransactionReceipt};
+use alloy_primitives::{map::HashSet, Address, Bytes, TxHash, B256, U256};
+use alloy_rpc_types_eth::{BlockTransactions, Transaction, TransactionReceipt};
 use alloy_rpc_types_trace::{
+    filter::TraceFilterMode,
     otterscan::{
         BlockDetails, ContractCreator, InternalOperation, OperationType, OtsBlockTransactions,
         OtsReceipt, OtsTransactionReceipt, TraceEntry, TransactionsWithReceipts,
@@ -16,16 +18,20 @@ use hanzo_evm_primitives_traits::TxTy;
 use hanzo_evm_rpc_api::{EthApiServer, OtterscanServer};
 use hanzo_evm_rpc_convert::RpcTxReq;
 use hanzo_evm_rpc_eth_api::{
-    helpers::{EthTransactions, TraceExt},
+    helpers::{EthTransactions, SpawnBlocking, TraceExt},
     FullEthApiTypes, RpcBlock, RpcHeader, RpcReceipt, RpcTransaction,
 };
 use hanzo_evm_rpc_eth_types::{utils::binary_search, EthApiError};
-use hanzo_evm_rpc_server_types::result::internal_rpc_err;
+use hanzo_evm_rpc_server_types::{
+    constants::DEFAULT_MAX_OTS_SEARCH_BLOCKS, result::internal_rpc_err,
+};
+use hanzo_evm_storage_api::TraceIndexReader;
 use revm::context_interface::result::ExecutionResult;
 use revm_inspectors::{
     tracing::{types::CallTraceNode, TracingInspectorConfig},
     transfer::{TransferInspector, TransferKind},
 };
+use std::{ops::RangeInclusive, sync::Arc};
 
 const API_LEVEL: u64 = 8;
 
@@ -33,12 +39,30 @@ const API_LEVEL: u64 = 8;
 #[derive(Debug)]
 pub struct OtterscanApi<Eth> {
     eth: Eth,
+    /// The index that is required to search the transactions of an address.
+    trace_index: Option<Arc<dyn TraceIndexReader>>,
+    /// The maximum number of blocks that are traced by a transaction search.
+    max_search_blocks: u64,
 }
 
 impl<Eth> OtterscanApi<Eth> {
     /// Creates a new instance of `Otterscan`.
     pub const fn new(eth: Eth) -> Self {
-        Self { eth }
+        Self { eth, trace_index: None, max_search_blocks: DEFAULT_MAX_OTS_SEARCH_BLOCKS }
+    }
+
+    /// Configures the [`TraceIndexReader`] that is used by `ots_searchTransactionsBefore` and
+    /// `ots_searchTransactionsAfter`, which are unavailable without it.
+    pub fn with_trace_index(mut self, trace_index: Arc<dyn TraceIndexReader>) -> Self {
+        self.trace_index = Some(trace_index);
+        self
+    }
+
+    /// Configures the maximum number of blocks that are traced by `ots_searchTransactionsBefore`
+    /// and `ots_searchTransactionsAfter`.
+    pub const fn with_max_search_blocks(mut self, max_search_blocks: u64) -> Self {
+        self.max_search_blocks = max_search_blocks;
+        self
     }
 }
 
@@ -62,6 +86,33 @@ where
 
         Ok(BlockDetails::new(block, Default::default(), U256::from(total_fees)))
     }
+
+    /// Returns the blocks of the range in which the address appeared in any call frame, in
+    /// ascending order.
+    ///
+    /// Blocks above the range of the trace index are always included, blocks below it are pruned
+    /// and skipped.
+    fn address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<u64>,
+    ) -> RpcResult<IndexedBlocks> {
+        let Some(index) = &self.trace_index else {
+            return Err(internal_rpc_err("searching transactions requires a trace index"))
+        };
+        if index.trace_index_range().map_err(EthApiError::from)?.is_none() {
+            return Err(internal_rpc_err("the trace index is empty"))
+        }
+        let blocks = indexed_blocks(
+            index.as_ref(),
+            &[address],
+            &[address],
+            TraceFilterMode::Union,
+            range.clone(),
+        )
+        .map_err(EthApiError::from)?;
+        Ok(blocks.unwrap_or_else(|| IndexedBlocks::all(range)))
+    }
 }
 
 #[async_trait]
@@ -249,32 +300,7 @@ where
         let receipts = receipts
             .drain(page_start..page_end)
             .zip(transactions.iter().map(Typed2718::ty))
-            .map(|(receipt, tx_ty)| {
-                let inner = OtsReceipt {
-                    status: receipt.status(),
-                    cumulative_gas_used: receipt.cumulative_gas_used(),
-                    logs: None,
-                    logs_bloom: None,
-                    r#type: tx_ty,
-                };
-
-                let receipt = TransactionReceipt {
-                    inner,
-                    transaction_hash: receipt.transaction_hash(),
-                    transaction_index: receipt.transaction_index(),
-                    block_hash: receipt.block_hash(),
-                    block_number: receipt.block_number(),
-                    gas_used: receipt.gas_used(),
-                    effective_gas_price: receipt.effective_gas_price(),
-                    blob_gas_used: receipt.blob_gas_used(),
-                    blob_gas_price: receipt.blob_gas_price(),
-                    from: receipt.from(),
-                    to: receipt.to(),
-                    contract_address: receipt.contract_address(),
-                };
-
-                OtsTransactionReceipt { receipt, timestamp }
-            })
+            .map(|(receipt, tx_ty)| ots_transaction_receipt(receipt, tx_ty, timestamp))
             .collect();
 
         // use `transaction_count` to indicate the paginate information
@@ -284,23 +310,64 @@ where
     }
 
     /// Handler for `ots_searchTransactionsBefore`
+    ///
+    /// Searches the blocks before the given block, or up to the latest block if it is `0`, from
+    /// the newest to the oldest block. Block tags are treated like `0`.
     async fn search_transactions_before(
         &self,
-        _address: Address,
-        _block_number: LenientBlockNumberOrTag,
-        _page_size: usize,
+        address: Address,
+        block_number: LenientBlockNumberOrTag,
+        page_size: usize,
     ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        let block_number = block_number.into_inner().as_number().unwrap_or_default();
+        let end = match block_number {
+            0 => self.eth.block_number()?.saturating_to(),
+            number => number - 1,
+        };
+        let blocks = self.address_blocks(address, 0..=end)?;
+
+        let _permit = self.eth.acquire_owned_tracing().await;
+        let (txs, receipts, exhausted) =
+            self.search_transactions(address, blocks.into_iter().rev(), page_size).await?;
+        Ok(TransactionsWithReceipts {
+            txs,
+            receipts,
+            first_page: block_number == 0,
+            last_page: exhausted,
+        })
     }
 
     /// Handler for `ots_searchTransactionsAfter`
+    ///
+    /// Searches the blocks after the given block, or from genesis if it is `0`, from the oldest to
+    /// the newest block. Block tags are treated like `0`.
     async fn search_transactions_after(
         &self,
-        _address: Address,
-        _block_number: LenientBlockNumberOrTag,
-        _page_size: usize,
+        address: Address,
+        block_number: LenientBlockNumberOrTag,
+        page_size: usize,
     ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        let block_number = block_number.into_inner().as_number().unwrap_or_default();
+        let latest: u64 = self.eth.block_number()?.saturating_to();
+        let start = if block_number == 0 { 0 } else { block_number + 1 };
+        let blocks = if start > latest {
+            IndexedBlocks::all(1..=0)
+        } else {
+            self.address_blocks(address, start..=latest)?
+        };
+
+        let _permit = self.eth.acquire_owned_tracing().await;
+        let (mut txs, mut receipts, exhausted) =
+            self.search_transactions(address, blocks.into_iter(), page_size).await?;
+        // results are always ordered from the newest to the oldest transaction
+        txs.reverse();
+        receipts.reverse();
+        Ok(TransactionsWithReceipts {
+            txs,
+            receipts,
+            first_page: exhausted,
+            last_page: block_number == 0,
+        })
     }
 
     /// Handler for `ots_getTransactionBySenderAndNonce`
@@ -384,3 +451,146 @@ where
         Ok(found)
     }
 }
+
+impl<Eth> OtterscanApi<Eth>
+where
+    Eth: EthApiServer<
+            RpcTxReq<Eth::NetworkTypes>,
+            RpcTransaction<Eth::NetworkTypes>,
+            RpcBlock<Eth::NetworkTypes>,
+            RpcReceipt<Eth::NetworkTypes>,
+            RpcHeader<Eth::NetworkTypes>,
+            TxTy<Eth::Primitives>,
+        > + TraceExt
+        + 'static,
+{
+    /// Collects the transactions of the address from the given blocks, until at least
+    /// `page_size` transactions were found.
+    ///
+    /// All matching transactions of a block are returned, so a page may contain more than
+    /// `page_size` transactions. At most `max_search_blocks` blocks are traced, a shorter page is
+    /// returned if the limit is reached, or an error if no transaction was found until then.
+    /// Returns whether all blocks were searched.
+    async fn search_transactions(
+        &self,
+        address: Address,
+        mut blocks: impl Iterator<Item = u64> + Send,
+        page_size: usize,
+    ) -> RpcResult<(Vec<Transaction>, Vec<OtsTransactionReceipt>, bool)> {
+        let mut txs = Vec::new();
+        let mut receipts = Vec::new();
+        let mut searched = 0;
+        while txs.len() < page_size {
+            let Some(block_number) = blocks.next() else { return Ok((txs, receipts, true)) };
+            if searched == self.max_search_blocks {
+                if txs.is_empty() {
+                    return Err(EthApiError::InvalidParams(format!(
+                        "no transactions found; the search is limited to {searched} blocks"
+                    ))
+                    .into())
+                }
+                break
+            }
+            searched += 1;
+
+            let (block_txs, block_receipts) =
+                self.block_transactions_with_address(address, block_number).await?;
+            txs.extend(block_txs);
+            receipts.extend(block_receipts);
+        }
+        Ok((txs, receipts, false))
+    }
+
+    /// Traces the block and returns the transactions in which the address appeared in any call
+    /// frame, together with their receipts, in ascending order.
+    async fn block_transactions_with_address(
+        &self,
+        address: Address,
+        block_number: u64,
+    ) -> RpcResult<(Vec<Transaction>, Vec<OtsTransactionReceipt>)> {
+        let block_id = BlockId::from(block_number);
+        let matched = self
+            .eth
+            .trace_block_with(
+                block_id,
+                None,
+                TracingInspectorConfig::default_parity(),
+                move |tx_info, mut ctx| {
+                    let inspector = ctx.take_inspector();
+                    let found = inspector.traces().nodes().iter().any(|node| {
+                        node.trace.caller == address ||
+                            node.trace.address == address ||
+                            node.trace.selfdestruct_refund_target == Some(address)
+                    });
+                    Ok(tx_info.index.filter(|_| found))
+                },
+            )
+            .await
+            .map_err(Into::into)?
+            .unwrap_or_default()
+            .into_iter()
+            .flatten()
+            .collect::<HashSet<_>>();
+        if matched.is_empty() {
+            return Ok(Default::default())
+        }
+
+        let block = self.eth.block_by_number(block_number.into(), true);
+        let receipts = self.eth.block_receipts(block_id);
+        let (block, receipts) = futures::try_join!(block, receipts)?;
+        let block = block.ok_or(EthApiError::HeaderNotFound(block_id))?;
+        let receipts = receipts.ok_or(EthApiError::ReceiptsNotFound(block_id))?;
+        let timestamp = Some(block.header.timestamp());
+        let BlockTransactions::Full(transactions) = block.transactions else {
+            return Err(internal_rpc_err("block is not full"));
+        };
+
+        let mut txs = Vec::with_capacity(matched.len());
+        let mut ots_receipts = Vec::with_capacity(matched.len());
+        for (index, (tx, receipt)) in transactions.into_iter().zip(receipts).enumerate() {
+            if !matched.contains(&(index as u64)) {
+                continue
+            }
+            ots_receipts.push(ots_transaction_receipt(receipt, tx.ty(), timestamp));
+            // the response type is not generic over the network, so the transaction is converted
+            // through its json representation
+            let tx = serde_json::to_value(tx)
+                .and_then(serde_json::from_value)
+                .map_err(|err| internal_rpc_err(err.to_string()))?;
+            txs.push(tx);
+        }
+        Ok((txs, ots_receipts))
+    }
+}
+
+/// Converts a receipt into an [`OtsTransactionReceipt`] of a transaction of the given type.
+fn ots_transaction_receipt<R: ReceiptResponse>(
+    receipt: R,
+    tx_ty: u8,
+    timestamp: Option<u64>,
+) -> OtsTransactionReceipt {
+    let inner = OtsReceipt {
+        status: receipt.status(),
+        cumulative_gas_used: receipt.cumulative_gas_used(),
+        logs: None,
+        logs_bloom: None,
+        r#type: tx_ty,
+    };
+
+    let receipt = TransactionReceipt {
+        inner,
+        transaction_hash: receipt.transaction_hash(),
+        transaction_index: receipt.transaction_index(),
+        block_hash: receipt.block_hash(),
+        block_number: receipt.block_number(),
+        gas_used: receipt.gas_used(),
+        effective_gas_price: receipt.effective_gas_price(),
+        blob_gas_used: receipt.blob_gas_used(),
+        blob_gas_price: receipt.blob_gas_price(),
+        from: receipt.from(),
+        to: receipt.to(),
+        contract_address: receipt.contract_address(),
+    };
+
+    OtsTransactionReceipt { receipt, timestamp }
+}
@@ -1,4 +1,8 @@
-use alloy_consensus::BlockHeader as _;
+use crate::{
+    trace_index::indexed_blocks,
+    tracer_timeout::{tracer_timeout, TracerDeadline},
+};
+use alloy_consensus::{transaction::TxHashRef, BlockHeader as _};
 use alloy_eips::BlockId;
 use alloy_evm::block::calc::{base_block_reward_pre_merge, block_reward, ommer_reward};
 use alloy_primitives::{
@@ -10,34 +14,40 @@ use alloy_rpc_types_eth::{
     BlockOverrides, Index,
 };
 use alloy_rpc_types_trace::{
-    filter::TraceFilter,
+    filter::{TraceFilter, TraceFilterMatcher},
+    geth::{GethDebugTracingOptions, TraceResult},
     opcode::{BlockOpcodeGas, TransactionOpcodeGas},
     parity::*,
     tracerequest::TraceCallRequest,
 };
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
+use jsonrpsee::{core::RpcResult, PendingSubscriptionSink, SubscriptionMessage, SubscriptionSink};
 use hanzo_evm_chainspec::{ChainSpecProvider, EthereumHardforks};
 use hanzo_evm_execution::ConfigureEvm;
-use hanzo_evm_primitives_traits::{BlockBody, BlockHeader};
-use hanzo_evm_rpc_api::TraceApiServer;
+use hanzo_evm_primitives_traits::{BlockBody, BlockHeader, RecoveredBlock};
+use hanzo_evm_rpc_api::{FilteredBlockTraces, TraceApiServer};
 use hanzo_evm_rpc_convert::RpcTxReq;
 use hanzo_evm_rpc_eth_api::{
     helpers::{Call, LoadPendingBlock, LoadTransaction, Trace, TraceExt},
     FromEthApiError, RpcNodeCore,
 };
 use hanzo_evm_rpc_eth_types::{error::EthApiError, utils::recover_raw_transaction, EthConfig};
-use hanzo_evm_storage_api::{BlockNumReader, BlockReader};
+use hanzo_evm_storage_api::{
+    BlockNumReader, BlockReader, ProviderBlock, TraceIndexReader, TransactionVariant,
+};
 use hanzo_evm_tasks::pool::BlockingTaskGuard;
 use hanzo_evm_transaction_pool::{PoolPooledTx, PoolTransaction, TransactionPool};
 use revm::DatabaseCommit;
 use revm_inspectors::{
     opcode::OpcodeGasInspector,
     storage::StorageInspector,
-    tracing::{parity::populate_state_diff, TracingInspector, TracingInspectorConfig},
+    tracing::{
+        parity::populate_state_diff, DebugInspector, TracingInspector, TracingInspectorConfig,
+        TransactionContext,
+    },
 };
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::{ops::RangeInclusive, sync::Arc};
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
 
 /// `trace` API implementation.
@@ -45,6 +55,8 @@ use tokio::sync::{AcquireError, OwnedSemaphorePermit};
 /// This type provides the functionality for handling `trace` related requests.
 pub struct TraceApi<Eth> {
     inner: Arc<TraceApiInner<Eth>>,
+    /// The optional index that is used to skip blocks in `trace_filter`.
+    trace_index: Option<Arc<dyn TraceIndexReader>>,
 }
 
 // === impl TraceApi ===
@@ -57,7 +69,14 @@ impl<Eth> TraceApi<Eth> {
         eth_config: EthConfig,
     ) -> Self {
         let inner = Arc::new(TraceApiInner { eth_api, blocking_task_guard, eth_config });
-        Self { inner }
+        Self { inner, trace_index: None }
+    }
+
+    /// Configures the [`TraceIndexReader`] that is used by `trace_filter` and
+    /// `trace_subscribeFilter` to only trace the blocks in which the filtered addresses appeared.
+    pub fn with_trace_index(mut self, trace_index: Arc<dyn TraceIndexReader>) -> Self {
+        self.trace_index = Some(trace_index);
+        self
     }
 
     /// Acquires a permit to execute a tracing call.
@@ -326,25 +345,15 @@ where
         }
         traces
     }
-}
 
-impl<Eth> TraceApi<Eth>
-where
-    // tracing methods read from mempool, hence `LoadBlock` trait bound via
-    // `TraceExt`
-    Eth: TraceExt + 'static,
-{
-    /// Returns all transaction traces that match the given filter.
+    /// Validates the block range of a [`TraceFilter`] against the latest block.
     ///
-    /// This is similar to [`Self::trace_block`] but only returns traces for transactions that match
-    /// the filter.
-    pub async fn trace_filter(
+    /// `from_block` defaults to genesis and `to_block` defaults to the latest block.
+    fn trace_filter_range(
         &self,
-        filter: TraceFilter,
-    ) -> Result<Vec<LocalizedTransactionTrace>, Eth::Error> {
-        // We'll reuse the matcher across multiple blocks that are traced in parallel
-        let matcher = Arc::new(filter.matcher());
-        let TraceFilter { from_block, to_block, mut after, count, .. } = filter;
+        from_block: Option<u64>,
+        to_block: Option<u64>,
+    ) -> Result<RangeInclusive<u64>, Eth::Error> {
         let start = from_block.unwrap_or(0);
 
         let latest_block = self.provider().best_block_number().map_err(Eth::Error::from_eth_err)?;
@@ -364,8 +373,33 @@ where
             .into())
         }
 
-        // ensure that the range is not too large, since we need to fetch all blocks in the range
-        let distance = end.saturating_sub(start);
+        Ok(start..=end)
+    }
+
+    /// Returns the blocks that need to be traced for the [`TraceFilter`], in ascending order.
+    ///
+    /// If a trace index is configured, only the blocks in which the filtered addresses appeared
+    /// are returned. Fails if more than `max_trace_filter_blocks` blocks would need to be traced.
+    fn trace_filter_blocks(&self, filter: &TraceFilter) -> Result<Vec<u64>, Eth::Error> {
+        let range = self.trace_filter_range(filter.from_block, filter.to_block)?;
+        let indexed = match &self.trace_index {
+            Some(index) => indexed_blocks(
+                index.as_ref(),
+                &filter.from_address,
+                &filter.to_address,
+                filter.mode,
+                range.clone(),
+            )
+            .map_err(Eth::Error::from_eth_err)?,
+            None => None,
+        };
+
+        // ensure that the range is not too large, since we need to fetch all blocks in the range,
+        // with a trace index only the blocks that need to be traced count towards the limit
+        let distance = match &indexed {
+            Some(blocks) => blocks.len().saturating_sub(1),
+            None => range.end().saturating_sub(*range.start()),
+        };
         if distance > self.inner.eth_config.max_trace_filter_blocks {
             return Err(EthApiError::InvalidParams(
                 "Block range too large; currently limited to 100 blocks".to_string(),
@@ -373,25 +407,57 @@ where
             .into())
         }
 
+        Ok(indexed.map_or_else(|| range.collect(), |blocks| blocks.into_iter().collect()))
+    }
+}
+
+impl<Eth> TraceApi<Eth>
+where
+    // tracing methods read from mempool, hence `LoadBlock` trait bound via
+    // `TraceExt`
+    Eth: TraceExt + 'static,
+{
+    /// Returns all transaction traces that match the given filter.
+    ///
+    /// This is similar to [`Self::trace_block`] but only returns traces for transactions that match
+    /// the filter.
+    pub async fn trace_filter(
+        &self,
+        filter: TraceFilter,
+    ) -> Result<Vec<LocalizedTransactionTrace>, Eth::Error> {
+        // We'll reuse the matcher across multiple blocks that are traced in parallel
+        let matcher = Arc::new(filter.matcher());
+        let block_numbers = self.trace_filter_blocks(&filter)?;
+        let TraceFilter { mut after, count, .. } = filter;
+
         let mut all_traces = Vec::new();
         let mut block_traces = Vec::with_capacity(self.inner.eth_config.max_tracing_requests);
-        for chunk_start in (start..=end).step_by(self.inner.eth_config.max_tracing_requests) {
-            let chunk_end = std::cmp::min(
-                chunk_start + self.inner.eth_config.max_tracing_requests as u64 - 1,
-                end,
-            );
+        for chunk in block_numbers.chunks(self.inner.eth_config.max_tracing_requests) {
+            let chunk = chunk.to_vec();
 
             // fetch all blocks in that chunk
             let blocks = self
                 .eth_api()
                 .spawn_blocking_io(move |this| {
-                    Ok(this
-                        .provider()
-                        .recovered_block_range(chunk_start..=chunk_end)
-                        .map_err(Eth::Error::from_eth_err)?
-                        .into_iter()
-                        .map(Arc::new)
-                        .collect::<Vec<_>>())
+                    let (first, last) = (chunk[0], chunk[chunk.len() - 1]);
+                    let blocks = if last - first + 1 == chunk.len() as u64 {
+                        this.provider()
+                            .recovered_block_range(first..=last)
+                            .map_err(Eth::Error::from_eth_err)?
+                    } else {
+                        chunk
+                            .into_iter()
+                            .map(|number| {
+                                this.provider()
+                                    .recovered_block(number.into(), TransactionVariant::WithHash)
+                                    .map_err(Eth::Error::from_eth_err)?
+                                    .ok_or_else(|| {
+                                        EthApiError::HeaderNotFound(number.into()).into()
+                                    })
+                            })
+                            .collect::<Result<Vec<_>, Eth::Error>>()?
+                    };
+                    Ok(blocks.into_iter().map(Arc::new).collect::<Vec<_>>())
                 })
                 .await?;
 
@@ -470,6 +536,197 @@ where
         Ok(all_traces)
     }
 
+    /// Traces the given block and returns the traces that match the filter.
+    ///
+    /// If geth tracing options are given, all transactions with at least one matching parity trace
+    /// are replayed with the configured geth tracer and only their geth traces are returned.
+    pub async fn trace_filter_block(
+        &self,
+        block: Arc<RecoveredBlock<ProviderBlock<Eth::Provider>>>,
+        matcher: Arc<TraceFilterMatcher>,
+        tracer: Option<GethDebugTracingOptions>,
+    ) -> Result<FilteredBlockTraces, Eth::Error> {
+        let tx_matcher = matcher.clone();
+        let mut traces = self
+            .eth_api()
+            .trace_block_until(
+                block.hash().into(),
+                Some(block.clone()),
+                None,
+                TracingInspectorConfig::default_parity(),
+                move |tx_info, mut ctx| {
+                    let mut traces = ctx
+                        .take_inspector()
+                        .into_parity_builder()
+                        .into_localized_transaction_traces(tx_info);
+                    traces.retain(|trace| tx_matcher.matches(&trace.trace));
+                    Ok(traces)
+                },
+            )
+            .await?
+            .into_iter()
+            .flatten()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        let mut block_traces = FilteredBlockTraces {
+            block_number: block.number(),
+            block_hash: block.hash(),
+            traces: Vec::new(),
+            geth_traces: Vec::new(),
+        };
+
+        if let Some(opts) = tracer {
+            let tx_hashes =
+                traces.iter().filter_map(|trace| trace.transaction_hash).collect::<HashSet<_>>();
+            if !tx_hashes.is_empty() {
+                block_traces.geth_traces =
+                    self.trace_block_transactions_with_geth_tracer(block, tx_hashes, opts).await?;
+            }
+            return Ok(block_traces)
+        }
+
+        if let Some(base_block_reward) = self.calculate_base_block_reward(block.header())? {
+            traces.extend(
+                self.extract_reward_traces(block.header(), block.body().ommers(), base_block_reward)
+                    .into_iter()
+                    .filter(|trace| matcher.matches(&trace.trace)),
+            );
+        }
+        block_traces.traces = traces;
+
+        Ok(block_traces)
+    }
+
+    /// Replays the given block and traces the transactions with the given hashes with the geth
+    /// tracer configured by the options.
+    ///
+    /// The block is only replayed up to the last of the given transactions.
+    async fn trace_block_transactions_with_geth_tracer(
+        &self,
+        block: Arc<RecoveredBlock<ProviderBlock<Eth::Provider>>>,
+        tx_hashes: HashSet<B256>,
+        opts: GethDebugTracingOptions,
+    ) -> Result<Vec<TraceResult>, Eth::Error> {
+        let (evm_env, _) = self.eth_api().evm_env_at(block.hash().into()).await?;
+        let timeout = tracer_timeout(&opts).map_err(Eth::Error::from_eth_err)?;
+
+        self.eth_api()
+            .spawn_with_state_at_block(block.parent_hash(), move |eth_api, mut db| {
+                let mut results = Vec::with_capacity(tx_hashes.len());
+
+                eth_api.apply_pre_execution_changes(&block, &mut db)?;
+
+                let mut inspector = TracerDeadline::new(
+                    DebugInspector::new(opts).map_err(Eth::Error::from_eth_err)?,
+                    timeout,
+                );
+                for (index, tx) in block.transactions_recovered().enumerate() {
+                    if results.len() == tx_hashes.len() {
+                        // all requested transactions are traced
+                        break
+                    }
+
+                    let tx_hash = *tx.tx_hash();
+                    let tx_env = eth_api.hanzo_evm_config().tx_env(tx);
+
+                    // the timeout applies to every transaction on its own
+                    inspector.restart();
+                    let res = eth_api.inspect(
+                        &mut db,
+                        evm_env.clone(),
+                        tx_env.clone(),
+                        &mut inspector,
+                    )?;
+                    inspector.ensure_not_timed_out().map_err(Eth::Error::from_eth_err)?;
+
+                    if tx_hashes.contains(&tx_hash) {
+                        let result = inspector
+                            .inner_mut()
+                            .get_result(
+                                Some(TransactionContext {
+                                    block_hash: Some(block.hash()),
+                                    tx_hash: Some(tx_hash),
+                                    tx_index: Some(index),
+                                }),
+                                &tx_env,
+                                &evm_env.block_env,
+                                &res,
+                                &mut db,
+                            )
+                            .map_err(Eth::Error::from_eth_err)?;
+                        inspector.ensure_not_timed_out().map_err(Eth::Error::from_eth_err)?;
+                        results.push(TraceResult::Success { result, tx_hash: Some(tx_hash) });
+                    }
+
+                    inspector.inner_mut().fuse().map_err(Eth::Error::from_eth_err)?;
+                    // need to apply the state changes of this transaction before executing the
+                    // next transaction
+                    db.commit(res.state)
+                }
+
+                Ok(results)
+            })
+            .await
+    }
+
+    /// Traces the given blocks in ascending order and sends the traces that match the filter to
+    /// the subscription sink, one message per block with matching traces.
+    ///
+    /// Blocks are traced one at a time and the next block is only traced once the previous
+    /// message was accepted by the sink.
+    async fn pipe_filtered_traces(
+        &self,
+        sink: SubscriptionSink,
+        block_numbers: Vec<u64>,
+        filter: TraceFilter,
+        tracer: Option<GethDebugTracingOptions>,
+    ) -> Result<(), Eth::Error> {
+        let matcher = Arc::new(filter.matcher());
+        let mut after = filter.after.unwrap_or_default() as usize;
+        let mut remaining = filter.count.map(|count| count as usize);
+
+        for block_number in block_numbers {
+            if sink.is_closed() || remaining == Some(0) {
+                break
+            }
+
+            let block_traces = {
+                let _permit = self.acquire_trace_permit().await;
+                let Some(block) = self.eth_api().recovered_block(block_number.into()).await?
+                else {
+                    return Err(EthApiError::HeaderNotFound(block_number.into()).into())
+                };
+                let mut block_traces =
+                    self.trace_filter_block(block, matcher.clone(), tracer.clone()).await?;
+                skip_and_take(&mut block_traces.traces, &mut after, &mut remaining);
+                skip_and_take(&mut block_traces.geth_traces, &mut after, &mut remaining);
+                block_traces
+            };
+
+            if block_traces.traces.is_empty() && block_traces.geth_traces.is_empty() {
+                continue
+            }
+
+            let msg = match SubscriptionMessage::new(
+                sink.method_name(),
+                sink.subscription_id(),
+                &block_traces,
+            ) {
+                Ok(msg) => msg,
+                Err(err) => {
+                    tracing::error!(target: "rpc::trace", %err, "Failed to serialize subscription message");
+                    break
+                }
+            };
+            if sink.send(msg).await.is_err() {
+                break
+            }
+        }
+
+        Ok(())
+    }
+
     /// Returns traces created at given block.
     pub async fn trace_block(
         &self,
@@ -693,12 +950,45 @@ where
     /// This is similar to `eth_getLogs` but for traces.
     ///
     /// # Limitations
-    /// This currently requires block filter fields, since evm does not have address indices yet.
+    /// This requires block filter fields unless a trace index is configured, see
+    /// [`TraceApi::with_trace_index`].
     async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<LocalizedTransactionTrace>> {
         let _permit = self.inner.blocking_task_guard.clone().acquire_many_owned(2).await;
         Ok(Self::trace_filter(self, filter).await.map_err(Into::into)?)
     }
 
+    /// Handler for `trace_subscribeFilter`
+    async fn trace_subscribe_filter(
+        &self,
+        pending: PendingSubscriptionSink,
+        filter: TraceFilter,
+        tracer: Option<GethDebugTracingOptions>,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        // the same block limit as for `trace_filter` applies
+        let block_numbers = match self.trace_filter_blocks(&filter) {
+            Ok(block_numbers) => block_numbers,
+            Err(err) => {
+                pending.reject(err).await;
+                return Ok(())
+            }
+        };
+
+        // reject unsupported tracers and invalid timeouts before accepting the subscription
+        if let Some(opts) = &tracer {
+            let res = tracer_timeout(opts)
+                .and_then(|_| DebugInspector::new(opts.clone()).map_err(EthApiError::from));
+            if let Err(err) = res {
+                pending.reject(Eth::Error::from_eth_err(err)).await;
+                return Ok(())
+            }
+        }
+
+        let sink = pending.accept().await?;
+        Self::pipe_filtered_traces(self, sink, block_numbers, filter, tracer).await?;
+
+        Ok(())
+    }
+
     /// Returns transaction trace at given index.
     /// Handler for `trace_get`
     async fn trace_get(
@@ -744,7 +1034,7 @@ impl<Eth> std::fmt::Debug for TraceApi<Eth> {
 }
 impl<Eth> Clone for TraceApi<Eth> {
     fn clone(&self) -> Self {
-        Self { inner: Arc::clone(&self.inner) }
+        Self { inner: Arc::clone(&self.inner), trace_index: self.trace_index.clone() }
     }
 }
 
@@ -801,3 +1091,43 @@ fn reward_trace<H: BlockHeader>(header: &H, reward: RewardAction) -> LocalizedTr
         },
     }
 }
+
+/// Skips the first `after` items and truncates the remaining items to at most `remaining`.
+///
+/// Both counters are updated, so this can be applied to consecutive chunks of a stream.
+fn skip_and_take<T>(items: &mut Vec<T>, after: &mut usize, remaining: &mut Option<usize>) {
+    let skip = (*after).min(items.len());
+    items.drain(..skip);
+    *after -= skip;
+
+    if let Some(remaining) = remaining {
+        items.truncate(*remaining);
+        *remaining -= items.len();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skip_and_take_across_chunks() {
+        let mut after = 3;
+        let mut remaining = Some(4);
+
+        let mut chunk = vec![1, 2];
+        skip_and_take(&mut chunk, &mut after, &mut remaining);
+        assert!(chunk.is_empty());
+        assert_eq!((after, remaining), (1, Some(4)));
+
+        let mut chunk = vec![3, 4, 5];
+        skip_and_take(&mut chunk, &mut after, &mut remaining);
+        assert_eq!(chunk, vec![4, 5]);
+        assert_eq!((after, remaining), (0, Some(2)));
+
+        let mut chunk = vec![6, 7, 8];
+        skip_and_take(&mut chunk, &mut after, &mut remaining);
+        assert_eq!(chunk, vec![6, 7]);
+        assert_eq!(remaining, Some(0));
+    }
+}
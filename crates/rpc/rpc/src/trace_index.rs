@@ -0,0 +1,190 @@
+//! Block selection with the optional [`TraceIndexReader`].
+
+use alloy_primitives::Address;
+use alloy_rpc_types_trace::filter::TraceFilterMode;
+use hanzo_evm_storage_api::{errors::provider::ProviderResult, TraceIndexReader, TracedAddressRole};
+use std::{
+    collections::{btree_set, BTreeSet},
+    iter::Chain,
+    ops::RangeInclusive,
+};
+
+/// The blocks of a range that need to be traced, in ascending order, see [`indexed_blocks`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct IndexedBlocks {
+    /// The blocks of the indexed part of the range that can contain matching call frames.
+    matched: BTreeSet<u64>,
+    /// The part of the range above the index, which is not indexed yet.
+    unindexed: RangeInclusive<u64>,
+}
+
+impl IndexedBlocks {
+    /// Returns the selection of every block of the range.
+    pub(crate) const fn all(range: RangeInclusive<u64>) -> Self {
+        Self { matched: BTreeSet::new(), unindexed: range }
+    }
+
+    /// Returns the number of selected blocks.
+    pub(crate) fn len(&self) -> u64 {
+        let unindexed = if self.unindexed.is_empty() {
+            0
+        } else {
+            self.unindexed.end() - self.unindexed.start() + 1
+        };
+        self.matched.len() as u64 + unindexed
+    }
+}
+
+impl IntoIterator for IndexedBlocks {
+    type Item = u64;
+    type IntoIter = Chain<btree_set::IntoIter<u64>, RangeInclusive<u64>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.matched.into_iter().chain(self.unindexed)
+    }
+}
+
+/// Returns the blocks of the range that can contain call frames from one of the `from` addresses
+/// and/or to one of the `to` addresses.
+///
+/// An empty address list doesn't restrict the frames. Blocks above the range of the index are
+/// always included because they are not indexed yet. Blocks below it are skipped: the index starts
+/// at the first block it was able to trace, so their parent state is pruned.
+///
+/// Returns `None` if the index can't narrow down the range, in which case every block of the range
+/// has to be traced.
+pub(crate) fn indexed_blocks(
+    index: &dyn TraceIndexReader,
+    from: &[Address],
+    to: &[Address],
+    mode: TraceFilterMode,
+    range: RangeInclusive<u64>,
+) -> ProviderResult<Option<IndexedBlocks>> {
+    let (start, end) = range.into_inner();
+    let Some(indexed) = index.trace_index_range()? else { return Ok(None) };
+    if start > *indexed.end() {
+        return Ok(None)
+    }
+    if end < *indexed.start() {
+        // an empty selection, the whole range is pruned
+        return Ok(Some(IndexedBlocks::all(1..=0)))
+    }
+    let indexed_start = start.max(*indexed.start());
+    let indexed_end = end.min(*indexed.end());
+    let indexed = indexed_start..=indexed_end;
+
+    let lookup = |addresses: &[Address], role| -> ProviderResult<Option<BTreeSet<u64>>> {
+        if addresses.is_empty() {
+            return Ok(None)
+        }
+        let mut blocks = BTreeSet::new();
+        for address in addresses {
+            blocks.extend(index.traced_address_blocks(*address, role, indexed.clone())?);
+        }
+        Ok(Some(blocks))
+    };
+    let from = lookup(from, TracedAddressRole::From)?;
+    let to = lookup(to, TracedAddressRole::To)?;
+
+    let matched = match (mode, from, to) {
+        (TraceFilterMode::Union, Some(from), Some(to)) => &from | &to,
+        (TraceFilterMode::Intersection, Some(from), Some(to)) => &from & &to,
+        (TraceFilterMode::Intersection, Some(blocks), None) |
+        (TraceFilterMode::Intersection, None, Some(blocks)) => blocks,
+        // a side without addresses matches every frame
+        _ => return Ok(None),
+    };
+
+    Ok(Some(IndexedBlocks { matched, unindexed: indexed_end + 1..=end }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    /// Returns the selected blocks in ascending order.
+    fn blocks(selection: ProviderResult<Option<IndexedBlocks>>) -> Option<Vec<u64>> {
+        selection.unwrap().map(|blocks| blocks.into_iter().collect())
+    }
+
+    #[derive(Debug, Default)]
+    struct MockIndex {
+        range: Option<RangeInclusive<u64>>,
+        blocks: BTreeMap<(Address, bool), Vec<u64>>,
+    }
+
+    impl TraceIndexReader for MockIndex {
+        fn trace_index_range(&self) -> ProviderResult<Option<RangeInclusive<u64>>> {
+            Ok(self.range.clone())
+        }
+
+        fn traced_address_blocks(
+            &self,
+            address: Address,
+            role: TracedAddressRole,
+            range: RangeInclusive<u64>,
+        ) -> ProviderResult<Vec<u64>> {
+            let key = (address, role == TracedAddressRole::From);
+            Ok(self
+                .blocks
+                .get(&key)
+                .map(|blocks| blocks.iter().copied().filter(|b| range.contains(b)).collect())
+                .unwrap_or_default())
+        }
+    }
+
+    #[test]
+    fn select_indexed_blocks() {
+        let (alice, bob) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let index = MockIndex {
+            range: Some(0..=10),
+            blocks: BTreeMap::from([
+                ((alice, true), vec![1, 4, 8]),
+                ((bob, false), vec![4, 5, 9]),
+            ]),
+        };
+
+        let union = indexed_blocks(&index, &[alice], &[bob], TraceFilterMode::Union, 0..=12);
+        assert_eq!(blocks(union), Some(vec![1, 4, 5, 8, 9, 11, 12]));
+
+        let intersection =
+            indexed_blocks(&index, &[alice], &[bob], TraceFilterMode::Intersection, 2..=9);
+        assert_eq!(blocks(intersection), Some(vec![4]));
+
+        let from_only = indexed_blocks(&index, &[alice], &[], TraceFilterMode::Intersection, 0..=5);
+        assert_eq!(blocks(from_only), Some(vec![1, 4]));
+
+        // no restriction on the recipients
+        let unrestricted = indexed_blocks(&index, &[alice], &[], TraceFilterMode::Union, 0..=5);
+        assert_eq!(blocks(unrestricted), None);
+
+        // the range is above the index
+        let unindexed = indexed_blocks(&index, &[alice], &[bob], TraceFilterMode::Union, 11..=12);
+        assert_eq!(blocks(unindexed), None);
+    }
+
+    #[test]
+    fn select_blocks_below_lower_bound() {
+        let alice = Address::repeat_byte(1);
+        let index = MockIndex {
+            range: Some(5..=10),
+            blocks: BTreeMap::from([((alice, true), vec![6, 9])]),
+        };
+
+        // the pruned blocks below the index are skipped
+        let partial = indexed_blocks(&index, &[alice], &[], TraceFilterMode::Intersection, 2..=12);
+        assert_eq!(blocks(partial), Some(vec![6, 9, 11, 12]));
+
+        let indexed = indexed_blocks(&index, &[alice], &[], TraceFilterMode::Intersection, 5..=8);
+        assert_eq!(blocks(indexed), Some(vec![6]));
+
+        // the range is below the index
+        let pruned = indexed_blocks(&index, &[alice], &[], TraceFilterMode::Intersection, 0..=4);
+        assert_eq!(blocks(pruned), Some(vec![]));
+
+        let all = IndexedBlocks::all(3..=7);
+        assert_eq!(all.len(), 5);
+        assert_eq!(all.into_iter().rev().collect::<Vec<_>>(), vec![7, 6, 5, 4, 3]);
+    }
+}
@@ -0,0 +1,333 @@
+//! Execution time limits for the geth tracers of the `debug` namespace.
+
+use alloy_primitives::{Address, Log, U256};
+use alloy_rpc_types_trace::geth::{GethDebugTracerType, GethDebugTracingOptions};
+use hanzo_evm_rpc_eth_types::EthApiError;
+use revm::{
+    inspector::Inspector,
+    interpreter::{
+        CallInputs, CallOutcome, CreateInputs, CreateOutcome, InstructionResult, Interpreter,
+    },
+};
+use std::time::{Duration, Instant};
+
+/// The timeout that is applied to `JavaScript` tracers if the request doesn't specify one.
+///
+/// This is the same default as geth's.
+pub(crate) const DEFAULT_JS_TRACER_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Returns the execution time limit for the given tracing options.
+///
+/// The `timeout` option is parsed as a duration string, e.g. `"5s"` or `"300ms"`. If no timeout
+/// is set, `JavaScript` tracers are limited to [`DEFAULT_JS_TRACER_TIMEOUT`] and all other tracers
+/// are not limited.
+pub(crate) fn tracer_timeout(
+    opts: &GethDebugTracingOptions,
+) -> Result<Option<Duration>, EthApiError> {
+    if let Some(timeout) = &opts.timeout {
+        return humantime::parse_duration(timeout).map(Some).map_err(|err| {
+            EthApiError::InvalidParams(format!("invalid tracer timeout {timeout:?}: {err}"))
+        })
+    }
+
+    Ok(matches!(opts.tracer, Some(GethDebugTracerType::JsTracer(_)))
+        .then_some(DEFAULT_JS_TRACER_TIMEOUT))
+}
+
+/// An [`Inspector`] that aborts the execution once a deadline is exceeded.
+///
+/// All hooks are forwarded to the wrapped inspector until the deadline has passed. The deadline
+/// is checked before every hook, so once it is exceeded none of the callbacks of a `JavaScript`
+/// tracer (`step`, `fault`, `enter`, `exit`) runs anymore. The next executed instruction halts the
+/// current frame and every frame that is resumed afterwards, so the transaction finishes early.
+/// The result of the wrapped inspector is incomplete in that case and must be discarded if
+/// [`TracerDeadline::ensure_not_timed_out`] fails.
+///
+/// Like in geth, the timeout applies to every transaction on its own, so the deadline must be
+/// restarted with [`TracerDeadline::restart`] before each transaction of a block is traced.
+#[derive(Debug, Clone)]
+pub(crate) struct TracerDeadline<I> {
+    /// The wrapped inspector
+    inner: I,
+    /// The configured timeout and the instant it expires, if any.
+    deadline: Option<(Duration, Instant)>,
+    /// Whether the deadline was exceeded.
+    timed_out: bool,
+}
+
+impl<I> TracerDeadline<I> {
+    /// Wraps the inspector, the deadline starts now.
+    pub(crate) fn new(inner: I, timeout: Option<Duration>) -> Self {
+        Self {
+            inner,
+            deadline: timeout.map(|timeout| (timeout, Instant::now() + timeout)),
+            timed_out: false,
+        }
+    }
+
+    /// Restarts the deadline now, e.g. before the next transaction is traced.
+    pub(crate) fn restart(&mut self) {
+        self.deadline = self.deadline.map(|(timeout, _)| (timeout, Instant::now() + timeout));
+        self.timed_out = false;
+    }
+
+    /// Returns `true` if the deadline was exceeded.
+    fn is_timed_out(&mut self) -> bool {
+        if !self.timed_out && self.deadline.is_some_and(|(_, deadline)| Instant::now() >= deadline)
+        {
+            self.timed_out = true;
+        }
+        self.timed_out
+    }
+
+    /// Returns an [`EthApiError::ExecutionTimedOut`] error if the deadline was exceeded.
+    ///
+    /// This must be checked both before and after the result of the wrapped inspector is
+    /// computed, since the `result` function of a `JavaScript` tracer runs outside of the
+    /// execution.
+    pub(crate) fn ensure_not_timed_out(&mut self) -> Result<(), EthApiError> {
+        match self.deadline {
+            Some((timeout, _)) if self.is_timed_out() => {
+                Err(EthApiError::ExecutionTimedOut(timeout))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns a mutable reference to the wrapped inspector.
+    pub(crate) const fn inner_mut(&mut self) -> &mut I {
+        &mut self.inner
+    }
+}
+
+impl<CTX, I> Inspector<CTX> for TracerDeadline<I>
+where
+    I: Inspector<CTX>,
+{
+    fn initialize_interp(&mut self, interp: &mut Interpreter, context: &mut CTX) {
+        if self.is_timed_out() {
+            return
+        }
+        self.inner.initialize_interp(interp, context)
+    }
+
+    fn step(&mut self, interp: &mut Interpreter, context: &mut CTX) {
+        if self.is_timed_out() {
+            interp.halt(InstructionResult::OutOfGas);
+            return
+        }
+        self.inner.step(interp, context)
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, context: &mut CTX) {
+        if self.is_timed_out() {
+            return
+        }
+        self.inner.step_end(interp, context)
+    }
+
+    fn log(&mut self, context: &mut CTX, log: Log) {
+        if self.is_timed_out() {
+            return
+        }
+        self.inner.log(context, log)
+    }
+
+    fn log_full(&mut self, interp: &mut Interpreter, context: &mut CTX, log: Log) {
+        if self.is_timed_out() {
+            return
+        }
+        self.inner.log_full(interp, context, log)
+    }
+
+    fn call(&mut self, context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        // the frame is halted by its first instruction
+        if self.is_timed_out() {
+            return None
+        }
+        self.inner.call(context, inputs)
+    }
+
+    fn call_end(&mut self, context: &mut CTX, inputs: &CallInputs, outcome: &mut CallOutcome) {
+        if self.is_timed_out() {
+            return
+        }
+        self.inner.call_end(context, inputs, outcome)
+    }
+
+    fn create(&mut self, context: &mut CTX, inputs: &mut CreateInputs) -> Option<CreateOutcome> {
+        // the frame is halted by its first instruction
+        if self.is_timed_out() {
+            return None
+        }
+        self.inner.create(context, inputs)
+    }
+
+    fn create_end(
+        &mut self,
+        context: &mut CTX,
+        inputs: &CreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        if self.is_timed_out() {
+            return
+        }
+        self.inner.create_end(context, inputs, outcome)
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        if self.is_timed_out() {
+            return
+        }
+        self.inner.selfdestruct(contract, target, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{bytes, TxKind};
+    use alloy_rpc_types_trace::geth::GethDebugBuiltInTracerType;
+    use hanzo_evm_eth_execution::EthEvmConfig;
+    use hanzo_evm_execution::{ConfigureEvm, Evm, EvmEnv};
+    use revm::{
+        context::TxEnv,
+        database::{CacheDB, EmptyDB},
+        state::{AccountInfo, Bytecode},
+    };
+
+    /// Counts the callbacks it receives, like the hooks of a `JavaScript` tracer.
+    #[derive(Debug, Default)]
+    struct CallbackCounter {
+        steps: usize,
+        step_ends: usize,
+        calls: usize,
+        call_ends: usize,
+    }
+
+    impl<CTX> Inspector<CTX> for CallbackCounter {
+        fn step(&mut self, _interp: &mut Interpreter, _context: &mut CTX) {
+            self.steps += 1;
+        }
+
+        fn step_end(&mut self, _interp: &mut Interpreter, _context: &mut CTX) {
+            self.step_ends += 1;
+        }
+
+        fn call(&mut self, _context: &mut CTX, _inputs: &mut CallInputs) -> Option<CallOutcome> {
+            self.calls += 1;
+            None
+        }
+
+        fn call_end(
+            &mut self,
+            _context: &mut CTX,
+            _inputs: &CallInputs,
+            _outcome: &mut CallOutcome,
+        ) {
+            self.call_ends += 1;
+        }
+    }
+
+    /// Runs a call to a contract that loops until it runs out of gas.
+    fn run_loop(inspector: &mut TracerDeadline<CallbackCounter>) {
+        let contract = Address::with_last_byte(0xaa);
+        let mut db = CacheDB::<EmptyDB>::default();
+        // JUMPDEST PUSH1 0 JUMP
+        let code = Bytecode::new_raw(bytes!("5b600056"));
+        db.insert_account_info(
+            contract,
+            AccountInfo { code_hash: code.hash_slow(), code: Some(code), ..Default::default() },
+        );
+
+        let mut evm = EthEvmConfig::mainnet().evm_with_env_and_inspector(
+            &mut db,
+            EvmEnv::default(),
+            inspector,
+        );
+        let tx = TxEnv { kind: TxKind::Call(contract), gas_limit: 100_000, ..Default::default() };
+        let res = evm.transact(tx).unwrap();
+        assert!(!res.result.is_success());
+    }
+
+    #[test]
+    fn forwards_callbacks_without_deadline() {
+        let mut inspector = TracerDeadline::new(CallbackCounter::default(), None);
+        run_loop(&mut inspector);
+
+        assert!(inspector.ensure_not_timed_out().is_ok());
+        let counter = inspector.inner_mut();
+        assert!(counter.steps > 1000);
+        assert_eq!(counter.steps, counter.step_ends);
+        assert_eq!((counter.calls, counter.call_ends), (1, 1));
+    }
+
+    #[test]
+    fn skips_callbacks_after_deadline() {
+        let mut inspector = TracerDeadline::new(CallbackCounter::default(), Some(Duration::ZERO));
+        run_loop(&mut inspector);
+
+        assert!(matches!(
+            inspector.ensure_not_timed_out(),
+            Err(EthApiError::ExecutionTimedOut(timeout)) if timeout.is_zero()
+        ));
+        let counter = inspector.inner_mut();
+        assert_eq!(counter.steps, 0);
+        assert_eq!(counter.step_ends, 0);
+        assert_eq!((counter.calls, counter.call_ends), (0, 0));
+    }
+
+    #[test]
+    fn detects_deadline_exceeded_after_execution() {
+        let mut inspector =
+            TracerDeadline::new(CallbackCounter::default(), Some(Duration::from_millis(50)));
+        run_loop(&mut inspector);
+        assert!(inspector.ensure_not_timed_out().is_ok());
+
+        // e.g. a slow `result` function of a `JavaScript` tracer
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(matches!(inspector.ensure_not_timed_out(), Err(EthApiError::ExecutionTimedOut(_))));
+    }
+
+    #[test]
+    fn restarts_deadline_for_every_transaction() {
+        // every transaction takes longer than half of the timeout, so they would exceed a single
+        // deadline for the whole block
+        let timeout = Duration::from_millis(100);
+        let mut inspector = TracerDeadline::new(CallbackCounter::default(), Some(timeout));
+        for _ in 0..3 {
+            inspector.restart();
+            run_loop(&mut inspector);
+            std::thread::sleep(timeout / 2);
+            assert!(inspector.ensure_not_timed_out().is_ok());
+        }
+
+        // without a restart the deadline of the last transaction is exceeded
+        std::thread::sleep(timeout);
+        assert!(matches!(inspector.ensure_not_timed_out(), Err(EthApiError::ExecutionTimedOut(_))));
+    }
+
+    #[test]
+    fn parse_tracer_timeout() {
+        let opts = GethDebugTracingOptions::default().with_timeout(Duration::from_millis(300));
+        assert_eq!(tracer_timeout(&opts).unwrap(), Some(Duration::from_millis(300)));
+
+        let opts = GethDebugTracingOptions { timeout: Some("2s".into()), ..Default::default() };
+        assert_eq!(tracer_timeout(&opts).unwrap(), Some(Duration::from_secs(2)));
+
+        let opts = GethDebugTracingOptions { timeout: Some("soon".into()), ..Default::default() };
+        assert!(tracer_timeout(&opts).is_err());
+    }
+
+    #[test]
+    fn default_tracer_timeout() {
+        let opts = GethDebugTracingOptions::default().with_tracer(
+            GethDebugTracerType::JsTracer("{result: function() {}, fault: function() {}}".into()),
+        );
+        assert_eq!(tracer_timeout(&opts).unwrap(), Some(DEFAULT_JS_TRACER_TIMEOUT));
+
+        let opts = GethDebugTracingOptions::default()
+            .with_tracer(GethDebugBuiltInTracerType::CallTracer.into());
+        assert_eq!(tracer_timeout(&opts).unwrap(), None);
+    }
+}
@@ -12,8 +12,9 @@ use hanzo_evm_primitives_traits::{format_gas_throughput, BlockBody, NodePrimitiv
 use hanzo_evm_provider::{
     providers::{StaticFileProvider, StaticFileWriter},
     BlockHashReader, BlockReader, DBProvider, EitherWriter, ExecutionOutcome, HeaderProvider,
-    LatestStateProviderRef, OriginalValuesKnown, ProviderError, StateWriteConfig, StateWriter,
-    StaticFileProviderFactory, StatsReader, StorageSettingsCache, TransactionVariant,
+    LatestStateProviderRef, OriginalValuesKnown, ProviderError, StateCursorProvider,
+    StateWriteConfig, StateWriter, StaticFileProviderFactory, StatsReader, StorageSettingsCache,
+    TransactionVariant,
 };
 use hanzo_evm_revm::database::StateProviderDatabase;
 use hanzo_evm_stages_api::{
@@ -268,6 +269,7 @@ where
         > + StatsReader
         + BlockHashReader
         + StateWriter<Receipt = <E::Primitives as NodePrimitives>::Receipt>
+        + StateCursorProvider
         + StorageSettingsCache,
 {
     /// Return the id of the stage
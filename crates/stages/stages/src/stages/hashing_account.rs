@@ -4,13 +4,14 @@ use hanzo_evm_config::config::{EtlConfig, HashingConfig};
 use hanzo_evm_db_api::{
     cursor::{DbCursorRO, DbCursorRW},
     tables,
-    transaction::{DbTx, DbTxMut},
-    RawKey, RawTable, RawValue,
+    transaction::DbTxMut,
+    RawKey, RawValue,
 };
 use reth_etl::Collector;
 use reth_primitives_traits::Account;
 use reth_provider::{
-    AccountExtReader, DBProvider, HashingWriter, StatsReader, StorageSettingsCache,
+    AccountExtReader, DBProvider, HashingWriter, StateCursorProvider, StatsReader,
+    StorageSettingsCache,
 };
 use reth_stages_api::{
     AccountHashingCheckpoint, EntitiesCheckpoint, ExecInput, ExecOutput, Stage, StageCheckpoint,
@@ -61,7 +62,10 @@ impl AccountHashingStage {
     ///
     /// Proceeds to go to the `BlockTransitionIndex` end, go back `transitions` and change the
     /// account state in the `AccountChangeSets` table.
-    pub fn seed<Tx: DbTx + DbTxMut + 'static, N: hanzo_evm_provider::providers::ProviderNodeTypes>(
+    pub fn seed<
+        Tx: hanzo_evm_db_api::transaction::DbTx + DbTxMut + 'static,
+        N: hanzo_evm_provider::providers::ProviderNodeTypes,
+    >(
         provider: &hanzo_evm_provider::DatabaseProvider<Tx, N>,
         opts: SeedOpts,
     ) -> Result<Vec<(alloy_primitives::Address, Account)>, StageError>
@@ -137,6 +141,7 @@ impl Default for AccountHashingStage {
 impl<Provider> Stage<Provider> for AccountHashingStage
 where
     Provider: DBProvider<Tx: DbTxMut>
+        + StateCursorProvider
         + HashingWriter
         + AccountExtReader
         + StatsReader
@@ -170,12 +175,10 @@ where
         // AccountHashing table. Also, if we start from genesis, we need to hash from scratch, as
         // genesis accounts are not in changeset.
         if to_block - from_block > self.clean_threshold || from_block == 1 {
-            let tx = provider.tx_ref();
-
             // clear table, load all accounts and hash it
-            tx.clear::<tables::HashedAccounts>()?;
+            provider.clear_state_table::<tables::HashedAccounts>()?;
 
-            let mut accounts_cursor = tx.cursor_read::<RawTable<tables::PlainAccountState>>()?;
+            let mut accounts_cursor = provider.state_cursor_read::<tables::PlainAccountState>()?;
             let mut collector =
                 Collector::new(self.etl_config.file_size, self.etl_config.dir.clone());
             let mut channels = Vec::with_capacity(MAXIMUM_CHANNELS);
@@ -189,8 +192,7 @@ where
                 // Spawn the hashing task onto the global rayon pool
                 rayon::spawn(move || {
                     for (address, account) in chunk {
-                        let address = address.key().unwrap();
-                        let _ = tx.send((RawKey::new(keccak256(address)), account));
+                        let _ = tx.send((RawKey::new(keccak256(address)), RawValue::new(account)));
                     }
                 });
 
@@ -203,7 +205,7 @@ where
             collect(&mut channels, &mut collector)?;
 
             let mut hashed_account_cursor =
-                tx.cursor_write::<RawTable<tables::HashedAccounts>>()?;
+                provider.state_cursor_write::<tables::HashedAccounts>()?;
 
             let total_hashes = collector.len();
             let interval = (total_hashes / 10).max(1);
@@ -217,8 +219,10 @@ where
                 }
 
                 let (key, value) = item?;
-                hashed_account_cursor
-                    .append(RawKey::<B256>::from_vec(key), &RawValue::<Account>::from_vec(value))?;
+                hashed_account_cursor.append(
+                    RawKey::<B256>::from_vec(key).key()?,
+                    &RawValue::<Account>::from_vec(value).value()?,
+                )?;
             }
         } else {
             // Aggregate all transition changesets and make a list of accounts that have been
@@ -323,6 +327,7 @@ mod tests {
     };
     use alloy_primitives::U256;
     use assert_matches::assert_matches;
+    use hanzo_evm_db_api::transaction::DbTx;
     use hanzo_evm_primitives_traits::Account;
     use hanzo_evm_provider::providers::StaticFileWriter;
     use hanzo_evm_stages_api::StageUnitCheckpoint;
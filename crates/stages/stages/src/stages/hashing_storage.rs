@@ -6,11 +6,13 @@ use hanzo_evm_db_api::{
     models::CompactU256,
     table::Decompress,
     tables,
-    transaction::{DbTx, DbTxMut},
+    transaction::DbTxMut,
 };
 use hanzo_evm_etl::Collector;
 use hanzo_evm_primitives_traits::StorageEntry;
-use hanzo_evm_provider::{DBProvider, HashingWriter, StatsReader, StorageReader};
+use hanzo_evm_provider::{
+    DBProvider, HashingWriter, StateCursorProvider, StatsReader, StorageReader,
+};
 use hanzo_evm_stages_api::{
     EntitiesCheckpoint, ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId,
     StorageHashingCheckpoint, UnwindInput, UnwindOutput,
@@ -70,6 +72,7 @@ impl Default for StorageHashingStage {
 impl<Provider> Stage<Provider> for StorageHashingStage
 where
     Provider: DBProvider<Tx: DbTxMut>
+        + StateCursorProvider
         + StorageReader
         + HashingWriter
         + StatsReader
@@ -82,7 +85,6 @@ where
 
     /// Execute the stage.
     fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
-        let tx = provider.tx_ref();
         if input.target_reached() {
             return Ok(ExecOutput::done(input.checkpoint()))
         }
@@ -101,9 +103,9 @@ where
         // genesis accounts are not in changeset, along with their storages.
         if to_block - from_block > self.clean_threshold || from_block == 1 {
             // clear table, load all accounts and hash it
-            tx.clear::<tables::HashedStorages>()?;
+            provider.clear_state_table::<tables::HashedStorages>()?;
 
-            let mut storage_cursor = tx.cursor_read::<tables::PlainStorageState>()?;
+            let mut storage_cursor = provider.state_cursor_read::<tables::PlainStorageState>()?;
             let mut collector =
                 Collector::new(self.etl_config.file_size, self.etl_config.dir.clone());
             let mut channels = Vec::with_capacity(MAXIMUM_CHANNELS);
@@ -139,7 +141,7 @@ where
 
             let total_hashes = collector.len();
             let interval = (total_hashes / 10).max(1);
-            let mut cursor = tx.cursor_dup_write::<tables::HashedStorages>()?;
+            let mut cursor = provider.state_cursor_dup_write::<tables::HashedStorages>()?;
             for (index, item) in collector.iter()?.enumerate() {
                 if index > 0 && index.is_multiple_of(interval) {
                     info!(
@@ -243,6 +245,7 @@ mod tests {
     use hanzo_evm_db_api::{
         cursor::{DbCursorRW, DbDupCursorRO},
         models::{BlockNumberAddress, StoredBlockBodyIndices},
+        transaction::DbTx,
     };
     use hanzo_evm_ethereum_primitives::Block;
     use hanzo_evm_primitives_traits::SealedBlock;
@@ -9,14 +9,14 @@ use hanzo_evm_db_api::{
 use hanzo_evm_primitives_traits::{GotExpected, SealedHeader};
 use hanzo_evm_provider::{
     ChangeSetReader, DBProvider, HeaderProvider, ProviderError, StageCheckpointReader,
-    StageCheckpointWriter, StatsReader, StorageChangeSetReader, StorageSettingsCache, TrieWriter,
+    StageCheckpointWriter, StateCursorFactory, StateCursorProvider, StatsReader,
+    StorageChangeSetReader, StorageSettingsCache, TrieWriter,
 };
 use hanzo_evm_stages_api::{
     BlockErrorKind, EntitiesCheckpoint, ExecInput, ExecOutput, MerkleCheckpoint, Stage,
     StageCheckpoint, StageError, StageId, StorageRootMerkleCheckpoint, UnwindInput, UnwindOutput,
 };
-use hanzo_evm_trie::{IntermediateStateRootState, StateRoot, StateRootProgress, StoredSubNode};
-use hanzo_evm_trie_db::DatabaseStateRoot;
+use hanzo_evm_trie::{IntermediateStateRootState, StateRootProgress, StoredSubNode};
 use std::fmt::Debug;
 use tracing::*;
 
@@ -155,6 +155,7 @@ impl MerkleStage {
 impl<Provider> Stage<Provider> for MerkleStage
 where
     Provider: DBProvider<Tx: DbTxMut>
+        + StateCursorProvider
         + TrieWriter
         + StatsReader
         + HeaderProvider
@@ -228,8 +229,8 @@ where
                 // Reset the checkpoint and clear trie tables
                 checkpoint = None;
                 self.save_execution_checkpoint(provider, None)?;
-                provider.tx_ref().clear::<tables::AccountsTrie>()?;
-                provider.tx_ref().clear::<tables::StoragesTrie>()?;
+                provider.clear_state_table::<tables::AccountsTrie>()?;
+                provider.clear_state_table::<tables::StoragesTrie>()?;
 
                 None
             }
@@ -240,8 +241,8 @@ where
                     as u64,
             });
 
-            let tx = provider.tx_ref();
-            let progress = StateRoot::from_tx(tx)
+            let progress = StateCursorFactory::new(provider)
+                .state_root()
                 .with_intermediate_state(checkpoint.map(IntermediateStateRootState::from))
                 .root_with_progress()
                 .map_err(|e| {
@@ -315,7 +316,7 @@ where
                     "Processing chunk"
                 );
                 let (root, updates) =
-                StateRoot::incremental_root_with_updates(provider, chunk_range)
+                StateCursorFactory::new(provider).incremental_root_with_updates(chunk_range)
                     .map_err(|e| {
                         error!(target: "sync::stages::merkle", %e, ?current_block_number, ?to_block, "Incremental state root failed! {INVALID_STATE_ROOT_ERROR_MESSAGE}");
                         StageError::Fatal(Box::new(e))
@@ -377,8 +378,8 @@ where
             });
 
         if input.unwind_to == 0 {
-            tx.clear::<tables::AccountsTrie>()?;
-            tx.clear::<tables::StoragesTrie>()?;
+            provider.clear_state_table::<tables::AccountsTrie>()?;
+            provider.clear_state_table::<tables::StoragesTrie>()?;
 
             entities_checkpoint.processed = 0;
 
@@ -392,7 +393,8 @@ where
         if range.is_empty() {
             info!(target: "sync::stages::merkle::unwind", "Nothing to unwind");
         } else {
-            let (block_root, updates) = StateRoot::incremental_root_with_updates(provider, range)
+            let (block_root, updates) = StateCursorFactory::new(provider)
+                .incremental_root_with_updates(range)
                 .map_err(|e| StageError::Fatal(Box::new(e)))?;
 
             // Validate the calculated state root
@@ -459,7 +461,11 @@ mod tests {
         self, random_block, random_block_range, random_changeset_range,
         random_contract_account_range, BlockParams, BlockRangeParams,
     };
-    use hanzo_evm_trie::test_utils::{state_root, state_root_prehashed};
+    use hanzo_evm_trie::{
+        test_utils::{state_root, state_root_prehashed},
+        StateRoot,
+    };
+    use hanzo_evm_trie_db::DatabaseStateRoot;
     use std::collections::BTreeMap;
 
     stage_test_suite_ext!(MerkleTestRunner, merkle);
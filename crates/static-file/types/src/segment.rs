@@ -61,6 +61,13 @@ pub enum StaticFileSegment {
     /// Storage changeset static files append block-by-block changesets sorted by address and
     /// storage slot.
     StorageChangeSets,
+    /// Static File segment responsible for periodic snapshots of the `HashedAccounts` and
+    /// `HashedStorages` tables.
+    ///
+    /// State snapshot static files are change-based: every block has an offset, but only
+    /// checkpoint blocks have rows. The rows of a checkpoint hold the full state after that block,
+    /// sorted by hashed address with every account followed by its storage slots.
+    StateSnapshots,
 }
 
 impl StaticFileSegment {
@@ -78,6 +85,7 @@ impl StaticFileSegment {
             Self::TransactionSenders => "transaction-senders",
             Self::AccountChangeSets => "account-change-sets",
             Self::StorageChangeSets => "storage-change-sets",
+            Self::StateSnapshots => "state-snapshots",
         }
     }
 
@@ -92,6 +100,7 @@ impl StaticFileSegment {
             Self::TransactionSenders => "tx-senders",
             Self::AccountChangeSets => "account-changes",
             Self::StorageChangeSets => "storage-changes",
+            Self::StateSnapshots => "state-snapshots",
         }
     }
 
@@ -105,6 +114,7 @@ impl StaticFileSegment {
             Self::TransactionSenders,
             Self::AccountChangeSets,
             Self::StorageChangeSets,
+            Self::StateSnapshots,
         ]
         .into_iter()
     }
@@ -122,7 +132,8 @@ impl StaticFileSegment {
             Self::Receipts |
             Self::TransactionSenders |
             Self::AccountChangeSets |
-            Self::StorageChangeSets => 1,
+            Self::StorageChangeSets |
+            Self::StateSnapshots => 1,
         }
     }
 
@@ -184,14 +195,17 @@ impl StaticFileSegment {
     pub const fn is_tx_based(&self) -> bool {
         match self {
             Self::Receipts | Self::Transactions | Self::TransactionSenders => true,
-            Self::Headers | Self::AccountChangeSets | Self::StorageChangeSets => false,
+            Self::Headers |
+            Self::AccountChangeSets |
+            Self::StorageChangeSets |
+            Self::StateSnapshots => false,
         }
     }
 
     /// Returns `true` if the segment is change-based.
     pub const fn is_change_based(&self) -> bool {
         match self {
-            Self::AccountChangeSets | Self::StorageChangeSets => true,
+            Self::AccountChangeSets | Self::StorageChangeSets | Self::StateSnapshots => true,
             Self::Receipts | Self::Transactions | Self::Headers | Self::TransactionSenders => false,
         }
     }
@@ -204,7 +218,8 @@ impl StaticFileSegment {
             Self::Transactions |
             Self::TransactionSenders |
             Self::AccountChangeSets |
-            Self::StorageChangeSets => false,
+            Self::StorageChangeSets |
+            Self::StateSnapshots => false,
         }
     }
 
@@ -219,9 +234,10 @@ impl StaticFileSegment {
         match self {
             Self::Headers => StageId::Headers,
             Self::Transactions => StageId::Bodies,
-            Self::Receipts | Self::AccountChangeSets | Self::StorageChangeSets => {
-                StageId::Execution
-            }
+            Self::Receipts |
+            Self::AccountChangeSets |
+            Self::StorageChangeSets |
+            Self::StateSnapshots => StageId::Execution,
             Self::TransactionSenders => StageId::SenderRecovery,
         }
     }
@@ -714,6 +730,12 @@ mod tests {
                 "static_file_storage-change-sets_1123233_11223233",
                 None,
             ),
+            (
+                StaticFileSegment::StateSnapshots,
+                1_123_233..=11_223_233,
+                "static_file_state-snapshots_1123233_11223233",
+                None,
+            ),
             (
                 StaticFileSegment::Headers,
                 2..=30,
@@ -804,6 +826,13 @@ mod tests {
                 segment: StaticFileSegment::StorageChangeSets,
                 changeset_offsets_len: 100,
             },
+            SegmentHeader {
+                expected_block_range: SegmentRangeInclusive::new(0, 200),
+                block_range: Some(SegmentRangeInclusive::new(0, 100)),
+                tx_range: None,
+                segment: StaticFileSegment::StateSnapshots,
+                changeset_offsets_len: 100,
+            },
         ];
         // Check that we test all segments
         assert_eq!(
@@ -838,6 +867,7 @@ mod tests {
                 StaticFileSegment::TransactionSenders => "transaction-senders",
                 StaticFileSegment::AccountChangeSets => "account-change-sets",
                 StaticFileSegment::StorageChangeSets => "storage-change-sets",
+                StaticFileSegment::StateSnapshots => "state-snapshots",
             };
             assert_eq!(static_str, expected_str);
         }
@@ -857,6 +887,7 @@ mod tests {
                 StaticFileSegment::TransactionSenders => "TransactionSenders",
                 StaticFileSegment::AccountChangeSets => "AccountChangeSets",
                 StaticFileSegment::StorageChangeSets => "StorageChangeSets",
+                StaticFileSegment::StateSnapshots => "StateSnapshots",
             };
             assert_eq!(ser, format!("\"{expected_str}\""));
         }
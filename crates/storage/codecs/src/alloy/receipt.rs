@@ -34,40 +34,60 @@ mod flags {
 
 pub(crate) use flags::ReceiptFlags;
 
+/// Encodes the receipt, compressing it with the fixed receipt dictionary if `compress` is set and
+/// the receipt is large enough.
+fn receipt_to_compact<T: Compact, B>(
+    receipt: &AlloyEthereumReceipt<T>,
+    buf: &mut B,
+    compress: bool,
+) -> usize
+where
+    B: bytes::BufMut + AsMut<[u8]>,
+{
+    let mut flags = ReceiptFlags::default();
+    let mut total_length = 0;
+    let mut buffer = bytes::BytesMut::new();
+
+    let tx_type_len = receipt.tx_type.to_compact(&mut buffer);
+    flags.set_tx_type_len(tx_type_len as u8);
+    let success_len = receipt.success.to_compact(&mut buffer);
+    flags.set_success_len(success_len as u8);
+    let cumulative_gas_used_len = receipt.cumulative_gas_used.to_compact(&mut buffer);
+    flags.set_cumulative_gas_used_len(cumulative_gas_used_len as u8);
+    receipt.logs.to_compact(&mut buffer);
+
+    let zstd = compress && buffer.len() > 7;
+    if zstd {
+        flags.set___zstd(1);
+    }
+
+    let flags = flags.into_bytes();
+    total_length += flags.len() + buffer.len();
+    buf.put_slice(&flags);
+    if zstd {
+        reth_zstd_compressors::with_receipt_compressor(|compressor| {
+            let compressed = compressor.compress(&buffer).expect("Failed to compress.");
+            buf.put(compressed.as_slice());
+        });
+    } else {
+        buf.put(buffer);
+    }
+    total_length
+}
+
 impl<T: Compact> Compact for AlloyEthereumReceipt<T> {
     fn to_compact<B>(&self, buf: &mut B) -> usize
     where
         B: bytes::BufMut + AsMut<[u8]>,
     {
-        let mut flags = ReceiptFlags::default();
-        let mut total_length = 0;
-        let mut buffer = bytes::BytesMut::new();
-
-        let tx_type_len = self.tx_type.to_compact(&mut buffer);
-        flags.set_tx_type_len(tx_type_len as u8);
-        let success_len = self.success.to_compact(&mut buffer);
-        flags.set_success_len(success_len as u8);
-        let cumulative_gas_used_len = self.cumulative_gas_used.to_compact(&mut buffer);
-        flags.set_cumulative_gas_used_len(cumulative_gas_used_len as u8);
-        self.logs.to_compact(&mut buffer);
-
-        let zstd = buffer.len() > 7;
-        if zstd {
-            flags.set___zstd(1);
-        }
+        receipt_to_compact(self, buf, true)
+    }
 
-        let flags = flags.into_bytes();
-        total_length += flags.len() + buffer.len();
-        buf.put_slice(&flags);
-        if zstd {
-            reth_zstd_compressors::with_receipt_compressor(|compressor| {
-                let compressed = compressor.compress(&buffer).expect("Failed to compress.");
-                buf.put(compressed.as_slice());
-            });
-        } else {
-            buf.put(buffer);
-        }
-        total_length
+    fn to_compact_uncompressed<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        receipt_to_compact(self, buf, false)
     }
 
     fn from_compact(buf: &[u8], _len: usize) -> (Self, &[u8]) {
@@ -118,5 +138,16 @@ mod tests {
             let (decoded, _) = AlloyEthereumReceipt::<TxType>::from_compact(&compacted_receipt, len);
             assert_eq!(receipt, decoded)
         }
+
+        #[test]
+        fn roundtrip_receipt_without_compression(
+            receipt in arb::<AlloyEthereumReceipt<TxType>>()
+        ) {
+            let mut compacted_receipt = Vec::<u8>::new();
+            let len = receipt.to_compact_uncompressed(&mut compacted_receipt);
+            assert_eq!(ReceiptFlags::from_bytes([compacted_receipt[0]]).__zstd(), 0);
+            let (decoded, _) = AlloyEthereumReceipt::<TxType>::from_compact(&compacted_receipt, len);
+            assert_eq!(receipt, decoded)
+        }
     }
 }
@@ -119,6 +119,12 @@ pub trait CompactEnvelope: Sized {
     where
         B: BufMut + AsMut<[u8]>;
 
+    /// Same as [`CompactEnvelope::to_compact`], but doesn't compress the transaction with the
+    /// fixed zstd dictionary, see [`Compact::to_compact_uncompressed`].
+    fn to_compact_uncompressed<B>(&self, buf: &mut B) -> usize
+    where
+        B: BufMut + AsMut<[u8]>;
+
     /// Takes a buffer which can be read from. Returns the object and `buf` with its internal cursor
     /// advanced (eg.`.advance(len)`).
     ///
@@ -128,43 +134,60 @@ pub trait CompactEnvelope: Sized {
     fn from_compact(buf: &[u8], len: usize) -> (Self, &[u8]);
 }
 
+/// Encodes the transaction envelope, compressing it with the fixed transaction dictionary if
+/// `compress` is set and the input is large enough.
+fn envelope_to_compact<T, B>(tx: &T, buf: &mut B, compress: bool) -> usize
+where
+    T: Envelope + ToTxCompact + Transaction,
+    B: BufMut + AsMut<[u8]>,
+{
+    let start = buf.as_mut().len();
+
+    // Placeholder for bitflags.
+    // The first byte uses 4 bits as flags: IsCompressed[1bit], TxType[2bits], Signature[1bit]
+    buf.put_u8(0);
+
+    let sig_bit = tx.signature().to_compact(buf) as u8;
+    let zstd_bit = compress && tx.input().len() >= 32;
+
+    let tx_bits = if zstd_bit {
+        // compress the tx prefixed with txtype
+        let mut tx_buf = Vec::with_capacity(256);
+        let tx_bits = tx.tx_type().to_compact(&mut tx_buf) as u8;
+        tx.to_tx_compact(&mut tx_buf);
+
+        buf.put_slice(
+            &hanzo_evm_zstd_compressors::with_tx_compressor(|compressor| {
+                compressor.compress(&tx_buf)
+            })
+            .expect("Failed to compress"),
+        );
+        tx_bits
+    } else {
+        let tx_bits = tx.tx_type().to_compact(buf) as u8;
+        tx.to_tx_compact(buf);
+        tx_bits
+    };
+
+    let flags = sig_bit | (tx_bits << 1) | ((zstd_bit as u8) << 3);
+    buf.as_mut()[start] = flags;
+
+    buf.as_mut().len() - start
+}
+
 impl<T: Envelope + ToTxCompact + Transaction + Send + Sync> CompactEnvelope for T {
     fn to_compact<B>(&self, buf: &mut B) -> usize
     where
         B: BufMut + AsMut<[u8]>,
     {
-        let start = buf.as_mut().len();
-
-        // Placeholder for bitflags.
-        // The first byte uses 4 bits as flags: IsCompressed[1bit], TxType[2bits], Signature[1bit]
-        buf.put_u8(0);
-
-        let sig_bit = self.signature().to_compact(buf) as u8;
-        let zstd_bit = self.input().len() >= 32;
-
-        let tx_bits = if zstd_bit {
-            // compress the tx prefixed with txtype
-            let mut tx_buf = Vec::with_capacity(256);
-            let tx_bits = self.tx_type().to_compact(&mut tx_buf) as u8;
-            self.to_tx_compact(&mut tx_buf);
-
-            buf.put_slice(
-                &hanzo_evm_zstd_compressors::with_tx_compressor(|compressor| {
-                    compressor.compress(&tx_buf)
-                })
-                .expect("Failed to compress"),
-            );
-            tx_bits
-        } else {
-            let tx_bits = self.tx_type().to_compact(buf) as u8;
-            self.to_tx_compact(buf);
-            tx_bits
-        };
-
-        let flags = sig_bit | (tx_bits << 1) | ((zstd_bit as u8) << 3);
-        buf.as_mut()[start] = flags;
+        envelope_to_compact(self, buf, true)
+    }
 
-        buf.as_mut().len() - start
+    fn to_compact_uncompressed<B>(&self, buf: &mut B) -> usize
+    where
+        B: BufMut + AsMut<[u8]>,
+    {
+        envelope_to_compact(self, buf, false)
     }
 
     fn from_compact(mut buf: &[u8], _len: usize) -> (Self, &[u8]) {
@@ -202,6 +225,13 @@ impl<Eip4844: Compact + RlpEcdsaEncodableTx + Transaction + Send + Sync> Compact
         <Self as CompactEnvelope>::to_compact(self, buf)
     }
 
+    fn to_compact_uncompressed<B>(&self, buf: &mut B) -> usize
+    where
+        B: BufMut + AsMut<[u8]>,
+    {
+        <Self as CompactEnvelope>::to_compact_uncompressed(self, buf)
+    }
+
     fn from_compact(buf: &[u8], len: usize) -> (Self, &[u8]) {
         <Self as CompactEnvelope>::from_compact(buf, len)
     }
@@ -93,6 +93,30 @@ mod tests {
         assert_eq!(TxEip7702::bitflag_encoded_bytes(), 4);
     }
 
+    #[test]
+    fn test_envelope_without_compression() {
+        use crate::Compact;
+        use alloc::{vec, vec::Vec};
+        use alloy_consensus::{EthereumTxEnvelope, Signed};
+
+        let tx: EthereumTxEnvelope<alloy_consensus::TxEip4844> = Signed::new_unhashed(
+            alloy_consensus::TxLegacy { input: vec![1; 64].into(), ..Default::default() },
+            alloy_primitives::Signature::test_signature(),
+        )
+        .into();
+
+        // the zstd flag is the fourth bit of the first byte
+        let mut compressed = Vec::new();
+        let len = tx.to_compact(&mut compressed);
+        assert_ne!(compressed[0] & 0b1000, 0);
+        assert_eq!(EthereumTxEnvelope::from_compact(&compressed, len).0, tx);
+
+        let mut uncompressed = Vec::new();
+        let len = tx.to_compact_uncompressed(&mut uncompressed);
+        assert_eq!(uncompressed[0] & 0b1000, 0);
+        assert_eq!(EthereumTxEnvelope::from_compact(&uncompressed, len).0, tx);
+    }
+
     #[cfg(feature = "op")]
     #[test]
     fn test_ensure_backwards_compatibility_optimism() {
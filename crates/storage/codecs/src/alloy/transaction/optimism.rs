@@ -244,6 +244,13 @@ impl Compact for OpTxEnvelope {
         CompactEnvelope::to_compact(self, buf)
     }
 
+    fn to_compact_uncompressed<B>(&self, buf: &mut B) -> usize
+    where
+        B: BufMut + AsMut<[u8]>,
+    {
+        CompactEnvelope::to_compact_uncompressed(self, buf)
+    }
+
     fn from_compact(buf: &[u8], len: usize) -> (Self, &[u8]) {
         CompactEnvelope::from_compact(buf, len)
     }
@@ -98,6 +98,20 @@ pub trait Compact: Sized {
     fn specialized_from_compact(buf: &[u8], len: usize) -> (Self, &[u8]) {
         Self::from_compact(buf, len)
     }
+
+    /// Same as [`Compact::to_compact`], but doesn't compress the value with the fixed zstd
+    /// dictionaries of the codecs. The value is decoded with [`Compact::from_compact`] as usual.
+    ///
+    /// Used to encode values for static files compressed with their own trained dictionaries.
+    /// Types that are never compressed, or only compressed by a derived implementation, encode
+    /// the same as with [`Compact::to_compact`].
+    #[inline]
+    fn to_compact_uncompressed<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        self.to_compact(buf)
+    }
 }
 
 impl Compact for alloc::string::String {
@@ -1,5 +1,6 @@
 //! Storage metadata models.
 
+use alloy_primitives::{BlockHash, BlockNumber, B256};
 use hanzo_evm_codecs::{add_arbitrary_tests, Compact};
 use serde::{Deserialize, Serialize};
 
@@ -24,6 +25,17 @@ pub struct StorageSettings {
     ///
     /// When `false`, uses v1/legacy layout (everything in MDBX).
     pub storage_v2: bool,
+    /// Whether state tables are stored in `RocksDB` instead of MDBX.
+    ///
+    /// When `true`, plain state (`PlainAccountState`/`PlainStorageState`), hashed state
+    /// (`HashedAccounts`/`HashedStorages`) and trie tables (`AccountsTrie`/`StoragesTrie`) are
+    /// routed to `RocksDB`. Independent of [`Self::storage_v2`].
+    ///
+    /// This layout exists to benchmark an LSM-backed state store. It can only be chosen at
+    /// genesis, and table entry counts reported by the database do not include the state kept in
+    /// `RocksDB`.
+    #[serde(default)]
+    pub state_in_rocksdb: bool,
 }
 
 impl StorageSettings {
@@ -52,14 +64,20 @@ impl StorageSettings {
     ///
     /// Use this when the `--storage.v2` CLI flag is set.
     pub const fn v2() -> Self {
-        Self { storage_v2: true }
+        Self { storage_v2: true, state_in_rocksdb: false }
     }
 
     /// Creates `StorageSettings` for v1/legacy nodes.
     ///
     /// This keeps all data in MDBX, matching the original storage layout.
     pub const fn v1() -> Self {
-        Self { storage_v2: false }
+        Self { storage_v2: false, state_in_rocksdb: false }
+    }
+
+    /// Sets whether state tables are stored in `RocksDB`.
+    pub const fn with_state_in_rocksdb(mut self, state_in_rocksdb: bool) -> Self {
+        self.state_in_rocksdb = state_in_rocksdb;
+        self
     }
 
     /// Returns `true` if this node uses v2 storage layout.
@@ -98,8 +116,54 @@ impl StorageSettings {
         self.storage_v2
     }
 
+    /// Whether plain state, hashed state and trie tables are stored in `RocksDB`.
+    pub const fn state_in_rocksdb(&self) -> bool {
+        self.state_in_rocksdb
+    }
+
     /// Returns `true` if any tables are configured to be stored in `RocksDB`.
     pub const fn any_in_rocksdb(&self) -> bool {
-        self.storage_v2
+        self.storage_v2 || self.state_in_rocksdb
     }
 }
+
+/// Describes a storage snapshot: a consistent copy of the MDBX database, static files and
+/// `RocksDB` taken from a node, laid out like its data directory.
+///
+/// Written as [`StorageSnapshotManifest::FILE_NAME`] at the root of the snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageSnapshotManifest {
+    /// Format version of the manifest.
+    pub version: u64,
+    /// Block the MDBX copy was pinned at.
+    ///
+    /// Static files and `RocksDB` may contain later blocks, those are unwound to this block when
+    /// the node first opens the restored data directory.
+    pub block_number: BlockNumber,
+    /// Hash of [`Self::block_number`].
+    pub block_hash: BlockHash,
+    /// Storage settings of the copied database.
+    pub storage_settings: StorageSettings,
+    /// Files of the snapshot, sorted by path.
+    pub files: Vec<StorageSnapshotFile>,
+}
+
+impl StorageSnapshotManifest {
+    /// Current manifest format version.
+    pub const VERSION: u64 = 2;
+
+    /// Name of the manifest file at the root of a snapshot.
+    pub const FILE_NAME: &'static str = "manifest.json";
+}
+
+/// A file of a [`StorageSnapshotManifest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageSnapshotFile {
+    /// Path relative to the snapshot root, using `/` as separator.
+    pub path: String,
+    /// Size in bytes.
+    pub size: u64,
+    /// Keccak256 hash of the file contents.
+    pub hash: B256,
+}
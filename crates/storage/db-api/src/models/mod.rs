@@ -27,8 +27,8 @@ pub use blocks::*;
 pub use integer_list::IntegerList;
 pub use metadata::*;
 pub use hanzo_evm_db_models::{
-    AccountBeforeTx, ClientVersion, StaticFileBlockWithdrawals, StorageBeforeTx,
-    StoredBlockBodyIndices, StoredBlockWithdrawals,
+    AccountBeforeTx, ClientVersion, StateSnapshotEntry, StaticFileBlockWithdrawals,
+    StorageBeforeTx, StoredBlockBodyIndices, StoredBlockWithdrawals,
 };
 pub use sharded_key::ShardedKey;
 
@@ -225,6 +225,7 @@ impl_compression_for_compact!(
     Bytecode,
     AccountBeforeTx,
     StorageBeforeTx,
+    StateSnapshotEntry,
     TransactionSigned,
     CompactU256,
     StageCheckpoint,
@@ -3,7 +3,7 @@ use crate::{
     table::{Decode, Encode},
     DatabaseError,
 };
-use alloy_primitives::{Address, BlockNumber};
+use alloy_primitives::{Address, BlockNumber, B256};
 use serde::{Deserialize, Serialize};
 use std::hash::Hash;
 
@@ -77,6 +77,37 @@ impl Decode for ShardedKey<Address> {
     }
 }
 
+/// Stack-allocated encoded key for `ShardedKey<B256>`.
+///
+/// The key layout is:
+/// - 32 bytes: `B256`
+/// - 8 bytes: `BlockNumber` (big-endian)
+pub type ShardedKeyB256Encoded = [u8; 32 + BLOCK_NUMBER_SIZE];
+
+impl Encode for ShardedKey<B256> {
+    type Encoded = ShardedKeyB256Encoded;
+
+    #[inline]
+    fn encode(self) -> Self::Encoded {
+        let mut buf = [0u8; 32 + BLOCK_NUMBER_SIZE];
+        buf[..32].copy_from_slice(self.key.as_slice());
+        buf[32..].copy_from_slice(&self.highest_block_number.to_be_bytes());
+        buf
+    }
+}
+
+impl Decode for ShardedKey<B256> {
+    fn decode(value: &[u8]) -> Result<Self, DatabaseError> {
+        if value.len() != 32 + BLOCK_NUMBER_SIZE {
+            return Err(DatabaseError::Decode);
+        }
+        let key = B256::from_slice(&value[..32]);
+        let highest_block_number =
+            u64::from_be_bytes(value[32..].try_into().map_err(|_| DatabaseError::Decode)?);
+        Ok(Self::new(key, highest_block_number))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -110,4 +141,16 @@ mod tests {
         let decoded = ShardedKey::<Address>::decode(&encoded).unwrap();
         assert_eq!(decoded.highest_block_number, u64::MAX);
     }
+
+    #[test]
+    fn sharded_key_b256_encode_decode_roundtrip() {
+        let key = ShardedKey::new(B256::repeat_byte(0xab), 0x123456789ABCDEF0u64);
+
+        let encoded = key.clone().encode();
+        assert_eq!(encoded.len(), 40);
+
+        let decoded = ShardedKey::<B256>::decode(&encoded).unwrap();
+        assert_eq!(decoded, key);
+        assert!(ShardedKey::<B256>::decode(&encoded[..39]).is_err());
+    }
 }
@@ -3,7 +3,7 @@ use crate::{
     table::{DupSort, Encode, Table},
     DatabaseError,
 };
-use std::fmt::Debug;
+use std::{fmt::Debug, path::Path};
 
 /// Helper adapter type for accessing [`DbTx`] cursor.
 pub type CursorTy<TX, T> = <TX as DbTx>::Cursor<T>;
@@ -46,6 +46,15 @@ pub trait DbTx: Debug + Send {
     fn entries<T: Table>(&self) -> Result<usize, DatabaseError>;
     /// Disables long-lived read transaction safety guarantees.
     fn disable_long_read_transaction_safety(&mut self);
+    /// Copies the whole database, as seen by this transaction, to a new file at `dest`.
+    ///
+    /// Databases that can't copy themselves from a transaction return an error.
+    fn copy_to_path(&self, dest: &Path) -> Result<(), DatabaseError> {
+        Err(DatabaseError::Other(format!(
+            "database can't be copied to {} from a transaction",
+            dest.display()
+        )))
+    }
 }
 
 /// Read write transaction that allows writing to database
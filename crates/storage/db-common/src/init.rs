@@ -26,16 +26,16 @@ use hanzo_evm_provider::{
     BundleStateInit, ChainSpecProvider, DBProvider, DatabaseProviderFactory, EitherWriter,
     ExecutionOutcome, HashingWriter, HeaderProvider, HistoryWriter, MetadataProvider,
     MetadataWriter, NodePrimitivesProvider, OriginalValuesKnown, ProviderError, RevertsInit,
-    RocksDBProviderFactory, StageCheckpointReader, StageCheckpointWriter, StateWriteConfig,
-    StateWriter, StaticFileProviderFactory, StorageSettings, StorageSettingsCache, TrieWriter,
+    RocksDBProviderFactory, StageCheckpointReader, StageCheckpointWriter, StateCursorFactory,
+    StateCursorProvider, StateWriteConfig, StateWriter, StaticFileProviderFactory, StorageSettings,
+    StorageSettingsCache, TrieWriter,
 };
 use hanzo_evm_stages_types::{StageCheckpoint, StageId};
 use hanzo_evm_static_file_types::StaticFileSegment;
 use hanzo_evm_trie::{
     prefix_set::{TriePrefixSets, TriePrefixSetsMut},
-    IntermediateStateRootState, Nibbles, StateRoot as StateRootComputer, StateRootProgress,
+    IntermediateStateRootState, Nibbles, StateRootProgress,
 };
-use hanzo_evm_trie_db::DatabaseStateRoot;
 use serde::{Deserialize, Serialize};
 use std::io::BufRead;
 use tracing::{debug, error, info, trace, warn};
@@ -109,6 +109,7 @@ where
         + HeaderProvider
         + HashingWriter
         + StateWriter
+        + StateCursorProvider
         + TrieWriter
         + MetadataWriter
         + ChainSpecProvider
@@ -140,6 +141,7 @@ where
         + HeaderProvider
         + HashingWriter
         + StateWriter
+        + StateCursorProvider
         + TrieWriter
         + MetadataWriter
         + ChainSpecProvider
@@ -611,6 +613,7 @@ where
         + HashingWriter
         + TrieWriter
         + StateWriter
+        + StateCursorProvider
         + StorageSettingsCache
         + RocksDBProviderFactory
         + NodePrimitivesProvider
@@ -824,17 +827,17 @@ fn compute_state_root<Provider>(
     prefix_sets: Option<TriePrefixSets>,
 ) -> Result<B256, InitStorageError>
 where
-    Provider: DBProvider<Tx: DbTxMut> + TrieWriter,
+    Provider: DBProvider<Tx: DbTxMut> + StateCursorProvider + TrieWriter,
 {
     trace!(target: "evm::cli", "Computing state root");
 
-    let tx = provider.tx_ref();
     let mut intermediate_state: Option<IntermediateStateRootState> = None;
     let mut total_flushed_updates = 0;
 
     loop {
-        let mut state_root =
-            StateRootComputer::from_tx(tx).with_intermediate_state(intermediate_state);
+        let mut state_root = StateCursorFactory::new(provider)
+            .state_root()
+            .with_intermediate_state(intermediate_state);
 
         if let Some(sets) = prefix_sets.clone() {
             state_root = state_root.with_prefix_sets(sets);
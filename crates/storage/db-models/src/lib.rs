@@ -23,6 +23,10 @@ pub use blocks::{StaticFileBlockWithdrawals, StoredBlockBodyIndices, StoredBlock
 pub mod storage;
 pub use storage::StorageBeforeTx;
 
+/// State snapshots
+pub mod snapshot;
+pub use snapshot::StateSnapshotEntry;
+
 /// Client Version
 pub mod client_version;
 pub use client_version::ClientVersion;
@@ -0,0 +1,88 @@
+use alloy_primitives::{B256, U256};
+use hanzo_evm_primitives_traits::Account;
+
+/// Row of a state snapshot static file.
+///
+/// Rows of a snapshot are sorted by [`StateSnapshotEntry::sort_key`], so every account is
+/// directly followed by its storage slots.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "hanzo-evm-codec"), hanzo_evm_codecs::add_arbitrary_tests(compact))]
+pub enum StateSnapshotEntry {
+    /// An account of the `HashedAccounts` table.
+    Account {
+        /// Hashed address of the account.
+        hashed_address: B256,
+        /// Account state.
+        account: Account,
+    },
+    /// A storage slot of the `HashedStorages` table.
+    Storage {
+        /// Hashed address of the account owning the slot.
+        hashed_address: B256,
+        /// Hashed storage key.
+        hashed_slot: B256,
+        /// Value of the slot.
+        value: U256,
+    },
+}
+
+impl StateSnapshotEntry {
+    /// Returns the hashed address of the entry.
+    pub const fn hashed_address(&self) -> B256 {
+        match self {
+            Self::Account { hashed_address, .. } | Self::Storage { hashed_address, .. } => {
+                *hashed_address
+            }
+        }
+    }
+
+    /// Returns the key snapshot rows are sorted by.
+    ///
+    /// Accounts have no slot and are therefore ordered before the storage of the same address.
+    pub const fn sort_key(&self) -> (B256, Option<B256>) {
+        match self {
+            Self::Account { hashed_address, .. } => (*hashed_address, None),
+            Self::Storage { hashed_address, hashed_slot, .. } => {
+                (*hashed_address, Some(*hashed_slot))
+            }
+        }
+    }
+}
+
+// NOTE: The variant is encoded as a leading tag byte, followed by the full hashed address (and
+// slot) so that rows can be compared without decompressing the remaining part of the value.
+#[cfg(any(test, feature = "hanzo-evm-codec"))]
+impl hanzo_evm_codecs::Compact for StateSnapshotEntry {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        match self {
+            Self::Account { hashed_address, account } => {
+                buf.put_u8(0);
+                buf.put_slice(hashed_address.as_slice());
+                account.to_compact(buf) + 33
+            }
+            Self::Storage { hashed_address, hashed_slot, value } => {
+                buf.put_u8(1);
+                buf.put_slice(hashed_address.as_slice());
+                buf.put_slice(hashed_slot.as_slice());
+                value.to_compact(buf) + 65
+            }
+        }
+    }
+
+    fn from_compact(buf: &[u8], len: usize) -> (Self, &[u8]) {
+        let hashed_address = B256::from_slice(&buf[1..33]);
+        if buf[0] == 0 {
+            let (account, out) = Account::from_compact(&buf[33..], len - 33);
+            (Self::Account { hashed_address, account }, out)
+        } else {
+            let hashed_slot = B256::from_slice(&buf[33..65]);
+            let (value, out) = U256::from_compact(&buf[65..], len - 65);
+            (Self::Storage { hashed_address, hashed_slot, value }, out)
+        }
+    }
+}
@@ -16,6 +16,7 @@ use std::{
     backtrace::Backtrace,
     collections::HashMap,
     marker::PhantomData,
+    path::Path,
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc,
@@ -351,6 +352,12 @@ impl<K: TransactionKind> DbTx for Tx<K> {
 
         self.inner.disable_timeout();
     }
+
+    fn copy_to_path(&self, dest: &Path) -> Result<(), DatabaseError> {
+        self.inner.copy_to_path(dest, false).map_err(|e| {
+            DatabaseError::Other(format!("failed to copy database to {}: {e}", dest.display()))
+        })
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -4,7 +4,11 @@ use crate::{
     HeaderTerminalDifficulties,
 };
 use alloy_primitives::{Address, BlockHash};
-use hanzo_evm_db_api::{models::StorageBeforeTx, table::Table, AccountChangeSets};
+use hanzo_evm_db_api::{
+    models::{StateSnapshotEntry, StorageBeforeTx},
+    table::Table,
+    AccountChangeSets,
+};
 
 // HEADER MASKS
 add_static_file_mask! {
@@ -60,3 +64,9 @@ add_static_file_mask! {
     #[doc = "Mask for selecting a single changeset from `StorageChangesets` static file segment"]
     StorageChangesetMask, StorageBeforeTx, 0b1
 }
+
+// STATE SNAPSHOT MASKS
+add_static_file_mask! {
+    #[doc = "Mask for selecting a single entry from `StateSnapshots` static file segment"]
+    StateSnapshotMask, StateSnapshotEntry, 0b1
+}
@@ -1,6 +1,6 @@
 //! evm's static file database table import and access
 
-use hanzo_evm_nippy_jar::{NippyJar, NippyJarError};
+use hanzo_evm_nippy_jar::{NippyJar, NippyJarError, CONFIG_FILE_EXTENSION};
 use hanzo_evm_static_file_types::{
     SegmentHeader, SegmentRangeInclusive, StaticFileMap, StaticFileSegment,
 };
@@ -31,13 +31,20 @@ pub fn iter_static_files(path: &Path) -> Result<SortedStaticFiles, NippyJarError
         .map_err(|err| NippyJarError::Custom(err.to_string()))?
         .filter_map(Result::ok);
     for entry in entries {
-        if entry.metadata().is_ok_and(|metadata| metadata.is_file()) &&
-            let Some((segment, _)) =
-                StaticFileSegment::parse_filename(&entry.file_name().to_string_lossy())
+        // Static files are found by their configuration, since the name of the data file of a
+        // recompressed static file is versioned
+        let path = entry.path();
+        if path.extension().is_some_and(|extension| extension == CONFIG_FILE_EXTENSION) &&
+            let Some((segment, _)) = path
+                .file_stem()
+                .and_then(|name| StaticFileSegment::parse_filename(&name.to_string_lossy()))
         {
-            let jar = NippyJar::<SegmentHeader>::load(&entry.path())?;
+            let jar = NippyJar::<SegmentHeader>::load(&path.with_extension(""))?;
 
-            if let Some(block_range) = jar.user_header().block_range() {
+            // Static files moved to a storage tier are symlinks, so follow them
+            if let Some(block_range) = jar.user_header().block_range() &&
+                std::fs::metadata(jar.data_path()).is_ok_and(|metadata| metadata.is_file())
+            {
                 static_files
                     .entry(segment)
                     .and_modify(|headers| headers.push((block_range, jar.user_header().clone())))
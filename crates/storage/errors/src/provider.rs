@@ -187,6 +187,16 @@ pub enum ProviderError {
         /// The block number to which the database must be unwound.
         unwind_to: BlockNumber,
     },
+    /// The `RocksDB` state and the database transaction of a provider are at different state
+    /// commits, because the provider was opened while a state commit was being applied. Opening
+    /// the provider again succeeds once the commit is applied.
+    #[error("RocksDB state is at commit {snapshot} but the database is at state commit {database}, retry once the commit is applied or restart the node if it persists")]
+    StateCommitInProgress {
+        /// The state commit of the `RocksDB` state snapshot.
+        snapshot: u64,
+        /// The state commit recorded in the database transaction.
+        database: u64,
+    },
     /// Any other error type wrapped into a cloneable [`AnyError`].
     #[error(transparent)]
     Other(#[from] AnyError),
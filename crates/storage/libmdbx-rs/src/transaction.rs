@@ -9,9 +9,10 @@ use crate::{
 use ffi::{MDBX_txn_flags_t, MDBX_TXN_RDONLY, MDBX_TXN_READWRITE};
 use parking_lot::{Mutex, MutexGuard};
 use std::{
-    ffi::{c_uint, c_void},
+    ffi::{c_uint, c_void, CString},
     fmt::{self, Debug},
     mem::size_of,
+    path::Path,
     ptr, slice,
     sync::{atomic::AtomicBool, mpsc::sync_channel, Arc},
     time::Duration,
@@ -269,6 +270,21 @@ where
             self.env().txn_manager().remove_active_read_transaction(self.inner.txn.txn);
         }
     }
+
+    /// Copies the environment as seen by this transaction to a new file at `dest`.
+    ///
+    /// This allows backing up a database while writers keep committing. Only read transactions
+    /// can be copied from. If `compact` is set, free pages are skipped and the remaining ones
+    /// renumbered.
+    pub fn copy_to_path(&self, dest: &Path, compact: bool) -> Result<()> {
+        let dest = dest.to_str().and_then(|dest| CString::new(dest).ok()).ok_or(Error::Invalid)?;
+        let flags = if compact { ffi::MDBX_CP_COMPACT } else { ffi::MDBX_CP_DEFAULTS };
+        mdbx_result(self.txn_execute(|txn| unsafe {
+            ffi::mdbx_txn_copy2pathname(txn, dest.as_ptr(), flags)
+        })?)?;
+
+        Ok(())
+    }
 }
 
 impl<K> Clone for Transaction<K>
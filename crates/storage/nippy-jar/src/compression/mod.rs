@@ -2,7 +2,8 @@ use crate::NippyJarError;
 use serde::{Deserialize, Serialize};
 
 mod zstd;
-pub use self::zstd::{DecoderDictionary, Decompressor, Zstd, ZstdState};
+pub(crate) use self::zstd::ZstdV1;
+pub use self::zstd::{train_dictionary, DecoderDictionary, Decompressor, Zstd, ZstdState};
 mod lz4;
 pub use self::lz4::Lz4;
 
@@ -50,6 +51,33 @@ pub enum Compressors {
     Lz4(Lz4),
 }
 
+/// [`Compressors`] as serialized by version `1` of the `NippyJar` format.
+#[derive(Serialize, Deserialize)]
+pub(crate) enum CompressorsV1 {
+    Zstd(ZstdV1),
+    Lz4(Lz4),
+}
+
+impl Compressors {
+    /// Returns the compressor in the layout of version `1` of the `NippyJar` format, unless it
+    /// uses a feature of a later version.
+    pub(crate) fn to_v1(&self) -> Option<CompressorsV1> {
+        match self {
+            Self::Zstd(zstd) => zstd.to_v1().map(CompressorsV1::Zstd),
+            Self::Lz4(_) => Some(CompressorsV1::Lz4(Lz4::default())),
+        }
+    }
+}
+
+impl From<CompressorsV1> for Compressors {
+    fn from(compressor: CompressorsV1) -> Self {
+        match compressor {
+            CompressorsV1::Zstd(zstd) => Self::Zstd(zstd.into()),
+            CompressorsV1::Lz4(lz4) => Self::Lz4(lz4),
+        }
+    }
+}
+
 impl Compression for Compressors {
     fn decompress_to(&self, value: &[u8], dest: &mut Vec<u8>) -> Result<(), NippyJarError> {
         match self {
@@ -13,7 +13,7 @@ pub use zstd::{bulk::Decompressor, dict::DecoderDictionary};
 type RawDictionary = Vec<u8>;
 
 /// Represents the state of a Zstandard compression operation.
-#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ZstdState {
     /// The compressor is pending a dictionary.
     #[default]
@@ -39,9 +39,47 @@ pub struct Zstd {
     pub(crate) dictionaries: Option<Arc<ZstdDictionaries<'static>>>,
     /// Number of columns to compress.
     columns: usize,
+    /// Version of the trained dictionaries, if they were set with [`Zstd::with_dictionaries`].
+    pub(crate) dictionary_id: Option<u32>,
+}
+
+/// [`Zstd`] as serialized by version `1` of the `NippyJar` format, which had no dictionary id.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct ZstdV1 {
+    pub(crate) state: ZstdState,
+    pub(crate) level: i32,
+    pub(crate) use_dict: bool,
+    pub(crate) max_dict_size: usize,
+    #[serde(with = "dictionaries_serde")]
+    pub(crate) dictionaries: Option<Arc<ZstdDictionaries<'static>>>,
+    pub(crate) columns: usize,
+}
+
+impl From<ZstdV1> for Zstd {
+    fn from(zstd: ZstdV1) -> Self {
+        let ZstdV1 { state, level, use_dict, max_dict_size, dictionaries, columns } = zstd;
+        Self { state, level, use_dict, max_dict_size, dictionaries, columns, dictionary_id: None }
+    }
 }
 
 impl Zstd {
+    /// Returns the configuration in the layout of version `1` of the `NippyJar` format, unless it
+    /// has a dictionary id.
+    pub(crate) fn to_v1(&self) -> Option<ZstdV1> {
+        if self.dictionary_id.is_some() {
+            return None
+        }
+
+        Some(ZstdV1 {
+            state: self.state,
+            level: self.level,
+            use_dict: self.use_dict,
+            max_dict_size: self.max_dict_size,
+            dictionaries: self.dictionaries.clone(),
+            columns: self.columns,
+        })
+    }
+
     /// Creates new [`Zstd`].
     pub const fn new(use_dict: bool, max_dict_size: usize, columns: usize) -> Self {
         Self {
@@ -51,9 +89,31 @@ impl Zstd {
             max_dict_size,
             dictionaries: None,
             columns,
+            dictionary_id: None,
         }
     }
 
+    /// Creates new [`Zstd`] using the given trained dictionaries, one per column.
+    ///
+    /// `dictionary_id` identifies the version of the dictionaries, so jars compressed with an
+    /// outdated version can be found and recompressed.
+    pub fn with_dictionaries(dictionary_id: u32, dictionaries: Vec<Vec<u8>>) -> Self {
+        Self {
+            state: ZstdState::Ready,
+            level: 0,
+            use_dict: true,
+            max_dict_size: dictionaries.iter().map(Vec::len).max().unwrap_or_default(),
+            columns: dictionaries.len(),
+            dictionaries: Some(Arc::new(ZstdDictionaries::new(dictionaries))),
+            dictionary_id: Some(dictionary_id),
+        }
+    }
+
+    /// Returns the version of the trained dictionaries, if any.
+    pub const fn dictionary_id(&self) -> Option<u32> {
+        self.dictionary_id
+    }
+
     /// Sets the compression level for the Zstd compression instance.
     pub const fn with_level(mut self, level: i32) -> Self {
         self.level = level;
@@ -71,7 +131,7 @@ impl Zstd {
     }
 
     /// If using dictionaries, creates a list of [`Compressor`].
-    pub fn compressors(&self) -> Result<Option<Vec<Compressor<'_>>>, NippyJarError> {
+    pub fn compressors(&self) -> Result<Option<Vec<Compressor<'static>>>, NippyJarError> {
         match self.state {
             ZstdState::PendingDictionary => Err(NippyJarError::CompressorNotReady),
             ZstdState::Ready => {
@@ -81,7 +141,7 @@ impl Zstd {
 
                 if let Some(dictionaries) = &self.dictionaries {
                     debug!(target: "nippy-jar", count=?dictionaries.len(), "Generating ZSTD compressor dictionaries.");
-                    return Ok(Some(dictionaries.compressors()?))
+                    return Ok(Some(dictionaries.compressors(self.level)?))
                 }
                 Ok(None)
             }
@@ -215,18 +275,7 @@ impl Compression for Zstd {
 
         let mut dictionaries = Vec::with_capacity(columns.len());
         for column in columns {
-            // ZSTD requires all training data to be continuous in memory, alongside the size of
-            // each entry
-            let mut sizes = vec![];
-            let data: Vec<_> = column
-                .into_iter()
-                .flat_map(|data| {
-                    sizes.push(data.len());
-                    data
-                })
-                .collect();
-
-            dictionaries.push(zstd::dict::from_continuous(&data, &sizes, self.max_dict_size)?);
+            dictionaries.push(train_dictionary(column, self.max_dict_size)?);
         }
 
         debug_assert_eq!(dictionaries.len(), self.columns);
@@ -238,6 +287,28 @@ impl Compression for Zstd {
     }
 }
 
+/// Trains a zstd dictionary of at most `max_dict_size` bytes from the given samples.
+///
+/// There's a 2GB hard limit on the total size of the samples, see
+/// <https://github.com/facebook/zstd/blob/dev/programs/zstd.1.md#dictionary-builder>.
+pub fn train_dictionary(
+    samples: impl IntoIterator<Item = Vec<u8>>,
+    max_dict_size: usize,
+) -> Result<Vec<u8>, NippyJarError> {
+    // ZSTD requires all training data to be continuous in memory, alongside the size of each
+    // entry
+    let mut sizes = vec![];
+    let data: Vec<_> = samples
+        .into_iter()
+        .flat_map(|data| {
+            sizes.push(data.len());
+            data
+        })
+        .collect();
+
+    Ok(zstd::dict::from_continuous(&data, &sizes, max_dict_size)?)
+}
+
 mod dictionaries_serde {
     use super::*;
 
@@ -277,7 +348,6 @@ impl std::fmt::Debug for ZstdDictionaries<'_> {
 }
 
 impl ZstdDictionaries<'_> {
-    #[cfg(test)]
     /// Creates [`ZstdDictionaries`].
     pub(crate) fn new(raw: Vec<RawDictionary>) -> Self {
         Self(raw.into_iter().map(ZstdDictionary::Raw).collect())
@@ -285,11 +355,7 @@ impl ZstdDictionaries<'_> {
 
     /// Loads a list [`RawDictionary`] into a list of [`ZstdDictionary::Loaded`].
     pub(crate) fn load(raw: Vec<RawDictionary>) -> Self {
-        Self(
-            raw.into_iter()
-                .map(|dict| ZstdDictionary::Loaded(DecoderDictionary::copy(&dict)))
-                .collect(),
-        )
+        Self(raw.into_iter().map(ZstdDictionary::load).collect())
     }
 
     /// Creates a list of decompressors from a list of [`ZstdDictionary::Loaded`].
@@ -304,33 +370,38 @@ impl ZstdDictionaries<'_> {
             .collect::<Result<Vec<_>, _>>()?)
     }
 
-    /// Creates a list of compressors from a list of [`ZstdDictionary::Raw`].
-    pub(crate) fn compressors(&self) -> Result<Vec<Compressor<'_>>, NippyJarError> {
+    /// Creates a list of compressors from the raw dictionaries.
+    pub(crate) fn compressors(
+        &self,
+        level: i32,
+    ) -> Result<Vec<Compressor<'static>>, NippyJarError> {
         Ok(self
             .iter()
-            .flat_map(|dict| {
-                dict.raw()
-                    .ok_or(NippyJarError::CompressorNotAllowed)
-                    .map(|dict| Compressor::with_dictionary(0, dict))
-            })
+            .map(|dict| Compressor::with_dictionary(level, dict.raw()))
             .collect::<Result<Vec<_>, _>>()?)
     }
 }
 
 /// A Zstd dictionary. It's created and serialized with [`ZstdDictionary::Raw`], and deserialized as
 /// [`ZstdDictionary::Loaded`].
+///
+/// The loaded variant keeps the raw dictionary, so rows can still be appended to a jar after it
+/// was reopened.
 pub(crate) enum ZstdDictionary<'a> {
-    #[cfg_attr(not(test), expect(dead_code))]
     Raw(RawDictionary),
-    Loaded(DecoderDictionary<'a>),
+    Loaded(DecoderDictionary<'a>, RawDictionary),
 }
 
 impl ZstdDictionary<'_> {
-    /// Returns a reference to the expected `RawDictionary`
-    pub(crate) const fn raw(&self) -> Option<&RawDictionary> {
+    /// Loads a [`RawDictionary`] into a [`ZstdDictionary::Loaded`].
+    fn load(raw: RawDictionary) -> Self {
+        Self::Loaded(DecoderDictionary::copy(&raw), raw)
+    }
+
+    /// Returns a reference to the `RawDictionary`
+    pub(crate) const fn raw(&self) -> &RawDictionary {
         match self {
-            ZstdDictionary::Raw(dict) => Some(dict),
-            ZstdDictionary::Loaded(_) => None,
+            ZstdDictionary::Raw(dict) | ZstdDictionary::Loaded(_, dict) => dict,
         }
     }
 
@@ -338,7 +409,7 @@ impl ZstdDictionary<'_> {
     pub(crate) const fn loaded(&self) -> Option<&DecoderDictionary<'_>> {
         match self {
             ZstdDictionary::Raw(_) => None,
-            ZstdDictionary::Loaded(dict) => Some(dict),
+            ZstdDictionary::Loaded(dict, _) => Some(dict),
         }
     }
 }
@@ -349,7 +420,7 @@ impl<'de> Deserialize<'de> for ZstdDictionary<'_> {
         D: Deserializer<'de>,
     {
         let dict = RawDictionary::deserialize(deserializer)?;
-        Ok(Self::Loaded(DecoderDictionary::copy(&dict)))
+        Ok(Self::load(dict))
     }
 }
 
@@ -358,21 +429,13 @@ impl Serialize for ZstdDictionary<'_> {
     where
         S: Serializer,
     {
-        match self {
-            ZstdDictionary::Raw(r) => r.serialize(serializer),
-            ZstdDictionary::Loaded(_) => unreachable!(),
-        }
+        self.raw().serialize(serializer)
     }
 }
 
 #[cfg(test)]
 impl PartialEq for ZstdDictionary<'_> {
     fn eq(&self, other: &Self) -> bool {
-        if let (Self::Raw(a), Self::Raw(b)) = (self, &other) {
-            return a == b
-        }
-        unimplemented!(
-            "`DecoderDictionary` can't be compared. So comparison should be done after decompressing a value."
-        );
+        self.raw() == other.raw()
     }
 }
@@ -160,7 +160,7 @@ impl<H: NippyJarHeader> NippyJarChecker<H> {
                 .ok_or_else(|| NippyJarError::MissingFile(path.to_path_buf()))?;
             Ok(BufWriter::new(OpenOptions::new().read(true).write(mode.should_heal()).open(path)?))
         };
-        self.data_file = Some(load_file(self.jar.data_path())?);
+        self.data_file = Some(load_file(&self.jar.data_path())?);
         self.offsets_file = Some(load_file(&self.jar.offsets_path())?);
         Ok(())
     }
@@ -15,6 +15,7 @@
 use memmap2::Mmap;
 use serde::{Deserialize, Serialize};
 use std::{
+    borrow::Cow,
     error::Error as StdError,
     fs::File,
     io::{self, Read, Write},
@@ -23,11 +24,16 @@ use std::{
 };
 use tracing::*;
 
+#[cfg(unix)]
+use std::os::unix::fs::symlink as symlink_file;
+#[cfg(windows)]
+use std::os::windows::fs::symlink_file;
+
 /// Compression algorithms supported by `NippyJar`.
 pub mod compression;
 #[cfg(test)]
 use compression::Compression;
-use compression::Compressors;
+use compression::{Compressors, CompressorsV1};
 
 /// empty enum for backwards compatibility
 #[derive(Debug, Serialize, Deserialize)]
@@ -52,7 +58,11 @@ mod consistency;
 pub use consistency::NippyJarChecker;
 
 /// The version number of the Nippy Jar format.
-const NIPPY_JAR_VERSION: usize = 1;
+///
+/// Version `2` added [`compression::Zstd::dictionary_id`] and versioned data files, see
+/// [`NippyJar::recompress`]. Only jars that use either of them are written as version `2`, all
+/// others are still written as version `1`. Configurations of version `1` are upgraded on load.
+const NIPPY_JAR_VERSION: usize = 2;
 /// The file extension used for index files.
 const INDEX_FILE_EXTENSION: &str = "idx";
 /// The file extension used for offsets files.
@@ -61,6 +71,8 @@ const OFFSETS_FILE_EXTENSION: &str = "off";
 pub const CONFIG_FILE_EXTENSION: &str = "conf";
 /// The file extension used for changeset offset sidecar files.
 pub const CHANGESET_OFFSETS_FILE_EXTENSION: &str = "csoff";
+/// The directory next to a jar where it's rewritten by [`NippyJar::recompress`].
+const RECOMPRESS_DIR: &str = "recompress";
 
 /// A [`RefRow`] is a list of column value slices pointing to either an internal buffer or a
 /// memory-mapped file.
@@ -108,11 +120,43 @@ pub struct NippyJar<H = ()> {
     /// Maximum uncompressed row size of the set. This will enable decompression without any
     /// resizing of the output buffer.
     max_row_size: usize,
+    /// Version of the data and offsets files, incremented every time the jar is recompressed.
+    /// Version `0` uses the jar path itself as the data path.
+    data_version: u32,
     /// Data path for file. Supporting files will have a format `{path}.{extension}`.
     #[serde(skip)]
     path: PathBuf,
 }
 
+/// [`NippyJar`] as serialized by version `1` of the format.
+#[derive(Serialize, Deserialize)]
+struct NippyJarV1<H> {
+    version: usize,
+    user_header: H,
+    columns: usize,
+    rows: usize,
+    compressor: Option<CompressorsV1>,
+    max_row_size: usize,
+}
+
+impl<H> From<NippyJarV1<H>> for NippyJar<H> {
+    fn from(jar: NippyJarV1<H>) -> Self {
+        let NippyJarV1 { version: _, user_header, columns, rows, compressor, max_row_size } = jar;
+        Self {
+            version: NIPPY_JAR_VERSION,
+            user_header,
+            columns,
+            rows,
+            compressor: compressor.map(Into::into),
+            filter: None,
+            phf: None,
+            max_row_size,
+            data_version: 0,
+            path: PathBuf::new(),
+        }
+    }
+}
+
 impl<H: NippyJarHeader> std::fmt::Debug for NippyJar<H> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("NippyJar")
@@ -125,6 +169,7 @@ impl<H: NippyJarHeader> std::fmt::Debug for NippyJar<H> {
             .field("phf", &self.phf)
             .field("path", &self.path)
             .field("max_row_size", &self.max_row_size)
+            .field("data_version", &self.data_version)
             .finish_non_exhaustive()
     }
 }
@@ -153,6 +198,7 @@ impl<H: NippyJarHeader> NippyJar<H> {
             compressor: None,
             filter: None,
             phf: None,
+            data_version: 0,
             path: path.to_path_buf(),
         }
     }
@@ -185,6 +231,11 @@ impl<H: NippyJarHeader> NippyJar<H> {
         self.rows
     }
 
+    /// Gets the version of the data and offsets files, increased by every recompression.
+    pub const fn data_version(&self) -> u32 {
+        self.data_version
+    }
+
     /// Gets a reference to the compressor.
     pub const fn compressor(&self) -> Option<&Compressors> {
         self.compressor.as_ref()
@@ -213,18 +264,57 @@ impl<H: NippyJarHeader> NippyJar<H> {
     }
 
     /// Deserializes an instance of [`Self`] from a [`Read`] type.
-    pub fn load_from_reader<R: Read>(reader: R) -> Result<Self, NippyJarError> {
-        Ok(bincode::deserialize_from(reader)?)
+    ///
+    /// Configurations written by an older version of the format are upgraded to the current one.
+    pub fn load_from_reader<R: Read>(mut reader: R) -> Result<Self, NippyJarError> {
+        let mut config = Vec::new();
+        reader.read_to_end(&mut config)?;
+
+        // The version is always serialized first
+        match bincode::deserialize::<usize>(&config)? {
+            1 => Ok(bincode::deserialize::<NippyJarV1<H>>(&config)?.into()),
+            NIPPY_JAR_VERSION => Ok(bincode::deserialize(&config)?),
+            version => {
+                Err(NippyJarError::Custom(format!("unsupported nippy jar version: {version}")))
+            }
+        }
     }
 
     /// Serializes an instance of [`Self`] to a [`Write`] type.
+    ///
+    /// The configuration is written as version `1` of the format, unless the jar was recompressed.
     pub fn save_to_writer<W: Write>(&self, writer: W) -> Result<(), NippyJarError> {
-        Ok(bincode::serialize_into(writer, self)?)
+        match self.to_v1() {
+            Some(jar) => Ok(bincode::serialize_into(writer, &jar)?),
+            None => Ok(bincode::serialize_into(writer, self)?),
+        }
+    }
+
+    /// Returns the configuration in the layout of version `1` of the format, unless it uses a
+    /// feature of a later version.
+    fn to_v1(&self) -> Option<NippyJarV1<&H>> {
+        let compressor = match &self.compressor {
+            Some(compressor) => Some(compressor.to_v1()?),
+            None => None,
+        };
+        (self.data_version == 0).then_some(NippyJarV1 {
+            version: 1,
+            user_header: &self.user_header,
+            columns: self.columns,
+            rows: self.rows,
+            compressor,
+            max_row_size: self.max_row_size,
+        })
+    }
+
+    /// Returns the path of the jar, which the paths of all its files are derived from.
+    pub fn path(&self) -> &Path {
+        &self.path
     }
 
     /// Returns the path for the data file
-    pub fn data_path(&self) -> &Path {
-        self.path.as_ref()
+    pub fn data_path(&self) -> PathBuf {
+        self.versioned_data_path(self.data_version)
     }
 
     /// Returns the path for the index file
@@ -234,7 +324,23 @@ impl<H: NippyJarHeader> NippyJar<H> {
 
     /// Returns the path for the offsets file
     pub fn offsets_path(&self) -> PathBuf {
-        self.path.with_extension(OFFSETS_FILE_EXTENSION)
+        self.versioned_offsets_path(self.data_version)
+    }
+
+    /// Returns the path of the data file with the given data version.
+    fn versioned_data_path(&self, data_version: u32) -> PathBuf {
+        if data_version == 0 {
+            return self.path.clone()
+        }
+        self.path.with_extension(format!("v{data_version}"))
+    }
+
+    /// Returns the path of the offsets file with the given data version.
+    fn versioned_offsets_path(&self, data_version: u32) -> PathBuf {
+        if data_version == 0 {
+            return self.path.with_extension(OFFSETS_FILE_EXTENSION)
+        }
+        self.path.with_extension(format!("v{data_version}.{OFFSETS_FILE_EXTENSION}"))
     }
 
     /// Returns the path for the config file
@@ -251,33 +357,161 @@ impl<H: NippyJarHeader> NippyJar<H> {
     pub fn delete(self) -> Result<(), NippyJarError> {
         // TODO(joshie): ensure consistency on unexpected shutdown
 
+        self.remove_stale_files()?;
         for path in [
-            self.data_path().into(),
+            self.data_path(),
             self.index_path(),
             self.offsets_path(),
             self.config_path(),
             self.changeset_offsets_path(),
         ] {
+            remove_file_and_target(&path)?;
+        }
+
+        Ok(())
+    }
+
+    /// Rewrites all rows of this jar with the given compressor into the data and offsets files of
+    /// the next data version, and returns the recompressed jar.
+    ///
+    /// Every column value is passed through `rewrite` before being compressed. The files of this
+    /// jar are left untouched, and it only switches to the recompressed files once
+    /// [`NippyJar::commit_recompressed`] writes the new configuration, so an interrupted
+    /// recompression leaves the jar intact. The caller must make sure that the jar isn't written
+    /// meanwhile.
+    ///
+    /// If the data file is a symlink to another volume, the recompressed files are written next to
+    /// its target and symlinked next to this jar.
+    pub fn recompress(
+        &self,
+        compressor: Compressors,
+        mut rewrite: impl for<'a> FnMut(usize, &'a [u8]) -> ColumnResult<Cow<'a, [u8]>>,
+    ) -> Result<Self, NippyJarError> {
+        let file_name = self.path.file_name().ok_or_else(|| {
+            NippyJarError::Custom(format!("invalid jar path: {}", self.path.display()))
+        })?;
+        let data_dir =
+            resolve_symlink(&self.data_path())?.parent().map(Path::to_path_buf).unwrap_or_default();
+        let recompress_dir = data_dir.join(RECOMPRESS_DIR);
+        hanzo_evm_fs_util::create_dir_all(&recompress_dir)?;
+
+        // The writer commits the configuration of the jar it writes, so the rows are written to a
+        // copy of the jar in a sibling directory, and only its data and offsets files are moved
+        // next to this jar.
+        let mut jar = Self::load(&self.path)?;
+        jar.path = recompress_dir.join(file_name);
+        jar.rows = 0;
+        jar.max_row_size = 0;
+        jar.data_version = 0;
+        jar.compressor = Some(compressor);
+
+        // Leftovers of an interrupted recompression
+        for path in [jar.data_path(), jar.offsets_path(), jar.config_path()] {
             if path.exists() {
-                debug!(target: "nippy-jar", ?path, "Removing file.");
                 hanzo_evm_fs_util::remove_file(path)?;
             }
         }
 
+        debug!(target: "nippy-jar", path=?self.data_path(), "Recompressing jar.");
+
+        let mut writer = NippyJarWriter::new(jar)?;
+        let mut cursor = NippyJarCursor::new(self)?;
+        while let Some(row) = cursor.next_row()? {
+            for (column, value) in row.into_iter().enumerate() {
+                writer.append_column(Some(rewrite(column, value)))?;
+            }
+        }
+        writer.commit()?;
+
+        let mut jar = writer.into_jar();
+        let (data_path, offsets_path) = (jar.data_path(), jar.offsets_path());
+        hanzo_evm_fs_util::remove_file(jar.config_path())?;
+
+        jar.path = self.path.clone();
+        jar.data_version = self.data_version + 1;
+        for (from, to) in [(data_path, jar.data_path()), (offsets_path, jar.offsets_path())] {
+            let file_name = to.file_name().expect("jar path has a file name");
+            let target = data_dir.join(file_name);
+            hanzo_evm_fs_util::rename(from, &target)?;
+
+            if target != to {
+                let link = to.with_file_name(format!("{}.link", file_name.to_string_lossy()));
+                let _ = std::fs::remove_file(&link);
+                symlink_file(&target, &link)?;
+                hanzo_evm_fs_util::rename(&link, &to)?;
+            }
+        }
+
+        Ok(jar)
+    }
+
+    /// Writes the configuration of a jar returned by [`NippyJar::recompress`], which atomically
+    /// switches the jar over to the recompressed data and offsets files.
+    ///
+    /// The files of the previous data version stay on disk for readers that still have them
+    /// mapped, until they are removed with [`NippyJar::remove_stale_files`].
+    pub fn commit_recompressed(&self) -> Result<(), NippyJarError> {
+        self.freeze_config()
+    }
+
+    /// Removes the data and offsets files of a jar returned by [`NippyJar::recompress`] that won't
+    /// be committed, leaving the jar it was recompressed from intact.
+    pub fn discard_recompressed(self) -> Result<(), NippyJarError> {
+        remove_file_and_target(&self.data_path())?;
+        remove_file_and_target(&self.offsets_path())
+    }
+
+    /// Removes the data and offsets files of the previous data versions of this jar.
+    pub fn remove_stale_files(&self) -> Result<(), NippyJarError> {
+        for data_version in 0..self.data_version {
+            remove_file_and_target(&self.versioned_data_path(data_version))?;
+            remove_file_and_target(&self.versioned_offsets_path(data_version))?;
+        }
+
         Ok(())
     }
 
     /// Returns a [`DataReader`] of the data and offset file
     pub fn open_data_reader(&self) -> Result<DataReader, NippyJarError> {
-        DataReader::new(self.data_path())
+        DataReader::new(self.data_path(), self.offsets_path())
     }
 
     /// Writes all necessary configuration to file.
+    ///
+    /// A configuration file that is a symlink is written to its target, keeping the symlink.
     fn freeze_config(&self) -> Result<(), NippyJarError> {
-        Ok(hanzo_evm_fs_util::atomic_write_file(&self.config_path(), |file| self.save_to_writer(file))?)
+        let config_path = resolve_symlink(&self.config_path())?;
+        Ok(hanzo_evm_fs_util::atomic_write_file(&config_path, |file| self.save_to_writer(file))?)
+    }
+}
+
+/// Returns the target of `path` if it's a symlink, or `path` itself otherwise.
+fn resolve_symlink(path: &Path) -> Result<PathBuf, NippyJarError> {
+    if path.is_symlink() {
+        Ok(std::fs::canonicalize(path)?)
+    } else {
+        Ok(path.to_path_buf())
     }
 }
 
+/// Removes the file at `path`, if it exists.
+///
+/// Files moved to another volume are symlinked, so their target is removed as well.
+fn remove_file_and_target(path: &Path) -> Result<(), NippyJarError> {
+    if let Ok(target) = std::fs::read_link(path) &&
+        target.exists()
+    {
+        debug!(target: "nippy-jar", path = ?target, "Removing file.");
+        hanzo_evm_fs_util::remove_file(target)?;
+    }
+    if path.exists() || path.is_symlink() {
+        debug!(target: "nippy-jar", ?path, "Removing file.");
+        hanzo_evm_fs_util::remove_file(path)?;
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 impl<H: NippyJarHeader> NippyJar<H> {
     /// If required, prepares any compression algorithm to an early pass of the data.
@@ -356,12 +590,15 @@ pub struct DataReader {
 
 impl DataReader {
     /// Reads the respective data and offsets file and returns [`DataReader`].
-    pub fn new(path: impl AsRef<Path>) -> Result<Self, NippyJarError> {
-        let data_file = File::open(path.as_ref())?;
+    pub fn new(
+        data_path: impl AsRef<Path>,
+        offsets_path: impl AsRef<Path>,
+    ) -> Result<Self, NippyJarError> {
+        let data_file = File::open(data_path)?;
         // SAFETY: File is read-only and its descriptor is kept alive as long as the mmap handle.
         let data_mmap = unsafe { Mmap::map(&data_file)? };
 
-        let offset_file = File::open(path.as_ref().with_extension(OFFSETS_FILE_EXTENSION))?;
+        let offset_file = File::open(offsets_path)?;
         // SAFETY: File is read-only and its descriptor is kept alive as long as the mmap handle.
         let offset_mmap = unsafe { Mmap::map(&offset_file)? };
 
@@ -476,7 +713,7 @@ mod tests {
     #[test]
     fn test_config_serialization() {
         let file = tempfile::NamedTempFile::new().unwrap();
-        let jar = NippyJar::new_without_header(23, file.path()).with_lz4();
+        let mut jar = NippyJar::new_without_header(23, file.path()).with_lz4();
         jar.freeze_config().unwrap();
 
         let mut config_file = OpenOptions::new().read(true).open(jar.config_path()).unwrap();
@@ -486,6 +723,7 @@ mod tests {
         let mut buf = Vec::with_capacity(config_file_len as usize);
         config_file.read_to_end(&mut buf).unwrap();
 
+        // Jars that weren't recompressed are written as version 1, which has no data version
         assert_eq!(
             vec![
                 1, 0, 0, 0, 0, 0, 0, 0, 23, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 0,
@@ -494,10 +732,57 @@ mod tests {
             buf
         );
 
-        let mut read_jar = bincode::deserialize_from::<_, NippyJar>(&buf[..]).unwrap();
+        let mut read_jar = NippyJar::load_from_reader(&buf[..]).unwrap();
         // Path is not ser/de
         read_jar.path = file.path().to_path_buf();
         assert_eq!(jar, read_jar);
+
+        // Recompressed jars are written as version 2
+        jar.data_version = 1;
+        jar.freeze_config().unwrap();
+        let buf = std::fs::read(jar.config_path()).unwrap();
+        assert_eq!(
+            vec![
+                2, 0, 0, 0, 0, 0, 0, 0, 23, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 0,
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0
+            ],
+            buf
+        );
+
+        let mut read_jar = NippyJar::load_from_reader(&buf[..]).unwrap();
+        read_jar.path = file.path().to_path_buf();
+        assert_eq!(jar, read_jar);
+    }
+
+    #[test]
+    fn test_load_v1_zstd_config() {
+        let (col1, col2) = test_data(None);
+        let num_rows = col1.len() as u64;
+        let num_columns = 2;
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+
+        let mut nippy =
+            NippyJar::new_without_header(num_columns, file_path.path()).with_zstd(true, 5000);
+        nippy.prepare_compression(vec![col1.clone(), col2.clone()]).unwrap();
+        let nippy = nippy
+            .freeze(vec![clone_with_result(&col1), clone_with_result(&col2)], num_rows)
+            .unwrap();
+        // Jars compressed without a dictionary id are written in the layout of version 1
+        let config = std::fs::read(nippy.config_path()).unwrap();
+        assert_eq!(bincode::deserialize::<usize>(&config).unwrap(), 1);
+
+        let loaded_nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+        assert_eq!(loaded_nippy.version, NIPPY_JAR_VERSION);
+        assert_eq!(loaded_nippy.data_version, 0);
+        assert_eq!(loaded_nippy.compressor(), nippy.compressor());
+
+        let mut cursor = NippyJarCursor::new(&loaded_nippy).unwrap();
+        let mut row_index = 0usize;
+        while let Some(row) = cursor.next_row().unwrap() {
+            assert_eq!((row[0], row[1]), (col1[row_index].as_slice(), col2[row_index].as_slice()));
+            row_index += 1;
+        }
+        assert_eq!(row_index, col1.len());
     }
 
     #[test]
@@ -651,6 +936,134 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_recompress_with_dictionaries() {
+        let (col1, col2) = test_data(None);
+        let num_rows = col1.len() as u64;
+        let num_columns = 2;
+        let dir = tempfile::tempdir().unwrap();
+        let file_path = dir.path().join("jar");
+
+        let nippy = NippyJar::new_without_header(num_columns, &file_path)
+            .freeze(vec![clone_with_result(&col1), clone_with_result(&col2)], num_rows)
+            .unwrap();
+
+        let dictionaries = vec![
+            compression::train_dictionary(col1.clone(), 5000).unwrap(),
+            compression::train_dictionary(col2.clone(), 5000).unwrap(),
+        ];
+        let compressor =
+            Compressors::Zstd(compression::Zstd::with_dictionaries(7, dictionaries.clone()));
+        let recompressed =
+            nippy.recompress(compressor, |_, value| Ok(Cow::Borrowed(value))).unwrap();
+        assert!(!dir.path().join(RECOMPRESS_DIR).join("jar").exists());
+        assert_eq!(recompressed.data_path(), dir.path().join("jar.v1"));
+        assert_eq!(recompressed.offsets_path(), dir.path().join("jar.v1.off"));
+
+        // The jar is unchanged until the recompressed configuration is committed
+        let loaded_nippy = NippyJar::load_without_header(&file_path).unwrap();
+        assert_eq!(loaded_nippy.compressor(), None);
+        assert_eq!(loaded_nippy.data_path(), file_path);
+        let mut cursor = NippyJarCursor::new(&loaded_nippy).unwrap();
+        assert_eq!(
+            cursor.row_by_number(0).unwrap(),
+            Some(vec![col1[0].as_slice(), col2[0].as_slice()])
+        );
+
+        recompressed.commit_recompressed().unwrap();
+        recompressed.remove_stale_files().unwrap();
+        assert!(!file_path.exists());
+        let config = std::fs::read(recompressed.config_path()).unwrap();
+        assert_eq!(bincode::deserialize::<usize>(&config).unwrap(), NIPPY_JAR_VERSION);
+        assert!(!dir.path().join("jar.off").exists());
+
+        let loaded_nippy = NippyJar::load_without_header(&file_path).unwrap();
+        assert_eq!(loaded_nippy.data_path(), recompressed.data_path());
+        assert_eq!(loaded_nippy.rows, col1.len());
+        let Some(Compressors::Zstd(zstd)) = loaded_nippy.compressor() else {
+            panic!("Expected Zstd compressor")
+        };
+        assert_eq!(zstd.dictionary_id(), Some(7));
+
+        // A discarded recompression leaves the committed files in place
+        let compressor = Compressors::Zstd(compression::Zstd::with_dictionaries(8, dictionaries));
+        let discarded =
+            loaded_nippy.recompress(compressor, |_, value| Ok(Cow::Borrowed(value))).unwrap();
+        assert_eq!(discarded.data_path(), dir.path().join("jar.v2"));
+        discarded.discard_recompressed().unwrap();
+        assert!(!dir.path().join("jar.v2").exists());
+        assert!(!dir.path().join("jar.v2.off").exists());
+        assert!(recompressed.data_path().exists());
+        assert_eq!(NippyJar::load_without_header(&file_path).unwrap().data_version(), 1);
+
+        // Rows can still be appended after reopening the jar
+        let mut writer = NippyJarWriter::new(loaded_nippy).unwrap();
+        writer.append_column(Some(Ok(&col1[0]))).unwrap();
+        writer.append_column(Some(Ok(&col2[0]))).unwrap();
+        writer.commit().unwrap();
+
+        let loaded_nippy = NippyJar::load_without_header(&file_path).unwrap();
+        let mut cursor = NippyJarCursor::new(&loaded_nippy).unwrap();
+        let mut row_index = 0usize;
+        while let Some(row) = cursor.next_row().unwrap() {
+            let expected = row_index % col1.len();
+            assert_eq!((row[0], row[1]), (col1[expected].as_slice(), col2[expected].as_slice()));
+            row_index += 1;
+        }
+        assert_eq!(row_index, col1.len() + 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_recompress_symlinked_jar() {
+        let (col1, col2) = test_data(None);
+        let num_rows = col1.len() as u64;
+        let dir = tempfile::tempdir().unwrap();
+        let tier_dir = tempfile::tempdir().unwrap();
+        let tier_dir = std::fs::canonicalize(tier_dir.path()).unwrap();
+        let file_path = dir.path().join("jar");
+
+        let nippy = NippyJar::new_without_header(2, &file_path)
+            .freeze(vec![clone_with_result(&col1), clone_with_result(&col2)], num_rows)
+            .unwrap();
+
+        // Move all files to another volume, leaving symlinks behind
+        for path in [nippy.data_path(), nippy.offsets_path(), nippy.config_path()] {
+            let target = tier_dir.join(path.file_name().unwrap());
+            std::fs::rename(&path, &target).unwrap();
+            symlink_file(&target, &path).unwrap();
+        }
+
+        let dictionaries = vec![
+            compression::train_dictionary(col1.clone(), 5000).unwrap(),
+            compression::train_dictionary(col2.clone(), 5000).unwrap(),
+        ];
+        let compressor = Compressors::Zstd(compression::Zstd::with_dictionaries(1, dictionaries));
+        let recompressed =
+            nippy.recompress(compressor, |_, value| Ok(Cow::Borrowed(value))).unwrap();
+        recompressed.commit_recompressed().unwrap();
+        recompressed.remove_stale_files().unwrap();
+
+        // The recompressed files are written next to the files they replace
+        for name in ["jar.v1", "jar.v1.off", "jar.conf"] {
+            assert_eq!(std::fs::read_link(dir.path().join(name)).unwrap(), tier_dir.join(name));
+        }
+        for name in ["jar", "jar.off"] {
+            assert!(!dir.path().join(name).is_symlink());
+            assert!(!tier_dir.join(name).exists());
+        }
+
+        let loaded_nippy = NippyJar::load_without_header(&file_path).unwrap();
+        assert_eq!(loaded_nippy.data_version(), 1);
+        let mut cursor = NippyJarCursor::new(&loaded_nippy).unwrap();
+        let mut row_index = 0usize;
+        while let Some(row) = cursor.next_row().unwrap() {
+            assert_eq!((row[0], row[1]), (col1[row_index].as_slice(), col2[row_index].as_slice()));
+            row_index += 1;
+        }
+        assert_eq!(row_index, col1.len());
+    }
+
     /// Tests `NippyJar` with everything enabled.
     #[test]
     fn test_full_nippy_jar() {
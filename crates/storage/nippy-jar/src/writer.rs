@@ -1,12 +1,13 @@
 use crate::{
-    compression::Compression, ColumnResult, NippyJar, NippyJarChecker, NippyJarError,
-    NippyJarHeader,
+    compression::{Compression, Compressors},
+    ColumnResult, NippyJar, NippyJarChecker, NippyJarError, NippyJarHeader,
 };
 use std::{
     fs::{File, OpenOptions},
     io::{BufWriter, Read, Seek, SeekFrom, Write},
     path::Path,
 };
+use zstd::bulk::Compressor;
 
 /// Size of one offset in bytes.
 pub(crate) const OFFSET_SIZE_BYTES: u8 = 8;
@@ -35,6 +36,8 @@ pub struct NippyJarWriter<H: NippyJarHeader = ()> {
     offsets_file: BufWriter<File>,
     /// Temporary buffer to reuse when compressing data.
     tmp_buf: Vec<u8>,
+    /// Per column compressors, if the jar is compressed with zstd dictionaries.
+    dictionary_compressors: Option<Vec<Compressor<'static>>>,
     /// Used to find the maximum uncompressed size of a row in a jar.
     uncompressed_row_size: usize,
     /// Partial offset list which hasn't been flushed to disk.
@@ -51,7 +54,7 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
     /// It will **always** attempt to heal any inconsistent state when called.
     pub fn new(jar: NippyJar<H>) -> Result<Self, NippyJarError> {
         let (data_file, offsets_file, is_created) =
-            Self::create_or_open_files(jar.data_path(), &jar.offsets_path())?;
+            Self::create_or_open_files(&jar.data_path(), &jar.offsets_path())?;
 
         let (jar, data_file, offsets_file) = if is_created {
             // Makes sure we don't have dangling data and offset files when we just created the file
@@ -70,11 +73,17 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
             (jar, data_file.expect("qed"), offsets_file.expect("qed"))
         };
 
+        let dictionary_compressors = match jar.compressor() {
+            Some(Compressors::Zstd(zstd)) if zstd.use_dict => zstd.compressors()?,
+            _ => None,
+        };
+
         let mut writer = Self {
             jar,
             data_file,
             offsets_file,
             tmp_buf: Vec::with_capacity(1_000_000),
+            dictionary_compressors,
             uncompressed_row_size: 0,
             offsets: Vec::with_capacity(1_000_000),
             column: 0,
@@ -227,7 +236,23 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
     /// Writes column to data file. If it's the last column of the row, call `finalize_row()`
     fn write_column(&mut self, value: &[u8]) -> Result<usize, NippyJarError> {
         self.uncompressed_row_size += value.len();
-        let len = if let Some(compression) = &self.jar.compressor {
+        let len = if let Some(compressors) = &mut self.dictionary_compressors {
+            // The compressor writes from the start of the buffer and requires it to be big enough,
+            // so the capacity is increased until the compressed value fits.
+            self.tmp_buf.clear();
+            let mut multiplier = 1;
+            while let Err(err) =
+                compressors[self.column].compress_to_buffer(value, &mut self.tmp_buf)
+            {
+                self.tmp_buf.reserve(value.len() * multiplier);
+                multiplier += 1;
+                if multiplier == 5 {
+                    return Err(NippyJarError::Disconnect(err))
+                }
+            }
+            self.data_file.write_all(&self.tmp_buf)?;
+            self.tmp_buf.len()
+        } else if let Some(compression) = &self.jar.compressor {
             let before = self.tmp_buf.len();
             let len = compression.compress_to(value, &mut self.tmp_buf)?;
             self.data_file.write_all(&self.tmp_buf[before..before + len])?;
@@ -469,7 +494,7 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
 
     /// Returns the path to the data file for the associated [`NippyJar`].
     #[cfg(test)]
-    pub fn data_path(&self) -> &Path {
+    pub fn data_path(&self) -> std::path::PathBuf {
         self.jar.data_path()
     }
 
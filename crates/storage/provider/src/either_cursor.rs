@@ -0,0 +1,266 @@
+//! Cursor abstraction over state tables stored either in MDBX or in `RocksDB`.
+//!
+//! Plain state, hashed state and trie tables are stored in MDBX by default. When
+//! [`StorageSettings::state_in_rocksdb`](hanzo_evm_db_api::models::StorageSettings::state_in_rocksdb)
+//! is set, they are stored in `RocksDB` instead and accessed through [`EitherCursor`], which
+//! implements the regular database cursor traits for both backends.
+//!
+//! Storing state in `RocksDB` is meant for benchmarking an LSM-backed state store. State writes
+//! are journaled before the database transaction is committed and applied after it, so a commit
+//! interrupted by a crash is finished on startup. Providers read the `RocksDB` state through a
+//! snapshot matching their database transaction. Entry counts of the state tables (stage
+//! progress, `db stats`) are read from the database and do not reflect the entries stored in
+//! `RocksDB`.
+
+#[cfg(all(unix, feature = "rocksdb"))]
+use crate::providers::rocksdb::RocksDBStateCursor;
+use alloy_primitives::{Address, B256};
+use hanzo_evm_db_api::{
+    common::{PairResult, ValueOnlyResult},
+    cursor::{
+        DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW, DupWalker, RangeWalker,
+        ReverseWalker, Walker,
+    },
+    table::{DupSort, Encode, Table},
+    tables, DatabaseError,
+};
+use std::{
+    fmt,
+    marker::PhantomData,
+    ops::{Bound, RangeBounds},
+};
+
+/// A state table that can be stored in `RocksDB`.
+///
+/// `RocksDB` has no notion of duplicate keys, so entries of dup-sorted tables are stored under the
+/// encoded key followed by the encoded subkey of the value. The value itself is stored unchanged,
+/// so entries are ordered the same way as in MDBX.
+pub trait RocksDBStateTable: Table {
+    /// Length of the encoded [`Table::Key`] if the table is dup-sorted, `None` otherwise.
+    const DUP_KEY_LENGTH: Option<usize> = None;
+
+    /// Encodes the subkey of a value of a dup-sorted table. Empty for plain tables.
+    fn encode_subkey(_value: &Self::Value) -> Vec<u8> {
+        Vec::new()
+    }
+}
+
+impl RocksDBStateTable for tables::PlainAccountState {}
+
+impl RocksDBStateTable for tables::PlainStorageState {
+    const DUP_KEY_LENGTH: Option<usize> = Some(Address::len_bytes());
+
+    fn encode_subkey(value: &Self::Value) -> Vec<u8> {
+        value.key.encode().to_vec()
+    }
+}
+
+impl RocksDBStateTable for tables::HashedAccounts {}
+
+impl RocksDBStateTable for tables::HashedStorages {
+    const DUP_KEY_LENGTH: Option<usize> = Some(B256::len_bytes());
+
+    fn encode_subkey(value: &Self::Value) -> Vec<u8> {
+        value.key.encode().to_vec()
+    }
+}
+
+impl RocksDBStateTable for tables::AccountsTrie {}
+
+impl RocksDBStateTable for tables::StoragesTrie {
+    const DUP_KEY_LENGTH: Option<usize> = Some(B256::len_bytes());
+
+    fn encode_subkey(value: &Self::Value) -> Vec<u8> {
+        value.nibbles.clone().encode().to_vec()
+    }
+}
+
+/// A cursor over a state table, backed either by a database cursor or by `RocksDB`.
+pub enum EitherCursor<CURSOR, T: Table> {
+    /// Database table cursor.
+    Database(CURSOR, PhantomData<T>),
+    /// `RocksDB` cursor that sees the uncommitted state writes of the provider.
+    #[cfg(all(unix, feature = "rocksdb"))]
+    RocksDB(RocksDBStateCursor<T>),
+}
+
+impl<CURSOR: fmt::Debug, T: Table> fmt::Debug for EitherCursor<CURSOR, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Database(cursor, _) => f.debug_tuple("Database").field(cursor).finish(),
+            #[cfg(all(unix, feature = "rocksdb"))]
+            Self::RocksDB(cursor) => f.debug_tuple("RocksDB").field(cursor).finish(),
+        }
+    }
+}
+
+impl<CURSOR, T: Table> EitherCursor<CURSOR, T> {
+    /// Creates a new [`EitherCursor`] over a database cursor.
+    pub const fn database(cursor: CURSOR) -> Self {
+        Self::Database(cursor, PhantomData)
+    }
+}
+
+/// Dispatches a cursor method to the active backend.
+macro_rules! dispatch {
+    ($cursor:expr, $method:ident($($arg:expr),*)) => {
+        match $cursor {
+            Self::Database(cursor, _) => cursor.$method($($arg),*),
+            #[cfg(all(unix, feature = "rocksdb"))]
+            Self::RocksDB(cursor) => cursor.$method($($arg),*),
+        }
+    };
+}
+
+impl<CURSOR, T> DbCursorRO<T> for EitherCursor<CURSOR, T>
+where
+    CURSOR: DbCursorRO<T>,
+    T: RocksDBStateTable,
+{
+    fn first(&mut self) -> PairResult<T> {
+        dispatch!(self, first())
+    }
+
+    fn seek_exact(&mut self, key: T::Key) -> PairResult<T> {
+        dispatch!(self, seek_exact(key))
+    }
+
+    fn seek(&mut self, key: T::Key) -> PairResult<T> {
+        dispatch!(self, seek(key))
+    }
+
+    fn next(&mut self) -> PairResult<T> {
+        dispatch!(self, next())
+    }
+
+    fn prev(&mut self) -> PairResult<T> {
+        dispatch!(self, prev())
+    }
+
+    fn last(&mut self) -> PairResult<T> {
+        dispatch!(self, last())
+    }
+
+    fn current(&mut self) -> PairResult<T> {
+        dispatch!(self, current())
+    }
+
+    fn walk(&mut self, start_key: Option<T::Key>) -> Result<Walker<'_, T, Self>, DatabaseError> {
+        let start =
+            if let Some(start_key) = start_key { self.seek(start_key) } else { self.first() }
+                .transpose();
+        Ok(Walker::new(self, start))
+    }
+
+    fn walk_range(
+        &mut self,
+        range: impl RangeBounds<T::Key>,
+    ) -> Result<RangeWalker<'_, T, Self>, DatabaseError> {
+        let start = match range.start_bound().cloned() {
+            Bound::Included(key) => self.seek(key),
+            Bound::Excluded(_key) => {
+                unreachable!("Rust doesn't allow for Bound::Excluded in starting bounds");
+            }
+            Bound::Unbounded => self.first(),
+        }
+        .transpose();
+        Ok(RangeWalker::new(self, start, range.end_bound().cloned()))
+    }
+
+    fn walk_back(
+        &mut self,
+        start_key: Option<T::Key>,
+    ) -> Result<ReverseWalker<'_, T, Self>, DatabaseError> {
+        let start =
+            if let Some(start_key) = start_key { self.seek(start_key) } else { self.last() }
+                .transpose();
+        Ok(ReverseWalker::new(self, start))
+    }
+}
+
+impl<CURSOR, T> DbDupCursorRO<T> for EitherCursor<CURSOR, T>
+where
+    CURSOR: DbDupCursorRO<T>,
+    T: RocksDBStateTable + DupSort,
+{
+    fn prev_dup(&mut self) -> PairResult<T> {
+        dispatch!(self, prev_dup())
+    }
+
+    fn next_dup(&mut self) -> PairResult<T> {
+        dispatch!(self, next_dup())
+    }
+
+    fn last_dup(&mut self) -> ValueOnlyResult<T> {
+        dispatch!(self, last_dup())
+    }
+
+    fn next_no_dup(&mut self) -> PairResult<T> {
+        dispatch!(self, next_no_dup())
+    }
+
+    fn next_dup_val(&mut self) -> ValueOnlyResult<T> {
+        dispatch!(self, next_dup_val())
+    }
+
+    fn seek_by_key_subkey(&mut self, key: T::Key, subkey: T::SubKey) -> ValueOnlyResult<T> {
+        dispatch!(self, seek_by_key_subkey(key, subkey))
+    }
+
+    fn walk_dup(
+        &mut self,
+        key: Option<T::Key>,
+        subkey: Option<T::SubKey>,
+    ) -> Result<DupWalker<'_, T, Self>, DatabaseError> {
+        let start = match (key, subkey) {
+            (Some(key), Some(subkey)) => {
+                self.seek_by_key_subkey(key.clone(), subkey)?.map(|value| Ok((key, value)))
+            }
+            (Some(key), None) => self.seek_exact(key)?.map(Ok),
+            (None, Some(subkey)) => match self.first()? {
+                Some((key, _)) => {
+                    self.seek_by_key_subkey(key.clone(), subkey)?.map(|value| Ok((key, value)))
+                }
+                None => None,
+            },
+            (None, None) => self.first()?.map(Ok),
+        };
+        Ok(DupWalker { cursor: self, start })
+    }
+}
+
+impl<CURSOR, T> DbCursorRW<T> for EitherCursor<CURSOR, T>
+where
+    CURSOR: DbCursorRW<T>,
+    T: RocksDBStateTable,
+{
+    fn upsert(&mut self, key: T::Key, value: &T::Value) -> Result<(), DatabaseError> {
+        dispatch!(self, upsert(key, value))
+    }
+
+    fn insert(&mut self, key: T::Key, value: &T::Value) -> Result<(), DatabaseError> {
+        dispatch!(self, insert(key, value))
+    }
+
+    fn append(&mut self, key: T::Key, value: &T::Value) -> Result<(), DatabaseError> {
+        dispatch!(self, append(key, value))
+    }
+
+    fn delete_current(&mut self) -> Result<(), DatabaseError> {
+        dispatch!(self, delete_current())
+    }
+}
+
+impl<CURSOR, T> DbDupCursorRW<T> for EitherCursor<CURSOR, T>
+where
+    CURSOR: DbDupCursorRW<T>,
+    T: RocksDBStateTable + DupSort,
+{
+    fn delete_current_duplicates(&mut self) -> Result<(), DatabaseError> {
+        dispatch!(self, delete_current_duplicates())
+    }
+
+    fn append_dup(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        dispatch!(self, append_dup(key, value))
+    }
+}
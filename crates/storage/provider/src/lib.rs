@@ -21,8 +21,8 @@ pub mod providers;
 pub use providers::{
     DatabaseProvider, DatabaseProviderRO, DatabaseProviderRW, HistoricalStateProvider,
     HistoricalStateProviderRef, LatestStateProvider, LatestStateProviderRef, ProviderFactory,
-    PruneShardOutcome, PrunedIndices, SaveBlocksMode, StaticFileAccess, StaticFileProviderBuilder,
-    StaticFileWriteCtx, StaticFileWriter,
+    PruneShardOutcome, PrunedIndices, SaveBlocksMode, StateCursorFactory, StaticFileAccess,
+    StaticFileProviderBuilder, StaticFileWriteCtx, StaticFileWriter,
 };
 
 pub mod changeset_walker;
@@ -35,6 +35,9 @@ pub mod test_utils;
 pub mod either_writer;
 pub use either_writer::*;
 
+pub mod either_cursor;
+pub use either_cursor::*;
+
 pub use hanzo_evm_chain_state::{
     CanonStateNotification, CanonStateNotificationSender, CanonStateNotificationStream,
     CanonStateNotifications, CanonStateSubscriptions,
@@ -20,7 +20,9 @@ use hanzo_evm_chain_state::{
     MemoryOverlayStateProvider, PersistedBlockNotifications, PersistedBlockSubscriptions,
 };
 use reth_chainspec::ChainInfo;
-use reth_db_api::models::{AccountBeforeTx, BlockNumberAddress, StoredBlockBodyIndices};
+use reth_db_api::models::{
+    AccountBeforeTx, BlockNumberAddress, StorageSnapshotManifest, StoredBlockBodyIndices,
+};
 use reth_execution_types::ExecutionOutcome;
 use reth_node_types::{BlockTy, HeaderTy, NodeTypesWithDB, ReceiptTy, TxTy};
 use reth_primitives_traits::{Account, RecoveredBlock, SealedHeader};
@@ -29,12 +31,14 @@ use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
 use reth_storage_api::{
     BlockBodyIndicesProvider, ChangesetEntry, NodePrimitivesProvider, StorageChangeSetReader,
+    StorageSnapshotProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{HashedPostState, KeccakKeyHasher};
 use revm_database::BundleState;
 use std::{
     ops::{RangeBounds, RangeInclusive},
+    path::Path,
     sync::Arc,
     time::Instant,
 };
@@ -795,6 +799,12 @@ impl<N: ProviderNodeTypes> StateReader for BlockchainProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> StorageSnapshotProvider for BlockchainProvider<N> {
+    fn create_storage_snapshot(&self, dest: &Path) -> ProviderResult<StorageSnapshotManifest> {
+        self.database.create_storage_snapshot(dest)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
@@ -18,7 +18,7 @@ use core::fmt;
 use parking_lot::RwLock;
 use hanzo_evm_chainspec::ChainInfo;
 use hanzo_evm_db::{init_db, mdbx::DatabaseArguments, DatabaseEnv};
-use hanzo_evm_db_api::{database::Database, models::StoredBlockBodyIndices};
+use hanzo_evm_db_api::{database::Database, models::StoredBlockBodyIndices, transaction::DbTx};
 use hanzo_evm_errors::{EvmError, EvmResult};
 use hanzo_evm_node_types::{
     BlockTy, HeaderTy, NodeTypesWithDB, NodeTypesWithDBAdapter, ReceiptTy, TxTy,
@@ -40,7 +40,7 @@ use std::{
     path::Path,
     sync::Arc,
 };
-use tracing::{instrument, trace};
+use tracing::{instrument, trace, warn};
 
 mod provider;
 pub use provider::{
@@ -50,6 +50,11 @@ pub use provider::{
 use super::ProviderNodeTypes;
 use hanzo_evm_trie::KeccakKeyHasher;
 
+/// Number of attempts to open a provider whose `RocksDB` state snapshot matches its database
+/// transaction.
+#[cfg(all(unix, feature = "rocksdb"))]
+const ROCKSDB_STATE_SNAPSHOT_ATTEMPTS: usize = 3;
+
 mod builder;
 pub use builder::{ProviderFactoryBuilder, ReadOnlyConfig};
 
@@ -58,6 +63,8 @@ mod metrics;
 mod chain;
 pub use chain::*;
 
+mod snapshot;
+
 /// A common provider that fetches data from a database or static file.
 ///
 /// This provider implements most provider or provider factory traits.
@@ -236,6 +243,19 @@ impl<N: ProviderNodeTypes> ProviderFactory<N> {
     /// data.
     #[track_caller]
     pub fn provider(&self) -> ProviderResult<DatabaseProviderRO<N::DB, N>> {
+        self.open_provider(|| {
+            #[cfg_attr(not(all(unix, feature = "rocksdb")), allow(unused_mut))]
+            let mut provider = self.provider_unchecked()?;
+            // Providers that don't write can read the state commit being applied.
+            #[cfg(all(unix, feature = "rocksdb"))]
+            provider.read_applying_rocksdb_state()?;
+            Ok(provider)
+        })
+    }
+
+    /// Returns a provider with a created `DbTx` inside, without checking that its `RocksDB` state
+    /// snapshot matches the database transaction.
+    fn provider_unchecked(&self) -> ProviderResult<DatabaseProviderRO<N::DB, N>> {
         Ok(DatabaseProvider::new(
             self.db.tx()?,
             self.chain_spec.clone(),
@@ -255,17 +275,20 @@ impl<N: ProviderNodeTypes> ProviderFactory<N> {
     /// open.
     #[track_caller]
     pub fn provider_rw(&self) -> ProviderResult<DatabaseProviderRW<N::DB, N>> {
-        Ok(DatabaseProviderRW(DatabaseProvider::new_rw(
-            self.db.tx_mut()?,
-            self.chain_spec.clone(),
-            self.static_file_provider.clone(),
-            self.prune_modes.clone(),
-            self.storage.clone(),
-            self.storage_settings.clone(),
-            self.rocksdb_provider.clone(),
-            self.changeset_cache.clone(),
-            self.runtime.clone(),
-        )))
+        self.open_provider(|| {
+            Ok(DatabaseProvider::new_rw(
+                self.db.tx_mut()?,
+                self.chain_spec.clone(),
+                self.static_file_provider.clone(),
+                self.prune_modes.clone(),
+                self.storage.clone(),
+                self.storage_settings.clone(),
+                self.rocksdb_provider.clone(),
+                self.changeset_cache.clone(),
+                self.runtime.clone(),
+            ))
+        })
+        .map(DatabaseProviderRW)
     }
 
     /// Returns a provider with a created `DbTxMut` inside, configured for unwind operations.
@@ -275,17 +298,68 @@ impl<N: ProviderNodeTypes> ProviderFactory<N> {
     pub fn unwind_provider_rw(
         &self,
     ) -> ProviderResult<DatabaseProvider<<N::DB as Database>::TXMut, N>> {
-        Ok(DatabaseProvider::new_unwind_rw(
-            self.db.tx_mut()?,
-            self.chain_spec.clone(),
-            self.static_file_provider.clone(),
-            self.prune_modes.clone(),
-            self.storage.clone(),
-            self.storage_settings.clone(),
-            self.rocksdb_provider.clone(),
-            self.changeset_cache.clone(),
-            self.runtime.clone(),
-        ))
+        self.open_provider(|| {
+            Ok(DatabaseProvider::new_unwind_rw(
+                self.db.tx_mut()?,
+                self.chain_spec.clone(),
+                self.static_file_provider.clone(),
+                self.prune_modes.clone(),
+                self.storage.clone(),
+                self.storage_settings.clone(),
+                self.rocksdb_provider.clone(),
+                self.changeset_cache.clone(),
+                self.runtime.clone(),
+            ))
+        })
+    }
+
+    /// Opens a provider with `open`, checking that its `RocksDB` state snapshot and its database
+    /// transaction are at the same state commit.
+    ///
+    /// A provider committing state writes to `RocksDB` commits its database transaction before
+    /// applying them, so a provider opened in between sees the new checkpoints with the old
+    /// state. Read-only providers read the state writes being applied instead, see
+    /// [`DatabaseProvider::read_applying_rocksdb_state`]. Other providers are opened again right
+    /// away, and [`ProviderError::StateCommitInProgress`] is returned if the state commits still
+    /// differ, so the caller can retry without blocking.
+    fn open_provider<TX: DbTx + 'static>(
+        &self,
+        open: impl Fn() -> ProviderResult<DatabaseProvider<TX, N>>,
+    ) -> ProviderResult<DatabaseProvider<TX, N>> {
+        #[cfg(all(unix, feature = "rocksdb"))]
+        {
+            let mut mismatch = None;
+            for _ in 0..ROCKSDB_STATE_SNAPSHOT_ATTEMPTS {
+                let provider = open()?;
+                mismatch = provider.rocksdb_state_commit_mismatch()?;
+                if mismatch.is_none() {
+                    return Ok(provider)
+                }
+                drop(provider);
+                // A secondary instance only sees the state commits of the primary after catching
+                // up.
+                self.rocksdb_provider.try_catch_up_with_primary()?;
+            }
+
+            let (snapshot, database) = mismatch.expect("at least one attempt");
+            Err(ProviderError::StateCommitInProgress { snapshot, database })
+        }
+
+        #[cfg(not(all(unix, feature = "rocksdb")))]
+        open()
+    }
+
+    /// Brings the `RocksDB` state to the state commit recorded in the database, replaying the
+    /// journal of a commit interrupted by a crash.
+    #[cfg(all(unix, feature = "rocksdb"))]
+    fn heal_rocksdb_state(&self) -> ProviderResult<()> {
+        if !self.cached_storage_settings().state_in_rocksdb || self.rocksdb_provider.is_read_only()
+        {
+            return Ok(())
+        }
+
+        let commit = self.provider_unchecked()?.rocksdb_state_database_commit()?;
+        self.rocksdb_provider.heal_state_commit(commit)
     }
 
     /// State provider for latest block
@@ -318,6 +392,37 @@ impl<N: ProviderNodeTypes> ProviderFactory<N> {
         Ok(state_provider)
     }
 
+    /// Appends the blocks up to the execution checkpoint to
+    /// [`StaticFileSegment::StateSnapshots`], writing a full snapshot of the state at the
+    /// checkpoint if the previous one is at least
+    /// [`StaticFileProvider::state_snapshot_interval`] blocks old.
+    ///
+    /// The snapshot is read from a read-only transaction opened after waiting for unwinds of
+    /// the segment to be committed, and is committed separately from other static files.
+    ///
+    /// Returns the block of the written snapshot, if any.
+    pub fn write_state_snapshots(&self) -> ProviderResult<Option<BlockNumber>> {
+        let _lock = self.static_file_provider.lock_state_snapshots();
+        self.provider()?.disable_long_read_transaction_safety().write_state_snapshots()
+    }
+
+    /// Runs [`Self::write_state_snapshots`] on a separate thread, unless state snapshots are
+    /// disabled or the segment is already being written or unwound.
+    pub fn spawn_state_snapshots(&self) {
+        if self.static_file_provider.state_snapshot_interval().is_none() ||
+            self.static_file_provider.is_state_snapshots_locked()
+        {
+            return
+        }
+
+        let factory = self.clone();
+        reth_tasks::spawn_os_thread("state-snapshots", move || {
+            if let Err(err) = factory.write_state_snapshots() {
+                warn!(target: "providers::db", %err, "Failed to write state snapshot")
+            }
+        });
+    }
+
     /// Asserts that the static files and database are consistent. If not,
     /// returns [`ProviderError::MustUnwind`] with the appropriate unwind
     /// target. May also return any [`ProviderError`] that
@@ -347,6 +452,10 @@ impl<N: ProviderNodeTypes> ProviderFactory<N> {
     /// consistency. I.e. this MAY result in writes to the static files.
     #[instrument(err, skip(self))]
     pub fn check_consistency(&self) -> ProviderResult<(Option<u64>, Option<u64>)> {
+        // Step 0: finish or discard an interrupted RocksDB state commit
+        #[cfg(all(unix, feature = "rocksdb"))]
+        self.heal_rocksdb_state()?;
+
         let provider_ro = self.database_provider_ro()?;
 
         // Step 1: heal file-level inconsistencies (no pruning)
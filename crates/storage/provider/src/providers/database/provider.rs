@@ -1,8 +1,12 @@
+#[cfg(all(unix, feature = "rocksdb"))]
+use crate::providers::rocksdb::{
+    RocksDBStateCursor, RocksDBStateSnapshot, STATE_COMMIT_METADATA_KEY,
+};
 use crate::{
     changesets_utils::StorageRevertsIter,
     providers::{
         database::{chain::ChainStorage, metrics},
-        rocksdb::{PendingRocksDBBatches, RocksDBProvider, RocksDBWriteCtx},
+        rocksdb::{PendingRocksDBBatches, PendingRocksDBState, RocksDBProvider, RocksDBWriteCtx},
         static_file::{StaticFileWriteCtx, StaticFileWriter},
         NodeTypesForProvider, StaticFileProvider,
     },
@@ -12,13 +16,15 @@ use crate::{
     },
     AccountReader, BlockBodyWriter, BlockExecutionWriter, BlockHashReader, BlockNumReader,
     BlockReader, BlockWriter, BundleStateInit, ChainStateBlockReader, ChainStateBlockWriter,
-    DBProvider, EitherReader, EitherWriter, EitherWriterDestination, HashingWriter, HeaderProvider,
-    HeaderSyncGapProvider, HistoricalStateProvider, HistoricalStateProviderRef, HistoryWriter,
-    LatestStateProvider, LatestStateProviderRef, OriginalValuesKnown, ProviderError,
-    PruneCheckpointReader, PruneCheckpointWriter, RawRocksDBBatch, RevertsInit, RocksBatchArg,
-    RocksDBProviderFactory, StageCheckpointReader, StateProviderBox, StateWriter,
-    StaticFileProviderFactory, StatsReader, StorageReader, StorageTrieWriter, TransactionVariant,
-    TransactionsProvider, TransactionsProviderExt, TrieWriter,
+    DBProvider, DupStateCursorMutTy, DupStateCursorTy, EitherCursor, EitherReader, EitherWriter,
+    EitherWriterDestination, HashingWriter, HeaderProvider, HeaderSyncGapProvider,
+    HistoricalStateProvider, HistoricalStateProviderRef, HistoryWriter, LatestStateProvider,
+    LatestStateProviderRef, OriginalValuesKnown, ProviderError, PruneCheckpointReader,
+    PruneCheckpointWriter, RawRocksDBBatch, RevertsInit, RocksBatchArg, RocksDBProviderFactory,
+    RocksDBStateTable, StageCheckpointReader, StateCursorMutTy, StateCursorProvider, StateCursorTy,
+    StateProviderBox, StateWriter, StaticFileProviderFactory, StatsReader, StorageReader,
+    StorageTrieWriter, TransactionVariant, TransactionsProvider, TransactionsProviderExt,
+    TrieWriter,
 };
 use alloy_consensus::{
     transaction::{SignerRecoverable, TransactionMeta, TxHashRef},
@@ -31,7 +37,7 @@ use alloy_primitives::{
     Address, BlockHash, BlockNumber, TxHash, TxNumber, B256,
 };
 use itertools::Itertools;
-use parking_lot::RwLock;
+use parking_lot::{ArcMutexGuard, RawMutex, RwLock};
 use rayon::slice::ParallelSliceMut;
 use hanzo_evm_chain_state::{ComputedTrieData, ExecutedBlock};
 use hanzo_evm_chainspec::{ChainInfo, ChainSpecProvider, EthChainSpec};
@@ -40,13 +46,13 @@ use hanzo_evm_db_api::{
     database::Database,
     models::{
         sharded_key, storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress,
-        BlockNumberAddressRange, ShardedKey, StorageBeforeTx, StorageSettings,
+        BlockNumberAddressRange, ShardedKey, StateSnapshotEntry, StorageBeforeTx, StorageSettings,
         StoredBlockBodyIndices,
     },
-    table::Table,
+    table::{DupSort, Table},
     tables,
     transaction::{DbTx, DbTxMut},
-    BlockNumberList, PlainAccountState, PlainStorageState,
+    BlockNumberList, DatabaseError, PlainAccountState, PlainStorageState,
 };
 use reth_execution_types::{BlockExecutionOutput, BlockExecutionResult, Chain, ExecutionOutcome};
 use reth_node_types::{BlockTy, BodyTy, HeaderTy, NodeTypes, ReceiptTy, TxTy};
@@ -78,7 +84,7 @@ use std::{
     collections::{BTreeMap, BTreeSet},
     fmt::Debug,
     ops::{Deref, DerefMut, Range, RangeBounds, RangeInclusive},
-    sync::Arc,
+    sync::{Arc, OnceLock},
 };
 use tracing::{debug, instrument, trace};
 
@@ -204,12 +210,25 @@ pub struct DatabaseProvider<TX, N: NodeTypes> {
     /// Pending `RocksDB` batches to be committed at provider commit time.
     #[cfg_attr(not(all(unix, feature = "rocksdb")), allow(dead_code))]
     pending_rocksdb_batches: PendingRocksDBBatches,
+    /// Pending `RocksDB` state writes to be committed at provider commit time.
+    #[cfg_attr(not(all(unix, feature = "rocksdb")), allow(dead_code))]
+    pending_rocksdb_state: PendingRocksDBState,
+    /// Snapshot of the `RocksDB` state, taken on creation if state is stored in `RocksDB`.
+    #[cfg(all(unix, feature = "rocksdb"))]
+    rocksdb_state_snapshot: OnceLock<Arc<RocksDBStateSnapshot>>,
+    /// State writes of the commit the database transaction is at, read instead of the pending
+    /// state if the snapshot was taken before they were applied.
+    #[cfg(all(unix, feature = "rocksdb"))]
+    applying_rocksdb_state: Option<PendingRocksDBState>,
     /// Commit order for database operations.
     commit_order: CommitOrder,
     /// Minimum distance from tip required for pruning
     minimum_pruning_distance: u64,
     /// Database provider metrics
     metrics: metrics::DatabaseProviderMetrics,
+    /// Held from the first unwind of state snapshots until this provider is dropped, so that no
+    /// state snapshot is written from the state being unwound.
+    state_snapshots_lock: OnceLock<ArcMutexGuard<RawMutex, ()>>,
 }
 
 impl<TX: Debug, N: NodeTypes> Debug for DatabaseProvider<TX, N> {
@@ -225,6 +244,7 @@ impl<TX: Debug, N: NodeTypes> Debug for DatabaseProvider<TX, N> {
             .field("changeset_cache", &self.changeset_cache)
             .field("runtime", &self.runtime)
             .field("pending_rocksdb_batches", &"<pending batches>")
+            .field("pending_rocksdb_state", &"<pending state>")
             .field("commit_order", &self.commit_order)
             .field("minimum_pruning_distance", &self.minimum_pruning_distance)
             .finish()
@@ -288,6 +308,99 @@ impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
         Ok(Box::new(state_provider))
     }
 
+    /// Appends the blocks up to the execution checkpoint of this transaction to
+    /// [`StaticFileSegment::StateSnapshots`] and commits them.
+    ///
+    /// A full snapshot of the hashed state is written at the checkpoint block if the previous
+    /// snapshot is at least [`StaticFileProvider::state_snapshot_interval`] blocks old, all
+    /// other blocks are appended without entries.
+    ///
+    /// The transaction must be opened while holding [`StaticFileProvider::lock_state_snapshots`],
+    /// see [`crate::ProviderFactory::write_state_snapshots`].
+    ///
+    /// Returns the block of the written snapshot. No-op if state snapshots are disabled or the
+    /// node doesn't use hashed state.
+    pub(crate) fn write_state_snapshots(&self) -> ProviderResult<Option<BlockNumber>> {
+        let Some(interval) = self.static_file_provider.state_snapshot_interval() else {
+            return Ok(None)
+        };
+        if !self.cached_storage_settings().use_hashed_state() {
+            return Ok(None)
+        }
+
+        let segment = StaticFileSegment::StateSnapshots;
+        let last = self.get_stage_checkpoint(StageId::Execution)?.unwrap_or_default().block_number;
+        let (first, mut writer) =
+            match self.static_file_provider.get_highest_static_file_block(segment) {
+                Some(highest_block) if highest_block >= last => return Ok(None),
+                Some(highest_block) => {
+                    (highest_block + 1, self.static_file_provider.latest_writer(segment)?)
+                }
+                None => {
+                    let mut writer = self.static_file_provider.get_writer(last, segment)?;
+                    writer.user_header_mut().set_expected_block_start(last);
+                    (last, writer)
+                }
+            };
+
+        let is_due = self
+            .static_file_provider
+            .state_snapshot_block((last + 1).saturating_sub(interval))?
+            .is_none();
+
+        for block in first..last {
+            writer.append_state_snapshot(std::iter::empty(), block)?;
+        }
+
+        if !is_due {
+            writer.append_state_snapshot(std::iter::empty(), last)?;
+            writer.commit()?;
+            return Ok(None)
+        }
+
+        let start = Instant::now();
+        let mut accounts_cursor = self.state_cursor_read::<tables::HashedAccounts>()?;
+        let mut storages_cursor = self.state_cursor_dup_read::<tables::HashedStorages>()?;
+        let mut accounts = accounts_cursor.walk(None)?.peekable();
+        let mut storages = storages_cursor.walk(None)?.peekable();
+
+        // Merge both tables by hashed address, every account goes before its storage.
+        let entries = std::iter::from_fn(|| {
+            let from_accounts = match (accounts.peek(), storages.peek()) {
+                (None, None) => return None,
+                (Some(Ok((account, _))), Some(Ok((storage, _)))) => account <= storage,
+                (Some(_), None) | (Some(Err(_)), _) => true,
+                (None, Some(_)) | (Some(Ok(_)), Some(Err(_))) => false,
+            };
+
+            let entry = if from_accounts {
+                accounts.next()?.map(|(hashed_address, account)| StateSnapshotEntry::Account {
+                    hashed_address,
+                    account,
+                })
+            } else {
+                storages.next()?.map(|(hashed_address, entry)| StateSnapshotEntry::Storage {
+                    hashed_address,
+                    hashed_slot: entry.key,
+                    value: entry.value,
+                })
+            };
+            Some(entry.map_err(Into::into))
+        });
+        let entries = writer.append_state_snapshot(entries, last)?;
+        writer.commit()?;
+
+        debug!(
+            target: "providers::db",
+            block = last,
+            entries,
+            elapsed = ?start.elapsed(),
+            "Wrote state snapshot"
+        );
+
+        Ok(Some(last))
+    }
+
     #[cfg(feature = "test-utils")]
     /// Sets the prune modes for provider.
     pub fn set_prune_modes(&mut self, prune_modes: PruneModes) {
@@ -314,6 +427,19 @@ impl<TX, N: NodeTypes> StaticFileProviderFactory for DatabaseProvider<TX, N> {
     }
 }
 
+/// Takes a snapshot of the `RocksDB` state for a new provider if state is stored in `RocksDB`.
+#[cfg(all(unix, feature = "rocksdb"))]
+fn rocksdb_state_snapshot(
+    storage_settings: &RwLock<StorageSettings>,
+    rocksdb_provider: &RocksDBProvider,
+) -> OnceLock<Arc<RocksDBStateSnapshot>> {
+    let snapshot = OnceLock::new();
+    if storage_settings.read().state_in_rocksdb {
+        let _ = snapshot.set(Arc::new(RocksDBStateSnapshot::new(rocksdb_provider.clone())));
+    }
+    snapshot
+}
+
 impl<TX, N: NodeTypes> RocksDBProviderFactory for DatabaseProvider<TX, N> {
     /// Returns the `RocksDB` provider.
     fn rocksdb_provider(&self) -> RocksDBProvider {
@@ -335,6 +461,170 @@ impl<TX, N: NodeTypes> RocksDBProviderFactory for DatabaseProvider<TX, N> {
     }
 }
 
+impl<TX: DbTx + 'static, N: NodeTypes> StateCursorProvider for DatabaseProvider<TX, N> {
+    fn state_cursor_read<T: RocksDBStateTable>(
+        &self,
+    ) -> Result<StateCursorTy<Self, T>, DatabaseError> {
+        #[cfg(all(unix, feature = "rocksdb"))]
+        if self.cached_storage_settings().state_in_rocksdb {
+            return Ok(EitherCursor::RocksDB(self.rocksdb_state_cursor()?))
+        }
+        Ok(EitherCursor::database(self.tx.cursor_read::<T>()?))
+    }
+
+    fn state_cursor_dup_read<T: RocksDBStateTable + DupSort>(
+        &self,
+    ) -> Result<DupStateCursorTy<Self, T>, DatabaseError> {
+        #[cfg(all(unix, feature = "rocksdb"))]
+        if self.cached_storage_settings().state_in_rocksdb {
+            return Ok(EitherCursor::RocksDB(self.rocksdb_state_cursor()?))
+        }
+        Ok(EitherCursor::database(self.tx.cursor_dup_read::<T>()?))
+    }
+
+    fn state_cursor_write<T: RocksDBStateTable>(
+        &self,
+    ) -> Result<StateCursorMutTy<Self, T>, DatabaseError>
+    where
+        TX: DbTxMut,
+    {
+        #[cfg(all(unix, feature = "rocksdb"))]
+        if self.cached_storage_settings().state_in_rocksdb {
+            self.begin_rocksdb_state_commit()?;
+            return Ok(EitherCursor::RocksDB(self.rocksdb_state_cursor()?))
+        }
+        Ok(EitherCursor::database(self.tx.cursor_write::<T>()?))
+    }
+
+    fn state_cursor_dup_write<T: RocksDBStateTable + DupSort>(
+        &self,
+    ) -> Result<DupStateCursorMutTy<Self, T>, DatabaseError>
+    where
+        TX: DbTxMut,
+    {
+        #[cfg(all(unix, feature = "rocksdb"))]
+        if self.cached_storage_settings().state_in_rocksdb {
+            self.begin_rocksdb_state_commit()?;
+            return Ok(EitherCursor::RocksDB(self.rocksdb_state_cursor()?))
+        }
+        Ok(EitherCursor::database(self.tx.cursor_dup_write::<T>()?))
+    }
+
+    fn clear_state_table<T: RocksDBStateTable>(&self) -> Result<(), DatabaseError>
+    where
+        TX: DbTxMut,
+    {
+        #[cfg(all(unix, feature = "rocksdb"))]
+        if self.cached_storage_settings().state_in_rocksdb {
+            self.begin_rocksdb_state_commit()?;
+            self.pending_rocksdb_state.lock().table_mut(T::NAME).clear();
+            return Ok(())
+        }
+        self.tx.clear::<T>()
+    }
+
+    fn state_entries<T: RocksDBStateTable>(&self) -> Result<usize, DatabaseError> {
+        #[cfg(all(unix, feature = "rocksdb"))]
+        if self.cached_storage_settings().state_in_rocksdb {
+            return self
+                .rocksdb_state_cursor::<T>()?
+                .walk(None)?
+                .try_fold(0, |entries, entry| entry.map(|_| entries + 1))
+        }
+        self.tx.entries::<T>()
+    }
+}
+
+#[cfg(all(unix, feature = "rocksdb"))]
+impl<TX, N: NodeTypes> DatabaseProvider<TX, N> {
+    /// Returns the snapshot of the `RocksDB` state this provider reads, taking it if the state
+    /// wasn't stored in `RocksDB` when the provider was created.
+    fn rocksdb_state_snapshot(&self) -> &Arc<RocksDBStateSnapshot> {
+        self.rocksdb_state_snapshot
+            .get_or_init(|| Arc::new(RocksDBStateSnapshot::new(self.rocksdb_provider.clone())))
+    }
+
+    /// Returns a cursor over state table `T` stored in `RocksDB` that reads the state snapshot
+    /// and the pending state writes of this provider.
+    fn rocksdb_state_cursor<T: RocksDBStateTable>(
+        &self,
+    ) -> Result<RocksDBStateCursor<T>, DatabaseError> {
+        RocksDBStateCursor::new(
+            self.rocksdb_state_snapshot().clone(),
+            self.applying_rocksdb_state.as_ref().unwrap_or(&self.pending_rocksdb_state).clone(),
+        )
+    }
+
+    /// Reads the state writes of the commit the database transaction is at if the `RocksDB`
+    /// state snapshot was taken while they were being applied, see
+    /// [`RocksDBProvider::applying_state`].
+    ///
+    /// Returns the state commit of the snapshot and of the database transaction if they still
+    /// differ.
+    pub(crate) fn read_applying_rocksdb_state(&mut self) -> ProviderResult<Option<(u64, u64)>>
+    where
+        TX: DbTx,
+    {
+        let Some((snapshot, database)) = self.rocksdb_state_commit_mismatch()? else {
+            return Ok(None)
+        };
+        if snapshot + 1 == database &&
+            let Some(state) = self.rocksdb_provider.applying_state(database)
+        {
+            self.applying_rocksdb_state = Some(state);
+            return Ok(None)
+        }
+        Ok(Some((snapshot, database)))
+    }
+
+    /// Returns the state commit of the `RocksDB` state snapshot and of the database transaction
+    /// if they differ.
+    ///
+    /// They differ if the provider was created while another provider was committing, between
+    /// its database transaction and its `RocksDB` state, unless the provider reads the state
+    /// writes of the commit, see [`Self::read_applying_rocksdb_state`].
+    pub(crate) fn rocksdb_state_commit_mismatch(&self) -> ProviderResult<Option<(u64, u64)>>
+    where
+        TX: DbTx,
+    {
+        if !self.storage_settings.read().state_in_rocksdb || self.applying_rocksdb_state.is_some()
+        {
+            return Ok(None)
+        }
+
+        let snapshot = self.rocksdb_state_snapshot().state_commit()?;
+        let database = self.rocksdb_state_database_commit()?;
+        Ok((snapshot != database).then_some((snapshot, database)))
+    }
+
+    /// Returns the number of the last state commit recorded in the database transaction.
+    pub(crate) fn rocksdb_state_database_commit(&self) -> ProviderResult<u64>
+    where
+        TX: DbTx,
+    {
+        let value = self.tx.get::<tables::Metadata>(STATE_COMMIT_METADATA_KEY.to_string())?;
+        Ok(value.and_then(|value| value.try_into().ok()).map_or(0, u64::from_be_bytes))
+    }
+
+    /// Allocates the next state commit number for the state writes of this provider and records
+    /// it in the database transaction, if not done yet.
+    fn begin_rocksdb_state_commit(&self) -> Result<(), DatabaseError>
+    where
+        TX: DbTxMut,
+    {
+        let mut pending = self.pending_rocksdb_state.lock();
+        if pending.commit.is_none() {
+            let commit = self.rocksdb_state_snapshot().state_commit()? + 1;
+            self.tx.put::<tables::Metadata>(
+                STATE_COMMIT_METADATA_KEY.to_string(),
+                commit.to_be_bytes().to_vec(),
+            )?;
+            pending.commit = Some(commit);
+        }
+        Ok(())
+    }
+}
+
 impl<TX: Debug + Send, N: NodeTypes<ChainSpec: EthChainSpec + 'static>> ChainSpecProvider
     for DatabaseProvider<TX, N>
 {
@@ -360,6 +650,8 @@ impl<TX: DbTxMut, N: NodeTypes> DatabaseProvider<TX, N> {
         runtime: reth_tasks::Runtime,
         commit_order: CommitOrder,
     ) -> Self {
+        #[cfg(all(unix, feature = "rocksdb"))]
+        let rocksdb_state_snapshot = rocksdb_state_snapshot(&storage_settings, &rocksdb_provider);
         Self {
             tx,
             chain_spec,
@@ -371,9 +663,15 @@ impl<TX: DbTxMut, N: NodeTypes> DatabaseProvider<TX, N> {
             changeset_cache,
             runtime,
             pending_rocksdb_batches: Default::default(),
+            pending_rocksdb_state: Default::default(),
+            #[cfg(all(unix, feature = "rocksdb"))]
+            rocksdb_state_snapshot,
+            #[cfg(all(unix, feature = "rocksdb"))]
+            applying_rocksdb_state: None,
             commit_order,
             minimum_pruning_distance: MINIMUM_UNWIND_SAFE_DISTANCE,
             metrics: metrics::DatabaseProviderMetrics::default(),
+            state_snapshots_lock: OnceLock::new(),
         }
     }
 
@@ -716,6 +1014,24 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
         Ok(())
     }
 
+    /// Removes all state snapshot blocks above `block` from static files, if any.
+    ///
+    /// Waits for a state snapshot being written and keeps new ones from being written until this
+    /// provider is committed.
+    fn prune_state_snapshots_above(&self, block: BlockNumber) -> ProviderResult<()> {
+        self.state_snapshots_lock.get_or_init(|| self.static_file_provider.lock_state_snapshots());
+
+        let segment = StaticFileSegment::StateSnapshots;
+        if self
+            .static_file_provider
+            .get_highest_static_file_block(segment)
+            .is_some_and(|highest_block| highest_block > block)
+        {
+            self.static_file_provider.latest_writer(segment)?.prune_state_snapshots(block)?;
+        }
+        Ok(())
+    }
+
     /// Writes MDBX-only data for a block (indices, lookups, and senders if configured for MDBX).
     ///
     /// SF data (headers, transactions, senders if SF, receipts if SF) must be written separately.
@@ -985,6 +1301,8 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
         changeset_cache: ChangesetCache,
         runtime: reth_tasks::Runtime,
     ) -> Self {
+        #[cfg(all(unix, feature = "rocksdb"))]
+        let rocksdb_state_snapshot = rocksdb_state_snapshot(&storage_settings, &rocksdb_provider);
         Self {
             tx,
             chain_spec,
@@ -996,9 +1314,15 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
             changeset_cache,
             runtime,
             pending_rocksdb_batches: Default::default(),
+            pending_rocksdb_state: Default::default(),
+            #[cfg(all(unix, feature = "rocksdb"))]
+            rocksdb_state_snapshot,
+            #[cfg(all(unix, feature = "rocksdb"))]
+            applying_rocksdb_state: None,
             commit_order: CommitOrder::Normal,
             minimum_pruning_distance: MINIMUM_UNWIND_SAFE_DISTANCE,
             metrics: metrics::DatabaseProviderMetrics::default(),
+            state_snapshots_lock: OnceLock::new(),
         }
     }
 
@@ -1405,13 +1729,13 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
     }
 }
 
-impl<TX: DbTx, N: NodeTypes> AccountReader for DatabaseProvider<TX, N> {
+impl<TX: DbTx + 'static, N: NodeTypes> AccountReader for DatabaseProvider<TX, N> {
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
         if self.cached_storage_settings().use_hashed_state() {
             let hashed_address = keccak256(address);
-            Ok(self.tx.get_by_encoded_key::<tables::HashedAccounts>(&hashed_address)?)
+            Ok(self.get_state::<tables::HashedAccounts>(hashed_address)?)
         } else {
-            Ok(self.tx.get_by_encoded_key::<tables::PlainAccountState>(address)?)
+            Ok(self.get_state::<tables::PlainAccountState>(*address)?)
         }
     }
 }
@@ -1431,7 +1755,7 @@ impl<TX: DbTx + 'static, N: NodeTypes> AccountExtReader for DatabaseProvider<TX,
         iter: impl IntoIterator<Item = Address>,
     ) -> ProviderResult<Vec<(Address, Option<Account>)>> {
         if self.cached_storage_settings().use_hashed_state() {
-            let mut hashed_accounts = self.tx.cursor_read::<tables::HashedAccounts>()?;
+            let mut hashed_accounts = self.state_cursor_read::<tables::HashedAccounts>()?;
             Ok(iter
                 .into_iter()
                 .map(|address| {
@@ -1440,7 +1764,7 @@ impl<TX: DbTx + 'static, N: NodeTypes> AccountExtReader for DatabaseProvider<TX,
                 })
                 .collect::<Result<Vec<_>, _>>()?)
         } else {
-            let mut plain_accounts = self.tx.cursor_read::<tables::PlainAccountState>()?;
+            let mut plain_accounts = self.state_cursor_read::<tables::PlainAccountState>()?;
             Ok(iter
                 .into_iter()
                 .map(|address| {
@@ -2255,7 +2579,7 @@ impl<TX: DbTx + 'static, N: NodeTypes> StorageReader for DatabaseProvider<TX, N>
         addresses_with_keys: impl IntoIterator<Item = (Address, impl IntoIterator<Item = B256>)>,
     ) -> ProviderResult<Vec<(Address, Vec<StorageEntry>)>> {
         if self.cached_storage_settings().use_hashed_state() {
-            let mut hashed_storage = self.tx.cursor_dup_read::<tables::HashedStorages>()?;
+            let mut hashed_storage = self.state_cursor_dup_read::<tables::HashedStorages>()?;
 
             addresses_with_keys
                 .into_iter()
@@ -2277,7 +2601,7 @@ impl<TX: DbTx + 'static, N: NodeTypes> StorageReader for DatabaseProvider<TX, N>
                 })
                 .collect::<ProviderResult<Vec<(_, _)>>>()
         } else {
-            let mut plain_storage = self.tx.cursor_dup_read::<tables::PlainStorageState>()?;
+            let mut plain_storage = self.state_cursor_dup_read::<tables::PlainStorageState>()?;
 
             addresses_with_keys
                 .into_iter()
@@ -2501,9 +2825,9 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
         if config.write_storage_changesets {
             tracing::trace!("Writing storage changes");
             let mut storages_cursor =
-                self.tx_ref().cursor_dup_write::<tables::PlainStorageState>()?;
+                self.state_cursor_dup_write::<tables::PlainStorageState>()?;
             let mut hashed_storages_cursor =
-                self.tx_ref().cursor_dup_write::<tables::HashedStorages>()?;
+                self.state_cursor_dup_write::<tables::HashedStorages>()?;
             for (block_index, mut storage_changes) in reverts.storage.into_iter().enumerate() {
                 let block_number = first_block + block_index as BlockNumber;
 
@@ -2603,7 +2927,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
         if !self.cached_storage_settings().use_hashed_state() {
             // Write new account state
             tracing::trace!(len = changes.accounts.len(), "Writing new account state");
-            let mut accounts_cursor = self.tx_ref().cursor_write::<tables::PlainAccountState>()?;
+            let mut accounts_cursor = self.state_cursor_write::<tables::PlainAccountState>()?;
             // write account to database.
             for (address, account) in changes.accounts {
                 if let Some(account) = account {
@@ -2618,7 +2942,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
             // Write new storage state and wipe storage if needed.
             tracing::trace!(len = changes.storage.len(), "Writing new storage state");
             let mut storages_cursor =
-                self.tx_ref().cursor_dup_write::<tables::PlainStorageState>()?;
+                self.state_cursor_dup_write::<tables::PlainStorageState>()?;
             for PlainStorageChangeset { address, wipe_storage, storage } in changes.storage {
                 // Wiping of storage.
                 if wipe_storage && storages_cursor.seek_exact(address)?.is_some() {
@@ -2660,7 +2984,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
     #[instrument(level = "debug", target = "providers::db", skip_all)]
     fn write_hashed_state(&self, hashed_state: &HashedPostStateSorted) -> ProviderResult<()> {
         // Write hashed account updates.
-        let mut hashed_accounts_cursor = self.tx_ref().cursor_write::<tables::HashedAccounts>()?;
+        let mut hashed_accounts_cursor = self.state_cursor_write::<tables::HashedAccounts>()?;
         for (hashed_address, account) in hashed_state.accounts() {
             if let Some(account) = account {
                 hashed_accounts_cursor.upsert(*hashed_address, account)?;
@@ -2672,7 +2996,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
         // Write hashed storage changes.
         let sorted_storages = hashed_state.account_storages().iter().sorted_by_key(|(key, _)| *key);
         let mut hashed_storage_cursor =
-            self.tx_ref().cursor_dup_write::<tables::HashedStorages>()?;
+            self.state_cursor_dup_write::<tables::HashedStorages>()?;
         for (hashed_address, storage) in sorted_storages {
             if storage.is_wiped() && hashed_storage_cursor.seek_exact(*hashed_address)?.is_some() {
                 hashed_storage_cursor.delete_current_duplicates()?;
@@ -2738,6 +3062,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
             let mut changeset_writer =
                 self.static_file_provider.latest_writer(StaticFileSegment::StorageChangeSets)?;
             changeset_writer.prune_storage_changesets(block)?;
+            self.prune_state_snapshots_above(block)?;
             changesets
         } else {
             self.take::<tables::StorageChangeSets>(storage_range)?
@@ -2758,8 +3083,8 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
         };
 
         if self.cached_storage_settings().use_hashed_state() {
-            let mut hashed_accounts_cursor = self.tx.cursor_write::<tables::HashedAccounts>()?;
-            let mut hashed_storage_cursor = self.tx.cursor_dup_write::<tables::HashedStorages>()?;
+            let mut hashed_accounts_cursor = self.state_cursor_write::<tables::HashedAccounts>()?;
+            let mut hashed_storage_cursor = self.state_cursor_dup_write::<tables::HashedStorages>()?;
 
             let (state, _) = self.populate_bundle_state_hashed(
                 account_changeset,
@@ -2801,9 +3126,9 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
             // state of end range. We should rename the functions or add support to access
             // History state. Accessing history state can be tricky but we are not gaining
             // anything.
-            let mut plain_accounts_cursor = self.tx.cursor_write::<tables::PlainAccountState>()?;
+            let mut plain_accounts_cursor = self.state_cursor_write::<tables::PlainAccountState>()?;
             let mut plain_storage_cursor =
-                self.tx.cursor_dup_write::<tables::PlainStorageState>()?;
+                self.state_cursor_dup_write::<tables::PlainStorageState>()?;
 
             let (state, _) = self.populate_bundle_state(
                 account_changeset,
@@ -2896,6 +3221,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
             let mut changeset_writer =
                 self.static_file_provider.latest_writer(StaticFileSegment::StorageChangeSets)?;
             changeset_writer.prune_storage_changesets(block)?;
+            self.prune_state_snapshots_above(block)?;
             changesets
         } else {
             self.take::<tables::StorageChangeSets>(storage_range)?
@@ -2927,8 +3253,8 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
         };
 
         let (state, reverts) = if self.cached_storage_settings().use_hashed_state() {
-            let mut hashed_accounts_cursor = self.tx.cursor_write::<tables::HashedAccounts>()?;
-            let mut hashed_storage_cursor = self.tx.cursor_dup_write::<tables::HashedStorages>()?;
+            let mut hashed_accounts_cursor = self.state_cursor_write::<tables::HashedAccounts>()?;
+            let mut hashed_storage_cursor = self.state_cursor_dup_write::<tables::HashedStorages>()?;
 
             let (state, reverts) = self.populate_bundle_state_hashed(
                 account_changeset,
@@ -2972,9 +3298,9 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
             // state of end range. We should rename the functions or add support to access
             // History state. Accessing history state can be tricky but we are not gaining
             // anything.
-            let mut plain_accounts_cursor = self.tx.cursor_write::<tables::PlainAccountState>()?;
+            let mut plain_accounts_cursor = self.state_cursor_write::<tables::PlainAccountState>()?;
             let mut plain_storage_cursor =
-                self.tx.cursor_dup_write::<tables::PlainStorageState>()?;
+                self.state_cursor_dup_write::<tables::PlainStorageState>()?;
 
             let (state, reverts) = self.populate_bundle_state(
                 account_changeset,
@@ -3074,8 +3400,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> TrieWriter for DatabaseProvider
         // Track the number of inserted entries.
         let mut num_entries = 0;
 
-        let tx = self.tx_ref();
-        let mut account_trie_cursor = tx.cursor_write::<tables::AccountsTrie>()?;
+        let mut account_trie_cursor = self.state_cursor_write::<tables::AccountsTrie>()?;
 
         // Process sorted account nodes
         for (key, updated_node) in trie_updates.account_nodes_ref() {
@@ -3116,7 +3441,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> StorageTrieWriter for DatabaseP
         let mut num_entries = 0;
         let mut storage_tries = storage_tries.collect::<Vec<_>>();
         storage_tries.sort_unstable_by(|a, b| a.0.cmp(b.0));
-        let mut cursor = self.tx_ref().cursor_dup_write::<tables::StoragesTrie>()?;
+        let mut cursor = self.state_cursor_dup_write::<tables::StoragesTrie>()?;
         for (hashed_address, storage_trie_updates) in storage_tries {
             let mut db_storage_trie_cursor =
                 DatabaseStorageTrieCursor::new(cursor, *hashed_address);
@@ -3146,7 +3471,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HashingWriter for DatabaseProvi
             .collect::<BTreeMap<_, _>>();
 
         // Apply values to HashedState, and remove the account if it's None.
-        let mut hashed_accounts_cursor = self.tx.cursor_write::<tables::HashedAccounts>()?;
+        let mut hashed_accounts_cursor = self.state_cursor_write::<tables::HashedAccounts>()?;
         for (hashed_address, account) in &hashed_accounts {
             if let Some(account) = account {
                 hashed_accounts_cursor.upsert(*hashed_address, account)?;
@@ -3170,7 +3495,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HashingWriter for DatabaseProvi
         &self,
         changesets: impl IntoIterator<Item = (Address, Option<Account>)>,
     ) -> ProviderResult<BTreeMap<B256, Option<Account>>> {
-        let mut hashed_accounts_cursor = self.tx.cursor_write::<tables::HashedAccounts>()?;
+        let mut hashed_accounts_cursor = self.state_cursor_write::<tables::HashedAccounts>()?;
         let hashed_accounts =
             changesets.into_iter().map(|(ad, ac)| (keccak256(ad), ac)).collect::<BTreeMap<_, _>>();
         for (hashed_address, account) in &hashed_accounts {
@@ -3200,7 +3525,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HashingWriter for DatabaseProvi
         // Apply values to HashedState, and remove the account if it's None.
         let mut hashed_storage_keys: B256Map<BTreeSet<B256>> =
             B256Map::with_capacity_and_hasher(hashed_storages.len(), Default::default());
-        let mut hashed_storage = self.tx.cursor_dup_write::<tables::HashedStorages>()?;
+        let mut hashed_storage = self.state_cursor_dup_write::<tables::HashedStorages>()?;
         for (hashed_address, key, value) in hashed_storages.into_iter().rev() {
             hashed_storage_keys.entry(hashed_address).or_default().insert(key);
 
@@ -3247,7 +3572,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HashingWriter for DatabaseProvi
             .map(|(hashed_address, entries)| (*hashed_address, entries.keys().copied().collect()))
             .collect();
 
-        let mut hashed_storage_cursor = self.tx.cursor_dup_write::<tables::HashedStorages>()?;
+        let mut hashed_storage_cursor = self.state_cursor_dup_write::<tables::HashedStorages>()?;
         // Hash the address and key and apply them to HashedStorage (if Storage is None
         // just remove it);
         hashed_storages.into_iter().try_for_each(|(hashed_address, storage)| {
@@ -3854,7 +4179,21 @@ impl<TX: DbTx + 'static, N: NodeTypes + 'static> DBProvider for DatabaseProvider
         // it is interrupted before the static files commit, we can just
         // truncate the static files according to the
         // checkpoints on the next start-up.
+        //
+        // State writes to `RocksDB` are journaled before the database commit that records their
+        // commit number and applied after it, so they can be replayed on the next start-up. They
+        // are published in between, so providers opened before they're applied can read them.
+        #[cfg(all(unix, feature = "rocksdb"))]
+        let state: PendingRocksDBState =
+            Arc::new(std::mem::take(&mut *self.pending_rocksdb_state.lock()).into());
+
         if self.static_file_provider.has_unwind_queued() || self.commit_order.is_unwind() {
+            #[cfg(all(unix, feature = "rocksdb"))]
+            {
+                self.rocksdb_provider.journal_pending_state(&state.lock())?;
+                self.rocksdb_provider.begin_applying_state(&state);
+            }
+
             self.tx.commit()?;
 
             #[cfg(all(unix, feature = "rocksdb"))]
@@ -3863,6 +4202,8 @@ impl<TX: DbTx + 'static, N: NodeTypes + 'static> DBProvider for DatabaseProvider
                 for batch in batches {
                     self.rocksdb_provider.commit_batch(batch)?;
                 }
+                self.rocksdb_provider.apply_pending_state(&state.lock())?;
+                self.rocksdb_provider.end_applying_state(&state);
             }
 
             self.static_file_provider.commit()?;
@@ -3881,6 +4222,8 @@ impl<TX: DbTx + 'static, N: NodeTypes + 'static> DBProvider for DatabaseProvider
                 for batch in batches {
                     self.rocksdb_provider.commit_batch(batch)?;
                 }
+                self.rocksdb_provider.journal_pending_state(&state.lock())?;
+                self.rocksdb_provider.begin_applying_state(&state);
                 timings.rocksdb = start.elapsed();
             }
 
@@ -3888,6 +4231,14 @@ impl<TX: DbTx + 'static, N: NodeTypes + 'static> DBProvider for DatabaseProvider
             self.tx.commit()?;
             timings.mdbx = start.elapsed();
 
+            #[cfg(all(unix, feature = "rocksdb"))]
+            {
+                let start = Instant::now();
+                self.rocksdb_provider.apply_pending_state(&state.lock())?;
+                self.rocksdb_provider.end_applying_state(&state);
+                timings.rocksdb += start.elapsed();
+            }
+
             self.metrics.record_commit(&timings);
         }
 
@@ -5228,6 +5579,175 @@ mod tests {
         run_save_blocks_and_verify(StorageMode::V2);
     }
 
+    #[test]
+    fn test_state_snapshot_reads_match_changesets() {
+        use crate::{
+            providers::{LowestAvailableBlocks, StaticFileProviderBuilder},
+            test_utils::MockNodeTypesWithDB,
+            ProviderFactory,
+        };
+        use alloy_primitives::map::HashMap;
+        use rand::{seq::IndexedRandom, Rng};
+
+        let mut rng = generators::rng();
+        let num_blocks = 21u64;
+        let addresses: Vec<_> = (1..=6).map(Address::with_last_byte).collect();
+        let slots: Vec<_> = (1..=3).map(U256::from).collect();
+
+        let test_factory = create_test_provider_factory();
+        let static_files = tempfile::tempdir().unwrap();
+        let factory = ProviderFactory::<MockNodeTypesWithDB>::new(
+            test_factory.db_ref().clone(),
+            test_factory.chain_spec(),
+            StaticFileProviderBuilder::read_write(static_files.path())
+                .with_state_snapshot_interval(Some(4))
+                .build()
+                .unwrap(),
+            test_factory.rocksdb_provider(),
+            reth_tasks::Runtime::test(),
+        )
+        .unwrap();
+        factory.set_storage_settings_cache(StorageSettings::v2());
+
+        fn executed_block(
+            block: SealedBlock<reth_ethereum_primitives::Block>,
+            bundle: BundleState,
+        ) -> ExecutedBlock {
+            let hashed_state =
+                HashedPostState::from_bundle_state::<KeccakKeyHasher>(bundle.state()).into_sorted();
+            ExecutedBlock::new(
+                Arc::new(block.try_recover().unwrap()),
+                Arc::new(BlockExecutionOutput {
+                    result: BlockExecutionResult {
+                        receipts: vec![],
+                        requests: Default::default(),
+                        gas_used: 0,
+                        blob_gas_used: 0,
+                    },
+                    state: bundle,
+                }),
+                ComputedTrieData { hashed_state: Arc::new(hashed_state), ..Default::default() },
+            )
+        }
+
+        let genesis = SealedBlock::<reth_ethereum_primitives::Block>::seal_parts(
+            Header { number: 0, difficulty: U256::from(1), ..Default::default() },
+            Default::default(),
+        );
+        let mut parent_hash = genesis.hash();
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .save_blocks(vec![executed_block(genesis, Default::default())], SaveBlocksMode::Full)
+            .unwrap();
+        provider_rw.commit().unwrap();
+        factory.write_state_snapshots().unwrap();
+
+        // The accounts and storage after every block, indexed by block number.
+        let mut accounts = vec![HashMap::<Address, AccountInfo>::default()];
+        let mut storages = vec![HashMap::<(Address, U256), U256>::default()];
+        let mut snapshot_blocks = Vec::new();
+        for block_num in 1..=num_blocks {
+            let (mut block_accounts, mut block_storages) =
+                (accounts.last().unwrap().clone(), storages.last().unwrap().clone());
+            let mut builder = BundleState::builder(block_num..=block_num);
+            for &address in addresses.choose_multiple(&mut rng, 2) {
+                let info = AccountInfo {
+                    nonce: block_num,
+                    balance: U256::from(rng.random::<u64>()),
+                    ..Default::default()
+                };
+                let slot = *slots.choose(&mut rng).unwrap();
+                let value = U256::from(rng.random::<u64>() | 1);
+                let previous_value = block_storages.get(&(address, slot)).copied();
+
+                builder = builder
+                    .state_present_account_info(address, info.clone())
+                    .revert_account_info(
+                        block_num,
+                        address,
+                        Some(block_accounts.get(&address).cloned()),
+                    )
+                    .state_storage(
+                        address,
+                        HashMap::from_iter([(slot, (previous_value.unwrap_or_default(), value))]),
+                    )
+                    .revert_storage(
+                        block_num,
+                        address,
+                        vec![(slot, previous_value.unwrap_or_default())],
+                    );
+                block_accounts.insert(address, info);
+                block_storages.insert((address, slot), value);
+            }
+            accounts.push(block_accounts);
+            storages.push(block_storages);
+
+            let block = SealedBlock::<reth_ethereum_primitives::Block>::seal_parts(
+                Header {
+                    number: block_num,
+                    parent_hash,
+                    difficulty: U256::from(1),
+                    ..Default::default()
+                },
+                Default::default(),
+            );
+            parent_hash = block.hash();
+
+            let provider_rw = factory.provider_rw().unwrap();
+            provider_rw
+                .save_blocks(vec![executed_block(block, builder.build())], SaveBlocksMode::Full)
+                .unwrap();
+            provider_rw.commit().unwrap();
+            snapshot_blocks.extend(factory.write_state_snapshots().unwrap());
+        }
+        assert_eq!(snapshot_blocks, vec![1, 5, 9, 13, 17, 21]);
+
+        let provider = factory.provider().unwrap();
+        let history_pruned = LowestAvailableBlocks {
+            account_history_block_number: Some(num_blocks + 1),
+            storage_history_block_number: Some(num_blocks + 1),
+        };
+        for _ in 0..16 {
+            let block_num = rng.random_range(1..=num_blocks);
+            let from_changesets = HistoricalStateProviderRef::new(&provider, block_num);
+            let from_snapshot = HistoricalStateProviderRef::new_with_lowest_available_blocks(
+                &provider,
+                block_num,
+                history_pruned,
+            );
+
+            // The state at a block is the state after the previous block.
+            for &address in &addresses {
+                let expected = accounts[block_num as usize - 1]
+                    .get(&address)
+                    .map(|info| (info.nonce, info.balance));
+                for state in [&from_changesets, &from_snapshot] {
+                    let account = state.basic_account(&address).unwrap();
+                    assert_eq!(
+                        account.map(|account| (account.nonce, account.balance)),
+                        expected,
+                        "account {address} at block {block_num}"
+                    );
+                }
+
+                for &slot in &slots {
+                    let expected = storages[block_num as usize - 1]
+                        .get(&(address, slot))
+                        .copied()
+                        .unwrap_or_default();
+                    for state in [&from_changesets, &from_snapshot] {
+                        let value = state.storage(address, slot.into()).unwrap();
+                        assert_eq!(
+                            value.unwrap_or_default(),
+                            expected,
+                            "slot {slot} of {address} at block {block_num}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_write_and_remove_state_roundtrip_v2() {
         let factory = create_test_provider_factory();
@@ -5508,4 +6028,43 @@ mod tests {
         assert!(!all_blocks.contains(&7), "block 7 should be unwound");
         assert!(!all_blocks.contains(&10), "block 10 should be unwound");
     }
+
+    #[test]
+    #[cfg(all(unix, feature = "rocksdb"))]
+    fn test_provider_reads_applying_rocksdb_state() {
+        let factory = create_test_provider_factory();
+        factory.set_storage_settings_cache(StorageSettings::v2().with_state_in_rocksdb(true));
+        let rocksdb = factory.rocksdb_provider();
+        let address = Address::with_last_byte(1);
+        let account = Account { nonce: 1, ..Default::default() };
+
+        // Commit the database transaction of a state commit without applying its state writes.
+        let provider_rw = factory.provider_rw().unwrap().0;
+        provider_rw
+            .state_cursor_write::<tables::PlainAccountState>()
+            .unwrap()
+            .upsert(address, &account)
+            .unwrap();
+        let state = provider_rw.pending_rocksdb_state.clone();
+        rocksdb.journal_pending_state(&state.lock()).unwrap();
+        rocksdb.begin_applying_state(&state);
+        provider_rw.into_tx().commit().unwrap();
+
+        let read_account = |provider: &DatabaseProviderRO<_, _>| {
+            provider.state_cursor_read::<tables::PlainAccountState>().unwrap().seek_exact(address)
+        };
+        let provider = factory.provider().unwrap();
+        assert_eq!(read_account(&provider).unwrap(), Some((address, account)));
+        assert!(matches!(
+            factory.provider_rw(),
+            Err(ProviderError::StateCommitInProgress { snapshot: 0, database: 1 })
+        ));
+
+        rocksdb.apply_pending_state(&state.lock()).unwrap();
+        rocksdb.end_applying_state(&state);
+        assert!(rocksdb.applying_state(1).is_none());
+        assert_eq!(read_account(&provider).unwrap(), Some((address, account)));
+        assert_eq!(read_account(&factory.provider().unwrap()).unwrap(), Some((address, account)));
+        assert!(factory.provider_rw().is_ok());
+    }
 }
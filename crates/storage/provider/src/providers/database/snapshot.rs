@@ -0,0 +1,253 @@
+//! Online storage snapshots.
+
+use super::ProviderFactory;
+use crate::{
+    providers::ProviderNodeTypes, BlockHashReader, BlockNumReader, DBProvider, ProviderError,
+    StaticFileProviderFactory,
+};
+use alloy_primitives::{Keccak256, B256};
+use hanzo_evm_db::version::create_db_version_file;
+use hanzo_evm_db_api::{
+    models::{StorageSnapshotFile, StorageSnapshotManifest},
+    transaction::DbTx,
+};
+use hanzo_evm_fs_util::FsPathError;
+use hanzo_evm_nippy_jar::{NippyJar, CONFIG_FILE_EXTENSION};
+use hanzo_evm_static_file_types::{SegmentHeader, StaticFileSegment};
+use hanzo_evm_storage_api::{StorageSettingsCache, StorageSnapshotProvider};
+use hanzo_evm_storage_errors::provider::ProviderResult;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    io::Read,
+    path::Path,
+};
+use tracing::{debug, info};
+
+/// Name of the MDBX data file inside the database directory.
+const MDBX_DATA_FILE: &str = "mdbx.dat";
+
+impl<N: ProviderNodeTypes> StorageSnapshotProvider for ProviderFactory<N> {
+    /// Copies the storage of a running node into `dest`.
+    ///
+    /// The snapshot is pinned by opening an MDBX read transaction first, the database is then
+    /// copied from that transaction. Static files and `RocksDB` can only be ahead of it, and are
+    /// unwound to the pinned block by the startup consistency checks of the restored node.
+    ///
+    /// The read transaction stays open for the whole copy, so the live database can't reuse
+    /// pages freed in the meantime and may grow while the snapshot is taken.
+    fn create_storage_snapshot(&self, dest: &Path) -> ProviderResult<StorageSnapshotManifest> {
+        if dest.exists() {
+            return Err(ProviderError::other(FsPathError::create_dir(
+                std::io::Error::from(std::io::ErrorKind::AlreadyExists),
+                dest,
+            )))
+        }
+
+        let provider = self.provider()?.disable_long_read_transaction_safety();
+        let block_number = provider.best_block_number()?;
+        let block_hash = provider
+            .block_hash(block_number)?
+            .ok_or(ProviderError::HeaderNotFound(block_number.into()))?;
+        let storage_settings = self.cached_storage_settings();
+        info!(
+            target: "providers::snapshot",
+            block_number,
+            ?block_hash,
+            dest = %dest.display(),
+            "Creating storage snapshot"
+        );
+
+        let static_files_dest = dest.join("static_files");
+        let static_file_provider = self.static_file_provider();
+        let static_files_lock = static_file_provider.lock_static_file_files();
+        copy_static_files(static_file_provider.directory(), &static_files_dest)?;
+        drop(static_files_lock);
+        debug!(target: "providers::snapshot", "Copied static files");
+
+        self.rocksdb_provider.create_checkpoint(&dest.join("rocksdb"))?;
+        debug!(target: "providers::snapshot", "Created RocksDB checkpoint");
+
+        let db_dest = dest.join("db");
+        hanzo_evm_fs_util::create_dir_all(&db_dest).map_err(ProviderError::other)?;
+        provider.tx_ref().copy_to_path(&db_dest.join(MDBX_DATA_FILE))?;
+        create_db_version_file(&db_dest)
+            .map_err(|err| ProviderError::other(FsPathError::write(err, &db_dest)))?;
+        drop(provider);
+        debug!(target: "providers::snapshot", "Copied database");
+
+        let mut files = Vec::new();
+        collect_files(dest, dest, &mut files)?;
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let manifest = StorageSnapshotManifest {
+            version: StorageSnapshotManifest::VERSION,
+            block_number,
+            block_hash,
+            storage_settings,
+            files,
+        };
+        hanzo_evm_fs_util::write_json_file(
+            &dest.join(StorageSnapshotManifest::FILE_NAME),
+            &manifest,
+        )
+        .map_err(ProviderError::other)?;
+        info!(
+            target: "providers::snapshot",
+            block_number,
+            files = manifest.files.len(),
+            "Created storage snapshot"
+        );
+
+        Ok(manifest)
+    }
+}
+
+/// Copies all static files from `src` to `dest`.
+///
+/// Only the files of the current data version of every static file are copied, following the
+/// symlinks of static files moved to a storage tier. Static files that are no longer written to
+/// are hard-linked, falling back to a copy if `dest` is on another filesystem. The configuration
+/// of the highest static file of each segment is copied first, so that it never describes more
+/// rows than the copied data and offsets contain, any trailing rows are then healed away when the
+/// snapshot is opened.
+fn copy_static_files(src: &Path, dest: &Path) -> ProviderResult<()> {
+    hanzo_evm_fs_util::create_dir_all(dest).map_err(ProviderError::other)?;
+
+    let mut jars = BTreeMap::<String, (StaticFileSegment, u64)>::new();
+    for entry in hanzo_evm_fs_util::read_dir(src).map_err(ProviderError::other)? {
+        let entry = entry.map_err(|err| ProviderError::other(FsPathError::read_dir(err, src)))?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if let Some(jar) = name.strip_suffix(&format!(".{CONFIG_FILE_EXTENSION}")) &&
+            let Some((segment, range)) = StaticFileSegment::parse_filename(jar)
+        {
+            jars.insert(jar.to_string(), (segment, range.end()));
+        }
+    }
+
+    let mut live = HashMap::<StaticFileSegment, u64>::new();
+    for (segment, end) in jars.values() {
+        let highest = live.entry(*segment).or_default();
+        *highest = (*highest).max(*end);
+    }
+
+    for (name, (segment, end)) in &jars {
+        let is_live = live.get(segment) == Some(end);
+        let jar = NippyJar::<SegmentHeader>::load(&src.join(name)).map_err(ProviderError::other)?;
+        for from in
+            [jar.config_path(), jar.offsets_path(), jar.data_path(), jar.changeset_offsets_path()]
+        {
+            if !from.exists() {
+                continue
+            }
+            let to = dest.join(from.file_name().expect("static file path has a file name"));
+
+            // Files moved to a storage tier are symlinks, which must not be linked themselves
+            let from = fs::canonicalize(&from)
+                .map_err(|err| ProviderError::other(FsPathError::read(err, &from)))?;
+            let copied = if is_live {
+                fs::copy(&from, &to).map(drop)
+            } else {
+                fs::hard_link(&from, &to).or_else(|_| fs::copy(&from, &to).map(drop))
+            };
+            copied.map_err(|err| ProviderError::other(FsPathError::write(err, &to)))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Appends all files below `dir` to `files`, with paths relative to `root`.
+fn collect_files(
+    root: &Path,
+    dir: &Path,
+    files: &mut Vec<StorageSnapshotFile>,
+) -> ProviderResult<()> {
+    for entry in hanzo_evm_fs_util::read_dir(dir).map_err(ProviderError::other)? {
+        let entry = entry.map_err(|err| ProviderError::other(FsPathError::read_dir(err, dir)))?;
+        let path = entry.path();
+        let metadata = hanzo_evm_fs_util::metadata(&path).map_err(ProviderError::other)?;
+        if metadata.is_dir() {
+            collect_files(root, &path, files)?;
+        } else if let Ok(relative) = path.strip_prefix(root) {
+            let relative = relative
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            let hash = hash_file(&path)?;
+            files.push(StorageSnapshotFile { path: relative, size: metadata.len(), hash });
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the keccak256 hash of the contents of the file at `path`.
+fn hash_file(path: &Path) -> ProviderResult<B256> {
+    let mut file = hanzo_evm_fs_util::open(path).map_err(ProviderError::other)?;
+    let mut hasher = Keccak256::new();
+    let mut buf = vec![0; 64 * 1024];
+    loop {
+        let n = file
+            .read(&mut buf)
+            .map_err(|err| ProviderError::other(FsPathError::read(err, path)))?;
+        if n == 0 {
+            return Ok(hasher.finalize())
+        }
+        hasher.update(&buf[..n]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        providers::{StaticFileProvider, StaticFileProviderBuilder, StaticFileTier},
+        HeaderProvider,
+    };
+    use alloy_consensus::Header;
+    use hanzo_evm_db::test_utils::create_test_static_files_dir;
+    use hanzo_evm_ethereum_primitives::EthPrimitives;
+    use hanzo_evm_static_file_types::SegmentRangeInclusive;
+
+    #[test]
+    fn copy_static_files_follows_tier_symlinks() -> eyre::Result<()> {
+        let (static_dir, _) = create_test_static_files_dir();
+        let tier_dir = tempfile::tempdir()?;
+        let dest = tempfile::tempdir()?;
+        let dest = dest.path().join("static_files");
+
+        let sf_rw: StaticFileProvider<EthPrimitives> =
+            StaticFileProviderBuilder::read_write(&static_dir)
+                .with_blocks_per_file(10)
+                .with_tiers(vec![StaticFileTier::new(tier_dir.path(), 10)])
+                .build()?;
+        let mut header_writer = sf_rw.latest_writer(StaticFileSegment::Headers)?;
+        let mut header = Header::default();
+        for num in 0..=25 {
+            header.number = num;
+            header_writer.append_header(&header, &B256::ZERO)?;
+        }
+        header_writer.commit()?;
+        drop(header_writer);
+        sf_rw.move_to_tiers()?;
+
+        let filename = StaticFileSegment::Headers.filename(&SegmentRangeInclusive::new(0, 9));
+        assert!(static_dir.join(&filename).is_symlink());
+
+        copy_static_files(&static_dir, &dest)?;
+
+        for name in [filename.clone(), format!("{filename}.off"), format!("{filename}.conf")] {
+            let copied = dest.join(&name);
+            assert!(!copied.is_symlink(), "{name} is a symlink");
+            assert_eq!(fs::read(&copied)?, fs::read(static_dir.join(&name))?);
+        }
+
+        let copy: StaticFileProvider<EthPrimitives> =
+            StaticFileProviderBuilder::read_write(&dest).build()?;
+        assert_eq!(copy.headers_range(0..=25)?.len(), 26);
+
+        Ok(())
+    }
+}
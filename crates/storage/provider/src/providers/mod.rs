@@ -9,12 +9,14 @@ pub use database::*;
 
 mod static_file;
 pub use static_file::{
-    StaticFileAccess, StaticFileJarProvider, StaticFileProvider, StaticFileProviderBuilder,
-    StaticFileProviderRW, StaticFileProviderRWRefMut, StaticFileWriteCtx, StaticFileWriter,
+    SegmentDictionaries, StaticFileAccess, StaticFileJarProvider, StaticFileProvider,
+    StaticFileProviderBuilder, StaticFileProviderRW, StaticFileProviderRWRefMut, StaticFileTier,
+    StaticFileWriteCtx, StaticFileWriter, DICTIONARIES_DIR,
 };
 
 mod state;
 pub use state::{
+    cursor_factory::StateCursorFactory,
     historical::{
         compute_history_rank, history_info, needs_prev_shard_check, HistoricalStateProvider,
         HistoricalStateProviderRef, HistoryInfo, LowestAvailableBlocks,
@@ -42,6 +44,8 @@ pub use rocksdb::{
     PruneShardOutcome, PrunedIndices, RocksDBBatch, RocksDBBuilder, RocksDBIter, RocksDBProvider,
     RocksDBRawIter, RocksDBStats, RocksDBTableStats, RocksTx,
 };
+#[cfg(all(unix, feature = "rocksdb"))]
+pub use rocksdb::{RocksDBStateCursor, RocksDBStateSnapshot, STATE_COMMIT_METADATA_KEY};
 
 /// Helper trait to bound [`NodeTypes`] so that combined with database they satisfy
 /// [`ProviderNodeTypes`].
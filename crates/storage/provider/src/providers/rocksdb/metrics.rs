@@ -10,6 +10,12 @@ pub(super) const ROCKSDB_TABLES: &[&str] = &[
     Tables::TransactionHashNumbers.name(),
     Tables::StoragesHistory.name(),
     Tables::AccountsHistory.name(),
+    Tables::PlainAccountState.name(),
+    Tables::PlainStorageState.name(),
+    Tables::HashedAccounts.name(),
+    Tables::HashedStorages.name(),
+    Tables::AccountsTrie.name(),
+    Tables::StoragesTrie.name(),
 ];
 
 /// Metrics for the `RocksDB` provider.
@@ -3,9 +3,12 @@
 mod invariants;
 mod metrics;
 mod provider;
+mod state;
 
 pub(crate) use provider::{PendingRocksDBBatches, RocksDBWriteCtx};
 pub use provider::{
     PruneShardOutcome, PrunedIndices, RocksDBBatch, RocksDBBuilder, RocksDBIter, RocksDBProvider,
     RocksDBRawIter, RocksDBStats, RocksDBTableStats, RocksTx,
 };
+pub(crate) use state::PendingRocksDBState;
+pub use state::{RocksDBStateCursor, RocksDBStateSnapshot, STATE_COMMIT_METADATA_KEY};
@@ -1,4 +1,7 @@
-use super::metrics::{RocksDBMetrics, RocksDBOperation, ROCKSDB_TABLES};
+use super::{
+    metrics::{RocksDBMetrics, RocksDBOperation, ROCKSDB_TABLES},
+    state::PendingRocksDBState,
+};
 use crate::providers::{compute_history_rank, needs_prev_shard_check, HistoryInfo};
 use alloy_consensus::transaction::TxHashRef;
 use alloy_primitives::{
@@ -10,6 +13,7 @@ use itertools::Itertools;
 use metrics::Label;
 use parking_lot::Mutex;
 use hanzo_evm_chain_state::ExecutedBlock;
+use hanzo_evm_db::lockfile::StorageLock;
 use hanzo_evm_db_api::{
     database_metrics::DatabaseMetrics,
     models::{
@@ -26,10 +30,10 @@ use reth_storage_errors::{
     provider::{ProviderError, ProviderResult},
 };
 use rocksdb::{
-    BlockBasedOptions, Cache, ColumnFamilyDescriptor, CompactionPri, DBCompressionType,
-    DBRawIteratorWithThreadMode, IteratorMode, OptimisticTransactionDB,
-    OptimisticTransactionOptions, Options, Transaction, WriteBatchWithTransaction, WriteOptions,
-    DB,
+    checkpoint::Checkpoint, BlockBasedOptions, Cache, ColumnFamilyDescriptor, CompactionPri,
+    DBCompressionType, DBRawIteratorWithThreadMode, IteratorMode, OptimisticTransactionDB,
+    OptimisticTransactionOptions, Options, SnapshotWithThreadMode, Transaction,
+    WriteBatchWithTransaction, WriteOptions, DB,
 };
 use std::{
     collections::BTreeMap,
@@ -148,6 +152,7 @@ pub struct RocksDBBuilder {
     log_level: rocksdb::LogLevel,
     block_cache: Cache,
     read_only: bool,
+    secondary_path: Option<PathBuf>,
 }
 
 impl fmt::Debug for RocksDBBuilder {
@@ -156,6 +161,7 @@ impl fmt::Debug for RocksDBBuilder {
             .field("path", &self.path)
             .field("column_families", &self.column_families)
             .field("enable_metrics", &self.enable_metrics)
+            .field("secondary_path", &self.secondary_path)
             .finish()
     }
 }
@@ -172,6 +178,7 @@ impl RocksDBBuilder {
             log_level: rocksdb::LogLevel::Info,
             block_cache: cache,
             read_only: false,
+            secondary_path: None,
         }
     }
 
@@ -280,10 +287,23 @@ impl RocksDBBuilder {
     /// - [`tables::TransactionHashNumbers`] - Transaction hash to number mapping
     /// - [`tables::AccountsHistory`] - Account history index
     /// - [`tables::StoragesHistory`] - Storage history index
+    /// - [`tables::PlainAccountState`], [`tables::PlainStorageState`] - Plain state
+    /// - [`tables::HashedAccounts`], [`tables::HashedStorages`] - Hashed state
+    /// - [`tables::AccountsTrie`], [`tables::StoragesTrie`] - State trie
+    ///
+    /// State tables are only written to if
+    /// [`StorageSettings::state_in_rocksdb`](hanzo_evm_db_api::models::StorageSettings::state_in_rocksdb)
+    /// is set.
     pub fn with_default_tables(self) -> Self {
         self.with_table::<tables::TransactionHashNumbers>()
             .with_table::<tables::AccountsHistory>()
             .with_table::<tables::StoragesHistory>()
+            .with_table::<tables::PlainAccountState>()
+            .with_table::<tables::PlainStorageState>()
+            .with_table::<tables::HashedAccounts>()
+            .with_table::<tables::HashedStorages>()
+            .with_table::<tables::AccountsTrie>()
+            .with_table::<tables::StoragesTrie>()
     }
 
     /// Enables metrics.
@@ -320,14 +340,41 @@ impl RocksDBBuilder {
         self
     }
 
+    /// Opens the database as a secondary instance of a primary that is running in another process.
+    ///
+    /// A secondary instance is read-only and keeps its own info logs at `secondary_path`, which
+    /// is locked by the provider and removed once it is dropped. Unlike a read-only instance, it
+    /// can pick up new writes of the primary with [`RocksDBProvider::try_catch_up_with_primary`].
+    ///
+    /// Note: Write operations on a secondary provider will panic at runtime.
+    pub fn with_secondary(mut self, secondary_path: impl AsRef<Path>) -> Self {
+        self.read_only = true;
+        self.secondary_path = Some(secondary_path.as_ref().to_path_buf());
+        self
+    }
+
     /// Builds the [`RocksDBProvider`].
     pub fn build(self) -> ProviderResult<RocksDBProvider> {
-        let options =
+        let mut options =
             Self::default_options(self.log_level, &self.block_cache, self.enable_statistics);
+        if self.secondary_path.is_some() {
+            // Secondary instances must keep all files open, as the primary may delete them at any
+            // time after compaction.
+            options.set_max_open_files(-1);
+        }
+
+        // A read-only database can't create column families, so only open the ones that exist.
+        // This keeps databases created before a table was registered readable.
+        let existing_column_families = if self.read_only {
+            Some(DB::list_cf(&options, &self.path).unwrap_or_default())
+        } else {
+            None
+        };
 
         let cf_descriptors: Vec<ColumnFamilyDescriptor> = self
             .column_families
             .iter()
+            .filter(|name| existing_column_families.as_ref().is_none_or(|cfs| cfs.contains(*name)))
             .map(|name| {
                 let cf_options = if name == tables::TransactionHashNumbers::NAME {
                     Self::tx_hash_numbers_column_family_options(&self.block_cache)
@@ -341,14 +388,31 @@ impl RocksDBBuilder {
         let metrics = self.enable_metrics.then(RocksDBMetrics::default);
 
         if self.read_only {
-            let db = DB::open_cf_descriptors_read_only(&options, &self.path, cf_descriptors, false)
-                .map_err(|e| {
-                    ProviderError::Database(DatabaseError::Open(DatabaseErrorInfo {
-                        message: e.to_string().into(),
-                        code: -1,
-                    }))
-                })?;
-            Ok(RocksDBProvider(Arc::new(RocksDBProviderInner::ReadOnly { db, metrics })))
+            let secondary = self
+                .secondary_path
+                .map(|path| {
+                    let lock = StorageLock::try_acquire(&path).map_err(ProviderError::other)?;
+                    Ok::<_, ProviderError>(SecondaryDir { path, _lock: lock })
+                })
+                .transpose()?;
+            let db = match &secondary {
+                Some(secondary) => DB::open_cf_descriptors_as_secondary(
+                    &options,
+                    &self.path,
+                    &secondary.path,
+                    cf_descriptors,
+                ),
+                None => {
+                    DB::open_cf_descriptors_read_only(&options, &self.path, cf_descriptors, false)
+                }
+            }
+            .map_err(|e| {
+                ProviderError::Database(DatabaseError::Open(DatabaseErrorInfo {
+                    message: e.to_string().into(),
+                    code: -1,
+                }))
+            })?;
+            Ok(RocksDBProvider(Arc::new(RocksDBProviderInner::ReadOnly { db, metrics, secondary })))
         } else {
             // Use OptimisticTransactionDB for MDBX-like transaction semantics (read-your-writes,
             // rollback) OptimisticTransactionDB uses optimistic concurrency control (conflict
@@ -362,7 +426,11 @@ impl RocksDBBuilder {
                             code: -1,
                         }))
                     })?;
-            Ok(RocksDBProvider(Arc::new(RocksDBProviderInner::ReadWrite { db, metrics })))
+            Ok(RocksDBProvider(Arc::new(RocksDBProviderInner::ReadWrite {
+                db,
+                metrics,
+                applying_state: Default::default(),
+            })))
         }
     }
 }
@@ -393,17 +461,38 @@ enum RocksDBProviderInner {
         db: OptimisticTransactionDB,
         /// Metrics latency & operations.
         metrics: Option<RocksDBMetrics>,
+        /// State commits whose database transaction is committed but which aren't applied to the
+        /// state tables yet, keyed by commit number.
+        applying_state: Mutex<BTreeMap<u64, PendingRocksDBState>>,
     },
-    /// Read-only mode using `DB` opened with `open_cf_descriptors_read_only`.
+    /// Read-only mode using `DB` opened with `open_cf_descriptors_read_only` or, for secondary
+    /// instances, `open_cf_descriptors_as_secondary`.
     /// This doesn't acquire an exclusive lock, allowing concurrent reads.
     ReadOnly {
         /// Read-only `RocksDB` database instance.
         db: DB,
         /// Metrics latency & operations.
         metrics: Option<RocksDBMetrics>,
+        /// The directory of a secondary instance, removed after the database is closed.
+        secondary: Option<SecondaryDir>,
     },
 }
 
+/// The locked directory a secondary `RocksDB` instance keeps its info logs in.
+#[derive(Debug)]
+struct SecondaryDir {
+    path: PathBuf,
+    _lock: StorageLock,
+}
+
+impl Drop for SecondaryDir {
+    fn drop(&mut self) {
+        if let Err(err) = hanzo_evm_fs_util::remove_dir_all(&self.path) {
+            tracing::warn!(target: "providers::rocksdb", path = ?self.path, %err, "Failed to remove secondary directory");
+        }
+    }
+}
+
 impl RocksDBProviderInner {
     /// Returns the metrics for this provider.
     const fn metrics(&self) -> Option<&RocksDBMetrics> {
@@ -505,6 +594,38 @@ impl RocksDBProviderInner {
         }
     }
 
+    /// Gets a value from the default column family.
+    fn get(&self, key: impl AsRef<[u8]>) -> Result<Option<Vec<u8>>, rocksdb::Error> {
+        match self {
+            Self::ReadWrite { db, .. } => db.get(key),
+            Self::ReadOnly { db, .. } => db.get(key),
+        }
+    }
+
+    /// Returns a raw iterator over the default column family.
+    fn raw_iterator(&self) -> RocksDBRawIterEnum<'_> {
+        match self {
+            Self::ReadWrite { db, .. } => RocksDBRawIterEnum::ReadWrite(db.raw_iterator()),
+            Self::ReadOnly { db, .. } => RocksDBRawIterEnum::ReadOnly(db.raw_iterator()),
+        }
+    }
+
+    /// Takes a snapshot of the database.
+    fn snapshot(&self) -> RocksDBSnapshotEnum<'_> {
+        match self {
+            Self::ReadWrite { db, .. } => RocksDBSnapshotEnum::ReadWrite(db.snapshot()),
+            Self::ReadOnly { db, .. } => RocksDBSnapshotEnum::ReadOnly(db.snapshot()),
+        }
+    }
+
+    /// Creates a checkpoint of the database at `dest`.
+    fn create_checkpoint(&self, dest: &Path) -> Result<(), rocksdb::Error> {
+        match self {
+            Self::ReadWrite { db, .. } => Checkpoint::new(db)?.create_checkpoint(dest),
+            Self::ReadOnly { db, .. } => Checkpoint::new(db)?.create_checkpoint(dest),
+        }
+    }
+
     /// Returns the path to the database directory.
     fn path(&self) -> &Path {
         match self {
@@ -704,6 +825,66 @@ impl RocksDBProvider {
         matches!(self.0.as_ref(), RocksDBProviderInner::ReadOnly { .. })
     }
 
+    /// Returns `true` if this provider was opened as a secondary instance.
+    pub fn is_secondary(&self) -> bool {
+        matches!(self.0.as_ref(), RocksDBProviderInner::ReadOnly { secondary: Some(_), .. })
+    }
+
+    /// Makes the writes the primary instance committed so far visible to this secondary instance.
+    ///
+    /// This is a no-op for providers that are not secondary instances.
+    pub fn try_catch_up_with_primary(&self) -> ProviderResult<()> {
+        let RocksDBProviderInner::ReadOnly { db, secondary: Some(_), .. } = self.0.as_ref() else {
+            return Ok(())
+        };
+        db.try_catch_up_with_primary().map_err(|e| {
+            ProviderError::Database(DatabaseError::Read(DatabaseErrorInfo {
+                message: e.to_string().into(),
+                code: -1,
+            }))
+        })
+    }
+
+    /// Publishes a pending state with a commit number until it's applied, so providers opened
+    /// after its database transaction is committed can read it, see [`Self::applying_state`].
+    ///
+    /// Must be called before the database transaction is committed. No-op for read-only
+    /// providers.
+    pub(crate) fn begin_applying_state(&self, state: &PendingRocksDBState) {
+        let RocksDBProviderInner::ReadWrite { applying_state, .. } = self.0.as_ref() else {
+            return
+        };
+        if let Some(commit) = state.lock().commit {
+            applying_state.lock().insert(commit, state.clone());
+        }
+    }
+
+    /// Stops publishing a pending state after it was applied.
+    ///
+    /// A state whose database transaction failed to commit may stay published, it's replaced by
+    /// the next provider allocating the same commit number.
+    pub(crate) fn end_applying_state(&self, state: &PendingRocksDBState) {
+        let RocksDBProviderInner::ReadWrite { applying_state, .. } = self.0.as_ref() else {
+            return
+        };
+        if let Some(commit) = state.lock().commit {
+            applying_state.lock().remove(&commit);
+        }
+    }
+
+    /// Returns the state of commit `commit` if its database transaction is committed but it's
+    /// not applied to the state tables yet.
+    ///
+    /// Always `None` for read-only providers, which don't see the commits of other processes.
+    pub(crate) fn applying_state(&self, commit: u64) -> Option<PendingRocksDBState> {
+        match self.0.as_ref() {
+            RocksDBProviderInner::ReadWrite { applying_state, .. } => {
+                applying_state.lock().get(&commit).cloned()
+            }
+            RocksDBProviderInner::ReadOnly { .. } => None,
+        }
+    }
+
     /// Creates a new transaction with MDBX-like semantics (read-your-writes, rollback).
     ///
     /// Note: With `OptimisticTransactionDB`, commits may fail if there are conflicts.
@@ -847,9 +1028,17 @@ impl RocksDBProvider {
     ///
     /// Uses `delete_range_cf` from empty key to a max key (256 bytes of 0xFF).
     /// This end key must exceed the maximum encoded key size for any table.
-    /// Current max is ~60 bytes (`StorageShardedKey` = 20 + 32 + 8).
+    /// Current max is 97 bytes (`StoragesTrie` = 32 + 65 for the nibbles subkey).
     pub fn clear<T: Table>(&self) -> ProviderResult<()> {
-        let cf = self.get_cf_handle::<T>()?;
+        self.get_cf_handle::<T>()?;
+        self.clear_cf(T::NAME)
+    }
+
+    /// Clears all entries from the column family with the given name.
+    ///
+    /// See [`Self::clear`].
+    pub(super) fn clear_cf(&self, name: &str) -> ProviderResult<()> {
+        let cf = self.0.cf_handle_rw(name)?;
 
         self.0.delete_range_cf(cf, &[] as &[u8], &[0xFF; 256]).map_err(|e| {
             ProviderError::Database(DatabaseError::Delete(DatabaseErrorInfo {
@@ -910,6 +1099,17 @@ impl RocksDBProvider {
         Ok(RocksDBIter { inner: iter, _marker: std::marker::PhantomData })
     }
 
+    /// Creates an iterator over the entries of the specified table, starting from the given key
+    /// (inclusive).
+    pub fn iter_from<T: Table>(&self, key: T::Key) -> ProviderResult<RocksDBIter<'_, T>> {
+        let cf = self.get_cf_handle::<T>()?;
+        let encoded_key = key.encode();
+        let iter = self
+            .0
+            .iterator_cf(cf, IteratorMode::From(encoded_key.as_ref(), rocksdb::Direction::Forward));
+        Ok(RocksDBIter { inner: iter, _marker: std::marker::PhantomData })
+    }
+
     /// Returns statistics for all column families in the database.
     ///
     /// Returns a vector of (`table_name`, `estimated_keys`, `estimated_size_bytes`) tuples.
@@ -972,6 +1172,21 @@ impl RocksDBProvider {
         Ok(())
     }
 
+    /// Creates a consistent checkpoint of all tables in `dest`.
+    ///
+    /// SST files are hard-linked when `dest` is on the same filesystem as the database and
+    /// copied otherwise. `dest` must not exist yet. The checkpoint can be opened as a regular
+    /// `RocksDB` database.
+    #[instrument(level = "debug", target = "providers::rocksdb", skip(self))]
+    pub fn create_checkpoint(&self, dest: &Path) -> ProviderResult<()> {
+        self.0.create_checkpoint(dest).map_err(|e| {
+            ProviderError::Database(DatabaseError::Other(format!(
+                "failed to create RocksDB checkpoint at {}: {e}",
+                dest.display()
+            )))
+        })
+    }
+
     /// Flushes and compacts all tables in `RocksDB`.
     ///
     /// This:
@@ -1007,6 +1222,42 @@ impl RocksDBProvider {
         Ok(RocksDBRawIter { inner: iter })
     }
 
+    /// Gets a value from the default column family, which holds `RocksDB` metadata rather than
+    /// table entries.
+    pub(super) fn get_metadata(&self, key: impl AsRef<[u8]>) -> ProviderResult<Option<Vec<u8>>> {
+        self.0.get(key).map_err(|e| {
+            ProviderError::Database(DatabaseError::Read(DatabaseErrorInfo {
+                message: e.to_string().into(),
+                code: -1,
+            }))
+        })
+    }
+
+    /// Creates a raw iterator over the default column family.
+    pub(super) fn raw_metadata_iterator(&self) -> RocksDBRawIterEnum<'_> {
+        self.0.raw_iterator()
+    }
+
+    /// Takes a snapshot of the database.
+    pub(super) fn snapshot(&self) -> RocksDBSnapshotEnum<'_> {
+        self.0.snapshot()
+    }
+
+    /// Gets the column family handle for a table.
+    pub(super) fn table_cf_handle<T: Table>(
+        &self,
+    ) -> Result<&rocksdb::ColumnFamily, DatabaseError> {
+        self.get_cf_handle::<T>()
+    }
+
+    /// Gets the column family handle for a table from the read-write database.
+    ///
+    /// # Panics
+    /// Panics if the provider is in read-only mode.
+    pub(super) fn cf_handle_rw(&self, name: &str) -> Result<&rocksdb::ColumnFamily, DatabaseError> {
+        self.0.cf_handle_rw(name)
+    }
+
     /// Returns all account history shards for the given address in ascending key order.
     ///
     /// This is used for unwind operations where we need to scan all shards for an address
@@ -2434,11 +2685,39 @@ impl Iterator for RocksDBIterEnum<'_> {
     }
 }
 
+/// Snapshot of either a read-write or a read-only database.
+pub(super) enum RocksDBSnapshotEnum<'db> {
+    /// Snapshot of a read-write `OptimisticTransactionDB`.
+    ReadWrite(SnapshotWithThreadMode<'db, OptimisticTransactionDB>),
+    /// Snapshot of a read-only `DB`.
+    ReadOnly(SnapshotWithThreadMode<'db, DB>),
+}
+
+impl RocksDBSnapshotEnum<'_> {
+    /// Gets a value from the default column family as of the snapshot.
+    pub(super) fn get(&self, key: impl AsRef<[u8]>) -> Result<Option<Vec<u8>>, rocksdb::Error> {
+        match self {
+            Self::ReadWrite(snapshot) => snapshot.get(key),
+            Self::ReadOnly(snapshot) => snapshot.get(key),
+        }
+    }
+
+    /// Returns a raw iterator over a column family as of the snapshot.
+    pub(super) fn raw_iterator_cf(&self, cf: &rocksdb::ColumnFamily) -> RocksDBRawIterEnum<'_> {
+        match self {
+            Self::ReadWrite(snapshot) => {
+                RocksDBRawIterEnum::ReadWrite(snapshot.raw_iterator_cf(cf))
+            }
+            Self::ReadOnly(snapshot) => RocksDBRawIterEnum::ReadOnly(snapshot.raw_iterator_cf(cf)),
+        }
+    }
+}
+
 /// Wrapper enum for raw `RocksDB` iterators that works in both read-write and read-only modes.
 ///
 /// Unlike [`RocksDBIterEnum`], raw iterators expose `seek()` for efficient repositioning
 /// without reinitializing the iterator.
-enum RocksDBRawIterEnum<'db> {
+pub(super) enum RocksDBRawIterEnum<'db> {
     /// Raw iterator from read-write `OptimisticTransactionDB`.
     ReadWrite(DBRawIteratorWithThreadMode<'db, OptimisticTransactionDB>),
     /// Raw iterator from read-only `DB`.
@@ -2447,15 +2726,39 @@ enum RocksDBRawIterEnum<'db> {
 
 impl RocksDBRawIterEnum<'_> {
     /// Positions the iterator at the first key >= `key`.
-    fn seek(&mut self, key: impl AsRef<[u8]>) {
+    pub(super) fn seek(&mut self, key: impl AsRef<[u8]>) {
         match self {
             Self::ReadWrite(iter) => iter.seek(key),
             Self::ReadOnly(iter) => iter.seek(key),
         }
     }
 
+    /// Positions the iterator at the last key <= `key`.
+    pub(super) fn seek_for_prev(&mut self, key: impl AsRef<[u8]>) {
+        match self {
+            Self::ReadWrite(iter) => iter.seek_for_prev(key),
+            Self::ReadOnly(iter) => iter.seek_for_prev(key),
+        }
+    }
+
+    /// Positions the iterator at the first key.
+    pub(super) fn seek_to_first(&mut self) {
+        match self {
+            Self::ReadWrite(iter) => iter.seek_to_first(),
+            Self::ReadOnly(iter) => iter.seek_to_first(),
+        }
+    }
+
+    /// Positions the iterator at the last key.
+    pub(super) fn seek_to_last(&mut self) {
+        match self {
+            Self::ReadWrite(iter) => iter.seek_to_last(),
+            Self::ReadOnly(iter) => iter.seek_to_last(),
+        }
+    }
+
     /// Returns true if the iterator is positioned at a valid key-value pair.
-    fn valid(&self) -> bool {
+    pub(super) fn valid(&self) -> bool {
         match self {
             Self::ReadWrite(iter) => iter.valid(),
             Self::ReadOnly(iter) => iter.valid(),
@@ -2463,7 +2766,7 @@ impl RocksDBRawIterEnum<'_> {
     }
 
     /// Returns the current key, if valid.
-    fn key(&self) -> Option<&[u8]> {
+    pub(super) fn key(&self) -> Option<&[u8]> {
         match self {
             Self::ReadWrite(iter) => iter.key(),
             Self::ReadOnly(iter) => iter.key(),
@@ -2471,7 +2774,7 @@ impl RocksDBRawIterEnum<'_> {
     }
 
     /// Returns the current value, if valid.
-    fn value(&self) -> Option<&[u8]> {
+    pub(super) fn value(&self) -> Option<&[u8]> {
         match self {
             Self::ReadWrite(iter) => iter.value(),
             Self::ReadOnly(iter) => iter.value(),
@@ -2479,15 +2782,23 @@ impl RocksDBRawIterEnum<'_> {
     }
 
     /// Advances the iterator to the next key.
-    fn next(&mut self) {
+    pub(super) fn next(&mut self) {
         match self {
             Self::ReadWrite(iter) => iter.next(),
             Self::ReadOnly(iter) => iter.next(),
         }
     }
 
+    /// Moves the iterator to the previous key.
+    pub(super) fn prev(&mut self) {
+        match self {
+            Self::ReadWrite(iter) => iter.prev(),
+            Self::ReadOnly(iter) => iter.prev(),
+        }
+    }
+
     /// Returns the status of the iterator.
-    fn status(&self) -> Result<(), rocksdb::Error> {
+    pub(super) fn status(&self) -> Result<(), rocksdb::Error> {
         match self {
             Self::ReadWrite(iter) => iter.status(),
             Self::ReadOnly(iter) => iter.status(),
@@ -2636,6 +2947,16 @@ mod tests {
         let key = StorageShardedKey::new(Address::ZERO, B256::ZERO, 100);
         provider.put::<tables::StoragesHistory>(key.clone(), &value).unwrap();
         assert!(provider.get::<tables::StoragesHistory>(key).unwrap().is_some());
+
+        // Should be able to write/read state tables
+        let account = reth_primitives_traits::Account { nonce: 1, ..Default::default() };
+        provider.put::<tables::PlainAccountState>(Address::ZERO, &account).unwrap();
+        assert_eq!(
+            provider.get::<tables::PlainAccountState>(Address::ZERO).unwrap(),
+            Some(account)
+        );
+        provider.put::<tables::HashedAccounts>(B256::ZERO, &account).unwrap();
+        assert_eq!(provider.get::<tables::HashedAccounts>(B256::ZERO).unwrap(), Some(account));
     }
 
     #[derive(Debug)]
@@ -2786,6 +3107,46 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_secondary_catches_up_with_primary() {
+        let temp_dir = TempDir::new().unwrap();
+        let secondary_dir = TempDir::new().unwrap();
+        let primary =
+            RocksDBBuilder::new(temp_dir.path()).with_table::<TestTable>().build().unwrap();
+        primary.put::<TestTable>(1, &vec![1]).unwrap();
+        primary.flush(&[TestTable::NAME]).unwrap();
+
+        let secondary = RocksDBBuilder::new(temp_dir.path())
+            .with_table::<TestTable>()
+            .with_secondary(secondary_dir.path())
+            .build()
+            .unwrap();
+        assert!(secondary.is_read_only());
+        assert!(secondary.is_secondary());
+        assert_eq!(secondary.get::<TestTable>(1).unwrap(), Some(vec![1]));
+
+        // New writes of the primary are only visible after catching up
+        primary.put::<TestTable>(2, &vec![2]).unwrap();
+        assert_eq!(secondary.get::<TestTable>(2).unwrap(), None);
+        secondary.try_catch_up_with_primary().unwrap();
+        assert_eq!(secondary.get::<TestTable>(2).unwrap(), Some(vec![2]));
+
+        // Catching up is a no-op for other providers
+        assert!(!primary.is_secondary());
+        primary.try_catch_up_with_primary().unwrap();
+
+        // The secondary directory is locked while the secondary is open and removed afterwards
+        let secondary_path = secondary_dir.path().join("secondary");
+        let secondary = RocksDBBuilder::new(temp_dir.path())
+            .with_table::<TestTable>()
+            .with_secondary(&secondary_path)
+            .build()
+            .unwrap();
+        assert!(secondary_path.join("lock").exists());
+        drop(secondary);
+        assert!(!secondary_path.exists());
+    }
+
     #[test]
     fn test_transaction_read_your_writes() {
         let temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,1008 @@
+//! Cursor over state tables stored in `RocksDB`.
+//!
+//! State writes of a provider are buffered in [`PendingRocksDBState`] and written to `RocksDB` in a
+//! single batch when the provider is committed. [`RocksDBStateCursor`] reads the committed entries
+//! through a [`RocksDBStateSnapshot`] taken when the provider was created and merges them with the
+//! pending writes, so reads within a provider see a fixed state plus its own writes.
+//!
+//! # Crash safety
+//!
+//! Every provider that writes state allocates the next state commit number and records it in the
+//! database [`tables::Metadata`] table under [`STATE_COMMIT_METADATA_KEY`], in the same transaction
+//! as its stage checkpoints. On commit:
+//! 1. The pending writes are journaled to the default column family of `RocksDB`.
+//! 2. The database transaction is committed.
+//! 3. The pending writes are applied to the state tables, together with a marker holding the commit
+//!    number, and the journal is removed.
+//!
+//! On startup, [`RocksDBProvider::heal_state_commit`] compares the marker to the commit number of
+//! the database. A crash between steps 2 and 3 is healed by replaying the journal, a crash before
+//! step 2 leaves a journal that is discarded, and any other mismatch refuses to start.
+
+use super::{
+    provider::{RocksDBRawIterEnum, RocksDBSnapshotEnum},
+    RocksDBProvider,
+};
+use crate::either_cursor::RocksDBStateTable;
+use alloy_primitives::map::HashMap;
+use hanzo_evm_db_api::{
+    common::{PairResult, ValueOnlyResult},
+    cursor::{
+        DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW, DupWalker, RangeWalker,
+        ReverseWalker, Walker,
+    },
+    table::{Compress, Decode, Decompress, DupSort, Encode, Table},
+    tables, DatabaseError,
+};
+use hanzo_evm_storage_errors::{
+    db::{DatabaseErrorInfo, DatabaseWriteError, DatabaseWriteOperation},
+    provider::{ProviderError, ProviderResult},
+};
+use parking_lot::Mutex;
+use rocksdb::WriteBatchWithTransaction;
+use std::{
+    collections::BTreeMap,
+    fmt,
+    marker::PhantomData,
+    ops::{Bound, RangeBounds},
+    sync::Arc,
+};
+use tracing::{info, warn};
+
+/// Key of the number of the last state commit in the database [`tables::Metadata`] table.
+pub const STATE_COMMIT_METADATA_KEY: &str = "rocksdb_state_commit";
+
+/// Key of the number of the last state commit applied to `RocksDB`, in the default column family.
+const STATE_COMMIT_KEY: &[u8] = b"state_commit";
+
+/// Prefix of the journal keys in the default column family.
+///
+/// The journal of commit `n` consists of a header at `prefix | n` and one entry per cleared table
+/// and written row at `prefix | n | table | op | raw key`.
+const STATE_JOURNAL_PREFIX: &[u8] = b"state_journal/";
+
+/// Journal operation clearing a table.
+const JOURNAL_CLEAR: u8 = 0;
+
+/// Journal operation writing or deleting a row.
+const JOURNAL_WRITE: u8 = 1;
+
+/// State tables stored in `RocksDB`, indexed by their journal table tag.
+const STATE_TABLES: [&str; 6] = [
+    tables::PlainAccountState::NAME,
+    tables::PlainStorageState::NAME,
+    tables::HashedAccounts::NAME,
+    tables::HashedStorages::NAME,
+    tables::AccountsTrie::NAME,
+    tables::StoragesTrie::NAME,
+];
+
+/// Uncommitted writes to `RocksDB` state tables of a provider.
+pub(crate) type PendingRocksDBState = Arc<Mutex<PendingState>>;
+
+/// Uncommitted writes to `RocksDB` state tables.
+#[derive(Debug, Default)]
+pub(crate) struct PendingState {
+    /// Number of the state commit the writes belong to, allocated with the first write.
+    pub(crate) commit: Option<u64>,
+    /// Writes keyed by table name.
+    tables: HashMap<&'static str, PendingStateTable>,
+}
+
+impl PendingState {
+    /// Returns the writes to a table, creating them if missing.
+    pub(crate) fn table_mut(&mut self, name: &'static str) -> &mut PendingStateTable {
+        self.tables.entry(name).or_default()
+    }
+}
+
+/// Uncommitted writes to a single `RocksDB` state table.
+#[derive(Debug, Default)]
+pub(crate) struct PendingStateTable {
+    /// Whether the table was cleared. Committed entries are hidden if set.
+    cleared: bool,
+    /// Raw keys to compressed values. `None` marks a deleted entry.
+    entries: BTreeMap<Vec<u8>, Option<Vec<u8>>>,
+}
+
+impl PendingStateTable {
+    /// Marks the table as cleared and drops all pending entries.
+    pub(crate) fn clear(&mut self) {
+        self.cleared = true;
+        self.entries.clear();
+    }
+}
+
+impl RocksDBProvider {
+    /// Returns the number of the last state commit applied to `RocksDB`, `0` if there was none.
+    pub fn state_commit(&self) -> ProviderResult<u64> {
+        Ok(self.get_metadata(STATE_COMMIT_KEY)?.map_or(0, |value| decode_commit(&value)))
+    }
+
+    /// Journals the pending state, so it can be applied after a crash once the database
+    /// transaction recording its commit number is committed.
+    ///
+    /// Replaces the journal left over by a failed commit with the same number, if any. No-op if
+    /// the pending state has no commit number.
+    ///
+    /// # Panics
+    /// Panics if the provider is in read-only mode.
+    pub(crate) fn journal_pending_state(&self, pending: &PendingState) -> ProviderResult<()> {
+        let Some(commit) = pending.commit else { return Ok(()) };
+
+        let mut batch = WriteBatchWithTransaction::<true>::default();
+        for key in self.state_journal_keys(commit)? {
+            batch.delete(key);
+        }
+        batch.put(journal_key(commit, None), b"");
+        for (&table, PendingStateTable { cleared, entries }) in &pending.tables {
+            let tag = table_tag(table)?;
+            if *cleared {
+                batch.put(journal_key(commit, Some((tag, JOURNAL_CLEAR, &[]))), b"");
+            }
+            for (key, value) in entries {
+                let mut journaled = Vec::with_capacity(1 + value.as_ref().map_or(0, Vec::len));
+                match value {
+                    Some(value) => {
+                        journaled.push(1);
+                        journaled.extend_from_slice(value);
+                    }
+                    None => journaled.push(0),
+                }
+                batch.put(journal_key(commit, Some((tag, JOURNAL_WRITE, key))), journaled);
+            }
+        }
+
+        self.commit_batch(batch)
+    }
+
+    /// Writes the pending state to `RocksDB` and marks its commit as applied.
+    ///
+    /// Must be called after the database transaction recording the commit number is committed.
+    /// Cleared tables are cleared first, then all pending entries, the marker and the removal of
+    /// the journal are written in a single batch. Applying the same state twice has no effect,
+    /// so an interrupted call is completed by replaying the journal.
+    ///
+    /// No-op if the pending state has no commit number.
+    ///
+    /// # Panics
+    /// Panics if the provider is in read-only mode.
+    pub(crate) fn apply_pending_state(&self, pending: &PendingState) -> ProviderResult<()> {
+        let Some(commit) = pending.commit else { return Ok(()) };
+
+        for (&table, PendingStateTable { cleared, .. }) in &pending.tables {
+            if *cleared {
+                self.clear_cf(table)?;
+            }
+        }
+
+        let mut batch = WriteBatchWithTransaction::<true>::default();
+        for (&table, PendingStateTable { cleared, entries }) in &pending.tables {
+            let tag = table_tag(table)?;
+            if *cleared {
+                batch.delete(journal_key(commit, Some((tag, JOURNAL_CLEAR, &[]))));
+            }
+
+            let cf = self.cf_handle_rw(table)?;
+            for (key, value) in entries {
+                batch.delete(journal_key(commit, Some((tag, JOURNAL_WRITE, key))));
+                match value {
+                    Some(value) => batch.put_cf(cf, key, value),
+                    None => batch.delete_cf(cf, key),
+                }
+            }
+        }
+        batch.put(STATE_COMMIT_KEY, commit.to_be_bytes());
+        batch.delete(journal_key(commit, None));
+
+        self.commit_batch(batch)
+    }
+
+    /// Brings the `RocksDB` state to `database_commit`, the number of the last state commit
+    /// recorded in the database together with the stage checkpoints.
+    ///
+    /// If the journal of `database_commit` is left over from a crash before it was applied, it's
+    /// replayed. Journals of commits the database never recorded are removed. Any other mismatch
+    /// means the `RocksDB` state doesn't belong to the database and returns an error.
+    ///
+    /// # Panics
+    /// Panics if the provider is in read-only mode.
+    pub fn heal_state_commit(&self, database_commit: u64) -> ProviderResult<()> {
+        let applied = self.state_commit()?;
+        let mut journals = self.read_state_journals()?;
+
+        if applied != database_commit {
+            let replayable = applied + 1 == database_commit;
+            let Some(pending) = journals.remove(&database_commit).filter(|_| replayable) else {
+                return Err(ProviderError::Database(DatabaseError::Other(format!(
+                    "RocksDB state is at commit {applied}, the database checkpoints are at state \
+                     commit {database_commit}. The RocksDB state doesn't belong to this \
+                     database, restore both from the same snapshot or resync"
+                ))))
+            };
+
+            info!(
+                target: "evm::providers::rocksdb",
+                commit = database_commit,
+                "Replaying the journal of an interrupted RocksDB state commit"
+            );
+            self.apply_pending_state(&pending)?;
+        }
+
+        if !journals.is_empty() {
+            warn!(
+                target: "evm::providers::rocksdb",
+                commits = ?journals.keys().collect::<Vec<_>>(),
+                "Removing journals of RocksDB state commits that never reached the database"
+            );
+            let mut batch = WriteBatchWithTransaction::<true>::default();
+            for commit in journals.into_keys() {
+                for key in self.state_journal_keys(commit)? {
+                    batch.delete(key);
+                }
+            }
+            self.commit_batch(batch)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the keys of the journal of commit `commit`.
+    fn state_journal_keys(&self, commit: u64) -> ProviderResult<Vec<Vec<u8>>> {
+        let prefix = journal_key(commit, None);
+        let mut keys = Vec::new();
+
+        let mut iter = self.raw_metadata_iterator();
+        iter.seek(&prefix);
+        while let Some((key, _)) = row(&iter)?.filter(|(key, _)| key.starts_with(&prefix)) {
+            keys.push(key);
+            iter.next();
+        }
+
+        Ok(keys)
+    }
+
+    /// Reads all state journals, keyed by commit number.
+    fn read_state_journals(&self) -> ProviderResult<BTreeMap<u64, PendingState>> {
+        let mut journals = BTreeMap::<u64, PendingState>::new();
+
+        let mut iter = self.raw_metadata_iterator();
+        iter.seek(STATE_JOURNAL_PREFIX);
+        while let Some((key, value)) = row(&iter)? {
+            let Some(key) = key.strip_prefix(STATE_JOURNAL_PREFIX) else { break };
+            let corrupt = || {
+                ProviderError::Database(DatabaseError::Other(format!(
+                    "Corrupt RocksDB state journal entry {key:?}"
+                )))
+            };
+
+            let (commit, entry) = key.split_first_chunk::<8>().ok_or_else(corrupt)?;
+            let pending = journals.entry(u64::from_be_bytes(*commit)).or_insert_with(|| {
+                PendingState { commit: Some(u64::from_be_bytes(*commit)), ..Default::default() }
+            });
+            if let [tag, op, raw_key @ ..] = entry {
+                let name = *STATE_TABLES.get(*tag as usize).ok_or_else(corrupt)?;
+                let table = pending.table_mut(name);
+                match (*op, value.split_first()) {
+                    (JOURNAL_CLEAR, _) => table.clear(),
+                    (JOURNAL_WRITE, Some((0, []))) => {
+                        table.entries.insert(raw_key.to_vec(), None);
+                    }
+                    (JOURNAL_WRITE, Some((1, value))) => {
+                        table.entries.insert(raw_key.to_vec(), Some(value.to_vec()));
+                    }
+                    _ => return Err(corrupt()),
+                }
+            } else if !entry.is_empty() {
+                return Err(corrupt())
+            }
+
+            iter.next();
+        }
+
+        Ok(journals)
+    }
+}
+
+/// Returns the journal table tag of a state table.
+fn table_tag(table: &str) -> Result<u8, DatabaseError> {
+    STATE_TABLES.iter().position(|name| *name == table).map(|tag| tag as u8).ok_or_else(|| {
+        DatabaseError::Other(format!("Table '{table}' is not a RocksDB state table"))
+    })
+}
+
+/// Returns the journal key of commit `commit`, or of an entry of it if `entry` is set.
+fn journal_key(commit: u64, entry: Option<(u8, u8, &[u8])>) -> Vec<u8> {
+    let mut key = STATE_JOURNAL_PREFIX.to_vec();
+    key.extend_from_slice(&commit.to_be_bytes());
+    if let Some((tag, op, raw_key)) = entry {
+        key.extend_from_slice(&[tag, op]);
+        key.extend_from_slice(raw_key);
+    }
+    key
+}
+
+/// Decodes a state commit number.
+fn decode_commit(value: &[u8]) -> u64 {
+    value.try_into().map_or(0, u64::from_be_bytes)
+}
+
+/// Snapshot of the state committed to `RocksDB`, taken when a provider is created.
+///
+/// Cursors of the provider read through the snapshot, so they see the same committed state for
+/// the lifetime of the provider, no matter what other providers commit in the meantime.
+pub struct RocksDBStateSnapshot {
+    /// Borrows the database kept alive by `provider`, declared first to be dropped before it.
+    snapshot: RocksDBSnapshotEnum<'static>,
+    provider: RocksDBProvider,
+}
+
+impl fmt::Debug for RocksDBStateSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RocksDBStateSnapshot").field("provider", &self.provider).finish()
+    }
+}
+
+impl RocksDBStateSnapshot {
+    /// Takes a snapshot of the current state of `provider`.
+    pub fn new(provider: RocksDBProvider) -> Self {
+        let snapshot = provider.snapshot();
+        // SAFETY: The snapshot borrows the database behind the `Arc` of `provider`, which is
+        // stored alongside it and dropped after it, so the database outlives the snapshot.
+        let snapshot = unsafe {
+            std::mem::transmute::<RocksDBSnapshotEnum<'_>, RocksDBSnapshotEnum<'static>>(snapshot)
+        };
+        Self { snapshot, provider }
+    }
+
+    /// Returns the number of the last state commit applied to `RocksDB` as of the snapshot.
+    pub fn state_commit(&self) -> Result<u64, DatabaseError> {
+        let value = self.snapshot.get(STATE_COMMIT_KEY).map_err(|e| {
+            DatabaseError::Read(DatabaseErrorInfo { message: e.to_string().into(), code: -1 })
+        })?;
+        Ok(value.map_or(0, |value| decode_commit(&value)))
+    }
+
+    /// Returns a raw iterator over table `T` as of the snapshot.
+    fn raw_iterator<T: Table>(&self) -> Result<RocksDBRawIterEnum<'_>, DatabaseError> {
+        Ok(self.snapshot.raw_iterator_cf(self.provider.table_cf_handle::<T>()?))
+    }
+}
+
+/// A row of a state table as raw key and compressed value.
+type RawRow = (Vec<u8>, Vec<u8>);
+
+/// Cursor over a state table stored in `RocksDB`.
+///
+/// Reads the committed entries with a single iterator over the snapshot of the provider that
+/// created the cursor and merges them with the pending writes of the provider. Writes are only
+/// added to the pending writes and become visible to other providers once the provider is
+/// committed.
+///
+/// Dup-sorted tables are stored with composite keys, see [`RocksDBStateTable`].
+pub struct RocksDBStateCursor<T: Table> {
+    /// Iterator over the table in `snapshot`, declared first to be dropped before it.
+    iter: RocksDBRawIterEnum<'static>,
+    /// Snapshot of the committed state, borrowed by `iter`.
+    snapshot: Arc<RocksDBStateSnapshot>,
+    /// Uncommitted state writes of the provider.
+    pending: PendingRocksDBState,
+    /// Raw row the cursor is positioned at.
+    current: Option<RawRow>,
+    /// Buffer for compressing values.
+    buf: Vec<u8>,
+    _table: PhantomData<T>,
+}
+
+impl<T: Table> fmt::Debug for RocksDBStateCursor<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RocksDBStateCursor")
+            .field("table", &T::NAME)
+            .field("current", &self.current.as_ref().map(|(key, _)| key))
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: RocksDBStateTable> RocksDBStateCursor<T> {
+    /// Creates a new cursor over table `T` that reads the committed state from `snapshot`.
+    pub(crate) fn new(
+        snapshot: Arc<RocksDBStateSnapshot>,
+        pending: PendingRocksDBState,
+    ) -> Result<Self, DatabaseError> {
+        let iter = snapshot.raw_iterator::<T>()?;
+        // SAFETY: The iterator borrows the snapshot behind the `Arc`, which is stored alongside
+        // it and dropped after it, so the snapshot outlives the iterator.
+        let iter = unsafe {
+            std::mem::transmute::<RocksDBRawIterEnum<'_>, RocksDBRawIterEnum<'static>>(iter)
+        };
+        Ok(Self { iter, snapshot, pending, current: None, buf: Vec::new(), _table: PhantomData })
+    }
+
+    /// Returns the first row with a key in `start..`, merging committed and pending entries.
+    fn seek_forward(&mut self, start: Bound<&[u8]>) -> Result<Option<RawRow>, DatabaseError> {
+        let pending = self.pending.lock();
+        let table = pending.tables.get(T::NAME);
+        let mut overlay = table
+            .into_iter()
+            .flat_map(|table| table.entries.range::<[u8], _>((start, Bound::Unbounded)));
+        let mut next_pending = overlay.next();
+
+        // Committed entries are hidden if the table was cleared
+        let mut iter = table.is_none_or(|table| !table.cleared).then_some(&mut self.iter);
+        if let Some(iter) = iter.as_mut() {
+            match start {
+                Bound::Included(key) => iter.seek(key),
+                Bound::Excluded(key) => {
+                    iter.seek(key);
+                    if iter.valid() && iter.key() == Some(key) {
+                        iter.next();
+                    }
+                }
+                Bound::Unbounded => iter.seek_to_first(),
+            }
+        }
+
+        loop {
+            let committed = iter.as_deref().map(row).transpose()?.flatten();
+            let Some((pending_key, pending_value)) = next_pending else { return Ok(committed) };
+
+            match committed {
+                Some((key, value)) if key.as_slice() < pending_key.as_slice() => {
+                    return Ok(Some((key, value)))
+                }
+                committed => {
+                    if let Some(value) = pending_value {
+                        return Ok(Some((pending_key.clone(), value.clone())))
+                    }
+                    // Skip the deleted entry in both sources.
+                    if committed.is_some_and(|(key, _)| key == *pending_key) &&
+                        let Some(iter) = iter.as_mut()
+                    {
+                        iter.next();
+                    }
+                    next_pending = overlay.next();
+                }
+            }
+        }
+    }
+
+    /// Returns the last row with a key in `..end`, merging committed and pending entries.
+    fn seek_backward(&mut self, end: Bound<&[u8]>) -> Result<Option<RawRow>, DatabaseError> {
+        let pending = self.pending.lock();
+        let table = pending.tables.get(T::NAME);
+        let mut overlay = table
+            .into_iter()
+            .flat_map(|table| table.entries.range::<[u8], _>((Bound::Unbounded, end)).rev());
+        let mut next_pending = overlay.next();
+
+        // Committed entries are hidden if the table was cleared
+        let mut iter = table.is_none_or(|table| !table.cleared).then_some(&mut self.iter);
+        if let Some(iter) = iter.as_mut() {
+            match end {
+                Bound::Included(key) => iter.seek_for_prev(key),
+                Bound::Excluded(key) => {
+                    iter.seek_for_prev(key);
+                    if iter.valid() && iter.key() == Some(key) {
+                        iter.prev();
+                    }
+                }
+                Bound::Unbounded => iter.seek_to_last(),
+            }
+        }
+
+        loop {
+            let committed = iter.as_deref().map(row).transpose()?.flatten();
+            let Some((pending_key, pending_value)) = next_pending else { return Ok(committed) };
+
+            match committed {
+                Some((key, value)) if key.as_slice() > pending_key.as_slice() => {
+                    return Ok(Some((key, value)))
+                }
+                committed => {
+                    if let Some(value) = pending_value {
+                        return Ok(Some((pending_key.clone(), value.clone())))
+                    }
+                    // Skip the deleted entry in both sources.
+                    if committed.is_some_and(|(key, _)| key == *pending_key) &&
+                        let Some(iter) = iter.as_mut()
+                    {
+                        iter.prev();
+                    }
+                    next_pending = overlay.next();
+                }
+            }
+        }
+    }
+
+    /// Positions the cursor at `row` and decodes it.
+    fn position(&mut self, row: Option<RawRow>) -> PairResult<T> {
+        let decoded = row.as_ref().map(|(key, value)| decode::<T>(key, value)).transpose()?;
+        self.current = row;
+        Ok(decoded)
+    }
+
+    /// Positions the cursor at `row` if it belongs to the same key as the current row.
+    ///
+    /// Leaves the cursor unchanged otherwise.
+    fn position_dup(&mut self, row: Option<RawRow>) -> PairResult<T> {
+        let Some((current, _)) = &self.current else { return Ok(None) };
+        match row {
+            Some(row) if dup_key::<T>(&row.0) == dup_key::<T>(current) => self.position(Some(row)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns a copy of the raw key the current row is stored at.
+    fn current_key(&self) -> Option<Vec<u8>> {
+        self.current.as_ref().map(|(key, _)| key.clone())
+    }
+
+    /// Adds a pending write of `value` at `raw_key` and positions the cursor at it.
+    fn put(&mut self, raw_key: Vec<u8>, value: &T::Value) {
+        let value = if let Some(value) = value.uncompressable_ref() {
+            value.to_vec()
+        } else {
+            self.buf.clear();
+            value.compress_to_buf(&mut self.buf);
+            self.buf.clone()
+        };
+        let mut pending = self.pending.lock();
+        pending.table_mut(T::NAME).entries.insert(raw_key.clone(), Some(value.clone()));
+        self.current = Some((raw_key, value));
+    }
+
+    /// Adds pending deletions of `raw_keys`.
+    fn delete(&self, raw_keys: impl IntoIterator<Item = Vec<u8>>) {
+        let mut pending = self.pending.lock();
+        let table = pending.table_mut(T::NAME);
+        for raw_key in raw_keys {
+            table.entries.insert(raw_key, None);
+        }
+    }
+
+    /// Returns the raw key `value` is stored at under `key`.
+    fn raw_key(key: T::Key, value: &T::Value) -> Vec<u8> {
+        let mut raw_key = key.encode().as_ref().to_vec();
+        raw_key.extend(T::encode_subkey(value));
+        raw_key
+    }
+}
+
+/// Reads the row an iterator is positioned at.
+fn row(iter: &RocksDBRawIterEnum<'_>) -> Result<Option<RawRow>, DatabaseError> {
+    if iter.valid() {
+        Ok(iter.key().zip(iter.value()).map(|(key, value)| (key.to_vec(), value.to_vec())))
+    } else {
+        iter.status().map_err(|e| {
+            DatabaseError::Read(DatabaseErrorInfo { message: e.to_string().into(), code: -1 })
+        })?;
+        Ok(None)
+    }
+}
+
+/// Returns the part of a raw key that encodes the table key.
+fn dup_key<T: RocksDBStateTable>(raw_key: &[u8]) -> &[u8] {
+    T::DUP_KEY_LENGTH.map_or(raw_key, |len| &raw_key[..len.min(raw_key.len())])
+}
+
+/// Returns the smallest key that is greater than all keys starting with `prefix`, if any.
+fn prefix_successor(prefix: &[u8]) -> Option<Vec<u8>> {
+    let mut successor = prefix.to_vec();
+    while let Some(byte) = successor.pop() {
+        if byte < u8::MAX {
+            successor.push(byte + 1);
+            return Some(successor)
+        }
+    }
+    None
+}
+
+/// Decodes a raw row of table `T`.
+fn decode<T: RocksDBStateTable>(
+    raw_key: &[u8],
+    value: &[u8],
+) -> Result<(T::Key, T::Value), DatabaseError> {
+    Ok((T::Key::decode(dup_key::<T>(raw_key))?, T::Value::decompress(value)?))
+}
+
+impl<T: RocksDBStateTable> DbCursorRO<T> for RocksDBStateCursor<T> {
+    fn first(&mut self) -> PairResult<T> {
+        let row = self.seek_forward(Bound::Unbounded)?;
+        self.position(row)
+    }
+
+    fn seek_exact(&mut self, key: T::Key) -> PairResult<T> {
+        let key = key.encode();
+        let row = self
+            .seek_forward(Bound::Included(key.as_ref()))?
+            .filter(|(raw_key, _)| dup_key::<T>(raw_key) == key.as_ref());
+        self.position(row)
+    }
+
+    fn seek(&mut self, key: T::Key) -> PairResult<T> {
+        let row = self.seek_forward(Bound::Included(key.encode().as_ref()))?;
+        self.position(row)
+    }
+
+    fn next(&mut self) -> PairResult<T> {
+        let row = match self.current_key() {
+            Some(key) => self.seek_forward(Bound::Excluded(&key))?,
+            None => self.seek_forward(Bound::Unbounded)?,
+        };
+        self.position(row)
+    }
+
+    fn prev(&mut self) -> PairResult<T> {
+        let row = match self.current_key() {
+            Some(key) => self.seek_backward(Bound::Excluded(&key))?,
+            None => self.seek_backward(Bound::Unbounded)?,
+        };
+        self.position(row)
+    }
+
+    fn last(&mut self) -> PairResult<T> {
+        let row = self.seek_backward(Bound::Unbounded)?;
+        self.position(row)
+    }
+
+    fn current(&mut self) -> PairResult<T> {
+        self.current.as_ref().map(|(key, value)| decode::<T>(key, value)).transpose()
+    }
+
+    fn walk(&mut self, start_key: Option<T::Key>) -> Result<Walker<'_, T, Self>, DatabaseError> {
+        let start =
+            if let Some(start_key) = start_key { self.seek(start_key) } else { self.first() }
+                .transpose();
+        Ok(Walker::new(self, start))
+    }
+
+    fn walk_range(
+        &mut self,
+        range: impl RangeBounds<T::Key>,
+    ) -> Result<RangeWalker<'_, T, Self>, DatabaseError> {
+        let start = match range.start_bound().cloned() {
+            Bound::Included(key) => self.seek(key),
+            Bound::Excluded(_key) => {
+                unreachable!("Rust doesn't allow for Bound::Excluded in starting bounds");
+            }
+            Bound::Unbounded => self.first(),
+        }
+        .transpose();
+        Ok(RangeWalker::new(self, start, range.end_bound().cloned()))
+    }
+
+    fn walk_back(
+        &mut self,
+        start_key: Option<T::Key>,
+    ) -> Result<ReverseWalker<'_, T, Self>, DatabaseError> {
+        let start =
+            if let Some(start_key) = start_key { self.seek(start_key) } else { self.last() }
+                .transpose();
+        Ok(ReverseWalker::new(self, start))
+    }
+}
+
+impl<T: RocksDBStateTable + DupSort> DbDupCursorRO<T> for RocksDBStateCursor<T> {
+    fn prev_dup(&mut self) -> PairResult<T> {
+        let Some(key) = self.current_key() else { return Ok(None) };
+        let row = self.seek_backward(Bound::Excluded(&key))?;
+        self.position_dup(row)
+    }
+
+    fn next_dup(&mut self) -> PairResult<T> {
+        let Some(key) = self.current_key() else { return Ok(None) };
+        let row = self.seek_forward(Bound::Excluded(&key))?;
+        self.position_dup(row)
+    }
+
+    fn last_dup(&mut self) -> ValueOnlyResult<T> {
+        let Some(key) = self.current_key() else { return Ok(None) };
+        let row = match prefix_successor(dup_key::<T>(&key)) {
+            Some(successor) => self.seek_backward(Bound::Excluded(&successor))?,
+            None => self.seek_backward(Bound::Unbounded)?,
+        };
+        Ok(self.position_dup(row)?.map(|(_, value)| value))
+    }
+
+    fn next_no_dup(&mut self) -> PairResult<T> {
+        let row = match self.current_key() {
+            Some(key) => match prefix_successor(dup_key::<T>(&key)) {
+                Some(successor) => self.seek_forward(Bound::Included(&successor))?,
+                None => None,
+            },
+            None => self.seek_forward(Bound::Unbounded)?,
+        };
+        self.position(row)
+    }
+
+    fn next_dup_val(&mut self) -> ValueOnlyResult<T> {
+        Ok(self.next_dup()?.map(|(_, value)| value))
+    }
+
+    fn seek_by_key_subkey(&mut self, key: T::Key, subkey: T::SubKey) -> ValueOnlyResult<T> {
+        let key = key.encode();
+        let mut target = key.as_ref().to_vec();
+        target.extend_from_slice(subkey.encode().as_ref());
+
+        let row = self
+            .seek_forward(Bound::Included(&target))?
+            .filter(|(raw_key, _)| dup_key::<T>(raw_key) == key.as_ref());
+        if row.is_none() {
+            return Ok(None)
+        }
+        Ok(self.position(row)?.map(|(_, value)| value))
+    }
+
+    fn walk_dup(
+        &mut self,
+        key: Option<T::Key>,
+        subkey: Option<T::SubKey>,
+    ) -> Result<DupWalker<'_, T, Self>, DatabaseError> {
+        let start = match (key, subkey) {
+            (Some(key), Some(subkey)) => {
+                self.seek_by_key_subkey(key.clone(), subkey)?.map(|value| Ok((key, value)))
+            }
+            (Some(key), None) => self.seek_exact(key)?.map(Ok),
+            (None, Some(subkey)) => match self.first()? {
+                Some((key, _)) => {
+                    self.seek_by_key_subkey(key.clone(), subkey)?.map(|value| Ok((key, value)))
+                }
+                None => None,
+            },
+            (None, None) => self.first()?.map(Ok),
+        };
+        Ok(DupWalker { cursor: self, start })
+    }
+}
+
+impl<T: RocksDBStateTable> DbCursorRW<T> for RocksDBStateCursor<T> {
+    fn upsert(&mut self, key: T::Key, value: &T::Value) -> Result<(), DatabaseError> {
+        self.put(Self::raw_key(key, value), value);
+        Ok(())
+    }
+
+    fn insert(&mut self, key: T::Key, value: &T::Value) -> Result<(), DatabaseError> {
+        let raw_key = Self::raw_key(key, value);
+        let exists = self
+            .seek_forward(Bound::Included(&raw_key))?
+            .is_some_and(|(existing, _)| existing == raw_key);
+        if exists {
+            return Err(DatabaseWriteError {
+                info: DatabaseErrorInfo { message: "key already exists".into(), code: -1 },
+                operation: DatabaseWriteOperation::CursorInsert,
+                table_name: T::NAME,
+                key: raw_key,
+            }
+            .into())
+        }
+
+        self.put(raw_key, value);
+        Ok(())
+    }
+
+    /// Appends a row. Unlike MDBX, the key is not required to be the greatest in the table.
+    fn append(&mut self, key: T::Key, value: &T::Value) -> Result<(), DatabaseError> {
+        self.put(Self::raw_key(key, value), value);
+        Ok(())
+    }
+
+    fn delete_current(&mut self) -> Result<(), DatabaseError> {
+        if let Some((key, _)) = &self.current {
+            self.delete([key.clone()]);
+        }
+        Ok(())
+    }
+}
+
+impl<T: RocksDBStateTable + DupSort> DbDupCursorRW<T> for RocksDBStateCursor<T> {
+    fn delete_current_duplicates(&mut self) -> Result<(), DatabaseError> {
+        let Some(key) = self.current_key() else { return Ok(()) };
+        let prefix = dup_key::<T>(&key).to_vec();
+
+        let mut duplicates = Vec::new();
+        let mut row = self.seek_forward(Bound::Included(&prefix))?;
+        while let Some((raw_key, _)) = row.filter(|(raw_key, _)| raw_key.starts_with(&prefix)) {
+            row = self.seek_forward(Bound::Excluded(&raw_key))?;
+            duplicates.push(raw_key);
+        }
+
+        self.delete(duplicates);
+        Ok(())
+    }
+
+    fn append_dup(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        self.put(Self::raw_key(key, &value), &value);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::rocksdb::RocksDBBuilder;
+    use alloy_primitives::{Address, B256, U256};
+    use hanzo_evm_db_api::tables;
+    use reth_primitives_traits::{Account, StorageEntry};
+    use tempfile::TempDir;
+
+    fn account(nonce: u64) -> Account {
+        Account { nonce, ..Default::default() }
+    }
+
+    fn slot(key: u8, value: u64) -> StorageEntry {
+        StorageEntry { key: B256::with_last_byte(key), value: U256::from(value) }
+    }
+
+    /// Creates a cursor reading a new snapshot of `provider`.
+    fn cursor<T: RocksDBStateTable>(
+        provider: &RocksDBProvider,
+        pending: &PendingRocksDBState,
+    ) -> RocksDBStateCursor<T> {
+        let snapshot = Arc::new(RocksDBStateSnapshot::new(provider.clone()));
+        RocksDBStateCursor::new(snapshot, pending.clone()).unwrap()
+    }
+
+    /// Takes the pending state as the next state commit.
+    fn next_commit(provider: &RocksDBProvider, pending: &PendingRocksDBState) -> PendingState {
+        let mut pending = std::mem::take(&mut *pending.lock());
+        pending.commit = Some(provider.state_commit().unwrap() + 1);
+        pending
+    }
+
+    fn commit(provider: &RocksDBProvider, pending: &PendingRocksDBState) {
+        let pending = next_commit(provider, pending);
+        provider.journal_pending_state(&pending).unwrap();
+        provider.apply_pending_state(&pending).unwrap();
+    }
+
+    #[test]
+    fn test_state_cursor_merges_pending_writes() {
+        let temp_dir = TempDir::new().unwrap();
+        let provider = RocksDBBuilder::new(temp_dir.path()).with_default_tables().build().unwrap();
+        let pending = PendingRocksDBState::default();
+        let address = |byte| Address::with_last_byte(byte);
+
+        let mut cursor = cursor::<tables::PlainAccountState>(&provider, &pending);
+        for byte in [1, 3, 5] {
+            cursor.upsert(address(byte), &account(byte as u64)).unwrap();
+        }
+        commit(&provider, &pending);
+
+        // Overwrite, delete and add entries without committing.
+        let mut cursor = cursor::<tables::PlainAccountState>(&provider, &pending);
+        cursor.upsert(address(1), &account(10)).unwrap();
+        cursor.seek_exact(address(3)).unwrap();
+        cursor.delete_current().unwrap();
+        cursor.upsert(address(4), &account(4)).unwrap();
+        assert!(cursor.insert(address(5), &account(50)).is_err());
+
+        let entries = cursor.walk(None).unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(
+            entries,
+            vec![(address(1), account(10)), (address(4), account(4)), (address(5), account(5))]
+        );
+        assert_eq!(cursor.last().unwrap(), Some((address(5), account(5))));
+        assert_eq!(cursor.prev().unwrap(), Some((address(4), account(4))));
+        assert_eq!(cursor.prev().unwrap(), Some((address(1), account(10))));
+        assert_eq!(cursor.seek(address(2)).unwrap(), Some((address(4), account(4))));
+        assert_eq!(cursor.seek_exact(address(3)).unwrap(), None);
+
+        // Pending writes are invisible to other providers, and commits are invisible to
+        // providers created before them.
+        let mut other = cursor::<tables::PlainAccountState>(&provider, &Default::default());
+        assert_eq!(other.seek_exact(address(3)).unwrap(), Some((address(3), account(3))));
+
+        commit(&provider, &pending);
+        assert_eq!(other.seek_exact(address(3)).unwrap(), Some((address(3), account(3))));
+        assert_eq!(other.first().unwrap(), Some((address(1), account(1))));
+
+        let mut other = cursor::<tables::PlainAccountState>(&provider, &Default::default());
+        assert_eq!(other.seek_exact(address(3)).unwrap(), None);
+        assert_eq!(other.first().unwrap(), Some((address(1), account(10))));
+    }
+
+    #[test]
+    fn test_state_cursor_dup_semantics() {
+        let temp_dir = TempDir::new().unwrap();
+        let provider = RocksDBBuilder::new(temp_dir.path()).with_default_tables().build().unwrap();
+        let pending = PendingRocksDBState::default();
+        let (first, second) = (Address::with_last_byte(1), Address::with_last_byte(2));
+
+        let mut cursor = cursor::<tables::PlainStorageState>(&provider, &pending);
+        for entry in [slot(1, 1), slot(2, 2), slot(3, 3)] {
+            cursor.append_dup(first, entry).unwrap();
+        }
+        cursor.append_dup(second, slot(1, 10)).unwrap();
+        commit(&provider, &pending);
+
+        let mut cursor = cursor::<tables::PlainStorageState>(&provider, &pending);
+        cursor.upsert(first, &slot(2, 20)).unwrap();
+
+        assert_eq!(
+            cursor.seek_by_key_subkey(first, B256::with_last_byte(2)).unwrap(),
+            Some(slot(2, 20))
+        );
+        assert_eq!(cursor.next_dup().unwrap(), Some((first, slot(3, 3))));
+        assert_eq!(cursor.next_dup().unwrap(), None);
+        assert_eq!(cursor.next_no_dup().unwrap(), Some((second, slot(1, 10))));
+
+        cursor.seek_exact(first).unwrap();
+        assert_eq!(cursor.last_dup().unwrap(), Some(slot(3, 3)));
+
+        let dups = cursor
+            .walk_dup(Some(first), None)
+            .unwrap()
+            .map(|entry| entry.map(|(_, value)| value))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(dups, vec![slot(1, 1), slot(2, 20), slot(3, 3)]);
+
+        cursor.seek_exact(first).unwrap();
+        cursor.delete_current_duplicates().unwrap();
+        assert_eq!(cursor.seek_exact(first).unwrap(), None);
+        assert_eq!(cursor.first().unwrap(), Some((second, slot(1, 10))));
+    }
+
+    #[test]
+    fn test_state_cursor_clear() {
+        let temp_dir = TempDir::new().unwrap();
+        let provider = RocksDBBuilder::new(temp_dir.path()).with_default_tables().build().unwrap();
+        let pending = PendingRocksDBState::default();
+
+        let mut cursor = cursor::<tables::HashedAccounts>(&provider, &pending);
+        cursor.upsert(B256::with_last_byte(1), &account(1)).unwrap();
+        commit(&provider, &pending);
+
+        let mut cursor = cursor::<tables::HashedAccounts>(&provider, &pending);
+        pending.lock().table_mut(tables::HashedAccounts::NAME).clear();
+        assert_eq!(cursor.first().unwrap(), None);
+
+        cursor.upsert(B256::with_last_byte(2), &account(2)).unwrap();
+        commit(&provider, &pending);
+
+        let mut cursor = cursor::<tables::HashedAccounts>(&provider, &pending);
+        let entries = cursor.walk(None).unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(entries, vec![(B256::with_last_byte(2), account(2))]);
+    }
+
+    #[test]
+    fn test_heal_state_commit() {
+        let temp_dir = TempDir::new().unwrap();
+        let provider = RocksDBBuilder::new(temp_dir.path()).with_default_tables().build().unwrap();
+        let pending = PendingRocksDBState::default();
+        let address = Address::with_last_byte(1);
+
+        cursor::<tables::PlainAccountState>(&provider, &pending)
+            .upsert(address, &account(1))
+            .unwrap();
+        commit(&provider, &pending);
+        assert_eq!(provider.state_commit().unwrap(), 1);
+        provider.heal_state_commit(1).unwrap();
+
+        // Crash after the database commit: the journal is replayed.
+        let mut cursor = cursor::<tables::PlainAccountState>(&provider, &pending);
+        pending.lock().table_mut(tables::PlainAccountState::NAME).clear();
+        cursor.upsert(address, &account(2)).unwrap();
+        provider.journal_pending_state(&next_commit(&provider, &pending)).unwrap();
+
+        assert!(provider.heal_state_commit(3).is_err());
+        provider.heal_state_commit(2).unwrap();
+        assert_eq!(provider.state_commit().unwrap(), 2);
+        assert_eq!(provider.get::<tables::PlainAccountState>(address).unwrap(), Some(account(2)));
+        assert!(provider.read_state_journals().unwrap().is_empty());
+
+        // Crash before the database commit: the journal is discarded.
+        cursor::<tables::PlainAccountState>(&provider, &pending)
+            .upsert(address, &account(3))
+            .unwrap();
+        provider.journal_pending_state(&next_commit(&provider, &pending)).unwrap();
+
+        provider.heal_state_commit(2).unwrap();
+        assert_eq!(provider.state_commit().unwrap(), 2);
+        assert_eq!(provider.get::<tables::PlainAccountState>(address).unwrap(), Some(account(2)));
+        assert!(provider.read_state_journals().unwrap().is_empty());
+
+        // The database is behind the RocksDB state.
+        assert!(provider.heal_state_commit(1).is_err());
+    }
+}
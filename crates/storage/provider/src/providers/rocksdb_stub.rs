@@ -15,6 +15,9 @@ use std::{path::Path, sync::Arc};
 /// Pending `RocksDB` batches type alias (stub - uses unit type).
 pub(crate) type PendingRocksDBBatches = Arc<Mutex<Vec<()>>>;
 
+/// Pending `RocksDB` state writes type alias (stub - uses unit type).
+pub(crate) type PendingRocksDBState = Arc<Mutex<()>>;
+
 /// Statistics for a single `RocksDB` table (column family) - stub.
 #[derive(Debug, Clone)]
 pub struct RocksDBTableStats {
@@ -127,6 +130,20 @@ impl RocksDBProvider {
         Ok(())
     }
 
+    /// Creates a checkpoint of all tables (stub implementation).
+    ///
+    /// This is a no-op since there is no `RocksDB` when the feature is disabled.
+    pub const fn create_checkpoint(&self, _dest: &Path) -> ProviderResult<()> {
+        Ok(())
+    }
+
+    /// Catches up with the primary instance (stub implementation).
+    ///
+    /// This is a no-op since there is no `RocksDB` when the feature is disabled.
+    pub const fn try_catch_up_with_primary(&self) -> ProviderResult<()> {
+        Ok(())
+    }
+
     /// Creates an iterator over all entries in the specified table (stub implementation).
     ///
     /// Returns an empty iterator since there is no `RocksDB` when the feature is disabled.
@@ -190,6 +207,11 @@ impl RocksDBBuilder {
         self
     }
 
+    /// Opens the database as a secondary instance (stub implementation).
+    pub fn with_secondary(self, _secondary_path: impl AsRef<Path>) -> Self {
+        self
+    }
+
     /// Build the `RocksDB` provider (stub implementation).
     pub const fn build(self) -> ProviderResult<RocksDBProvider> {
         Ok(RocksDBProvider)
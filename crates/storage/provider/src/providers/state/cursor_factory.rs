@@ -0,0 +1,271 @@
+use crate::{
+    ChangeSetReader, DupStateCursorTy, StateCursorProvider, StateCursorTy, StorageChangeSetReader,
+};
+use alloy_primitives::{keccak256, map::B256Map, Address, BlockNumber, Bytes, B256};
+use hanzo_evm_db_api::{tables, DatabaseError};
+use hanzo_evm_storage_errors::provider::{ProviderError, ProviderResult};
+use hanzo_evm_trie::{
+    hashed_cursor::{HashedCursorFactory, HashedPostStateCursorFactory},
+    metrics::TrieRootMetrics,
+    proof::{Proof, StorageProof},
+    trie_cursor::{InMemoryTrieCursorFactory, TrieCursorFactory},
+    updates::TrieUpdates,
+    witness::TrieWitness,
+    AccountProof, HashedPostState, HashedPostStateSorted, HashedStorage, MultiProof,
+    MultiProofTargets, StateRoot, StorageMultiProof, StorageRoot, TrieInput, TrieInputSorted,
+    TrieType,
+};
+use hanzo_evm_trie_db::{
+    load_prefix_sets_with_cursor, DatabaseAccountTrieCursor, DatabaseHashedAccountCursor,
+    DatabaseHashedStorageCursor, DatabaseStorageTrieCursor,
+};
+use std::ops::RangeInclusive;
+
+/// Trie and hashed cursor factory over the state tables of a [`StateCursorProvider`].
+///
+/// Unlike `DatabaseTrieCursorFactory` and `DatabaseHashedCursorFactory`, which always read from
+/// the database transaction, this factory reads the tables from wherever the provider stores them.
+#[derive(Debug)]
+pub struct StateCursorFactory<'a, P>(&'a P);
+
+impl<P> Clone for StateCursorFactory<'_, P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P> Copy for StateCursorFactory<'_, P> {}
+
+impl<'a, P: StateCursorProvider> StateCursorFactory<'a, P> {
+    /// Creates a new [`StateCursorFactory`].
+    pub const fn new(provider: &'a P) -> Self {
+        Self(provider)
+    }
+
+    /// Returns a [`StateRoot`] calculator over the stored state.
+    pub fn state_root(&self) -> StateRoot<Self, Self> {
+        StateRoot::new(*self, *self)
+    }
+
+    /// Calculates the state root and trie updates for the state changes in the given block range.
+    pub fn incremental_root_with_updates(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<(B256, TrieUpdates)>
+    where
+        P: ChangeSetReader + StorageChangeSetReader,
+    {
+        let mut hashed_accounts_cursor = self.0.state_cursor_read::<tables::HashedAccounts>()?;
+        let prefix_sets = load_prefix_sets_with_cursor(self.0, &mut hashed_accounts_cursor, range)?;
+        Ok(self.state_root().with_prefix_sets(prefix_sets).root_with_updates()?)
+    }
+
+    /// Calculates the state root with the given hashed state applied on top of the stored state.
+    pub fn overlay_root(&self, post_state: &HashedPostStateSorted) -> ProviderResult<B256> {
+        let prefix_sets = post_state.construct_prefix_sets().freeze();
+        Ok(StateRoot::new(*self, HashedPostStateCursorFactory::new(*self, post_state))
+            .with_prefix_sets(prefix_sets)
+            .root()?)
+    }
+
+    /// Calculates the state root and trie updates with the given hashed state applied on top of
+    /// the stored state.
+    pub fn overlay_root_with_updates(
+        &self,
+        post_state: &HashedPostStateSorted,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        let prefix_sets = post_state.construct_prefix_sets().freeze();
+        Ok(StateRoot::new(*self, HashedPostStateCursorFactory::new(*self, post_state))
+            .with_prefix_sets(prefix_sets)
+            .root_with_updates()?)
+    }
+
+    /// Calculates the state root for the given trie input using cached intermediate nodes.
+    pub fn overlay_root_from_nodes(&self, input: TrieInputSorted) -> ProviderResult<B256> {
+        Ok(StateRoot::new(
+            InMemoryTrieCursorFactory::new(*self, input.nodes.as_ref()),
+            HashedPostStateCursorFactory::new(*self, input.state.as_ref()),
+        )
+        .with_prefix_sets(input.prefix_sets.freeze())
+        .root()?)
+    }
+
+    /// Calculates the state root and trie updates for the given trie input using cached
+    /// intermediate nodes.
+    pub fn overlay_root_from_nodes_with_updates(
+        &self,
+        input: TrieInputSorted,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        Ok(StateRoot::new(
+            InMemoryTrieCursorFactory::new(*self, input.nodes.as_ref()),
+            HashedPostStateCursorFactory::new(*self, input.state.as_ref()),
+        )
+        .with_prefix_sets(input.prefix_sets.freeze())
+        .root_with_updates()?)
+    }
+
+    /// Calculates the storage root of an account with the given hashed storage applied on top of
+    /// the stored storage.
+    pub fn storage_overlay_root(
+        &self,
+        address: Address,
+        hashed_storage: HashedStorage,
+    ) -> ProviderResult<B256> {
+        let prefix_set = hashed_storage.construct_prefix_set().freeze();
+        let state_sorted =
+            HashedPostState::from_hashed_storage(keccak256(address), hashed_storage).into_sorted();
+        StorageRoot::new(
+            *self,
+            HashedPostStateCursorFactory::new(*self, &state_sorted),
+            address,
+            prefix_set,
+            TrieRootMetrics::new(TrieType::Storage),
+        )
+        .root()
+        .map_err(|err| ProviderError::Database(err.into()))
+    }
+
+    /// Generates the storage proof of a slot with the given hashed storage applied on top of the
+    /// stored storage.
+    pub fn overlay_storage_proof(
+        &self,
+        address: Address,
+        slot: B256,
+        storage: HashedStorage,
+    ) -> ProviderResult<hanzo_evm_trie::StorageProof> {
+        let prefix_set = storage.construct_prefix_set();
+        let state_sorted = HashedPostStateSorted::new(
+            Default::default(),
+            B256Map::from_iter([(keccak256(address), storage.into_sorted())]),
+        );
+        StorageProof::new(*self, HashedPostStateCursorFactory::new(*self, &state_sorted), address)
+            .with_prefix_set_mut(prefix_set)
+            .storage_proof(slot)
+            .map_err(ProviderError::from)
+    }
+
+    /// Generates the storage multiproof of the given slots with the given hashed storage applied
+    /// on top of the stored storage.
+    pub fn overlay_storage_multiproof(
+        &self,
+        address: Address,
+        slots: &[B256],
+        storage: HashedStorage,
+    ) -> ProviderResult<StorageMultiProof> {
+        let targets = slots.iter().map(keccak256).collect();
+        let prefix_set = storage.construct_prefix_set();
+        let state_sorted = HashedPostStateSorted::new(
+            Default::default(),
+            B256Map::from_iter([(keccak256(address), storage.into_sorted())]),
+        );
+        StorageProof::new(*self, HashedPostStateCursorFactory::new(*self, &state_sorted), address)
+            .with_prefix_set_mut(prefix_set)
+            .storage_multiproof(targets)
+            .map_err(ProviderError::from)
+    }
+
+    /// Generates the account proof for the given trie input.
+    pub fn overlay_account_proof(
+        &self,
+        input: TrieInput,
+        address: Address,
+        slots: &[B256],
+    ) -> ProviderResult<AccountProof> {
+        let nodes_sorted = input.nodes.into_sorted();
+        let state_sorted = input.state.into_sorted();
+        Proof::new(
+            InMemoryTrieCursorFactory::new(*self, &nodes_sorted),
+            HashedPostStateCursorFactory::new(*self, &state_sorted),
+        )
+        .with_prefix_sets_mut(input.prefix_sets)
+        .account_proof(address, slots)
+        .map_err(ProviderError::from)
+    }
+
+    /// Generates the multiproof of the given targets for the given trie input.
+    pub fn overlay_multiproof(
+        &self,
+        input: TrieInput,
+        targets: MultiProofTargets,
+    ) -> ProviderResult<MultiProof> {
+        let nodes_sorted = input.nodes.into_sorted();
+        let state_sorted = input.state.into_sorted();
+        Proof::new(
+            InMemoryTrieCursorFactory::new(*self, &nodes_sorted),
+            HashedPostStateCursorFactory::new(*self, &state_sorted),
+        )
+        .with_prefix_sets_mut(input.prefix_sets)
+        .multiproof(targets)
+        .map_err(ProviderError::from)
+    }
+
+    /// Generates the trie witness of the target state for the given trie input.
+    pub fn overlay_witness(
+        &self,
+        input: TrieInput,
+        target: HashedPostState,
+    ) -> ProviderResult<B256Map<Bytes>> {
+        let nodes_sorted = input.nodes.into_sorted();
+        let state_sorted = input.state.into_sorted();
+        TrieWitness::new(
+            InMemoryTrieCursorFactory::new(*self, &nodes_sorted),
+            HashedPostStateCursorFactory::new(*self, &state_sorted),
+        )
+        .with_prefix_sets_mut(input.prefix_sets)
+        .always_include_root_node()
+        .compute(target)
+        .map_err(ProviderError::from)
+    }
+}
+
+impl<P: StateCursorProvider> TrieCursorFactory for StateCursorFactory<'_, P> {
+    type AccountTrieCursor<'a>
+        = DatabaseAccountTrieCursor<StateCursorTy<P, tables::AccountsTrie>>
+    where
+        Self: 'a;
+
+    type StorageTrieCursor<'a>
+        = DatabaseStorageTrieCursor<DupStateCursorTy<P, tables::StoragesTrie>>
+    where
+        Self: 'a;
+
+    fn account_trie_cursor(&self) -> Result<Self::AccountTrieCursor<'_>, DatabaseError> {
+        Ok(DatabaseAccountTrieCursor::new(self.0.state_cursor_read::<tables::AccountsTrie>()?))
+    }
+
+    fn storage_trie_cursor(
+        &self,
+        hashed_address: B256,
+    ) -> Result<Self::StorageTrieCursor<'_>, DatabaseError> {
+        Ok(DatabaseStorageTrieCursor::new(
+            self.0.state_cursor_dup_read::<tables::StoragesTrie>()?,
+            hashed_address,
+        ))
+    }
+}
+
+impl<P: StateCursorProvider> HashedCursorFactory for StateCursorFactory<'_, P> {
+    type AccountCursor<'a>
+        = DatabaseHashedAccountCursor<StateCursorTy<P, tables::HashedAccounts>>
+    where
+        Self: 'a;
+
+    type StorageCursor<'a>
+        = DatabaseHashedStorageCursor<DupStateCursorTy<P, tables::HashedStorages>>
+    where
+        Self: 'a;
+
+    fn hashed_account_cursor(&self) -> Result<Self::AccountCursor<'_>, DatabaseError> {
+        Ok(DatabaseHashedAccountCursor::new(self.0.state_cursor_read::<tables::HashedAccounts>()?))
+    }
+
+    fn hashed_storage_cursor(
+        &self,
+        hashed_address: B256,
+    ) -> Result<Self::StorageCursor<'_>, DatabaseError> {
+        Ok(DatabaseHashedStorageCursor::new(
+            self.0.state_cursor_dup_read::<tables::HashedStorages>()?,
+            hashed_address,
+        ))
+    }
+}
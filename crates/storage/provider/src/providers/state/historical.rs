@@ -1,9 +1,10 @@
 use crate::{
-    AccountReader, BlockHashReader, ChangeSetReader, EitherReader, HashedPostStateProvider,
-    ProviderError, RocksDBProviderFactory, StateProvider, StateRootProvider,
+    providers::StateCursorFactory, AccountReader, BlockHashReader, ChangeSetReader, EitherReader,
+    HashedPostStateProvider, ProviderError, RocksDBProviderFactory, StateCursorProvider,
+    StateProvider, StateRootProvider, StaticFileProviderFactory,
 };
 use alloy_eips::merge::EPOCH_SLOTS;
-use alloy_primitives::{Address, BlockNumber, Bytes, StorageKey, StorageValue, B256};
+use alloy_primitives::{keccak256, Address, BlockNumber, Bytes, StorageKey, StorageValue, B256};
 use hanzo_evm_db_api::{
     cursor::{DbCursorRO, DbDupCursorRO},
     table::Table,
@@ -12,23 +13,18 @@ use hanzo_evm_db_api::{
     BlockNumberList,
 };
 use reth_primitives_traits::{Account, Bytecode, StorageSlotKey};
+use hanzo_evm_static_file_types::StaticFileSegment;
 use reth_storage_api::{
     BlockNumReader, BytecodeReader, DBProvider, NodePrimitivesProvider, StateProofProvider,
     StorageChangeSetReader, StorageRootProvider, StorageSettingsCache,
 };
 use hanzo_evm_storage_errors::provider::ProviderResult;
 use hanzo_evm_trie::{
-    proof::{Proof, StorageProof},
-    updates::TrieUpdates,
-    witness::TrieWitness,
-    AccountProof, HashedPostState, HashedPostStateSorted, HashedStorage, KeccakKeyHasher,
-    MultiProof, MultiProofTargets, StateRoot, StorageMultiProof, StorageRoot, TrieInput,
+    updates::TrieUpdates, AccountProof, HashedPostState, HashedPostStateSorted, HashedStorage,
+    KeccakKeyHasher, MultiProof, MultiProofTargets, StorageMultiProof, TrieInput,
     TrieInputSorted,
 };
-use reth_trie_db::{
-    hashed_storage_from_reverts_with_provider, DatabaseProof, DatabaseStateRoot,
-    DatabaseStorageProof, DatabaseStorageRoot, DatabaseTrieWitness,
-};
+use reth_trie_db::hashed_storage_from_reverts_with_provider;
 
 use std::fmt::Debug;
 
@@ -191,7 +187,10 @@ impl<'b, Provider: DBProvider + ChangeSetReader + StorageChangeSetReader + Block
         storage_key: StorageSlotKey,
     ) -> ProviderResult<Option<StorageValue>>
     where
-        Provider: StorageSettingsCache + RocksDBProviderFactory + NodePrimitivesProvider,
+        Provider: StateCursorProvider
+            + StorageSettingsCache
+            + RocksDBProviderFactory
+            + StaticFileProviderFactory,
     {
         let lookup_key = if self.provider.cached_storage_settings().use_hashed_state() {
             storage_key.to_hashed()
@@ -203,7 +202,17 @@ impl<'b, Provider: DBProvider + ChangeSetReader + StorageChangeSetReader + Block
             storage_key.as_b256()
         };
 
-        match self.storage_history_lookup(address, storage_key)? {
+        let history_info = match self.storage_history_lookup(address, storage_key) {
+            Err(ProviderError::StateAtBlockPruned(block_number)) => {
+                return self
+                    .storage_from_state_snapshot(address, lookup_key)?
+                    .map(Some)
+                    .ok_or(ProviderError::StateAtBlockPruned(block_number))
+            }
+            result => result?,
+        };
+
+        match history_info {
             HistoryInfo::NotYetWritten => Ok(None),
             HistoryInfo::InChangeset(changeset_block_number) => self
                 .provider
@@ -219,16 +228,16 @@ impl<'b, Provider: DBProvider + ChangeSetReader + StorageChangeSetReader + Block
                 if self.provider.cached_storage_settings().use_hashed_state() {
                     let hashed_address = alloy_primitives::keccak256(address);
                     Ok(self
-                        .tx()
-                        .cursor_dup_read::<tables::HashedStorages>()?
+                        .provider
+                        .state_cursor_dup_read::<tables::HashedStorages>()?
                         .seek_by_key_subkey(hashed_address, lookup_key)?
                         .filter(|entry| entry.key == lookup_key)
                         .map(|entry| entry.value)
                         .or(Some(StorageValue::ZERO)))
                 } else {
                     Ok(self
-                        .tx()
-                        .cursor_dup_read::<tables::PlainStorageState>()?
+                        .provider
+                        .state_cursor_dup_read::<tables::PlainStorageState>()?
                         .seek_by_key_subkey(address, lookup_key)?
                         .filter(|entry| entry.key == lookup_key)
                         .map(|entry| entry.value)
@@ -238,6 +247,95 @@ impl<'b, Provider: DBProvider + ChangeSetReader + StorageChangeSetReader + Block
         }
     }
 
+    /// Returns the closest state snapshot at or after this block that can be used to resolve the
+    /// state at this block, i.e. the `changesets` segment covers all blocks up to the snapshot.
+    ///
+    /// Snapshots are only written when the state is stored in hashed tables.
+    fn usable_state_snapshot_block(
+        &self,
+        changesets: StaticFileSegment,
+    ) -> ProviderResult<Option<BlockNumber>>
+    where
+        Provider: StorageSettingsCache + StaticFileProviderFactory,
+    {
+        if !self.provider.cached_storage_settings().use_hashed_state() {
+            return Ok(None)
+        }
+
+        let static_file_provider = self.provider.static_file_provider();
+        if static_file_provider
+            .get_lowest_range_start(changesets)
+            .is_none_or(|lowest_block| lowest_block > self.block_number)
+        {
+            return Ok(None)
+        }
+
+        static_file_provider.state_snapshot_block(self.block_number)
+    }
+
+    /// Resolves the account from the closest usable state snapshot.
+    ///
+    /// The first account changeset between this block and the snapshot holds the account before
+    /// this block, otherwise the account is unchanged since and is read from the snapshot.
+    ///
+    /// Returns `None` if there is no usable snapshot.
+    fn account_from_state_snapshot(
+        &self,
+        address: Address,
+    ) -> ProviderResult<Option<Option<Account>>>
+    where
+        Provider: StorageSettingsCache + StaticFileProviderFactory,
+    {
+        let Some(snapshot_block) =
+            self.usable_state_snapshot_block(StaticFileSegment::AccountChangeSets)?
+        else {
+            return Ok(None)
+        };
+
+        let static_file_provider = self.provider.static_file_provider();
+        if let Some((_, account_before)) = static_file_provider
+            .first_account_change_in_range(self.block_number..=snapshot_block, address)?
+        {
+            return Ok(Some(account_before.info))
+        }
+
+        static_file_provider.state_snapshot_account(snapshot_block, keccak256(address)).map(Some)
+    }
+
+    /// Resolves the storage slot from the closest usable state snapshot, see
+    /// [`Self::account_from_state_snapshot`].
+    ///
+    /// Returns `None` if there is no usable snapshot.
+    fn storage_from_state_snapshot(
+        &self,
+        address: Address,
+        hashed_slot: B256,
+    ) -> ProviderResult<Option<StorageValue>>
+    where
+        Provider: StorageSettingsCache + StaticFileProviderFactory,
+    {
+        let Some(snapshot_block) =
+            self.usable_state_snapshot_block(StaticFileSegment::StorageChangeSets)?
+        else {
+            return Ok(None)
+        };
+
+        let static_file_provider = self.provider.static_file_provider();
+        if let Some((_, entry)) = static_file_provider.first_storage_change_in_range(
+            self.block_number..=snapshot_block,
+            address,
+            hashed_slot,
+        )? {
+            return Ok(Some(entry.value))
+        }
+
+        Ok(Some(
+            static_file_provider
+                .state_snapshot_storage(snapshot_block, keccak256(address), hashed_slot)?
+                .unwrap_or_default(),
+        ))
+    }
+
     /// Checks and returns `true` if distance to historical block exceeds the provided limit.
     fn check_distance_against_limit(&self, limit: u64) -> ProviderResult<bool> {
         let tip = self.provider.last_block_number()?;
@@ -312,19 +410,34 @@ impl<Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'_, Provi
     }
 }
 
+impl<Provider: StateCursorProvider> HistoricalStateProviderRef<'_, Provider> {
+    fn cursor_factory(&self) -> StateCursorFactory<'_, Provider> {
+        StateCursorFactory::new(self.provider)
+    }
+}
+
 impl<
-        Provider: DBProvider
+        Provider: StateCursorProvider
             + BlockNumReader
             + ChangeSetReader
             + StorageChangeSetReader
             + StorageSettingsCache
             + RocksDBProviderFactory
-            + NodePrimitivesProvider,
+            + StaticFileProviderFactory,
     > AccountReader for HistoricalStateProviderRef<'_, Provider>
 {
     /// Get basic account information.
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
-        match self.account_history_lookup(*address)? {
+        let history_info = match self.account_history_lookup(*address) {
+            Err(ProviderError::StateAtBlockPruned(block_number)) => {
+                return self
+                    .account_from_state_snapshot(*address)?
+                    .ok_or(ProviderError::StateAtBlockPruned(block_number))
+            }
+            result => result?,
+        };
+
+        match history_info {
             HistoryInfo::NotYetWritten => Ok(None),
             HistoryInfo::InChangeset(changeset_block_number) => {
                 // Use ChangeSetReader trait method to get the account from changesets
@@ -339,9 +452,9 @@ impl<
             HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => {
                 if self.provider.cached_storage_settings().use_hashed_state() {
                     let hashed_address = alloy_primitives::keccak256(address);
-                    Ok(self.tx().get_by_encoded_key::<tables::HashedAccounts>(&hashed_address)?)
+                    Ok(self.provider.get_state::<tables::HashedAccounts>(hashed_address)?)
                 } else {
-                    Ok(self.tx().get_by_encoded_key::<tables::PlainAccountState>(address)?)
+                    Ok(self.provider.get_state::<tables::PlainAccountState>(*address)?)
                 }
             }
         }
@@ -366,7 +479,7 @@ impl<Provider: DBProvider + BlockNumReader + BlockHashReader> BlockHashReader
 }
 
 impl<
-        Provider: DBProvider
+        Provider: StateCursorProvider
             + ChangeSetReader
             + StorageChangeSetReader
             + BlockNumReader
@@ -377,12 +490,12 @@ impl<
         let mut revert_state = self.revert_state()?;
         let hashed_state_sorted = hashed_state.into_sorted();
         revert_state.extend_ref_and_sort(&hashed_state_sorted);
-        Ok(StateRoot::overlay_root(self.tx(), &revert_state)?)
+        self.cursor_factory().overlay_root(&revert_state)
     }
 
     fn state_root_from_nodes(&self, mut input: TrieInput) -> ProviderResult<B256> {
         input.prepend(self.revert_state()?.into());
-        Ok(StateRoot::overlay_root_from_nodes(self.tx(), TrieInputSorted::from_unsorted(input))?)
+        self.cursor_factory().overlay_root_from_nodes(TrieInputSorted::from_unsorted(input))
     }
 
     fn state_root_with_updates(
@@ -392,7 +505,7 @@ impl<
         let mut revert_state = self.revert_state()?;
         let hashed_state_sorted = hashed_state.into_sorted();
         revert_state.extend_ref_and_sort(&hashed_state_sorted);
-        Ok(StateRoot::overlay_root_with_updates(self.tx(), &revert_state)?)
+        self.cursor_factory().overlay_root_with_updates(&revert_state)
     }
 
     fn state_root_from_nodes_with_updates(
@@ -400,15 +513,13 @@ impl<
         mut input: TrieInput,
     ) -> ProviderResult<(B256, TrieUpdates)> {
         input.prepend(self.revert_state()?.into());
-        Ok(StateRoot::overlay_root_from_nodes_with_updates(
-            self.tx(),
-            TrieInputSorted::from_unsorted(input),
-        )?)
+        self.cursor_factory()
+            .overlay_root_from_nodes_with_updates(TrieInputSorted::from_unsorted(input))
     }
 }
 
 impl<
-        Provider: DBProvider
+        Provider: StateCursorProvider
             + ChangeSetReader
             + StorageChangeSetReader
             + BlockNumReader
@@ -422,8 +533,7 @@ impl<
     ) -> ProviderResult<B256> {
         let mut revert_storage = self.revert_storage(address)?;
         revert_storage.extend(&hashed_storage);
-        StorageRoot::overlay_root(self.tx(), address, revert_storage)
-            .map_err(|err| ProviderError::Database(err.into()))
+        self.cursor_factory().storage_overlay_root(address, revert_storage)
     }
 
     fn storage_proof(
@@ -434,8 +544,7 @@ impl<
     ) -> ProviderResult<hanzo_evm_trie::StorageProof> {
         let mut revert_storage = self.revert_storage(address)?;
         revert_storage.extend(&hashed_storage);
-        StorageProof::overlay_storage_proof(self.tx(), address, slot, revert_storage)
-            .map_err(ProviderError::from)
+        self.cursor_factory().overlay_storage_proof(address, slot, revert_storage)
     }
 
     fn storage_multiproof(
@@ -446,13 +555,12 @@ impl<
     ) -> ProviderResult<StorageMultiProof> {
         let mut revert_storage = self.revert_storage(address)?;
         revert_storage.extend(&hashed_storage);
-        StorageProof::overlay_storage_multiproof(self.tx(), address, slots, revert_storage)
-            .map_err(ProviderError::from)
+        self.cursor_factory().overlay_storage_multiproof(address, slots, revert_storage)
     }
 }
 
 impl<
-        Provider: DBProvider
+        Provider: StateCursorProvider
             + ChangeSetReader
             + StorageChangeSetReader
             + BlockNumReader
@@ -467,8 +575,7 @@ impl<
         slots: &[B256],
     ) -> ProviderResult<AccountProof> {
         input.prepend(self.revert_state()?.into());
-        let proof = <Proof<_, _> as DatabaseProof>::from_tx(self.tx());
-        proof.overlay_account_proof(input, address, slots).map_err(ProviderError::from)
+        self.cursor_factory().overlay_account_proof(input, address, slots)
     }
 
     fn multiproof(
@@ -477,15 +584,12 @@ impl<
         targets: MultiProofTargets,
     ) -> ProviderResult<MultiProof> {
         input.prepend(self.revert_state()?.into());
-        let proof = <Proof<_, _> as DatabaseProof>::from_tx(self.tx());
-        proof.overlay_multiproof(input, targets).map_err(ProviderError::from)
+        self.cursor_factory().overlay_multiproof(input, targets)
     }
 
     fn witness(&self, mut input: TrieInput, target: HashedPostState) -> ProviderResult<Vec<Bytes>> {
         input.prepend(self.revert_state()?.into());
-        TrieWitness::overlay_witness(self.tx(), input, target)
-            .map_err(ProviderError::from)
-            .map(|hm| hm.into_values().collect())
+        self.cursor_factory().overlay_witness(input, target).map(|hm| hm.into_values().collect())
     }
 }
 
@@ -496,14 +600,14 @@ impl<Provider> HashedPostStateProvider for HistoricalStateProviderRef<'_, Provid
 }
 
 impl<
-        Provider: DBProvider
+        Provider: StateCursorProvider
             + BlockNumReader
             + BlockHashReader
             + ChangeSetReader
             + StorageChangeSetReader
             + StorageSettingsCache
             + RocksDBProviderFactory
-            + NodePrimitivesProvider,
+            + StaticFileProviderFactory,
     > StateProvider for HistoricalStateProviderRef<'_, Provider>
 {
     /// Expects a plain (unhashed) storage key slot.
@@ -586,7 +690,7 @@ impl<Provider: DBProvider + ChangeSetReader + StorageChangeSetReader + BlockNumR
 }
 
 // Delegates all provider impls to [HistoricalStateProviderRef]
-hanzo_evm_storage_api::macros::delegate_provider_impls!(HistoricalStateProvider<Provider> where [Provider: DBProvider + BlockNumReader + BlockHashReader + ChangeSetReader + StorageChangeSetReader + StorageSettingsCache + RocksDBProviderFactory + NodePrimitivesProvider]);
+hanzo_evm_storage_api::macros::delegate_provider_impls!(HistoricalStateProvider<Provider> where [Provider: StateCursorProvider + BlockNumReader + BlockHashReader + ChangeSetReader + StorageChangeSetReader + StorageSettingsCache + RocksDBProviderFactory + StaticFileProviderFactory]);
 
 /// Lowest blocks at which different parts of the state are available.
 /// They may be [Some] if pruning is enabled.
@@ -707,7 +811,7 @@ mod tests {
         providers::state::historical::{HistoryInfo, LowestAvailableBlocks},
         test_utils::create_test_provider_factory,
         AccountReader, HistoricalStateProvider, HistoricalStateProviderRef, RocksDBProviderFactory,
-        StateProvider,
+        StateCursorProvider, StateProvider, StaticFileProviderFactory,
     };
     use alloy_primitives::{address, b256, Address, B256, U256};
     use hanzo_evm_db_api::{
@@ -719,7 +823,7 @@ mod tests {
     use reth_primitives_traits::{Account, StorageEntry, StorageSlotKey};
     use reth_storage_api::{
         BlockHashReader, BlockNumReader, ChangeSetReader, DBProvider, DatabaseProviderFactory,
-        NodePrimitivesProvider, StorageChangeSetReader, StorageSettingsCache,
+        StorageChangeSetReader, StorageSettingsCache,
     };
     use hanzo_evm_storage_errors::provider::ProviderError;
 
@@ -731,14 +835,14 @@ mod tests {
     const fn assert_state_provider<T: StateProvider>() {}
     #[expect(dead_code)]
     const fn assert_historical_state_provider<
-        T: DBProvider
+        T: StateCursorProvider
             + BlockNumReader
             + BlockHashReader
             + ChangeSetReader
             + StorageChangeSetReader
             + StorageSettingsCache
             + RocksDBProviderFactory
-            + NodePrimitivesProvider,
+            + StaticFileProviderFactory,
     >() {
         assert_state_provider::<HistoricalStateProvider<T>>();
     }
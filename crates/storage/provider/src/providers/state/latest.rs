@@ -1,5 +1,6 @@
 use crate::{
-    AccountReader, BlockHashReader, HashedPostStateProvider, StateProvider, StateRootProvider,
+    providers::StateCursorFactory, AccountReader, BlockHashReader, HashedPostStateProvider,
+    StateCursorProvider, StateProvider, StateRootProvider,
 };
 use alloy_primitives::{Address, BlockNumber, Bytes, StorageKey, StorageValue, B256};
 use reth_db_api::{cursor::DbDupCursorRO, tables, transaction::DbTx};
@@ -9,15 +10,8 @@ use reth_storage_api::{
 };
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use reth_trie::{
-    proof::{Proof, StorageProof},
-    updates::TrieUpdates,
-    witness::TrieWitness,
-    AccountProof, HashedPostState, HashedStorage, KeccakKeyHasher, MultiProof, MultiProofTargets,
-    StateRoot, StorageMultiProof, StorageRoot, TrieInput, TrieInputSorted,
-};
-use hanzo_evm_trie_db::{
-    DatabaseProof, DatabaseStateRoot, DatabaseStorageProof, DatabaseStorageRoot,
-    DatabaseTrieWitness,
+    updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, KeccakKeyHasher,
+    MultiProof, MultiProofTargets, StorageMultiProof, TrieInput, TrieInputSorted,
 };
 
 /// State provider over latest state that takes tx reference.
@@ -35,13 +29,19 @@ impl<'b, Provider: DBProvider> LatestStateProviderRef<'b, Provider> {
     fn tx(&self) -> &Provider::Tx {
         self.0.tx_ref()
     }
+}
+
+impl<Provider: StateCursorProvider> LatestStateProviderRef<'_, Provider> {
+    fn cursor_factory(&self) -> StateCursorFactory<'_, Provider> {
+        StateCursorFactory::new(self.0)
+    }
 
     fn hashed_storage_lookup(
         &self,
         hashed_address: B256,
         hashed_slot: StorageKey,
     ) -> ProviderResult<Option<StorageValue>> {
-        let mut cursor = self.tx().cursor_dup_read::<tables::HashedStorages>()?;
+        let mut cursor = self.0.state_cursor_dup_read::<tables::HashedStorages>()?;
         Ok(cursor
             .seek_by_key_subkey(hashed_address, hashed_slot)?
             .filter(|e| e.key == hashed_slot)
@@ -49,18 +49,16 @@ impl<'b, Provider: DBProvider> LatestStateProviderRef<'b, Provider> {
     }
 }
 
-impl<Provider: DBProvider + StorageSettingsCache> AccountReader
+impl<Provider: StateCursorProvider + StorageSettingsCache> AccountReader
     for LatestStateProviderRef<'_, Provider>
 {
     /// Get basic account information.
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
         if self.0.cached_storage_settings().use_hashed_state() {
             let hashed_address = alloy_primitives::keccak256(address);
-            self.tx()
-                .get_by_encoded_key::<tables::HashedAccounts>(&hashed_address)
-                .map_err(Into::into)
+            self.0.get_state::<tables::HashedAccounts>(hashed_address).map_err(Into::into)
         } else {
-            self.tx().get_by_encoded_key::<tables::PlainAccountState>(address).map_err(Into::into)
+            self.0.get_state::<tables::PlainAccountState>(*address).map_err(Into::into)
         }
     }
 }
@@ -80,41 +78,38 @@ impl<Provider: BlockHashReader> BlockHashReader for LatestStateProviderRef<'_, P
     }
 }
 
-impl<Provider: DBProvider> StateRootProvider for LatestStateProviderRef<'_, Provider> {
+impl<Provider: StateCursorProvider> StateRootProvider for LatestStateProviderRef<'_, Provider> {
     fn state_root(&self, hashed_state: HashedPostState) -> ProviderResult<B256> {
-        Ok(StateRoot::overlay_root(self.tx(), &hashed_state.into_sorted())?)
+        self.cursor_factory().overlay_root(&hashed_state.into_sorted())
     }
 
     fn state_root_from_nodes(&self, input: TrieInput) -> ProviderResult<B256> {
-        Ok(StateRoot::overlay_root_from_nodes(self.tx(), TrieInputSorted::from_unsorted(input))?)
+        self.cursor_factory().overlay_root_from_nodes(TrieInputSorted::from_unsorted(input))
     }
 
     fn state_root_with_updates(
         &self,
         hashed_state: HashedPostState,
     ) -> ProviderResult<(B256, TrieUpdates)> {
-        Ok(StateRoot::overlay_root_with_updates(self.tx(), &hashed_state.into_sorted())?)
+        self.cursor_factory().overlay_root_with_updates(&hashed_state.into_sorted())
     }
 
     fn state_root_from_nodes_with_updates(
         &self,
         input: TrieInput,
     ) -> ProviderResult<(B256, TrieUpdates)> {
-        Ok(StateRoot::overlay_root_from_nodes_with_updates(
-            self.tx(),
-            TrieInputSorted::from_unsorted(input),
-        )?)
+        self.cursor_factory()
+            .overlay_root_from_nodes_with_updates(TrieInputSorted::from_unsorted(input))
     }
 }
 
-impl<Provider: DBProvider> StorageRootProvider for LatestStateProviderRef<'_, Provider> {
+impl<Provider: StateCursorProvider> StorageRootProvider for LatestStateProviderRef<'_, Provider> {
     fn storage_root(
         &self,
         address: Address,
         hashed_storage: HashedStorage,
     ) -> ProviderResult<B256> {
-        StorageRoot::overlay_root(self.tx(), address, hashed_storage)
-            .map_err(|err| ProviderError::Database(err.into()))
+        self.cursor_factory().storage_overlay_root(address, hashed_storage)
     }
 
     fn storage_proof(
@@ -123,8 +118,7 @@ impl<Provider: DBProvider> StorageRootProvider for LatestStateProviderRef<'_, Pr
         slot: B256,
         hashed_storage: HashedStorage,
     ) -> ProviderResult<hanzo_evm_trie::StorageProof> {
-        StorageProof::overlay_storage_proof(self.tx(), address, slot, hashed_storage)
-            .map_err(ProviderError::from)
+        self.cursor_factory().overlay_storage_proof(address, slot, hashed_storage)
     }
 
     fn storage_multiproof(
@@ -133,20 +127,18 @@ impl<Provider: DBProvider> StorageRootProvider for LatestStateProviderRef<'_, Pr
         slots: &[B256],
         hashed_storage: HashedStorage,
     ) -> ProviderResult<StorageMultiProof> {
-        StorageProof::overlay_storage_multiproof(self.tx(), address, slots, hashed_storage)
-            .map_err(ProviderError::from)
+        self.cursor_factory().overlay_storage_multiproof(address, slots, hashed_storage)
     }
 }
 
-impl<Provider: DBProvider> StateProofProvider for LatestStateProviderRef<'_, Provider> {
+impl<Provider: StateCursorProvider> StateProofProvider for LatestStateProviderRef<'_, Provider> {
     fn proof(
         &self,
         input: TrieInput,
         address: Address,
         slots: &[B256],
     ) -> ProviderResult<AccountProof> {
-        let proof = <Proof<_, _> as DatabaseProof>::from_tx(self.tx());
-        proof.overlay_account_proof(input, address, slots).map_err(ProviderError::from)
+        self.cursor_factory().overlay_account_proof(input, address, slots)
     }
 
     fn multiproof(
@@ -154,13 +146,12 @@ impl<Provider: DBProvider> StateProofProvider for LatestStateProviderRef<'_, Pro
         input: TrieInput,
         targets: MultiProofTargets,
     ) -> ProviderResult<MultiProof> {
-        let proof = <Proof<_, _> as DatabaseProof>::from_tx(self.tx());
-        proof.overlay_multiproof(input, targets).map_err(ProviderError::from)
+        self.cursor_factory().overlay_multiproof(input, targets)
     }
 
     fn witness(&self, input: TrieInput, target: HashedPostState) -> ProviderResult<Vec<Bytes>> {
-        TrieWitness::overlay_witness(self.tx(), input, target)
-            .map_err(ProviderError::from)
+        self.cursor_factory()
+            .overlay_witness(input, target)
             .map(|hm| hm.into_values().collect())
     }
 }
@@ -171,7 +162,7 @@ impl<Provider: DBProvider> HashedPostStateProvider for LatestStateProviderRef<'_
     }
 }
 
-impl<Provider: DBProvider + BlockHashReader + StorageSettingsCache> StateProvider
+impl<Provider: StateCursorProvider + BlockHashReader + StorageSettingsCache> StateProvider
     for LatestStateProviderRef<'_, Provider>
 {
     /// Get storage by plain (unhashed) storage key slot.
@@ -186,7 +177,7 @@ impl<Provider: DBProvider + BlockHashReader + StorageSettingsCache> StateProvide
                 alloy_primitives::keccak256(storage_key),
             )
         } else {
-            let mut cursor = self.tx().cursor_dup_read::<tables::PlainStorageState>()?;
+            let mut cursor = self.0.state_cursor_dup_read::<tables::PlainStorageState>()?;
             if let Some(entry) = cursor.seek_by_key_subkey(account, storage_key)? &&
                 entry.key == storage_key
             {
@@ -236,7 +227,7 @@ impl<Provider: DBProvider> LatestStateProvider<Provider> {
 }
 
 // Delegates all provider impls to [LatestStateProviderRef]
-reth_storage_api::macros::delegate_provider_impls!(LatestStateProvider<Provider> where [Provider: DBProvider + BlockHashReader + StorageSettingsCache]);
+reth_storage_api::macros::delegate_provider_impls!(LatestStateProvider<Provider> where [Provider: StateCursorProvider + BlockHashReader + StorageSettingsCache]);
 
 #[cfg(test)]
 mod tests {
@@ -255,7 +246,7 @@ mod tests {
     const fn assert_state_provider<T: StateProvider>() {}
     #[expect(dead_code)]
     const fn assert_latest_state_provider<
-        T: DBProvider + BlockHashReader + StorageSettingsCache,
+        T: StateCursorProvider + BlockHashReader + StorageSettingsCache,
     >() {
         assert_state_provider::<LatestStateProvider<T>>();
     }
@@ -1,4 +1,5 @@
 //! [`StateProvider`](crate::StateProvider) implementations
+pub(crate) mod cursor_factory;
 pub(crate) mod historical;
 pub(crate) mod latest;
 pub(crate) mod overlay;
@@ -1,3 +1,4 @@
+use crate::{providers::StateCursorFactory, StateCursorProvider};
 use alloy_primitives::{BlockNumber, B256};
 use metrics::{Counter, Histogram};
 use hanzo_evm_chain_state::LazyOverlay;
@@ -18,7 +19,7 @@ use hanzo_evm_trie::{
     updates::TrieUpdatesSorted,
     HashedPostStateSorted,
 };
-use reth_trie_db::{ChangesetCache, DatabaseHashedCursorFactory, DatabaseTrieCursorFactory};
+use reth_trie_db::ChangesetCache;
 use std::{
     sync::Arc,
     time::{Duration, Instant},
@@ -500,11 +501,11 @@ where
 
 impl<Provider> TrieCursorFactory for OverlayStateProvider<Provider>
 where
-    Provider: DBProvider,
+    Provider: StateCursorProvider,
 {
     type AccountTrieCursor<'a>
         = <InMemoryTrieCursorFactory<
-        DatabaseTrieCursorFactory<&'a Provider::Tx>,
+        StateCursorFactory<'a, Provider>,
         &'a TrieUpdatesSorted,
     > as TrieCursorFactory>::AccountTrieCursor<'a>
     where
@@ -512,14 +513,14 @@ where
 
     type StorageTrieCursor<'a>
         = <InMemoryTrieCursorFactory<
-        DatabaseTrieCursorFactory<&'a Provider::Tx>,
+        StateCursorFactory<'a, Provider>,
         &'a TrieUpdatesSorted,
     > as TrieCursorFactory>::StorageTrieCursor<'a>
     where
         Self: 'a;
 
     fn account_trie_cursor(&self) -> Result<Self::AccountTrieCursor<'_>, DatabaseError> {
-        let db_trie_cursor_factory = DatabaseTrieCursorFactory::new(self.provider.tx_ref());
+        let db_trie_cursor_factory = StateCursorFactory::new(&self.provider);
         let trie_cursor_factory =
             InMemoryTrieCursorFactory::new(db_trie_cursor_factory, self.trie_updates.as_ref());
         trie_cursor_factory.account_trie_cursor()
@@ -529,7 +530,7 @@ where
         &self,
         hashed_address: B256,
     ) -> Result<Self::StorageTrieCursor<'_>, DatabaseError> {
-        let db_trie_cursor_factory = DatabaseTrieCursorFactory::new(self.provider.tx_ref());
+        let db_trie_cursor_factory = StateCursorFactory::new(&self.provider);
         let trie_cursor_factory =
             InMemoryTrieCursorFactory::new(db_trie_cursor_factory, self.trie_updates.as_ref());
         trie_cursor_factory.storage_trie_cursor(hashed_address)
@@ -538,11 +539,11 @@ where
 
 impl<Provider> HashedCursorFactory for OverlayStateProvider<Provider>
 where
-    Provider: DBProvider,
+    Provider: StateCursorProvider,
 {
     type AccountCursor<'a>
         = <HashedPostStateCursorFactory<
-        DatabaseHashedCursorFactory<&'a Provider::Tx>,
+        StateCursorFactory<'a, Provider>,
         &'a Arc<HashedPostStateSorted>,
     > as HashedCursorFactory>::AccountCursor<'a>
     where
@@ -550,14 +551,14 @@ where
 
     type StorageCursor<'a>
         = <HashedPostStateCursorFactory<
-        DatabaseHashedCursorFactory<&'a Provider::Tx>,
+        StateCursorFactory<'a, Provider>,
         &'a Arc<HashedPostStateSorted>,
     > as HashedCursorFactory>::StorageCursor<'a>
     where
         Self: 'a;
 
     fn hashed_account_cursor(&self) -> Result<Self::AccountCursor<'_>, DatabaseError> {
-        let db_hashed_cursor_factory = DatabaseHashedCursorFactory::new(self.provider.tx_ref());
+        let db_hashed_cursor_factory = StateCursorFactory::new(&self.provider);
         let hashed_cursor_factory =
             HashedPostStateCursorFactory::new(db_hashed_cursor_factory, &self.hashed_post_state);
         hashed_cursor_factory.hashed_account_cursor()
@@ -567,7 +568,7 @@ where
         &self,
         hashed_address: B256,
     ) -> Result<Self::StorageCursor<'_>, DatabaseError> {
-        let db_hashed_cursor_factory = DatabaseHashedCursorFactory::new(self.provider.tx_ref());
+        let db_hashed_cursor_factory = StateCursorFactory::new(&self.provider);
         let hashed_cursor_factory =
             HashedPostStateCursorFactory::new(db_hashed_cursor_factory, &self.hashed_post_state);
         hashed_cursor_factory.hashed_storage_cursor(hashed_address)
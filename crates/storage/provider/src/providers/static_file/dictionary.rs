@@ -0,0 +1,119 @@
+use hanzo_evm_nippy_jar::compression::{Compressors, Zstd};
+use hanzo_evm_static_file_types::StaticFileSegment;
+use hanzo_evm_storage_errors::provider::{ProviderError, ProviderResult};
+use std::path::{Path, PathBuf};
+
+/// Directory inside the static files directory holding the trained dictionaries.
+pub const DICTIONARIES_DIR: &str = "dictionaries";
+
+/// File extension of a trained dictionary.
+const DICTIONARY_FILE_EXTENSION: &str = "zdict";
+
+/// Trained zstd dictionaries of a [`StaticFileSegment`], one per column.
+///
+/// Every column is stored as `{segment}_v{id}_{column}.zdict` in [`DICTIONARIES_DIR`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SegmentDictionaries {
+    /// Segment the dictionaries were trained for.
+    pub segment: StaticFileSegment,
+    /// Version of the dictionaries, stored in the configuration of every jar compressed with
+    /// them.
+    pub id: u32,
+    /// Dictionary of each column.
+    pub columns: Vec<Vec<u8>>,
+}
+
+impl SegmentDictionaries {
+    /// Reads the dictionaries of `segment` with the given version from the static files
+    /// directory.
+    pub fn load(directory: &Path, segment: StaticFileSegment, id: u32) -> ProviderResult<Self> {
+        let columns = (0..segment.columns())
+            .map(|column| {
+                hanzo_evm_fs_util::read(Self::path(directory, segment, id, column))
+                    .map_err(ProviderError::other)
+            })
+            .collect::<ProviderResult<_>>()?;
+
+        Ok(Self { segment, id, columns })
+    }
+
+    /// Writes the dictionaries into the static files directory.
+    pub fn save(&self, directory: &Path) -> ProviderResult<()> {
+        hanzo_evm_fs_util::create_dir_all(directory.join(DICTIONARIES_DIR))
+            .map_err(ProviderError::other)?;
+
+        for (column, dictionary) in self.columns.iter().enumerate() {
+            hanzo_evm_fs_util::write(
+                Self::path(directory, self.segment, self.id, column),
+                dictionary,
+            )
+            .map_err(ProviderError::other)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the highest version of the dictionaries of `segment` in the static files
+    /// directory, if any.
+    pub fn latest_id(directory: &Path, segment: StaticFileSegment) -> ProviderResult<Option<u32>> {
+        let directory = directory.join(DICTIONARIES_DIR);
+        if !directory.exists() {
+            return Ok(None)
+        }
+
+        let prefix = format!("{}_v", segment.as_str());
+        let suffix = format!("_0.{DICTIONARY_FILE_EXTENSION}");
+
+        let mut latest = None;
+        for entry in hanzo_evm_fs_util::read_dir(&directory).map_err(ProviderError::other)? {
+            let file_name = entry.map_err(ProviderError::other)?.file_name();
+            let id = file_name
+                .to_str()
+                .and_then(|name| name.strip_prefix(&prefix))
+                .and_then(|name| name.strip_suffix(&suffix))
+                .and_then(|id| id.parse::<u32>().ok());
+            latest = latest.max(id);
+        }
+
+        Ok(latest)
+    }
+
+    /// Returns the zstd compressor using these dictionaries.
+    pub fn compressor(&self) -> Compressors {
+        Compressors::Zstd(Zstd::with_dictionaries(self.id, self.columns.clone()))
+    }
+
+    fn path(directory: &Path, segment: StaticFileSegment, id: u32, column: usize) -> PathBuf {
+        directory
+            .join(DICTIONARIES_DIR)
+            .join(format!("{}_v{id}_{column}.{DICTIONARY_FILE_EXTENSION}", segment.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segment_dictionaries_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let segment = StaticFileSegment::Headers;
+        assert_eq!(SegmentDictionaries::latest_id(dir.path(), segment).unwrap(), None);
+
+        for id in [1, 3, 2] {
+            let dictionaries = SegmentDictionaries {
+                segment,
+                id,
+                columns: (0..segment.columns()).map(|column| vec![id as u8; column + 1]).collect(),
+            };
+            dictionaries.save(dir.path()).unwrap();
+            assert_eq!(SegmentDictionaries::load(dir.path(), segment, id).unwrap(), dictionaries);
+        }
+
+        assert_eq!(SegmentDictionaries::latest_id(dir.path(), segment).unwrap(), Some(3));
+        assert_eq!(
+            SegmentDictionaries::latest_id(dir.path(), StaticFileSegment::Receipts).unwrap(),
+            None
+        );
+    }
+}
@@ -24,6 +24,7 @@ use std::{
     fmt::Debug,
     ops::{Deref, RangeBounds, RangeInclusive},
     sync::Arc,
+    time::Instant,
 };
 /// Provider over a specific `NippyJar` and range.
 #[derive(Debug)]
@@ -87,6 +88,16 @@ impl<'a, N: NodePrimitives> StaticFileJarProvider<'a, N> {
         self
     }
 
+    /// Runs `read`, recording its duration against the storage tier of the static file.
+    fn timed_read<T>(&self, read: impl FnOnce() -> ProviderResult<T>) -> ProviderResult<T> {
+        let Some(metrics) = &self.metrics else { return read() };
+
+        let start = Instant::now();
+        let result = read();
+        metrics.record_tier_read(self.tier(), start.elapsed());
+        result
+    }
+
     /// Returns the total size of the data and offsets files (from the in-memory mmap).
     pub fn size(&self) -> usize {
         self.jar.value().size()
@@ -154,41 +165,47 @@ impl<N: NodePrimitives<BlockHeader: Value>> HeaderProvider for StaticFileJarProv
     type Header = N::BlockHeader;
 
     fn header(&self, block_hash: BlockHash) -> ProviderResult<Option<Self::Header>> {
-        Ok(self
-            .cursor()?
-            .get_two::<HeaderWithHashMask<Self::Header>>((&block_hash).into())?
-            .filter(|(_, hash)| hash == &block_hash)
-            .map(|(header, _)| header))
+        self.timed_read(|| {
+            Ok(self
+                .cursor()?
+                .get_two::<HeaderWithHashMask<Self::Header>>((&block_hash).into())?
+                .filter(|(_, hash)| hash == &block_hash)
+                .map(|(header, _)| header))
+        })
     }
 
     fn header_by_number(&self, num: BlockNumber) -> ProviderResult<Option<Self::Header>> {
-        self.cursor()?.get_one::<HeaderMask<Self::Header>>(num.into())
+        self.timed_read(|| self.cursor()?.get_one::<HeaderMask<Self::Header>>(num.into()))
     }
 
     fn headers_range(
         &self,
         range: impl RangeBounds<BlockNumber>,
     ) -> ProviderResult<Vec<Self::Header>> {
-        let mut cursor = self.cursor()?;
-        let mut headers = Vec::with_capacity(range_size_hint(&range).unwrap_or(1024));
+        self.timed_read(|| {
+            let mut cursor = self.cursor()?;
+            let mut headers = Vec::with_capacity(range_size_hint(&range).unwrap_or(1024));
 
-        for num in to_range(range) {
-            if let Some(header) = cursor.get_one::<HeaderMask<Self::Header>>(num.into())? {
-                headers.push(header);
+            for num in to_range(range) {
+                if let Some(header) = cursor.get_one::<HeaderMask<Self::Header>>(num.into())? {
+                    headers.push(header);
+                }
             }
-        }
 
-        Ok(headers)
+            Ok(headers)
+        })
     }
 
     fn sealed_header(
         &self,
         number: BlockNumber,
     ) -> ProviderResult<Option<SealedHeader<Self::Header>>> {
-        Ok(self
-            .cursor()?
-            .get_two::<HeaderWithHashMask<Self::Header>>(number.into())?
-            .map(|(header, hash)| SealedHeader::new(header, hash)))
+        self.timed_read(|| {
+            Ok(self
+                .cursor()?
+                .get_two::<HeaderWithHashMask<Self::Header>>(number.into())?
+                .map(|(header, hash)| SealedHeader::new(header, hash)))
+        })
     }
 
     fn sealed_headers_while(
@@ -196,27 +213,29 @@ impl<N: NodePrimitives<BlockHeader: Value>> HeaderProvider for StaticFileJarProv
         range: impl RangeBounds<BlockNumber>,
         mut predicate: impl FnMut(&SealedHeader<Self::Header>) -> bool,
     ) -> ProviderResult<Vec<SealedHeader<Self::Header>>> {
-        let mut cursor = self.cursor()?;
-        let mut headers = Vec::with_capacity(range_size_hint(&range).unwrap_or(1024));
-
-        for number in to_range(range) {
-            if let Some((header, hash)) =
-                cursor.get_two::<HeaderWithHashMask<Self::Header>>(number.into())?
-            {
-                let sealed = SealedHeader::new(header, hash);
-                if !predicate(&sealed) {
-                    break
+        self.timed_read(|| {
+            let mut cursor = self.cursor()?;
+            let mut headers = Vec::with_capacity(range_size_hint(&range).unwrap_or(1024));
+
+            for number in to_range(range) {
+                if let Some((header, hash)) =
+                    cursor.get_two::<HeaderWithHashMask<Self::Header>>(number.into())?
+                {
+                    let sealed = SealedHeader::new(header, hash);
+                    if !predicate(&sealed) {
+                        break
+                    }
+                    headers.push(sealed);
                 }
-                headers.push(sealed);
             }
-        }
-        Ok(headers)
+            Ok(headers)
+        })
     }
 }
 
 impl<N: NodePrimitives> BlockHashReader for StaticFileJarProvider<'_, N> {
     fn block_hash(&self, number: u64) -> ProviderResult<Option<B256>> {
-        self.cursor()?.get_one::<BlockHashMask>(number.into())
+        self.timed_read(|| self.cursor()?.get_one::<BlockHashMask>(number.into()))
     }
 
     fn canonical_hashes_range(
@@ -224,15 +243,17 @@ impl<N: NodePrimitives> BlockHashReader for StaticFileJarProvider<'_, N> {
         start: BlockNumber,
         end: BlockNumber,
     ) -> ProviderResult<Vec<B256>> {
-        let mut cursor = self.cursor()?;
-        let mut hashes = Vec::with_capacity((end - start) as usize);
+        self.timed_read(|| {
+            let mut cursor = self.cursor()?;
+            let mut hashes = Vec::with_capacity((end - start) as usize);
 
-        for number in start..end {
-            if let Some(hash) = cursor.get_one::<BlockHashMask>(number.into())? {
-                hashes.push(hash)
+            for number in start..end {
+                if let Some(hash) = cursor.get_one::<BlockHashMask>(number.into())? {
+                    hashes.push(hash)
+                }
             }
-        }
-        Ok(hashes)
+            Ok(hashes)
+        })
     }
 }
 
@@ -275,18 +296,20 @@ impl<N: NodePrimitives<SignedTx: Decompress + SignedTransaction>> TransactionsPr
     }
 
     fn transaction_by_id(&self, num: TxNumber) -> ProviderResult<Option<Self::Transaction>> {
-        self.cursor()?.get_one::<TransactionMask<Self::Transaction>>(num.into())
+        self.timed_read(|| self.cursor()?.get_one::<TransactionMask<Self::Transaction>>(num.into()))
     }
 
     fn transaction_by_id_unhashed(
         &self,
         num: TxNumber,
     ) -> ProviderResult<Option<Self::Transaction>> {
-        self.cursor()?.get_one::<TransactionMask<Self::Transaction>>(num.into())
+        self.timed_read(|| self.cursor()?.get_one::<TransactionMask<Self::Transaction>>(num.into()))
     }
 
     fn transaction_by_hash(&self, hash: TxHash) -> ProviderResult<Option<Self::Transaction>> {
-        self.cursor()?.get_one::<TransactionMask<Self::Transaction>>((&hash).into())
+        self.timed_read(|| {
+            self.cursor()?.get_one::<TransactionMask<Self::Transaction>>((&hash).into())
+        })
     }
 
     fn transaction_by_hash_with_meta(
@@ -319,34 +342,40 @@ impl<N: NodePrimitives<SignedTx: Decompress + SignedTransaction>> TransactionsPr
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Self::Transaction>> {
-        let mut cursor = self.cursor()?;
-        let mut txs = Vec::with_capacity(range_size_hint(&range).unwrap_or(1024));
-
-        for num in to_range(range) {
-            if let Some(tx) = cursor.get_one::<TransactionMask<Self::Transaction>>(num.into())? {
-                txs.push(tx)
+        self.timed_read(|| {
+            let mut cursor = self.cursor()?;
+            let mut txs = Vec::with_capacity(range_size_hint(&range).unwrap_or(1024));
+
+            for num in to_range(range) {
+                if let Some(tx) =
+                    cursor.get_one::<TransactionMask<Self::Transaction>>(num.into())?
+                {
+                    txs.push(tx)
+                }
             }
-        }
-        Ok(txs)
+            Ok(txs)
+        })
     }
 
     fn senders_by_tx_range(
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Address>> {
-        let mut cursor = self.cursor()?;
-        let mut senders = Vec::with_capacity(range_size_hint(&range).unwrap_or(1024));
+        self.timed_read(|| {
+            let mut cursor = self.cursor()?;
+            let mut senders = Vec::with_capacity(range_size_hint(&range).unwrap_or(1024));
 
-        for num in to_range(range) {
-            if let Some(tx) = cursor.get_one::<TransactionSenderMask>(num.into())? {
-                senders.push(tx)
+            for num in to_range(range) {
+                if let Some(tx) = cursor.get_one::<TransactionSenderMask>(num.into())? {
+                    senders.push(tx)
+                }
             }
-        }
-        Ok(senders)
+            Ok(senders)
+        })
     }
 
     fn transaction_sender(&self, id: TxNumber) -> ProviderResult<Option<Address>> {
-        self.cursor()?.get_one::<TransactionSenderMask>(id.into())
+        self.timed_read(|| self.cursor()?.get_one::<TransactionSenderMask>(id.into()))
     }
 }
 
@@ -356,7 +385,7 @@ impl<N: NodePrimitives<SignedTx: Decompress + SignedTransaction, Receipt: Decomp
     type Receipt = N::Receipt;
 
     fn receipt(&self, num: TxNumber) -> ProviderResult<Option<Self::Receipt>> {
-        self.cursor()?.get_one::<ReceiptMask<Self::Receipt>>(num.into())
+        self.timed_read(|| self.cursor()?.get_one::<ReceiptMask<Self::Receipt>>(num.into()))
     }
 
     fn receipt_by_hash(&self, hash: TxHash) -> ProviderResult<Option<Self::Receipt>> {
@@ -381,15 +410,17 @@ impl<N: NodePrimitives<SignedTx: Decompress + SignedTransaction, Receipt: Decomp
         &self,
         range: impl RangeBounds<TxNumber>,
     ) -> ProviderResult<Vec<Self::Receipt>> {
-        let mut cursor = self.cursor()?;
-        let mut receipts = Vec::with_capacity(range_size_hint(&range).unwrap_or(1024));
+        self.timed_read(|| {
+            let mut cursor = self.cursor()?;
+            let mut receipts = Vec::with_capacity(range_size_hint(&range).unwrap_or(1024));
 
-        for num in to_range(range) {
-            if let Some(tx) = cursor.get_one::<ReceiptMask<Self::Receipt>>(num.into())? {
-                receipts.push(tx)
+            for num in to_range(range) {
+                if let Some(tx) = cursor.get_one::<ReceiptMask<Self::Receipt>>(num.into())? {
+                    receipts.push(tx)
+                }
             }
-        }
-        Ok(receipts)
+            Ok(receipts)
+        })
     }
 
     fn receipts_by_block_range(
@@ -1,6 +1,9 @@
 use super::{
-    metrics::StaticFileProviderMetrics, writer::StaticFileWriters, LoadedJar,
-    StaticFileJarProvider, StaticFileProviderRW, StaticFileProviderRWRefMut,
+    metrics::StaticFileProviderMetrics,
+    tier::{current_tier, target_tier},
+    writer::StaticFileWriters,
+    LoadedJar, SegmentDictionaries, StaticFileJarProvider, StaticFileProviderRW,
+    StaticFileProviderRWRefMut, StaticFileTier,
 };
 use crate::{
     changeset_walker::{StaticFileAccountChangesetWalker, StaticFileStorageChangesetWalker},
@@ -10,31 +13,41 @@ use crate::{
 };
 use alloy_consensus::{transaction::TransactionMeta, Header};
 use alloy_eips::{eip2718::Encodable2718, BlockHashOrNumber};
-use alloy_primitives::{b256, keccak256, Address, BlockHash, BlockNumber, TxHash, TxNumber, B256};
+use alloy_primitives::{
+    b256, keccak256, Address, BlockHash, BlockNumber, TxHash, TxNumber, B256, U256,
+};
 use notify::{RecommendedWatcher, RecursiveMode, Watcher};
-use parking_lot::RwLock;
+use parking_lot::{ArcMutexGuard, Mutex, MutexGuard, RawMutex, RwLock};
 use hanzo_evm_chain_state::ExecutedBlock;
 use hanzo_evm_chainspec::{ChainInfo, ChainSpecProvider, EthChainSpec, NamedChain};
+use hanzo_evm_codecs::Compact;
 use hanzo_evm_db::{
     lockfile::StorageLock,
     static_file::{
         iter_static_files, BlockHashMask, HeaderMask, HeaderWithHashMask, ReceiptMask,
-        StaticFileCursor, StorageChangesetMask, TransactionMask, TransactionSenderMask,
+        StateSnapshotMask, StaticFileCursor, StorageChangesetMask, TransactionMask,
+        TransactionSenderMask,
     },
 };
 use hanzo_evm_db_api::{
     cursor::DbCursorRO,
-    models::{AccountBeforeTx, BlockNumberAddress, StorageBeforeTx, StoredBlockBodyIndices},
+    models::{
+        AccountBeforeTx, BlockNumberAddress, StateSnapshotEntry, StorageBeforeTx,
+        StoredBlockBodyIndices,
+    },
     table::{Decompress, Table, Value},
     tables,
     transaction::DbTx,
 };
 use hanzo_evm_ethereum_primitives::{Receipt, TransactionSigned};
-use hanzo_evm_nippy_jar::{NippyJar, NippyJarChecker, CONFIG_FILE_EXTENSION};
+use hanzo_evm_nippy_jar::{
+    compression::{train_dictionary, Compressors},
+    NippyJar, NippyJarChecker, NippyJarCursor, CONFIG_FILE_EXTENSION,
+};
 use hanzo_evm_node_types::NodePrimitives;
 use hanzo_evm_primitives_traits::{
-    dashmap::DashMap, AlloyBlockHeader as _, BlockBody as _, RecoveredBlock, SealedHeader,
-    SignedTransaction, StorageSlotKey,
+    dashmap::DashMap, Account, AlloyBlockHeader as _, BlockBody as _, RecoveredBlock,
+    SealedHeader, SignedTransaction, StorageSlotKey,
 };
 use hanzo_evm_prune_types::PruneSegment;
 use hanzo_evm_stages_types::PipelineTarget;
@@ -48,6 +61,7 @@ use reth_storage_api::{
 };
 use hanzo_evm_storage_errors::provider::{ProviderError, ProviderResult, StaticFileWriterError};
 use std::{
+    borrow::Cow,
     collections::BTreeMap,
     fmt::Debug,
     ops::{Bound, Deref, Range, RangeBounds, RangeInclusive},
@@ -56,6 +70,11 @@ use std::{
 };
 use tracing::{debug, info, info_span, instrument, trace, warn};
 
+#[cfg(unix)]
+use std::os::unix::fs::symlink as symlink_file;
+#[cfg(windows)]
+use std::os::windows::fs::symlink_file;
+
 /// Alias type for a map that can be queried for block or transaction ranges. It uses `u64` to
 /// represent either a block or a transaction number end of a static file range.
 type SegmentRanges = BTreeMap<u64, SegmentRangeInclusive>;
@@ -128,6 +147,8 @@ pub struct StaticFileProviderBuilder<P> {
     blocks_per_file: StaticFileMap<u64>,
     path: P,
     genesis_block_number: u64,
+    state_snapshot_interval: Option<u64>,
+    tiers: Vec<StaticFileTier>,
 }
 
 impl<P: AsRef<Path>> StaticFileProviderBuilder<P> {
@@ -139,6 +160,8 @@ impl<P: AsRef<Path>> StaticFileProviderBuilder<P> {
             blocks_per_file: Default::default(),
             use_metrics: false,
             genesis_block_number: 0,
+            state_snapshot_interval: None,
+            tiers: Vec::new(),
         }
     }
 
@@ -150,6 +173,8 @@ impl<P: AsRef<Path>> StaticFileProviderBuilder<P> {
             blocks_per_file: Default::default(),
             use_metrics: false,
             genesis_block_number: 0,
+            state_snapshot_interval: None,
+            tiers: Vec::new(),
         }
     }
 
@@ -215,20 +240,54 @@ impl<P: AsRef<Path>> StaticFileProviderBuilder<P> {
         self
     }
 
+    /// Enables [`StaticFileSegment::StateSnapshots`], writing a snapshot of the state once the
+    /// previous one is at least `interval` blocks old.
+    pub const fn with_state_snapshot_interval(mut self, interval: Option<u64>) -> Self {
+        self.state_snapshot_interval = interval;
+        self
+    }
+
+    /// Sets the storage tiers that finished static files are moved to once they are old enough.
+    ///
+    /// See [`StaticFileProvider::move_to_tiers`].
+    pub fn with_tiers(mut self, mut tiers: Vec<StaticFileTier>) -> Self {
+        tiers.sort_by_key(|tier| tier.min_age);
+        self.tiers = tiers;
+        self
+    }
+
     /// Builds the final [`StaticFileProvider`] and initializes the index.
     pub fn build<N: NodePrimitives>(self) -> ProviderResult<StaticFileProvider<N>> {
         let mut provider = StaticFileProviderInner::new(self.path, self.access)?;
         if self.use_metrics {
-            provider.metrics = Some(Arc::new(StaticFileProviderMetrics::default()));
+            provider.metrics =
+                Some(Arc::new(StaticFileProviderMetrics::default().with_tiers(self.tiers.len())));
         }
 
         for (segment, blocks_per_file) in *self.blocks_per_file {
             provider.blocks_per_file.insert(segment, blocks_per_file);
         }
         provider.genesis_block_number = self.genesis_block_number;
+        provider.state_snapshot_interval = self.state_snapshot_interval;
+        provider.tiers = self
+            .tiers
+            .into_iter()
+            .map(|tier| {
+                let path = std::path::absolute(&tier.path).map_err(ProviderError::other)?;
+                Ok(StaticFileTier { path, ..tier })
+            })
+            .collect::<ProviderResult<_>>()?;
 
         let provider = StaticFileProvider(Arc::new(provider));
         provider.initialize_index()?;
+
+        // Catch up on files that aged past a tier threshold or weren't recompressed yet while the
+        // node was stopped
+        if provider.access.is_read_write() {
+            provider.spawn_tier_migration();
+            provider.spawn_recompression();
+        }
+
         Ok(provider)
     }
 }
@@ -393,6 +452,20 @@ pub struct StaticFileProviderInner<N> {
     _lock_file: Option<StorageLock>,
     /// Genesis block number, default is 0;
     genesis_block_number: u64,
+    /// Minimum number of blocks between two state snapshots, [`None`] if state snapshots are
+    /// disabled.
+    state_snapshot_interval: Option<u64>,
+    /// Storage tiers for old static files, sorted by [`StaticFileTier::min_age`].
+    tiers: Vec<StaticFileTier>,
+    /// Held while static files are being moved to the storage tiers or recompressed.
+    tier_migration_lock: Mutex<()>,
+    /// Held while static files are being recompressed with trained dictionaries.
+    recompression_lock: Mutex<()>,
+    /// Held while the files of a static file are switched to a new data version or deleted.
+    jar_files_lock: Mutex<()>,
+    /// Held while a state snapshot is written, and by read-write providers from the unwind of
+    /// [`StaticFileSegment::StateSnapshots`] until their commit.
+    state_snapshots_lock: Arc<Mutex<()>>,
 }
 
 impl<N: NodePrimitives> StaticFileProviderInner<N> {
@@ -420,6 +493,12 @@ impl<N: NodePrimitives> StaticFileProviderInner<N> {
             blocks_per_file,
             _lock_file,
             genesis_block_number: 0,
+            state_snapshot_interval: None,
+            tiers: Vec::new(),
+            tier_migration_lock: Mutex::new(()),
+            recompression_lock: Mutex::new(()),
+            jar_files_lock: Mutex::new(()),
+            state_snapshots_lock: Default::default(),
         };
 
         Ok(provider)
@@ -497,6 +576,29 @@ impl<N: NodePrimitives> StaticFileProviderInner<N> {
     pub const fn genesis_block_number(&self) -> u64 {
         self.genesis_block_number
     }
+
+    /// Returns the minimum number of blocks between two state snapshots, [`None`] if state
+    /// snapshots are disabled.
+    pub const fn state_snapshot_interval(&self) -> Option<u64> {
+        self.state_snapshot_interval
+    }
+
+    /// Locks [`StaticFileSegment::StateSnapshots`], so that no state snapshot is written from a
+    /// state that is being unwound.
+    pub fn lock_state_snapshots(&self) -> ArcMutexGuard<RawMutex, ()> {
+        self.state_snapshots_lock.lock_arc()
+    }
+
+    /// Locks the files of all finished static files, so that none of them is moved to a storage
+    /// tier or recompressed while the guard is held.
+    pub(crate) fn lock_static_file_files(&self) -> MutexGuard<'_, ()> {
+        self.tier_migration_lock.lock()
+    }
+
+    /// Returns `true` if a state snapshot is being written or unwound.
+    pub fn is_state_snapshots_locked(&self) -> bool {
+        self.state_snapshots_lock.is_locked()
+    }
 }
 
 impl<N: NodePrimitives> StaticFileProvider<N> {
@@ -976,6 +1078,7 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         let fixed_block_range = self.find_fixed_range(segment, block);
         let key = (fixed_block_range.end(), segment);
         let file = self.path.join(segment.filename(&fixed_block_range));
+        let _lock = self.jar_files_lock.lock();
         let jar = if let Some((_, jar)) = self.map.remove(&key) {
             jar.jar
         } else {
@@ -1055,7 +1158,8 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             trace!(target: "providers::static_file", ?segment, ?fixed_block_range, "Creating jar from scratch");
             let path = self.path.join(segment.filename(fixed_block_range));
             let jar = NippyJar::load(&path).map_err(ProviderError::other)?;
-            self.map.entry(key).insert(LoadedJar::new(jar)?).downgrade().into()
+            let tier = current_tier(&self.tiers, &jar.data_path());
+            self.map.entry(key).insert(LoadedJar::new(jar, tier)?).downgrade().into()
         };
 
         if let Some(metrics) = &self.metrics {
@@ -1240,7 +1344,8 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
 
                 // Update the cached provider.
                 debug!(target: "providers::static_file", ?segment, "Inserting updated jar into cache");
-                self.map.insert((fixed_range.end(), segment), LoadedJar::new(jar)?);
+                let tier = current_tier(&self.tiers, &jar.data_path());
+                self.map.insert((fixed_range.end(), segment), LoadedJar::new(jar, tier)?);
 
                 // Delete any cached provider that no longer has an associated jar.
                 debug!(target: "providers::static_file", ?segment, "Cleaning up jar map");
@@ -1563,6 +1668,13 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                 }
                 true
             }
+            StaticFileSegment::StateSnapshots => {
+                if self.get_highest_static_file_block(segment).is_none() {
+                    debug!(target: "evm::providers::static_file", ?segment, "Skipping state snapshots segment: no state snapshots written");
+                    return false
+                }
+                true
+            }
         }
     }
 
@@ -1699,6 +1811,9 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                     highest_block,
                     |key| key.block_number(),
                 ),
+            StaticFileSegment::StateSnapshots => {
+                self.ensure_state_snapshot_invariants(provider, highest_block)
+            }
         }
     }
 
@@ -1827,7 +1942,8 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                         }
                         StaticFileSegment::Headers |
                         StaticFileSegment::AccountChangeSets |
-                        StaticFileSegment::StorageChangeSets => {
+                        StaticFileSegment::StorageChangeSets |
+                        StaticFileSegment::StateSnapshots => {
                             unreachable!()
                         }
                     }
@@ -1841,6 +1957,9 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             StaticFileSegment::StorageChangeSets => {
                 writer.prune_storage_changesets(checkpoint_block_number)?;
             }
+            StaticFileSegment::StateSnapshots => {
+                writer.prune_state_snapshots(checkpoint_block_number)?;
+            }
         }
 
         debug!(target: "evm::providers::static_file", "Committing writer after pruning");
@@ -1943,6 +2062,42 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         Ok(None)
     }
 
+    /// Ensures that state snapshots don't go beyond the execution stage checkpoint.
+    ///
+    /// State snapshots are derived from the database state, so any extra blocks are pruned and
+    /// missing ones are filled in on the next write. This never requests an unwind.
+    fn ensure_state_snapshot_invariants<Provider>(
+        &self,
+        provider: &Provider,
+        highest_static_file_block: Option<BlockNumber>,
+    ) -> ProviderResult<Option<BlockNumber>>
+    where
+        Provider: DBProvider + StageCheckpointReader,
+    {
+        let segment = StaticFileSegment::StateSnapshots;
+        let Some(highest_static_file_block) = highest_static_file_block else { return Ok(None) };
+
+        let checkpoint_block_number = provider
+            .get_stage_checkpoint(segment.to_stage_id())?
+            .unwrap_or_default()
+            .block_number;
+
+        if checkpoint_block_number < highest_static_file_block {
+            info!(
+                target: "evm::providers",
+                ?segment,
+                from = highest_static_file_block,
+                to = checkpoint_block_number,
+                "Unwinding static file segment."
+            );
+            let mut writer = self.latest_writer(segment)?;
+            writer.prune_state_snapshots(checkpoint_block_number)?;
+            writer.commit()?;
+        }
+
+        Ok(None)
+    }
+
     /// Returns the earliest available block number that has not been expired and is still
     /// available.
     ///
@@ -2172,6 +2327,332 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         &self.path
     }
 
+    /// Trains a zstd dictionary for every column of `segment` from the rows of its static files
+    /// overlapping `block_range`.
+    ///
+    /// At most `max_samples_size` bytes of rows are sampled per column.
+    pub fn train_zstd_dictionaries(
+        &self,
+        segment: StaticFileSegment,
+        block_range: RangeInclusive<BlockNumber>,
+        max_dict_size: usize,
+        max_samples_size: usize,
+    ) -> ProviderResult<Vec<Vec<u8>>> {
+        let mut samples = vec![Vec::new(); segment.columns()];
+        let mut samples_size = vec![0; segment.columns()];
+
+        let static_files = iter_static_files(&self.path).map_err(ProviderError::other)?;
+        'files: for (range, header) in static_files.get(segment).into_iter().flatten() {
+            if range.end() < *block_range.start() || range.start() > *block_range.end() {
+                continue
+            }
+
+            let path = self.path.join(segment.filename(&header.expected_block_range()));
+            let jar = NippyJar::<SegmentHeader>::load(&path).map_err(ProviderError::other)?;
+            let mut cursor = NippyJarCursor::new(&jar).map_err(ProviderError::other)?;
+            while let Some(row) = cursor.next_row().map_err(ProviderError::other)? {
+                for (column, value) in row.into_iter().enumerate() {
+                    if samples_size[column] < max_samples_size {
+                        let value = Self::without_fixed_dictionary(segment, value).into_owned();
+                        samples_size[column] += value.len();
+                        samples[column].push(value);
+                    }
+                }
+
+                if samples_size.iter().all(|size| *size >= max_samples_size) {
+                    break 'files
+                }
+            }
+        }
+
+        debug!(target: "providers::static_file", ?segment, ?samples_size, "Training dictionaries");
+
+        samples
+            .into_iter()
+            .map(|column| train_dictionary(column, max_dict_size).map_err(ProviderError::other))
+            .collect()
+    }
+
+    /// Returns `value` of a column of `segment` as encoded without the fixed zstd dictionaries of
+    /// the codecs, which the trained dictionaries of the segment replace.
+    fn without_fixed_dictionary(segment: StaticFileSegment, value: &[u8]) -> Cow<'_, [u8]> {
+        fn reencode<T: Compact>(value: &[u8]) -> Vec<u8> {
+            let (decoded, _) = T::from_compact(value, value.len());
+            let mut buf = Vec::with_capacity(value.len());
+            decoded.to_compact_uncompressed(&mut buf);
+            buf
+        }
+
+        match segment {
+            StaticFileSegment::Transactions => Cow::Owned(reencode::<N::SignedTx>(value)),
+            StaticFileSegment::Receipts => Cow::Owned(reencode::<N::Receipt>(value)),
+            _ => Cow::Borrowed(value),
+        }
+    }
+
+    /// Recompresses the static file with the given fixed block range using `dictionaries`, unless
+    /// it's already compressed with them.
+    ///
+    /// The rows are written to the next data version of the static file, which readers only
+    /// switch to once its configuration is committed. The recompression is discarded if the
+    /// static file was written to or deleted meanwhile.
+    ///
+    /// Returns `true` if the static file was recompressed. Must only be called for static files
+    /// that are no longer written to.
+    pub fn recompress_static_file(
+        &self,
+        fixed_block_range: &SegmentRangeInclusive,
+        dictionaries: &SegmentDictionaries,
+    ) -> ProviderResult<bool> {
+        let segment = dictionaries.segment;
+        let path = self.path.join(segment.filename(fixed_block_range));
+        let jar = NippyJar::<SegmentHeader>::load(&path).map_err(ProviderError::other)?;
+
+        if let Some(Compressors::Zstd(zstd)) = jar.compressor() &&
+            zstd.dictionary_id() == Some(dictionaries.id)
+        {
+            // Leftovers of a recompression interrupted after its commit
+            jar.remove_stale_files().map_err(ProviderError::other)?;
+            return Ok(false)
+        }
+
+        let data_stamp = |jar: &NippyJar<SegmentHeader>| {
+            std::fs::metadata(jar.data_path())
+                .and_then(|metadata| Ok((metadata.len(), metadata.modified()?)))
+                .ok()
+        };
+        let stamp = data_stamp(&jar);
+
+        let recompressed = jar
+            .recompress(dictionaries.compressor(), |_, value| {
+                Ok(Self::without_fixed_dictionary(segment, value))
+            })
+            .map_err(ProviderError::other)?;
+
+        {
+            let writer = self.writers.lock(segment);
+            let _lock = self.jar_files_lock.lock();
+
+            let is_written = writer.as_ref().is_some_and(|writer| {
+                writer.user_header().expected_block_range() == *fixed_block_range
+            });
+            let is_unchanged = NippyJar::<SegmentHeader>::load(&path).is_ok_and(|current| {
+                current.rows() == jar.rows() &&
+                    current.data_version() == jar.data_version() &&
+                    data_stamp(&current) == stamp
+            });
+            if is_written || !is_unchanged {
+                debug!(
+                    target: "providers::static_file",
+                    ?segment,
+                    %fixed_block_range,
+                    "Static file changed while being recompressed"
+                );
+                recompressed.discard_recompressed().map_err(ProviderError::other)?;
+                return Ok(false)
+            }
+
+            recompressed.commit_recompressed().map_err(ProviderError::other)?;
+        }
+
+        self.remove_cached_provider(segment, fixed_block_range.end());
+        recompressed.remove_stale_files().map_err(ProviderError::other)?;
+
+        Ok(true)
+    }
+
+    /// Recompresses every finished static file of the segment of `dictionaries` with them, see
+    /// [`StaticFileProvider::recompress_static_file`].
+    ///
+    /// Returns the number of recompressed static files.
+    pub fn recompress_segment(&self, dictionaries: &SegmentDictionaries) -> ProviderResult<usize> {
+        let _lock = self.recompression_lock.lock();
+
+        let segment = dictionaries.segment;
+        let Some(highest_block) = self.get_highest_static_file_block(segment) else { return Ok(0) };
+
+        let static_files = iter_static_files(&self.path).map_err(ProviderError::other)?;
+        let mut recompressed = 0;
+        for (block_range, header) in static_files.get(segment).into_iter().flatten() {
+            let fixed_block_range = header.expected_block_range();
+
+            // The latest static file is still being written to
+            if block_range.end() != fixed_block_range.end() ||
+                fixed_block_range.end() >= highest_block
+            {
+                continue
+            }
+
+            // Static files aren't moved to another tier while their files are replaced
+            let _tier_lock = self.tier_migration_lock.lock();
+            if self.recompress_static_file(&fixed_block_range, dictionaries)? {
+                recompressed += 1;
+
+                debug!(
+                    target: "providers::static_file",
+                    ?segment,
+                    %fixed_block_range,
+                    id = dictionaries.id,
+                    "Recompressed static file"
+                );
+            }
+        }
+
+        Ok(recompressed)
+    }
+
+    /// Recompresses the finished static files of every segment with the latest dictionaries
+    /// trained for it, if any.
+    ///
+    /// Returns the number of recompressed static files.
+    pub fn recompress_static_files(&self) -> ProviderResult<usize> {
+        let mut recompressed = 0;
+        for segment in StaticFileSegment::iter() {
+            let Some(id) = SegmentDictionaries::latest_id(&self.path, segment)? else { continue };
+            let dictionaries = SegmentDictionaries::load(&self.path, segment, id)?;
+            recompressed += self.recompress_segment(&dictionaries)?;
+        }
+
+        Ok(recompressed)
+    }
+
+    /// Runs [`StaticFileProvider::recompress_static_files`] on a separate thread, unless a
+    /// previous run is still in progress.
+    pub(crate) fn spawn_recompression(&self) {
+        if self.recompression_lock.is_locked() {
+            return
+        }
+
+        let provider = self.clone();
+        hanzo_evm_tasks::spawn_os_thread("sf-recompress", move || {
+            match provider.recompress_static_files() {
+                Ok(0) => {}
+                Ok(recompressed) => {
+                    info!(target: "providers::static_file", recompressed, "Recompressed static files")
+                }
+                Err(err) => {
+                    warn!(target: "providers::static_file", %err, "Failed to recompress static files")
+                }
+            }
+        });
+    }
+
+    /// Moves every finished static file that is old enough to the storage tier configured for its
+    /// age, leaving a symlink to it in the static files directory.
+    ///
+    /// The age of a static file is the number of blocks between the end of its block range and
+    /// the highest block of its segment. Static files are never moved back to a lower tier.
+    ///
+    /// Returns the number of moved static files.
+    pub fn move_to_tiers(&self) -> ProviderResult<usize> {
+        if self.tiers.is_empty() {
+            return Ok(0)
+        }
+        let _lock = self.tier_migration_lock.lock();
+
+        let static_files = iter_static_files(&self.path).map_err(ProviderError::other)?;
+        let mut moved = 0;
+        for segment in StaticFileSegment::iter() {
+            let Some(highest_block) = self.get_highest_static_file_block(segment) else { continue };
+
+            for (block_range, header) in static_files.get(segment).into_iter().flatten() {
+                let fixed_block_range = header.expected_block_range();
+
+                // The latest static file is still being written to
+                if block_range.end() != fixed_block_range.end() ||
+                    fixed_block_range.end() >= highest_block
+                {
+                    continue
+                }
+
+                let path = self.path.join(segment.filename(&fixed_block_range));
+                let tier = target_tier(&self.tiers, highest_block - fixed_block_range.end());
+                if tier <= current_tier(&self.tiers, &path) {
+                    continue
+                }
+
+                self.move_static_file_to_tier(&path, tier)?;
+                self.remove_cached_provider(segment, fixed_block_range.end());
+                moved += 1;
+
+                debug!(
+                    target: "providers::static_file",
+                    ?segment,
+                    %fixed_block_range,
+                    tier,
+                    "Moved static file to storage tier"
+                );
+            }
+        }
+
+        Ok(moved)
+    }
+
+    /// Runs [`StaticFileProvider::move_to_tiers`] on a separate thread, unless no tiers are
+    /// configured or a previous run is still in progress.
+    pub(crate) fn spawn_tier_migration(&self) {
+        if self.tiers.is_empty() || self.tier_migration_lock.is_locked() {
+            return
+        }
+
+        let provider = self.clone();
+        hanzo_evm_tasks::spawn_os_thread("sf-tiers", move || match provider.move_to_tiers() {
+            Ok(0) => {}
+            Ok(moved) => {
+                info!(target: "providers::static_file", moved, "Moved static files to tiers")
+            }
+            Err(err) => {
+                warn!(target: "providers::static_file", %err, "Failed to move static files")
+            }
+        });
+    }
+
+    /// Moves all files of the static file at `path` to the storage tier at index `tier`.
+    ///
+    /// Every file is copied under a temporary name and synced before being renamed, and then
+    /// atomically replaced by a symlink, so readers always see a complete file at the original
+    /// path.
+    fn move_static_file_to_tier(&self, path: &Path, tier: usize) -> ProviderResult<()> {
+        let directory = &self.tiers[tier - 1].path;
+        hanzo_evm_fs_util::create_dir_all(directory).map_err(ProviderError::other)?;
+
+        let jar = NippyJar::<SegmentHeader>::load(path).map_err(ProviderError::other)?;
+        for file in [
+            jar.data_path(),
+            jar.offsets_path(),
+            jar.config_path(),
+            jar.changeset_offsets_path(),
+        ] {
+            if !file.exists() {
+                continue
+            }
+
+            let file_name = file.file_name().expect("static file path has a file name");
+            let source = std::fs::read_link(&file).unwrap_or_else(|_| file.clone());
+            let target = directory.join(file_name);
+
+            let tmp = directory.join(format!("{}.tmp", file_name.to_string_lossy()));
+            std::fs::copy(&source, &tmp).map_err(ProviderError::other)?;
+            hanzo_evm_fs_util::open(&tmp)
+                .map_err(ProviderError::other)?
+                .sync_all()
+                .map_err(ProviderError::other)?;
+            hanzo_evm_fs_util::rename(&tmp, &target).map_err(ProviderError::other)?;
+
+            let link = self.path.join(format!("{}.link", file_name.to_string_lossy()));
+            let _ = std::fs::remove_file(&link);
+            symlink_file(&target, &link).map_err(ProviderError::other)?;
+            hanzo_evm_fs_util::rename(&link, &file).map_err(ProviderError::other)?;
+
+            // Remove the copy in the previous tier
+            if source != file {
+                hanzo_evm_fs_util::remove_file(&source).map_err(ProviderError::other)?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Retrieves data from the database or static file, wherever it's available.
     ///
     /// # Arguments
@@ -2438,48 +2919,12 @@ impl<N: NodePrimitives> ChangeSetReader for StaticFileProvider<N> {
             return Ok(None);
         };
 
-        let mut cursor = provider.cursor()?;
-        let range = offset.changeset_range();
-        let mut low = range.start;
-        let mut high = range.end;
-
-        while low < high {
-            let mid = low + (high - low) / 2;
-            if let Some(change) =
-                cursor.get_one::<hanzo_evm_db::static_file::AccountChangesetMask>(mid.into())?
-            {
-                if change.address < address {
-                    low = mid + 1;
-                } else {
-                    high = mid;
-                }
-            } else {
-                // This is not expected but means we are out of the range / file somehow, and can't
-                // continue
-                debug!(
-                    target: "providers::static_file",
-                    ?low,
-                    ?mid,
-                    ?high,
-                    ?range,
-                    ?block_number,
-                    ?address,
-                    "Cannot continue binary search for account changeset fetch"
-                );
-                low = range.end;
-                break;
-            }
-        }
-
-        if low < range.end &&
-            let Some(change) = cursor
-                .get_one::<hanzo_evm_db::static_file::AccountChangesetMask>(low.into())?
-                .filter(|change| change.address == address)
-        {
-            return Ok(Some(change));
-        }
-
-        Ok(None)
+        find_account_change(
+            &mut provider.cursor()?,
+            offset.changeset_range(),
+            block_number,
+            address,
+        )
     }
 
     fn account_changesets_range(
@@ -2571,47 +3016,13 @@ impl<N: NodePrimitives> StorageChangeSetReader for StaticFileProvider<N> {
             return Ok(None);
         };
 
-        let mut cursor = provider.cursor()?;
-        let range = offset.changeset_range();
-        let mut low = range.start;
-        let mut high = range.end;
-
-        while low < high {
-            let mid = low + (high - low) / 2;
-            if let Some(change) = cursor.get_one::<StorageChangesetMask>(mid.into())? {
-                match (change.address, change.key).cmp(&(address, storage_key)) {
-                    std::cmp::Ordering::Less => low = mid + 1,
-                    _ => high = mid,
-                }
-            } else {
-                debug!(
-                    target: "providers::static_file",
-                    ?low,
-                    ?mid,
-                    ?high,
-                    ?range,
-                    ?block_number,
-                    ?address,
-                    ?storage_key,
-                    "Cannot continue binary search for storage changeset fetch"
-                );
-                low = range.end;
-                break;
-            }
-        }
-
-        if low < range.end &&
-            let Some(change) = cursor
-                .get_one::<StorageChangesetMask>(low.into())?
-                .filter(|change| change.address == address && change.key == storage_key)
-        {
-            return Ok(Some(ChangesetEntry {
-                key: StorageSlotKey::hashed(change.key),
-                value: change.value,
-            }));
-        }
-
-        Ok(None)
+        find_storage_change(
+            &mut provider.cursor()?,
+            offset.changeset_range(),
+            block_number,
+            address,
+            storage_key,
+        )
     }
 
     fn storage_changesets_range(
@@ -2672,6 +3083,208 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
     ) -> StaticFileStorageChangesetWalker<Self> {
         StaticFileStorageChangesetWalker::new(self.clone(), range)
     }
+
+    /// Returns the first change of `address` in the account changesets of the blocks in `range`,
+    /// with its block number.
+    pub fn first_account_change_in_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        address: Address,
+    ) -> ProviderResult<Option<(BlockNumber, AccountBeforeTx)>> {
+        self.find_first_change(
+            StaticFileSegment::AccountChangeSets,
+            range,
+            |cursor, changes, block_number| {
+                find_account_change(cursor, changes, block_number, address)
+            },
+        )
+    }
+
+    /// Returns the first change of `storage_key` of `address` in the storage changesets of the
+    /// blocks in `range`, with its block number.
+    pub fn first_storage_change_in_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        address: Address,
+        storage_key: B256,
+    ) -> ProviderResult<Option<(BlockNumber, ChangesetEntry)>> {
+        self.find_first_change(
+            StaticFileSegment::StorageChangeSets,
+            range,
+            |cursor, changes, block_number| {
+                find_storage_change(cursor, changes, block_number, address, storage_key)
+            },
+        )
+    }
+
+    /// Walks the changesets of `segment` in the blocks of `range` and returns the first change
+    /// `find` locates in the changes of a block, with its block number.
+    ///
+    /// The changeset offsets of each static file are read once, and the changesets of all its
+    /// blocks are searched with a single cursor.
+    fn find_first_change<T>(
+        &self,
+        segment: StaticFileSegment,
+        range: RangeInclusive<BlockNumber>,
+        mut find: impl FnMut(
+            &mut StaticFileCursor<'_>,
+            Range<u64>,
+            BlockNumber,
+        ) -> ProviderResult<Option<T>>,
+    ) -> ProviderResult<Option<(BlockNumber, T)>> {
+        let mut block = *range.start();
+        while block <= *range.end() {
+            let provider = match self.get_segment_provider_for_block(segment, block, None) {
+                Ok(provider) => provider,
+                Err(ProviderError::MissingStaticFileBlock(_, _)) => return Ok(None),
+                Err(err) => return Err(err),
+            };
+            let Some(block_range) = provider.user_header().block_range() else { return Ok(None) };
+
+            let offsets = provider.read_changeset_offsets()?.unwrap_or_default();
+            let mut cursor = provider.cursor()?;
+            let blocks = block.max(block_range.start())..=block_range.end().min(*range.end());
+            for block_number in blocks {
+                let Some(offset) = offsets.get((block_number - block_range.start()) as usize)
+                else {
+                    break
+                };
+                if let Some(change) = find(&mut cursor, offset.changeset_range(), block_number)? {
+                    return Ok(Some((block_number, change)))
+                }
+            }
+
+            block = block_range.end() + 1;
+        }
+
+        Ok(None)
+    }
+
+    /// Returns the first block at or after `block_number` that has a state snapshot.
+    ///
+    /// Returns [`None`] if there is no such snapshot in static files.
+    pub fn state_snapshot_block(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Option<BlockNumber>> {
+        let segment = StaticFileSegment::StateSnapshots;
+        let (Some(lowest), Some(highest)) =
+            (self.get_lowest_range_start(segment), self.get_highest_static_file_block(segment))
+        else {
+            return Ok(None)
+        };
+
+        let mut block = block_number.max(lowest);
+        while block <= highest {
+            let provider = match self.get_segment_provider_for_block(segment, block, None) {
+                Ok(provider) => provider,
+                Err(ProviderError::MissingStaticFileBlock(_, _)) => return Ok(None),
+                Err(err) => return Err(err),
+            };
+            let Some(block_range) = provider.user_header().block_range() else { return Ok(None) };
+
+            let offsets = provider.read_changeset_offsets()?.unwrap_or_default();
+            let skip = block.saturating_sub(block_range.start()) as usize;
+            if let Some(index) =
+                offsets.iter().skip(skip).position(|offset| offset.num_changes() > 0)
+            {
+                return Ok(Some(block_range.start() + (skip + index) as u64))
+            }
+
+            block = block_range.end() + 1;
+        }
+
+        Ok(None)
+    }
+
+    /// Returns the account with the given hashed address from the state snapshot taken at
+    /// `snapshot_block`.
+    pub fn state_snapshot_account(
+        &self,
+        snapshot_block: BlockNumber,
+        hashed_address: B256,
+    ) -> ProviderResult<Option<Account>> {
+        Ok(self.find_state_snapshot_entry(snapshot_block, (hashed_address, None))?.and_then(
+            |entry| match entry {
+                StateSnapshotEntry::Account { account, .. } => Some(account),
+                StateSnapshotEntry::Storage { .. } => None,
+            },
+        ))
+    }
+
+    /// Returns the value of the given hashed storage slot from the state snapshot taken at
+    /// `snapshot_block`.
+    pub fn state_snapshot_storage(
+        &self,
+        snapshot_block: BlockNumber,
+        hashed_address: B256,
+        hashed_slot: B256,
+    ) -> ProviderResult<Option<U256>> {
+        Ok(self
+            .find_state_snapshot_entry(snapshot_block, (hashed_address, Some(hashed_slot)))?
+            .and_then(|entry| match entry {
+                StateSnapshotEntry::Storage { value, .. } => Some(value),
+                StateSnapshotEntry::Account { .. } => None,
+            }))
+    }
+
+    /// Binary searches the state snapshot taken at `snapshot_block` for the entry with the given
+    /// [`StateSnapshotEntry::sort_key`].
+    fn find_state_snapshot_entry(
+        &self,
+        snapshot_block: BlockNumber,
+        key: (B256, Option<B256>),
+    ) -> ProviderResult<Option<StateSnapshotEntry>> {
+        let provider = match self.get_segment_provider_for_block(
+            StaticFileSegment::StateSnapshots,
+            snapshot_block,
+            None,
+        ) {
+            Ok(provider) => provider,
+            Err(ProviderError::MissingStaticFileBlock(_, _)) => return Ok(None),
+            Err(err) => return Err(err),
+        };
+
+        let Some(offset) = provider.read_changeset_offset(snapshot_block)? else {
+            return Ok(None)
+        };
+
+        let mut cursor = provider.cursor()?;
+        let range = offset.changeset_range();
+        let mut low = range.start;
+        let mut high = range.end;
+
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if let Some(entry) = cursor.get_one::<StateSnapshotMask>(mid.into())? {
+                if entry.sort_key() < key {
+                    low = mid + 1;
+                } else {
+                    high = mid;
+                }
+            } else {
+                debug!(
+                    target: "providers::static_file",
+                    ?low,
+                    ?mid,
+                    ?high,
+                    ?range,
+                    ?snapshot_block,
+                    ?key,
+                    "Cannot continue binary search for state snapshot fetch"
+                );
+                return Ok(None)
+            }
+        }
+
+        if low < range.end {
+            return Ok(cursor
+                .get_one::<StateSnapshotMask>(low.into())?
+                .filter(|entry| entry.sort_key() == key))
+        }
+
+        Ok(None)
+    }
 }
 
 impl<N: NodePrimitives<BlockHeader: Value>> HeaderProvider for StaticFileProvider<N> {
@@ -3143,6 +3756,106 @@ impl<N: NodePrimitives> StatsReader for StaticFileProvider<N> {
     }
 }
 
+/// Binary searches the account changeset of `block_number`, stored at `range` of the static file
+/// `cursor` reads, for the change of `address`.
+fn find_account_change(
+    cursor: &mut StaticFileCursor<'_>,
+    range: Range<u64>,
+    block_number: BlockNumber,
+    address: Address,
+) -> ProviderResult<Option<AccountBeforeTx>> {
+    let mut low = range.start;
+    let mut high = range.end;
+
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if let Some(change) =
+            cursor.get_one::<hanzo_evm_db::static_file::AccountChangesetMask>(mid.into())?
+        {
+            if change.address < address {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        } else {
+            // This is not expected but means we are out of the range / file somehow, and can't
+            // continue
+            debug!(
+                target: "providers::static_file",
+                ?low,
+                ?mid,
+                ?high,
+                ?range,
+                ?block_number,
+                ?address,
+                "Cannot continue binary search for account changeset fetch"
+            );
+            low = range.end;
+            break;
+        }
+    }
+
+    if low < range.end &&
+        let Some(change) = cursor
+            .get_one::<hanzo_evm_db::static_file::AccountChangesetMask>(low.into())?
+            .filter(|change| change.address == address)
+    {
+        return Ok(Some(change));
+    }
+
+    Ok(None)
+}
+
+/// Binary searches the storage changeset of `block_number`, stored at `range` of the static file
+/// `cursor` reads, for the change of `storage_key` of `address`.
+fn find_storage_change(
+    cursor: &mut StaticFileCursor<'_>,
+    range: Range<u64>,
+    block_number: BlockNumber,
+    address: Address,
+    storage_key: B256,
+) -> ProviderResult<Option<ChangesetEntry>> {
+    let mut low = range.start;
+    let mut high = range.end;
+
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if let Some(change) = cursor.get_one::<StorageChangesetMask>(mid.into())? {
+            match (change.address, change.key).cmp(&(address, storage_key)) {
+                std::cmp::Ordering::Less => low = mid + 1,
+                _ => high = mid,
+            }
+        } else {
+            debug!(
+                target: "providers::static_file",
+                ?low,
+                ?mid,
+                ?high,
+                ?range,
+                ?block_number,
+                ?address,
+                ?storage_key,
+                "Cannot continue binary search for storage changeset fetch"
+            );
+            low = range.end;
+            break;
+        }
+    }
+
+    if low < range.end &&
+        let Some(change) = cursor
+            .get_one::<StorageChangesetMask>(low.into())?
+            .filter(|change| change.address == address && change.key == storage_key)
+    {
+        return Ok(Some(ChangesetEntry {
+            key: StorageSlotKey::hashed(change.key),
+            value: change.value,
+        }));
+    }
+
+    Ok(None)
+}
+
 /// Calculates the tx hash for the given transaction and its id.
 #[inline]
 fn calculate_hash<T>(
@@ -14,6 +14,8 @@ pub struct StaticFileProviderMetrics {
         (StaticFileSegment, StaticFileProviderOperation),
         StaticFileProviderOperationMetrics,
     >,
+    /// Read metrics per storage tier, the first one being the static files directory.
+    tiers: Vec<StaticFileTierMetrics>,
 }
 
 impl Default for StaticFileProviderMetrics {
@@ -44,11 +46,26 @@ impl Default for StaticFileProviderMetrics {
                     )
                 })
                 .collect(),
+            tiers: vec![StaticFileTierMetrics::new_with_labels(&[("tier", "0")])],
         }
     }
 }
 
 impl StaticFileProviderMetrics {
+    /// Adds read metrics for the given number of storage tiers.
+    pub(crate) fn with_tiers(mut self, tiers: usize) -> Self {
+        self.tiers = (0..=tiers)
+            .map(|tier| StaticFileTierMetrics::new_with_labels(&[("tier", tier.to_string())]))
+            .collect();
+        self
+    }
+
+    pub(crate) fn record_tier_read(&self, tier: usize, duration: Duration) {
+        let tier = self.tiers.get(tier).expect("tier metrics should exist");
+        tier.reads_total.increment(1);
+        tier.read_duration_seconds.record(duration.as_secs_f64());
+    }
+
     pub(crate) fn record_segment(
         &self,
         segment: StaticFileSegment,
@@ -149,3 +166,13 @@ pub(crate) struct StaticFileProviderOperationMetrics {
     /// The time it took to execute the static file jar provider operation that writes data.
     write_duration_seconds: Histogram,
 }
+
+/// Read metrics for a storage tier of static files.
+#[derive(Metrics)]
+#[metrics(scope = "static_files.tier")]
+pub(crate) struct StaticFileTierMetrics {
+    /// Total number of reads from static files in the storage tier.
+    reads_total: Counter,
+    /// The time it took to read from a static file in the storage tier.
+    read_duration_seconds: Histogram,
+}
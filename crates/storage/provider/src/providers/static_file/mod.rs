@@ -7,6 +7,12 @@ pub use manager::{
 mod jar;
 pub use jar::StaticFileJarProvider;
 
+mod dictionary;
+pub use dictionary::{SegmentDictionaries, DICTIONARIES_DIR};
+
+mod tier;
+pub use tier::StaticFileTier;
+
 mod writer;
 pub use writer::{StaticFileProviderRW, StaticFileProviderRWRefMut};
 
@@ -29,14 +35,16 @@ type LoadedJarRef<'a> =
 pub struct LoadedJar {
     jar: NippyJar<SegmentHeader>,
     mmap_handle: Arc<hanzo_evm_nippy_jar::DataReader>,
+    /// Storage tier the jar is read from, `0` being the static files directory.
+    tier: usize,
 }
 
 impl LoadedJar {
-    fn new(jar: NippyJar<SegmentHeader>) -> ProviderResult<Self> {
+    fn new(jar: NippyJar<SegmentHeader>, tier: usize) -> ProviderResult<Self> {
         match jar.open_data_reader() {
             Ok(data_reader) => {
                 let mmap_handle = Arc::new(data_reader);
-                Ok(Self { jar, mmap_handle })
+                Ok(Self { jar, mmap_handle, tier })
             }
             Err(e) => Err(ProviderError::other(e)),
         }
@@ -51,6 +59,11 @@ impl LoadedJar {
         self.jar.user_header().segment()
     }
 
+    /// Returns the storage tier the jar is read from.
+    const fn tier(&self) -> usize {
+        self.tier
+    }
+
     /// Returns the total size of the data and offsets files (from the in-memory mmap).
     fn size(&self) -> usize {
         self.mmap_handle.size() + self.mmap_handle.offsets_size()
@@ -68,14 +81,16 @@ impl Deref for LoadedJar {
 mod tests {
     use super::*;
     use crate::{
-        providers::static_file::manager::StaticFileProviderBuilder,
-        test_utils::create_test_provider_factory, HeaderProvider, StaticFileProviderFactory,
+        providers::static_file::{manager::StaticFileProviderBuilder, SegmentDictionaries},
+        test_utils::create_test_provider_factory,
+        HeaderProvider, StaticFileProviderFactory,
     };
     use alloy_consensus::{Header, SignableTransaction, Transaction, TxLegacy};
     use alloy_primitives::{Address, BlockHash, Signature, TxNumber, B256, U160, U256};
     use rand::seq::SliceRandom;
     use hanzo_evm_db::{
-        models::{AccountBeforeTx, StorageBeforeTx},
+        models::{AccountBeforeTx, StateSnapshotEntry, StorageBeforeTx},
+        static_file::iter_static_files,
         test_utils::create_test_static_files_dir,
     };
     use hanzo_evm_db_api::{transaction::DbTxMut, CanonicalHeaders, HeaderNumbers, Headers};
@@ -333,7 +348,8 @@ mod tests {
                     match segment {
                         StaticFileSegment::Headers |
                         StaticFileSegment::AccountChangeSets |
-                        StaticFileSegment::StorageChangeSets => {
+                        StaticFileSegment::StorageChangeSets |
+                        StaticFileSegment::StateSnapshots => {
                             panic!("non tx based segment")
                         }
                         StaticFileSegment::Transactions => {
@@ -452,7 +468,8 @@ mod tests {
             match segment {
                 StaticFileSegment::Headers |
                 StaticFileSegment::AccountChangeSets |
-                StaticFileSegment::StorageChangeSets => {
+                StaticFileSegment::StorageChangeSets |
+                StaticFileSegment::StateSnapshots => {
                     panic!("non tx based segment")
                 }
                 StaticFileSegment::Transactions => {
@@ -479,7 +496,8 @@ mod tests {
                 match segment {
                     StaticFileSegment::Headers |
                     StaticFileSegment::AccountChangeSets |
-                    StaticFileSegment::StorageChangeSets => {
+                    StaticFileSegment::StorageChangeSets |
+                    StaticFileSegment::StateSnapshots => {
                         panic!("non tx based segment")
                     }
                     StaticFileSegment::Transactions => assert_eyre(
@@ -689,6 +707,111 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_move_to_tiers() -> eyre::Result<()> {
+        let (static_dir, _) = create_test_static_files_dir();
+        let tiers_dir = tempfile::tempdir()?;
+        let (warm_dir, cold_dir) = (tiers_dir.path().join("warm"), tiers_dir.path().join("cold"));
+
+        let sf_rw: StaticFileProvider<EthPrimitives> =
+            StaticFileProviderBuilder::read_write(&static_dir)
+                .with_blocks_per_file(10)
+                .with_tiers(vec![
+                    StaticFileTier::new(&cold_dir, 20),
+                    StaticFileTier::new(&warm_dir, 10),
+                ])
+                .build()?;
+
+        let mut header_writer = sf_rw.latest_writer(StaticFileSegment::Headers)?;
+        let mut header = Header::default();
+        for num in 0..=35 {
+            header.number = num;
+            header_writer.append_header(&header, &BlockHash::default())?;
+        }
+        header_writer.commit()?;
+        drop(header_writer);
+
+        // Files may already have been moved in the background when new files were opened.
+        sf_rw.move_to_tiers()?;
+
+        let filename = |start, end| {
+            StaticFileSegment::Headers.filename(&SegmentRangeInclusive::new(start, end))
+        };
+        let link = |start, end| fs::read_link(static_dir.join(filename(start, end))).ok();
+
+        // Blocks 0..=9 are 26 blocks old, 10..=19 are 16 blocks old, and the rest are too recent.
+        assert_eq!(link(0, 9), Some(cold_dir.join(filename(0, 9))));
+        assert_eq!(link(10, 19), Some(warm_dir.join(filename(10, 19))));
+        assert_eq!(link(20, 29), None);
+        assert_eq!(link(30, 39), None);
+        assert!(cold_dir.join(filename(0, 9)).with_extension("conf").exists());
+
+        // Moved files are still listed and readable through the static files directory.
+        assert_eq!(
+            iter_static_files(&static_dir)?.get(StaticFileSegment::Headers).map(Vec::len),
+            Some(4)
+        );
+        assert_eq!(sf_rw.headers_range(0..=35)?.len(), 36);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_recompress_static_files() -> eyre::Result<()> {
+        let (static_dir, _) = create_test_static_files_dir();
+        let segment = StaticFileSegment::Transactions;
+
+        let sf_rw: StaticFileProvider<EthPrimitives> =
+            StaticFileProviderBuilder::read_write(&static_dir).with_blocks_per_file(10).build()?;
+
+        // Inputs of at least 32 bytes are compressed with the fixed dictionary of the codecs
+        let transactions = (0..25u64)
+            .map(|nonce| {
+                let tx =
+                    TxLegacy { nonce, input: vec![nonce as u8; 64].into(), ..Default::default() };
+                TransactionSigned::from(tx.into_signed(Signature::test_signature()))
+            })
+            .collect::<Vec<_>>();
+
+        let mut writer = sf_rw.latest_writer(segment)?;
+        for (block, tx) in transactions.iter().enumerate() {
+            writer.increment_block(block as u64)?;
+            writer.append_transaction(block as TxNumber, tx)?;
+        }
+        writer.commit()?;
+        drop(writer);
+
+        let columns = sf_rw.train_zstd_dictionaries(segment, 0..=24, 1024, 1024 * 1024)?;
+        SegmentDictionaries { segment, id: 1, columns }.save(&static_dir)?;
+
+        // Finished static files may already have been recompressed in the background
+        sf_rw.recompress_static_files()?;
+        assert_eq!(sf_rw.recompress_static_files()?, 0);
+
+        let filename = |start, end| segment.filename(&SegmentRangeInclusive::new(start, end));
+        for (start, end) in [(0, 9), (10, 19)] {
+            let path = static_dir.join(filename(start, end));
+            let jar = NippyJar::<SegmentHeader>::load(&path)?;
+            assert_eq!(jar.data_version(), 1);
+            assert!(!path.exists());
+            assert!(!path.with_extension("off").exists());
+
+            // Values are stored without the fixed dictionary compression flag
+            let mut cursor = hanzo_evm_nippy_jar::NippyJarCursor::new(&jar)?;
+            while let Some(row) = cursor.next_row()? {
+                assert_eq!(row[0][0] & 0b1000, 0);
+            }
+        }
+
+        // The latest static file is still being written to
+        let latest = NippyJar::<SegmentHeader>::load(&static_dir.join(filename(20, 29)))?;
+        assert_eq!(latest.data_version(), 0);
+
+        assert_eq!(sf_rw.transactions_by_tx_range(0..25)?, transactions);
+
+        Ok(())
+    }
+
     #[test]
     fn test_account_changeset_static_files() {
         let (static_dir, _) = create_test_static_files_dir();
@@ -1361,6 +1484,62 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_state_snapshots() {
+        let (static_dir, _) = create_test_static_files_dir();
+
+        let sf_rw = StaticFileProvider::<EthPrimitives>::read_write(&static_dir)
+            .expect("Failed to create static file provider");
+
+        let addresses = [B256::with_last_byte(1), B256::with_last_byte(2)];
+        let slot = B256::with_last_byte(3);
+        let account = Account { nonce: 1, balance: U256::from(10), bytecode_hash: None };
+
+        // Only block 2 is a checkpoint, the other blocks have no rows.
+        {
+            let mut writer = sf_rw.latest_writer(StaticFileSegment::StateSnapshots).unwrap();
+            for block_num in 0..4 {
+                let entries = if block_num == 2 {
+                    vec![
+                        StateSnapshotEntry::Account { hashed_address: addresses[0], account },
+                        StateSnapshotEntry::Storage {
+                            hashed_address: addresses[0],
+                            hashed_slot: slot,
+                            value: U256::from(5),
+                        },
+                        StateSnapshotEntry::Account { hashed_address: addresses[1], account },
+                    ]
+                } else {
+                    Vec::new()
+                };
+                writer.append_state_snapshot(entries.into_iter().map(Ok), block_num).unwrap();
+            }
+            writer.commit().unwrap();
+        }
+
+        assert_eq!(sf_rw.state_snapshot_block(0).unwrap(), Some(2));
+        assert_eq!(sf_rw.state_snapshot_block(2).unwrap(), Some(2));
+        assert_eq!(sf_rw.state_snapshot_block(3).unwrap(), None);
+
+        assert_eq!(sf_rw.state_snapshot_account(2, addresses[0]).unwrap(), Some(account));
+        assert_eq!(sf_rw.state_snapshot_account(2, addresses[1]).unwrap(), Some(account));
+        assert_eq!(sf_rw.state_snapshot_account(2, B256::with_last_byte(9)).unwrap(), None);
+
+        assert_eq!(
+            sf_rw.state_snapshot_storage(2, addresses[0], slot).unwrap(),
+            Some(U256::from(5))
+        );
+        assert_eq!(sf_rw.state_snapshot_storage(2, addresses[1], slot).unwrap(), None);
+
+        // Pruning the checkpoint block removes the snapshot.
+        {
+            let mut writer = sf_rw.latest_writer(StaticFileSegment::StateSnapshots).unwrap();
+            writer.prune_state_snapshots(1).unwrap();
+            writer.commit().unwrap();
+        }
+        assert_eq!(sf_rw.state_snapshot_block(0).unwrap(), None);
+    }
+
     #[test]
     fn test_last_block_flushed_on_commit() {
         let (static_dir, _) = create_test_static_files_dir();
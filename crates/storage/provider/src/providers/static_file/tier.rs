@@ -0,0 +1,51 @@
+use std::path::{Path, PathBuf};
+
+/// A storage tier that finished static files are moved to once they are old enough.
+///
+/// Moved files are replaced by symlinks in the static files directory, so they stay readable
+/// through the same paths.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaticFileTier {
+    /// Directory of the tier, usually on a slower and larger volume.
+    pub path: PathBuf,
+    /// Number of blocks between the end of a static file and the highest block of its segment
+    /// before the file is moved to this tier.
+    pub min_age: u64,
+}
+
+impl StaticFileTier {
+    /// Creates a new [`StaticFileTier`].
+    pub fn new(path: impl Into<PathBuf>, min_age: u64) -> Self {
+        Self { path: path.into(), min_age }
+    }
+}
+
+/// Returns the tier a static file should be stored in, `0` being the static files directory.
+///
+/// `tiers` must be sorted by [`StaticFileTier::min_age`].
+pub(crate) fn target_tier(tiers: &[StaticFileTier], age: u64) -> usize {
+    tiers.iter().take_while(|tier| tier.min_age <= age).count()
+}
+
+/// Returns the tier a static file is currently stored in, `0` being the static files directory.
+pub(crate) fn current_tier(tiers: &[StaticFileTier], path: &Path) -> usize {
+    let Ok(target) = std::fs::read_link(path) else { return 0 };
+    tiers.iter().position(|tier| target.starts_with(&tier.path)).map_or(0, |tier| tier + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_tier() {
+        let tiers = [StaticFileTier::new("/warm", 1_000), StaticFileTier::new("/cold", 5_000)];
+
+        assert_eq!(target_tier(&tiers, 0), 0);
+        assert_eq!(target_tier(&tiers, 999), 0);
+        assert_eq!(target_tier(&tiers, 1_000), 1);
+        assert_eq!(target_tier(&tiers, 4_999), 1);
+        assert_eq!(target_tier(&tiers, 5_000), 2);
+        assert_eq!(target_tier(&[], u64::MAX), 0);
+    }
+}
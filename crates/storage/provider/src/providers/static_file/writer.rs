@@ -6,7 +6,7 @@ use alloy_consensus::BlockHeader;
 use alloy_primitives::{BlockHash, BlockNumber, TxNumber, U256};
 use parking_lot::{lock_api::RwLockWriteGuard, RawRwLock, RwLock};
 use reth_codecs::Compact;
-use reth_db::models::{AccountBeforeTx, StorageBeforeTx};
+use reth_db::models::{AccountBeforeTx, StateSnapshotEntry, StorageBeforeTx};
 use reth_db_api::models::CompactU256;
 use reth_nippy_jar::{NippyJar, NippyJarError, NippyJarWriter};
 use reth_node_types::NodePrimitives;
@@ -64,6 +64,11 @@ enum PruneStrategy {
         /// The target block number to prune to.
         last_block: BlockNumber,
     },
+    /// Prune state snapshots to a target block number.
+    StateSnapshots {
+        /// The target block number to prune to.
+        last_block: BlockNumber,
+    },
 }
 
 /// Static file writers for every known [`StaticFileSegment`].
@@ -78,6 +83,7 @@ pub(crate) struct StaticFileWriters<N> {
     transaction_senders: RwLock<Option<StaticFileProviderRW<N>>>,
     account_change_sets: RwLock<Option<StaticFileProviderRW<N>>>,
     storage_change_sets: RwLock<Option<StaticFileProviderRW<N>>>,
+    state_snapshots: RwLock<Option<StaticFileProviderRW<N>>>,
 }
 
 impl<N> Default for StaticFileWriters<N> {
@@ -89,6 +95,7 @@ impl<N> Default for StaticFileWriters<N> {
             transaction_senders: Default::default(),
             account_change_sets: Default::default(),
             storage_change_sets: Default::default(),
+            state_snapshots: Default::default(),
         }
     }
 }
@@ -99,20 +106,29 @@ impl<N: NodePrimitives> StaticFileWriters<N> {
         segment: StaticFileSegment,
         create_fn: impl FnOnce() -> ProviderResult<StaticFileProviderRW<N>>,
     ) -> ProviderResult<StaticFileProviderRWRefMut<'_, N>> {
-        let mut write_guard = match segment {
+        let mut write_guard = self.lock(segment);
+
+        if write_guard.is_none() {
+            *write_guard = Some(create_fn()?);
+        }
+
+        Ok(StaticFileProviderRWRefMut(write_guard))
+    }
+
+    /// Locks the writer of `segment`, which may not have been created yet.
+    pub(crate) fn lock(
+        &self,
+        segment: StaticFileSegment,
+    ) -> RwLockWriteGuard<'_, RawRwLock, Option<StaticFileProviderRW<N>>> {
+        match segment {
             StaticFileSegment::Headers => self.headers.write(),
             StaticFileSegment::Transactions => self.transactions.write(),
             StaticFileSegment::Receipts => self.receipts.write(),
             StaticFileSegment::TransactionSenders => self.transaction_senders.write(),
             StaticFileSegment::AccountChangeSets => self.account_change_sets.write(),
             StaticFileSegment::StorageChangeSets => self.storage_change_sets.write(),
-        };
-
-        if write_guard.is_none() {
-            *write_guard = Some(create_fn()?);
+            StaticFileSegment::StateSnapshots => self.state_snapshots.write(),
         }
-
-        Ok(StaticFileProviderRWRefMut(write_guard))
     }
 
     #[instrument(
@@ -138,6 +154,12 @@ impl<N: NodePrimitives> StaticFileWriters<N> {
             }
         }
 
+        if let Some(mut writer) = self.state_snapshots_writer() &&
+            let Some(writer) = writer.as_mut()
+        {
+            writer.commit()?;
+        }
+
         debug!(target: "providers::static_file", "Committed all static file segments");
         Ok(())
     }
@@ -158,7 +180,21 @@ impl<N: NodePrimitives> StaticFileWriters<N> {
                 return true
             }
         }
-        false
+        self.state_snapshots_writer().is_some_and(|writer| {
+            writer.as_ref().is_some_and(|writer| writer.will_prune_on_commit())
+        })
+    }
+
+    /// Returns the [`StaticFileSegment::StateSnapshots`] writer, unless it's in use.
+    ///
+    /// State snapshots are written and committed in the background, see
+    /// [`crate::ProviderFactory::write_state_snapshots`], which must not block the commits of
+    /// other segments. Read-write providers only unwind the segment while holding the state
+    /// snapshots lock, so a writer in use never holds their changes.
+    fn state_snapshots_writer(
+        &self,
+    ) -> Option<RwLockWriteGuard<'_, RawRwLock, Option<StaticFileProviderRW<N>>>> {
+        self.state_snapshots.try_write()
     }
 
     /// Finalizes all writers by committing their configuration to disk and updating indices.
@@ -188,6 +224,12 @@ impl<N: NodePrimitives> StaticFileWriters<N> {
             }
         }
 
+        if let Some(mut writer) = self.state_snapshots_writer() &&
+            let Some(writer) = writer.as_mut()
+        {
+            writer.finalize()?;
+        }
+
         debug!(target: "providers::static_file", "Finalized all static file segments into disk");
         Ok(())
     }
@@ -296,8 +338,8 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
             None,
         ) {
             Ok(provider) => (
-                NippyJar::load(provider.data_path()).map_err(ProviderError::other)?,
-                provider.data_path().into(),
+                NippyJar::load(provider.path()).map_err(ProviderError::other)?,
+                provider.path().into(),
             ),
             Err(ProviderError::MissingStaticFileBlock(_, _)) => {
                 let path = static_file_provider.directory().join(segment.filename(&block_range));
@@ -613,6 +655,9 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
                 PruneStrategy::StorageChangeSets { last_block } => {
                     self.prune_storage_changeset_data(last_block)?
                 }
+                PruneStrategy::StateSnapshots { last_block } => {
+                    self.prune_state_snapshot_data(last_block)?
+                }
             }
         }
 
@@ -784,6 +829,11 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
                     None,
                     segment,
                 );
+
+                // Older static files may now be old enough for a storage tier, and the finished
+                // one can be recompressed with the trained dictionaries
+                self.reader().spawn_tier_migration();
+                self.reader().spawn_recompression();
             }
         }
 
@@ -1399,6 +1449,42 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
         Ok(())
     }
 
+    /// Appends a block state snapshot to the static file.
+    ///
+    /// Entries must be sorted by [`StateSnapshotEntry::sort_key`], blocks that are not snapshot
+    /// checkpoints are appended without entries.
+    ///
+    /// It **CALLS** `increment_block()`.
+    ///
+    /// Returns the number of appended entries.
+    pub fn append_state_snapshot<I>(&mut self, entries: I, block_number: u64) -> ProviderResult<u64>
+    where
+        I: IntoIterator<Item = ProviderResult<StateSnapshotEntry>>,
+    {
+        debug_assert!(self.writer.user_header().segment() == StaticFileSegment::StateSnapshots);
+        let start = Instant::now();
+
+        self.increment_block(block_number)?;
+        self.ensure_no_queued_prune()?;
+
+        let mut count: u64 = 0;
+        for entry in entries {
+            self.append_change(&entry?)?;
+            count += 1;
+        }
+
+        if let Some(metrics) = &self.metrics {
+            metrics.record_segment_operations(
+                StaticFileSegment::StateSnapshots,
+                StaticFileProviderOperation::Append,
+                count,
+                Some(start.elapsed()),
+            );
+        }
+
+        Ok(count)
+    }
+
     /// Adds an instruction to prune `to_delete` transactions during commit.
     ///
     /// Note: `last_block` refers to the block the unwinds ends at.
@@ -1456,6 +1542,12 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
         self.queue_prune(PruneStrategy::StorageChangeSets { last_block })
     }
 
+    /// Adds an instruction to prune state snapshots until the given block.
+    pub fn prune_state_snapshots(&mut self, last_block: u64) -> ProviderResult<()> {
+        debug_assert_eq!(self.writer.user_header().segment(), StaticFileSegment::StateSnapshots);
+        self.queue_prune(PruneStrategy::StateSnapshots { last_block })
+    }
+
     /// Adds an instruction to prune elements during commit using the specified strategy.
     fn queue_prune(&mut self, strategy: PruneStrategy) -> ProviderResult<()> {
         self.ensure_no_queued_prune()?;
@@ -1534,6 +1626,25 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
         Ok(())
     }
 
+    /// Prunes the last state snapshot blocks from the data file.
+    fn prune_state_snapshot_data(&mut self, last_block: BlockNumber) -> ProviderResult<()> {
+        let start = Instant::now();
+
+        debug_assert!(self.writer.user_header().segment() == StaticFileSegment::StateSnapshots);
+
+        self.truncate_changesets(last_block)?;
+
+        if let Some(metrics) = &self.metrics {
+            metrics.record_segment_operation(
+                StaticFileSegment::StateSnapshots,
+                StaticFileProviderOperation::Prune,
+                Some(start.elapsed()),
+            );
+        }
+
+        Ok(())
+    }
+
     /// Prunes the last `to_delete` receipts from the data file.
     fn prune_receipt_data(
         &mut self,
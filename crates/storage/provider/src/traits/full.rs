@@ -10,7 +10,9 @@ use hanzo_evm_chain_state::{
     CanonStateSubscriptions, ForkChoiceSubscriptions, PersistedBlockSubscriptions,
 };
 use reth_node_types::{BlockTy, HeaderTy, NodeTypesWithDB, ReceiptTy, TxTy};
-use reth_storage_api::{NodePrimitivesProvider, StorageChangeSetReader, StorageSettingsCache};
+use reth_storage_api::{
+    NodePrimitivesProvider, StorageChangeSetReader, StorageSettingsCache, StorageSnapshotProvider,
+};
 use std::fmt::Debug;
 
 /// Helper trait to unify all provider traits for simplicity.
@@ -42,6 +44,7 @@ pub trait FullProvider<N: NodeTypesWithDB>:
     + ForkChoiceSubscriptions<Header = HeaderTy<N>>
     + PersistedBlockSubscriptions
     + StageCheckpointReader
+    + StorageSnapshotProvider
     + Clone
     + Debug
     + Unpin
@@ -77,6 +80,7 @@ impl<T, N: NodeTypesWithDB> FullProvider<N> for T where
         + ForkChoiceSubscriptions<Header = HeaderTy<N>>
         + PersistedBlockSubscriptions
         + StageCheckpointReader
+        + StorageSnapshotProvider
         + Clone
         + Debug
         + Unpin
@@ -11,5 +11,10 @@ pub use static_file_provider::StaticFileProviderFactory;
 mod rocksdb_provider;
 pub use rocksdb_provider::RocksDBProviderFactory;
 
+mod state_cursor;
+pub use state_cursor::{
+    DupStateCursorMutTy, DupStateCursorTy, StateCursorMutTy, StateCursorProvider, StateCursorTy,
+};
+
 mod full;
 pub use full::FullProvider;
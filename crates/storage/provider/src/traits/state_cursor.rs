@@ -0,0 +1,71 @@
+use crate::{either_cursor::RocksDBStateTable, EitherCursor};
+use hanzo_evm_db_api::{
+    cursor::DbCursorRO,
+    table::DupSort,
+    transaction::{CursorMutTy, CursorTy, DbTxMut, DupCursorMutTy, DupCursorTy},
+    DatabaseError,
+};
+use hanzo_evm_storage_api::DBProvider;
+
+/// Type alias for a read-only [`EitherCursor`] over a state table.
+pub type StateCursorTy<P, T> = EitherCursor<CursorTy<<P as DBProvider>::Tx, T>, T>;
+
+/// Type alias for a read-only dup [`EitherCursor`] over a state table.
+pub type DupStateCursorTy<P, T> = EitherCursor<DupCursorTy<<P as DBProvider>::Tx, T>, T>;
+
+/// Type alias for a read-write [`EitherCursor`] over a state table.
+pub type StateCursorMutTy<P, T> = EitherCursor<CursorMutTy<<P as DBProvider>::Tx, T>, T>;
+
+/// Type alias for a read-write dup [`EitherCursor`] over a state table.
+pub type DupStateCursorMutTy<P, T> = EitherCursor<DupCursorMutTy<<P as DBProvider>::Tx, T>, T>;
+
+/// Provides cursors over plain state, hashed state and trie tables.
+///
+/// These tables are stored either in the database or in `RocksDB`, depending on
+/// [`StorageSettings::state_in_rocksdb`](hanzo_evm_db_api::models::StorageSettings::state_in_rocksdb).
+/// Code touching them should go through this trait instead of opening cursors on the
+/// transaction directly.
+pub trait StateCursorProvider: DBProvider {
+    /// Returns a read-only cursor over state table `T`.
+    fn state_cursor_read<T: RocksDBStateTable>(
+        &self,
+    ) -> Result<StateCursorTy<Self, T>, DatabaseError>;
+
+    /// Returns a read-only dup cursor over state table `T`.
+    fn state_cursor_dup_read<T: RocksDBStateTable + DupSort>(
+        &self,
+    ) -> Result<DupStateCursorTy<Self, T>, DatabaseError>;
+
+    /// Returns a read-write cursor over state table `T`.
+    fn state_cursor_write<T: RocksDBStateTable>(
+        &self,
+    ) -> Result<StateCursorMutTy<Self, T>, DatabaseError>
+    where
+        Self::Tx: DbTxMut;
+
+    /// Returns a read-write dup cursor over state table `T`.
+    fn state_cursor_dup_write<T: RocksDBStateTable + DupSort>(
+        &self,
+    ) -> Result<DupStateCursorMutTy<Self, T>, DatabaseError>
+    where
+        Self::Tx: DbTxMut;
+
+    /// Removes all entries from state table `T`.
+    fn clear_state_table<T: RocksDBStateTable>(&self) -> Result<(), DatabaseError>
+    where
+        Self::Tx: DbTxMut;
+
+    /// Returns the number of entries in state table `T`.
+    ///
+    /// Tables stored in `RocksDB` keep no entry count, so their entries are counted by walking
+    /// the table.
+    fn state_entries<T: RocksDBStateTable>(&self) -> Result<usize, DatabaseError>;
+
+    /// Returns the value stored under `key` in state table `T`.
+    fn get_state<T: RocksDBStateTable>(
+        &self,
+        key: T::Key,
+    ) -> Result<Option<T::Value>, DatabaseError> {
+        Ok(self.state_cursor_read::<T>()?.seek_exact(key)?.map(|(_, value)| value))
+    }
+}
@@ -96,6 +96,17 @@ pub use state_writer::*;
 mod header_sync_gap;
 pub use header_sync_gap::HeaderSyncGapProvider;
 
+mod trace_index;
+pub use trace_index::*;
+
+mod log_index;
+pub use log_index::*;
+
+#[cfg(all(feature = "db-api", feature = "std"))]
+mod snapshot;
+#[cfg(all(feature = "db-api", feature = "std"))]
+pub use snapshot::*;
+
 #[cfg(feature = "db-api")]
 pub mod metadata;
 #[cfg(feature = "db-api")]
@@ -0,0 +1,40 @@
+use alloc::vec::Vec;
+use alloy_primitives::{Address, BlockNumber, B256};
+use core::{fmt::Debug, ops::RangeInclusive};
+use hanzo_evm_storage_errors::provider::ProviderResult;
+
+/// Client trait for an index of the blocks in which an address emitted a log or a topic appeared
+/// in a log.
+///
+/// Topics are indexed regardless of their position in the log, so the returned blocks are a
+/// superset of the blocks that match a positional topic filter.
+///
+/// The index only covers the blocks in [`LogIndexReader::log_index_range`], blocks outside of it
+/// have to be checked against their bloom filter.
+#[auto_impl::auto_impl(&, Box, Arc)]
+pub trait LogIndexReader: Send + Sync + Debug {
+    /// Returns the range of blocks that is covered by the index, or `None` if the index is empty.
+    ///
+    /// The index starts above genesis if the receipts of the node are pruned.
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>>;
+
+    /// Returns the blocks in the given range that contain a log emitted by the address, in
+    /// ascending order.
+    ///
+    /// Blocks outside of [`LogIndexReader::log_index_range`] are never returned.
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+
+    /// Returns the blocks in the given range that contain a log with the topic at any position,
+    /// in ascending order.
+    ///
+    /// Blocks outside of [`LogIndexReader::log_index_range`] are never returned.
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+}
@@ -8,6 +8,8 @@ use hanzo_evm_storage_errors::provider::{ProviderError, ProviderResult};
 pub mod keys {
     /// Storage configuration settings for this node.
     pub const STORAGE_SETTINGS: &str = "storage_settings";
+    /// Progress of an in-flight v1 to v2 storage layout migration.
+    pub const STORAGE_MIGRATION: &str = "storage_migration";
 }
 
 /// Client trait for reading node metadata from the database.
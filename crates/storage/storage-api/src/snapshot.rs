@@ -0,0 +1,13 @@
+//! Trait for taking snapshots of the node's storage.
+
+use hanzo_evm_db_api::models::StorageSnapshotManifest;
+use hanzo_evm_storage_errors::provider::ProviderResult;
+use std::path::Path;
+
+/// Client trait for copying the node's storage while it keeps running.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait StorageSnapshotProvider: Send + Sync {
+    /// Copies the database, static files and `RocksDB` into `dest`, which must not exist yet,
+    /// and writes a [`StorageSnapshotManifest`] describing the copy next to them.
+    fn create_storage_snapshot(&self, dest: &Path) -> ProviderResult<StorageSnapshotManifest>;
+}
@@ -0,0 +1,40 @@
+use alloc::vec::Vec;
+use alloy_primitives::{Address, BlockNumber};
+use core::{fmt::Debug, ops::RangeInclusive};
+use hanzo_evm_storage_errors::provider::ProviderResult;
+
+/// The role of an address in a call frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TracedAddressRole {
+    /// The address was the caller of a call, the creator of a contract or a self-destructed
+    /// contract.
+    From,
+    /// The address was the callee of a call, a created contract or the refund target of a
+    /// self-destruct.
+    To,
+}
+
+/// Client trait for an index of the blocks in which an address appeared in any call frame,
+/// including internal calls.
+///
+/// The index only covers the blocks in [`TraceIndexReader::trace_index_range`], blocks outside of
+/// it have to be traced in full.
+#[auto_impl::auto_impl(&, Box, Arc)]
+pub trait TraceIndexReader: Send + Sync + Debug {
+    /// Returns the range of blocks that is covered by the index, or `None` if the index is empty.
+    ///
+    /// The index starts at the first block it was able to trace, which is above genesis if the
+    /// history of the node is pruned.
+    fn trace_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>>;
+
+    /// Returns the blocks in the given range in which the address appeared with the given role,
+    /// in ascending order.
+    ///
+    /// Blocks outside of [`TraceIndexReader::trace_index_range`] are never returned.
+    fn traced_address_blocks(
+        &self,
+        address: Address,
+        role: TracedAddressRole,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+}
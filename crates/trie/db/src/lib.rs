@@ -15,7 +15,7 @@ mod witness;
 pub use hashed_cursor::{
     DatabaseHashedAccountCursor, DatabaseHashedCursorFactory, DatabaseHashedStorageCursor,
 };
-pub use prefix_set::load_prefix_sets_with_provider;
+pub use prefix_set::{load_prefix_sets_with_cursor, load_prefix_sets_with_provider};
 pub use proof::{DatabaseProof, DatabaseStorageProof};
 pub use state::{from_reverts_auto, DatabaseHashedPostState, DatabaseStateRoot};
 pub use storage::{hashed_storage_from_reverts_with_provider, DatabaseStorageRoot};
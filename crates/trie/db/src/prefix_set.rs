@@ -31,8 +31,22 @@ pub fn load_prefix_sets_with_provider<Provider>(
 where
     Provider: ChangeSetReader + StorageChangeSetReader + DBProvider,
 {
-    let tx = provider.tx_ref();
+    let mut hashed_accounts_cursor = provider.tx_ref().cursor_read::<tables::HashedAccounts>()?;
+    load_prefix_sets_with_cursor(provider, &mut hashed_accounts_cursor, range)
+}
 
+/// Load prefix sets like [`load_prefix_sets_with_provider`], but look up destroyed accounts with
+/// the given [`tables::HashedAccounts`] cursor instead of a cursor on the provider transaction.
+///
+/// Used when hashed state is not stored in the database.
+pub fn load_prefix_sets_with_cursor<Provider>(
+    provider: &Provider,
+    hashed_accounts_cursor: &mut impl DbCursorRO<tables::HashedAccounts>,
+    range: RangeInclusive<BlockNumber>,
+) -> Result<TriePrefixSets, ProviderError>
+where
+    Provider: ChangeSetReader + StorageChangeSetReader,
+{
     // Initialize prefix sets.
     let mut account_prefix_set = PrefixSetMut::default();
     let mut storage_prefix_sets = HashMap::<B256, PrefixSetMut>::default();
@@ -41,14 +55,11 @@ where
     // Get account changesets using the provider (handles static files + database)
     let account_changesets = provider.account_changesets_range(*range.start()..*range.end() + 1)?;
 
-    // We still need direct access to HashedAccounts table
-    let mut account_hashed_state_cursor = tx.cursor_read::<tables::HashedAccounts>()?;
-
     for (_, AccountBeforeTx { address, .. }) in account_changesets {
         let hashed_address = keccak256(address);
         account_prefix_set.insert(Nibbles::unpack(hashed_address));
 
-        if account_hashed_state_cursor.seek_exact(hashed_address)?.is_none() {
+        if hashed_accounts_cursor.seek_exact(hashed_address)?.is_none() {
             destroyed_accounts.insert(hashed_address);
         }
     }